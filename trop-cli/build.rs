@@ -44,6 +44,21 @@ fn build_cli() -> Command {
                 .global(true)
                 .env("TROP_DATA_DIR"),
         )
+        .arg(
+            Arg::new("db-name")
+                .long("db-name")
+                .help("Database file name inside the data directory (default: trop.db)")
+                .value_name("NAME")
+                .global(true)
+                .env("TROP_DB_NAME"),
+        )
+        .arg(
+            Arg::new("log-file")
+                .long("log-file")
+                .help("Append diagnostic log lines to this file instead of stderr")
+                .value_name("PATH")
+                .global(true),
+        )
         .arg(
             Arg::new("busy-timeout")
                 .long("busy-timeout")
@@ -60,6 +75,23 @@ fn build_cli() -> Command {
                 .action(clap::ArgAction::SetTrue)
                 .env("TROP_DISABLE_AUTOINIT"),
         )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .help("Merge the named configuration profile's overrides")
+                .value_name("NAME")
+                .global(true)
+                .env("TROP_PROFILE"),
+        )
+        .arg(
+            Arg::new("error-format")
+                .long("error-format")
+                .help("Report errors as human text or a JSON object on stderr")
+                .value_name("FORMAT")
+                .value_parser(["human", "json"])
+                .default_value("human")
+                .global(true),
+        )
         .subcommands(vec![
             Command::new("reserve")
                 .about("Reserve a port for a directory")
@@ -99,15 +131,21 @@ fn build_cli() -> Command {
             Command::new("port-info")
                 .about("Display information about a specific port")
                 .long_about("Show detailed information about a port reservation"),
+            Command::new("first-free")
+                .about("Show the lowest free port without reserving it")
+                .long_about("Print the lowest port in the configured range that is neither reserved, excluded, nor (with --check-occupancy) occupied"),
+            Command::new("show-config")
+                .about("Show the effective merged configuration")
+                .long_about("Print the configuration after merging defaults, config files, the selected profile, and environment overrides, optionally annotated with each field's source"),
             Command::new("show-data-dir")
                 .about("Show the resolved data directory path")
                 .long_about("Display the path to the trop data directory"),
             Command::new("show-path")
                 .about("Show the resolved path for a reservation")
-                .long_about("Display the path associated with a reservation"),
+                .long_about("Display the path associated with a reservation, optionally relative to a base directory with --relative-to"),
             Command::new("scan")
                 .about("Scan port range for occupied ports")
-                .long_about("Scan a range of ports to identify which are currently in use"),
+                .long_about("Scan a range of ports to identify which are currently in use, optionally re-scanning periodically with --watch"),
             Command::new("validate")
                 .about("Validate a configuration file")
                 .long_about("Check a trop configuration file for errors"),
@@ -123,9 +161,36 @@ fn build_cli() -> Command {
             Command::new("list-projects")
                 .about("List all unique project identifiers")
                 .long_about("Display all unique project names in the database"),
+            Command::new("doctor")
+                .about("Diagnose configuration and environment issues")
+                .long_about("Check the data directory, config discovery, TROP_* variables, and database integrity"),
+            Command::new("health")
+                .about("Report overall health for monitoring")
+                .long_about("Check the data directory, database, schema version, and integrity; exit nonzero unless healthy"),
+            Command::new("stats")
+                .about("Summarize reservation database usage")
+                .long_about("Show reservation counts, per-project totals, and port range utilization"),
             Command::new("migrate")
                 .about("Migrate reservations between paths")
                 .long_about("Move port reservations from one directory to another"),
+            Command::new("move")
+                .about("Move a reservation to a new path, keeping its port")
+                .long_about("Transfer a single reservation to a new directory without reallocating its port"),
+            Command::new("backup")
+                .about("Back up the reservation database to a file")
+                .long_about("Copy the reservation database to a file using SQLite's online backup API"),
+            Command::new("restore")
+                .about("Restore the reservation database from a backup file")
+                .long_about("Replace the reservation database with a verified backup (an existing database needs --force)"),
+            Command::new("export")
+                .about("Export all reservations as a versioned JSON document")
+                .long_about("Serialize every reservation to JSON that can be imported on another machine"),
+            Command::new("import")
+                .about("Import reservations from a document written by export")
+                .long_about("Create reservations from an export document, skipping conflicts unless --force is given"),
+            Command::new("vacuum")
+                .about("Reclaim unused space in the reservation database")
+                .long_about("Run SQLite VACUUM (and optionally a WAL checkpoint) to shrink the database file"),
             Command::new("completions")
                 .about("Generate shell completion scripts")
                 .long_about("Generate shell completion scripts for bash, zsh, fish, or PowerShell"),
@@ -5,11 +5,15 @@
 
 use crate::commands::{
     AssertDataDirCommand, AssertPortCommand, AssertReservationCommand, AutocleanCommand,
-    AutoreserveCommand, CompactExclusionsCommand, CompletionsCommand, ExcludeCommand,
-    ExpireCommand, InitCommand, ListCommand, ListProjectsCommand, MigrateCommand, PortInfoCommand,
-    PruneCommand, ReleaseCommand, ReserveCommand, ReserveGroupCommand, ScanCommand,
-    ShowDataDirCommand, ShowPathCommand, ValidateCommand,
+    AutoreserveCommand, BackupCommand, CompactExclusionsCommand, CompletionsCommand, DiffCommand,
+    DoctorCommand, ExcludeCommand, ExpireCommand, ExportCommand, FirstFreeCommand, HealthCommand,
+    ImportCommand, InitCommand, ListCommand, ListExclusionsCommand, ListProjectsCommand,
+    MigrateCommand, MoveCommand, PinCommand, PortInfoCommand, PruneCommand, RehomeCommand,
+    ReleaseCommand, RenewCommand, ReserveCommand, ReserveGroupCommand, RestoreCommand, ScanCommand,
+    ShowConfigCommand, ShowDataDirCommand, ShowPathCommand, StatsCommand, UnpinCommand,
+    VacuumCommand, ValidateCommand,
 };
+use crate::utils::{ColorChoice, ErrorFormat};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -30,6 +34,15 @@ pub struct Cli {
     #[arg(long, value_name = "PATH", global = true, env = "TROP_DATA_DIR")]
     pub data_dir: Option<PathBuf>,
 
+    /// Database file name inside the data directory (default: trop.db)
+    #[arg(long, value_name = "NAME", global = true, env = "TROP_DB_NAME")]
+    pub db_name: Option<String>,
+
+    /// Append diagnostic log lines to this file instead of stderr
+    /// (also settable via TROP_LOG_FILE)
+    #[arg(long, value_name = "PATH", global = true)]
+    pub log_file: Option<PathBuf>,
+
     /// Override the default busy timeout (in seconds)
     #[arg(long, value_name = "SECONDS", global = true, env = "TROP_BUSY_TIMEOUT")]
     pub busy_timeout: Option<u32>,
@@ -38,11 +51,37 @@ pub struct Cli {
     #[arg(long, global = true, env = "TROP_DISABLE_AUTOINIT")]
     pub disable_autoinit: bool,
 
+    /// Merge the named configuration profile's overrides
+    #[arg(long, value_name = "NAME", global = true, env = "TROP_PROFILE")]
+    pub profile: Option<String>,
+
+    /// When to color output (auto honors NO_COLOR, CLICOLOR_FORCE, and TTY detection)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "WHEN",
+        global = true,
+        default_value = "auto"
+    )]
+    pub color: ColorChoice,
+
+    /// Report errors as human text or a JSON object on stderr
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        global = true,
+        default_value = "human"
+    )]
+    pub error_format: ErrorFormat,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
 /// Available CLI commands.
+// Parsed once per run, so the size of the largest variant doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum Command {
     /// Reserve a port for a directory
@@ -51,6 +90,12 @@ pub enum Command {
     /// Release a port reservation
     Release(ReleaseCommand),
 
+    /// Pin a reservation so its port is never moved or released
+    Pin(PinCommand),
+
+    /// Unpin a reservation
+    Unpin(UnpinCommand),
+
     /// List active reservations
     List(ListCommand),
 
@@ -82,6 +127,12 @@ pub enum Command {
     #[command(name = "port-info")]
     PortInfo(PortInfoCommand),
 
+    /// Show the lowest free port in the configured range without reserving it
+    FirstFree(FirstFreeCommand),
+
+    /// Show the effective merged configuration
+    ShowConfig(ShowConfigCommand),
+
     /// Show the resolved data directory path
     ShowDataDir(ShowDataDirCommand),
 
@@ -100,15 +151,54 @@ pub enum Command {
     /// Compact exclusion list to minimal representation
     CompactExclusions(CompactExclusionsCommand),
 
+    /// List effective port exclusions with reasons and sources
+    ListExclusions(ListExclusionsCommand),
+
     /// Initialize trop data directory and database
     Init(InitCommand),
 
     /// List all unique project identifiers
     ListProjects(ListProjectsCommand),
 
+    /// Summarize reservation database usage
+    Stats(StatsCommand),
+
+    /// Diagnose configuration and environment issues
+    Doctor(DoctorCommand),
+
+    /// Report overall health for monitoring
+    Health(HealthCommand),
+
     /// Migrate reservations between paths
     Migrate(MigrateCommand),
 
+    /// Move reservations outside the configured port range onto in-range ports
+    Rehome(RehomeCommand),
+
+    /// Compare a reservation manifest against the current database
+    Diff(DiffCommand),
+
+    /// Mark a reservation as freshly used without reallocating it
+    Renew(RenewCommand),
+
+    /// Move a reservation to a new path, keeping its port
+    Move(MoveCommand),
+
+    /// Back up the reservation database to a file
+    Backup(BackupCommand),
+
+    /// Restore the reservation database from a backup file
+    Restore(RestoreCommand),
+
+    /// Export all reservations as a versioned JSON document
+    Export(ExportCommand),
+
+    /// Import reservations from a document written by `export`
+    Import(ImportCommand),
+
+    /// Reclaim unused space in the reservation database
+    Vacuum(VacuumCommand),
+
     /// Generate shell completion scripts
     Completions(CompletionsCommand),
 }
@@ -1,7 +1,7 @@
 //! Command to assert that the data directory exists and is valid.
 
 use crate::error::CliError;
-use crate::utils::{resolve_data_dir, GlobalOptions};
+use crate::utils::{resolve_data_dir, resolve_database_filename, GlobalOptions};
 use clap::Args;
 use std::path::{Path, PathBuf};
 use trop::{Database, DatabaseConfig};
@@ -35,7 +35,7 @@ impl AssertDataDirCommand {
 
         // 3. If validating, check database integrity
         let valid = if exists && self.validate {
-            match validate_database(&data_dir) {
+            match validate_database(&data_dir.join(resolve_database_filename(global)?)) {
                 Ok(()) => true,
                 Err(_) => false,
             }
@@ -68,8 +68,8 @@ impl AssertDataDirCommand {
     }
 }
 
-fn validate_database(data_dir: &Path) -> Result<(), CliError> {
-    let db_path = data_dir.join("trop.db");
+/// Open the database at `db_path` and run an integrity check on it.
+pub(crate) fn validate_database(db_path: &Path) -> Result<(), CliError> {
     if !db_path.exists() {
         return Err(CliError::InvalidArguments("Database file not found".into()));
     }
@@ -22,6 +22,10 @@ pub struct AssertReservationCommand {
     /// Invert the assertion (fail if reservation exists)
     #[arg(long)]
     pub not: bool,
+
+    /// Also require the reservation to be for this port
+    #[arg(long, value_name = "PORT", conflicts_with = "not")]
+    pub port: Option<u16>,
 }
 
 impl AssertReservationCommand {
@@ -35,8 +39,9 @@ impl AssertReservationCommand {
         let db = open_database(global, &config)?;
 
         // 3. Build reservation key and query
-        let key =
-            ReservationKey::new(normalized, self.tag).map_err(|e| CliError::Library(e.into()))?;
+        let tag_case = config.tag_case.unwrap_or_default();
+        let tag = self.tag.map(|tag| tag_case.normalize(tag));
+        let key = ReservationKey::new(normalized, tag).map_err(|e| CliError::Library(e.into()))?;
 
         let reservation =
             Database::get_reservation(db.connection(), &key).map_err(CliError::from)?;
@@ -45,6 +50,15 @@ impl AssertReservationCommand {
         let exists = reservation.is_some();
         let success = if self.not { !exists } else { exists };
 
+        if let (Some(expected), Some(res)) = (self.port, &reservation) {
+            let actual = res.port().value();
+            if actual != expected {
+                return Err(CliError::SemanticFailure(format!(
+                    "Assertion failed: reservation for {key} is on port {actual}, expected {expected}"
+                )));
+            }
+        }
+
         // 5. Output port if found (unless --quiet)
         if !self.not && exists && !global.quiet {
             if let Some(res) = reservation {
@@ -4,10 +4,11 @@
 //! prune and expire operations.
 
 use crate::error::CliError;
-use crate::utils::{load_configuration, open_database, GlobalOptions};
-use clap::Args;
+use crate::utils::{format_timestamp, load_configuration, open_database, GlobalOptions};
+use clap::{Args, ValueEnum};
+use std::time::SystemTime;
 use trop::config::CleanupConfig;
-use trop::operations::CleanupOperations;
+use trop::operations::{AutocleanResult, CleanupOperations, PlanAction};
 
 /// Combined cleanup (prune + expire).
 #[derive(Args)]
@@ -16,9 +17,27 @@ pub struct AutocleanCommand {
     #[arg(long, value_name = "DAYS")]
     pub days: Option<u32>,
 
+    /// Also expire reservations with a priority above `cleanup.protect_priority`
+    #[arg(long)]
+    pub force: bool,
+
     /// Perform a dry run
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: AutocleanOutputFormat,
+}
+
+/// Output format for the autoclean command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum AutocleanOutputFormat {
+    /// Human-readable summary on stderr
+    Text,
+    /// Counts, removed keys by category, and the full plan of removals as
+    /// a JSON object on stdout
+    Json,
 }
 
 impl AutocleanCommand {
@@ -28,17 +47,13 @@ impl AutocleanCommand {
         let config = load_configuration(global)?;
 
         // Build cleanup config with overrides
-        let cleanup_config = if let Some(days) = self.days {
-            CleanupConfig {
-                expire_after_days: Some(days),
-            }
-        } else if let Some(ref cleanup) = config.cleanup {
-            cleanup.clone()
-        } else {
-            CleanupConfig {
-                expire_after_days: None,
-            }
-        };
+        let mut cleanup_config = config.cleanup.clone().unwrap_or_default();
+        if let Some(days) = self.days {
+            cleanup_config.expire_after_days = Some(days);
+        }
+        if self.force {
+            cleanup_config.protect_priority = None;
+        }
 
         if self.dry_run && !global.quiet {
             eprintln!("[DRY RUN] Performing combined cleanup...");
@@ -52,7 +67,12 @@ impl AutocleanCommand {
             .map_err(CliError::from)?;
 
         // Format output
-        if global.quiet {
+        if let AutocleanOutputFormat::Json = self.format {
+            let output = autoclean_json(&result, &cleanup_config, self.dry_run)
+                .and_then(|json| serde_json::to_string_pretty(&json))
+                .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+            println!("{output}");
+        } else if global.quiet {
             if result.total_removed > 0 {
                 println!("{}", result.total_removed);
             }
@@ -62,36 +82,46 @@ impl AutocleanCommand {
             } else {
                 "Removed"
             };
+            let logger = &global.logger;
 
-            eprintln!("{} {} total reservation(s):", prefix, result.total_removed);
-            eprintln!("  Pruned: {} (non-existent paths)", result.pruned_count);
+            logger.info(&format!(
+                "{} {} total reservation(s):",
+                prefix, result.total_removed
+            ));
+            logger.info(&format!(
+                "  Pruned: {} (non-existent paths)",
+                result.pruned_count
+            ));
 
             if cleanup_config.expire_after_days.is_some() {
-                eprintln!("  Expired: {} (old reservations)", result.expired_count);
+                logger.info(&format!(
+                    "  Expired: {} (old reservations)",
+                    result.expired_count
+                ));
             }
 
             if !result.pruned_reservations.is_empty() {
-                eprintln!("\nPruned reservations:");
+                logger.info("Pruned reservations:");
                 for res in &result.pruned_reservations {
-                    eprintln!(
+                    logger.info(&format!(
                         "  - Port {}: {}",
                         res.port().value(),
                         res.key().path.display()
-                    );
+                    ));
                 }
             }
 
             if !result.expired_reservations.is_empty() {
-                eprintln!("\nExpired reservations:");
+                logger.info("Expired reservations:");
+                let now = SystemTime::now();
                 for res in &result.expired_reservations {
-                    let age_days =
-                        res.last_used_at().elapsed().unwrap_or_default().as_secs() / 86400;
-                    eprintln!(
+                    let age_days = res.age(now).as_secs() / 86400;
+                    logger.info(&format!(
                         "  - Port {}: {} ({} days old)",
                         res.port().value(),
                         res.key().path.display(),
                         age_days
-                    );
+                    ));
                 }
             }
         } else {
@@ -109,3 +139,43 @@ impl AutocleanCommand {
         Ok(())
     }
 }
+
+/// Build the JSON representation of an autoclean run.
+///
+/// The serialized [`AutocleanResult`] (counts and the `pruned` and `expired`
+/// key lists) is extended with the run's settings, and every removal is
+/// also listed as a `delete_reservation` action with the reason it was
+/// selected (`path_missing` for prune, `expired` for expire).
+fn autoclean_json(
+    result: &AutocleanResult,
+    cleanup_config: &CleanupConfig,
+    dry_run: bool,
+) -> Result<serde_json::Value, serde_json::Error> {
+    let actions: Vec<serde_json::Value> = result
+        .removals()
+        .map(|(r, reason)| {
+            serde_json::json!({
+                "action": "delete_reservation",
+                "description": PlanAction::DeleteReservation(r.key().clone()).description(),
+                "reason": reason.as_str(),
+                "port": r.port().value(),
+                "path": r.key().path.display().to_string(),
+                "tag": r.key().tag,
+                "project": r.project(),
+                "task": r.task(),
+                "last_used_at": format_timestamp(r.last_used_at()),
+            })
+        })
+        .collect();
+
+    let mut json = serde_json::to_value(result)?;
+    if let serde_json::Value::Object(ref mut fields) = json {
+        fields.insert("dry_run".to_string(), dry_run.into());
+        fields.insert(
+            "expire_after_days".to_string(),
+            cleanup_config.expire_after_days.into(),
+        );
+        fields.insert("actions".to_string(), actions.into());
+    }
+    Ok(json)
+}
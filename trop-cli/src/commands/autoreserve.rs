@@ -4,14 +4,20 @@
 //! discovers a config file and reserves ports for the defined group.
 
 use crate::error::CliError;
-use crate::utils::{format_allocations, load_configuration, open_database, GlobalOptions};
+use crate::utils::{
+    check_output_file, emit_output, format_allocations, load_configuration, merge_into_dotenv_file,
+    open_database, stderr_color_enabled, GlobalOptions,
+};
 use clap::Args;
 use std::env;
+use std::path::PathBuf;
 use trop::config::ConfigLoader;
+use trop::operations::hook::PreReserveHook;
 use trop::operations::{AutoreserveOptions, AutoreservePlan};
-use trop::PlanExecutor;
+use trop::output::{validate_env_prefix, OutputFormat};
+use trop::{PhaseTimings, PlanExecutor};
 
-use super::reserve_group::OutputFormatArg;
+use super::reserve_group::{print_group_summary, OutputFormatArg, SummaryFormat};
 
 /// Automatically discover and reserve ports from project config.
 #[derive(Args)]
@@ -21,14 +27,28 @@ pub struct AutoreserveCommand {
     #[arg(long, env = "TROP_TASK")]
     pub task: Option<String>,
 
-    /// Output format
-    #[arg(long, value_enum, default_value = "export")]
+    /// Output format; `auto` is human on a terminal and JSON when piped or
+    /// written to a file
+    #[arg(long, value_enum, default_value = "auto")]
     pub format: OutputFormatArg,
 
     /// Shell type for export format (auto-detect if not specified)
     #[arg(long)]
     pub shell: Option<String>,
 
+    /// Wrap values in double quotes (dotenv format only)
+    #[arg(long)]
+    pub quote: bool,
+
+    /// Prepend PREFIX to every variable name in export and dotenv output
+    /// (e.g. MYAPP_ turns WEB_PORT into MYAPP_WEB_PORT)
+    #[arg(long, value_name = "PREFIX")]
+    pub env_prefix: Option<String>,
+
+    /// Format of the status summary written to stderr
+    #[arg(long, value_enum, default_value = "human")]
+    pub summary: SummaryFormat,
+
     /// Force operation (overrides all protections)
     #[arg(long)]
     pub force: bool,
@@ -52,6 +72,19 @@ pub struct AutoreserveCommand {
     /// Perform a dry run
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Write the formatted output to a file instead of stdout (overwriting requires --force)
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+
+    /// Update the services' variables in this dotenv file instead of printing,
+    /// keeping every other line (implies dotenv format)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["output_file", "format", "shell"])]
+    pub merge_into: Option<PathBuf>,
+
+    /// Reservation group to reserve when the config defines several
+    #[arg(long, value_name = "NAME")]
+    pub group: Option<String>,
 }
 
 impl AutoreserveCommand {
@@ -60,13 +93,27 @@ impl AutoreserveCommand {
         // 1. Get current working directory as start directory
         let start_dir = env::current_dir().map_err(CliError::Io)?;
 
-        // 2. Build AutoreserveOptions
+        if let Some(ref output_file) = self.output_file {
+            check_output_file(output_file, self.force)?;
+        }
+
+        if let Some(ref prefix) = self.env_prefix {
+            validate_env_prefix(prefix).map_err(|e| CliError::InvalidArguments(e.to_string()))?;
+        }
+
+        // 2. Load configuration and build AutoreserveOptions
+        let mut timings = PhaseTimings::new(global.verbose);
+        let config = timings.time("config load", || load_configuration(global))?;
+        let group = self.group;
         let options = AutoreserveOptions::new(start_dir.clone())
             .with_task(self.task)
             .with_force(self.force)
             .with_allow_unrelated_path(self.allow_unrelated_path)
             .with_allow_project_change(self.allow_project_change || self.allow_change)
-            .with_allow_task_change(self.allow_task_change || self.allow_change);
+            .with_allow_task_change(self.allow_task_change || self.allow_change)
+            .with_tag_case(config.tag_case.unwrap_or_default())
+            .with_group(group.clone())
+            .with_pre_reserve_hook(PreReserveHook::from_config(&config));
 
         // 3. Discover config file
         let planner = AutoreservePlan::new(options).map_err(|e| match &e {
@@ -93,24 +140,26 @@ impl AutoreserveCommand {
             return Ok(());
         }
 
-        // 5. Load configuration and open database
-        let config = load_configuration(global)?;
-        let mut db = open_database(global, &config)?;
+        // 5. Open database
+        let mut db = timings.time("database open", || open_database(global, &config))?;
 
         // 6. Begin transaction
         let tx = db.begin_transaction().map_err(CliError::from)?;
 
         // 7. Build plan (inside transaction)
-        let plan = planner.build_plan(&tx).map_err(CliError::from)?;
+        let plan = timings
+            .time("plan build", || planner.build_plan(&tx))
+            .map_err(CliError::from)?;
 
         // 8. Execute plan (inside transaction)
-        let mut executor = PlanExecutor::new(&tx);
+        let mut executor = PlanExecutor::new(&tx).with_timings(&mut timings);
         let result = executor.execute(&plan).map_err(CliError::from)?;
 
         // 9. Commit transaction
         tx.commit()
             .map_err(trop::Error::from)
             .map_err(CliError::from)?;
+        timings.report(&global.logger);
 
         // 8. Extract allocated ports
         let allocated_ports = result.allocated_ports.ok_or_else(|| {
@@ -118,34 +167,47 @@ impl AutoreserveCommand {
         })?;
 
         // 9. Format output based on selected format
-        let output_format = self.format.to_output_format(self.shell.as_deref())?;
+        let output_format = if self.merge_into.is_some() {
+            OutputFormat::Dotenv { quote: self.quote }
+        } else {
+            self.format.to_output_format(
+                self.shell.as_deref(),
+                self.quote,
+                self.output_file.as_deref(),
+            )?
+        };
 
         let output_config = ConfigLoader::load_file(discovered_config).map_err(CliError::from)?;
-        let formatted_output =
-            format_allocations(&output_format, &allocated_ports, &output_config)?;
-
-        // 10. Print to stdout (machine-readable)
-        println!("{formatted_output}");
-
-        // 11. Print status to stderr (human-readable, unless quiet)
-        if !global.quiet {
-            eprintln!("Discovered config: {}", discovered_config.display());
-            eprintln!(
-                "Reserved {} ports for services: {}",
-                allocated_ports.len(),
-                allocated_ports
-                    .keys()
-                    .map(|s| s.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
+        let tag_case = output_config
+            .tag_case
+            .or(config.tag_case)
+            .unwrap_or_default();
+        let formatted_output = format_allocations(
+            &output_format,
+            &allocated_ports,
+            &output_config,
+            group.as_deref(),
+            tag_case,
+            self.env_prefix.as_deref(),
+        )?;
+
+        // 10. Write machine-readable output to stdout, --output-file, or --merge-into
+        if let Some(ref path) = self.merge_into {
+            merge_into_dotenv_file(path, &formatted_output)?;
+        } else {
+            emit_output(&formatted_output, self.output_file.as_deref())?;
         }
 
-        // 12. Print warnings to stderr if any
-        if !global.quiet && !result.warnings.is_empty() {
-            for warning in &result.warnings {
-                eprintln!("Warning: {warning}");
-            }
+        // 11. Print status and warnings to stderr (unless quiet)
+        if !global.quiet {
+            print_group_summary(
+                self.summary,
+                discovered_config,
+                true,
+                &allocated_ports,
+                &result.warnings,
+                stderr_color_enabled(global.color),
+            )?;
         }
 
         Ok(())
@@ -0,0 +1,52 @@
+//! Backup command implementation.
+//!
+//! This module implements the `backup` command, which copies the reservation
+//! database to a file using `SQLite`'s online backup API, so it is safe to
+//! run while other trop processes are active.
+
+use crate::error::CliError;
+use crate::utils::{
+    check_output_file, load_configuration, open_database, resolve_database_path, GlobalOptions,
+};
+use clap::Args;
+use std::path::PathBuf;
+
+/// Back up the reservation database to a file.
+#[derive(Args)]
+pub struct BackupCommand {
+    /// File to write the backup to
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Overwrite the backup file if it already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl BackupCommand {
+    /// Execute the backup command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. There must be a database to back up; don't create an empty one
+        let db_path = resolve_database_path(global)?;
+        if !db_path.exists() {
+            return Err(CliError::SemanticFailure(format!(
+                "No database to back up at {}",
+                db_path.display()
+            )));
+        }
+
+        // 2. Refuse to clobber an existing backup unless forced
+        check_output_file(&self.file, self.force)?;
+
+        // 3. Open the database and copy it
+        let config = load_configuration(global)?;
+        let db = open_database(global, &config)?;
+        db.backup_to(&self.file).map_err(CliError::from)?;
+
+        if !global.quiet {
+            eprintln!("Backed up {} to {}", db_path.display(), self.file.display());
+        }
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@
 use crate::error::CliError;
 use crate::utils::GlobalOptions;
 use clap::Args;
-use std::collections::BTreeSet;
+use std::io::Read;
 use std::path::PathBuf;
 use trop::config::{Config, PortExclusion};
 
@@ -11,25 +11,47 @@ use trop::config::{Config, PortExclusion};
 #[derive(Args)]
 pub struct CompactExclusionsCommand {
     /// Configuration file path
-    #[arg(value_name = "PATH")]
-    pub path: PathBuf,
+    #[arg(
+        value_name = "PATH",
+        required_unless_present = "stdin",
+        conflicts_with = "stdin"
+    )]
+    pub path: Option<PathBuf>,
 
     /// Dry run (show changes without applying)
-    #[arg(long)]
+    #[arg(long, conflicts_with_all = ["stdin", "stdout"])]
     pub dry_run: bool,
+
+    /// Read a config (or a bare exclusion list) from stdin; implies --stdout
+    #[arg(long)]
+    pub stdin: bool,
+
+    /// Write the compacted result to stdout instead of updating the file
+    #[arg(long)]
+    pub stdout: bool,
 }
 
 impl CompactExclusionsCommand {
-    pub fn execute(self, _global: &GlobalOptions) -> Result<(), CliError> {
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        let Some(path) = self.path.as_ref() else {
+            let mut contents = String::new();
+            std::io::stdin().read_to_string(&mut contents)?;
+            return Self::pipe(&contents, global);
+        };
+
         // 1. Load configuration
-        if !self.path.exists() {
+        if !path.exists() {
             return Err(CliError::InvalidArguments(format!(
                 "File not found: {}",
-                self.path.display()
+                path.display()
             )));
         }
 
-        let contents = std::fs::read_to_string(&self.path)?;
+        let contents = std::fs::read_to_string(path)?;
+        if self.stdout {
+            return Self::pipe(&contents, global);
+        }
+
         let mut config: Config = serde_yaml::from_str(&contents)
             .map_err(|e| CliError::Config(format!("Parse error: {e}")))?;
 
@@ -48,8 +70,8 @@ impl CompactExclusionsCommand {
                     // 3. Save configuration (YAML comments will be lost)
                     let yaml = serde_yaml::to_string(&config)
                         .map_err(|e| CliError::Config(format!("Serialize error: {e}")))?;
-                    std::fs::write(&self.path, yaml)?;
-                    println!("Updated {}", self.path.display());
+                    std::fs::write(path, yaml)?;
+                    println!("Updated {}", path.display());
                 } else {
                     println!("Dry run - no changes made");
                     println!("Would save: {compacted:?}");
@@ -63,68 +85,46 @@ impl CompactExclusionsCommand {
 
         Ok(())
     }
-}
-
-/// Compact a list of port exclusions to minimal representation.
-pub fn compact_exclusion_list(exclusions: &[PortExclusion]) -> Vec<PortExclusion> {
-    // Collect all excluded ports
-    let mut ports = BTreeSet::new();
-    for exclusion in exclusions {
-        match exclusion {
-            PortExclusion::Single(p) => {
-                ports.insert(*p);
-            }
-            PortExclusion::Range { start, end } => {
-                for p in *start..=*end {
-                    ports.insert(p);
-                }
-            }
-        }
-    }
 
-    // Build minimal ranges
-    let mut result = Vec::new();
-    let mut current_start: Option<u16> = None;
-    let mut current_end: Option<u16> = None;
-
-    for &port in &ports {
-        match (current_start, current_end) {
-            (None, None) => {
-                current_start = Some(port);
-                current_end = Some(port);
-            }
-            (Some(start), Some(end)) => {
-                if port == end + 1 {
-                    // Extend current range
-                    current_end = Some(port);
-                } else {
-                    // Save current range and start new one
-                    if start == end {
-                        result.push(PortExclusion::Single(start));
-                    } else {
-                        result.push(PortExclusion::Range { start, end });
-                    }
-                    current_start = Some(port);
-                    current_end = Some(port);
-                }
+    /// Compact `contents` and write the result to stdout.
+    ///
+    /// The input is either a full configuration or a bare YAML list of
+    /// exclusions; the output has the same shape as the input. Status
+    /// messages go to stderr so stdout stays pipeable.
+    fn pipe(contents: &str, global: &GlobalOptions) -> Result<(), CliError> {
+        let parse_error = |e: serde_yaml::Error| CliError::Config(format!("Parse error: {e}"));
+        let value: serde_yaml::Value = serde_yaml::from_str(contents).map_err(parse_error)?;
+
+        let (yaml, counts) = if value.is_sequence() {
+            let exclusions: Vec<PortExclusion> =
+                serde_yaml::from_value(value).map_err(parse_error)?;
+            let compacted = compact_exclusion_list(&exclusions);
+            let counts = (exclusions.len(), compacted.len());
+            (serde_yaml::to_string(&compacted), counts)
+        } else {
+            let mut config: Config = serde_yaml::from_value(value).map_err(parse_error)?;
+            let mut counts = (0, 0);
+            if let Some(ref mut exclusions) = config.excluded_ports {
+                let compacted = compact_exclusion_list(exclusions);
+                counts = (exclusions.len(), compacted.len());
+                *exclusions = compacted;
             }
-            _ => unreachable!(),
-        }
-    }
+            (serde_yaml::to_string(&config), counts)
+        };
+        let yaml = yaml.map_err(|e| CliError::Config(format!("Serialize error: {e}")))?;
 
-    // Save final range
-    if let (Some(start), Some(end)) = (current_start, current_end) {
-        debug_assert!(start <= end, "start should never exceed end");
-        if start == end {
-            result.push(PortExclusion::Single(start));
-        } else {
-            result.push(PortExclusion::Range { start, end });
+        if !global.quiet {
+            let (original_count, new_count) = counts;
+            eprintln!("Compacted {original_count} exclusions to {new_count}");
         }
+        print!("{yaml}");
+        Ok(())
     }
-
-    result
 }
 
+/// Compact a list of port exclusions to minimal representation.
+pub use trop::config::compact_exclusions as compact_exclusion_list;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,6 +331,49 @@ mod tests {
         }
     }
 
+    /// Test compaction keeps reasons from every coalesced entry.
+    ///
+    /// **Example**: [Single(8080, "a"), Range{8081..8085}, Single(8086, "b")]
+    ///              → Range{8080..8086, "a; b"}
+    /// **Why test this**: Compaction must not silently drop documentation
+    #[test]
+    fn test_compact_merges_reasons() {
+        let exclusions = vec![
+            PortExclusion::DescribedSingle {
+                port: 8080,
+                reason: "a".to_string(),
+            },
+            PortExclusion::Range {
+                start: 8081,
+                end: 8085,
+            },
+            PortExclusion::DescribedSingle {
+                port: 8086,
+                reason: "b".to_string(),
+            },
+            PortExclusion::DescribedSingle {
+                port: 9000,
+                reason: "c".to_string(),
+            },
+        ];
+        let compacted = compact_exclusion_list(&exclusions);
+
+        assert_eq!(
+            compacted,
+            vec![
+                PortExclusion::DescribedRange {
+                    start: 8080,
+                    end: 8086,
+                    reason: "a; b".to_string(),
+                },
+                PortExclusion::DescribedSingle {
+                    port: 9000,
+                    reason: "c".to_string(),
+                },
+            ]
+        );
+    }
+
     /// Test compaction merges ranges with singles filling gaps.
     ///
     /// **Complex scenario**: [Range{8080..8082}, Single(8083), Range{8084..8086}]
@@ -515,32 +558,16 @@ mod tests {
         // Collect all ports from original
         let mut original_ports = HashSet::new();
         for excl in &exclusions {
-            match excl {
-                PortExclusion::Single(p) => {
-                    original_ports.insert(*p);
-                }
-                PortExclusion::Range { start, end } => {
-                    for p in *start..=*end {
-                        original_ports.insert(p);
-                    }
-                }
-            }
+            let (start, end) = excl.bounds();
+            original_ports.extend(start..=end);
         }
 
         // Compact and collect all ports from result
         let compacted = compact_exclusion_list(&exclusions);
         let mut compacted_ports = HashSet::new();
         for excl in &compacted {
-            match excl {
-                PortExclusion::Single(p) => {
-                    compacted_ports.insert(*p);
-                }
-                PortExclusion::Range { start, end } => {
-                    for p in *start..=*end {
-                        compacted_ports.insert(p);
-                    }
-                }
-            }
+            let (start, end) = excl.bounds();
+            compacted_ports.extend(start..=end);
         }
 
         assert_eq!(
@@ -1,7 +1,7 @@
 //! Shell completion generation command.
 //!
 //! This module provides the `completions` command which generates shell completion
-//! scripts for bash, zsh, fish, and PowerShell.
+//! scripts for bash, zsh, fish, PowerShell, and elvish.
 
 use crate::cli::Cli;
 use crate::error::CliError;
@@ -10,9 +10,6 @@ use clap::{CommandFactory, Parser};
 use clap_complete::{generate, Shell};
 use std::io;
 
-/// Binary name from Cargo.toml package name
-const BIN_NAME: &str = env!("CARGO_PKG_NAME");
-
 /// Generate shell completion scripts
 #[derive(Parser)]
 pub struct CompletionsCommand {
@@ -24,8 +21,10 @@ pub struct CompletionsCommand {
 impl CompletionsCommand {
     /// Execute the completions command.
     pub fn execute(&self, _global: &GlobalOptions) -> Result<(), CliError> {
+        // Use the command's own name (`trop`) rather than the package name
+        // (`trop-cli`) so the completions bind to the installed binary
         let mut cmd = Cli::command();
-        let bin_name = BIN_NAME;
+        let bin_name = cmd.get_name().to_string();
 
         eprintln!("# Generating {} completion script", self.shell);
         eprintln!("# Run the following command to enable completions:");
@@ -55,7 +54,9 @@ impl CompletionsCommand {
                 eprintln!("#   trop completions powershell | Out-String | Invoke-Expression");
             }
             Shell::Elvish => {
-                // Elvish included by default in clap_complete but no custom instructions needed
+                eprintln!("#   trop completions elvish > ~/.config/elvish/lib/trop.elv");
+                eprintln!("# Then add to rc.elv:");
+                eprintln!("#   use trop");
             }
             _ => {
                 // Future shells added to clap_complete
@@ -0,0 +1,173 @@
+//! Diff command implementation.
+//!
+//! This module implements the `diff` command, which compares a reservation
+//! manifest against the database and reports what applying it would create,
+//! delete, or change. Nothing is modified.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, GlobalOptions};
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+use trop::operations::{Manifest, ManifestChange, ManifestDiff, ManifestEntry};
+use trop::{Database, Reservation};
+
+/// Compare a reservation manifest against the current database.
+#[derive(Args)]
+pub struct DiffCommand {
+    /// Manifest file: a JSON array of reservations, as `list --format json` produces
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text", ignore_case = true)]
+    pub format: DiffFormat,
+}
+
+/// Output format for diff command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DiffFormat {
+    /// One line per difference (`+` create, `-` delete, `~` change)
+    Text,
+    /// JSON object with `create`, `delete`, `change`, and `unchanged`
+    Json,
+}
+
+impl DiffCommand {
+    /// Execute the diff command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Load the manifest
+        if !self.file.is_file() {
+            return Err(CliError::InvalidArguments(format!(
+                "Manifest file not found: {}",
+                self.file.display()
+            )));
+        }
+        let manifest = Manifest::load(&self.file).map_err(CliError::from)?;
+
+        // 2. Read current reservations
+        let config = load_configuration(global)?;
+        let db = open_database(global, &config)?;
+        let current = Database::list_all_reservations(db.connection()).map_err(CliError::from)?;
+
+        // 3. Compare and report
+        let diff = manifest.diff(&current);
+        match self.format {
+            DiffFormat::Text => print_text(&diff, global),
+            DiffFormat::Json => {
+                let output = serde_json::to_string_pretty(&diff_json(&diff))
+                    .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+                println!("{output}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Print one line per difference, with a summary on stderr.
+fn print_text(diff: &ManifestDiff, global: &GlobalOptions) {
+    for entry in &diff.create {
+        println!("+ {} port {}", entry.key, entry.port);
+    }
+    for reservation in &diff.delete {
+        println!("- {} port {}", reservation.key(), reservation.port());
+    }
+    for change in &diff.change {
+        let fields: Vec<String> = change
+            .fields
+            .iter()
+            .map(|field| {
+                let (from, to) = field_values(change, field);
+                format!("{field} {} -> {}", text_value(&from), text_value(&to))
+            })
+            .collect();
+        println!("~ {}: {}", change.current.key(), fields.join(", "));
+    }
+
+    if !global.quiet {
+        eprintln!(
+            "{} to create, {} to delete, {} to change, {} unchanged",
+            diff.create.len(),
+            diff.delete.len(),
+            diff.change.len(),
+            diff.unchanged
+        );
+    }
+}
+
+/// Render a JSON field value for text output.
+fn text_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "-".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Current and desired values of a changed field.
+fn field_values(change: &ManifestChange, field: &str) -> (serde_json::Value, serde_json::Value) {
+    let current = &change.current;
+    let desired = &change.desired;
+    match field {
+        "port" => (current.port().value().into(), desired.port.value().into()),
+        "project" => (current.project().into(), desired.project.as_deref().into()),
+        "task" => (current.task().into(), desired.task.as_deref().into()),
+        "pinned" => (current.pinned().into(), desired.pinned.into()),
+        _ => (serde_json::Value::Null, serde_json::Value::Null),
+    }
+}
+
+/// Build the structured JSON representation of a diff.
+fn diff_json(diff: &ManifestDiff) -> serde_json::Value {
+    let change: Vec<serde_json::Value> = diff
+        .change
+        .iter()
+        .map(|change| {
+            let fields: serde_json::Map<String, serde_json::Value> = change
+                .fields
+                .iter()
+                .map(|field| {
+                    let (from, to) = field_values(change, field);
+                    (
+                        (*field).to_string(),
+                        serde_json::json!({ "from": from, "to": to }),
+                    )
+                })
+                .collect();
+            serde_json::json!({
+                "path": change.current.key().path.display().to_string(),
+                "tag": change.current.key().tag,
+                "changes": fields,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "create": diff.create.iter().map(entry_json).collect::<Vec<_>>(),
+        "delete": diff.delete.iter().map(reservation_json).collect::<Vec<_>>(),
+        "change": change,
+        "unchanged": diff.unchanged,
+    })
+}
+
+/// JSON for a manifest entry that would be created.
+fn entry_json(entry: &ManifestEntry) -> serde_json::Value {
+    serde_json::json!({
+        "path": entry.key.path.display().to_string(),
+        "tag": entry.key.tag,
+        "port": entry.port.value(),
+        "project": entry.project,
+        "task": entry.task,
+    })
+}
+
+/// JSON for a reservation that would be deleted.
+fn reservation_json(reservation: &Reservation) -> serde_json::Value {
+    serde_json::json!({
+        "path": reservation.key().path.display().to_string(),
+        "tag": reservation.key().tag,
+        "port": reservation.port().value(),
+        "project": reservation.project(),
+        "task": reservation.task(),
+    })
+}
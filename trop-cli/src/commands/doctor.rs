@@ -0,0 +1,276 @@
+//! Doctor command implementation.
+//!
+//! This module implements the `doctor` command, which checks the data
+//! directory, configuration discovery, the effective configuration, `TROP_*`
+//! environment variables, and the database, reporting PASS, WARN, or FAIL
+//! for each so misconfigurations surface before a command trips over them.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, resolve_data_dir, resolve_database_path, GlobalOptions};
+use clap::Args;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use trop::config::ConfigLoader;
+use trop::{Database, DatabaseConfig};
+
+/// Diagnose configuration and environment issues.
+#[derive(Args)]
+pub struct DoctorCommand {}
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+        })
+    }
+}
+
+/// A check result with its summary and any supporting detail lines.
+struct Check {
+    status: Status,
+    name: &'static str,
+    summary: String,
+    details: Vec<String>,
+}
+
+impl Check {
+    fn new(status: Status, name: &'static str, summary: impl Into<String>) -> Self {
+        Self {
+            status,
+            name,
+            summary: summary.into(),
+            details: Vec::new(),
+        }
+    }
+
+    fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+impl DoctorCommand {
+    /// Execute the doctor command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        let checks = [
+            check_data_dir(global),
+            check_config_files(global),
+            check_effective_config(global),
+            check_environment(),
+            check_database(global),
+        ];
+
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        for check in &checks {
+            writeln!(
+                handle,
+                "[{}] {}: {}",
+                check.status, check.name, check.summary
+            )?;
+            for detail in &check.details {
+                writeln!(handle, "    {detail}")?;
+            }
+        }
+
+        let failures = checks.iter().filter(|c| c.status == Status::Fail).count();
+        if failures > 0 {
+            return Err(CliError::SemanticFailure(format!(
+                "{failures} check(s) failed"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that the data directory exists and accepts new files.
+fn check_data_dir(global: &GlobalOptions) -> Check {
+    const NAME: &str = "Data directory";
+    let data_dir = global.data_dir.clone().unwrap_or_else(resolve_data_dir);
+
+    if !data_dir.exists() {
+        let status = if global.disable_autoinit {
+            Status::Fail
+        } else {
+            Status::Warn
+        };
+        return Check::new(
+            status,
+            NAME,
+            format!(
+                "{} does not exist (run `trop init` to create it)",
+                data_dir.display()
+            ),
+        );
+    }
+    if !data_dir.is_dir() {
+        return Check::new(
+            Status::Fail,
+            NAME,
+            format!("{} is not a directory", data_dir.display()),
+        );
+    }
+
+    match probe_writable(&data_dir) {
+        Ok(()) => Check::new(
+            Status::Pass,
+            NAME,
+            format!("{} (writable)", data_dir.display()),
+        ),
+        Err(e) => Check::new(
+            Status::Fail,
+            NAME,
+            format!("{} is not writable: {e}", data_dir.display()),
+        ),
+    }
+}
+
+/// Create and remove a scratch file to prove the directory is writable.
+pub(crate) fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".trop-doctor-{}", std::process::id()));
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+/// Report which configuration files are discovered, lowest precedence first.
+fn check_config_files(global: &GlobalOptions) -> Check {
+    const NAME: &str = "Config files";
+    let working_dir = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            return Check::new(
+                Status::Fail,
+                NAME,
+                format!("cannot read working directory: {e}"),
+            )
+        }
+    };
+
+    match ConfigLoader::load_all(&working_dir, global.data_dir.as_deref()) {
+        Ok(sources) if sources.is_empty() => {
+            Check::new(Status::Pass, NAME, "none found; using built-in defaults")
+        }
+        Ok(sources) => {
+            let mut details: Vec<String> = sources
+                .iter()
+                .map(|source| {
+                    format!(
+                        "{} (precedence {})",
+                        source.path.display(),
+                        source.precedence
+                    )
+                })
+                .collect();
+
+            // A JSON tropfile next to its YAML counterpart is silently unused
+            let shadowed: Vec<PathBuf> = sources
+                .iter()
+                .filter(|source| {
+                    source.is_tropfile() && source.path.extension().is_some_and(|ext| ext == "yaml")
+                })
+                .map(|source| source.path.with_extension("json"))
+                .filter(|json| json.exists())
+                .collect();
+            let status = if shadowed.is_empty() {
+                Status::Pass
+            } else {
+                Status::Warn
+            };
+            details.extend(
+                shadowed
+                    .iter()
+                    .map(|json| format!("{} is ignored in favor of its YAML file", json.display())),
+            );
+
+            Check::new(
+                status,
+                NAME,
+                format!("{} found, lowest precedence first", sources.len()),
+            )
+            .with_details(details)
+        }
+        Err(e) => Check::new(Status::Fail, NAME, e.to_string()),
+    }
+}
+
+/// Build the merged configuration exactly as other commands do and show it.
+fn check_effective_config(global: &GlobalOptions) -> Check {
+    const NAME: &str = "Effective config";
+    match load_configuration(global) {
+        Ok(config) => match serde_yaml::to_string(&config) {
+            Ok(yaml) => Check::new(Status::Pass, NAME, "valid")
+                .with_details(yaml.lines().map(str::to_string).collect()),
+            Err(e) => Check::new(Status::Warn, NAME, format!("valid, but not printable: {e}")),
+        },
+        Err(e) => Check::new(Status::Fail, NAME, e.to_string()),
+    }
+}
+
+/// List the `TROP_*` environment variables currently set.
+fn check_environment() -> Check {
+    const NAME: &str = "Environment";
+    let mut vars: Vec<(String, String)> = env::vars()
+        .filter(|(name, _)| name.starts_with("TROP_"))
+        .collect();
+    vars.sort();
+
+    if vars.is_empty() {
+        return Check::new(Status::Pass, NAME, "no TROP_* variables set");
+    }
+    Check::new(
+        Status::Pass,
+        NAME,
+        format!("{} TROP_* variable(s) set", vars.len()),
+    )
+    .with_details(
+        vars.into_iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect(),
+    )
+}
+
+/// Check that the database exists and passes an integrity check.
+fn check_database(global: &GlobalOptions) -> Check {
+    const NAME: &str = "Database";
+    let db_path = match resolve_database_path(global) {
+        Ok(path) => path,
+        Err(e) => return Check::new(Status::Fail, NAME, e.to_string()),
+    };
+
+    if !db_path.exists() {
+        return Check::new(
+            Status::Warn,
+            NAME,
+            format!(
+                "{} does not exist yet (created on first reservation)",
+                db_path.display()
+            ),
+        );
+    }
+
+    // Read-only so diagnosing never migrates or otherwise changes the file
+    let result = Database::open(DatabaseConfig::new(&db_path).read_only())
+        .and_then(|mut db| db.verify_integrity());
+    match result {
+        Ok(()) => Check::new(
+            Status::Pass,
+            NAME,
+            format!("{} (integrity check passed)", db_path.display()),
+        ),
+        Err(e) => Check::new(Status::Fail, NAME, format!("{}: {e}", db_path.display())),
+    }
+}
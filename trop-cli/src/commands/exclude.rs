@@ -2,11 +2,12 @@
 
 use crate::error::CliError;
 use crate::utils::{
-    load_configuration, open_database, resolve_config_file, resolve_data_dir, GlobalOptions,
+    load_configuration, open_database, resolve_config_file, resolve_data_dir, write_config_file,
+    GlobalOptions,
 };
 use clap::Args;
 use std::path::Path;
-use trop::config::{Config, PortExclusion};
+use trop::config::{merge_exclusion_reasons, Config, PortExclusion};
 use trop::{Database, Port};
 
 /// Add port or range to exclusion list.
@@ -23,6 +24,10 @@ pub struct ExcludeCommand {
     /// Force exclusion even if port is reserved
     #[arg(long)]
     pub force: bool,
+
+    /// Why the port or range is excluded (recorded in the config file)
+    #[arg(long, value_name = "REASON")]
+    pub reason: Option<String>,
 }
 
 impl ExcludeCommand {
@@ -68,6 +73,10 @@ impl ExcludeCommand {
     }
 
     fn parse_exclusion(&self) -> Result<PortExclusion, CliError> {
+        if self.reason.as_deref().is_some_and(|r| r.trim().is_empty()) {
+            return Err(CliError::InvalidArguments("Reason cannot be empty".into()));
+        }
+
         // Parse "8080" or "8080..8090" format
         if let Some(separator_pos) = self.port_or_range.find("..") {
             // Range format
@@ -84,25 +93,23 @@ impl ExcludeCommand {
             Ok(PortExclusion::Range {
                 start: min,
                 end: max,
-            })
+            }
+            .with_reason(self.reason.clone()))
         } else {
             // Single port
             let port = self
                 .port_or_range
                 .parse::<u16>()
                 .map_err(|_| CliError::InvalidArguments("Invalid port number".into()))?;
-            Ok(PortExclusion::Single(port))
+            Ok(PortExclusion::Single(port).with_reason(self.reason.clone()))
         }
     }
 
     fn check_reserved(&self, db: &Database, exclusion: &PortExclusion) -> Result<(), CliError> {
         // Check if any ports in the exclusion are reserved
-        let ports_to_check = match exclusion {
-            PortExclusion::Single(p) => vec![*p],
-            PortExclusion::Range { start, end } => (*start..=*end).collect(),
-        };
+        let (start, end) = exclusion.bounds();
 
-        for port_value in ports_to_check {
+        for port_value in start..=end {
             if let Ok(port) = Port::try_from(port_value) {
                 if Database::is_port_reserved(db.connection(), port).unwrap_or(false) {
                     return Err(CliError::InvalidArguments(format!(
@@ -136,12 +143,20 @@ impl ExcludeCommand {
         }
 
         if let Some(ref mut exclusions) = config.excluded_ports {
-            // Check for duplicates
-            if !exclusions.contains(&exclusion) {
+            // Check for duplicates (same ports); a new reason is merged in
+            if let Some(existing) = exclusions
+                .iter_mut()
+                .find(|e| e.bounds() == exclusion.bounds())
+            {
+                let reason = merge_exclusion_reasons(existing.reason(), exclusion.reason());
+                if reason.as_deref() == existing.reason() {
+                    return Ok(false); // Already existed
+                }
+                *existing = existing.with_reason(reason);
+                Ok(true) // Reason was added
+            } else {
                 exclusions.push(exclusion);
                 Ok(true) // Was added
-            } else {
-                Ok(false) // Already existed
             }
         } else {
             Ok(false)
@@ -151,9 +166,6 @@ impl ExcludeCommand {
     fn save_config_file(&self, path: &Path, config: &Config) -> Result<(), CliError> {
         // Note: YAML comments will be lost during this process
         // This is a known limitation documented in the plan
-        let yaml = serde_yaml::to_string(config)
-            .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))?;
-        std::fs::write(path, yaml)?;
-        Ok(())
+        write_config_file(path, config)
     }
 }
@@ -1,21 +1,46 @@
 //! Expire command implementation.
 //!
 //! This module implements the `expire` command, which removes reservations
-//! based on age.
+//! based on age, along with any whose own TTL (`reserve --ttl`) has run out.
 
 use crate::error::CliError;
-use crate::utils::{load_configuration, open_database, GlobalOptions};
+use crate::utils::{
+    load_configuration, open_database, parse_duration, parse_time_bound, GlobalOptions,
+};
 use clap::Args;
-use trop::config::CleanupConfig;
+use std::time::{Duration, SystemTime};
 use trop::operations::CleanupOperations;
 
-/// Remove reservations based on age.
+/// Remove reservations based on age or an elapsed TTL.
 #[derive(Args)]
 pub struct ExpireCommand {
     /// Remove reservations unused for N days
     #[arg(long, value_name = "DAYS")]
     pub days: Option<u32>,
 
+    /// Remove reservations unused for longer than DURATION (e.g. 12h, 14d),
+    /// overriding the configured threshold for this run
+    #[arg(long, value_name = "DURATION", conflicts_with = "days")]
+    pub older_than: Option<String>,
+
+    /// Also remove reservations last used (reserved or renewed) before TIME
+    /// (RFC 3339, or a duration ago like 30d)
+    #[arg(long, value_name = "TIME")]
+    pub used_before: Option<String>,
+
+    /// Only remove reservations past their own TTL, ignoring age entirely
+    /// (reservations without a TTL are always kept)
+    #[arg(
+        long,
+        alias = "expires-at-only",
+        conflicts_with_all = ["days", "older_than", "used_before"]
+    )]
+    pub ttl_only: bool,
+
+    /// Also remove reservations with a priority above `cleanup.protect_priority`
+    #[arg(long)]
+    pub force: bool,
+
     /// Perform a dry run
     #[arg(long)]
     pub dry_run: bool,
@@ -27,34 +52,61 @@ impl ExpireCommand {
         // Load configuration
         let config = load_configuration(global)?;
 
-        // Determine expiration threshold
-        let expire_days = self
-            .days
-            .or_else(|| config.cleanup.as_ref().and_then(|c| c.expire_after_days));
-
-        // Validate we have a threshold
-        let Some(days) = expire_days else {
-            return Err(CliError::InvalidArguments(
-                "No expiration threshold specified. Use --days or configure expire_after_days"
-                    .into(),
-            ));
+        // Determine expiration threshold; without one, only TTLs apply
+        let threshold = if self.ttl_only {
+            None
+        } else if let Some(raw) = &self.older_than {
+            Some((parse_duration(raw)?, raw.clone()))
+        } else {
+            self.days
+                .or_else(|| config.cleanup.as_ref().and_then(|c| c.expire_after_days))
+                .map(|days| {
+                    (
+                        Duration::from_secs(u64::from(days) * 86400),
+                        format!("{days} days"),
+                    )
+                })
+        };
+        let used_before = self
+            .used_before
+            .as_deref()
+            .map(parse_time_bound)
+            .transpose()?;
+        let criteria = match (&threshold, used_before.is_some()) {
+            (Some((_, age)), false) => format!("older than {age} or past their TTL"),
+            (Some((_, age)), true) => {
+                format!("older than {age}, last used before the cutoff, or past their TTL")
+            }
+            (None, true) => "last used before the cutoff or past their TTL".to_string(),
+            (None, false) => "past their TTL".to_string(),
         };
 
-        // Create cleanup config
-        let cleanup_config = CleanupConfig {
-            expire_after_days: Some(days),
+        let protect_priority = if self.force {
+            None
+        } else {
+            config.cleanup.as_ref().and_then(|c| c.protect_priority)
         };
 
         if self.dry_run && !global.quiet {
-            eprintln!("[DRY RUN] Scanning for reservations unused for {days} days...");
+            eprintln!("[DRY RUN] Scanning for reservations {criteria}...");
         }
 
         // Open database
         let mut db = open_database(global, &config)?;
 
         // Perform expiration
-        let result = CleanupOperations::expire(&mut db, &cleanup_config, self.dry_run)
-            .map_err(CliError::from)?;
+        let result = if self.ttl_only {
+            CleanupOperations::expire_past_ttl(&mut db, protect_priority, self.dry_run)
+        } else {
+            CleanupOperations::expire_older_than(
+                &mut db,
+                threshold.map(|(max_age, _)| max_age),
+                used_before,
+                protect_priority,
+                self.dry_run,
+            )
+        }
+        .map_err(CliError::from)?;
 
         // Format output
         if global.quiet {
@@ -63,42 +115,35 @@ impl ExpireCommand {
             }
         } else if global.verbose {
             if self.dry_run {
-                eprintln!(
-                    "[DRY RUN] Would expire {} reservation(s) older than {} days:",
-                    result.removed_count, days
-                );
+                global.logger.info(&format!(
+                    "[DRY RUN] Would expire {} reservation(s) {criteria}:",
+                    result.removed_count
+                ));
             } else {
-                eprintln!(
-                    "Expired {} reservation(s) older than {} days:",
-                    result.removed_count, days
-                );
+                global.logger.info(&format!(
+                    "Expired {} reservation(s) {criteria}:",
+                    result.removed_count
+                ));
             }
 
+            let now = SystemTime::now();
             for reservation in &result.removed_reservations {
-                let age_days = reservation
-                    .last_used_at()
-                    .elapsed()
-                    .unwrap_or_default()
-                    .as_secs()
-                    / 86400;
-                eprintln!(
+                let age_days = reservation.age(now).as_secs() / 86400;
+                global.logger.info(&format!(
                     "  - Port {}: {} ({} days old, project: {:?})",
                     reservation.port().value(),
                     reservation.key().path.display(),
                     age_days,
                     reservation.project()
-                );
+                ));
             }
         } else if self.dry_run {
             eprintln!(
-                "[DRY RUN] Would expire {} reservation(s) older than {} days",
-                result.removed_count, days
+                "[DRY RUN] Would expire {} reservation(s) {criteria}",
+                result.removed_count
             );
         } else {
-            eprintln!(
-                "Expired {} reservation(s) older than {} days",
-                result.removed_count, days
-            );
+            eprintln!("Expired {} reservation(s) {criteria}", result.removed_count);
         }
 
         Ok(())
@@ -0,0 +1,50 @@
+//! Export command implementation.
+//!
+//! This module implements the `export` command, which writes every
+//! reservation to a versioned JSON document that `import` can read back on
+//! another machine, independent of the database schema.
+
+use crate::error::CliError;
+use crate::utils::{
+    check_output_file, emit_output, load_configuration, open_database, GlobalOptions,
+};
+use clap::Args;
+use std::path::PathBuf;
+use trop::database::ExportDocument;
+
+/// Export all reservations as a JSON document.
+#[derive(Args)]
+pub struct ExportCommand {
+    /// Write the document to a file instead of stdout (overwriting requires --force)
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+
+    /// Overwrite the output file if it already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl ExportCommand {
+    /// Execute the export command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Resolve output options up front so bad arguments fail early
+        if let Some(ref output_file) = self.output_file {
+            check_output_file(output_file, self.force)?;
+        }
+
+        // 2. Read and serialize every reservation
+        let config = load_configuration(global)?;
+        let db = open_database(global, &config)?;
+        let document = ExportDocument::export(db.connection()).map_err(CliError::from)?;
+        let json = document.to_json().map_err(CliError::from)?;
+
+        // 3. Emit the document
+        emit_output(&json, self.output_file.as_deref())?;
+
+        if !global.quiet {
+            eprintln!("Exported {} reservation(s)", document.reservations.len());
+        }
+
+        Ok(())
+    }
+}
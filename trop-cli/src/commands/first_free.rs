@@ -0,0 +1,38 @@
+//! Command to show the lowest free port without reserving it.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, GlobalOptions};
+use clap::Args;
+use trop::operations::first_free_port;
+
+/// Print the lowest port in the configured range that a new reservation
+/// could take, without reserving it.
+#[derive(Args)]
+pub struct FirstFreeCommand {
+    /// Also skip ports that are currently in use by other processes
+    #[arg(long)]
+    pub check_occupancy: bool,
+}
+
+impl FirstFreeCommand {
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Load configuration and open database
+        let config = load_configuration(global)?;
+        let db = open_database(global, &config)?;
+
+        // 2. Search the range, lowest first
+        let port = first_free_port(db.connection(), &config, self.check_occupancy)
+            .map_err(CliError::from)?;
+
+        // 3. Print the port, or fail if the range is full
+        match port {
+            Some(port) => {
+                println!("{port}");
+                Ok(())
+            }
+            None => Err(CliError::SemanticFailure(
+                "No free port in the configured range".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Health command implementation.
+//!
+//! This module implements the `health` command, a monitoring-oriented
+//! counterpart to `doctor`: it checks that the data directory is writable,
+//! the database is reachable, its schema is current, and it passes an
+//! integrity check, then reports an overall `ok`, `degraded`, or `fail`
+//! status through both its output and its exit code.
+
+use super::assert_data_dir::validate_database;
+use super::doctor::probe_writable;
+use crate::error::CliError;
+use crate::utils::{resolve_data_dir, resolve_database_filename, GlobalOptions};
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use trop::operations::init::existing_schema_version;
+use trop::{Database, DatabaseConfig};
+
+/// Report trop's health for monitoring.
+#[derive(Args)]
+pub struct HealthCommand {
+    /// Output format
+    #[arg(long, value_enum, default_value = "json", ignore_case = true)]
+    pub format: HealthFormat,
+}
+
+/// Output format for health command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum HealthFormat {
+    /// JSON object with `status` and `checks`
+    Json,
+    /// One line per check followed by the overall status
+    Text,
+}
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    /// Not run because a check it depends on failed.
+    Skip,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+            Self::Skip => "SKIP",
+        })
+    }
+}
+
+/// Overall health, derived from the individual checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum HealthStatus {
+    Ok,
+    Degraded,
+    Fail,
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Degraded => "degraded",
+            Self::Fail => "fail",
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Check {
+    name: &'static str,
+    status: CheckStatus,
+    message: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct HealthReport {
+    status: HealthStatus,
+    checks: Vec<Check>,
+}
+
+impl HealthCommand {
+    /// Execute the health command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        let data_dir = global.data_dir.clone().unwrap_or_else(resolve_data_dir);
+        let db_path = data_dir.join(resolve_database_filename(global)?);
+        let checks = run_checks(&data_dir, &db_path);
+
+        let status = if checks.iter().any(|c| c.status == CheckStatus::Fail) {
+            HealthStatus::Fail
+        } else if checks.iter().any(|c| c.status == CheckStatus::Warn) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        };
+        let report = HealthReport { status, checks };
+
+        match self.format {
+            HealthFormat::Json => {
+                let json = serde_json::to_string_pretty(&report)
+                    .map_err(|e| CliError::Config(format!("JSON serialization failed: {e}")))?;
+                println!("{json}");
+            }
+            HealthFormat::Text => {
+                for check in &report.checks {
+                    println!("[{}] {}: {}", check.status, check.name, check.message);
+                }
+                println!("status: {status}");
+            }
+        }
+
+        if status == HealthStatus::Ok {
+            Ok(())
+        } else {
+            Err(CliError::SemanticFailure(format!(
+                "health status: {status}"
+            )))
+        }
+    }
+}
+
+/// Run every check in order; later checks are skipped when the database
+/// they need is unreachable.
+fn run_checks(data_dir: &Path, db_path: &Path) -> Vec<Check> {
+    let mut checks = vec![check_data_dir(data_dir)];
+
+    let database = check_database(db_path);
+    let reachable = database.status == CheckStatus::Pass;
+    checks.push(database);
+
+    let schema = if reachable {
+        check_schema(db_path)
+    } else {
+        Check::new("schema", CheckStatus::Skip, "database is not reachable")
+    };
+    let schema_current = schema.status == CheckStatus::Pass;
+    checks.push(schema);
+
+    checks.push(if schema_current {
+        match validate_database(db_path) {
+            Ok(()) => Check::new("integrity", CheckStatus::Pass, "integrity check passed"),
+            Err(e) => Check::new("integrity", CheckStatus::Fail, e.to_string()),
+        }
+    } else {
+        Check::new("integrity", CheckStatus::Skip, "schema is not current")
+    });
+
+    checks
+}
+
+/// A missing data directory fails; a read-only one still serves lookups,
+/// so it only degrades health.
+fn check_data_dir(data_dir: &Path) -> Check {
+    const NAME: &str = "data_dir";
+    if !data_dir.is_dir() {
+        return Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!(
+                "{} does not exist or is not a directory",
+                data_dir.display()
+            ),
+        );
+    }
+    match probe_writable(data_dir) {
+        Ok(()) => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} is writable", data_dir.display()),
+        ),
+        Err(e) => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{} is not writable: {e}", data_dir.display()),
+        ),
+    }
+}
+
+/// Check that the database file exists and can be queried.
+fn check_database(db_path: &Path) -> Check {
+    const NAME: &str = "database";
+    if !db_path.exists() {
+        return Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} does not exist", db_path.display()),
+        );
+    }
+    match existing_schema_version(db_path) {
+        Ok(version) => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} is reachable (schema {version})", db_path.display()),
+        ),
+        Err(e) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{}: {e}", db_path.display()),
+        ),
+    }
+}
+
+/// Check the schema version against this client, without changing it.
+fn check_schema(db_path: &Path) -> Check {
+    const NAME: &str = "schema";
+    if existing_schema_version(db_path).unwrap_or(0) == 0 {
+        return Check::new(
+            NAME,
+            CheckStatus::Fail,
+            "database is not initialized (run `trop init`)",
+        );
+    }
+
+    // Opening runs the schema compatibility check; read-only so it never
+    // applies fixes
+    match Database::open(DatabaseConfig::new(db_path).read_only()) {
+        Ok(_) => Check::new(NAME, CheckStatus::Pass, "schema version is current"),
+        Err(e) => Check::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
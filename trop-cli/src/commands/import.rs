@@ -0,0 +1,63 @@
+//! Import command implementation.
+//!
+//! This module implements the `import` command, which reads a document
+//! written by `export` and creates its reservations. Records that conflict
+//! with existing reservations (same path and tag, or same port) are skipped
+//! unless `--force` is given.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, GlobalOptions};
+use clap::Args;
+use std::path::PathBuf;
+use trop::database::ExportDocument;
+
+/// Import reservations from a JSON document written by `export`.
+#[derive(Args)]
+pub struct ImportCommand {
+    /// Export document to read
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Replace conflicting reservations instead of skipping them
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl ImportCommand {
+    /// Execute the import command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Load and parse the document
+        if !self.file.is_file() {
+            return Err(CliError::InvalidArguments(format!(
+                "Import file not found: {}",
+                self.file.display()
+            )));
+        }
+        let json = std::fs::read_to_string(&self.file).map_err(CliError::Io)?;
+        let document = ExportDocument::from_json(&json).map_err(CliError::from)?;
+
+        // 2. Import everything in one transaction; a bad record aborts it all
+        let config = load_configuration(global)?;
+        let mut db = open_database(global, &config)?;
+        let tx = db.begin_transaction().map_err(CliError::from)?;
+        let summary = document.import(&tx, self.force).map_err(CliError::from)?;
+        tx.commit()
+            .map_err(trop::Error::from)
+            .map_err(CliError::from)?;
+
+        if !global.quiet {
+            eprintln!(
+                "Imported {} reservation(s): {} created, {} replaced, {} skipped",
+                summary.created + summary.replaced,
+                summary.created,
+                summary.replaced,
+                summary.skipped
+            );
+            if summary.skipped > 0 {
+                eprintln!("Use --force to replace conflicting reservations");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -4,11 +4,11 @@
 //! the trop data directory and database.
 
 use crate::error::CliError;
-use crate::utils::GlobalOptions;
+use crate::utils::{resolve_database_filename, GlobalOptions};
 use clap::Parser;
 use std::path::PathBuf;
-use trop::database::default_data_dir;
-use trop::operations::init::{init_database, InitOptions};
+use trop::database::{default_data_dir, pending_schema_upgrades, CURRENT_SCHEMA_VERSION};
+use trop::operations::init::{existing_schema_version, init_database, InitOptions};
 
 /// Initialize trop data directory and database.
 #[derive(Parser)]
@@ -26,6 +26,22 @@ pub struct InitCommand {
     #[arg(long)]
     with_config: bool,
 
+    /// Write a commented starter trop.yaml into the current directory
+    #[arg(long)]
+    project_config: bool,
+
+    /// Overwrite an existing trop.yaml when scaffolding with --project-config
+    #[arg(long, requires = "project_config")]
+    force: bool,
+
+    /// Upgrade the schema of an existing database to the current version
+    #[arg(long, conflicts_with = "overwrite")]
+    migrate: bool,
+
+    /// With --migrate, upgrade only as far as this schema version
+    #[arg(long, value_name = "VERSION", requires = "migrate")]
+    schema_version: Option<i32>,
+
     /// Preview actions without executing
     #[arg(long)]
     dry_run: bool,
@@ -49,6 +65,29 @@ impl InitCommand {
                 )
             })?;
 
+        let project_dir = if self.project_config {
+            Some(std::env::current_dir()?)
+        } else {
+            None
+        };
+        let options = InitOptions::new(data_dir.clone())
+            .with_database_filename(resolve_database_filename(global)?)
+            .with_overwrite(self.overwrite)
+            .with_create_config(self.with_config)
+            .with_migrate(self.migrate)
+            .with_target_schema_version(self.schema_version)
+            .with_project_config(project_dir)
+            .with_force(self.force);
+
+        if let Some(path) = options.project_config_path() {
+            if path.exists() && !self.force {
+                return Err(CliError::InvalidArguments(format!(
+                    "{} already exists (use --force to overwrite)",
+                    path.display()
+                )));
+            }
+        }
+
         if self.dry_run {
             // Dry-run mode: show what would be done
             println!("Dry-run mode: no changes will be made");
@@ -61,16 +100,32 @@ impl InitCommand {
                 println!("  - Data directory already exists: {}", data_dir.display());
             }
 
-            let db_path = data_dir.join("trop.db");
+            let db_path = options.database_path();
             if db_path.exists() {
                 if self.overwrite {
                     println!("  - Remove existing database: {}", db_path.display());
                     println!("  - Create new database: {}", db_path.display());
                 } else {
+                    let version = existing_schema_version(&db_path).map_err(CliError::from)?;
                     println!(
-                        "  - ERROR: Database already exists (use --overwrite to replace): {}",
+                        "  - Database already exists (schema {version}): {}",
                         db_path.display()
                     );
+                    if self.migrate {
+                        let target = options.target_schema_version();
+                        let steps =
+                            pending_schema_upgrades(version, target).map_err(CliError::from)?;
+                        if version == target {
+                            println!("  - Schema is already at version {target}; nothing to do");
+                        } else {
+                            println!("  - Upgrade database schema from {version} to {target}");
+                            for step in steps {
+                                println!("    - Schema {}: {}", step.version, step.description);
+                            }
+                        }
+                    } else if version < CURRENT_SCHEMA_VERSION {
+                        println!("  - Schema is outdated (use --migrate to upgrade)");
+                    }
                 }
             } else {
                 println!("  - Create database: {}", db_path.display());
@@ -88,19 +143,39 @@ impl InitCommand {
                 }
             }
 
+            if let Some(path) = options.project_config_path() {
+                if path.exists() {
+                    println!("  - Overwrite project configuration: {}", path.display());
+                } else {
+                    println!("  - Create project configuration: {}", path.display());
+                }
+            }
+
             return Ok(());
         }
 
-        // Build initialization options
-        let options = InitOptions::new(data_dir.clone())
-            .with_overwrite(self.overwrite)
-            .with_create_config(self.with_config);
-
         // Execute initialization
         let result = init_database(&options).map_err(CliError::from)?;
 
-        // Report what was created
-        println!("Initialized trop in: {}", result.data_dir.display());
+        // Report what was created (or found)
+        if let Some(version) = result.existing_schema_version {
+            println!(
+                "trop already initialized in: {} (schema {version})",
+                result.data_dir.display()
+            );
+            if result.schema_upgraded {
+                println!(
+                    "  - Upgraded database schema from {version} to {}",
+                    result.schema_version
+                );
+            } else if result.is_schema_outdated() {
+                println!(
+                    "  - Schema is outdated (current is {CURRENT_SCHEMA_VERSION}); run with --migrate to upgrade"
+                );
+            }
+        } else {
+            println!("Initialized trop in: {}", result.data_dir.display());
+        }
 
         if result.data_dir_created {
             println!("  - Created data directory");
@@ -120,6 +195,10 @@ impl InitCommand {
             println!("  - Configuration file already exists (not overwritten)");
         }
 
+        if let Some(path) = &result.project_config_created {
+            println!("  - Created project configuration: {}", path.display());
+        }
+
         Ok(())
     }
 }
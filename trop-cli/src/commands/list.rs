@@ -1,20 +1,23 @@
 //! List command implementation.
 //!
 //! This module implements the `list` command, which displays active
-//! reservations in various formats (table, JSON, CSV, TSV).
+//! reservations in various formats (table, JSON, YAML, CSV, TSV).
 
 use crate::error::CliError;
 use crate::utils::{
-    format_timestamp, load_configuration, normalize_path, open_database, shorten_path,
-    GlobalOptions,
+    format_aligned_table, format_timestamp, format_timestamp_rfc3339, load_configuration,
+    normalize_path, open_database, parse_time_bound, shorten_path, stdout_color_enabled,
+    truncate_start, write_delimited, GlobalOptions,
 };
 use clap::{Args, ValueEnum};
 use std::io::Write;
 use std::path::PathBuf;
+use trop::database::ReservationFilter;
+use trop::output::YamlFormatter;
 use trop::{Database, Reservation};
 
 /// Column headers for CSV/TSV output.
-const COLUMN_HEADERS: [&str; 7] = [
+const COLUMN_HEADERS: [&str; 8] = [
     "port",
     "path",
     "tag",
@@ -22,8 +25,15 @@ const COLUMN_HEADERS: [&str; 7] = [
     "task",
     "created_at",
     "last_used_at",
+    "note",
 ];
 
+/// Column headers for table output.
+const TABLE_HEADERS: [&str; 7] = ["PORT", "PATH", "TAG", "PROJECT", "TASK", "USED", "NOTE"];
+
+/// Widest a path is shown in table output before it is truncated.
+const MAX_TABLE_PATH_WIDTH: usize = 48;
+
 /// List active reservations.
 #[derive(Args)]
 pub struct ListCommand {
@@ -38,30 +48,64 @@ pub struct ListCommand {
     pub format: OutputFormat,
 
     /// Filter by project
-    #[arg(long, value_name = "PROJECT")]
+    #[arg(long, visible_alias = "project", value_name = "PROJECT")]
     pub filter_project: Option<String>,
 
+    /// Filter by task
+    #[arg(long, visible_alias = "task", value_name = "TASK")]
+    pub filter_task: Option<String>,
+
     /// Filter by tag
-    #[arg(long, value_name = "TAG")]
+    #[arg(long, visible_alias = "tag", value_name = "TAG")]
     pub filter_tag: Option<String>,
 
     /// Filter by path prefix
     #[arg(long, value_name = "PATH")]
     pub filter_path: Option<PathBuf>,
 
+    /// Only reservations first created at or after TIME (RFC 3339, or a duration ago like 7d)
+    #[arg(long, value_name = "TIME")]
+    pub created_since: Option<String>,
+
+    /// Only reservations first created before TIME (RFC 3339, or a duration ago like 7d)
+    #[arg(long, value_name = "TIME")]
+    pub created_before: Option<String>,
+
+    /// Only reservations last used (reserved or renewed) at or after TIME
+    #[arg(long, value_name = "TIME")]
+    pub used_since: Option<String>,
+
+    /// Only reservations last used (reserved or renewed) before TIME
+    #[arg(long, value_name = "TIME")]
+    pub used_before: Option<String>,
+
     /// Show full paths instead of shortened forms
     #[arg(long)]
     pub show_full_paths: bool,
+
+    /// Don't truncate long paths in table output
+    #[arg(long)]
+    pub no_truncate: bool,
+
+    /// Output only the sorted reserved port numbers, one per line
+    #[arg(long)]
+    pub ports_only: bool,
+
+    /// Join the port numbers with this separator instead of newlines
+    #[arg(long, value_name = "SEP", requires = "ports_only")]
+    pub join: Option<String>,
 }
 
 /// Output format for list command.
 #[derive(Clone, Copy, ValueEnum)]
 #[value(rename_all = "lowercase")]
 pub enum OutputFormat {
-    /// Tab-separated table format (human-readable)
+    /// Aligned table format (human-readable)
     Table,
     /// JSON format
     Json,
+    /// YAML format
+    Yaml,
     /// CSV format
     Csv,
     /// TSV format (tab-separated values)
@@ -77,28 +121,39 @@ impl ListCommand {
         // 2. Open database (read-only access is fine)
         let db = open_database(global, &config)?;
 
-        // 3. Query reservations
-        let mut reservations =
-            Database::list_all_reservations(db.connection()).map_err(CliError::from)?;
-
-        // 4. Apply filters
-        if let Some(ref project) = self.filter_project {
-            reservations.retain(|r| r.project() == Some(project.as_str()));
-        }
-
-        if let Some(ref tag) = self.filter_tag {
-            reservations.retain(|r| r.key().tag == Some(tag.clone()));
-        }
+        // 3. Query reservations; project, task, tag, and time filters are
+        //    applied in the query (all must match)
+        let time = |value: Option<String>| value.as_deref().map(parse_time_bound).transpose();
+        let filter = ReservationFilter {
+            project: self.filter_project,
+            task: self.filter_task,
+            tag: self.filter_tag,
+            created_since: time(self.created_since)?,
+            created_before: time(self.created_before)?,
+            used_since: time(self.used_since)?,
+            used_before: time(self.used_before)?,
+        };
+        let mut reservations = Database::list_reservations_filtered(db.connection(), &filter)
+            .map_err(CliError::from)?;
 
+        // 4. Apply the path prefix filter
         if let Some(ref path) = self.filter_path {
             let normalized = normalize_path(path)?;
             reservations.retain(|r| r.key().path.starts_with(&normalized));
         }
 
         // 5. Format and output to stdout
+        if self.ports_only {
+            return format_as_ports(&reservations, self.join.as_deref());
+        }
+
         match self.format {
-            OutputFormat::Table => format_as_table(&reservations, self.show_full_paths)?,
+            OutputFormat::Table => {
+                let color = stdout_color_enabled(global.color);
+                format_as_table(&reservations, self.show_full_paths, self.no_truncate, color)?;
+            }
             OutputFormat::Json => format_as_json(&reservations)?,
+            OutputFormat::Yaml => format_as_yaml(&reservations)?,
             OutputFormat::Csv => format_as_csv(&reservations)?,
             OutputFormat::Tsv => format_as_tsv(&reservations)?,
         }
@@ -107,63 +162,104 @@ impl ListCommand {
     }
 }
 
-/// Format reservations as a human-readable table.
-fn format_as_table(reservations: &[Reservation], show_full: bool) -> Result<(), CliError> {
+/// Output just the sorted port numbers.
+///
+/// Ports are printed one per line, or on a single line joined by
+/// `separator` if given. Nothing is printed when there are no reservations.
+fn format_as_ports(reservations: &[Reservation], separator: Option<&str>) -> Result<(), CliError> {
+    let mut ports: Vec<u16> = reservations.iter().map(|r| r.port().value()).collect();
+    ports.sort_unstable();
+
+    if ports.is_empty() {
+        return Ok(());
+    }
+
     let stdout = std::io::stdout();
     let mut handle = stdout.lock();
 
-    // Print header (uppercase for table display)
-    let header_line = COLUMN_HEADERS
+    let ports: Vec<String> = ports.iter().map(ToString::to_string).collect();
+    writeln!(handle, "{}", ports.join(separator.unwrap_or("\n")))?;
+
+    Ok(())
+}
+
+/// Format reservations as a human-readable table with aligned columns.
+///
+/// Paths longer than [`MAX_TABLE_PATH_WIDTH`] are shortened from the start
+/// with an ellipsis unless `no_truncate` is set.
+fn format_as_table(
+    reservations: &[Reservation],
+    show_full: bool,
+    no_truncate: bool,
+    color: bool,
+) -> Result<(), CliError> {
+    let rows: Vec<Vec<String>> = reservations
         .iter()
-        .map(|s| s.to_uppercase())
-        .collect::<Vec<_>>()
-        .join("\t");
-    writeln!(handle, "{header_line}")?;
-
-    // Print each reservation
-    for res in reservations {
-        let path_str = if show_full {
-            res.key().path.display().to_string()
-        } else {
-            shorten_path(&res.key().path)
-        };
+        .map(|res| {
+            let path = if show_full {
+                res.key().path.display().to_string()
+            } else {
+                shorten_path(&res.key().path)
+            };
+            let path = if no_truncate {
+                path
+            } else {
+                truncate_start(&path, MAX_TABLE_PATH_WIDTH)
+            };
+
+            vec![
+                res.port().value().to_string(),
+                path,
+                res.key().tag.as_deref().unwrap_or("-").to_string(),
+                res.project().unwrap_or("-").to_string(),
+                res.task().unwrap_or("-").to_string(),
+                format_timestamp(res.last_used_at()),
+                res.note().map_or_else(|| "-".to_string(), single_line),
+            ]
+        })
+        .collect();
 
-        writeln!(
-            handle,
-            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
-            res.port().value(),
-            path_str,
-            res.key().tag.as_deref().unwrap_or("-"),
-            res.project().unwrap_or("-"),
-            res.task().unwrap_or("-"),
-            format_timestamp(res.created_at()),
-            format_timestamp(res.last_used_at()),
-        )?;
-    }
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+    writeln!(
+        handle,
+        "{}",
+        format_aligned_table(&TABLE_HEADERS, &rows, color)
+    )?;
 
     Ok(())
 }
 
+/// Flatten a note onto one line so it can't break the table layout.
+fn single_line(note: &str) -> String {
+    note.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// Build the structured record for a reservation, shared by JSON and YAML.
+fn reservation_record(r: &Reservation) -> serde_json::Value {
+    serde_json::json!({
+        "port": r.port().value(),
+        "path": r.key().path.display().to_string(),
+        "tag": r.key().tag,
+        "project": r.project(),
+        "task": r.task(),
+        "created_at": format_timestamp(r.created_at()),
+        "last_used_at": format_timestamp(r.last_used_at()),
+        "pinned": r.pinned(),
+        "note": r.note(),
+        "priority": r.priority(),
+    })
+}
+
 /// Format reservations as JSON.
 fn format_as_json(reservations: &[Reservation]) -> Result<(), CliError> {
     let stdout = std::io::stdout();
     let mut handle = stdout.lock();
 
     // Build a JSON array of reservation objects
-    let json_data: Vec<serde_json::Value> = reservations
-        .iter()
-        .map(|r| {
-            serde_json::json!({
-                "port": r.port().value(),
-                "path": r.key().path.display().to_string(),
-                "tag": r.key().tag,
-                "project": r.project(),
-                "task": r.task(),
-                "created_at": format_timestamp(r.created_at()),
-                "last_used_at": format_timestamp(r.last_used_at()),
-            })
-        })
-        .collect();
+    let json_data: Vec<serde_json::Value> = reservations.iter().map(reservation_record).collect();
 
     serde_json::to_writer_pretty(&mut handle, &json_data)
         .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
@@ -173,40 +269,39 @@ fn format_as_json(reservations: &[Reservation]) -> Result<(), CliError> {
     Ok(())
 }
 
-/// Convert csv::Error to CliError.
-fn csv_error(e: csv::Error) -> CliError {
-    CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e))
+/// Format reservations as YAML, a sequence of the same records as JSON.
+fn format_as_yaml(reservations: &[Reservation]) -> Result<(), CliError> {
+    let records: Vec<serde_json::Value> = reservations.iter().map(reservation_record).collect();
+    let yaml = YamlFormatter::format_value(&records).map_err(CliError::from)?;
+
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+    writeln!(handle, "{yaml}")?;
+
+    Ok(())
 }
 
 /// Format reservations as delimited output (CSV or TSV).
+///
+/// Timestamps are RFC 3339 so spreadsheets and scripts can parse them.
 fn format_as_delimited(reservations: &[Reservation], delimiter: u8) -> Result<(), CliError> {
-    let stdout = std::io::stdout();
-    let handle = stdout.lock();
-    let mut writer = csv::WriterBuilder::new()
-        .delimiter(delimiter)
-        .from_writer(handle);
-
-    // Write header
-    writer.write_record(COLUMN_HEADERS).map_err(csv_error)?;
-
-    // Write each reservation
-    for res in reservations {
-        writer
-            .write_record(&[
+    let rows: Vec<Vec<String>> = reservations
+        .iter()
+        .map(|res| {
+            vec![
                 res.port().value().to_string(),
                 res.key().path.display().to_string(),
                 res.key().tag.as_deref().unwrap_or("").to_string(),
                 res.project().unwrap_or("").to_string(),
                 res.task().unwrap_or("").to_string(),
-                format_timestamp(res.created_at()),
-                format_timestamp(res.last_used_at()),
-            ])
-            .map_err(csv_error)?;
-    }
-
-    writer.flush()?;
+                format_timestamp_rfc3339(res.created_at()),
+                format_timestamp_rfc3339(res.last_used_at()),
+                res.note().unwrap_or("").to_string(),
+            ]
+        })
+        .collect();
 
-    Ok(())
+    write_delimited(&COLUMN_HEADERS, &rows, delimiter)
 }
 
 /// Format reservations as CSV.
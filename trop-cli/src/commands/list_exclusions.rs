@@ -0,0 +1,161 @@
+//! List exclusions command implementation.
+//!
+//! This module implements the `list-exclusions` command, which shows the
+//! effective exclusion list merged from all configuration sources, along
+//! with each exclusion's reason and the sources that define it. Overlapping
+//! and adjacent exclusions are compacted for display.
+
+use crate::error::CliError;
+use crate::utils::GlobalOptions;
+use clap::{Args, ValueEnum};
+use std::io::Write;
+use std::path::Path;
+use trop::config::{
+    merge_exclusion_reasons, Config, ConfigLoader, EnvironmentConfig, PortExclusion,
+};
+
+/// Source label for exclusions coming from `TROP_EXCLUDED_PORTS`.
+const ENV_SOURCE: &str = "TROP_EXCLUDED_PORTS";
+
+/// List the effective port exclusions with reasons and sources.
+#[derive(Args)]
+pub struct ListExclusionsCommand {
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: ListExclusionsFormat,
+}
+
+/// Output format for list-exclusions command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListExclusionsFormat {
+    /// Tab-separated table format (human-readable)
+    Table,
+    /// JSON format
+    Json,
+}
+
+/// An exclusion in the effective set, with the sources that define it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExclusionEntry {
+    /// The exclusion (reasons from all sources combined).
+    pub exclusion: PortExclusion,
+    /// Configuration files (or environment variable) defining it.
+    pub sources: Vec<String>,
+}
+
+impl ListExclusionsCommand {
+    /// Execute the list-exclusions command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        let entries = collect_exclusions(global)?;
+
+        match self.format {
+            ListExclusionsFormat::Table => format_as_table(&entries),
+            ListExclusionsFormat::Json => format_as_json(&entries),
+        }
+    }
+}
+
+/// Format exclusions as a tab-separated table.
+fn format_as_table(entries: &[ExclusionEntry]) -> Result<(), CliError> {
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    writeln!(handle, "PORTS\tREASON\tSOURCE")?;
+    for entry in entries {
+        writeln!(
+            handle,
+            "{}\t{}\t{}",
+            format_ports(&entry.exclusion),
+            entry.exclusion.reason().unwrap_or("-"),
+            entry.sources.join(", ")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Format exclusions as a JSON array.
+fn format_as_json(entries: &[ExclusionEntry]) -> Result<(), CliError> {
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    let json_data: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            let (start, end) = entry.exclusion.bounds();
+            serde_json::json!({
+                "start": start,
+                "end": end,
+                "reason": entry.exclusion.reason(),
+                "sources": entry.sources,
+            })
+        })
+        .collect();
+
+    serde_json::to_writer_pretty(&mut handle, &json_data)
+        .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+
+    writeln!(handle)?;
+
+    Ok(())
+}
+
+/// Collect the effective exclusions from every configuration layer.
+///
+/// Exclusions accumulate across the user config, project configs, and
+/// `TROP_EXCLUDED_PORTS`. Overlapping and adjacent entries are compacted
+/// into one, with their reasons combined and every defining source recorded.
+pub fn collect_exclusions(global: &GlobalOptions) -> Result<Vec<ExclusionEntry>, CliError> {
+    let sources = ConfigLoader::load_all(Path::new("."), global.data_dir.as_deref())
+        .map_err(|e| CliError::Config(e.to_string()))?;
+
+    let mut labelled: Vec<(PortExclusion, String)> = Vec::new();
+    for source in &sources {
+        let label = source.path.display().to_string();
+        for exclusion in source.config.excluded_ports.iter().flatten() {
+            labelled.push((exclusion.normalized(), label.clone()));
+        }
+    }
+
+    let mut env_config = Config::default();
+    EnvironmentConfig::apply_overrides(&mut env_config)
+        .map_err(|e| CliError::Config(e.to_string()))?;
+    for exclusion in env_config.excluded_ports.iter().flatten() {
+        labelled.push((exclusion.clone(), ENV_SOURCE.to_string()));
+    }
+
+    // Stable sort keeps sources in precedence order within equal bounds
+    labelled.sort_by_key(|(exclusion, _)| exclusion.bounds());
+
+    let mut entries: Vec<ExclusionEntry> = Vec::new();
+    for (exclusion, source) in labelled {
+        let (start, end) = exclusion.bounds();
+        match entries.last_mut() {
+            Some(last) if u32::from(start) <= u32::from(last.exclusion.bounds().1) + 1 => {
+                let (last_start, last_end) = last.exclusion.bounds();
+                last.exclusion = PortExclusion::from_bounds(
+                    last_start,
+                    last_end.max(end),
+                    merge_exclusion_reasons(last.exclusion.reason(), exclusion.reason()),
+                );
+                if !last.sources.contains(&source) {
+                    last.sources.push(source);
+                }
+            }
+            _ => entries.push(ExclusionEntry {
+                exclusion,
+                sources: vec![source],
+            }),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Format the ports covered by an exclusion (`5000` or `5000..5010`).
+fn format_ports(exclusion: &PortExclusion) -> String {
+    match exclusion.bounds() {
+        (start, end) if start == end => start.to_string(),
+        (start, end) => format!("{start}..{end}"),
+    }
+}
@@ -1,11 +1,12 @@
 //! List projects command implementation.
 //!
 //! This module implements the `list-projects` command, which displays
-//! all unique project identifiers from the database.
+//! all unique project identifiers from the database, optionally as JSON
+//! with per-project reservation counts.
 
 use crate::error::CliError;
-use crate::utils::{load_configuration, open_database, GlobalOptions};
-use clap::Parser;
+use crate::utils::{format_timestamp, load_configuration, open_database, GlobalOptions};
+use clap::{Parser, ValueEnum};
 use std::io::Write;
 use trop::Database;
 
@@ -13,7 +14,19 @@ use trop::Database;
 #[derive(Parser)]
 #[command(about = "List all unique project identifiers")]
 pub struct ListProjectsCommand {
-    // Future: could add format options, filters
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: ListProjectsFormat,
+}
+
+/// Output format for list-projects command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListProjectsFormat {
+    /// One project name per line
+    Text,
+    /// JSON array with reservation counts per project, including a `null`
+    /// project for reservations without one
+    Json,
 }
 
 impl ListProjectsCommand {
@@ -25,17 +38,50 @@ impl ListProjectsCommand {
         // 2. Open database (read-only access is fine)
         let db = open_database(global, &config)?;
 
-        // 3. Query projects
-        let projects = Database::list_projects(db.connection()).map_err(CliError::from)?;
+        // 3. Query projects and output
+        match self.format {
+            ListProjectsFormat::Text => format_as_text(&db),
+            ListProjectsFormat::Json => format_as_json(&db),
+        }
+    }
+}
 
-        // 4. Output one per line to stdout
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
+/// Output one project per line to stdout.
+fn format_as_text(db: &Database) -> Result<(), CliError> {
+    let projects = Database::list_projects(db.connection()).map_err(CliError::from)?;
 
-        for project in projects {
-            writeln!(handle, "{project}")?;
-        }
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
 
-        Ok(())
+    for project in projects {
+        writeln!(handle, "{project}")?;
     }
+
+    Ok(())
+}
+
+/// Output a JSON array of per-project summaries.
+fn format_as_json(db: &Database) -> Result<(), CliError> {
+    let summaries = Database::project_summaries(db.connection()).map_err(CliError::from)?;
+
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    let json_data: Vec<serde_json::Value> = summaries
+        .iter()
+        .map(|summary| {
+            serde_json::json!({
+                "project": summary.project,
+                "reservation_count": summary.reservation_count,
+                "latest_used_at": format_timestamp(summary.latest_used_at),
+            })
+        })
+        .collect();
+
+    serde_json::to_writer_pretty(&mut handle, &json_data)
+        .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+
+    writeln!(handle)?;
+
+    Ok(())
 }
@@ -13,34 +13,68 @@
 //! - `assert_port`: Assert specific port is reserved
 //! - `assert_data_dir`: Assert data directory exists and is valid
 //! - `port_info`: Display information about a specific port
+//! - `first_free`: Show the lowest free port without reserving it
+//! - `show_config`: Show the effective merged configuration
 //! - `show_data_dir`: Show resolved data directory path
 //! - `show_path`: Show resolved path for a reservation
 //! - `scan`: Scan port range for occupied ports
 //! - `validate`: Validate configuration file
 //! - `exclude`: Add port or range to exclusion list
 //! - `compact_exclusions`: Compact exclusion list to minimal representation
+//! - `list_exclusions`: List the effective exclusions with reasons and sources
+//! - `pin`: Pin a reservation so its port is never displaced
+//! - `unpin`: Remove a reservation's pin
+//! - `rehome`: Move out-of-range reservations into the configured range
+//! - `diff`: Compare a reservation manifest against the database
+//! - `renew`: Refresh a reservation's last-used timestamp
+//! - `move_reservation`: Move a single reservation to a new path
+//! - `backup`: Back up the reservation database to a file
+//! - `restore`: Restore the reservation database from a backup
+//! - `export`: Export reservations as a versioned JSON document
+//! - `import`: Import reservations from an exported JSON document
+//! - `vacuum`: Reclaim unused space in the database
+//! - `stats`: Summarize reservation database usage
+//! - `doctor`: Diagnose configuration and environment issues
+//! - `health`: Report overall health for monitoring
 
 pub mod assert_data_dir;
 pub mod assert_port;
 pub mod assert_reservation;
 pub mod autoclean;
 pub mod autoreserve;
+pub mod backup;
 pub mod compact_exclusions;
 pub mod completions;
+pub mod diff;
+pub mod doctor;
 pub mod exclude;
 pub mod expire;
+pub mod export;
+pub mod first_free;
+pub mod health;
+pub mod import;
 pub mod init;
 pub mod list;
+pub mod list_exclusions;
 pub mod list_projects;
 pub mod migrate;
+pub mod move_reservation;
+pub mod pin;
 pub mod port_info;
 pub mod prune;
+pub mod rehome;
 pub mod release;
+pub mod renew;
 pub mod reserve;
 pub mod reserve_group;
+pub mod restore;
 pub mod scan;
+pub mod show_config;
 pub mod show_data_dir;
 pub mod show_path;
+pub mod stats;
+pub mod unpin;
+pub mod vacuum;
 pub mod validate;
 
 pub use assert_data_dir::AssertDataDirCommand;
@@ -48,20 +82,37 @@ pub use assert_port::AssertPortCommand;
 pub use assert_reservation::AssertReservationCommand;
 pub use autoclean::AutocleanCommand;
 pub use autoreserve::AutoreserveCommand;
+pub use backup::BackupCommand;
 pub use compact_exclusions::CompactExclusionsCommand;
 pub use completions::CompletionsCommand;
+pub use diff::DiffCommand;
+pub use doctor::DoctorCommand;
 pub use exclude::ExcludeCommand;
 pub use expire::ExpireCommand;
+pub use export::ExportCommand;
+pub use first_free::FirstFreeCommand;
+pub use health::HealthCommand;
+pub use import::ImportCommand;
 pub use init::InitCommand;
 pub use list::ListCommand;
+pub use list_exclusions::ListExclusionsCommand;
 pub use list_projects::ListProjectsCommand;
 pub use migrate::MigrateCommand;
+pub use move_reservation::MoveCommand;
+pub use pin::PinCommand;
 pub use port_info::PortInfoCommand;
 pub use prune::PruneCommand;
+pub use rehome::RehomeCommand;
 pub use release::ReleaseCommand;
+pub use renew::RenewCommand;
 pub use reserve::ReserveCommand;
 pub use reserve_group::ReserveGroupCommand;
+pub use restore::RestoreCommand;
 pub use scan::ScanCommand;
+pub use show_config::ShowConfigCommand;
 pub use show_data_dir::ShowDataDirCommand;
 pub use show_path::ShowPathCommand;
+pub use stats::StatsCommand;
+pub use unpin::UnpinCommand;
+pub use vacuum::VacuumCommand;
 pub use validate::ValidateCommand;
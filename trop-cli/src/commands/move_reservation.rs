@@ -0,0 +1,94 @@
+//! Move command implementation.
+//!
+//! This module implements the `move` command, which transfers a single
+//! reservation to a new path while keeping its port, tag, and metadata.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, resolve_path, GlobalOptions};
+use clap::Args;
+use std::path::PathBuf;
+use trop::{MoveOptions, MovePlan, PlanExecutor, ReservationKey};
+
+/// Move a reservation to a new path, keeping its port.
+#[derive(Args)]
+pub struct MoveCommand {
+    /// Directory path of the reservation to move (default: current directory)
+    #[arg(long, value_name = "PATH", env = "TROP_PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Service tag
+    #[arg(long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Destination path
+    #[arg(long, value_name = "PATH")]
+    pub to: PathBuf,
+
+    /// Replace an existing reservation at the destination
+    #[arg(long)]
+    pub force: bool,
+
+    /// Perform a dry run
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl MoveCommand {
+    /// Execute the move command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Resolve path
+        let path = resolve_path(self.path)?;
+
+        // 2. Load configuration and apply the tag case policy
+        let config = load_configuration(global)?;
+        let tag_case = config.tag_case.unwrap_or_default();
+        let tag = self.tag.map(|tag| tag_case.normalize(tag));
+
+        // 3. Build ReservationKey
+        let key = ReservationKey::new(path, tag)
+            .map_err(|e| CliError::InvalidArguments(e.to_string()))?;
+
+        // 4. Open database and build the plan inside one transaction
+        let mut db = open_database(global, &config)?;
+        let tx = db.begin_transaction().map_err(CliError::from)?;
+        let options = MoveOptions::new(key.clone(), self.to).with_force(self.force);
+        let plan = MovePlan::new(options)
+            .build_plan(&tx)
+            .map_err(|e| match e {
+                trop::Error::NotFound { .. } => {
+                    CliError::SemanticFailure(format!("No reservation found for {key}"))
+                }
+                trop::Error::ReservationConflict { details } => CliError::SemanticFailure(details),
+                other => CliError::from(other),
+            })?;
+
+        // 5. Handle dry-run mode
+        if self.dry_run {
+            if !global.quiet {
+                eprintln!("Dry run - would perform the following actions:");
+                for action in &plan.actions {
+                    eprintln!("  {}", action.description());
+                }
+            }
+            return Ok(());
+        }
+
+        // 6. Execute plan and commit
+        let mut executor = PlanExecutor::new(&tx);
+        let result = executor.execute(&plan).map_err(CliError::from)?;
+        tx.commit()
+            .map_err(trop::Error::from)
+            .map_err(CliError::from)?;
+
+        if !global.quiet {
+            eprintln!("{}", plan.description);
+        }
+
+        // 7. Output the port to stdout for scripting
+        if let Some(port) = result.port {
+            println!("{port}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Pin command implementation.
+//!
+//! This module implements the `pin` command, which marks a reservation's
+//! port as pinned. Pinned reservations are never moved or released, even by
+//! forced operations, until they are unpinned.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, resolve_path, GlobalOptions};
+use clap::Args;
+use std::path::PathBuf;
+use trop::{Database, ReservationKey};
+
+/// Pin a reservation so its port is never displaced.
+#[derive(Args)]
+pub struct PinCommand {
+    /// Directory path (default: current directory)
+    #[arg(long, value_name = "PATH", env = "TROP_PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Service tag
+    #[arg(long, value_name = "TAG")]
+    pub tag: Option<String>,
+}
+
+impl PinCommand {
+    /// Execute the pin command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        set_pin_state(self.path, self.tag, true, global)
+    }
+}
+
+/// Set or clear the pinned flag on the reservation for `path` and `tag`.
+///
+/// Fails with exit code 1 if there is no such reservation.
+pub(crate) fn set_pin_state(
+    path: Option<PathBuf>,
+    tag: Option<String>,
+    pinned: bool,
+    global: &GlobalOptions,
+) -> Result<(), CliError> {
+    let path = resolve_path(path)?;
+
+    let config = load_configuration(global)?;
+    let tag_case = config.tag_case.unwrap_or_default();
+    let tag = tag.map(|tag| tag_case.normalize(tag));
+
+    let key =
+        ReservationKey::new(path, tag).map_err(|e| CliError::InvalidArguments(e.to_string()))?;
+
+    let mut db = open_database(global, &config)?;
+    let tx = db.begin_transaction().map_err(CliError::from)?;
+
+    let Some(reservation) = Database::get_reservation(&tx, &key).map_err(CliError::from)? else {
+        return Err(CliError::SemanticFailure(format!(
+            "No reservation found for {key}"
+        )));
+    };
+
+    let verb = if pinned { "pinned" } else { "unpinned" };
+    if reservation.pinned() == pinned {
+        if !global.quiet {
+            eprintln!("Reservation {key} is already {verb}");
+        }
+        return Ok(());
+    }
+
+    Database::set_pinned(&tx, &key, pinned).map_err(CliError::from)?;
+    tx.commit()
+        .map_err(trop::Error::from)
+        .map_err(CliError::from)?;
+
+    if !global.quiet {
+        eprintln!("Reservation {key} {verb} (port {})", reservation.port());
+    }
+
+    Ok(())
+}
@@ -1,10 +1,15 @@
 //! Command to display information about a specific port.
 
 use crate::error::CliError;
-use crate::utils::{format_timestamp, load_configuration, open_database, GlobalOptions};
-use clap::Args;
-use trop::port::occupancy::{OccupancyCheckConfig, PortOccupancyChecker, SystemOccupancyChecker};
-use trop::{Database, Port};
+use crate::utils::{
+    format_timestamp, load_configuration, open_database, paint, stdout_color_enabled,
+    GlobalOptions, Style, SKIP_OCCUPANCY_HELP,
+};
+use clap::{Args, ValueEnum};
+use trop::port::occupancy::{
+    Occupancy, OccupancyCheckConfig, PortOccupancyChecker, SystemOccupancyChecker,
+};
+use trop::{Database, Port, Reservation};
 
 /// Display information about a specific port.
 #[derive(Args)]
@@ -16,6 +21,35 @@ pub struct PortInfoCommand {
     /// Include occupancy information
     #[arg(long)]
     pub include_occupancy: bool,
+
+    /// Also show the PID and name of the process holding the port (needs
+    /// lsof or ss on Unix, netstat on Windows)
+    #[arg(long, requires = "include_occupancy")]
+    pub with_process: bool,
+
+    #[arg(long, help = SKIP_OCCUPANCY_HELP)]
+    pub skip_occupancy: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text", ignore_case = true)]
+    pub format: PortInfoFormat,
+}
+
+/// Occupancy section of the port-info output.
+enum OccupancyStatus {
+    /// The port was probed
+    Checked(trop::Result<Occupancy>),
+    /// Occupancy was requested but checks are skipped for this run
+    Skipped,
+}
+
+/// Output format for port-info command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PortInfoFormat {
+    /// Human-readable lines
+    Text,
+    /// JSON object with the reservation record and occupancy
+    Json,
 }
 
 impl PortInfoCommand {
@@ -32,51 +66,156 @@ impl PortInfoCommand {
         let reservation =
             Database::get_reservation_by_port(db.connection(), port).map_err(CliError::from)?;
 
-        // 4. Display reservation info
-        if let Some(res) = reservation {
-            println!("Port: {}", res.port());
-            println!("Path: {}", res.key().path.display());
-            if let Some(tag) = &res.key().tag {
-                println!("Tag: {tag}");
+        // 4. Check occupancy if requested, unless checks are skipped
+        let skip = self.skip_occupancy
+            || config
+                .occupancy_check
+                .as_ref()
+                .and_then(|occupancy| occupancy.skip)
+                .unwrap_or(false);
+        let occupancy = self.include_occupancy.then(|| {
+            if skip {
+                return OccupancyStatus::Skipped;
             }
-            if let Some(project) = res.project() {
-                println!("Project: {project}");
+            let check_config = OccupancyCheckConfig {
+                on_error: config.on_occupancy_error.unwrap_or_default(),
+                ..OccupancyCheckConfig::default()
+            };
+            OccupancyStatus::Checked(SystemOccupancyChecker.check_occupancy(
+                port,
+                &check_config,
+                self.with_process,
+            ))
+        });
+
+        // 5. Display
+        match self.format {
+            PortInfoFormat::Text => {
+                print_text(port, reservation.as_ref(), occupancy, global);
             }
-            if let Some(task) = res.task() {
-                println!("Task: {task}");
+            PortInfoFormat::Json => {
+                let json = port_info_json(port, reservation.as_ref(), occupancy);
+                let json = serde_json::to_string_pretty(&json)
+                    .map_err(|e| CliError::Config(format!("JSON serialization failed: {e}")))?;
+                println!("{json}");
             }
-            println!("Created: {}", format_timestamp(res.created_at()));
-            println!("Last used: {}", format_timestamp(res.last_used_at()));
+        }
 
-            // Check if path exists
-            let path_exists = res.key().path.exists();
-            println!("Path exists: {}", if path_exists { "yes" } else { "no" });
-        } else {
-            println!("Port {port} is not reserved");
+        Ok(())
+    }
+}
+
+/// Print the reservation and occupancy as human-readable lines.
+fn print_text(
+    port: Port,
+    reservation: Option<&Reservation>,
+    occupancy: Option<OccupancyStatus>,
+    global: &GlobalOptions,
+) {
+    let color = stdout_color_enabled(global.color);
+    if let Some(res) = reservation {
+        let port = res.port().to_string();
+        println!("Port: {}", paint(&port, Style::Highlight, color));
+        println!("Path: {}", res.key().path.display());
+        if let Some(tag) = &res.key().tag {
+            println!("Tag: {tag}");
         }
+        if let Some(project) = res.project() {
+            println!("Project: {project}");
+        }
+        if let Some(task) = res.task() {
+            println!("Task: {task}");
+        }
+        if res.pinned() {
+            println!("Pinned: yes");
+        }
+        if let Some(note) = res.note() {
+            println!("Note: {note}");
+        }
+        println!("Created: {}", format_timestamp(res.created_at()));
+        println!("Last used: {}", format_timestamp(res.last_used_at()));
 
-        // 5. Check occupancy if requested
-        if self.include_occupancy {
-            println!();
-            println!("Occupancy status:");
+        // Check if path exists
+        let path_exists = res.key().path.exists();
+        let path_exists = if path_exists {
+            "yes".to_string()
+        } else {
+            paint("no", Style::Warning, color)
+        };
+        println!("Path exists: {path_exists}");
+    } else {
+        println!("Port {port} is not reserved");
+    }
 
-            let checker = SystemOccupancyChecker;
-            let check_config = OccupancyCheckConfig::default();
+    if let Some(occupancy) = occupancy {
+        println!();
+        println!("Occupancy status:");
 
-            match checker.is_occupied(port, &check_config) {
-                Ok(occupied) => {
-                    if occupied {
-                        println!("  Port is currently in use");
-                    } else {
-                        println!("  Port is available");
-                    }
+        match occupancy {
+            OccupancyStatus::Skipped => println!("  Skipped (occupancy checks are off)"),
+            OccupancyStatus::Checked(Ok(occupancy)) => {
+                if occupancy.occupied {
+                    println!(
+                        "  Port is {}",
+                        paint("currently in use", Style::Warning, color)
+                    );
+                } else {
+                    println!("  Port is available");
                 }
-                Err(e) => {
-                    println!("  Unable to check occupancy: {e}");
+                // Without a resolvable owner this is just the plain status
+                if let Some(pid) = occupancy.pid {
+                    match occupancy.process {
+                        Some(process) => println!("  Process: {process} (PID {pid})"),
+                        None => println!("  Process: PID {pid}"),
+                    }
                 }
             }
+            OccupancyStatus::Checked(Err(e)) => {
+                println!("  Unable to check occupancy: {e}");
+            }
         }
+    }
+}
 
-        Ok(())
+/// Build the JSON object for a port.
+///
+/// `reservation` is `null` for an unreserved port, and `occupancy` is only
+/// present when it was requested; it is `{"skipped": true}` when occupancy
+/// checks are off for the run.
+fn port_info_json(
+    port: Port,
+    reservation: Option<&Reservation>,
+    occupancy: Option<OccupancyStatus>,
+) -> serde_json::Value {
+    let mut json = serde_json::json!({
+        "port": port.value(),
+        "reserved": reservation.is_some(),
+        "reservation": reservation.map(|res| serde_json::json!({
+            "path": res.key().path.display().to_string(),
+            "tag": res.key().tag,
+            "project": res.project(),
+            "task": res.task(),
+            "pinned": res.pinned(),
+            "note": res.note(),
+            "priority": res.priority(),
+            "created_at": format_timestamp(res.created_at()),
+            "last_used_at": format_timestamp(res.last_used_at()),
+            "expires_at": res.expires_at().map(format_timestamp),
+            "path_exists": res.key().path.exists(),
+        })),
+    });
+
+    if let Some(occupancy) = occupancy {
+        json["occupancy"] = match occupancy {
+            OccupancyStatus::Skipped => serde_json::json!({ "skipped": true }),
+            OccupancyStatus::Checked(Ok(occupancy)) => serde_json::json!({
+                "occupied": occupancy.occupied,
+                "pid": occupancy.pid,
+                "process": occupancy.process,
+            }),
+            OccupancyStatus::Checked(Err(e)) => serde_json::json!({ "error": e.to_string() }),
+        };
     }
+
+    json
 }
@@ -1,12 +1,17 @@
 //! Prune command implementation.
 //!
 //! This module implements the `prune` command, which removes reservations
-//! for non-existent directories.
+//! for non-existent directories and, with `--used-before`, reservations
+//! that have gone unused since a cutoff. `--path` and `--project` limit
+//! pruning to one part of a shared data directory.
 
 use crate::error::CliError;
-use crate::utils::{load_configuration, open_database, GlobalOptions};
+use crate::utils::{
+    load_configuration, normalize_path, open_database, parse_time_bound, GlobalOptions,
+};
 use clap::Args;
-use trop::operations::CleanupOperations;
+use std::path::PathBuf;
+use trop::operations::{CleanupOperations, PruneScope};
 
 /// Remove reservations for non-existent directories.
 #[derive(Args)]
@@ -14,6 +19,19 @@ pub struct PruneCommand {
     /// Perform a dry run (show what would be removed without removing)
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Also remove reservations last used (reserved or renewed) before TIME,
+    /// even if their path still exists (RFC 3339, or a duration ago like 30d)
+    #[arg(long, value_name = "TIME")]
+    pub used_before: Option<String>,
+
+    /// Only prune reservations at or below this directory
+    #[arg(long, value_name = "DIR")]
+    pub path: Option<PathBuf>,
+
+    /// Only prune reservations in this project
+    #[arg(long)]
+    pub project: Option<String>,
 }
 
 impl PruneCommand {
@@ -22,16 +40,39 @@ impl PruneCommand {
         // Load configuration for database location
         let config = load_configuration(global)?;
 
+        let used_before = self
+            .used_before
+            .as_deref()
+            .map(parse_time_bound)
+            .transpose()?;
+        let scope = PruneScope::new(
+            self.path.as_deref().map(normalize_path).transpose()?,
+            self.project,
+        );
+
+        let mut criteria = if used_before.is_some() {
+            "for non-existent paths or last used before the cutoff".to_string()
+        } else {
+            "for non-existent paths".to_string()
+        };
+        if let Some(path) = &scope.path {
+            criteria = format!("{criteria} under {}", path.display());
+        }
+        if let Some(project) = &scope.project {
+            criteria = format!("{criteria} in project {project}");
+        }
+
         // Handle dry-run output
         if self.dry_run && !global.quiet {
-            eprintln!("[DRY RUN] Scanning for reservations with non-existent paths...");
+            eprintln!("[DRY RUN] Scanning for reservations {criteria}...");
         }
 
         // Open database with write access
         let mut db = open_database(global, &config)?;
 
         // Perform pruning operation
-        let result = CleanupOperations::prune(&mut db, self.dry_run).map_err(CliError::from)?;
+        let result = CleanupOperations::prune_scoped(&mut db, &scope, used_before, self.dry_run)
+            .map_err(CliError::from)?;
 
         // Format and output results
         if global.quiet {
@@ -40,37 +81,36 @@ impl PruneCommand {
                 println!("{}", result.removed_count);
             }
         } else if global.verbose {
-            // Verbose mode: detailed output to stderr
+            // Verbose mode: detailed output through the logger
             if self.dry_run {
-                eprintln!(
+                global.logger.info(&format!(
                     "[DRY RUN] Would remove {} reservation(s):",
                     result.removed_count
-                );
+                ));
             } else {
-                eprintln!("Removed {} reservation(s):", result.removed_count);
+                global
+                    .logger
+                    .info(&format!("Removed {} reservation(s):", result.removed_count));
             }
 
             for reservation in &result.removed_reservations {
-                eprintln!(
+                global.logger.info(&format!(
                     "  - Port {}: {} (tag: {:?}, project: {:?})",
                     reservation.port().value(),
                     reservation.key().path.display(),
                     reservation.key().tag,
                     reservation.project()
-                );
+                ));
             }
         } else {
             // Normal mode: summary to stderr
             if self.dry_run {
                 eprintln!(
-                    "[DRY RUN] Would remove {} reservation(s) for non-existent paths",
+                    "[DRY RUN] Would remove {} reservation(s) {criteria}",
                     result.removed_count
                 );
             } else {
-                eprintln!(
-                    "Removed {} reservation(s) for non-existent paths",
-                    result.removed_count
-                );
+                eprintln!("Removed {} reservation(s) {criteria}", result.removed_count);
             }
         }
 
@@ -0,0 +1,94 @@
+//! Rehome command implementation.
+//!
+//! This module implements the `rehome` command, which moves reservations
+//! stranded outside the configured port range (e.g. after `ports.min`/`max`
+//! was narrowed) onto free in-range ports, preserving their keys and metadata.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, GlobalOptions};
+use clap::Args;
+use trop::{execute_rehome, RehomeOptions, RehomePlan};
+
+/// Move reservations onto ports inside the configured range.
+#[derive(Args)]
+pub struct RehomeCommand {
+    /// Rehome reservations whose port is outside the configured range
+    #[arg(long, required = true)]
+    pub out_of_range: bool,
+
+    /// Also rehome pinned and sticky reservations
+    #[arg(long)]
+    pub force: bool,
+
+    /// Preview changes without applying them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl RehomeCommand {
+    /// Execute the rehome command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Load configuration
+        let config = load_configuration(global)?;
+
+        // 2. Open database
+        let mut db = open_database(global, &config)?;
+
+        // 3. Build the plan against the configured range
+        let options = RehomeOptions::new()
+            .with_force(self.force)
+            .with_dry_run(self.dry_run);
+        let mut plan = RehomePlan::new(options);
+        plan.build(db.connection(), &config)
+            .map_err(CliError::from)?;
+
+        // 4. Display the plan
+        if !global.quiet {
+            if let Some(range) = plan.range {
+                eprintln!("Configured port range: {range}");
+            }
+
+            if plan.rehome_count() == 0 && plan.skipped_count() == 0 {
+                eprintln!("No reservations outside the configured range.");
+                return Ok(());
+            }
+
+            if plan.rehome_count() > 0 {
+                eprintln!("Reservations to rehome ({}):", plan.rehome_count());
+                for item in &plan.moves {
+                    eprintln!(
+                        "  {}: {} -> {}",
+                        item.reservation.key(),
+                        item.reservation.port(),
+                        item.new_port
+                    );
+                }
+            }
+
+            if plan.skipped_count() > 0 {
+                eprintln!(
+                    "Skipped pinned or sticky reservations ({}) (use --force to rehome):",
+                    plan.skipped_count()
+                );
+                for reservation in &plan.skipped {
+                    eprintln!("  {}: {}", reservation.key(), reservation.port());
+                }
+            }
+        }
+
+        // 5. Execute unless this is a dry run
+        if self.dry_run {
+            if !global.quiet {
+                eprintln!("Dry run - no changes made.");
+            }
+            return Ok(());
+        }
+
+        let result = execute_rehome(&plan, &mut db).map_err(CliError::from)?;
+        if !global.quiet && result.rehomed_count > 0 {
+            eprintln!("Rehomed {} reservation(s)", result.rehomed_count);
+        }
+
+        Ok(())
+    }
+}
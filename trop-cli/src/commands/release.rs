@@ -1,13 +1,20 @@
 //! Release command implementation.
 //!
 //! This module implements the `release` command, which releases port
-//! reservations based on path and tag filters.
+//! reservations based on path and tag filters, by the port they hold, or
+//! in bulk by project or task.
 
 use crate::error::CliError;
-use crate::utils::{load_configuration, open_database, resolve_path, GlobalOptions};
-use clap::Args;
+use crate::utils::{
+    load_configuration, open_database, resolve_path, stderr_color_enabled, warning_prefix,
+    GlobalOptions,
+};
+use clap::{Args, ValueEnum};
 use std::path::PathBuf;
-use trop::{Database, PlanExecutor, ReleaseOptions, ReleasePlan, ReservationKey};
+use trop::{
+    BulkReleaseOptions, BulkReleasePlan, Database, ExecutionResult, PhaseTimings, PlanAction,
+    PlanExecutor, Port, ReleaseOptions, ReleasePlan, ReservationKey,
+};
 
 /// Release a port reservation.
 #[derive(Args)]
@@ -24,10 +31,35 @@ pub struct ReleaseCommand {
     #[arg(long)]
     pub untagged_only: bool,
 
-    /// Release all reservations under path recursively
+    /// Release all reservations under path recursively (pinned ones are skipped)
     #[arg(long)]
     pub recursive: bool,
 
+    /// Release whichever reservation holds this port
+    #[arg(
+        long,
+        value_name = "PORT",
+        conflicts_with_all = ["path", "tag", "untagged_only", "recursive"]
+    )]
+    pub by_port: Option<u16>,
+
+    /// Release every reservation in this project (requires --force)
+    #[arg(
+        long,
+        value_name = "NAME",
+        visible_alias = "all-for-project",
+        conflicts_with_all = ["path", "tag", "untagged_only", "recursive", "by_port"]
+    )]
+    pub project: Option<String>,
+
+    /// Release every reservation for this task (requires --force)
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["path", "tag", "untagged_only", "recursive", "by_port"]
+    )]
+    pub task: Option<String>,
+
     /// Force operation
     #[arg(long)]
     pub force: bool,
@@ -35,11 +67,33 @@ pub struct ReleaseCommand {
     /// Perform a dry run
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Output format: `json` prints the released reservation key(s) to stdout
+    #[arg(long, value_enum, default_value = "plain", ignore_case = true)]
+    pub format: ReleaseFormat,
+}
+
+/// Output format for the release command.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReleaseFormat {
+    /// Status messages on stderr only
+    Plain,
+    /// The released key(s) as JSON on stdout
+    Json,
 }
 
 impl ReleaseCommand {
     /// Execute the release command.
     pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        if let Some(port) = self.by_port {
+            return Self::execute_by_port(port, self.force, self.dry_run, self.format, global);
+        }
+
+        if self.project.is_some() || self.task.is_some() {
+            let options = BulkReleaseOptions::new(self.project, self.task).with_force(self.force);
+            return Self::execute_bulk(options, self.dry_run, self.format, global);
+        }
+
         // 1. Resolve path
         let path = resolve_path(self.path)?;
 
@@ -50,11 +104,14 @@ impl ReleaseCommand {
             ));
         }
 
-        // 3. Load configuration
-        let config = load_configuration(global)?;
+        // 3. Load configuration and apply the tag case policy
+        let mut timings = PhaseTimings::new(global.verbose);
+        let config = timings.time("config load", || load_configuration(global))?;
+        let tag_case = config.tag_case.unwrap_or_default();
+        let tag = self.tag.map(|tag| tag_case.normalize(tag));
 
         // 4. Open database
-        let mut db = open_database(global, &config)?;
+        let mut db = timings.time("database open", || open_database(global, &config))?;
 
         // 5. Handle recursive release or single release
         if self.recursive {
@@ -65,6 +122,7 @@ impl ReleaseCommand {
 
             let mut released_count = 0;
             let mut plans = Vec::new();
+            let mut pinned_count = 0;
 
             for reservation in all_reservations {
                 // Check if this reservation is under the target path
@@ -77,12 +135,18 @@ impl ReleaseCommand {
                     continue;
                 }
 
-                if let Some(ref tag) = self.tag {
+                if let Some(ref tag) = tag {
                     if reservation.key().tag.as_deref() != Some(tag.as_str()) {
                         continue;
                     }
                 }
 
+                // Pinned reservations are left in place, even with --force
+                if reservation.pinned() {
+                    pinned_count += 1;
+                    continue;
+                }
+
                 // Build release options for this reservation
                 let options = ReleaseOptions::new(reservation.key().clone())
                     .with_force(self.force)
@@ -108,7 +172,8 @@ impl ReleaseCommand {
                     }
                 }
             } else {
-                for (_, plan) in plans {
+                let mut released = Vec::new();
+                for (key, plan) in plans {
                     // Each release in its own transaction
                     let tx = db.begin_transaction().map_err(CliError::from)?;
                     let mut executor = PlanExecutor::new(&tx);
@@ -117,28 +182,37 @@ impl ReleaseCommand {
                         .map_err(trop::Error::from)
                         .map_err(CliError::from)?;
                     released_count += 1;
+                    released.push(key_json(&key));
+                }
+
+                if self.format == ReleaseFormat::Json {
+                    print_json(&serde_json::Value::Array(released))?;
                 }
 
                 if !global.quiet {
                     eprintln!("Released {released_count} reservation(s)");
                 }
             }
+
+            if pinned_count > 0 && !global.quiet {
+                eprintln!("Skipped {pinned_count} pinned reservation(s)");
+            }
         } else {
             // Single release: build key and release it
-            let tag = if self.untagged_only { None } else { self.tag };
+            let tag = if self.untagged_only { None } else { tag };
 
             let key = ReservationKey::new(path, tag)
                 .map_err(|e| CliError::InvalidArguments(e.to_string()))?;
 
-            let options = ReleaseOptions::new(key)
+            let options = ReleaseOptions::new(key.clone())
                 .with_force(self.force)
                 .with_allow_unrelated_path(true); // Path was resolved from CWD
 
             // Begin transaction for single release
             let tx = db.begin_transaction().map_err(CliError::from)?;
 
-            let plan = ReleasePlan::new(options)
-                .build_plan(&tx)
+            let plan = timings
+                .time("plan build", || ReleasePlan::new(options).build_plan(&tx))
                 .map_err(CliError::from)?;
 
             if self.dry_run {
@@ -155,7 +229,7 @@ impl ReleaseCommand {
                     }
                 }
             } else {
-                let mut executor = PlanExecutor::new(&tx);
+                let mut executor = PlanExecutor::new(&tx).with_timings(&mut timings);
                 let result = executor.execute(&plan).map_err(CliError::from)?;
 
                 // Commit transaction
@@ -163,6 +237,10 @@ impl ReleaseCommand {
                     .map_err(trop::Error::from)
                     .map_err(CliError::from)?;
 
+                if self.format == ReleaseFormat::Json {
+                    print_json(&released_json(&key, &result))?;
+                }
+
                 if !global.quiet {
                     if plan.actions.is_empty() {
                         eprintln!("No reservation found (already released)");
@@ -173,13 +251,199 @@ impl ReleaseCommand {
                     // Print warnings if any
                     if !result.warnings.is_empty() {
                         for warning in &result.warnings {
-                            eprintln!("Warning: {warning}");
+                            eprintln!(
+                                "{} {warning}",
+                                warning_prefix(stderr_color_enabled(global.color))
+                            );
                         }
                     }
                 }
             }
         }
 
+        timings.report(&global.logger);
+        Ok(())
+    }
+
+    /// Release every reservation matching a project and/or task.
+    ///
+    /// All matches are released in a single transaction. Sticky matches
+    /// (which is all of them) need `--force`; pinned ones are skipped.
+    fn execute_bulk(
+        options: BulkReleaseOptions,
+        dry_run: bool,
+        format: ReleaseFormat,
+        global: &GlobalOptions,
+    ) -> Result<(), CliError> {
+        let scope = match (&options.project, &options.task) {
+            (Some(project), Some(task)) => format!("project {project}, task {task}"),
+            (Some(project), None) => format!("project {project}"),
+            (None, task) => format!("task {}", task.as_deref().unwrap_or_default()),
+        };
+
+        let config = load_configuration(global)?;
+        let mut db = open_database(global, &config)?;
+
+        let tx = db.begin_transaction().map_err(CliError::from)?;
+        let plan = BulkReleasePlan::new(options)
+            .build_plan(&tx)
+            .map_err(CliError::from)?;
+
+        if dry_run {
+            if !global.quiet {
+                eprintln!(
+                    "Dry run - would release {} reservation(s) for {scope}:",
+                    plan.len()
+                );
+                for action in &plan.actions {
+                    if let PlanAction::DeleteReservation(key) = action {
+                        eprintln!("  {key}");
+                    }
+                }
+                for warning in &plan.warnings {
+                    eprintln!(
+                        "{} {warning}",
+                        warning_prefix(stderr_color_enabled(global.color))
+                    );
+                }
+            }
+            return Ok(());
+        }
+
+        let mut executor = PlanExecutor::new(&tx);
+        let result = executor.execute(&plan).map_err(CliError::from)?;
+
+        tx.commit()
+            .map_err(trop::Error::from)
+            .map_err(CliError::from)?;
+
+        if format == ReleaseFormat::Json {
+            let released = plan
+                .actions
+                .iter()
+                .filter_map(|action| match action {
+                    PlanAction::DeleteReservation(key) => Some(key_json(key)),
+                    _ => None,
+                })
+                .collect();
+            print_json(&serde_json::Value::Array(released))?;
+        }
+
+        if !global.quiet {
+            eprintln!("Released {} reservation(s) for {scope}", plan.len());
+            for warning in &result.warnings {
+                eprintln!(
+                    "{} {warning}",
+                    warning_prefix(stderr_color_enabled(global.color))
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Release the reservation holding `port`.
+    ///
+    /// Fails with exit code 1 if no reservation holds the port, if the holder
+    /// is pinned, or if the holder is sticky and `--force` was not given.
+    fn execute_by_port(
+        port: u16,
+        force: bool,
+        dry_run: bool,
+        format: ReleaseFormat,
+        global: &GlobalOptions,
+    ) -> Result<(), CliError> {
+        let port = Port::try_from(port).map_err(|e| CliError::InvalidArguments(e.to_string()))?;
+
+        let config = load_configuration(global)?;
+        let mut db = open_database(global, &config)?;
+
+        let tx = db.begin_transaction().map_err(CliError::from)?;
+
+        let Some(reservation) =
+            Database::get_reservation_by_port(&tx, port).map_err(CliError::from)?
+        else {
+            return Err(CliError::SemanticFailure(format!(
+                "Port {port} is not reserved"
+            )));
+        };
+
+        if reservation.pinned() {
+            return Err(CliError::SemanticFailure(format!(
+                "Port {port} is held by pinned reservation {} (run 'trop unpin' first)",
+                reservation.key()
+            )));
+        }
+
+        if reservation.is_sticky() && !force {
+            return Err(CliError::SemanticFailure(format!(
+                "Port {port} is held by sticky reservation {} (use --force to release it)",
+                reservation.key()
+            )));
+        }
+
+        let options = ReleaseOptions::new(reservation.key().clone())
+            .with_force(force)
+            .with_allow_unrelated_path(true); // Holder may live anywhere
+
+        let plan = ReleasePlan::new(options)
+            .build_plan(&tx)
+            .map_err(CliError::from)?;
+
+        if dry_run {
+            if !global.quiet {
+                eprintln!("Dry run - would release port {port}:");
+                for (i, action) in plan.actions.iter().enumerate() {
+                    eprintln!("  {}. {}", i + 1, action.description());
+                }
+            }
+            return Ok(());
+        }
+
+        let mut executor = PlanExecutor::new(&tx);
+        let result = executor.execute(&plan).map_err(CliError::from)?;
+
+        tx.commit()
+            .map_err(trop::Error::from)
+            .map_err(CliError::from)?;
+
+        if format == ReleaseFormat::Json {
+            let mut json = released_json(reservation.key(), &result);
+            json["port"] = port.value().into();
+            print_json(&json)?;
+        }
+
+        if !global.quiet {
+            eprintln!("Released port {port} (held by {})", reservation.key());
+        }
+
         Ok(())
     }
 }
+
+/// Build the JSON representation of a reservation key.
+fn key_json(key: &ReservationKey) -> serde_json::Value {
+    serde_json::json!({
+        "path": key.path.display().to_string(),
+        "tag": key.tag,
+    })
+}
+
+/// Describe the outcome of releasing `key` as JSON.
+///
+/// `released` is false when the plan had nothing to delete (the key was
+/// already released).
+fn released_json(key: &ReservationKey, result: &ExecutionResult) -> serde_json::Value {
+    let mut json = key_json(key);
+    json["released"] = (!result.actions_taken.is_empty()).into();
+    json["warnings"] = result.warnings.clone().into();
+    json
+}
+
+/// Print a JSON value to stdout.
+fn print_json(json: &serde_json::Value) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(json)
+        .map_err(|e| CliError::Config(format!("JSON serialization failed: {e}")))?;
+    println!("{json}");
+    Ok(())
+}
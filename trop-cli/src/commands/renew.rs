@@ -0,0 +1,149 @@
+//! Renew command implementation.
+//!
+//! This module implements the `renew` command, which marks a reservation as
+//! freshly used so that `expire` keeps it, without releasing it or changing
+//! its port. With `--project`, every reservation in the project is renewed
+//! at once.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, resolve_path, GlobalOptions};
+use clap::Args;
+use std::path::PathBuf;
+use trop::database::ReservationFilter;
+use trop::{Database, PlanExecutor, RenewOptions, RenewPlan, ReservationKey};
+
+/// Renew a reservation's last-used timestamp without reallocating it.
+#[derive(Args)]
+pub struct RenewCommand {
+    /// Directory path (default: current directory)
+    #[arg(long, value_name = "PATH", env = "TROP_PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Service tag
+    #[arg(long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Renew every reservation in this project instead of a single one (ignores --path)
+    #[arg(long, value_name = "PROJECT", conflicts_with = "tag")]
+    pub project: Option<String>,
+
+    /// With --project, renew only reservations for this task
+    #[arg(long, value_name = "TASK", requires = "project")]
+    pub task: Option<String>,
+
+    /// Perform a dry run
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl RenewCommand {
+    /// Execute the renew command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        if let Some(project) = &self.project {
+            return renew_project(project, self.task.as_deref(), self.dry_run, global);
+        }
+
+        // 1. Resolve path
+        let path = resolve_path(self.path)?;
+
+        // 2. Load configuration and apply the tag case policy
+        let config = load_configuration(global)?;
+        let tag_case = config.tag_case.unwrap_or_default();
+        let tag = self.tag.map(|tag| tag_case.normalize(tag));
+
+        // 3. Build ReservationKey
+        let key = ReservationKey::new(path, tag)
+            .map_err(|e| CliError::InvalidArguments(e.to_string()))?;
+
+        // 4. Open database and build the plan; a missing reservation is a
+        //    semantic failure (exit code 1)
+        let mut db = open_database(global, &config)?;
+        let tx = db.begin_transaction().map_err(CliError::from)?;
+        let plan = RenewPlan::new(RenewOptions::new(key.clone()))
+            .build_plan(&tx)
+            .map_err(|e| match e {
+                trop::Error::NotFound { .. } => {
+                    CliError::SemanticFailure(format!("No reservation found for {key}"))
+                }
+                other => CliError::from(other),
+            })?;
+
+        // 5. Handle dry-run mode
+        if self.dry_run {
+            if !global.quiet {
+                eprintln!("Dry run - would perform the following actions:");
+                for action in &plan.actions {
+                    eprintln!("  {}", action.description());
+                }
+            }
+            return Ok(());
+        }
+
+        // 6. Execute plan and commit
+        let mut executor = PlanExecutor::new(&tx);
+        let result = executor.execute(&plan).map_err(CliError::from)?;
+        tx.commit()
+            .map_err(trop::Error::from)
+            .map_err(CliError::from)?;
+
+        if !global.quiet {
+            match result.port {
+                Some(port) => eprintln!("Renewed reservation {key} (port {port})"),
+                None => eprintln!("Renewed reservation {key}"),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Renew every reservation in a project (optionally narrowed to a task).
+///
+/// All matching reservations are touched in one transaction. Matching
+/// nothing is not an error.
+fn renew_project(
+    project: &str,
+    task: Option<&str>,
+    dry_run: bool,
+    global: &GlobalOptions,
+) -> Result<(), CliError> {
+    let config = load_configuration(global)?;
+    let mut db = open_database(global, &config)?;
+
+    let project = project.trim();
+    let task = task.map(str::trim);
+    let scope = match task {
+        Some(task) => format!("project {project}, task {task}"),
+        None => format!("project {project}"),
+    };
+
+    if dry_run {
+        let filter = ReservationFilter {
+            project: Some(project.to_string()),
+            task: task.map(String::from),
+            ..ReservationFilter::default()
+        };
+        let matching = Database::list_reservations_filtered(db.connection(), &filter)
+            .map_err(CliError::from)?;
+        if !global.quiet {
+            eprintln!(
+                "Dry run - would renew {} reservation(s) for {scope}",
+                matching.len()
+            );
+        }
+        return Ok(());
+    }
+
+    let tx = db.begin_transaction().map_err(CliError::from)?;
+    let touched =
+        Database::touch_reservations_by_project(&tx, project, task).map_err(CliError::from)?;
+    tx.commit()
+        .map_err(trop::Error::from)
+        .map_err(CliError::from)?;
+
+    if !global.quiet {
+        eprintln!("Renewed {touched} reservation(s) for {scope}");
+    }
+
+    Ok(())
+}
@@ -4,11 +4,26 @@
 //! for a directory with optional metadata and constraints.
 
 use crate::error::CliError;
-use crate::utils::{load_configuration, open_database, resolve_path, GlobalOptions};
-use clap::Args;
-use std::path::PathBuf;
-use trop::config::{PortConfig, DEFAULT_MIN_PORT};
-use trop::{PlanExecutor, Port, ReservationKey, ReserveOptions, ReservePlan};
+use crate::utils::{
+    check_output_file, emit_output, format_timestamp, load_configuration_with_port_overrides,
+    normalize_path, open_database_with_wait, open_dry_run_database, parse_duration,
+    parse_exclude_args, resolve_path, retry_on_lock, stderr_color_enabled, warning_prefix,
+    GlobalOptions, SKIP_OCCUPANCY_HELP,
+};
+use clap::{Args, ValueEnum};
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use trop::config::{normalize_exclusions, Config, PortRangeOverrides};
+use trop::output::{ExportFormatter, OutputFormatter, ShellType};
+use trop::port::occupancy::{OccupancyCheckConfig, PortOccupancyChecker, SystemOccupancyChecker};
+use trop::reservation::MAX_NOTE_LENGTH;
+use trop::{
+    AllocationStrategy, Database, PhaseTimings, PlanExecutor, Port, Reservation, ReservationKey,
+    ReserveOptions, ReservePlan,
+};
 
 /// Reserve a port for a directory.
 #[derive(Args)]
@@ -18,10 +33,14 @@ pub struct ReserveCommand {
     #[arg(long, value_name = "PATH", env = "TROP_PATH")]
     pub path: Option<PathBuf>,
 
-    /// Service tag
+    /// Service tag (default: `default_tag` from configuration; "" for untagged)
     #[arg(long, value_name = "TAG")]
     pub tag: Option<String>,
 
+    /// Reserve untagged, ignoring any configured default tag
+    #[arg(long, conflicts_with = "tag")]
+    pub no_tag: bool,
+
     /// Project identifier
     #[arg(long, value_name = "PROJECT", env = "TROP_PROJECT")]
     pub project: Option<String>,
@@ -34,18 +53,59 @@ pub struct ReserveCommand {
     #[arg(long, value_name = "PORT")]
     pub port: Option<String>,
 
-    /// Minimum acceptable port
-    #[arg(long, value_name = "MIN", env = "TROP_PORT_MIN")]
+    /// Try this port first, falling back to any free port in the range
+    #[arg(long, value_name = "PORT", conflicts_with = "port")]
+    pub preferred: Option<String>,
+
+    /// Reserve N consecutive ports, one reservation each, tagged
+    /// `<prefix>-0` through `<prefix>-<N-1>`
+    #[arg(long, value_name = "N", conflicts_with_all = ["port", "preferred", "print_export"])]
+    pub count: Option<usize>,
+
+    /// Tag prefix for the ports reserved with --count (default: the tag, or "port")
+    #[arg(long, value_name = "PREFIX", requires = "count", conflicts_with_all = ["tag", "no_tag"])]
+    pub tag_prefix: Option<String>,
+
+    /// Minimum acceptable port (overrides `TROP_PORT_MIN` and the configuration)
+    #[arg(long, value_name = "MIN")]
     pub min: Option<String>,
 
-    /// Maximum acceptable port
-    #[arg(long, value_name = "MAX", env = "TROP_PORT_MAX")]
+    /// Maximum acceptable port (overrides `TROP_PORT_MAX` and the configuration)
+    #[arg(long, value_name = "MAX")]
     pub max: Option<String>,
 
+    /// Maximum acceptable port as an offset from the minimum (max = min + OFFSET);
+    /// replaces a maximum from `TROP_PORT_MAX` or the configuration
+    #[arg(long, value_name = "OFFSET")]
+    pub max_offset: Option<u16>,
+
+    /// Order in which to try ports (default: `allocation_strategy` from configuration, else lowest)
+    #[arg(long, value_enum, value_name = "STRATEGY", ignore_case = true)]
+    pub strategy: Option<StrategyArg>,
+
+    /// Expire the reservation after this long, e.g. `30m`, `2d`, or `1h30m`
+    #[arg(long, value_name = "DURATION")]
+    pub ttl: Option<String>,
+
+    /// Free-text note describing the reservation (replaces any existing note; "" clears it)
+    #[arg(long, value_name = "TEXT")]
+    pub note: Option<String>,
+
+    /// Cleanup priority; reservations above `cleanup.protect_priority` are
+    /// kept by expire and autoclean unless forced (default: 0)
+    #[arg(long, value_name = "N", allow_hyphen_values = true)]
+    pub priority: Option<i32>,
+
     /// Overwrite existing reservation
     #[arg(long)]
     pub overwrite: bool,
 
+    /// Keep an existing reservation's port but replace its project, task,
+    /// and note with the ones given, clearing a project or task left out
+    /// (re-reserving without this returns the existing port unchanged)
+    #[arg(long)]
+    pub replace: bool,
+
     /// Ignore if preferred port is occupied
     #[arg(long)]
     pub ignore_occupied: bool,
@@ -54,6 +114,11 @@ pub struct ReserveCommand {
     #[arg(long)]
     pub ignore_exclusions: bool,
 
+    /// Also exclude a port or range (START..END) for this invocation only;
+    /// repeatable, never saved to config
+    #[arg(long, value_name = "PORT|START..END", alias = "exclude-range")]
+    pub exclude: Vec<String>,
+
     /// Force operation (overrides all protections)
     #[arg(long)]
     pub force: bool,
@@ -90,9 +155,17 @@ pub struct ReserveCommand {
     #[arg(long)]
     pub dry_run: bool,
 
-    /// Skip occupancy check
-    #[arg(long)]
-    pub skip_occupancy_check: bool,
+    /// Keep retrying for up to SECONDS while another process holds the database lock
+    #[arg(long, value_name = "SECONDS")]
+    pub wait: Option<u64>,
+
+    #[arg(long, alias = "skip-occupancy-check", help = SKIP_OCCUPANCY_HELP)]
+    pub skip_occupancy: bool,
+
+    /// Skip ports in use by other processes even if configuration disables
+    /// occupancy checks
+    #[arg(long, conflicts_with = "skip_occupancy")]
+    pub check_occupancy: bool,
 
     /// Skip TCP checks
     #[arg(long)]
@@ -113,20 +186,123 @@ pub struct ReserveCommand {
     /// Check all network interfaces
     #[arg(long)]
     pub check_all_interfaces: bool,
+
+    /// Only treat a port as occupied if it is bound on this address (repeatable)
+    #[arg(long = "interface", value_name = "ADDR")]
+    pub interfaces: Vec<IpAddr>,
+
+    /// Output the reservation as a JSON object (same as `--format json`)
+    #[arg(long)]
+    pub json: bool,
+
+    /// Output format: `plain` prints the bare port, `json` the full reservation
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        ignore_case = true,
+        conflicts_with = "json"
+    )]
+    pub format: Option<ReserveFormat>,
+
+    /// Include occupancy of the reserved port in the JSON output
+    #[arg(long)]
+    pub include_occupancy: bool,
+
+    /// Also print a shell export line for the reserved port
+    #[arg(long, conflicts_with = "json")]
+    pub print_export: bool,
+
+    /// Environment variable name for the export line (default: derived from the tag)
+    #[arg(long, value_name = "VAR", requires = "print_export")]
+    pub env_name: Option<String>,
+
+    /// Shell type for the export line (auto-detect if not specified)
+    #[arg(long, value_name = "SHELL", requires = "print_export")]
+    pub shell: Option<String>,
+
+    /// Print only the export line, omitting the bare port
+    #[arg(long, requires = "print_export")]
+    pub export_only: bool,
+
+    /// Write the formatted output to a file instead of stdout (overwriting requires --force)
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+
+    /// Reserve a port for each newline-separated path read from stdin, in a
+    /// single transaction, printing `path<TAB>port` per reservation
+    #[arg(
+        long,
+        conflicts_with_all = ["path", "count", "port", "json", "format", "print_export", "dry_run"]
+    )]
+    pub stdin_paths: bool,
+}
+
+/// Output format for the `--format` flag.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReserveFormat {
+    /// The bare port number, for shell interpolation
+    Plain,
+    /// The resulting reservation as a JSON object
+    Json,
+}
+
+/// Allocation strategy for the `--strategy` flag.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StrategyArg {
+    /// Lowest available port
+    Lowest,
+    /// Highest available port
+    Highest,
+    /// Random available port
+    Random,
+}
+
+impl From<StrategyArg> for AllocationStrategy {
+    fn from(strategy: StrategyArg) -> Self {
+        match strategy {
+            StrategyArg::Lowest => Self::Lowest,
+            StrategyArg::Highest => Self::Highest,
+            StrategyArg::Random => Self::Random,
+        }
+    }
 }
 
 impl ReserveCommand {
     /// Execute the reserve command.
     pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
-        // 1. Resolve path (use CWD if not specified, canonicalize if implicit)
-        let path = resolve_path(self.path)?;
-
-        // 2. Build ReservationKey
-        let key = ReservationKey::new(path, self.tag)
-            .map_err(|e| CliError::InvalidArguments(e.to_string()))?;
-
-        // 3. Load configuration
-        let config = load_configuration(global)?;
+        // 1. Load configuration, with the port range flags layered on top
+        let port_overrides = PortRangeOverrides {
+            min: self.min.as_deref().map(parse_port_string).transpose()?,
+            max: self.max.as_deref().map(parse_port_string).transpose()?,
+            max_offset: self.max_offset,
+        };
+        if let (Some(min_val), Some(max_val)) = (port_overrides.min, port_overrides.max) {
+            if min_val > max_val {
+                return Err(CliError::InvalidArguments(format!(
+                    "Invalid port range: min ({min_val}) must be less than or equal to max ({max_val})"
+                )));
+            }
+        }
+        let mut timings = PhaseTimings::new(global.verbose);
+        let config = timings.time("config load", || {
+            load_configuration_with_port_overrides(global, port_overrides)
+        })?;
+
+        // 2. Build the ReservationKey, falling back to the configured default
+        //    tag, for the path (CWD if not specified) or each path on stdin.
+        //    For a block the tag is the prefix of the per-port tags
+        let tag = resolve_tag(self.tag.or(self.tag_prefix), self.no_tag, &config);
+        let batch_keys = if self.stdin_paths {
+            Some(read_batch_keys(std::io::stdin().lock(), tag.as_ref())?)
+        } else {
+            None
+        };
+        let key = match batch_keys.as_ref().and_then(|keys| keys.first()) {
+            Some((_, first)) => first.clone(),
+            None => ReservationKey::new(resolve_path(self.path)?, tag)
+                .map_err(|e| CliError::InvalidArguments(e.to_string()))?,
+        };
 
         // 4. Parse and validate port arguments
         let port = self
@@ -138,49 +314,91 @@ impl ReserveCommand {
             .transpose()
             .map_err(|e| CliError::InvalidArguments(e.to_string()))?;
 
-        let min = self.min.as_deref().map(parse_port_string).transpose()?;
+        let preferred = self
+            .preferred
+            .as_deref()
+            .map(parse_port_string)
+            .transpose()?
+            .map(Port::try_from)
+            .transpose()
+            .map_err(|e| CliError::InvalidArguments(e.to_string()))?;
 
-        let max = self.max.as_deref().map(parse_port_string).transpose()?;
+        let ttl = self.ttl.as_deref().map(parse_duration).transpose()?;
 
-        // 5. Validate port range (min <= max)
-        if let (Some(min_val), Some(max_val)) = (min, max) {
-            if min_val > max_val {
+        let exclusions = parse_exclude_args(&self.exclude)?;
+
+        let count = self.count.unwrap_or(1);
+        if count == 0 {
+            return Err(CliError::InvalidArguments(
+                "--count must be at least 1".to_string(),
+            ));
+        }
+
+        if let Some(note) = &self.note {
+            if note.chars().count() > MAX_NOTE_LENGTH {
                 return Err(CliError::InvalidArguments(format!(
-                    "Invalid port range: min ({min_val}) must be less than or equal to max ({max_val})"
+                    "Note is too long: must be at most {MAX_NOTE_LENGTH} characters"
                 )));
             }
         }
 
-        // 6. Modify config for port range if min/max specified
-        let mut config = config;
-        if min.is_some() || max.is_some() {
-            // Override config port range with CLI arguments
-            let mut port_config = config.ports.clone().unwrap_or(PortConfig {
-                min: DEFAULT_MIN_PORT,
-                max: Some(trop::config::DEFAULT_MAX_PORT),
-                max_offset: None,
-            });
-            if let Some(min) = min {
-                port_config.min = min;
-            }
-            if let Some(max) = max {
-                port_config.max = Some(max);
-                port_config.max_offset = None;
+        // Resolve output options up front so bad arguments fail before reserving
+        let json = self.json || self.format == Some(ReserveFormat::Json);
+        if self.include_occupancy && !json {
+            return Err(CliError::InvalidArguments(
+                "--include-occupancy requires --format json".to_string(),
+            ));
+        }
+        if self.print_export && json {
+            return Err(CliError::InvalidArguments(
+                "--print-export cannot be used with --format json".to_string(),
+            ));
+        }
+        if let Some(ref output_file) = self.output_file {
+            check_output_file(output_file, self.force)?;
+        }
+        if let Some(name) = &self.env_name {
+            if !is_valid_env_name(name) {
+                return Err(CliError::InvalidArguments(format!(
+                    "Invalid environment variable name '{name}': must contain only alphanumeric characters and underscores, and start with a letter or underscore"
+                )));
             }
-            config.ports = Some(port_config);
+        }
+        let export_shell = if self.print_export {
+            Some(match self.shell.as_deref() {
+                Some(shell) => ShellType::from_string(shell).map_err(CliError::from)?,
+                None => ShellType::detect().map_err(CliError::from)?,
+            })
+        } else {
+            None
+        };
+
+        let mut config = config;
+
+        // Ad-hoc exclusions layer on top of the configured ones; the config
+        // is never written back, so they only apply to this invocation
+        if !exclusions.is_empty() {
+            let excluded = config.excluded_ports.get_or_insert_with(Vec::new);
+            excluded.extend(exclusions);
+            normalize_exclusions(excluded);
         }
 
-        if self.skip_occupancy_check
+        if self.skip_occupancy
+            || self.check_occupancy
             || self.skip_tcp
             || self.skip_udp
             || self.skip_ipv4
             || self.skip_ipv6
             || self.check_all_interfaces
+            || !self.interfaces.is_empty()
         {
             let mut occupancy = config.occupancy_check.clone().unwrap_or_default();
-            if self.skip_occupancy_check {
+            if self.skip_occupancy {
                 occupancy.skip = Some(true);
             }
+            if self.check_occupancy {
+                occupancy.skip = Some(false);
+            }
             if self.skip_tcp {
                 occupancy.skip_tcp = Some(true);
             }
@@ -196,61 +414,182 @@ impl ReserveCommand {
             if self.check_all_interfaces {
                 occupancy.check_all_interfaces = Some(true);
             }
+            if !self.interfaces.is_empty() {
+                occupancy.interfaces = Some(self.interfaces.clone());
+            }
             config.occupancy_check = Some(occupancy);
         }
 
         // 7. Build library ReserveOptions
-        let options = ReserveOptions::new(key, port)
+        let options = ReserveOptions::new(key.clone(), port)
+            .with_preferred_port(preferred)
             .with_project(self.project)
             .with_task(self.task)
             .with_ignore_occupied(self.ignore_occupied)
             .with_ignore_exclusions(self.ignore_exclusions)
+            .with_strategy(self.strategy.map(AllocationStrategy::from))
+            .with_ttl(ttl)
+            .with_note(self.note)
+            .with_priority(self.priority)
+            .with_replace(self.replace)
             .with_force(self.force)
             .with_allow_unrelated_path(self.allow_unrelated_path)
             .with_allow_project_change(self.allow_project_change || self.allow_change)
             .with_allow_task_change(self.allow_task_change || self.allow_change)
             .with_disable_autoprune(self.disable_autoprune || self.disable_autoclean)
-            .with_disable_autoexpire(self.disable_autoexpire || self.disable_autoclean);
-
-        // 8. Handle dry-run mode
+            .with_disable_autoexpire(self.disable_autoexpire || self.disable_autoclean)
+            .with_count(count);
+        let block_keys = if count > 1 {
+            Some(options.block_keys().map_err(CliError::from)?)
+        } else {
+            None
+        };
+
+        // 8. Handle dry-run mode: plan inside a transaction that is rolled
+        // back, without running the pre-reserve hook
         if self.dry_run {
-            // In dry-run mode, just print what would happen without opening database
+            config.pre_reserve_hook = None;
+            let mut db = open_dry_run_database(global, &config)?;
+            let tx = db.begin_transaction().map_err(CliError::from)?;
+            let plan = ReservePlan::new(options, &config)
+                .build_plan(&tx)
+                .map_err(CliError::from)?;
+            drop(tx);
+
             if !global.quiet {
-                eprintln!("Dry run - would perform the following actions:");
-                eprintln!("  1. Reserve port");
+                eprintln!("Dry run - would make the following changes:");
+                for line in plan.render_diff().lines() {
+                    eprintln!("  {line}");
+                }
             }
             return Ok(());
         }
 
         // 8. Open database
-        let mut db = open_database(global, &config)?;
-
-        // 9. Begin transaction - wraps entire operation (planning + execution)
-        let tx = db.begin_transaction().map_err(CliError::from)?;
-
-        // 10. Build plan (inside transaction - sees consistent view)
-        let plan = ReservePlan::new(options, &config)
-            .build_plan(&tx)
-            .map_err(CliError::from)?;
-
-        // 11. Execute plan (inside same transaction)
-        let mut executor = PlanExecutor::new(&tx);
-        let result = executor.execute(&plan).map_err(CliError::from)?;
-
-        // 12. Commit transaction - all or nothing
-        tx.commit()
-            .map_err(trop::Error::from)
-            .map_err(CliError::from)?;
+        let wait = self.wait.map(Duration::from_secs);
+        let mut db = timings.time("database open", || {
+            open_database_with_wait(global, &config, wait)
+        })?;
+
+        if let Some(keys) = batch_keys {
+            let mut failed_line = None;
+            let batch = retry_on_lock(wait, || {
+                reserve_batch(
+                    &mut db,
+                    &keys,
+                    &options,
+                    &config,
+                    &mut timings,
+                    &mut failed_line,
+                )
+            })
+            .map_err(|e| match (failed_line, e) {
+                (_, CliError::Timeout) => CliError::Timeout,
+                (Some(line), e) => CliError::OnLine(line, Box::new(e)),
+                (None, e) => e,
+            })?;
+            timings.report(&global.logger);
+
+            let lines: Vec<String> = batch
+                .reserved
+                .iter()
+                .map(|(key, port)| format!("{}\t{port}", key.path.display()))
+                .collect();
+            if !lines.is_empty() {
+                emit_output(&lines.join("\n"), self.output_file.as_deref())?;
+            }
+            if !global.quiet {
+                let prefix = warning_prefix(stderr_color_enabled(global.color));
+                for warning in &batch.warnings {
+                    eprintln!("{prefix} {warning}");
+                }
+            }
+            return Ok(());
+        }
 
-        // 11. Output just the port number (shell-friendly) to stdout
-        if let Some(port) = result.port {
-            println!("{}", port.value());
+        // 9. Plan and execute inside one transaction - all or nothing. With
+        //    --wait, the whole attempt is retried while the database is locked
+        let result = retry_on_lock(wait, || {
+            let tx = db.begin_transaction()?;
+            let plan = timings.time("plan build", || {
+                ReservePlan::new(options.clone(), &config).build_plan(&tx)
+            })?;
+            let result = PlanExecutor::new(&tx)
+                .with_timings(&mut timings)
+                .execute(&plan)?;
+            tx.commit()?;
+            Ok(result)
+        })?;
+        timings.report(&global.logger);
+
+        // 11. Output the result: a JSON object, or the port number and/or an export line
+        let mut output = Vec::new();
+        if let Some(keys) = block_keys {
+            // A block prints one port per line, or a JSON array
+            let mut reservations = Vec::with_capacity(keys.len());
+            for key in &keys {
+                reservations.push(
+                    Database::get_reservation(db.connection(), key)
+                        .map_err(CliError::from)?
+                        .ok_or_else(|| {
+                            CliError::Library(trop::Error::NotFound {
+                                resource: format!("reservation for {key}"),
+                            })
+                        })?,
+                );
+            }
+            if json {
+                let json: Vec<serde_json::Value> = reservations
+                    .iter()
+                    .map(|reservation| {
+                        let mut json = reservation_json(reservation);
+                        if self.include_occupancy {
+                            json["occupancy"] = occupancy_json(reservation.port(), &config);
+                        }
+                        json
+                    })
+                    .collect();
+                output.push(serde_json::to_string_pretty(&json).map_err(|e| {
+                    CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e))
+                })?);
+            } else {
+                output.extend(reservations.iter().map(|r| r.port().value().to_string()));
+            }
+        } else if json {
+            let reservation = Database::get_reservation(db.connection(), &key)
+                .map_err(CliError::from)?
+                .ok_or_else(|| {
+                    CliError::Library(trop::Error::NotFound {
+                        resource: format!("reservation for {key}"),
+                    })
+                })?;
+            let mut json = reservation_json(&reservation);
+            if self.include_occupancy {
+                json["occupancy"] = occupancy_json(reservation.port(), &config);
+            }
+            output
+                .push(serde_json::to_string_pretty(&json).map_err(|e| {
+                    CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e))
+                })?);
+        } else if let Some(port) = result.port {
+            if !self.export_only {
+                output.push(port.value().to_string());
+            }
+            if let Some(shell) = export_shell {
+                output.push(export_line(port, key.tag.as_deref(), self.env_name, shell)?);
+            }
+        }
+        if !output.is_empty() {
+            emit_output(&output.join("\n"), self.output_file.as_deref())?;
         }
 
         // 11. Print warnings to stderr if any
         if !global.quiet && !result.warnings.is_empty() {
             for warning in &result.warnings {
-                eprintln!("Warning: {warning}");
+                eprintln!(
+                    "{} {warning}",
+                    warning_prefix(stderr_color_enabled(global.color))
+                );
             }
         }
 
@@ -258,6 +597,166 @@ impl ReserveCommand {
     }
 }
 
+/// Read newline-separated paths for `--stdin-paths` and build their keys,
+/// each with its 1-based line number.
+///
+/// Blank lines are skipped. A path that cannot be resolved aborts the whole
+/// batch with an error naming its line.
+fn read_batch_keys(
+    input: impl BufRead,
+    tag: Option<&String>,
+) -> Result<Vec<(usize, ReservationKey)>, CliError> {
+    let mut keys = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let key = normalize_path(Path::new(&line))
+            .and_then(|path| {
+                ReservationKey::new(path, tag.cloned())
+                    .map_err(|e| CliError::InvalidArguments(e.to_string()))
+            })
+            .map_err(|e| {
+                CliError::InvalidArguments(format!("Invalid path on line {}: {e}", index + 1))
+            })?;
+        keys.push((index + 1, key));
+    }
+
+    if keys.is_empty() {
+        return Err(CliError::InvalidArguments(
+            "No paths read from stdin".to_string(),
+        ));
+    }
+    Ok(keys)
+}
+
+/// Outcome of a `--stdin-paths` batch.
+struct BatchResult {
+    /// Each key with its port, in input order.
+    reserved: Vec<(ReservationKey, Port)>,
+    /// Warnings from every reservation in the batch.
+    warnings: Vec<String>,
+}
+
+/// Reserve a port for each key in one transaction, reusing the single
+/// reservation plan per key under a shared executor.
+///
+/// Each plan is built after the previous one has executed, so later keys
+/// see the ports taken by earlier ones. If planning or executing a key
+/// fails, `failed_line` is left holding that key's input line.
+fn reserve_batch(
+    db: &mut Database,
+    keys: &[(usize, ReservationKey)],
+    options: &ReserveOptions,
+    config: &Config,
+    timings: &mut PhaseTimings,
+    failed_line: &mut Option<usize>,
+) -> trop::Result<BatchResult> {
+    *failed_line = None;
+    let tx = db.begin_transaction()?;
+    let mut reserved = Vec::with_capacity(keys.len());
+    let mut warnings = Vec::new();
+    {
+        let mut executor = PlanExecutor::new(&tx).with_timings(timings);
+        for (line, key) in keys {
+            *failed_line = Some(*line);
+            let mut options = options.clone();
+            options.key = key.clone();
+            let plan = ReservePlan::new(options, config).build_plan(&tx)?;
+            let result = executor.execute(&plan)?;
+            let port = result.port.ok_or_else(|| trop::Error::NotFound {
+                resource: format!("port for {key}"),
+            })?;
+            reserved.push((key.clone(), port));
+            warnings.extend(result.warnings);
+        }
+    }
+    *failed_line = None;
+    tx.commit()?;
+    Ok(BatchResult { reserved, warnings })
+}
+
+/// Determine the tag for a reservation.
+///
+/// An explicit `--tag` wins (an empty value means untagged), `--no-tag`
+/// forces untagged, and otherwise the configured `default_tag` applies.
+/// The configured `tag_case` is applied to whichever tag is chosen.
+fn resolve_tag(tag: Option<String>, no_tag: bool, config: &Config) -> Option<String> {
+    if no_tag {
+        return None;
+    }
+    let tag = match tag {
+        Some(tag) if tag.trim().is_empty() => None,
+        Some(tag) => Some(tag),
+        None => config.default_tag.clone(),
+    };
+    let tag_case = config.tag_case.unwrap_or_default();
+    tag.map(|tag| tag_case.normalize(tag))
+}
+
+/// Format a shell export line for a reserved port.
+///
+/// The variable is `env_name` if given, otherwise it is derived from the tag
+/// the same way `reserve-group` derives names from service tags (`PORT` for
+/// untagged reservations).
+fn export_line(
+    port: Port,
+    tag: Option<&str>,
+    env_name: Option<String>,
+    shell: ShellType,
+) -> Result<String, CliError> {
+    let service = tag.unwrap_or("port").to_string();
+    let env_mappings = env_name.map(|name| HashMap::from([(service.clone(), name)]));
+
+    ExportFormatter::new(shell, env_mappings)
+        .format(&HashMap::from([(service, port)]))
+        .map_err(CliError::from)
+}
+
+/// Check that a name is usable as a shell environment variable.
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Build the JSON representation of a reservation.
+fn reservation_json(reservation: &Reservation) -> serde_json::Value {
+    serde_json::json!({
+        "port": reservation.port().value(),
+        "path": reservation.key().path.display().to_string(),
+        "tag": reservation.key().tag,
+        "project": reservation.project(),
+        "task": reservation.task(),
+        "created_at": format_timestamp(reservation.created_at()),
+        "last_used_at": format_timestamp(reservation.last_used_at()),
+        "expires_at": reservation.expires_at().map(format_timestamp),
+        "note": reservation.note(),
+        "priority": reservation.priority(),
+    })
+}
+
+/// Check the occupancy of a reserved port and describe it as JSON.
+///
+/// The status is `"occupied"`, `"free"`, `"skipped"` (occupancy checks are
+/// disabled by configuration), or `"error"` (the check failed and the
+/// configured error policy is `fail`).
+fn occupancy_json(port: Port, config: &Config) -> serde_json::Value {
+    let check_config = OccupancyCheckConfig::from_config(config);
+    if check_config.skips_all() {
+        return serde_json::json!({ "status": "skipped" });
+    }
+
+    match SystemOccupancyChecker.check_occupied(port, &check_config) {
+        Ok(true) => serde_json::json!({ "status": "occupied" }),
+        Ok(false) => serde_json::json!({ "status": "free" }),
+        Err(e) => serde_json::json!({ "status": "error", "error": e.to_string() }),
+    }
+}
+
 /// Parse a port number from a string, validating it's in the valid range (1-65535).
 ///
 /// Returns an error if the string cannot be parsed as a number or if the number
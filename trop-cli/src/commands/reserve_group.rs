@@ -4,13 +4,21 @@
 //! ports for a group of services defined in a configuration file.
 
 use crate::error::CliError;
-use crate::utils::{format_allocations, load_configuration, open_database, GlobalOptions};
+use crate::utils::{
+    check_output_file, emit_output, format_allocations, load_configuration, merge_into_dotenv_file,
+    open_database_with_wait, parse_exclude_args, prefers_human_output, retry_on_lock,
+    stderr_color_enabled, warning_prefix, GlobalOptions,
+};
 use clap::{Args, ValueEnum};
-use std::path::PathBuf;
-use trop::config::ConfigLoader;
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use trop::config::{ConfigLoader, TagCase};
+use trop::operations::hook::PreReserveHook;
 use trop::operations::{ReserveGroupOptions, ReserveGroupPlan};
-use trop::output::{OutputFormat, ShellType};
-use trop::PlanExecutor;
+use trop::output::{validate_env_prefix, OutputFormat, ShellType};
+use trop::{Config, PhaseTimings, PlanExecutor, Port};
 
 /// Reserve ports for a group of services defined in a config file.
 #[derive(Args)]
@@ -23,14 +31,28 @@ pub struct ReserveGroupCommand {
     #[arg(long, env = "TROP_TASK")]
     pub task: Option<String>,
 
-    /// Output format
-    #[arg(long, value_enum, default_value = "export")]
+    /// Output format; `auto` is human on a terminal and JSON when piped or
+    /// written to a file
+    #[arg(long, value_enum, default_value = "auto")]
     pub format: OutputFormatArg,
 
     /// Shell type for export format (auto-detect if not specified)
     #[arg(long)]
     pub shell: Option<String>,
 
+    /// Wrap values in double quotes (dotenv format only)
+    #[arg(long)]
+    pub quote: bool,
+
+    /// Prepend PREFIX to every variable name in export and dotenv output
+    /// (e.g. MYAPP_ turns WEB_PORT into MYAPP_WEB_PORT)
+    #[arg(long, value_name = "PREFIX")]
+    pub env_prefix: Option<String>,
+
+    /// Format of the status summary written to stderr
+    #[arg(long, value_enum, default_value = "human")]
+    pub summary: SummaryFormat,
+
     /// Force operation (overrides all protections)
     #[arg(long)]
     pub force: bool,
@@ -51,20 +73,70 @@ pub struct ReserveGroupCommand {
     #[arg(long, env = "TROP_ALLOW_CHANGE")]
     pub allow_change: bool,
 
+    /// Also exclude a port or range (START..END) for this invocation only;
+    /// repeatable, never saved to config
+    #[arg(long, value_name = "PORT|START..END", alias = "exclude-range")]
+    pub exclude: Vec<String>,
+
     /// Perform a dry run
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Keep retrying for up to SECONDS while another process holds the database lock
+    #[arg(long, value_name = "SECONDS")]
+    pub wait: Option<u64>,
+
+    /// Write the formatted output to a file instead of stdout (overwriting requires --force)
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+
+    /// Update the services' variables in this dotenv file instead of printing,
+    /// keeping every other line (implies dotenv format)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["output_file", "format", "shell"])]
+    pub merge_into: Option<PathBuf>,
+
+    /// Reservation group to reserve when the config defines several
+    #[arg(long, value_name = "NAME")]
+    pub group: Option<String>,
+
+    /// Store each service's reservation under PREFIX-<service> (variable
+    /// names still come from the service name)
+    #[arg(long, value_name = "PREFIX")]
+    pub tag_prefix: Option<String>,
+
+    /// Store each service's reservation under <service>-SUFFIX (variable
+    /// names still come from the service name)
+    #[arg(long, value_name = "SUFFIX")]
+    pub tag_suffix: Option<String>,
+
+    /// Reserve only these services (comma-separated or repeated)
+    #[arg(
+        long,
+        value_name = "SERVICE,...",
+        value_delimiter = ',',
+        conflicts_with = "except"
+    )]
+    pub only: Vec<String>,
+
+    /// Reserve every service except these (comma-separated or repeated)
+    #[arg(long, value_name = "SERVICE,...", value_delimiter = ',')]
+    pub except: Vec<String>,
 }
 
 /// Output format argument for clap.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 #[value(rename_all = "lowercase")]
 pub enum OutputFormatArg {
+    /// Human on a terminal, JSON otherwise
+    Auto,
     /// Shell-specific export statements
     Export,
     /// JSON format
     Json,
+    /// YAML format
+    Yaml,
     /// Dotenv (.env file) format
+    #[value(alias = "env")]
     Dotenv,
     /// Human-readable format
     Human,
@@ -72,8 +144,34 @@ pub enum OutputFormatArg {
 
 impl OutputFormatArg {
     /// Convert to `OutputFormat`, detecting shell type if needed.
-    pub fn to_output_format(self, shell_arg: Option<&str>) -> Result<OutputFormat, CliError> {
+    ///
+    /// `auto` resolves to human output when it goes to a terminal and to
+    /// JSON when stdout is piped or `output_file` is set; with `--shell` it
+    /// means export statements for that shell. `quote` is only
+    /// meaningful for dotenv output and is rejected for any other format.
+    pub fn to_output_format(
+        self,
+        shell_arg: Option<&str>,
+        quote: bool,
+        output_file: Option<&Path>,
+    ) -> Result<OutputFormat, CliError> {
+        if quote && !matches!(self, Self::Dotenv) {
+            return Err(CliError::InvalidArguments(
+                "--quote only applies to --format dotenv".to_string(),
+            ));
+        }
         match self {
+            Self::Auto => {
+                // An explicit --shell only makes sense for export statements
+                let resolved = if shell_arg.is_some() {
+                    Self::Export
+                } else if prefers_human_output(output_file, std::io::stdout().is_terminal()) {
+                    Self::Human
+                } else {
+                    Self::Json
+                };
+                resolved.to_output_format(shell_arg, quote, output_file)
+            }
             Self::Export => {
                 let shell = if let Some(shell_str) = shell_arg {
                     ShellType::from_string(shell_str).map_err(CliError::from)?
@@ -83,12 +181,80 @@ impl OutputFormatArg {
                 Ok(OutputFormat::Export(shell))
             }
             Self::Json => Ok(OutputFormat::Json),
-            Self::Dotenv => Ok(OutputFormat::Dotenv),
+            Self::Yaml => Ok(OutputFormat::Yaml),
+            Self::Dotenv => Ok(OutputFormat::Dotenv { quote }),
             Self::Human => Ok(OutputFormat::Human),
         }
     }
 }
 
+/// Format of the status summary written to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum SummaryFormat {
+    /// Human-readable status lines
+    Human,
+    /// A single JSON object
+    Json,
+    /// No summary
+    None,
+}
+
+/// Write the status summary for a group reservation to stderr.
+///
+/// The human format prints the existing status lines followed by any
+/// warnings. The JSON format emits a single object (including warnings) so
+/// stderr stays machine-parseable. `none` suppresses the summary but still
+/// prints warnings, whose prefix is colored when `color` is set.
+pub fn print_group_summary(
+    format: SummaryFormat,
+    config_path: &Path,
+    discovered: bool,
+    allocated_ports: &HashMap<String, Port>,
+    warnings: &[String],
+    color: bool,
+) -> Result<(), CliError> {
+    match format {
+        SummaryFormat::Human => {
+            if discovered {
+                eprintln!("Discovered config: {}", config_path.display());
+            }
+            eprintln!(
+                "Reserved {} ports for services: {}",
+                allocated_ports.len(),
+                allocated_ports
+                    .keys()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        SummaryFormat::Json => {
+            let services: serde_json::Map<String, serde_json::Value> = allocated_ports
+                .iter()
+                .map(|(tag, port)| (tag.clone(), serde_json::json!(port.value())))
+                .collect();
+            let summary = serde_json::json!({
+                "config": config_path.display().to_string(),
+                "reserved": allocated_ports.len(),
+                "services": services,
+                "warnings": warnings,
+            });
+            let json = serde_json::to_string(&summary)
+                .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+            eprintln!("{json}");
+            return Ok(());
+        }
+        SummaryFormat::None => {}
+    }
+
+    for warning in warnings {
+        eprintln!("{} {warning}", warning_prefix(color));
+    }
+
+    Ok(())
+}
+
 impl ReserveGroupCommand {
     /// Execute the reserve-group command.
     pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
@@ -107,13 +273,30 @@ impl ReserveGroupCommand {
             )));
         }
 
+        if let Some(ref output_file) = self.output_file {
+            check_output_file(output_file, self.force)?;
+        }
+
+        if let Some(ref prefix) = self.env_prefix {
+            validate_env_prefix(prefix).map_err(|e| CliError::InvalidArguments(e.to_string()))?;
+        }
+
+        let exclusions = parse_exclude_args(&self.exclude)?;
+        let group = self.group;
+
         // 2. Build ReserveGroupOptions
         let options = ReserveGroupOptions::new(self.config_path.clone())
             .with_task(self.task)
             .with_force(self.force)
             .with_allow_unrelated_path(self.allow_unrelated_path)
             .with_allow_project_change(self.allow_project_change || self.allow_change)
-            .with_allow_task_change(self.allow_task_change || self.allow_change);
+            .with_allow_task_change(self.allow_task_change || self.allow_change)
+            .with_exclusions(exclusions)
+            .with_group(group.clone())
+            .with_tag_prefix(self.tag_prefix)
+            .with_tag_suffix(self.tag_suffix)
+            .with_only(self.only)
+            .with_except(self.except);
 
         // 3. Handle dry-run mode
         if self.dry_run {
@@ -128,24 +311,30 @@ impl ReserveGroupCommand {
         }
 
         // 4. Load configuration and open database
-        let config = load_configuration(global)?;
-        let mut db = open_database(global, &config)?;
-
-        // 5. Begin transaction
-        let tx = db.begin_transaction().map_err(CliError::from)?;
-
-        // 6. Build plan (inside transaction)
-        let planner = ReserveGroupPlan::new(options).map_err(CliError::from)?;
-        let plan = planner.build_plan(&tx).map_err(CliError::from)?;
-
-        // 7. Execute plan (inside transaction)
-        let mut executor = PlanExecutor::new(&tx);
-        let result = executor.execute(&plan).map_err(CliError::from)?;
+        let mut timings = PhaseTimings::new(global.verbose);
+        let config = timings.time("config load", || load_configuration(global))?;
+        let wait = self.wait.map(Duration::from_secs);
+        let mut db = timings.time("database open", || {
+            open_database_with_wait(global, &config, wait)
+        })?;
 
-        // 8. Commit transaction
-        tx.commit()
-            .map_err(trop::Error::from)
-            .map_err(CliError::from)?;
+        // 5. Build and execute the plan inside one transaction, retrying the
+        //    whole attempt while the database is locked if --wait was given
+        let options = options
+            .with_tag_case(config.tag_case.unwrap_or_default())
+            .with_pre_reserve_hook(PreReserveHook::from_config(&config));
+        let result = retry_on_lock(wait, || {
+            let tx = db.begin_transaction()?;
+            let plan = timings.time("plan build", || {
+                ReserveGroupPlan::new(options.clone())?.build_plan(&tx)
+            })?;
+            let result = PlanExecutor::new(&tx)
+                .with_timings(&mut timings)
+                .execute(&plan)?;
+            tx.commit()?;
+            Ok(result)
+        })?;
+        timings.report(&global.logger);
 
         // 7. Extract allocated ports
         let allocated_ports = result.allocated_ports.ok_or_else(|| {
@@ -153,35 +342,86 @@ impl ReserveGroupCommand {
         })?;
 
         // 8. Format output based on selected format
-        let output_format = self.format.to_output_format(self.shell.as_deref())?;
+        let output_format = if self.merge_into.is_some() {
+            OutputFormat::Dotenv { quote: self.quote }
+        } else {
+            self.format.to_output_format(
+                self.shell.as_deref(),
+                self.quote,
+                self.output_file.as_deref(),
+            )?
+        };
 
         let output_config = ConfigLoader::load_file(&self.config_path).map_err(CliError::from)?;
-        let formatted_output =
-            format_allocations(&output_format, &allocated_ports, &output_config)?;
-
-        // 9. Print to stdout (machine-readable)
-        println!("{formatted_output}");
+        let tag_case = output_config
+            .tag_case
+            .or(config.tag_case)
+            .unwrap_or_default();
+        let service_ports = service_ports(
+            &allocated_ports,
+            &output_config,
+            group.as_deref(),
+            &options,
+            tag_case,
+        )?;
+        let formatted_output = format_allocations(
+            &output_format,
+            &service_ports,
+            &output_config,
+            group.as_deref(),
+            tag_case,
+            self.env_prefix.as_deref(),
+        )?;
 
-        // 10. Print status to stderr (human-readable, unless quiet)
-        if !global.quiet {
-            eprintln!(
-                "Reserved {} ports for services: {}",
-                allocated_ports.len(),
-                allocated_ports
-                    .keys()
-                    .map(|s| s.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
+        // 9. Write machine-readable output to stdout, --output-file, or --merge-into
+        if let Some(ref path) = self.merge_into {
+            merge_into_dotenv_file(path, &formatted_output)?;
+        } else {
+            emit_output(&formatted_output, self.output_file.as_deref())?;
         }
 
-        // 11. Print warnings to stderr if any
-        if !global.quiet && !result.warnings.is_empty() {
-            for warning in &result.warnings {
-                eprintln!("Warning: {warning}");
-            }
+        // 10. Print status and warnings to stderr (unless quiet)
+        if !global.quiet {
+            print_group_summary(
+                self.summary,
+                &self.config_path,
+                false,
+                &allocated_ports,
+                &result.warnings,
+                stderr_color_enabled(global.color),
+            )?;
         }
 
         Ok(())
     }
 }
+
+/// Re-key allocations from their stored (possibly decorated) tags to the
+/// service names, so output variable names ignore `--tag-prefix` and
+/// `--tag-suffix`.
+fn service_ports(
+    allocated_ports: &HashMap<String, Port>,
+    config: &Config,
+    group: Option<&str>,
+    options: &ReserveGroupOptions,
+    tag_case: TagCase,
+) -> Result<HashMap<String, Port>, CliError> {
+    if options.tag_prefix.is_none() && options.tag_suffix.is_none() {
+        return Ok(allocated_ports.clone());
+    }
+
+    let Some(ref groups) = config.reservations else {
+        return Ok(allocated_ports.clone());
+    };
+    let services = &groups.select(group).map_err(CliError::from)?.services;
+
+    Ok(services
+        .keys()
+        .filter_map(|service| {
+            let stored = tag_case.normalize(options.decorate_tag(service));
+            allocated_ports
+                .get(&stored)
+                .map(|port| (tag_case.normalize(service.clone()), *port))
+        })
+        .collect())
+}
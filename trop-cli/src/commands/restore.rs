@@ -0,0 +1,58 @@
+//! Restore command implementation.
+//!
+//! This module implements the `restore` command, which replaces the
+//! reservation database with a file written by `backup`. The backup is
+//! checked with `PRAGMA integrity_check` before anything is overwritten.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, resolve_database_path, GlobalOptions};
+use clap::Args;
+use std::path::PathBuf;
+
+/// Restore the reservation database from a backup file.
+#[derive(Args)]
+pub struct RestoreCommand {
+    /// Backup file to restore from
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Replace an existing database
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl RestoreCommand {
+    /// Execute the restore command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Validate arguments before touching the database
+        if !self.file.is_file() {
+            return Err(CliError::InvalidArguments(format!(
+                "Backup file not found: {}",
+                self.file.display()
+            )));
+        }
+
+        let db_path = resolve_database_path(global)?;
+        if db_path.exists() && !self.force {
+            return Err(CliError::InvalidArguments(format!(
+                "Database already exists: {} (use --force to overwrite)",
+                db_path.display()
+            )));
+        }
+
+        // 2. Open (or create) the database and swap in the backup
+        let config = load_configuration(global)?;
+        let mut db = open_database(global, &config)?;
+        db.restore_from(&self.file).map_err(CliError::from)?;
+
+        if !global.quiet {
+            eprintln!(
+                "Restored {} from {}",
+                db_path.display(),
+                self.file.display()
+            );
+        }
+
+        Ok(())
+    }
+}
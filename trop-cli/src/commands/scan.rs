@@ -2,12 +2,24 @@
 
 use crate::commands::compact_exclusions::compact_exclusion_list;
 use crate::error::CliError;
-use crate::utils::{load_configuration, open_database, resolve_config_file, GlobalOptions};
+use crate::utils::{
+    load_configuration, load_configuration_with_port_overrides, open_database, paint,
+    resolve_config_file, stdout_color_enabled, write_config_file, write_delimited, GlobalOptions,
+    Style, SKIP_OCCUPANCY_HELP,
+};
 use clap::{Args, ValueEnum};
 use serde::Serialize;
-use trop::config::{Config, PortExclusion, DEFAULT_MAX_PORT, DEFAULT_MIN_PORT};
-use trop::port::occupancy::{OccupancyCheckConfig, PortOccupancyChecker, SystemOccupancyChecker};
-use trop::{Database, Port, PortRange};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use trop::config::{Config, PortExclusion, PortRangeOverrides};
+use trop::port::occupancy::{
+    Occupancy, OccupancyCache, OccupancyCheckConfig, PortOccupancyChecker, SystemOccupancyChecker,
+};
+use trop::{Database, Port, PortRange, Reservation};
 
 /// Scan port range for occupied ports.
 #[derive(Args)]
@@ -20,6 +32,10 @@ pub struct ScanCommand {
     #[arg(long)]
     pub max: Option<u16>,
 
+    /// Maximum port as an offset from the minimum (max = min + OFFSET)
+    #[arg(long, value_name = "OFFSET")]
+    pub max_offset: Option<u16>,
+
     /// Automatically add occupied, unreserved ports to exclusion list
     #[arg(long)]
     pub autoexclude: bool,
@@ -32,7 +48,57 @@ pub struct ScanCommand {
     #[arg(long, value_enum, default_value = "table")]
     pub format: ScanOutputFormat,
 
+    /// Output JSON (shorthand for `--format json`)
+    #[arg(long, conflicts_with = "format")]
+    pub json: bool,
+
+    /// Include the PID and name of the process holding each occupied port
+    /// in the JSON output (needs lsof or ss on Unix, netstat on Windows)
+    #[arg(long)]
+    pub with_process: bool,
+
+    /// Re-run the scan every SECONDS (default 2) until interrupted; JSON
+    /// output becomes one document per line
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        num_args = 0..=1,
+        default_missing_value = "2",
+        conflicts_with = "autoexclude"
+    )]
+    pub watch: Option<f64>,
+
+    /// Stop watching after this many scans
+    #[arg(long, requires = "watch")]
+    pub count: Option<u64>,
+
+    /// Reuse a port's occupancy for SECONDS before probing it again across
+    /// watch iterations (0 disables); reserved ports are always re-probed
+    #[arg(long, value_name = "SECONDS", default_value = "0")]
+    pub occupancy_ttl: u64,
+
+    /// Only list ports that are currently occupied
+    #[arg(long, conflicts_with = "free_only")]
+    pub occupied_only: bool,
+
+    /// Only list ports that are currently free; every free port in the
+    /// range is listed unless narrowed by `--reserved-only`
+    #[arg(long)]
+    pub free_only: bool,
+
+    /// Only list ports that have a reservation in the database
+    #[arg(long)]
+    pub reserved_only: bool,
+
+    /// Leave out every port that has a reservation in the database, so
+    /// only ports trop does not manage are listed
+    #[arg(long, conflicts_with = "reserved_only")]
+    pub exclude_reserved: bool,
+
     // Occupancy check options
+    #[arg(long, help = SKIP_OCCUPANCY_HELP)]
+    pub skip_occupancy: bool,
+
     #[arg(long)]
     pub skip_tcp: bool,
 
@@ -47,6 +113,10 @@ pub struct ScanCommand {
 
     #[arg(long)]
     pub check_all_interfaces: bool,
+
+    /// Only report ports bound on this address (repeatable)
+    #[arg(long = "interface", value_name = "ADDR")]
+    pub interfaces: Vec<IpAddr>,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -60,28 +130,105 @@ pub enum ScanOutputFormat {
 impl ScanCommand {
     pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
         // 1. Load configuration and determine port range
+        if self.with_process && !matches!(self.output_format(), ScanOutputFormat::Json) {
+            return Err(CliError::InvalidArguments(
+                "--with-process requires JSON output (--json or --format json)".to_string(),
+            ));
+        }
+
+        let interval = self.watch_interval()?;
+
         let mut config = load_configuration(global)?;
-        let range = self.determine_range(&config)?;
+        let range = self.determine_range(&config, global)?;
 
         // 2. Open database
         let db = open_database(global, &config)?;
 
-        // 3. Scan for occupied ports
-        let checker = SystemOccupancyChecker;
+        let checker = OccupancyCache::new(
+            SystemOccupancyChecker,
+            Duration::from_secs(self.occupancy_ttl),
+        );
+
+        let Some(interval) = interval else {
+            return self.scan_once(&db, &range, &mut config, &checker, global);
+        };
+
+        // Ctrl-C ends the loop between scans so the process exits normally
+        let interrupted = Arc::new(AtomicBool::new(false));
+        {
+            let interrupted = Arc::clone(&interrupted);
+            ctrlc::set_handler(move || interrupted.store(true, Ordering::SeqCst))
+                .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+        }
+
+        let clear_screen = matches!(self.output_format(), ScanOutputFormat::Table)
+            && std::io::stdout().is_terminal();
+        let mut iterations = 0;
+        while !interrupted.load(Ordering::SeqCst) {
+            if clear_screen {
+                print!("\x1b[2J\x1b[H");
+            }
+            self.scan_once(&db, &range, &mut config, &checker, global)?;
+
+            iterations += 1;
+            if self.count.is_some_and(|count| iterations >= count) {
+                break;
+            }
+            sleep_unless_interrupted(interval, &interrupted);
+        }
+
+        Ok(())
+    }
+
+    /// Parse and validate the `--watch` interval, if watching.
+    fn watch_interval(&self) -> Result<Option<Duration>, CliError> {
+        self.watch
+            .map(|seconds| {
+                Duration::try_from_secs_f64(seconds)
+                    .ok()
+                    .filter(|interval| !interval.is_zero())
+                    .ok_or_else(|| {
+                        CliError::InvalidArguments(format!(
+                            "--watch interval must be a positive number of seconds, got {seconds}"
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    /// Run a single scan of `range` and print the results.
+    fn scan_once(
+        &self,
+        db: &Database,
+        range: &PortRange,
+        config: &mut Config,
+        checker: &OccupancyCache<SystemOccupancyChecker>,
+        global: &GlobalOptions,
+    ) -> Result<(), CliError> {
+        // 3. Get reservations in the range from database
+        let reservations =
+            Database::get_reservations_in_range(db.connection(), range).map_err(CliError::from)?;
+        let reserved_ports: Vec<Port> = reservations.iter().map(Reservation::port).collect();
+
+        // A port reserved since it was cached may now be in use, so never
+        // trust a cached result for it
+        for &port in &reserved_ports {
+            checker.forget(port);
+        }
+
+        // 4. Scan for occupied ports
         let check_config = OccupancyCheckConfig {
-            skip_tcp: self.skip_tcp,
-            skip_udp: self.skip_udp,
-            skip_ipv4: self.skip_ipv4,
-            skip_ipv6: self.skip_ipv6,
+            skip_tcp: self.skip_occupancy || self.skip_tcp,
+            skip_udp: self.skip_occupancy || self.skip_udp,
+            skip_ipv4: self.skip_occupancy || self.skip_ipv4,
+            skip_ipv6: self.skip_occupancy || self.skip_ipv6,
             check_all_interfaces: self.check_all_interfaces,
+            interfaces: self.interfaces.clone(),
+            on_error: config.on_occupancy_error.unwrap_or_default(),
         };
 
         let occupied_ports = checker
-            .find_occupied_ports(&range, &check_config)
-            .map_err(CliError::from)?;
-
-        // 4. Get reserved ports from database
-        let reserved_ports = Database::get_reserved_ports_in_range(db.connection(), &range)
+            .find_occupied_ports(range, &check_config)
             .map_err(CliError::from)?;
 
         // 5. Find unreserved occupied ports
@@ -93,35 +240,55 @@ impl ScanCommand {
 
         // 6. Auto-exclude if requested
         if self.autoexclude && !unreserved_occupied.is_empty() {
-            self.add_exclusions(&mut config, &unreserved_occupied, global)?;
+            self.add_exclusions(config, &unreserved_occupied, global)?;
 
             if self.autocompact {
-                self.compact_exclusions(&mut config, global)?;
+                self.compact_exclusions(config, global)?;
             }
         }
 
         // 7. Format and output results
-        self.output_results(&occupied_ports, &reserved_ports, &unreserved_occupied)?;
+        let color = stdout_color_enabled(global.color);
+        self.output_results(
+            range,
+            &occupied_ports,
+            &reservations,
+            &unreserved_occupied,
+            color,
+        )?;
 
         Ok(())
     }
 
-    fn determine_range(&self, config: &Config) -> Result<PortRange, CliError> {
-        let min = self
-            .min
-            .or(config.ports.as_ref().map(|p| p.min))
-            .unwrap_or(DEFAULT_MIN_PORT);
-        let max = self
-            .max
-            .or(config.ports.as_ref().and_then(|p| p.max))
-            .unwrap_or(DEFAULT_MAX_PORT);
-
-        let min_port =
-            Port::try_from(min).map_err(|e| CliError::InvalidArguments(e.to_string()))?;
-        let max_port =
-            Port::try_from(max).map_err(|e| CliError::InvalidArguments(e.to_string()))?;
-
-        PortRange::new(min_port, max_port).map_err(|e| CliError::Library(e.into()))
+    fn output_format(&self) -> ScanOutputFormat {
+        if self.json {
+            ScanOutputFormat::Json
+        } else {
+            self.format
+        }
+    }
+
+    /// Determine the port range to scan, from the configuration with the
+    /// range flags layered on top.
+    ///
+    /// The flags go through a separate load so they never end up in the
+    /// configuration that `--autoexclude` writes back.
+    fn determine_range(
+        &self,
+        config: &Config,
+        global: &GlobalOptions,
+    ) -> Result<PortRange, CliError> {
+        let overrides = PortRangeOverrides {
+            min: self.min,
+            max: self.max,
+            max_offset: self.max_offset,
+        };
+        let range = if overrides.is_empty() {
+            config.effective_port_range()
+        } else {
+            load_configuration_with_port_overrides(global, overrides)?.effective_port_range()
+        };
+        range.map_err(|e| CliError::Config(e.to_string()))
     }
 
     fn add_exclusions(
@@ -141,17 +308,14 @@ impl ScanCommand {
         // Add new exclusions
         if let Some(ref mut exclusions) = config.excluded_ports {
             for port in ports {
-                let exclusion = PortExclusion::Single(port.value());
-                if !exclusions.contains(&exclusion) {
-                    exclusions.push(exclusion);
+                if !exclusions.iter().any(|e| e.contains(port.value())) {
+                    exclusions.push(PortExclusion::Single(port.value()));
                 }
             }
         }
 
         // Save config
-        let yaml = serde_yaml::to_string(config)
-            .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))?;
-        std::fs::write(&config_path, yaml)?;
+        write_config_file(&config_path, config)?;
 
         if !global.quiet {
             eprintln!(
@@ -180,9 +344,7 @@ impl ScanCommand {
                 // Save compacted config
                 let config_path = resolve_config_file(global)?;
 
-                let yaml = serde_yaml::to_string(config)
-                    .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))?;
-                std::fs::write(&config_path, yaml)?;
+                write_config_file(&config_path, config)?;
 
                 if !global.quiet {
                     eprintln!("Compacted {original_count} exclusions to {new_count}");
@@ -193,62 +355,151 @@ impl ScanCommand {
         Ok(())
     }
 
+    /// Whether a port passes the `--*-only` and `--exclude-reserved`
+    /// filters; combined filters must all hold.
+    fn keeps(&self, is_occupied: bool, is_reserved: bool) -> bool {
+        (!self.occupied_only || is_occupied)
+            && (!self.free_only || !is_occupied)
+            && (!self.reserved_only || is_reserved)
+            && (!self.exclude_reserved || !is_reserved)
+    }
+
     fn output_results(
         &self,
+        range: &PortRange,
         occupied: &[Port],
-        reserved: &[Port],
+        reservations: &[Reservation],
         unreserved: &[Port],
+        color: bool,
     ) -> Result<(), CliError> {
         #[derive(Serialize)]
         struct ScanResult {
             port: u16,
             status: String,
             reserved: bool,
+            path: Option<String>,
+            tag: Option<String>,
+            project: Option<String>,
+            /// Present only with `--with-process`; the inner fields are
+            /// null when the owner could not be resolved.
+            #[serde(flatten)]
+            process: Option<ProcessInfo>,
         }
 
-        let mut results = Vec::new();
+        #[derive(Serialize)]
+        struct ProcessInfo {
+            pid: Option<u32>,
+            process_name: Option<String>,
+        }
 
-        for port in occupied {
-            let is_reserved = reserved.contains(port);
-            results.push(ScanResult {
-                port: port.value(),
-                status: if is_reserved {
-                    "occupied (reserved)".to_string()
-                } else {
-                    "occupied".to_string()
-                },
-                reserved: is_reserved,
-            });
+        impl ScanResult {
+            /// Holder column for the table format (`path` or `path [tag]`).
+            fn holder(&self) -> String {
+                match (&self.path, &self.tag) {
+                    (Some(path), Some(tag)) => format!("{path} [{tag}]"),
+                    (Some(path), None) => path.clone(),
+                    (None, _) => "-".to_string(),
+                }
+            }
         }
 
+        // Join occupancy against reservations: every occupied port, plus
+        // reserved ports that are not currently occupied. `--free-only`
+        // widens this to every port in the range before filtering.
+        let by_port: BTreeMap<Port, &Reservation> =
+            reservations.iter().map(|r| (r.port(), r)).collect();
+        let occupied_set: BTreeSet<Port> = occupied.iter().copied().collect();
+        let mut ports: BTreeSet<Port> = if self.free_only {
+            range.iter().collect()
+        } else {
+            occupied_set
+                .iter()
+                .copied()
+                .chain(by_port.keys().copied())
+                .collect()
+        };
+        ports.retain(|port| self.keeps(occupied_set.contains(port), by_port.contains_key(port)));
+
+        let results: Vec<ScanResult> = ports
+            .into_iter()
+            .map(|port| {
+                let holder = by_port.get(&port);
+                let is_occupied = occupied_set.contains(&port);
+                let status = match (is_occupied, holder.is_some()) {
+                    (true, true) => "occupied (reserved)",
+                    (true, false) => "occupied",
+                    (false, true) => "reserved",
+                    (false, false) => "free",
+                };
+                ScanResult {
+                    port: port.value(),
+                    status: status.to_string(),
+                    reserved: holder.is_some(),
+                    path: holder.map(|r| r.key().path.display().to_string()),
+                    tag: holder.and_then(|r| r.key().tag.clone()),
+                    project: holder.and_then(|r| r.project().map(str::to_string)),
+                    process: self.with_process.then(|| {
+                        // Reserved-but-free ports have no owner to look up
+                        let occupancy = Occupancy::new(is_occupied).with_owner(port);
+                        ProcessInfo {
+                            pid: occupancy.pid,
+                            process_name: occupancy.process,
+                        }
+                    }),
+                }
+            })
+            .collect();
+
         // Format based on requested output format
-        match self.format {
+        match self.output_format() {
             ScanOutputFormat::Table => {
-                println!("{:<10} {:<20} Reserved", "Port", "Status");
-                println!("{}", "-".repeat(40));
+                let header = format!("{:<10} {:<20} {:<10} Holder", "Port", "Status", "Reserved");
+                println!("{}", paint(&header, Style::Header, color));
+                println!("{}", "-".repeat(60));
                 for result in &results {
                     println!(
-                        "{:<10} {:<20} {}",
-                        result.port, result.status, result.reserved
+                        "{} {:<20} {:<10} {}",
+                        paint(&format!("{:<10}", result.port), Style::Highlight, color),
+                        result.status,
+                        result.reserved,
+                        result.holder()
                     );
                 }
             }
             ScanOutputFormat::Json => {
-                let json = serde_json::to_string_pretty(&results)
-                    .map_err(|e| CliError::Config(format!("JSON serialization failed: {e}")))?;
-                println!("{json}");
-            }
-            ScanOutputFormat::Csv => {
-                println!("port,status,reserved");
-                for result in &results {
-                    println!("{},{},{}", result.port, result.status, result.reserved);
+                // Watch mode emits newline-delimited JSON, one scan per line
+                let json = if self.watch.is_some() {
+                    serde_json::to_string(&results)
+                } else {
+                    serde_json::to_string_pretty(&results)
                 }
+                .map_err(|e| CliError::Config(format!("JSON serialization failed: {e}")))?;
+                println!("{json}");
             }
-            ScanOutputFormat::Tsv => {
-                println!("port\tstatus\treserved");
-                for result in &results {
-                    println!("{}\t{}\t{}", result.port, result.status, result.reserved);
-                }
+            format @ (ScanOutputFormat::Csv | ScanOutputFormat::Tsv) => {
+                let delimiter = if matches!(format, ScanOutputFormat::Csv) {
+                    b','
+                } else {
+                    b'\t'
+                };
+                let rows: Vec<Vec<String>> = results
+                    .iter()
+                    .map(|result| {
+                        vec![
+                            result.port.to_string(),
+                            result.status.clone(),
+                            result.reserved.to_string(),
+                            result.path.clone().unwrap_or_default(),
+                            result.tag.clone().unwrap_or_default(),
+                            result.project.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                write_delimited(
+                    &["port", "status", "reserved", "path", "tag", "project"],
+                    &rows,
+                    delimiter,
+                )?;
             }
         }
 
@@ -260,3 +511,16 @@ impl ScanCommand {
         Ok(())
     }
 }
+
+/// Sleep for `interval`, waking early if Ctrl-C was pressed.
+fn sleep_unless_interrupted(interval: Duration, interrupted: &AtomicBool) {
+    const TICK: Duration = Duration::from_millis(100);
+    let deadline = Instant::now() + interval;
+    while !interrupted.load(Ordering::SeqCst) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        std::thread::sleep(remaining.min(TICK));
+    }
+}
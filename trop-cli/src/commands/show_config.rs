@@ -0,0 +1,96 @@
+//! Show-config command implementation.
+//!
+//! This module implements the `show-config` command, which prints the
+//! effective configuration for the current directory after merging defaults,
+//! configuration files, the selected profile, and environment overrides.
+//! With `--sources`, each top-level field is annotated with the source that
+//! supplied it.
+
+use crate::error::CliError;
+use crate::utils::{configuration_builder, GlobalOptions};
+use clap::{Args, ValueEnum};
+use trop::config::{Config, ConfigProvenance};
+
+/// Print the effective merged configuration.
+#[derive(Args)]
+pub struct ShowConfigCommand {
+    /// Output format
+    #[arg(long, value_enum, default_value = "yaml")]
+    pub format: ShowConfigFormat,
+
+    /// Annotate each top-level field with the source that supplied it
+    #[arg(long)]
+    pub sources: bool,
+}
+
+/// Output format for the show-config command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ShowConfigFormat {
+    /// YAML, in the same shape as a configuration file
+    Yaml,
+    /// Pretty-printed JSON
+    Json,
+}
+
+impl ShowConfigCommand {
+    /// Execute the show-config command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        let (config, provenance) = configuration_builder(global)
+            .build_with_provenance()
+            .map_err(|e| CliError::Config(e.to_string()))?;
+
+        let output = match (self.format, self.sources) {
+            (ShowConfigFormat::Yaml, false) => to_yaml(&config)?,
+            (ShowConfigFormat::Yaml, true) => annotated_yaml(&config, &provenance)?,
+            (ShowConfigFormat::Json, sources) => {
+                let config = serde_json::to_value(&config)
+                    .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))?;
+                let json = if sources {
+                    let sources: serde_json::Map<String, serde_json::Value> = provenance
+                        .iter()
+                        .map(|(field, origin)| (field.clone(), origin.to_string().into()))
+                        .collect();
+                    serde_json::json!({ "config": config, "sources": sources })
+                } else {
+                    config
+                };
+                let mut output = serde_json::to_string_pretty(&json)
+                    .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))?;
+                output.push('\n');
+                output
+            }
+        };
+
+        print!("{output}");
+        Ok(())
+    }
+}
+
+/// Serialize a value as YAML.
+fn to_yaml(value: &impl serde::Serialize) -> Result<String, CliError> {
+    serde_yaml::to_string(value)
+        .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))
+}
+
+/// Serialize the config as YAML with a `# source:` comment above each
+/// top-level field that some source set.
+fn annotated_yaml(config: &Config, provenance: &ConfigProvenance) -> Result<String, CliError> {
+    let serde_yaml::Value::Mapping(fields) = serde_yaml::to_value(config)
+        .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))?
+    else {
+        return to_yaml(config);
+    };
+
+    let mut output = String::new();
+    for (name, value) in fields {
+        if let Some(origin) = name.as_str().and_then(|name| provenance.get(name)) {
+            output.push_str("# source: ");
+            output.push_str(&origin.to_string());
+            output.push('\n');
+        }
+        let mut field = serde_yaml::Mapping::new();
+        field.insert(name, value);
+        output.push_str(&to_yaml(&field)?);
+    }
+    Ok(output)
+}
@@ -3,7 +3,8 @@
 use crate::error::CliError;
 use crate::utils::{normalize_path, resolve_path, GlobalOptions};
 use clap::Args;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use trop::path::PathRelationship;
 
 /// Show the resolved path that would be used for a reservation.
 #[derive(Args)]
@@ -15,6 +16,10 @@ pub struct ShowPathCommand {
     /// Explicitly request canonicalization
     #[arg(long)]
     pub canonicalize: bool,
+
+    /// Print the path relative to BASE when it lies beneath it
+    #[arg(long, value_name = "BASE")]
+    pub relative_to: Option<PathBuf>,
 }
 
 impl ShowPathCommand {
@@ -23,13 +28,28 @@ impl ShowPathCommand {
         let path = resolve_path(self.path)?;
 
         // Normalize or canonicalize as requested
-        let resolved = if self.canonicalize {
-            path.canonicalize().map_err(CliError::from)?
-        } else {
-            normalize_path(&path)?
+        let resolved = resolve(&path, self.canonicalize)?;
+
+        // Paths outside the base stay absolute
+        let shown = match self.relative_to {
+            Some(ref base) => {
+                let base = resolve(base, self.canonicalize)?;
+                PathRelationship::relative_to(&resolved, &base).unwrap_or(resolved)
+            }
+            None => resolved,
         };
 
-        println!("{}", resolved.display());
+        println!("{}", shown.display());
         Ok(())
     }
 }
+
+/// Normalize `path`, or canonicalize it with `--canonicalize`, so the path
+/// and the base are compared in the same form.
+fn resolve(path: &Path, canonicalize: bool) -> Result<PathBuf, CliError> {
+    if canonicalize {
+        path.canonicalize().map_err(CliError::from)
+    } else {
+        normalize_path(path)
+    }
+}
@@ -0,0 +1,155 @@
+//! Stats command implementation.
+//!
+//! This module implements the `stats` command, which summarizes the
+//! reservation database: totals, per-project counts, and how much of the
+//! configured port range is in use.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, GlobalOptions};
+use clap::{Args, ValueEnum};
+use std::io::Write;
+use trop::config::Config;
+use trop::database::ReservationStats;
+use trop::{Database, PortRange};
+
+/// Summarize reservation database usage.
+#[derive(Args)]
+pub struct StatsCommand {
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: StatsFormat,
+
+    /// Also count reservations whose paths no longer exist (checks the
+    /// filesystem once per reserved path)
+    #[arg(long)]
+    pub check_paths: bool,
+}
+
+/// Output format for stats command.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StatsFormat {
+    /// Human-readable summary
+    Table,
+    /// JSON format
+    Json,
+}
+
+impl StatsCommand {
+    /// Execute the stats command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Load configuration for the database location and port range
+        let config = load_configuration(global)?;
+        let range = configured_range(&config)?;
+
+        // 2. Open database (read-only access is fine)
+        let db = open_database(global, &config)?;
+
+        // 3. Aggregate in the database; only paths are read for --check-paths
+        let stats = Database::reservation_stats(db.connection(), &range).map_err(CliError::from)?;
+        let missing_paths = if self.check_paths {
+            let paths =
+                Database::reservation_path_counts(db.connection()).map_err(CliError::from)?;
+            Some(
+                paths
+                    .into_iter()
+                    .filter(|(path, _)| !path.exists())
+                    .map(|(_, count)| count)
+                    .sum(),
+            )
+        } else {
+            None
+        };
+
+        // 4. Output
+        match self.format {
+            StatsFormat::Table => format_as_table(&stats, missing_paths),
+            StatsFormat::Json => format_as_json(&stats, missing_paths),
+        }
+    }
+}
+
+/// Resolve the allocation range, falling back to the defaults when no port
+/// configuration is present.
+fn configured_range(config: &Config) -> Result<PortRange, CliError> {
+    config
+        .ports
+        .clone()
+        .unwrap_or_default()
+        .effective_range()
+        .map_err(|e| CliError::Config(e.to_string()))
+}
+
+/// Label used for reservations without a project.
+const NO_PROJECT: &str = "(none)";
+
+/// Format stats as a human-readable summary.
+fn format_as_table(stats: &ReservationStats, missing_paths: Option<usize>) -> Result<(), CliError> {
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    writeln!(handle, "Total reservations: {}", stats.total)?;
+    writeln!(handle, "Distinct ports: {}", stats.distinct_ports)?;
+    writeln!(handle, "Sticky reservations: {}", stats.sticky)?;
+    writeln!(
+        handle,
+        "Port range: {} ({} of {} ports reserved, {:.1}%)",
+        stats.range,
+        stats.ports_in_range,
+        stats.range.len(),
+        stats.range_utilization()
+    )?;
+    if let Some(missing) = missing_paths {
+        writeln!(handle, "Missing paths: {missing}")?;
+    }
+
+    if !stats.by_project.is_empty() {
+        writeln!(handle, "Reservations by project:")?;
+        for (project, count) in &stats.by_project {
+            writeln!(
+                handle,
+                "  {}\t{count}",
+                project.as_deref().unwrap_or(NO_PROJECT)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Format stats as a JSON object.
+fn format_as_json(stats: &ReservationStats, missing_paths: Option<usize>) -> Result<(), CliError> {
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+
+    let projects: Vec<serde_json::Value> = stats
+        .by_project
+        .iter()
+        .map(|(project, count)| {
+            serde_json::json!({
+                "project": project,
+                "count": count,
+            })
+        })
+        .collect();
+
+    let json_data = serde_json::json!({
+        "total": stats.total,
+        "distinct_ports": stats.distinct_ports,
+        "sticky": stats.sticky,
+        "range": {
+            "min": stats.range.min().value(),
+            "max": stats.range.max().value(),
+        },
+        "ports_in_range": stats.ports_in_range,
+        "range_utilization_percent": stats.range_utilization(),
+        "missing_paths": missing_paths,
+        "projects": projects,
+    });
+
+    serde_json::to_writer_pretty(&mut handle, &json_data)
+        .map_err(|e| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+
+    writeln!(handle)?;
+
+    Ok(())
+}
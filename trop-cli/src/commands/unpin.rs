@@ -0,0 +1,29 @@
+//! Unpin command implementation.
+//!
+//! This module implements the `unpin` command, which clears a reservation's
+//! pinned flag so it can be released or reallocated again.
+
+use super::pin::set_pin_state;
+use crate::error::CliError;
+use crate::utils::GlobalOptions;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Unpin a reservation so it can be released or reallocated.
+#[derive(Args)]
+pub struct UnpinCommand {
+    /// Directory path (default: current directory)
+    #[arg(long, value_name = "PATH", env = "TROP_PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Service tag
+    #[arg(long, value_name = "TAG")]
+    pub tag: Option<String>,
+}
+
+impl UnpinCommand {
+    /// Execute the unpin command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        set_pin_state(self.path, self.tag, false, global)
+    }
+}
@@ -0,0 +1,42 @@
+//! Vacuum command implementation.
+//!
+//! This module implements the `vacuum` command, which rebuilds the database
+//! file to reclaim space left behind by released, pruned, and expired
+//! reservations.
+
+use crate::error::CliError;
+use crate::utils::{load_configuration, open_database, GlobalOptions};
+use clap::Args;
+
+/// Reclaim unused space in the reservation database.
+#[derive(Args)]
+pub struct VacuumCommand {
+    /// Also checkpoint and truncate the write-ahead log
+    #[arg(long)]
+    pub checkpoint: bool,
+}
+
+impl VacuumCommand {
+    /// Execute the vacuum command.
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        let config = load_configuration(global)?;
+        let mut db = open_database(global, &config)?;
+
+        // Lock contention surfaces as a timeout (exit code 2)
+        let stats = db.vacuum().map_err(CliError::from)?;
+        if self.checkpoint {
+            db.checkpoint_wal().map_err(CliError::from)?;
+        }
+
+        if !global.quiet {
+            eprintln!(
+                "Vacuumed database: reclaimed {} bytes ({} -> {} bytes)",
+                stats.reclaimed(),
+                stats.size_before,
+                stats.size_after
+            );
+        }
+
+        Ok(())
+    }
+}
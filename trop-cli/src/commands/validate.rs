@@ -1,22 +1,43 @@
 //! Command to validate a trop configuration file.
+//!
+//! Given a directory, every tropfile under it is validated and reported
+//! individually, which lets CI lint all configs in a repository in one pass.
+//! With `--strict`, settings that have no effect (values equal to the
+//! built-in defaults or repeated from `trop.yaml` in `trop.local.yaml`) and
+//! empty reservation groups are reported as warnings that fail validation.
 
 use crate::error::CliError;
 use crate::utils::GlobalOptions;
 use clap::Args;
-use std::path::PathBuf;
-use trop::config::{Config, ConfigValidator};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use trop::config::{
+    Config, ConfigBuilder, ConfigLoader, ConfigValidator, ReservationGroup, ReservationGroups,
+};
 
-/// Validate a trop configuration file.
+/// Fields a configuration must spell out whenever their section is present,
+/// so matching the built-in default is not redundant.
+const REQUIRED_FIELDS: &[&str] = &["ports.min"];
+
+/// Validate a trop configuration file, or every tropfile under a directory.
 #[derive(Args)]
 pub struct ValidateCommand {
-    /// Configuration file to validate
+    /// Configuration file to validate, or directory to search for tropfiles
     #[arg(value_name = "CONFIG_PATH")]
     pub config_path: PathBuf,
+
+    /// When validating a directory, stop at the first invalid file
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Also fail on redundant settings and empty reservation groups
+    #[arg(long)]
+    pub strict: bool,
 }
 
 impl ValidateCommand {
-    pub fn execute(self, _global: &GlobalOptions) -> Result<(), CliError> {
-        // 1. Check file exists
+    pub fn execute(self, global: &GlobalOptions) -> Result<(), CliError> {
+        // 1. Check path exists
         if !self.config_path.exists() {
             return Err(CliError::InvalidArguments(format!(
                 "File not found: {}",
@@ -24,38 +45,248 @@ impl ValidateCommand {
             )));
         }
 
-        // 2. Determine file type (trop.yaml vs config.yaml)
-        let filename = self
-            .config_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("");
-        let is_tropfile = filename == "trop.yaml" || filename == "trop.local.yaml";
-
-        // 3. Parse the file
-        let contents = std::fs::read_to_string(&self.config_path)?;
-        let config: Config = match serde_yaml::from_str(&contents) {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("Parse error: {e}");
-                return Err(CliError::SemanticFailure(
-                    "Configuration file is invalid".to_string(),
-                ));
+        if self.config_path.is_dir() {
+            return self.validate_directory(global);
+        }
+
+        // 2. Validate the single file
+        if let Some(failure) = validate_file(&self.config_path)? {
+            eprintln!("{}", failure.message);
+            return Err(CliError::SemanticFailure(failure.summary.to_string()));
+        }
+
+        // 3. In strict mode, warnings fail validation
+        if self.strict {
+            let warnings = strict_warnings(&self.config_path)?;
+            if !warnings.is_empty() {
+                for warning in &warnings {
+                    eprintln!("warning: {warning}");
+                }
+                return Err(CliError::SemanticFailure(format!(
+                    "Strict validation failed with {} warning(s)",
+                    warnings.len()
+                )));
             }
-        };
+        }
+
+        println!("Configuration is valid");
+        Ok(())
+    }
+
+    /// Validate every tropfile under the directory, reporting each by path.
+    fn validate_directory(&self, global: &GlobalOptions) -> Result<(), CliError> {
+        let tropfiles = ConfigLoader::find_tropfiles(&self.config_path).map_err(CliError::from)?;
+
+        if tropfiles.is_empty() {
+            if !global.quiet {
+                eprintln!("No tropfiles found under {}", self.config_path.display());
+            }
+            return Ok(());
+        }
+
+        let mut failed = 0;
+        let mut checked = 0;
+        for path in &tropfiles {
+            checked += 1;
+            let valid = match validate_file(path)? {
+                None => {
+                    let warnings = if self.strict {
+                        strict_warnings(path)?
+                    } else {
+                        Vec::new()
+                    };
+                    for warning in &warnings {
+                        eprintln!("{}: warning: {warning}", path.display());
+                    }
+                    warnings.is_empty()
+                }
+                Some(failure) => {
+                    eprintln!("{}: {}", path.display(), failure.message);
+                    false
+                }
+            };
+
+            if valid {
+                println!("{}: valid", path.display());
+            } else {
+                failed += 1;
+                if self.fail_fast {
+                    return Err(CliError::SemanticFailure(format!(
+                        "Validation failed for {}",
+                        path.display()
+                    )));
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(CliError::SemanticFailure(format!(
+                "{failed} of {checked} configuration file(s) failed validation"
+            )));
+        }
+
+        if !global.quiet {
+            eprintln!("All {checked} configuration file(s) are valid");
+        }
+        Ok(())
+    }
+}
+
+/// Why a configuration file failed validation.
+struct ValidationFailure {
+    /// Detailed error, e.g. `Parse error: ...`.
+    message: String,
+    /// Short description used as the command's error.
+    summary: &'static str,
+}
+
+/// Parse and validate a single configuration file.
+///
+/// Returns `None` if the file is valid.
+fn validate_file(path: &Path) -> Result<Option<ValidationFailure>, CliError> {
+    // Determine file type (trop.yaml vs config.yaml)
+    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let is_tropfile = matches!(
+        filename,
+        "trop.yaml" | "trop.local.yaml" | "trop.json" | "trop.local.json"
+    );
+
+    let config = match parse_file::<Config>(path)? {
+        Ok(c) => c,
+        Err(e) => {
+            return Ok(Some(ValidationFailure {
+                message: format!("Parse error: {e}"),
+                summary: "Configuration file is invalid",
+            }));
+        }
+    };
+
+    // Validate the configuration
+    Ok(ConfigValidator::validate(&config, is_tropfile)
+        .err()
+        .map(|e| ValidationFailure {
+            message: format!("Validation error: {e}"),
+            summary: "Configuration validation failed",
+        }))
+}
+
+/// Parse a configuration file, as JSON if it has a .json extension.
+fn parse_file<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Result<T, String>, CliError> {
+    let contents = std::fs::read_to_string(path)?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    Ok(if is_json {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(&contents).map_err(|e| e.to_string())
+    })
+}
+
+/// Collect the `--strict` warnings for a configuration file that has
+/// already passed validation.
+///
+/// Fields are compared as written, so a warning names the exact setting to
+/// remove. A field repeated from the sibling `trop.yaml` is reported in
+/// preference to one that also matches the built-in default, and required
+/// fields are never reported as defaults.
+fn strict_warnings(path: &Path) -> Result<Vec<String>, CliError> {
+    let mut warnings = Vec::new();
 
-        // 4. Validate the configuration (ConfigValidator already exists)
-        match ConfigValidator::validate(&config, is_tropfile) {
-            Ok(()) => {
-                println!("Configuration is valid");
-                Ok(())
+    let config: Config = parse_file(path)?.map_err(CliError::InvalidArguments)?;
+    match &config.reservations {
+        Some(ReservationGroups::Single(group)) if is_empty_group(group) => {
+            warnings.push("reservations defines no services".to_string());
+        }
+        Some(ReservationGroups::Named(named)) => {
+            let mut names: Vec<_> = named
+                .iter()
+                .filter(|(_, group)| is_empty_group(group))
+                .map(|(name, _)| name)
+                .collect();
+            names.sort();
+            for name in names {
+                warnings.push(format!("reservation group '{name}' defines no services"));
             }
-            Err(e) => {
-                eprintln!("Validation error: {e}");
-                Err(CliError::SemanticFailure(
-                    "Configuration validation failed".to_string(),
-                ))
+        }
+        _ => {}
+    }
+
+    let written: Value = parse_file(path)?.map_err(CliError::InvalidArguments)?;
+
+    let mut repeated = Vec::new();
+    if let Some(base_path) = base_tropfile(path) {
+        if let Ok(base) = parse_file::<Value>(&base_path)? {
+            collect_matching_fields(&written, &base, "", &mut repeated);
+            let base_name = base_path.file_name().unwrap_or_default().to_string_lossy();
+            for field in &repeated {
+                warnings.push(format!("`{field}` repeats the value set in {base_name}"));
+            }
+        }
+    }
+
+    let defaults = ConfigBuilder::new()
+        .skip_files()
+        .skip_env()
+        .build()
+        .map_err(CliError::from)?;
+    let defaults = serde_json::to_value(defaults)
+        .map_err(|e| CliError::InvalidArguments(format!("Failed to serialize defaults: {e}")))?;
+    let mut defaulted = Vec::new();
+    collect_matching_fields(&written, &defaults, "", &mut defaulted);
+    for field in defaulted
+        .iter()
+        .filter(|f| !repeated.contains(f) && !REQUIRED_FIELDS.contains(&f.as_str()))
+    {
+        warnings.push(format!("`{field}` is set to its built-in default"));
+    }
+
+    Ok(warnings)
+}
+
+/// Whether a reservation group has no services to reserve.
+fn is_empty_group(group: &ReservationGroup) -> bool {
+    group.services.is_empty()
+}
+
+/// The `trop.yaml` (or `trop.json`) that a `trop.local.*` file overrides.
+fn base_tropfile(path: &Path) -> Option<PathBuf> {
+    let base_name = match path.file_name()?.to_str()? {
+        "trop.local.yaml" => "trop.yaml",
+        "trop.local.json" => "trop.json",
+        _ => return None,
+    };
+    let base = path.with_file_name(base_name);
+    base.is_file().then_some(base)
+}
+
+/// Record the dotted path of every field in `written` whose value equals
+/// the field at the same path in `reference`.
+///
+/// Objects are compared field by field; any other value (including lists)
+/// must match as a whole. Fields absent or null in `reference` never match.
+fn collect_matching_fields(
+    written: &Value,
+    reference: &Value,
+    prefix: &str,
+    out: &mut Vec<String>,
+) {
+    let Value::Object(fields) = written else {
+        return;
+    };
+    for (key, value) in fields {
+        let field = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match reference.get(key) {
+            None | Some(Value::Null) => {}
+            Some(expected) if value.is_object() && expected.is_object() => {
+                collect_matching_fields(value, expected, &field, out);
             }
+            Some(expected) if expected == value => out.push(field),
+            Some(_) => {}
         }
     }
 }
@@ -4,6 +4,7 @@
 //! wrapping library errors and providing appropriate exit codes.
 
 use std::fmt;
+use std::path::PathBuf;
 use trop::Error as LibError;
 
 /// CLI-specific error type with exit code mapping.
@@ -21,14 +22,17 @@ pub enum CliError {
     /// Timeout waiting for database lock.
     Timeout,
 
-    /// Data directory not found (and auto-init disabled).
-    NoDataDirectory,
+    /// Database not found at the given path (and auto-init disabled).
+    NoDataDirectory(PathBuf),
 
     /// Configuration error.
     Config(String),
 
     /// Semantic failure (e.g., assertion failed) - exit code 1.
     SemanticFailure(String),
+
+    /// An error for one line of `--stdin-paths` input (1-based line number).
+    OnLine(usize, Box<CliError>),
 }
 
 impl CliError {
@@ -49,13 +53,57 @@ impl CliError {
             CliError::Library(lib_err) => match lib_err {
                 LibError::StickyFieldChange { .. } => 1,
                 LibError::PathRelationshipViolation { .. } => 1,
+                LibError::ReservationPinned { .. } => 1,
                 _ => 6,
             },
             CliError::Timeout => 2,
-            CliError::NoDataDirectory => 3,
+            CliError::NoDataDirectory(_) => 3,
             CliError::InvalidArguments(_) => 4,
             CliError::Io(_) => 5,
             CliError::Config(_) => 7,
+            CliError::OnLine(_, e) => e.exit_code(),
+        }
+    }
+
+    /// Name of the failure kind for machine-readable output.
+    ///
+    /// Library errors report their [`LibError`] variant name; CLI-level
+    /// errors report their own variant name.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CliError::Library(e) => e.kind(),
+            CliError::InvalidArguments(_) => "InvalidArguments",
+            CliError::Io(_) => "Io",
+            CliError::Timeout => "Timeout",
+            CliError::NoDataDirectory(_) => "NoDataDirectory",
+            CliError::Config(_) => "Config",
+            CliError::SemanticFailure(_) => "SemanticFailure",
+            CliError::OnLine(_, e) => e.kind(),
+        }
+    }
+
+    /// Render this error as the `--error-format json` object.
+    ///
+    /// Errors about a specific unavailable port also carry a `reason`
+    /// (`reserved`, `excluded`, or `occupied`).
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "kind": self.kind(),
+            "message": self.to_string(),
+            "exit_code": self.exit_code(),
+        });
+        if let Some(reason) = self.port_unavailable_reason() {
+            json["reason"] = reason.to_string().into();
+        }
+        json
+    }
+
+    /// Why a specific port couldn't be used, if this error is about one.
+    pub fn port_unavailable_reason(&self) -> Option<trop::PortUnavailableReason> {
+        match self {
+            CliError::Library(e) => e.port_unavailable_reason(),
+            CliError::OnLine(_, e) => e.port_unavailable_reason(),
+            _ => None,
         }
     }
 }
@@ -67,14 +115,14 @@ impl fmt::Display for CliError {
             CliError::InvalidArguments(msg) => write!(f, "Invalid arguments: {msg}"),
             CliError::Io(e) => write!(f, "I/O error: {e}"),
             CliError::Timeout => write!(f, "Timeout waiting for database lock"),
-            CliError::NoDataDirectory => {
-                write!(
-                    f,
-                    "Data directory not found (use --data-dir or enable auto-init)"
-                )
-            }
+            CliError::NoDataDirectory(path) => write!(
+                f,
+                "Database not found at {} and auto-init is disabled; run `trop init` to create it",
+                path.display()
+            ),
             CliError::Config(msg) => write!(f, "Configuration error: {msg}"),
             CliError::SemanticFailure(msg) => write!(f, "{msg}"),
+            CliError::OnLine(line, e) => write!(f, "line {line}: {e}"),
         }
     }
 }
@@ -84,6 +132,7 @@ impl std::error::Error for CliError {
         match self {
             CliError::Library(e) => Some(e),
             CliError::Io(e) => Some(e),
+            CliError::OnLine(_, e) => Some(e.as_ref()),
             _ => None,
         }
     }
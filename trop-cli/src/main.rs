@@ -15,28 +15,60 @@ mod utils;
 
 use clap::Parser;
 use cli::Cli;
-use utils::GlobalOptions;
+use error::CliError;
+use std::sync::Arc;
+use trop::Logger;
+use utils::{ColorChoice, ErrorFormat, GlobalOptions};
 
 fn main() {
     // Parse CLI arguments
     let cli = Cli::parse();
 
     // Initialize logging based on verbosity
-    let _logger = trop::init_logger(cli.verbose, cli.quiet);
+    let logger = trop::init_logger(cli.verbose, cli.quiet, cli.log_file.as_deref());
+    if logger.has_file() {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        logger.info(&format!(
+            "trop {} started: {}",
+            env!("CARGO_PKG_VERSION"),
+            args.join(" ")
+        ));
+    }
+
+    // Expand `~` and `$VAR` in the data directory (which may come from
+    // TROP_DATA_DIR, where the shell did not expand them)
+    let data_dir = match cli
+        .data_dir
+        .map(|dir| trop::path::normalize::expand_path(&dir))
+        .transpose()
+    {
+        Ok(data_dir) => data_dir,
+        Err(e) => {
+            let e = CliError::InvalidArguments(format!("Invalid data directory: {e}"));
+            exit_with_error(&e, &logger, cli.error_format, cli.color)
+        }
+    };
 
     // Convert CLI args to GlobalOptions
     let global = GlobalOptions {
         verbose: cli.verbose,
         quiet: cli.quiet,
-        data_dir: cli.data_dir,
+        data_dir,
+        db_name: cli.db_name,
         busy_timeout: cli.busy_timeout,
         disable_autoinit: cli.disable_autoinit,
+        profile: cli.profile,
+        color: cli.color,
+        error_format: cli.error_format,
+        logger: Arc::new(logger),
     };
 
     // Execute the command
     let result = match cli.command {
         cli::Command::Reserve(cmd) => cmd.execute(&global),
         cli::Command::Release(cmd) => cmd.execute(&global),
+        cli::Command::Pin(cmd) => cmd.execute(&global),
+        cli::Command::Unpin(cmd) => cmd.execute(&global),
         cli::Command::List(cmd) => cmd.execute(&global),
         cli::Command::ReserveGroup(cmd) => cmd.execute(&global),
         cli::Command::Autoreserve(cmd) => cmd.execute(&global),
@@ -47,24 +79,61 @@ fn main() {
         cli::Command::AssertPort(cmd) => cmd.execute(&global),
         cli::Command::AssertDataDir(cmd) => cmd.execute(&global),
         cli::Command::PortInfo(cmd) => cmd.execute(&global),
+        cli::Command::FirstFree(cmd) => cmd.execute(&global),
+        cli::Command::ShowConfig(cmd) => cmd.execute(&global),
         cli::Command::ShowDataDir(cmd) => cmd.execute(&global),
         cli::Command::ShowPath(cmd) => cmd.execute(&global),
         cli::Command::Scan(cmd) => cmd.execute(&global),
         cli::Command::Validate(cmd) => cmd.execute(&global),
         cli::Command::Exclude(cmd) => cmd.execute(&global),
         cli::Command::CompactExclusions(cmd) => cmd.execute(&global),
+        cli::Command::ListExclusions(cmd) => cmd.execute(&global),
         cli::Command::Init(cmd) => cmd.execute(&global),
         cli::Command::ListProjects(cmd) => cmd.execute(&global),
+        cli::Command::Stats(cmd) => cmd.execute(&global),
+        cli::Command::Doctor(cmd) => cmd.execute(&global),
+        cli::Command::Health(cmd) => cmd.execute(&global),
         cli::Command::Migrate(cmd) => cmd.execute(&global),
+        cli::Command::Rehome(cmd) => cmd.execute(&global),
+        cli::Command::Diff(cmd) => cmd.execute(&global),
+        cli::Command::Renew(cmd) => cmd.execute(&global),
+        cli::Command::Move(cmd) => cmd.execute(&global),
+        cli::Command::Backup(cmd) => cmd.execute(&global),
+        cli::Command::Restore(cmd) => cmd.execute(&global),
+        cli::Command::Export(cmd) => cmd.execute(&global),
+        cli::Command::Import(cmd) => cmd.execute(&global),
+        cli::Command::Vacuum(cmd) => cmd.execute(&global),
         cli::Command::Completions(cmd) => cmd.execute(&global),
     };
 
     // Handle errors and set exit code
     match result {
-        Ok(()) => std::process::exit(0),
-        Err(e) => {
-            eprintln!("Error: {e}");
-            std::process::exit(e.exit_code());
+        Ok(()) => {
+            if global.logger.has_file() {
+                global.logger.info("finished successfully");
+            }
+            std::process::exit(0)
+        }
+        Err(e) => exit_with_error(&e, &global.logger, global.error_format, global.color),
+    }
+}
+
+/// Log and report a failed command on stderr, then exit with its code.
+fn exit_with_error(
+    e: &CliError,
+    logger: &Logger,
+    error_format: ErrorFormat,
+    color: ColorChoice,
+) -> ! {
+    if logger.has_file() {
+        logger.error(&format!("failed with exit code {}: {e}", e.exit_code()));
+    }
+    match error_format {
+        ErrorFormat::Human => {
+            let prefix = utils::error_prefix(utils::stderr_color_enabled(color));
+            eprintln!("{prefix} {e}");
         }
+        ErrorFormat::Json => eprintln!("{}", e.to_json()),
     }
+    std::process::exit(e.exit_code());
 }
@@ -5,11 +5,24 @@
 //! and output formatting.
 
 use crate::error::CliError;
+use clap::ValueEnum;
 use std::collections::HashMap;
 use std::env;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use trop::config::{
+    ConfigLoader, ConfigValidator, EnvironmentConfig, PortExclusion, PortRangeOverrides, TagCase,
+};
+use trop::database::{validate_database_filename, DEFAULT_DATABASE_FILENAME};
 use trop::output::OutputFormat;
-use trop::{Config, ConfigBuilder, Database, DatabaseConfig, PathResolver, Port};
+use trop::{Config, ConfigBuilder, Database, DatabaseConfig, Logger, PathResolver, Port};
+
+/// Help text for the `--skip-occupancy` flag shared by reserve, scan, and
+/// port-info.
+pub const SKIP_OCCUPANCY_HELP: &str =
+    "Skip occupancy checks for this run, as if `occupancy_check.skip` were set";
 
 /// Global CLI options shared across all commands.
 #[derive(Debug, Clone)]
@@ -24,11 +37,157 @@ pub struct GlobalOptions {
     /// Override the data directory location.
     pub data_dir: Option<PathBuf>,
 
+    /// Override the database file name.
+    pub db_name: Option<String>,
+
     /// Override the default busy timeout (in seconds).
     pub busy_timeout: Option<u32>,
 
     /// Disable automatic database initialization.
     pub disable_autoinit: bool,
+
+    /// Configuration profile to apply.
+    pub profile: Option<String>,
+
+    /// When to color human-readable output.
+    pub color: ColorChoice,
+
+    /// How to report a failing command on stderr.
+    pub error_format: ErrorFormat,
+
+    /// Destination for diagnostics such as `--verbose` timings.
+    pub logger: Arc<Logger>,
+}
+
+/// How errors are reported on stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// An `error:` line for people
+    #[default]
+    Human,
+    /// A JSON object with `kind`, `message`, and `exit_code`
+    Json,
+}
+
+/// When to emit ANSI colors in human-readable output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when the environment allows it and the stream is a terminal
+    #[default]
+    Auto,
+    /// Always color, even when output is piped
+    Always,
+    /// Never color
+    Never,
+}
+
+/// ANSI escape resetting all text attributes.
+const RESET_STYLE: &str = "\x1b[0m";
+
+/// A highlight applied to human-readable output when color is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Bold red, for error prefixes
+    Error,
+    /// Bold yellow, for warning prefixes and problem states
+    Warning,
+    /// Bold, for table headers
+    Header,
+    /// Cyan, for the key value of a row (usually the port)
+    Highlight,
+}
+
+impl Style {
+    /// The ANSI escape that starts this style.
+    const fn escape(self) -> &'static str {
+        match self {
+            Self::Error => "\x1b[1;31m",
+            Self::Warning => "\x1b[1;33m",
+            Self::Header => "\x1b[1m",
+            Self::Highlight => "\x1b[36m",
+        }
+    }
+}
+
+/// Wrap `text` in the escapes for `style` when `color` is set.
+pub fn paint(text: &str, style: Style, color: bool) -> String {
+    if color {
+        format!("{}{text}{RESET_STYLE}", style.escape())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Decide whether output written to stderr should be colored.
+///
+/// Precedence: `--color always|never` > `NO_COLOR` / `CLICOLOR_FORCE` >
+/// TTY auto-detection. See [`resolve_color`].
+pub fn stderr_color_enabled(choice: ColorChoice) -> bool {
+    resolve_color(
+        choice,
+        env::var("CLICOLOR_FORCE").ok().as_deref(),
+        env::var("NO_COLOR").ok().as_deref(),
+        std::io::stderr().is_terminal(),
+    )
+}
+
+/// Decide whether output written to stdout should be colored.
+///
+/// Same rules as [`stderr_color_enabled`], but auto-detection looks at
+/// stdout so piping a table never captures escapes.
+pub fn stdout_color_enabled(choice: ColorChoice) -> bool {
+    resolve_color(
+        choice,
+        env::var("CLICOLOR_FORCE").ok().as_deref(),
+        env::var("NO_COLOR").ok().as_deref(),
+        std::io::stdout().is_terminal(),
+    )
+}
+
+/// Resolve the color decision from the flag, environment, and TTY state.
+///
+/// An explicit `always` or `never` wins. Under `auto`, a non-empty
+/// `NO_COLOR` disables color, otherwise `CLICOLOR_FORCE` set to anything but
+/// `0` enables it (e.g. for CI logs that render ANSI but are not TTYs), and
+/// otherwise color follows whether the stream is a terminal.
+fn resolve_color(
+    choice: ColorChoice,
+    clicolor_force: Option<&str>,
+    no_color: Option<&str>,
+    is_terminal: bool,
+) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            if no_color.is_some_and(|v| !v.is_empty()) {
+                false
+            } else if clicolor_force.is_some_and(|v| !v.is_empty() && v != "0") {
+                true
+            } else {
+                is_terminal
+            }
+        }
+    }
+}
+
+/// Decide whether `--format auto` should pick human-readable output.
+///
+/// Output is only human when it goes to stdout and stdout is a terminal;
+/// pipes, command substitution, redirects, and `--output-file` all get
+/// machine-readable output instead.
+pub fn prefers_human_output(output_file: Option<&Path>, stdout_is_terminal: bool) -> bool {
+    output_file.is_none() && stdout_is_terminal
+}
+
+/// Format the `Error:` prefix for error messages, colored when enabled.
+pub fn error_prefix(color: bool) -> String {
+    paint("Error:", Style::Error, color)
+}
+
+/// Format the `Warning:` prefix for warnings, colored when enabled.
+pub fn warning_prefix(color: bool) -> String {
+    paint("Warning:", Style::Warning, color)
 }
 
 /// Resolve a path, using CWD if not specified.
@@ -62,9 +221,43 @@ pub fn normalize_path(path: &Path) -> Result<PathBuf, CliError> {
 /// Configuration is merged from multiple sources with precedence:
 /// 1. Global options (highest priority)
 /// 2. Environment variables
-/// 3. Configuration files
-/// 4. Built-in defaults (lowest priority)
+/// 3. The selected profile (`--profile` or `TROP_PROFILE`)
+/// 4. Configuration files
+/// 5. Built-in defaults (lowest priority)
 pub fn load_configuration(global: &GlobalOptions) -> Result<Config, CliError> {
+    // Build configuration from environment and files
+    let config = configuration_builder(global)
+        .build()
+        .map_err(|e| CliError::Config(e.to_string()))?;
+
+    Ok(config)
+}
+
+/// Load configuration with port range overrides from command-line flags
+/// layered over every other source.
+///
+/// A port range the overrides make invalid (such as `--max` together with
+/// `--max-offset`) is reported as an argument error.
+pub fn load_configuration_with_port_overrides(
+    global: &GlobalOptions,
+    overrides: PortRangeOverrides,
+) -> Result<Config, CliError> {
+    configuration_builder(global)
+        .with_port_overrides(overrides)
+        .build()
+        .map_err(|e| match e {
+            trop::Error::Validation { ref field, .. }
+                if !overrides.is_empty() && field.starts_with("ports") =>
+            {
+                CliError::InvalidArguments(e.to_string())
+            }
+            _ => CliError::Config(e.to_string()),
+        })
+}
+
+/// Create the configuration builder [`load_configuration`] uses, with the
+/// data directory and profile from the global options applied.
+pub fn configuration_builder(global: &GlobalOptions) -> ConfigBuilder {
     let mut builder = ConfigBuilder::new();
 
     // Set data directory if provided via --data-dir
@@ -72,38 +265,89 @@ pub fn load_configuration(global: &GlobalOptions) -> Result<Config, CliError> {
         builder = builder.with_data_dir(data_dir);
     }
 
-    // Build configuration from environment and files
-    let config = builder
-        .build()
-        .map_err(|e| CliError::Config(e.to_string()))?;
+    if let Some(ref profile) = global.profile {
+        builder = builder.with_profile(profile);
+    }
 
-    Ok(config)
+    builder
+}
+
+/// Resolve the database file name inside the data directory.
+///
+/// Priority: `--db-name` (or `TROP_DB_NAME`) > `database_filename` in the
+/// configuration > `trop.db`. A configuration that fails to load is
+/// ignored here; the command reports it when it loads the configuration
+/// itself.
+///
+/// # Errors
+///
+/// Returns `InvalidArguments` if the name is not a plain file name.
+pub fn resolve_database_filename(global: &GlobalOptions) -> Result<String, CliError> {
+    let name = match global.db_name {
+        Some(ref name) => name.clone(),
+        None => load_configuration(global)
+            .ok()
+            .and_then(|config| config.database_filename)
+            .unwrap_or_else(|| DEFAULT_DATABASE_FILENAME.to_string()),
+    };
+
+    validate_database_filename(&name).map_err(|e| CliError::InvalidArguments(e.to_string()))?;
+    Ok(name)
 }
 
 /// Resolve the database path from global options.
-fn resolve_database_path(global: &GlobalOptions) -> Result<PathBuf, CliError> {
+pub fn resolve_database_path(global: &GlobalOptions) -> Result<PathBuf, CliError> {
+    let db_name = resolve_database_filename(global)?;
+
     // Priority: global option > default
     if let Some(ref data_dir) = global.data_dir {
-        return Ok(data_dir.join("trop.db"));
+        return Ok(data_dir.join(db_name));
     }
 
     // Default: ~/.trop/trop.db
     let home_dir = home::home_dir()
         .ok_or_else(|| CliError::Config("Could not determine home directory".to_string()))?;
 
-    Ok(home_dir.join(".trop").join("trop.db"))
+    Ok(home_dir.join(".trop").join(db_name))
 }
 
 /// Open database with configuration.
 ///
 /// # Errors
 ///
-/// Returns `NoDataDirectory` if the database doesn't exist and auto-init is disabled.
+/// Returns `NoDataDirectory` if the database doesn't exist and auto-init is
+/// disabled, either by `--disable-autoinit` or by `disable_autoinit` in the
+/// configuration.
 pub fn open_database(global: &GlobalOptions, config: &Config) -> Result<Database, CliError> {
+    Database::open(database_config(global, config)?).map_err(CliError::from)
+}
+
+/// Open the database like [`open_database`], retrying lock contention for
+/// up to `wait` as [`retry_on_lock`] does.
+///
+/// Opening takes a write lock to set up or fix the schema, so commands that
+/// accept `--wait` must not open the database outside the retry.
+///
+/// # Errors
+///
+/// Returns the same errors as [`open_database`], and `Timeout` if the
+/// database is still locked when `wait` runs out.
+pub fn open_database_with_wait(
+    global: &GlobalOptions,
+    config: &Config,
+    wait: Option<Duration>,
+) -> Result<Database, CliError> {
+    let db_config = database_config(global, config)?;
+    retry_on_lock(wait, || Database::open(db_config.clone()))
+}
+
+/// Build the database configuration [`open_database`] opens.
+fn database_config(global: &GlobalOptions, config: &Config) -> Result<DatabaseConfig, CliError> {
     let db_path = resolve_database_path(global)?;
 
-    if !db_path.exists() && global.disable_autoinit {
-        return Err(CliError::NoDataDirectory);
+    let autoinit_disabled = global.disable_autoinit || config.disable_autoinit == Some(true);
+    if !db_path.exists() && autoinit_disabled {
+        return Err(CliError::NoDataDirectory(db_path));
     }
 
     let mut db_config = DatabaseConfig::new(db_path);
@@ -116,7 +360,22 @@ pub fn open_database(global: &GlobalOptions, config: &Config) -> Result<Database
         db_config = db_config.with_busy_timeout(std::time::Duration::from_secs(timeout_seconds));
     }
 
-    Database::open(db_config).map_err(CliError::from)
+    Ok(db_config)
+}
+
+/// Open the database for a dry run without creating it.
+///
+/// An existing database is opened as usual. Otherwise an empty in-memory
+/// database stands in, so planning sees no reservations and nothing is
+/// written to disk.
+pub fn open_dry_run_database(
+    global: &GlobalOptions,
+    config: &Config,
+) -> Result<Database, CliError> {
+    if resolve_database_path(global)?.exists() {
+        return open_database(global, config);
+    }
+    Database::open(DatabaseConfig::new(":memory:")).map_err(CliError::from)
 }
 
 /// Format a timestamp for display.
@@ -126,6 +385,152 @@ pub fn format_timestamp(ts: std::time::SystemTime) -> String {
     dt.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
+/// Format a timestamp as RFC 3339 in UTC (e.g. `2024-05-01T12:00:00Z`).
+pub fn format_timestamp_rfc3339(ts: std::time::SystemTime) -> String {
+    use chrono::{DateTime, SecondsFormat, Utc};
+    let dt: DateTime<Utc> = ts.into();
+    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// First delay between attempts in [`retry_on_lock`].
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Longest delay between attempts in [`retry_on_lock`].
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Run `attempt`, retrying lock contention for up to `wait`.
+///
+/// Errors that [`trop::Error::is_retryable`] accepts are retried with
+/// exponential backoff until `wait` has elapsed, after which the command
+/// gives up with [`CliError::Timeout`]. Permanent errors, and any error when
+/// `wait` is `None`, are returned straight away.
+pub fn retry_on_lock<T>(
+    wait: Option<Duration>,
+    mut attempt: impl FnMut() -> trop::Result<T>,
+) -> Result<T, CliError> {
+    let Some(wait) = wait else {
+        return attempt().map_err(CliError::from);
+    };
+
+    let deadline = Instant::now() + wait;
+    let mut delay = INITIAL_RETRY_DELAY;
+    loop {
+        match attempt() {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_retryable() => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(CliError::Timeout);
+                }
+                std::thread::sleep(delay.min(deadline - now));
+                delay = (delay * 2).min(MAX_RETRY_DELAY);
+            }
+            Err(e) => return Err(CliError::from(e)),
+        }
+    }
+}
+
+/// Parse a humantime-style duration such as `90s`, `30m`, `2d`, or `1h30m`.
+///
+/// Units are `s`, `m`, `h`, `d`, and `w` (or their long forms, such as
+/// `min`, `hours`, or `days`). The total must be greater than zero.
+pub fn parse_duration(s: &str) -> Result<std::time::Duration, CliError> {
+    let invalid =
+        |reason: &str| CliError::InvalidArguments(format!("Invalid duration '{s}': {reason}"));
+
+    let mut total: u64 = 0;
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        return Err(invalid("expected a value like 30m or 2d"));
+    }
+
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(invalid("expected a number before each unit"));
+        }
+        let value: u64 = rest[..digits]
+            .parse()
+            .map_err(|_| invalid("number is too large"))?;
+        rest = rest[digits..].trim_start();
+
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let seconds_per_unit = match &rest[..unit_len] {
+            "s" | "sec" | "secs" | "second" | "seconds" => 1,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600,
+            "d" | "day" | "days" => 86_400,
+            "w" | "week" | "weeks" => 604_800,
+            "" => return Err(invalid("missing unit (s, m, h, d, or w)")),
+            unit => return Err(invalid(&format!("unknown unit '{unit}'"))),
+        };
+        rest = rest[unit_len..].trim_start();
+
+        total = value
+            .checked_mul(seconds_per_unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| invalid("duration is too large"))?;
+    }
+
+    if total == 0 {
+        return Err(invalid("duration must be greater than zero"));
+    }
+    Ok(std::time::Duration::from_secs(total))
+}
+
+/// Parse a point in time for a `--*-since`/`--*-before` filter.
+///
+/// Accepts an RFC 3339 timestamp (`2024-05-01T00:00:00Z`) or a duration as
+/// accepted by [`parse_duration`], meaning that long before now (`7d` is
+/// seven days ago).
+pub fn parse_time_bound(s: &str) -> Result<std::time::SystemTime, CliError> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(s.trim()) {
+        return Ok(time.into());
+    }
+
+    let ago = parse_duration(s).map_err(|_| {
+        CliError::InvalidArguments(format!(
+            "Invalid time '{s}': expected an RFC 3339 timestamp (e.g. 2024-05-01T00:00:00Z) or a duration ago (e.g. 7d)"
+        ))
+    })?;
+    std::time::SystemTime::now()
+        .checked_sub(ago)
+        .ok_or_else(|| {
+            CliError::InvalidArguments(format!("Invalid time '{s}': too far in the past"))
+        })
+}
+
+/// Parse `--exclude` values into port exclusions.
+///
+/// Each value uses the `TROP_EXCLUDED_PORTS` syntax: a port (`5001`), a
+/// range (`5000..5010`), or a comma-separated mix of both.
+pub fn parse_exclude_args(values: &[String]) -> Result<Vec<PortExclusion>, CliError> {
+    let mut exclusions = Vec::new();
+    for value in values {
+        let parsed = EnvironmentConfig::parse_excluded_ports(value)
+            .and_then(|parsed| {
+                let check = Config {
+                    excluded_ports: Some(parsed),
+                    ..Config::default()
+                };
+                ConfigValidator::validate(&check, false)?;
+                Ok(check.excluded_ports.unwrap_or_default())
+            })
+            .map_err(|e| match e {
+                trop::Error::Validation { message, .. } => {
+                    CliError::InvalidArguments(format!("Invalid --exclude '{value}': {message}"))
+                }
+                other => CliError::from(other),
+            })?;
+        exclusions.extend(parsed);
+    }
+    Ok(exclusions)
+}
+
 /// Shorten a path for display.
 ///
 /// If the path is within the home directory, show it as ~/...
@@ -139,6 +544,85 @@ pub fn shorten_path(path: &Path) -> String {
     path.display().to_string()
 }
 
+/// Truncate `text` to at most `max` characters, replacing the start with `…`.
+///
+/// The end of a path is usually the part that identifies it, so that is
+/// what gets kept.
+pub fn truncate_start(text: &str, max: usize) -> String {
+    let len = text.chars().count();
+    if len <= max || max == 0 {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(len - (max - 1)).collect();
+    format!("…{tail}")
+}
+
+/// Render rows as a table with space-padded, left-aligned columns.
+///
+/// Each column is as wide as its longest cell (header included), columns
+/// are separated by two spaces, and the last column is not padded. With
+/// `color`, the header is bold and the first column is highlighted.
+pub fn format_aligned_table(headers: &[&str], rows: &[Vec<String>], color: bool) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render = |cells: &mut dyn Iterator<Item = &str>, header: bool| {
+        let mut line = String::new();
+        for (i, (cell, width)) in cells.zip(&widths).enumerate() {
+            if i > 0 {
+                line.push_str("  ");
+            }
+            // Escapes wrap the cell text only, so padding stays aligned
+            if header {
+                line.push_str(&paint(cell, Style::Header, color));
+            } else if i == 0 {
+                line.push_str(&paint(cell, Style::Highlight, color));
+            } else {
+                line.push_str(cell);
+            }
+            let padding = width.saturating_sub(cell.chars().count());
+            line.extend(std::iter::repeat(' ').take(padding));
+        }
+        line.trim_end().to_string()
+    };
+
+    let mut lines = vec![render(&mut headers.iter().copied(), true)];
+    lines.extend(
+        rows.iter()
+            .map(|row| render(&mut row.iter().map(String::as_str), false)),
+    );
+    lines.join("\n")
+}
+
+/// Write a header and rows to stdout as delimited text (CSV or TSV).
+///
+/// Fields containing the delimiter, a quote, or a newline are quoted with
+/// embedded quotes doubled, so the output parses back to the same values.
+pub fn write_delimited(
+    headers: &[&str],
+    rows: &[Vec<String>],
+    delimiter: u8,
+) -> Result<(), CliError> {
+    let csv_error = |e: csv::Error| CliError::Io(std::io::Error::new(std::io::ErrorKind::Other, e));
+
+    let stdout = std::io::stdout();
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(stdout.lock());
+
+    writer.write_record(headers).map_err(csv_error)?;
+    for row in rows {
+        writer.write_record(row).map_err(csv_error)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
 /// Format port allocations using the specified output format.
 ///
 /// This function extracts environment variable mappings from the config
@@ -149,6 +633,9 @@ pub fn shorten_path(path: &Path) -> String {
 /// * `output_format` - The desired output format (export, json, dotenv, human)
 /// * `allocations` - Map of service tags to allocated ports
 /// * `config` - Configuration containing service definitions with env mappings
+/// * `group` - Name of the reservation group that was reserved, if one was chosen
+/// * `tag_case` - Tag case policy the allocations were keyed with
+/// * `env_prefix` - Prefix for every variable name in export and dotenv output
 ///
 /// # Returns
 ///
@@ -157,9 +644,18 @@ pub fn format_allocations(
     output_format: &OutputFormat,
     allocations: &HashMap<String, Port>,
     config: &Config,
+    group: Option<&str>,
+    tag_case: TagCase,
+    env_prefix: Option<&str>,
 ) -> Result<String, CliError> {
-    // Extract environment variable mappings from config if present
-    let env_mappings = config.reservations.as_ref().map(|group| {
+    // Extract environment variable mappings from the reserved group if present
+    let reserved_group = config
+        .reservations
+        .as_ref()
+        .map(|groups| groups.select(group))
+        .transpose()
+        .map_err(CliError::from)?;
+    let env_mappings = reserved_group.map(|group| {
         group
             .services
             .iter()
@@ -167,15 +663,87 @@ pub fn format_allocations(
                 service
                     .env
                     .as_ref()
-                    .map(|env_name| (tag.clone(), env_name.clone()))
+                    .map(|env_name| (tag_case.normalize(tag.clone()), env_name.clone()))
             })
             .collect::<HashMap<String, String>>()
     });
 
-    let formatter = output_format.create_formatter(env_mappings);
+    let formatter = output_format.create_formatter(env_mappings, env_prefix);
     formatter.format(allocations).map_err(CliError::from)
 }
 
+/// Check that `--output-file` may be written before doing any work.
+///
+/// Fails with invalid arguments if the file already exists and `force` is not
+/// set, so nothing is reserved when the output could not be delivered.
+pub fn check_output_file(path: &Path, force: bool) -> Result<(), CliError> {
+    if path.exists() && !force {
+        return Err(CliError::InvalidArguments(format!(
+            "Output file already exists: {} (use --force to overwrite)",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Emit a command's formatted output, to stdout or atomically to a file.
+///
+/// The file receives exactly what stdout would have (including the trailing
+/// newline). It is written to a temporary file in the same directory and
+/// then renamed into place, so an existing file is never left half-written.
+pub fn emit_output(output: &str, output_file: Option<&Path>) -> Result<(), CliError> {
+    let Some(path) = output_file else {
+        println!("{output}");
+        return Ok(());
+    };
+
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| {
+            CliError::InvalidArguments(format!("Invalid output file: {}", path.display()))
+        })?
+        .to_string_lossy();
+    let temp_path = dir.join(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    let result = write_and_rename(&temp_path, path, format!("{output}\n").as_bytes());
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result.map_err(CliError::Io)
+}
+
+/// Merge dotenv `assignments` into the file at `path`, replacing it atomically.
+///
+/// Only lines assigning one of the variables change; see
+/// [`trop::output::merge_dotenv`]. A missing file is created.
+pub fn merge_into_dotenv_file(path: &Path, assignments: &str) -> Result<(), CliError> {
+    let existing = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(CliError::Io(e)),
+    };
+    let merged = trop::output::merge_dotenv(&existing, assignments);
+    // emit_output appends the final newline itself
+    emit_output(merged.strip_suffix('\n').unwrap_or(&merged), Some(path))
+}
+
+/// Write `contents` to `temp_path`, flush it to disk, and rename it to `path`.
+fn write_and_rename(temp_path: &Path, path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(temp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    std::fs::rename(temp_path, path)
+}
+
 /// Resolve the data directory path.
 ///
 /// Respects `TROP_DATA_DIR` environment variable, otherwise defaults to `~/.trop`.
@@ -189,41 +757,39 @@ pub fn resolve_data_dir() -> PathBuf {
     )
 }
 
-/// Find project configuration file (trop.yaml) starting from current directory.
+/// Find the project configuration file that applies in the current directory.
 ///
-/// Searches up the directory tree for `trop.local.yaml` or `trop.yaml`.
-/// Returns the first match found, with `trop.local.yaml` taking precedence.
+/// Uses the same discovery as configuration loading, so `trop.yaml`,
+/// `trop.json`, and their `trop.local.*` overrides are all found. Returns the
+/// highest-precedence tropfile, so a `trop.local.*` file wins.
 ///
 /// # Returns
 ///
 /// - `Ok(Some(path))` if a configuration file is found
 /// - `Ok(None)` if no configuration file is found
-/// - `Err(_)` if there's an error accessing the file system
+/// - `Err(_)` if the current directory cannot be determined
 pub fn find_project_config() -> Result<Option<PathBuf>, CliError> {
-    let mut current = env::current_dir()?;
-
-    loop {
-        // Check for trop.local.yaml first (higher precedence)
-        let local_config = current.join("trop.local.yaml");
-        if local_config.exists() {
-            return Ok(Some(local_config));
-        }
-
-        // Check for trop.yaml
-        let config = current.join("trop.yaml");
-        if config.exists() {
-            return Ok(Some(config));
-        }
+    let current = env::current_dir()?;
+    Ok(ConfigLoader::project_config_paths(&current).pop())
+}
 
-        // Move up one directory
-        if let Some(parent) = current.parent() {
-            current = parent.to_path_buf();
-        } else {
-            break;
-        }
+/// Write a configuration file, as JSON for a `.json` path and YAML otherwise.
+///
+/// Comments in the existing file are lost.
+pub fn write_config_file(path: &Path, config: &Config) -> Result<(), CliError> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let contents = if is_json {
+        serde_json::to_string_pretty(config)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string())
+    } else {
+        serde_yaml::to_string(config).map_err(|e| e.to_string())
     }
-
-    Ok(None)
+    .map_err(|e| CliError::Config(format!("Failed to serialize config: {e}")))?;
+    std::fs::write(path, contents)?;
+    Ok(())
 }
 
 /// Resolve the configuration file to use (project or global).
@@ -259,9 +825,202 @@ mod tests {
         assert!(formatted.contains("2024-01-15"));
     }
 
+    #[test]
+    fn test_prefers_human_output() {
+        assert!(prefers_human_output(None, true));
+        assert!(!prefers_human_output(None, false));
+        // A file is never a terminal, even when stdout is
+        assert!(!prefers_human_output(Some(Path::new("ports.json")), true));
+        assert!(!prefers_human_output(Some(Path::new("ports.json")), false));
+    }
+
+    #[test]
+    fn test_resolve_color_flag_overrides_environment() {
+        for (force, no_color, tty) in [
+            (None, None, false),
+            (Some("1"), None, false),
+            (None, Some("1"), true),
+            (Some("1"), Some("1"), true),
+        ] {
+            assert!(resolve_color(ColorChoice::Always, force, no_color, tty));
+            assert!(!resolve_color(ColorChoice::Never, force, no_color, tty));
+        }
+    }
+
+    #[test]
+    fn test_resolve_color_auto_environment() {
+        // CLICOLOR_FORCE enables color without a terminal
+        assert!(resolve_color(ColorChoice::Auto, Some("1"), None, false));
+        // CLICOLOR_FORCE=0 and empty values are ignored
+        assert!(!resolve_color(ColorChoice::Auto, Some("0"), None, false));
+        assert!(!resolve_color(ColorChoice::Auto, Some(""), None, false));
+        // NO_COLOR disables color on a terminal, and wins over CLICOLOR_FORCE
+        assert!(!resolve_color(ColorChoice::Auto, None, Some("1"), true));
+        assert!(!resolve_color(
+            ColorChoice::Auto,
+            Some("1"),
+            Some("1"),
+            true
+        ));
+        // Empty NO_COLOR is ignored
+        assert!(resolve_color(ColorChoice::Auto, None, Some(""), true));
+    }
+
+    #[test]
+    fn test_resolve_color_auto_tty_detection() {
+        assert!(resolve_color(ColorChoice::Auto, None, None, true));
+        assert!(!resolve_color(ColorChoice::Auto, None, None, false));
+    }
+
+    #[test]
+    fn test_error_prefix() {
+        assert_eq!(error_prefix(false), "Error:");
+        assert_eq!(error_prefix(true), "\x1b[1;31mError:\x1b[0m");
+    }
+
+    #[test]
+    fn test_emit_output_writes_file_with_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ports.env");
+        std::fs::write(&path, "stale contents that are longer\n").unwrap();
+
+        emit_output("WEB=5000", Some(&path)).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "WEB=5000\n");
+        // Only the output file remains; the temporary file was renamed away
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_check_output_file_requires_force_to_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ports.env");
+        assert!(check_output_file(&path, false).is_ok());
+
+        std::fs::write(&path, "").unwrap();
+        assert!(matches!(
+            check_output_file(&path, false),
+            Err(CliError::InvalidArguments(_))
+        ));
+        assert!(check_output_file(&path, true).is_ok());
+    }
+
+    #[test]
+    fn test_parse_duration_units() {
+        use std::time::Duration;
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(
+            parse_duration("2 days").unwrap(),
+            Duration::from_secs(172_800)
+        );
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("5").is_err());
+        assert!(parse_duration("3 fortnights").is_err());
+    }
+
+    #[test]
+    fn test_parse_exclude_args() {
+        let exclusions =
+            parse_exclude_args(&["5001".to_string(), "5005..5009, 5020".to_string()]).unwrap();
+        assert_eq!(
+            exclusions,
+            vec![
+                PortExclusion::Single(5001),
+                PortExclusion::Range {
+                    start: 5005,
+                    end: 5009
+                },
+                PortExclusion::Single(5020),
+            ]
+        );
+
+        for bad in ["abc", "5010..5000", "0", "5000..x"] {
+            assert!(
+                matches!(
+                    parse_exclude_args(&[bad.to_string()]),
+                    Err(CliError::InvalidArguments(_))
+                ),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_time_bound() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+        assert_eq!(
+            parse_time_bound("2024-01-15T10:30:45Z").unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1_705_314_645)
+        );
+        assert_eq!(
+            parse_time_bound("2024-01-15T12:30:45+02:00").unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1_705_314_645)
+        );
+
+        let week_ago = parse_time_bound("7d").unwrap();
+        let expected = SystemTime::now() - Duration::from_secs(7 * 86_400);
+        let skew = expected
+            .duration_since(week_ago)
+            .unwrap_or_else(|e| e.duration());
+        assert!(skew < Duration::from_secs(5));
+
+        assert!(matches!(
+            parse_time_bound("last tuesday"),
+            Err(CliError::InvalidArguments(_))
+        ));
+    }
+
     #[test]
     fn test_shorten_path_outside_home() {
         let path = PathBuf::from("/usr/local/bin");
         assert_eq!(shorten_path(&path), "/usr/local/bin");
     }
+
+    #[test]
+    fn test_truncate_start() {
+        assert_eq!(truncate_start("short", 10), "short");
+        assert_eq!(truncate_start("/a/very/long/path", 8), "…ng/path");
+        assert_eq!(truncate_start("/a/very/long/path", 8).chars().count(), 8);
+    }
+
+    #[test]
+    fn test_format_aligned_table() {
+        let rows = vec![
+            vec![
+                "5000".to_string(),
+                "~/long-project".to_string(),
+                "-".to_string(),
+            ],
+            vec!["12345".to_string(), "~/p".to_string(), "web".to_string()],
+        ];
+        let table = format_aligned_table(&["PORT", "PATH", "TAG"], &rows, false);
+        assert_eq!(
+            table,
+            "PORT   PATH            TAG\n\
+             5000   ~/long-project  -\n\
+             12345  ~/p             web"
+        );
+    }
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("Warning:", Style::Warning, false), "Warning:");
+        assert_eq!(
+            paint("Warning:", Style::Warning, true),
+            "\x1b[1;33mWarning:\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_format_aligned_table_color_keeps_alignment() {
+        let rows = vec![vec!["5000".to_string(), "~/p".to_string()]];
+        let table = format_aligned_table(&["PORT", "PATH"], &rows, true);
+        let plain: String = table
+            .replace("\x1b[1m", "")
+            .replace("\x1b[36m", "")
+            .replace("\x1b[0m", "");
+        assert_eq!(plain, format_aligned_table(&["PORT", "PATH"], &rows, false));
+        assert!(table.contains("\x1b[36m5000\x1b[0m"), "{table:?}");
+    }
 }
@@ -10,7 +10,7 @@
 //!
 //! **Information Commands**:
 //! - `show-data-dir`: Display resolved data directory path
-//! - `show-path`: Display resolved/canonicalized path
+//! - `show-path`: Display resolved/canonicalized path, optionally relative to a base
 //! - `port-info`: Display detailed port information with occupancy
 //!
 //! **Configuration Commands**:
@@ -245,6 +245,70 @@ fn test_assert_reservation_with_tag() {
     );
 }
 
+/// Test assert-reservation --port passes when the reservation is on that port.
+#[test]
+fn test_assert_reservation_port_matches() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("deployed");
+    let port = env.reserve_with_tag(&test_path, "web");
+
+    env.command()
+        .arg("assert-reservation")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--tag", "web", "--port", &port.to_string()])
+        .assert()
+        .success()
+        .stdout(format!("{port}\n"));
+}
+
+/// Test assert-reservation --port fails with both ports named on a mismatch.
+#[test]
+fn test_assert_reservation_port_mismatch() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("deployed");
+    let port = env.reserve_with_tag(&test_path, "web");
+    let expected = if port == 65535 { port - 1 } else { port + 1 };
+
+    let output = env
+        .command()
+        .arg("assert-reservation")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--tag", "web", "--port", &expected.to_string()])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(&format!("is on port {port}, expected {expected}")),
+        "{stderr}"
+    );
+}
+
+/// Test assert-reservation --port still fails when there is no reservation.
+#[test]
+fn test_assert_reservation_port_missing_reservation() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("never-reserved");
+    env.reserve_simple(&env.create_dir("other"));
+
+    let output = env
+        .command()
+        .arg("assert-reservation")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--port", "5000"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("no reservation found"));
+}
+
 // ============================================================================
 // Assertion Command Tests: assert-port
 // ============================================================================
@@ -675,6 +739,83 @@ fn test_show_path_uses_cwd_by_default() {
     );
 }
 
+/// Test show-path --relative-to prints paths under BASE relative to it.
+///
+/// The base is resolved the same way as the path, so this holds with and
+/// without --canonicalize.
+#[test]
+fn test_show_path_relative_to_base() {
+    let env = TestEnv::new();
+    let nested = env.create_dir("project/service");
+
+    for extra in [None, Some("--canonicalize")] {
+        let mut cmd = env.command();
+        cmd.arg("show-path")
+            .arg("--path")
+            .arg(&nested)
+            .arg("--relative-to")
+            .arg(env.path());
+        if let Some(flag) = extra {
+            cmd.arg(flag);
+        }
+        let output = cmd.output().expect("Failed to run show-path");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+        assert_eq!(
+            stdout.trim(),
+            Path::new("project").join("service").to_str().unwrap()
+        );
+    }
+}
+
+/// Test show-path --relative-to keeps paths outside BASE absolute.
+#[test]
+fn test_show_path_relative_to_outside_base() {
+    let env = TestEnv::new();
+    let base = env.create_dir("base");
+    let other = env.create_dir("other");
+
+    let output = env
+        .command()
+        .arg("show-path")
+        .arg("--path")
+        .arg(&other)
+        .arg("--relative-to")
+        .arg(&base)
+        .output()
+        .expect("Failed to run show-path");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let shown_path = stdout.trim();
+    assert!(
+        Path::new(shown_path).is_absolute(),
+        "Output should be absolute path: {shown_path}"
+    );
+    assert!(shown_path.ends_with("other"));
+}
+
+/// Test show-path --relative-to applies to the current directory default.
+#[test]
+fn test_show_path_relative_to_with_cwd_default() {
+    let env = TestEnv::new();
+    let test_dir = env.create_dir("test");
+
+    let mut cmd = Command::cargo_bin("trop").unwrap();
+    let output = cmd
+        .arg("show-path")
+        .arg("--relative-to")
+        .arg(env.path())
+        .current_dir(&test_dir)
+        .output()
+        .expect("Failed to run show-path");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert_eq!(stdout.trim(), "test");
+}
+
 // ============================================================================
 // Information Command Tests: port-info
 // ============================================================================
@@ -790,6 +931,174 @@ fn test_port_info_include_occupancy() {
     );
 }
 
+/// Test port-info --skip-occupancy reports occupancy as skipped instead of
+/// probing a port that is in use.
+#[test]
+fn test_port_info_skip_occupancy_reports_skipped() {
+    let env = TestEnv::new();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().unwrap().port().to_string();
+
+    let output = env
+        .command()
+        .args([
+            "port-info",
+            &port,
+            "--include-occupancy",
+            "--skip-occupancy",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(
+        stdout.contains("Skipped"),
+        "occupancy should be skipped: {stdout}"
+    );
+    assert!(
+        !stdout.contains("currently in use"),
+        "port was probed: {stdout}"
+    );
+
+    let output = env
+        .command()
+        .args([
+            "port-info",
+            &port,
+            "--include-occupancy",
+            "--skip-occupancy",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["occupancy"], serde_json::json!({ "skipped": true }));
+
+    // Without --include-occupancy there is no occupancy section to skip
+    let output = env
+        .command()
+        .args(["port-info", &port, "--skip-occupancy", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("occupancy").is_none());
+
+    drop(listener);
+}
+
+/// Test port-info --with-process names the owner of an occupied port.
+///
+/// The owner is resolved with system tools, so the process line may be
+/// missing when none are installed; when present, it must be this process.
+#[test]
+fn test_port_info_with_process() {
+    let env = TestEnv::new();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().unwrap().port().to_string();
+
+    let output = env
+        .command()
+        .args(["port-info", &port, "--include-occupancy", "--with-process"])
+        .output()
+        .expect("Failed to run port-info");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(stdout.contains("Port is currently in use"), "{stdout}");
+    if let Some(line) = stdout.lines().find(|l| l.contains("Process:")) {
+        assert!(
+            line.contains(&format!("PID {})", std::process::id()))
+                || line.ends_with(&format!("PID {}", std::process::id())),
+            "{stdout}"
+        );
+    }
+    drop(listener);
+
+    // The flag only makes sense alongside the occupancy check
+    env.command()
+        .args(["port-info", &port, "--with-process"])
+        .assert()
+        .failure();
+}
+
+/// Test port-info --format json for a reserved port.
+///
+/// **Contract**: the object carries the full reservation record and path
+/// existence, and no occupancy without --include-occupancy
+#[test]
+fn test_port_info_json_reserved() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let port = env.reserve_with_tag(&test_path, "web");
+
+    let output = env
+        .command()
+        .args(["port-info", &port.to_string(), "--format", "json"])
+        .output()
+        .expect("Failed to run port-info");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["port"], port);
+    assert_eq!(json["reserved"], true);
+    let reservation = &json["reservation"];
+    assert_eq!(reservation["path"], test_path.to_str().unwrap());
+    assert_eq!(reservation["tag"], "web");
+    assert_eq!(reservation["pinned"], false);
+    assert_eq!(reservation["path_exists"], true);
+    assert!(reservation["created_at"].is_string());
+    assert!(json.get("occupancy").is_none(), "{json}");
+}
+
+/// Test port-info --format json for an unreserved port.
+///
+/// **Contract**: still valid JSON, with `reserved: false`
+#[test]
+fn test_port_info_json_unreserved() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .args(["port-info", "9999", "--format", "json"])
+        .output()
+        .expect("Failed to run port-info");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["port"], 9999);
+    assert_eq!(json["reserved"], false);
+    assert!(json["reservation"].is_null());
+}
+
+/// Test port-info --format json includes occupancy only with the flag.
+#[test]
+fn test_port_info_json_include_occupancy() {
+    let env = TestEnv::new();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().unwrap().port().to_string();
+
+    let output = env
+        .command()
+        .args([
+            "port-info",
+            &port,
+            "--format",
+            "json",
+            "--include-occupancy",
+        ])
+        .output()
+        .expect("Failed to run port-info");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["reserved"], false);
+    assert_eq!(json["occupancy"]["occupied"], true, "{json}");
+    drop(listener);
+}
+
 // ============================================================================
 // Configuration Command Tests: validate
 // ============================================================================
@@ -985,91 +1294,312 @@ fn test_validate_nonexistent_file() {
         );
 }
 
-// ============================================================================
-// Configuration Command Tests: exclude
-// ============================================================================
+/// Write a monorepo with a valid root tropfile and two invalid service tropfiles.
+fn create_validate_monorepo(env: &TestEnv) -> std::path::PathBuf {
+    let root = env.create_dir("repo");
+    let invalid = "ports:\n  min: 9000\n  max: 5000\n";
+    fs::write(root.join("trop.yaml"), "project: repo\n").expect("Failed to write config");
+    fs::create_dir_all(root.join("a")).expect("Failed to create dir");
+    fs::create_dir_all(root.join("b")).expect("Failed to create dir");
+    fs::write(root.join("a/trop.yaml"), invalid).expect("Failed to write config");
+    fs::write(root.join("b/trop.yaml"), invalid).expect("Failed to write config");
+    root
+}
 
-/// Test exclude adds single port to exclusion list.
-///
-/// The most basic exclusion operation: adding one port to the config file's
-/// excluded_ports list.
-///
-/// **Postcondition**: Config file contains the new exclusion
-/// **Side effect**: Config file is modified (YAML comments lost)
+/// Test validate on a directory checks every tropfile and reports each by path.
 #[test]
-fn test_exclude_single_port() {
+fn test_validate_directory_all_valid() {
     let env = TestEnv::new();
+    let root = env.create_dir("repo");
+    fs::create_dir_all(root.join("services/api")).expect("Failed to create dir");
+    fs::write(root.join("trop.yaml"), "project: repo\n").expect("Failed to write config");
+    fs::write(root.join("services/api/trop.yaml"), "project: api\n")
+        .expect("Failed to write config");
 
-    // Create a config file
-    let config_path = env.path().join("trop.yaml");
-    let initial_config = "project: test\n";
-    fs::write(&config_path, initial_config).expect("Failed to write config");
-
-    // Exclude a port
-    env.command_bare()  // Use bare command to avoid --data-dir
-        .arg("exclude")
-        .arg("8080")
-        .current_dir(env.path())
-        .assert()
-        .success();
+    let output = env
+        .command()
+        .arg("validate")
+        .arg(&root)
+        .output()
+        .expect("Failed to run validate");
 
-    // Verify config was updated
-    let config_content = fs::read_to_string(&config_path).expect("Failed to read config");
-    assert!(
-        config_content.contains("8080") && config_content.contains("excluded_ports"),
-        "Config should contain excluded port: {config_content}"
-    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let api = root.join("services/api/trop.yaml");
+    assert!(stdout.contains(&format!("{}: valid", api.display())));
+    assert!(stdout.contains(&format!("{}: valid", root.join("trop.yaml").display())));
 }
 
-/// Test exclude adds port range to exclusion list.
-///
-/// Ranges are specified as "start..end" and should be parsed correctly.
-///
-/// **Format**: "8080..8090" creates `Range { start: 8080, end: 8090 }`
+/// Test validate on a directory reports all failures and exits 1.
 #[test]
-fn test_exclude_port_range() {
+fn test_validate_directory_reports_all_failures() {
     let env = TestEnv::new();
+    let root = create_validate_monorepo(&env);
 
-    let config_path = env.path().join("trop.yaml");
-    fs::write(&config_path, "project: test\n").expect("Failed to write config");
+    let output = env
+        .command()
+        .arg("validate")
+        .arg(&root)
+        .output()
+        .expect("Failed to run validate");
 
-    // Exclude a range
-    env.command_bare()
-        .arg("exclude")
-        .arg("8080..8090")
-        .current_dir(env.path())
-        .assert()
-        .success();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
 
-    let config_content = fs::read_to_string(&config_path).expect("Failed to read config");
-    assert!(
-        config_content.contains("8080") && config_content.contains("8090"),
-        "Config should contain port range: {config_content}"
-    );
+    for dir in ["a", "b"] {
+        let path = root.join(dir).join("trop.yaml");
+        assert!(
+            stderr.contains(&format!("{}: Validation error", path.display())),
+            "stderr should attribute the error to {}: {stderr}",
+            path.display()
+        );
+    }
+    assert!(stdout.contains(&format!("{}: valid", root.join("trop.yaml").display())));
+    assert!(stderr.contains("2 of 3 configuration file(s) failed validation"));
 }
 
-/// Test exclude with --global flag writes to global config.
-///
-/// Without --global, exclude writes to project config (trop.yaml in cwd).
-/// With --global, it writes to ~/.trop/config.yaml.
-///
-/// **Location precedence**: Project config > Global config > Built-in defaults
-///
-/// NOTE: This test is currently ignored because the exclude command's --global
-/// flag implementation needs database initialization which affects the test setup.
-/// The functionality works in practice but requires more complex test infrastructure.
+/// Test validate --fail-fast stops at the first invalid tropfile.
 #[test]
-#[ignore]
-fn test_exclude_global_flag() {
+fn test_validate_directory_fail_fast() {
     let env = TestEnv::new();
+    let root = create_validate_monorepo(&env);
 
-    // Create global config location
-    let global_config = env.data_dir.join("config.yaml");
-    fs::create_dir_all(&env.data_dir).expect("Failed to create data dir");
-    fs::write(&global_config, "project: global\n").expect("Failed to write config");
-
-    // Exclude with --global
-    env.command()
+    let output = env
+        .command()
+        .arg("validate")
+        .arg(&root)
+        .arg("--fail-fast")
+        .output()
+        .expect("Failed to run validate");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
+
+    let first = root.join("a/trop.yaml");
+    let second = root.join("b/trop.yaml");
+    assert!(stderr.contains(&format!("Validation failed for {}", first.display())));
+    assert!(!stderr.contains(&second.display().to_string()));
+    assert!(stdout.is_empty(), "No files after the failure are checked");
+}
+
+/// Test validate --strict fails on settings repeated from trop.yaml, which
+/// normal validation accepts.
+#[test]
+fn test_validate_strict_rejects_redundant_local_fields() {
+    let env = TestEnv::new();
+    let root = env.create_dir("repo");
+    fs::write(
+        root.join("trop.yaml"),
+        "project: repo\nports:\n  min: 6000\n  max: 6100\n",
+    )
+    .expect("Failed to write config");
+    let local = root.join("trop.local.yaml");
+    fs::write(&local, "ports:\n  min: 6000\n  max: 6200\n").expect("Failed to write config");
+
+    let normal = env
+        .command()
+        .arg("validate")
+        .arg(&local)
+        .output()
+        .expect("Failed to run validate");
+    assert!(normal.status.success());
+
+    let strict = env
+        .command()
+        .arg("validate")
+        .arg(&local)
+        .arg("--strict")
+        .output()
+        .expect("Failed to run validate");
+    assert_eq!(strict.status.code(), Some(1));
+    let stderr = String::from_utf8(strict.stderr).expect("Invalid UTF-8");
+    assert!(
+        stderr.contains("warning: `ports.min` repeats the value set in trop.yaml"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("ports.max"), "stderr: {stderr}");
+}
+
+/// Test validate --strict flags built-in defaults and empty reservation groups.
+#[test]
+fn test_validate_strict_flags_defaults_and_empty_groups() {
+    let env = TestEnv::new();
+    let config = env.path().join("trop.yaml");
+    fs::write(
+        &config,
+        "project: app\nallocation_strategy: lowest\nreservations:\n  web:\n    services:\n      api: {}\n  worker:\n    services: {}\n",
+    )
+    .expect("Failed to write config");
+
+    let output = env
+        .command()
+        .arg("validate")
+        .arg(&config)
+        .arg("--strict")
+        .output()
+        .expect("Failed to run validate");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
+    assert!(stderr.contains("warning: reservation group 'worker' defines no services"));
+    assert!(stderr.contains("warning: `allocation_strategy` is set to its built-in default"));
+    assert!(!stderr.contains("project"), "stderr: {stderr}");
+}
+
+/// Test validate --strict passes a config with no redundant settings.
+#[test]
+fn test_validate_strict_accepts_clean_config() {
+    let env = TestEnv::new();
+    let config = env.path().join("trop.yaml");
+    fs::write(&config, "project: app\nports:\n  min: 6000\n  max: 6100\n")
+        .expect("Failed to write config");
+
+    env.command()
+        .arg("validate")
+        .arg(&config)
+        .arg("--strict")
+        .assert()
+        .success();
+}
+
+/// Test the `init --project-config` scaffold passes validate --strict.
+///
+/// The scaffold sets `ports.min` to the default, but `min` is required
+/// whenever `ports` is written, so it is not redundant.
+#[test]
+fn test_validate_strict_accepts_init_scaffold() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+
+    env.command()
+        .current_dir(&project)
+        .arg("init")
+        .arg("--project-config")
+        .assert()
+        .success();
+
+    env.command()
+        .arg("validate")
+        .arg(project.join("trop.yaml"))
+        .arg("--strict")
+        .assert()
+        .success();
+}
+
+// ============================================================================
+// Configuration Command Tests: exclude
+// ============================================================================
+
+/// Test exclude adds single port to exclusion list.
+///
+/// The most basic exclusion operation: adding one port to the config file's
+/// excluded_ports list.
+///
+/// **Postcondition**: Config file contains the new exclusion
+/// **Side effect**: Config file is modified (YAML comments lost)
+#[test]
+fn test_exclude_single_port() {
+    let env = TestEnv::new();
+
+    // Create a config file
+    let config_path = env.path().join("trop.yaml");
+    let initial_config = "project: test\n";
+    fs::write(&config_path, initial_config).expect("Failed to write config");
+
+    // Exclude a port
+    env.command_bare()  // Use bare command to avoid --data-dir
+        .arg("exclude")
+        .arg("8080")
+        .current_dir(env.path())
+        .assert()
+        .success();
+
+    // Verify config was updated
+    let config_content = fs::read_to_string(&config_path).expect("Failed to read config");
+    assert!(
+        config_content.contains("8080") && config_content.contains("excluded_ports"),
+        "Config should contain excluded port: {config_content}"
+    );
+}
+
+/// Test exclude finds a `trop.local.json` project config and keeps it JSON.
+#[test]
+fn test_exclude_writes_local_json_config() {
+    let env = TestEnv::new();
+
+    fs::write(env.path().join("trop.yaml"), "project: test\n").expect("Failed to write config");
+    let local_path = env.path().join("trop.local.json");
+    fs::write(&local_path, "{\"default_tag\": \"web\"}\n").expect("Failed to write config");
+
+    env.command_bare()
+        .arg("exclude")
+        .arg("8080")
+        .current_dir(env.path())
+        .assert()
+        .success();
+
+    let config_content = fs::read_to_string(&local_path).expect("Failed to read config");
+    let config: serde_json::Value =
+        serde_json::from_str(&config_content).expect("Config should still be JSON");
+    assert_eq!(config["default_tag"], "web", "{config_content}");
+    assert!(config_content.contains("8080"), "{config_content}");
+    assert_eq!(
+        fs::read_to_string(env.path().join("trop.yaml")).unwrap(),
+        "project: test\n"
+    );
+}
+
+/// Test exclude adds port range to exclusion list.
+///
+/// Ranges are specified as "start..end" and should be parsed correctly.
+///
+/// **Format**: "8080..8090" creates `Range { start: 8080, end: 8090 }`
+#[test]
+fn test_exclude_port_range() {
+    let env = TestEnv::new();
+
+    let config_path = env.path().join("trop.yaml");
+    fs::write(&config_path, "project: test\n").expect("Failed to write config");
+
+    // Exclude a range
+    env.command_bare()
+        .arg("exclude")
+        .arg("8080..8090")
+        .current_dir(env.path())
+        .assert()
+        .success();
+
+    let config_content = fs::read_to_string(&config_path).expect("Failed to read config");
+    assert!(
+        config_content.contains("8080") && config_content.contains("8090"),
+        "Config should contain port range: {config_content}"
+    );
+}
+
+/// Test exclude with --global flag writes to global config.
+///
+/// Without --global, exclude writes to project config (trop.yaml in cwd).
+/// With --global, it writes to ~/.trop/config.yaml.
+///
+/// **Location precedence**: Project config > Global config > Built-in defaults
+///
+/// NOTE: This test is currently ignored because the exclude command's --global
+/// flag implementation needs database initialization which affects the test setup.
+/// The functionality works in practice but requires more complex test infrastructure.
+#[test]
+#[ignore]
+fn test_exclude_global_flag() {
+    let env = TestEnv::new();
+
+    // Create global config location
+    let global_config = env.data_dir.join("config.yaml");
+    fs::create_dir_all(&env.data_dir).expect("Failed to create data dir");
+    fs::write(&global_config, "project: global\n").expect("Failed to write config");
+
+    // Exclude with --global
+    env.command()
         .arg("exclude")
         .arg("9000")
         .arg("--global")
@@ -1362,6 +1892,291 @@ excluded_ports:
     );
 }
 
+/// Test compact-exclusions keeps the reasons of merged entries.
+#[test]
+fn test_compact_exclusions_preserves_reasons() {
+    let env = TestEnv::new();
+
+    let config_path = env.path().join("trop.yaml");
+    let config = r#"
+project: test
+
+excluded_ports:
+  - port: 8080
+    reason: legacy proxy
+  - start: 8081
+    end: 8085
+    reason: staging cluster
+"#;
+    fs::write(&config_path, config).expect("Failed to write config");
+
+    env.command()
+        .arg("compact-exclusions")
+        .arg(&config_path)
+        .assert()
+        .success();
+
+    let after = fs::read_to_string(&config_path).expect("Failed to read config");
+    assert_eq!(after.matches("start:").count(), 1, "{after}");
+    assert!(
+        after.contains("legacy proxy; staging cluster"),
+        "Both reasons should survive compaction: {after}"
+    );
+}
+
+/// Test compact-exclusions --stdin compacts a piped exclusion list to stdout.
+#[test]
+fn test_compact_exclusions_stdin_list() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .arg("compact-exclusions")
+        .arg("--stdin")
+        .write_stdin("- 8080\n- 8081\n- 8082\n")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let exclusions: Vec<serde_yaml::Value> =
+        serde_yaml::from_slice(&output).expect("stdout should be a YAML list");
+    assert_eq!(exclusions.len(), 1, "Adjacent ports should form one range");
+    assert_eq!(exclusions[0]["start"].as_u64(), Some(8080));
+    assert_eq!(exclusions[0]["end"].as_u64(), Some(8082));
+}
+
+/// Test compact-exclusions --stdout prints the compacted config and leaves
+/// the file alone.
+#[test]
+fn test_compact_exclusions_stdout_does_not_modify_file() {
+    let env = TestEnv::new();
+
+    let config_path = env.path().join("trop.yaml");
+    let config = r#"
+project: test
+
+excluded_ports:
+  - 8080
+  - 8081
+"#;
+    fs::write(&config_path, config).expect("Failed to write config");
+
+    env.command()
+        .arg("compact-exclusions")
+        .arg(&config_path)
+        .arg("--stdout")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("project: test"))
+        .stdout(predicate::str::contains("start: 8080"));
+
+    let after = fs::read_to_string(&config_path).expect("Failed to read config");
+    assert_eq!(after, config, "--stdout should not modify the file");
+}
+
+// ============================================================================
+// Configuration Command Tests: exclusion reasons and list-exclusions
+// ============================================================================
+
+/// Test exclude --reason records the reason alongside the port.
+#[test]
+fn test_exclude_with_reason() {
+    let env = TestEnv::new();
+
+    let config_path = env.path().join("trop.yaml");
+    fs::write(&config_path, "project: test\n").expect("Failed to write config");
+
+    env.command_bare()
+        .arg("exclude")
+        .arg("5432")
+        .arg("--reason")
+        .arg("local postgres")
+        .current_dir(env.path())
+        .assert()
+        .success();
+
+    let config_content = fs::read_to_string(&config_path).expect("Failed to read config");
+    assert!(config_content.contains("port: 5432"), "{config_content}");
+    assert!(
+        config_content.contains("reason: local postgres"),
+        "{config_content}"
+    );
+}
+
+/// Test exclude --reason on an existing exclusion adds the reason instead
+/// of duplicating the entry.
+#[test]
+fn test_exclude_reason_updates_existing_entry() {
+    let env = TestEnv::new();
+
+    let config_path = env.path().join("trop.yaml");
+    fs::write(&config_path, "project: test\nexcluded_ports:\n  - 5432\n")
+        .expect("Failed to write config");
+
+    env.command_bare()
+        .arg("exclude")
+        .arg("5432")
+        .arg("--reason")
+        .arg("local postgres")
+        .current_dir(env.path())
+        .assert()
+        .success();
+
+    let config_content = fs::read_to_string(&config_path).expect("Failed to read config");
+    assert_eq!(
+        config_content.matches("5432").count(),
+        1,
+        "{config_content}"
+    );
+    assert!(
+        config_content.contains("reason: local postgres"),
+        "{config_content}"
+    );
+}
+
+/// Test exclude rejects an empty reason.
+#[test]
+fn test_exclude_empty_reason_rejected() {
+    let env = TestEnv::new();
+
+    fs::write(env.path().join("trop.yaml"), "project: test\n").expect("Failed to write config");
+
+    env.command_bare()
+        .arg("exclude")
+        .arg("5432")
+        .arg("--reason")
+        .arg("")
+        .current_dir(env.path())
+        .assert()
+        .failure();
+}
+
+/// Test list-exclusions shows reasons and the file each exclusion comes from.
+#[test]
+fn test_list_exclusions_shows_reasons_and_sources() {
+    let env = TestEnv::new();
+
+    let config = r#"
+project: test
+
+excluded_ports:
+  - port: 5432
+    reason: local postgres
+  - "6000..6010"
+"#;
+    fs::write(env.path().join("trop.yaml"), config).expect("Failed to write config");
+    fs::write(
+        env.path().join("trop.local.yaml"),
+        "excluded_ports:\n  - port: 5432\n    reason: pgbouncer\n",
+    )
+    .expect("Failed to write config");
+
+    let output = env
+        .command()
+        .arg("list-exclusions")
+        .current_dir(env.path())
+        .output()
+        .expect("Failed to run list-exclusions");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "PORTS\tREASON\tSOURCE");
+    assert_eq!(lines.len(), 3, "{stdout}");
+
+    let postgres: Vec<&str> = lines[1].split('\t').collect();
+    assert_eq!(postgres[0], "5432");
+    assert_eq!(postgres[1], "local postgres; pgbouncer");
+    assert!(postgres[2].contains("trop.yaml"));
+    assert!(postgres[2].contains("trop.local.yaml"));
+
+    let range: Vec<&str> = lines[2].split('\t').collect();
+    assert_eq!(range[0], "6000..6010");
+    assert_eq!(range[1], "-");
+}
+
+/// Test list-exclusions includes exclusions from `TROP_EXCLUDED_PORTS`.
+#[test]
+fn test_list_exclusions_includes_environment() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .arg("list-exclusions")
+        .env("TROP_EXCLUDED_PORTS", "7000")
+        .current_dir(env.path())
+        .output()
+        .expect("Failed to run list-exclusions");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("7000\t-\tTROP_EXCLUDED_PORTS"), "{stdout}");
+}
+
+/// Test list-exclusions compacts overlapping and adjacent entries across
+/// sources for display, keeping every reason and source.
+#[test]
+fn test_list_exclusions_compacts_across_sources() {
+    let env = TestEnv::new();
+
+    let config = r#"
+project: test
+
+excluded_ports:
+  - start: 6000
+    end: 6004
+    reason: vpn
+  - 9000
+"#;
+    fs::write(env.path().join("trop.yaml"), config).expect("Failed to write config");
+
+    let output = env
+        .command()
+        .arg("list-exclusions")
+        .arg("--format")
+        .arg("json")
+        .env("TROP_EXCLUDED_PORTS", "6005..6008")
+        .current_dir(env.path())
+        .output()
+        .expect("Failed to run list-exclusions");
+    assert!(output.status.success());
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    let entries = json.as_array().expect("Output should be an array");
+    assert_eq!(entries.len(), 2, "{json}");
+
+    assert_eq!(entries[0]["start"], 6000);
+    assert_eq!(entries[0]["end"], 6008);
+    assert_eq!(entries[0]["reason"], "vpn");
+    let sources = entries[0]["sources"].as_array().unwrap();
+    assert_eq!(sources.len(), 2);
+    assert!(sources[0].as_str().unwrap().ends_with("trop.yaml"));
+    assert_eq!(sources[1], "TROP_EXCLUDED_PORTS");
+
+    assert_eq!(entries[1]["start"], 9000);
+    assert_eq!(entries[1]["end"], 9000);
+    assert!(entries[1]["reason"].is_null());
+}
+
+/// Test list-exclusions JSON output is an empty array when nothing is excluded.
+#[test]
+fn test_list_exclusions_json_empty() {
+    let env = TestEnv::new();
+
+    env.command()
+        .arg("list-exclusions")
+        .arg("--format")
+        .arg("json")
+        .env_remove("TROP_EXCLUDED_PORTS")
+        .current_dir(env.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("[]"));
+}
+
 // ============================================================================
 // Scan Command Tests (Basic Functionality)
 // ============================================================================
@@ -1487,6 +2302,403 @@ fn test_scan_uses_config_range() {
     );
 }
 
+/// Test scan reports trop reservations in the range along with their holder.
+///
+/// A reserved port that is not bound on the system still appears, with
+/// status "reserved" and the reserving path/tag/project.
+#[test]
+fn test_scan_reports_reservation_holders() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("holder");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("web")
+        .arg("--project")
+        .arg("shop")
+        .arg("--port")
+        .arg("6913")
+        .arg("--allow-unrelated-path")
+        .arg("--ignore-occupied")
+        .assert()
+        .success();
+
+    let output = env
+        .command()
+        .arg("scan")
+        .arg("--min")
+        .arg("6910")
+        .arg("--max")
+        .arg("6915")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .expect("Failed to run scan");
+    assert!(output.status.success());
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("JSON output should be valid");
+    let entry = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["port"] == 6913)
+        .expect("Reserved port should appear in scan output");
+
+    assert!(entry["status"].as_str().unwrap().contains("reserved"));
+    assert_eq!(entry["reserved"], true);
+    assert!(entry["path"].as_str().unwrap().ends_with("holder"));
+    assert_eq!(entry["tag"], "web");
+    assert_eq!(entry["project"], "shop");
+
+    let table = env
+        .command()
+        .arg("scan")
+        .arg("--min")
+        .arg("6910")
+        .arg("--max")
+        .arg("6915")
+        .output()
+        .expect("Failed to run scan");
+    let stdout = String::from_utf8_lossy(&table.stdout);
+    assert!(stdout.contains("Holder"), "{stdout}");
+    assert!(stdout.contains("holder [web]"), "{stdout}");
+}
+
+/// Test scan --reserved-only lists just the reserved ports in the range,
+/// and --free-only lists every unoccupied port.
+#[test]
+fn test_scan_reserved_and_free_filters() {
+    let env = TestEnv::new();
+    let path = env.create_dir("filtered");
+    env.command()
+        .args(["reserve", "--port", "6933", "--allow-unrelated-path"])
+        .arg("--ignore-occupied")
+        .arg("--path")
+        .arg(&path)
+        .assert()
+        .success();
+
+    let scan = |filters: &[&str]| -> Vec<serde_json::Value> {
+        let output = env
+            .command()
+            .args(["scan", "--min", "6930", "--max", "6935", "--json"])
+            .args(filters)
+            .output()
+            .expect("Failed to run scan");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice::<serde_json::Value>(&output.stdout)
+            .expect("JSON output should be valid")
+            .as_array()
+            .unwrap()
+            .clone()
+    };
+
+    let reserved = scan(&["--reserved-only"]);
+    assert_eq!(reserved.len(), 1, "{reserved:?}");
+    assert_eq!(reserved[0]["port"], 6933);
+    assert_eq!(reserved[0]["reserved"], true);
+
+    // Free ports include unreserved ones, reported with status "free"
+    let free = scan(&["--free-only"]);
+    assert!(free.iter().any(|e| e["port"] == 6933), "{free:?}");
+    assert!(
+        free.iter()
+            .any(|e| e["status"] == "free" && e["reserved"] == false),
+        "{free:?}"
+    );
+    assert!(free.iter().all(|e| e["status"] != "occupied"), "{free:?}");
+
+    // Combined filters must both hold
+    let both = scan(&["--free-only", "--reserved-only"]);
+    assert_eq!(both.len(), 1, "{both:?}");
+    assert_eq!(both[0]["port"], 6933);
+}
+
+/// Test scan --exclude-reserved drops reserved ports from the output, and
+/// conflicts with --reserved-only.
+#[test]
+fn test_scan_exclude_reserved() {
+    let env = TestEnv::new();
+    let path = env.create_dir("excluded");
+    env.command()
+        .args(["reserve", "--port", "6943", "--allow-unrelated-path"])
+        .arg("--ignore-occupied")
+        .arg("--path")
+        .arg(&path)
+        .assert()
+        .success();
+
+    let scan = |filters: &[&str]| -> Vec<serde_json::Value> {
+        let output = env
+            .command()
+            .args(["scan", "--min", "6940", "--max", "6945", "--json"])
+            .args(filters)
+            .output()
+            .expect("Failed to run scan");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice::<serde_json::Value>(&output.stdout)
+            .expect("JSON output should be valid")
+            .as_array()
+            .unwrap()
+            .clone()
+    };
+
+    let all = scan(&[]);
+    assert!(all.iter().any(|e| e["port"] == 6943), "{all:?}");
+
+    let excluded = scan(&["--exclude-reserved"]);
+    assert!(excluded.iter().all(|e| e["port"] != 6943), "{excluded:?}");
+
+    // Still lists the unreserved free ports around it
+    let free = scan(&["--free-only", "--exclude-reserved"]);
+    assert!(free.iter().all(|e| e["reserved"] == false), "{free:?}");
+    assert!(free.iter().all(|e| e["port"] != 6943), "{free:?}");
+
+    env.command()
+        .args(["scan", "--exclude-reserved", "--reserved-only"])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+/// Test scan --occupied-only drops reserved ports that are not bound, and
+/// conflicts with --free-only.
+#[test]
+fn test_scan_occupied_only_filter() {
+    let env = TestEnv::new();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let bound = listener.local_addr().unwrap().port();
+    let port = bound.to_string();
+
+    let output = env
+        .command()
+        .args(["scan", "--min", &port, "--max", &port, "--json"])
+        .arg("--occupied-only")
+        .output()
+        .expect("Failed to run scan");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 1, "{json}");
+    assert_eq!(entries[0]["port"], bound);
+
+    let output = env
+        .command()
+        .args(["scan", "--min", &port, "--max", &port, "--json"])
+        .args(["--free-only"])
+        .output()
+        .expect("Failed to run scan");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.as_array().unwrap().is_empty(), "{json}");
+    drop(listener);
+
+    env.command()
+        .args(["scan", "--occupied-only", "--free-only"])
+        .assert()
+        .failure();
+}
+
+/// Test scan --with-process annotates occupied ports with their owner.
+///
+/// The owner is resolved with system tools, so the fields may be null when
+/// none are installed; when resolved, the PID must be this test process.
+#[test]
+fn test_scan_with_process_reports_owner() {
+    let env = TestEnv::new();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let bound = listener.local_addr().unwrap().port();
+    let port = bound.to_string();
+
+    let output = env
+        .command()
+        .args([
+            "scan",
+            "--min",
+            &port,
+            "--max",
+            &port,
+            "--json",
+            "--with-process",
+        ])
+        .output()
+        .expect("Failed to run scan");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("JSON output should be valid");
+    let entry = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["port"] == bound)
+        .expect("Bound port should appear in scan output");
+
+    let object = entry.as_object().unwrap();
+    assert!(object.contains_key("pid"), "{entry}");
+    assert!(object.contains_key("process_name"), "{entry}");
+    if let Some(pid) = entry["pid"].as_u64() {
+        assert_eq!(pid, u64::from(std::process::id()));
+    }
+    drop(listener);
+}
+
+/// Test scan only resolves owners when asked, and only for JSON output.
+#[test]
+fn test_scan_with_process_is_opt_in_and_json_only() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .args(["scan", "--min", "9990", "--max", "9995", "--json"])
+        .output()
+        .expect("Failed to run scan");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    for entry in json.as_array().unwrap() {
+        assert!(entry.get("pid").is_none(), "{entry}");
+    }
+
+    env.command()
+        .args(["scan", "--min", "9990", "--max", "9995", "--with-process"])
+        .assert()
+        .failure()
+        .code(4);
+
+    env.command()
+        .args(["scan", "--json", "--format", "csv"])
+        .assert()
+        .failure();
+}
+
+/// Test that watch mode with a count limit emits one JSON document per scan.
+#[test]
+fn test_scan_watch_emits_ndjson_per_iteration() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    env.command()
+        .args(["reserve", "--port", "6923", "--allow-unrelated-path"])
+        .arg("--ignore-occupied")
+        .arg("--path")
+        .arg(&path)
+        .assert()
+        .success();
+
+    let output = env
+        .command()
+        .args(["scan", "--min", "6920", "--max", "6925", "--format", "json"])
+        .args(["--watch", "0.05", "--count", "2"])
+        .output()
+        .expect("Failed to run scan");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    for line in lines {
+        let json: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(
+            json.as_array()
+                .unwrap()
+                .iter()
+                .any(|entry| entry["port"] == 6923),
+            "{line}"
+        );
+    }
+}
+
+/// Test that watch mode with an occupancy TTL still reports held ports on
+/// every iteration, from the cache after the first probe.
+#[test]
+fn test_scan_watch_with_occupancy_ttl() {
+    let env = TestEnv::new();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().unwrap().port();
+    let port_arg = port.to_string();
+
+    let output = env
+        .command()
+        .args([
+            "scan", "--min", &port_arg, "--max", &port_arg, "--format", "json",
+        ])
+        .args(["--watch", "0.05", "--count", "2", "--occupancy-ttl", "60"])
+        .output()
+        .expect("Failed to run scan");
+    assert!(output.status.success());
+    drop(listener);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    for line in lines {
+        let json: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(json[0]["port"], port, "{line}");
+        assert_eq!(json[0]["status"], "occupied", "{line}");
+    }
+}
+
+/// Test that a single watch iteration in table format does not clear a
+/// non-terminal stdout.
+#[test]
+fn test_scan_watch_single_table_iteration() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .args(["scan", "--min", "9990", "--max", "9995"])
+        .args(["--watch", "0.01", "--count", "1"])
+        .output()
+        .expect("Failed to run scan");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Port"), "{stdout:?}");
+    assert_eq!(stdout.matches("Port").count(), 1, "{stdout}");
+}
+
+/// Test that invalid watch arguments are rejected.
+#[test]
+fn test_scan_watch_rejects_invalid_arguments() {
+    let env = TestEnv::new();
+
+    env.command()
+        .args(["scan", "--watch", "0", "--count", "1"])
+        .assert()
+        .failure()
+        .code(4);
+
+    // --count only makes sense while watching
+    env.command()
+        .args(["scan", "--count", "1"])
+        .assert()
+        .failure();
+
+    // Watching must not rewrite the config on every scan
+    env.command()
+        .args(["scan", "--watch", "1", "--autoexclude"])
+        .assert()
+        .failure();
+}
+
 // Note: --autoexclude and --autocompact flags are difficult to test in
 // integration tests because they require actually occupied ports.
 // These are better tested manually or with mock occupancy checkers.
@@ -0,0 +1,159 @@
+//! Integration tests for the `backup` and `restore` commands.
+//!
+//! These tests verify that a database can be round-tripped through a backup
+//! file:
+//! - Reservations reappear after wiping the data directory and restoring
+//! - Backups and restores respect --data-dir
+//! - Restore refuses to overwrite an existing database without --force
+//! - Restore rejects files that are not valid trop databases
+
+mod common;
+
+use common::TestEnv;
+use std::fs;
+
+// ============================================================================
+// Round Trip Tests
+// ============================================================================
+
+/// Test that reservations survive backup, wiping the data dir, and restore.
+#[test]
+fn test_backup_wipe_restore_round_trip() {
+    let env = TestEnv::new();
+    let web = env.create_dir("web");
+    let api = env.create_dir("api");
+    let web_port = env.reserve_simple(&web);
+    let api_port = env.reserve_with_tag(&api, "api");
+
+    let backup = env.path().join("trop-backup.db");
+    let output = env.command().arg("backup").arg(&backup).output().unwrap();
+    assert!(
+        output.status.success(),
+        "backup failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(backup.exists());
+
+    fs::remove_dir_all(&env.data_dir).unwrap();
+    assert!(!common::contains_port(&env.list(), web_port));
+
+    // The list above recreated an empty database, so wipe it again
+    fs::remove_dir_all(&env.data_dir).unwrap();
+    let output = env.command().arg("restore").arg(&backup).output().unwrap();
+    assert!(
+        output.status.success(),
+        "restore failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let list = env.list();
+    assert!(common::contains_port(&list, web_port), "list: {list}");
+    assert!(common::contains_port(&list, api_port), "list: {list}");
+}
+
+/// Test that backup and restore use the database under --data-dir.
+#[test]
+fn test_backup_restore_respect_data_dir() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = env.reserve_simple(&dir);
+
+    let backup = env.path().join("trop-backup.db");
+    env.command().arg("backup").arg(&backup).assert().success();
+
+    let other = env.path().join("other-data");
+    env.command_bare()
+        .arg("--data-dir")
+        .arg(&other)
+        .arg("restore")
+        .arg(&backup)
+        .assert()
+        .success();
+    assert!(other.join("trop.db").exists());
+
+    let output = env
+        .command_bare()
+        .arg("--data-dir")
+        .arg(&other)
+        .arg("list")
+        .output()
+        .unwrap();
+    assert!(common::contains_port(
+        &String::from_utf8_lossy(&output.stdout),
+        port
+    ));
+}
+
+// ============================================================================
+// Safety Tests
+// ============================================================================
+
+/// Test that restore refuses to replace an existing database without --force.
+#[test]
+fn test_restore_requires_force_for_existing_database() {
+    let env = TestEnv::new();
+    let first = env.create_dir("first");
+    let first_port = env.reserve_simple(&first);
+
+    let backup = env.path().join("trop-backup.db");
+    env.command().arg("backup").arg(&backup).assert().success();
+
+    env.release(&first);
+    env.command()
+        .arg("restore")
+        .arg(&backup)
+        .assert()
+        .failure()
+        .code(4);
+    assert!(!common::contains_port(&env.list(), first_port));
+
+    env.command()
+        .args(["restore", "--force"])
+        .arg(&backup)
+        .assert()
+        .success();
+    assert!(common::contains_port(&env.list(), first_port));
+}
+
+/// Test that backup refuses to overwrite an existing file without --force.
+#[test]
+fn test_backup_requires_force_for_existing_file() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    env.reserve_simple(&dir);
+
+    let backup = env.path().join("trop-backup.db");
+    fs::write(&backup, "keep me").unwrap();
+
+    env.command()
+        .arg("backup")
+        .arg(&backup)
+        .assert()
+        .failure()
+        .code(4);
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "keep me");
+
+    env.command()
+        .args(["backup", "--force"])
+        .arg(&backup)
+        .assert()
+        .success();
+}
+
+/// Test that restore rejects a file that is not a trop database.
+#[test]
+fn test_restore_rejects_invalid_backup() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = env.reserve_simple(&dir);
+
+    let bogus = env.path().join("bogus.db");
+    fs::write(&bogus, "this is not a database").unwrap();
+
+    env.command()
+        .args(["restore", "--force"])
+        .arg(&bogus)
+        .assert()
+        .failure();
+    assert!(common::contains_port(&env.list(), port));
+}
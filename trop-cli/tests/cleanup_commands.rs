@@ -2,7 +2,7 @@
 //!
 //! These tests verify the CLI behavior of cleanup operations, including:
 //! - `prune`: Remove reservations for non-existent paths
-//! - `expire`: Remove reservations based on age
+//! - `expire`: Remove reservations based on age or an elapsed TTL
 //! - `autoclean`: Combined prune + expire operation
 //!
 //! Tests cover:
@@ -77,11 +77,21 @@ ports:
 /// always sets last_used_at to "now". So we create a reservation normally,
 /// then use SQL to update its timestamp directly.
 fn reserve_old_port(env: &TestEnv, path: &Path, days_old: u64) -> u16 {
+    reserve_aged_port(env, path, Duration::from_secs(days_old * 86400))
+}
+
+/// Like [`reserve_old_port`], but backdates by an arbitrary duration.
+fn reserve_aged_port(env: &TestEnv, path: &Path, age: Duration) -> u16 {
     // Create a normal reservation (this also ensures the database exists)
     let port = env.reserve_simple(path);
+    backdate_last_used(env, port, age);
+    port
+}
 
-    // Calculate the old timestamp (days_old days ago)
-    let old_time = SystemTime::now() - Duration::from_secs(days_old * 86400);
+/// Set the `last_used_at` timestamp of the reservation on `port` to `age` ago.
+fn backdate_last_used(env: &TestEnv, port: u16, age: Duration) {
+    // Calculate the old timestamp (age ago)
+    let old_time = SystemTime::now() - age;
     let old_timestamp = old_time
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
@@ -101,8 +111,6 @@ fn reserve_old_port(env: &TestEnv, path: &Path, days_old: u64) -> u16 {
         rusqlite::params![old_timestamp as i64, port as i64],
     )
     .expect("Failed to update timestamp");
-
-    port
 }
 
 // ============================================================================
@@ -396,6 +404,64 @@ fn test_prune_verbose_mode() {
     );
 }
 
+/// Test that `prune --path` leaves missing directories outside the subtree alone.
+#[test]
+fn test_prune_scoped_to_path() {
+    let env = TestEnv::new();
+    let mine = env.create_dir("mine");
+    let inside = env.create_dir("mine/api");
+    let outside = env.create_dir("theirs");
+    let inside_port = env.reserve_simple(&inside);
+    let outside_port = env.reserve_simple(&outside);
+    fs::remove_dir_all(&inside).unwrap();
+    fs::remove_dir_all(&outside).unwrap();
+
+    let output = env
+        .command()
+        .arg("prune")
+        .arg("--path")
+        .arg(&mine)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Removed 1 reservation(s)"), "{stderr}");
+    let list = env.list();
+    assert!(!list.contains(&inside_port.to_string()));
+    assert!(list.contains(&outside_port.to_string()));
+}
+
+/// Test that `prune --project` only considers that project's reservations.
+#[test]
+fn test_prune_scoped_to_project() {
+    let env = TestEnv::new();
+    let reserve = |name: &str, project: &str| {
+        let path = env.create_dir(name);
+        let output = env
+            .command()
+            .args(["reserve", "--allow-unrelated-path", "--project", project])
+            .arg("--path")
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        fs::remove_dir_all(&path).unwrap();
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    };
+    let mine = reserve("mine", "mine");
+    let theirs = reserve("theirs", "theirs");
+
+    env.command()
+        .args(["prune", "--project", "mine"])
+        .assert()
+        .success();
+
+    let list = env.list();
+    assert!(!list.contains(&mine));
+    assert!(list.contains(&theirs));
+}
+
 // ============================================================================
 // Expire Command Tests
 // ============================================================================
@@ -761,6 +827,378 @@ fn test_expire_boundary_threshold() {
     );
 }
 
+/// Test that --older-than overrides the configured threshold.
+///
+/// The default config expires after 30 days, so a 2-day-old reservation
+/// would normally survive; `--older-than 1d` expires it while keeping one
+/// that is only 12 hours old.
+#[test]
+fn test_expire_older_than_overrides_config() {
+    let env = TestEnv::new();
+
+    let stale = env.create_dir("stale");
+    let recent = env.create_dir("recent");
+
+    reserve_old_port(&env, &stale, 2);
+    reserve_aged_port(&env, &recent, Duration::from_secs(12 * 3600));
+
+    let output = env
+        .command()
+        .arg("expire")
+        .arg("--older-than")
+        .arg("1d")
+        .output()
+        .expect("Failed to run expire");
+
+    assert!(output.status.success());
+
+    let list_after = env.list();
+    assert!(
+        !list_after.contains(stale.to_str().unwrap()),
+        "2-day-old reservation should be expired"
+    );
+    assert!(
+        list_after.contains(recent.to_str().unwrap()),
+        "12-hour-old reservation should remain"
+    );
+}
+
+/// Test that --older-than rejects zero and negative durations.
+#[test]
+fn test_expire_older_than_rejects_non_positive() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    env.reserve_simple(&path);
+
+    for value in ["0d", "-1d"] {
+        let output = env
+            .command()
+            .arg("expire")
+            .arg(format!("--older-than={value}"))
+            .output()
+            .expect("Failed to run expire");
+
+        assert_eq!(
+            output.status.code(),
+            Some(4),
+            "--older-than {value} should be rejected"
+        );
+    }
+
+    assert!(env.list().contains(path.to_str().unwrap()));
+}
+
+// ============================================================================
+// Used-Before Cutoff Tests
+// ============================================================================
+
+/// Test that prune --used-before removes stale reservations whose paths exist.
+#[test]
+fn test_prune_used_before_removes_stale_existing_paths() {
+    let env = TestEnv::new();
+    let stale_dir = env.create_dir("stale");
+    let fresh_dir = env.create_dir("fresh");
+
+    let stale_port = reserve_old_port(&env, &stale_dir, 40);
+    let fresh_port = env.reserve_simple(&fresh_dir);
+
+    // Without the cutoff both paths exist, so nothing is pruned
+    env.command().arg("prune").assert().success();
+    assert!(common::contains_port(&env.list(), stale_port));
+
+    env.command()
+        .args(["prune", "--used-before", "30d"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Removed 1 reservation(s)"));
+
+    let list = env.list();
+    assert!(!common::contains_port(&list, stale_port), "list: {list}");
+    assert!(common::contains_port(&list, fresh_port), "list: {list}");
+}
+
+/// Test that expire --used-before applies without a day threshold.
+#[test]
+fn test_expire_used_before_cutoff() {
+    let env = TestEnv::new();
+    let old_dir = env.create_dir("old");
+    let recent_dir = env.create_dir("recent");
+
+    let old_port = reserve_old_port(&env, &old_dir, 5);
+    let recent_port = reserve_old_port(&env, &recent_dir, 1);
+
+    env.command()
+        .args(["expire", "--used-before", "3d", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Would expire 1 reservation(s)"));
+    assert!(common::contains_port(&env.list(), old_port));
+
+    env.command()
+        .args(["expire", "--used-before", "3d"])
+        .assert()
+        .success();
+
+    let list = env.list();
+    assert!(!common::contains_port(&list, old_port), "list: {list}");
+    assert!(common::contains_port(&list, recent_port), "list: {list}");
+}
+
+/// Test that an unparseable cutoff is rejected as an invalid argument.
+#[test]
+fn test_prune_used_before_rejects_invalid_time() {
+    let env = TestEnv::new();
+
+    env.command()
+        .args(["prune", "--used-before", "soon"])
+        .assert()
+        .failure()
+        .code(4);
+}
+
+// ============================================================================
+// TTL Tests
+// ============================================================================
+
+/// Reserve a port with a TTL, returning the port.
+fn reserve_with_ttl(env: &TestEnv, path: &Path, ttl: &str) -> u16 {
+    let output = env
+        .command()
+        .args(["reserve", "--allow-unrelated-path", "--ttl", ttl, "--path"])
+        .arg(path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "reserve --ttl failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    common::parse_port(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Move a reservation's TTL deadline into the past.
+fn backdate_ttl(env: &TestEnv, port: u16) {
+    let past = SystemTime::now() - Duration::from_secs(60);
+    let past = past
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let conn = rusqlite::Connection::open(env.data_dir.join("trop.db")).unwrap();
+    let updated = conn
+        .execute(
+            "UPDATE reservations SET expires_at = ?1 WHERE port = ?2 AND expires_at IS NOT NULL",
+            rusqlite::params![past as i64, i64::from(port)],
+        )
+        .unwrap();
+    assert_eq!(updated, 1, "reservation on port {port} has no TTL");
+}
+
+/// Test that expire removes a reservation past its TTL and keeps one without.
+///
+/// Reservations without a TTL keep the age-based behavior, so a fresh one
+/// survives even though the TTL'd one is removed.
+#[test]
+fn test_expire_removes_reservation_past_ttl() {
+    let env = TestEnv::new();
+    let scratch = env.create_dir("scratch");
+    let keeper = env.create_dir("keeper");
+
+    let scratch_port = reserve_with_ttl(&env, &scratch, "30m");
+    let keeper_port = env.reserve_simple(&keeper);
+    backdate_ttl(&env, scratch_port);
+
+    env.command()
+        .args(["expire", "--days", "30"])
+        .assert()
+        .success();
+
+    let list = env.list();
+    assert!(!common::contains_port(&list, scratch_port), "list: {list}");
+    assert!(common::contains_port(&list, keeper_port), "list: {list}");
+}
+
+/// Test that a reservation whose TTL is still running is kept.
+#[test]
+fn test_expire_keeps_reservation_within_ttl() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = reserve_with_ttl(&env, &dir, "2d");
+
+    let output = env.command().arg("expire").output().unwrap();
+    assert!(
+        output.status.success(),
+        "expire failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(common::contains_port(&env.list(), port));
+}
+
+/// Test that --ttl-only removes a reservation past its TTL.
+#[test]
+fn test_expire_ttl_only_removes_reservation_past_ttl() {
+    let env = TestEnv::new();
+    let scratch = env.create_dir("scratch");
+    let port = reserve_with_ttl(&env, &scratch, "30m");
+    backdate_ttl(&env, port);
+
+    env.command()
+        .args(["expire", "--ttl-only"])
+        .assert()
+        .success();
+
+    assert!(!common::contains_port(&env.list(), port));
+}
+
+/// Test that --ttl-only keeps an old reservation that has no TTL.
+///
+/// The age threshold is ignored entirely, so even a reservation far past
+/// the default `expire_after_days` survives.
+#[test]
+fn test_expire_ttl_only_keeps_old_reservation_without_ttl() {
+    let env = TestEnv::new();
+    let old = env.create_dir("old-project");
+    let port = reserve_old_port(&env, &old, 100);
+
+    env.command()
+        .args(["expire", "--ttl-only"])
+        .assert()
+        .success();
+
+    assert!(common::contains_port(&env.list(), port));
+
+    // Age-based flags make no sense alongside it
+    env.command()
+        .args(["expire", "--ttl-only", "--days", "7"])
+        .assert()
+        .failure();
+}
+
+/// Test that without --ttl-only the same old reservation still expires by age.
+#[test]
+fn test_expire_default_mode_still_expires_by_age() {
+    let env = TestEnv::new();
+    let old = env.create_dir("old-project");
+    let port = reserve_old_port(&env, &old, 100);
+
+    env.command().arg("expire").assert().success();
+
+    assert!(!common::contains_port(&env.list(), port));
+}
+
+/// Test that an unparseable TTL is rejected as an invalid argument.
+#[test]
+fn test_reserve_rejects_invalid_ttl() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+
+    for ttl in ["soon", "30", "5y", "0m"] {
+        env.command()
+            .args(["reserve", "--allow-unrelated-path", "--ttl", ttl, "--path"])
+            .arg(&dir)
+            .assert()
+            .failure()
+            .code(4);
+    }
+}
+
+/// Test that reserve --json reports the TTL deadline.
+#[test]
+fn test_reserve_json_includes_expires_at() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+
+    let output = env
+        .command()
+        .args([
+            "reserve",
+            "--allow-unrelated-path",
+            "--json",
+            "--ttl",
+            "1h30m",
+            "--path",
+        ])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["expires_at"].is_string(), "json: {json}");
+}
+
+// ============================================================================
+// Priority Tests
+// ============================================================================
+
+/// Reserve a port with a cleanup priority and backdate it past the default
+/// 30-day threshold, returning the port.
+fn reserve_stale_with_priority(env: &TestEnv, path: &Path, priority: i32) -> u16 {
+    let output = env
+        .command()
+        .args(["reserve", "--allow-unrelated-path", "--priority"])
+        .arg(priority.to_string())
+        .arg("--path")
+        .arg(path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "reserve --priority failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let port = common::parse_port(&String::from_utf8_lossy(&output.stdout));
+    backdate_last_used(env, port, Duration::from_secs(45 * 86400));
+    port
+}
+
+/// Test that expire keeps a stale reservation above the protected priority.
+#[test]
+fn test_expire_keeps_high_priority_reservation() {
+    let env = TestEnv::new();
+    let important = reserve_stale_with_priority(&env, &env.create_dir("important"), 5);
+    let ordinary = reserve_stale_with_priority(&env, &env.create_dir("ordinary"), 0);
+
+    env.command().arg("expire").assert().success();
+
+    let list = env.list();
+    assert!(common::contains_port(&list, important));
+    assert!(!common::contains_port(&list, ordinary));
+}
+
+/// Test that `expire --force` removes high-priority reservations as well.
+#[test]
+fn test_expire_force_removes_high_priority_reservation() {
+    let env = TestEnv::new();
+    let important = reserve_stale_with_priority(&env, &env.create_dir("important"), 5);
+    let ordinary = reserve_stale_with_priority(&env, &env.create_dir("ordinary"), 0);
+
+    env.command().args(["expire", "--force"]).assert().success();
+
+    let list = env.list();
+    assert!(!common::contains_port(&list, important));
+    assert!(!common::contains_port(&list, ordinary));
+}
+
+/// Test that autoclean honors the protected priority unless forced.
+#[test]
+fn test_autoclean_respects_priority_unless_forced() {
+    let env = TestEnv::new();
+    let important = reserve_stale_with_priority(&env, &env.create_dir("important"), 5);
+    let ordinary = reserve_stale_with_priority(&env, &env.create_dir("ordinary"), 0);
+
+    env.command().arg("autoclean").assert().success();
+    let list = env.list();
+    assert!(common::contains_port(&list, important));
+    assert!(!common::contains_port(&list, ordinary));
+
+    env.command()
+        .args(["autoclean", "--force"])
+        .assert()
+        .success();
+    assert!(!common::contains_port(&env.list(), important));
+}
+
 // ============================================================================
 // Autoclean Command Tests
 // ============================================================================
@@ -880,6 +1318,123 @@ fn test_autoclean_dry_run() {
     );
 }
 
+/// Test autoclean dry-run with JSON output.
+///
+/// The JSON plan should list every prune and expire removal as a
+/// structured action with a distinct reason, without modifying the database.
+#[test]
+fn test_autoclean_dry_run_json_plan() {
+    let env = TestEnv::new();
+
+    let dead = env.create_dir("dead");
+    let aged = env.create_dir("aged");
+    let fresh = env.create_dir("fresh");
+
+    let port_dead = env.reserve_simple(&dead);
+    let port_aged = reserve_old_port(&env, &aged, 30);
+    let port_fresh = env.reserve_simple(&fresh);
+
+    fs::remove_dir_all(&dead).expect("Failed to remove directory");
+
+    let output = env
+        .command()
+        .arg("autoclean")
+        .arg("--days")
+        .arg("7")
+        .arg("--dry-run")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .expect("Failed to run autoclean");
+
+    assert!(
+        output.status.success(),
+        "autoclean should succeed, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be valid JSON");
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["pruned_count"], 1);
+    assert_eq!(json["expired_count"], 1);
+    assert_eq!(json["total_removed"], 2);
+
+    let actions = json["actions"]
+        .as_array()
+        .expect("actions should be an array");
+    assert_eq!(actions.len(), 2);
+
+    let reason_for = |port: u16| {
+        actions
+            .iter()
+            .find(|a| a["port"] == u64::from(port))
+            .map(|a| {
+                assert_eq!(a["action"], "delete_reservation");
+                a["reason"].as_str().unwrap().to_string()
+            })
+    };
+    assert_eq!(reason_for(port_dead).as_deref(), Some("path_missing"));
+    assert_eq!(reason_for(port_aged).as_deref(), Some("expired"));
+    assert_eq!(reason_for(port_fresh), None);
+
+    // Dry run must not remove anything
+    let list_after = env.list();
+    assert!(list_after.contains(&port_dead.to_string()));
+    assert!(list_after.contains(&port_aged.to_string()));
+}
+
+/// Test autoclean JSON reports the removed keys under each category.
+#[test]
+fn test_autoclean_json_reports_removed_keys() {
+    let env = TestEnv::new();
+
+    let dead = env.create_dir("dead");
+    let aged = env.create_dir("aged");
+    let fresh = env.create_dir("fresh");
+
+    env.reserve_simple(&dead);
+    reserve_old_port(&env, &aged, 30);
+    let port_fresh = env.reserve_simple(&fresh);
+
+    fs::remove_dir_all(&dead).expect("Failed to remove directory");
+
+    let output = env
+        .command()
+        .args(["autoclean", "--days", "7", "--format", "json"])
+        .output()
+        .expect("Failed to run autoclean");
+    assert!(
+        output.status.success(),
+        "autoclean should succeed, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be valid JSON");
+    assert_eq!(json["dry_run"], false);
+    assert_eq!(json["pruned_count"], 1);
+    assert_eq!(json["expired_count"], 1);
+
+    let keys = |field: &str| -> Vec<String> {
+        json[field]
+            .as_array()
+            .unwrap_or_else(|| panic!("{field} should be an array: {json}"))
+            .iter()
+            .map(|key| key.as_str().unwrap().to_string())
+            .collect()
+    };
+    let pruned = keys("pruned");
+    let expired = keys("expired");
+    assert_eq!(pruned.len(), 1);
+    assert!(pruned[0].ends_with("dead"), "pruned: {pruned:?}");
+    assert_eq!(expired.len(), 1);
+    assert!(expired[0].ends_with("aged"), "expired: {expired:?}");
+
+    // Only the fresh reservation is left
+    assert!(common::contains_port(&env.list(), port_fresh));
+}
+
 /// Test autoclean uses default threshold.
 ///
 /// When no --days flag is provided, autoclean should use the default
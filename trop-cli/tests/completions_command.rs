@@ -0,0 +1,81 @@
+//! Integration tests for the `completions` command.
+//!
+//! These tests verify that `trop completions <SHELL>` prints a completion
+//! script generated from the real CLI definition:
+//! - Every supported shell produces a non-empty script on stdout
+//! - Scripts are bound to the `trop` binary and include subcommands
+//! - Unknown shells are rejected with the list of supported shells
+
+mod common;
+
+use common::TestEnv;
+use predicates::prelude::*;
+
+// ============================================================================
+// Completions Tests
+// ============================================================================
+
+/// Test that every supported shell generates a script.
+#[test]
+fn test_completions_all_shells() {
+    let env = TestEnv::new();
+
+    for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+        let output = env
+            .command_bare()
+            .args(["completions", shell])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "completions {shell} failed");
+        assert!(!output.stdout.is_empty(), "completions {shell} was empty");
+    }
+}
+
+/// Test that the bash script targets the `trop` binary and lists subcommands.
+#[test]
+fn test_completions_bash_script() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command_bare()
+        .args(["completions", "bash"])
+        .output()
+        .unwrap();
+    let script = String::from_utf8(output.stdout).unwrap();
+
+    assert!(script.contains("_trop()"), "script: {script}");
+    assert!(!script.contains("trop-cli"));
+    assert!(script.contains("reserve-group"));
+    assert!(script.contains("--allow-unrelated-path"));
+}
+
+/// Test that setup instructions go to stderr, keeping stdout redirectable.
+#[test]
+fn test_completions_instructions_on_stderr() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command_bare()
+        .args(["completions", "zsh"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stdout.starts_with("#compdef trop"), "stdout: {stdout}");
+    assert!(stderr.contains("trop completions zsh"));
+}
+
+/// Test that an unknown shell is rejected with the supported shells.
+#[test]
+fn test_completions_invalid_shell() {
+    let env = TestEnv::new();
+
+    env.command_bare()
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values"))
+        .stderr(predicate::str::contains("bash"))
+        .stderr(predicate::str::contains("powershell"));
+}
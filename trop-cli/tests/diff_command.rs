@@ -0,0 +1,157 @@
+//! Integration tests for the `diff` command.
+//!
+//! These tests verify that `trop diff` compares a manifest against the
+//! database without modifying it:
+//! - A `list --format json` snapshot diffs clean against itself
+//! - Creates, deletes, and field changes are reported in text and JSON
+//! - Invalid or missing manifests are rejected
+
+mod common;
+
+use common::TestEnv;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+impl TestEnv {
+    /// Write `entries` as a manifest file and return its path.
+    fn write_manifest(&self, entries: &serde_json::Value) -> PathBuf {
+        let path = self.path().join("reservations.json");
+        fs::write(&path, serde_json::to_string_pretty(entries).unwrap())
+            .expect("Failed to write manifest");
+        path
+    }
+
+    /// Run `diff --format json` on `manifest` and parse the output.
+    fn diff_json(&self, manifest: &Path) -> serde_json::Value {
+        let output = self
+            .command()
+            .arg("diff")
+            .arg(manifest)
+            .args(["--format", "json"])
+            .output()
+            .expect("Failed to run diff");
+        assert!(
+            output.status.success(),
+            "diff failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).expect("Invalid JSON")
+    }
+}
+
+// ============================================================================
+// Diff Tests
+// ============================================================================
+
+/// Test that a snapshot from `list --format json` has no differences.
+#[test]
+fn test_diff_snapshot_is_clean() {
+    let env = TestEnv::new();
+    env.reserve_simple(&env.create_dir("a"));
+    env.reserve_with_tag(&env.create_dir("b"), "web");
+
+    let output = env
+        .command()
+        .args(["list", "--format", "json"])
+        .output()
+        .unwrap();
+    let snapshot = env.path().join("snapshot.json");
+    fs::write(&snapshot, &output.stdout).unwrap();
+
+    let diff = env.diff_json(&snapshot);
+    assert_eq!(diff["create"].as_array().unwrap().len(), 0);
+    assert_eq!(diff["delete"].as_array().unwrap().len(), 0);
+    assert_eq!(diff["change"].as_array().unwrap().len(), 0);
+    assert_eq!(diff["unchanged"], 2);
+}
+
+/// Test that creates, deletes, and changes are all reported.
+#[test]
+fn test_diff_reports_create_delete_change() {
+    let env = TestEnv::new();
+    let kept = env.create_dir("kept");
+    let removed = env.create_dir("removed");
+    let added = env.create_dir("added");
+    let kept_port = env.reserve_simple(&kept);
+    let removed_port = env.reserve_simple(&removed);
+
+    let manifest = env.write_manifest(&serde_json::json!([
+        { "path": kept, "port": 6500, "project": "alpha" },
+        { "path": added, "tag": "api", "port": 6501 },
+    ]));
+
+    let diff = env.diff_json(&manifest);
+    let create = diff["create"].as_array().unwrap();
+    assert_eq!(create.len(), 1);
+    assert_eq!(create[0]["path"], added.display().to_string());
+    assert_eq!(create[0]["tag"], "api");
+
+    let delete = diff["delete"].as_array().unwrap();
+    assert_eq!(delete.len(), 1);
+    assert_eq!(delete[0]["port"], u64::from(removed_port));
+
+    let change = diff["change"].as_array().unwrap();
+    assert_eq!(change.len(), 1);
+    assert_eq!(change[0]["changes"]["port"]["from"], u64::from(kept_port));
+    assert_eq!(change[0]["changes"]["port"]["to"], 6500);
+    assert_eq!(
+        change[0]["changes"]["project"]["from"],
+        serde_json::Value::Null
+    );
+    assert_eq!(change[0]["changes"]["project"]["to"], "alpha");
+
+    // Nothing was modified
+    assert!(env.list().contains(&removed_port.to_string()));
+}
+
+/// Test the text output markers.
+#[test]
+fn test_diff_text_output() {
+    let env = TestEnv::new();
+    let removed = env.create_dir("removed");
+    let added = env.create_dir("added");
+    env.reserve_simple(&removed);
+
+    let manifest = env.write_manifest(&serde_json::json!([
+        { "path": added, "port": 6501 },
+    ]));
+
+    let output = env.command().arg("diff").arg(&manifest).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+ "), "stdout: {stdout}");
+    assert!(stdout.contains("port 6501"), "stdout: {stdout}");
+    assert!(stdout.contains("- "), "stdout: {stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 to create, 1 to delete, 0 to change, 0 unchanged"),
+        "stderr: {stderr}"
+    );
+}
+
+/// Test that an invalid manifest is rejected.
+#[test]
+fn test_diff_invalid_manifest() {
+    let env = TestEnv::new();
+    let manifest = env.path().join("reservations.json");
+    fs::write(&manifest, r#"{"not": "an array"}"#).unwrap();
+
+    env.command().arg("diff").arg(&manifest).assert().failure();
+}
+
+/// Test that a missing manifest fails with invalid arguments.
+#[test]
+fn test_diff_missing_manifest() {
+    let env = TestEnv::new();
+
+    env.command()
+        .arg("diff")
+        .arg(env.path().join("missing.json"))
+        .assert()
+        .failure()
+        .code(4);
+}
@@ -0,0 +1,142 @@
+//! Integration tests for the `doctor` command.
+//!
+//! These tests verify that each check reports PASS, WARN, or FAIL as
+//! appropriate and that the exit code reflects whether any check failed.
+
+mod common;
+
+use common::TestEnv;
+use predicates::prelude::*;
+
+/// Test that a healthy environment passes every check.
+#[test]
+fn test_doctor_healthy_environment() {
+    let env = TestEnv::new();
+    env.reserve_simple(&env.create_dir("project"));
+
+    env.command()
+        .arg("doctor")
+        .current_dir(&env.temp_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[PASS] Data directory:"))
+        .stdout(predicate::str::contains("(writable)"))
+        .stdout(predicate::str::contains("[PASS] Effective config: valid"))
+        .stdout(predicate::str::contains("[PASS] Database:"))
+        .stdout(predicate::str::contains("integrity check passed"))
+        .stdout(predicate::str::contains("FAIL").not());
+}
+
+/// Test that a missing data directory and database only warn.
+#[test]
+fn test_doctor_fresh_install_warns() {
+    let env = TestEnv::new();
+
+    env.command()
+        .arg("doctor")
+        .current_dir(&env.temp_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[WARN] Data directory:"))
+        .stdout(predicate::str::contains("[WARN] Database:"));
+}
+
+/// Test that discovered config files are listed in precedence order.
+#[test]
+fn test_doctor_lists_config_files_and_env() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(project.join("trop.yaml"), "project: doctor-test\n").unwrap();
+    std::fs::write(project.join("trop.local.yaml"), "project: doctor-local\n").unwrap();
+
+    let output = env
+        .command()
+        .arg("doctor")
+        .current_dir(&project)
+        .env("TROP_EXPIRE_AFTER_DAYS", "9")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[PASS] Config files: 2 found"), "{stdout}");
+    let shared = stdout.find("trop.yaml (precedence 2)").expect(&stdout);
+    let local = stdout
+        .find("trop.local.yaml (precedence 3)")
+        .expect(&stdout);
+    assert!(shared < local, "{stdout}");
+
+    assert!(stdout.contains("project: doctor-local"), "{stdout}");
+    assert!(stdout.contains("expire_after_days: 9"), "{stdout}");
+    assert!(stdout.contains("TROP_EXPIRE_AFTER_DAYS=9"), "{stdout}");
+}
+
+/// Test that an invalid config file fails the config checks.
+#[test]
+fn test_doctor_invalid_config_fails() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(project.join("trop.yaml"), "ports: [not, a, mapping]\n").unwrap();
+
+    env.command()
+        .arg("doctor")
+        .current_dir(&project)
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("[FAIL] Config files:"))
+        .stdout(predicate::str::contains("[FAIL] Effective config:"));
+}
+
+/// Test that a corrupt database fails the database check.
+#[test]
+fn test_doctor_corrupt_database_fails() {
+    let env = TestEnv::new();
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+    std::fs::write(env.data_dir.join("trop.db"), b"this is not a database").unwrap();
+
+    env.command()
+        .arg("doctor")
+        .current_dir(&env.temp_path)
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("[FAIL] Database:"));
+}
+
+/// Test that a JSON tropfile shadowed by its YAML counterpart is flagged.
+#[test]
+fn test_doctor_warns_on_shadowed_json_tropfile() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(project.join("trop.yaml"), "project: from-yaml\n").unwrap();
+    std::fs::write(project.join("trop.json"), r#"{"project": "from-json"}"#).unwrap();
+
+    env.command()
+        .arg("doctor")
+        .current_dir(&project)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[WARN] Config files:"))
+        .stdout(predicate::str::contains(
+            "trop.json is ignored in favor of its YAML file",
+        ))
+        .stdout(predicate::str::contains("project: from-yaml"));
+}
+
+/// Test that a JSON-only project is picked up like a YAML one.
+#[test]
+fn test_doctor_json_only_project() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(project.join("trop.json"), r#"{"project": "from-json"}"#).unwrap();
+
+    env.command()
+        .arg("doctor")
+        .current_dir(&project)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[PASS] Config files: 1 found"))
+        .stdout(predicate::str::contains("trop.json (precedence 2)"))
+        .stdout(predicate::str::contains("project: from-json"));
+}
@@ -10,6 +10,7 @@
 //! - Exit code 5: I/O error
 //! - Exit code 6: Other library errors
 //! - Exit code 7: Configuration error
+//! - `--error-format json` output
 //!
 //! Each test documents the expected error scenario and verifies both the
 //! exit code and error message quality.
@@ -583,6 +584,93 @@ fn test_invalid_argument_error_message() {
     );
 }
 
+// ============================================================================
+// JSON Error Format Tests
+// ============================================================================
+
+/// Run a failing command with `--error-format json` and parse its stderr.
+fn json_error(env: &TestEnv, args: &[&str]) -> (i32, serde_json::Value) {
+    let output = env
+        .command()
+        .args(["--error-format", "json"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let json = serde_json::from_slice(&output.stderr).expect("stderr should be JSON");
+    (output.status.code().unwrap(), json)
+}
+
+/// Test that an exhausted port range reports its kind and exit code in JSON.
+#[test]
+fn test_json_error_port_exhausted() {
+    let env = TestEnv::new();
+    let first = env.create_dir("first");
+    let second = env.create_dir("second");
+    let range = ["--min", "6600", "--max", "6600", "--skip-occupancy-check"];
+    let first = first.to_str().unwrap();
+    let second = second.to_str().unwrap();
+
+    env.command()
+        .args(["reserve", "--allow-unrelated-path", "--path", first])
+        .args(range)
+        .assert()
+        .success();
+
+    let mut args = vec!["reserve", "--allow-unrelated-path", "--path", second];
+    args.extend(range);
+    let (code, json) = json_error(&env, &args);
+
+    assert_eq!(code, 6);
+    assert_eq!(json["kind"], "PortExhausted");
+    assert_eq!(json["exit_code"], 6);
+    assert!(json["message"].as_str().unwrap().contains("exhausted"));
+}
+
+/// Test that a validation failure reports its kind and exit code in JSON.
+#[test]
+fn test_json_error_validation() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("cluster");
+    let path = test_path.to_str().unwrap();
+
+    env.command()
+        .args(["reserve", "--allow-unrelated-path", "--path", path])
+        .args(["--tag", "node-0", "--skip-occupancy-check"])
+        .assert()
+        .success();
+
+    let (code, json) = json_error(
+        &env,
+        &[
+            "reserve",
+            "--allow-unrelated-path",
+            "--path",
+            path,
+            "--count",
+            "3",
+            "--tag-prefix",
+            "node",
+            "--skip-occupancy-check",
+        ],
+    );
+
+    assert_eq!(code, 6);
+    assert_eq!(json["kind"], "Validation");
+    assert_eq!(json["exit_code"], 6);
+}
+
+/// Test that CLI-level errors use their own kind names.
+#[test]
+fn test_json_error_cli_kind() {
+    let env = TestEnv::new();
+
+    let (code, json) = json_error(&env, &["release", "--tag", "web", "--untagged-only"]);
+
+    assert_eq!(code, 4);
+    assert_eq!(json["kind"], "InvalidArguments");
+}
+
 // ============================================================================
 // Error Consistency Tests
 // ============================================================================
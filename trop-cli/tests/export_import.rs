@@ -0,0 +1,139 @@
+//! Integration tests for the `export` and `import` commands.
+//!
+//! These tests verify that reservations survive a JSON round trip:
+//! - An export imported into an empty data directory recreates everything
+//! - Conflicting records are skipped unless --force is given
+//! - Malformed records are rejected without changing the database
+
+mod common;
+
+use common::TestEnv;
+use std::fs;
+
+impl TestEnv {
+    /// Export all reservations to a file and return its path.
+    fn export_to(&self, name: &str) -> std::path::PathBuf {
+        let file = self.path().join(name);
+        self.command()
+            .args(["export", "--output-file"])
+            .arg(&file)
+            .assert()
+            .success();
+        file
+    }
+}
+
+// ============================================================================
+// Round Trip Tests
+// ============================================================================
+
+/// Test that an export imported into a fresh data dir recreates reservations.
+#[test]
+fn test_export_import_round_trip() {
+    let env = TestEnv::new();
+    let web = env.create_dir("web");
+    let api = env.create_dir("api");
+    let web_port = env.reserve_simple(&web);
+    let api_port = env.reserve_with_tag(&api, "api");
+
+    let file = env.export_to("export.json");
+    let document: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+    assert_eq!(document["version"], 1);
+    assert_eq!(document["reservations"].as_array().unwrap().len(), 2);
+
+    let other = env.path().join("other-data");
+    let output = env
+        .command_bare()
+        .arg("--data-dir")
+        .arg(&other)
+        .arg("import")
+        .arg(&file)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "import failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = env
+        .command_bare()
+        .arg("--data-dir")
+        .arg(&other)
+        .arg("list")
+        .output()
+        .unwrap();
+    let list = String::from_utf8_lossy(&output.stdout);
+    assert!(common::contains_port(&list, web_port), "list: {list}");
+    assert!(common::contains_port(&list, api_port), "list: {list}");
+}
+
+/// Test that export writes the document to stdout by default.
+#[test]
+fn test_export_to_stdout() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = env.reserve_simple(&dir);
+
+    let output = env.command().arg("export").output().unwrap();
+    assert!(output.status.success());
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["reservations"][0]["port"], port);
+}
+
+// ============================================================================
+// Conflict Tests
+// ============================================================================
+
+/// Test that conflicting records are skipped without --force and replaced with it.
+#[test]
+fn test_import_conflicts_require_force() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = env.reserve_simple(&dir);
+    let file = env.export_to("export.json");
+
+    // Rewrite the export so the same path holds a different port
+    let other_port = if port == 6500 { 6501 } else { 6500 };
+    let contents = fs::read_to_string(&file).unwrap().replace(
+        &format!("\"port\": {port}"),
+        &format!("\"port\": {other_port}"),
+    );
+    fs::write(&file, contents).unwrap();
+
+    let output = env.command().arg("import").arg(&file).output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 skipped"));
+    assert!(common::contains_port(&env.list(), port));
+
+    env.command()
+        .args(["import", "--force"])
+        .arg(&file)
+        .assert()
+        .success();
+    let list = env.list();
+    assert!(common::contains_port(&list, other_port), "list: {list}");
+    assert!(!common::contains_port(&list, port), "list: {list}");
+}
+
+/// Test that a malformed record rejects the whole import.
+#[test]
+fn test_import_rejects_malformed_record() {
+    let env = TestEnv::new();
+    let file = env.path().join("bad.json");
+    fs::write(
+        &file,
+        r#"{"version": 1, "reservations": [
+            {"path": "/srv/ok", "port": 6000, "created_at": 0, "last_used_at": 0},
+            {"path": "/srv/bad", "port": 0, "created_at": 0, "last_used_at": 0}
+        ]}"#,
+    )
+    .unwrap();
+
+    let output = env.command().arg("import").arg(&file).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("reservations[1]"));
+    assert!(!env.list().contains("/srv/ok"));
+}
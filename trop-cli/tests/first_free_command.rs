@@ -0,0 +1,90 @@
+//! Integration tests for the `first-free` command.
+//!
+//! These tests verify that `first-free`:
+//! - Prints the lowest port in the configured range
+//! - Skips reserved and excluded ports without reserving anything
+//! - Exits 1 when every port in the range is taken
+
+mod common;
+
+use common::{parse_port, TestEnv};
+
+/// Build a command limited to the range 7100-7103.
+fn in_range(env: &TestEnv) -> assert_cmd::Command {
+    let mut cmd = env.command();
+    cmd.env("TROP_PORT_MIN", "7100")
+        .env("TROP_PORT_MAX", "7103");
+    cmd
+}
+
+/// Reserve `port` for a fresh directory named `name`.
+fn reserve_port(env: &TestEnv, name: &str, port: u16) {
+    let path = env.create_dir(name);
+    in_range(env)
+        .arg("reserve")
+        .arg("--path")
+        .arg(&path)
+        .args(["--port", &port.to_string()])
+        .args(["--allow-unrelated-path", "--skip-occupancy-check"])
+        .assert()
+        .success();
+}
+
+/// Run `first-free` in the test range and return its output.
+fn first_free(env: &TestEnv, excluded: &str) -> std::process::Output {
+    in_range(env)
+        .env("TROP_EXCLUDED_PORTS", excluded)
+        .arg("first-free")
+        .output()
+        .expect("Failed to run first-free")
+}
+
+// ============================================================================
+// Basic Tests
+// ============================================================================
+
+/// Test first-free prints the lowest port in the range.
+#[test]
+fn test_first_free_returns_lowest_port() {
+    let env = TestEnv::new();
+
+    let output = first_free(&env, "");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(parse_port(&String::from_utf8(output.stdout).unwrap()), 7100);
+}
+
+/// Test first-free skips reserved and excluded ports, and reserves nothing.
+#[test]
+fn test_first_free_skips_reserved_and_excluded() {
+    let env = TestEnv::new();
+    reserve_port(&env, "a", 7100);
+
+    let output = first_free(&env, "7101");
+
+    assert!(output.status.success());
+    assert_eq!(parse_port(&String::from_utf8(output.stdout).unwrap()), 7102);
+
+    // Asking again gives the same answer, since nothing was reserved
+    let output = first_free(&env, "7101");
+    assert_eq!(parse_port(&String::from_utf8(output.stdout).unwrap()), 7102);
+}
+
+/// Test first-free exits 1 when the range is full.
+#[test]
+fn test_first_free_exits_1_when_range_full() {
+    let env = TestEnv::new();
+    reserve_port(&env, "a", 7100);
+    reserve_port(&env, "b", 7101);
+
+    let output = first_free(&env, "7102..7103");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("No free port"), "stderr: {stderr}");
+}
@@ -5,14 +5,17 @@
 //! - --verbose flag
 //! - --quiet flag
 //! - --data-dir override
+//! - --db-name override (with TROP_DB_NAME)
 //! - --busy-timeout override
 //! - --disable-autoinit flag
+//! - --color flag (with NO_COLOR / CLICOLOR_FORCE)
+//! - --profile flag (with TROP_PROFILE)
 //! - Environment variable handling (TROP_DATA_DIR, TROP_BUSY_TIMEOUT, etc.)
 //! - Precedence rules (CLI flags > env vars > defaults)
 
 mod common;
 
-use common::TestEnv;
+use common::{parse_port, TestEnv};
 use predicates::prelude::*;
 
 // ============================================================================
@@ -126,6 +129,45 @@ fn test_verbose_flag_position_independence() {
     // This documents the expected behavior
 }
 
+/// Test --verbose reports a timing breakdown on stderr only.
+#[test]
+fn test_verbose_reports_phase_timings() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("--verbose")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    for phase in ["config load", "database open", "plan build", "plan execute"] {
+        assert!(
+            stderr.contains(&format!("TIMING: {phase}: ")),
+            "missing {phase}: {stderr}"
+        );
+    }
+    assert!(!stdout.contains("TIMING"), "{stdout}");
+    assert!(stdout.trim().parse::<u16>().is_ok(), "{stdout}");
+
+    // Default verbosity prints no timings
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("TIMING").not());
+}
+
 // ============================================================================
 // Quiet Flag Tests
 // ============================================================================
@@ -351,6 +393,231 @@ fn test_data_dir_with_relative_path() {
     assert!(relative_data.exists());
 }
 
+// ============================================================================
+// Database Name Tests
+// ============================================================================
+
+/// Test --db-name keeps two instances apart in one data directory.
+///
+/// Each name selects its own database file, so reservations made through
+/// one instance are invisible to the other.
+#[test]
+fn test_db_name_instances_are_independent() {
+    let env = TestEnv::new();
+    let first = env.create_dir("first");
+    let second = env.create_dir("second");
+
+    env.command()
+        .arg("--db-name")
+        .arg("first.db")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&first)
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success();
+
+    // The environment variable selects the name as well
+    env.command()
+        .env("TROP_DB_NAME", "second.db")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&second)
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success();
+
+    assert!(env.data_dir.join("first.db").exists());
+    assert!(env.data_dir.join("second.db").exists());
+    assert!(!env.data_dir.join("trop.db").exists());
+
+    let list_first = env
+        .command()
+        .arg("--db-name")
+        .arg("first.db")
+        .arg("list")
+        .output()
+        .unwrap();
+    let list_first = String::from_utf8(list_first.stdout).unwrap();
+    assert!(list_first.contains("first"));
+    assert!(!list_first.contains("second"));
+
+    let list_second = env
+        .command()
+        .arg("--db-name")
+        .arg("second.db")
+        .arg("list")
+        .output()
+        .unwrap();
+    let list_second = String::from_utf8(list_second.stdout).unwrap();
+    assert!(list_second.contains("second"));
+    assert!(!list_second.contains("first"));
+}
+
+/// Test --db-name rejects names containing a path separator.
+#[test]
+fn test_db_name_with_separator_rejected() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("--db-name")
+        .arg("nested/trop.db")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("path separators"));
+
+    assert!(!env.data_dir.join("nested").exists());
+}
+
+// ============================================================================
+// Log File Tests
+// ============================================================================
+
+/// Test --log-file writes log lines at the chosen verbosity.
+///
+/// Info lines only appear with --verbose, and the file is appended to
+/// across runs. The port still goes to stdout.
+#[test]
+fn test_log_file_receives_log_lines() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let log_file = env.path().join("trop.log");
+
+    let output = env
+        .command()
+        .arg("--log-file")
+        .arg(&log_file)
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    parse_port(&String::from_utf8_lossy(&output.stdout));
+    assert_eq!(std::fs::read_to_string(&log_file).unwrap(), "");
+
+    let output = env
+        .command()
+        .arg("--verbose")
+        .arg("--log-file")
+        .arg(&log_file)
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    parse_port(&String::from_utf8_lossy(&output.stdout));
+
+    let log = std::fs::read_to_string(&log_file).unwrap();
+    assert!(log.contains("INFO: trop "), "log was: {log}");
+    assert!(
+        log.contains("INFO: finished successfully"),
+        "log was: {log}"
+    );
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("INFO:"));
+
+    // Failures are logged even without --verbose
+    env.command()
+        .arg("--log-file")
+        .arg(&log_file)
+        .arg("assert-port")
+        .arg("1")
+        .assert()
+        .failure();
+    let log = std::fs::read_to_string(&log_file).unwrap();
+    assert!(
+        log.contains("ERROR: failed with exit code"),
+        "log was: {log}"
+    );
+}
+
+/// Test --verbose timings and diagnostics go to the --log-file, not stderr.
+#[test]
+fn test_log_file_receives_timings_and_verbose_output() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let log_file = env.path().join("trop.log");
+
+    let output = env
+        .command()
+        .arg("--verbose")
+        .arg("--log-file")
+        .arg(&log_file)
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let port = parse_port(&String::from_utf8_lossy(&output.stdout));
+
+    let log = std::fs::read_to_string(&log_file).unwrap();
+    assert!(log.contains("TIMING: plan execute: "), "log was: {log}");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("TIMING"));
+
+    std::fs::remove_dir_all(&test_path).unwrap();
+    let output = env
+        .command()
+        .arg("--verbose")
+        .arg("--log-file")
+        .arg(&log_file)
+        .arg("prune")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let log = std::fs::read_to_string(&log_file).unwrap();
+    assert!(
+        log.contains("INFO: Removed 1 reservation(s):"),
+        "log was: {log}"
+    );
+    assert!(
+        log.contains(&format!("INFO:   - Port {port}: ")),
+        "log was: {log}"
+    );
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Removed"));
+}
+
+/// Test TROP_LOG_FILE, and that an unopenable log file only warns.
+#[test]
+fn test_log_file_env_and_unopenable_path() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let log_file = env.path().join("env.log");
+
+    env.command()
+        .env("TROP_LOG_FILE", &log_file)
+        .arg("--verbose")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success();
+    assert!(std::fs::read_to_string(&log_file)
+        .unwrap()
+        .contains("INFO: finished successfully"));
+
+    env.command()
+        .arg("--log-file")
+        .arg(env.path().join("missing").join("trop.log"))
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("cannot open log file"));
+}
+
 // ============================================================================
 // Busy Timeout Override Tests
 // ============================================================================
@@ -504,6 +771,51 @@ fn test_disable_autoinit_with_existing_database() {
         .success();
 }
 
+/// Test that a missing database with autoinit disabled fails with a hint to
+/// run `trop init`.
+#[test]
+fn test_disable_autoinit_missing_database_suggests_init() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .arg("--disable-autoinit")
+        .arg("list")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("auto-init is disabled"), "{stderr}");
+    assert!(stderr.contains("run `trop init`"), "{stderr}");
+    assert!(
+        stderr.contains(&env.data_dir.join("trop.db").display().to_string()),
+        "{stderr}"
+    );
+    assert!(!env.data_dir.exists());
+}
+
+/// Test that `disable_autoinit` in a tropfile also stops database creation.
+#[test]
+fn test_disable_autoinit_from_config() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(project.join("trop.yaml"), "disable_autoinit: true\n").unwrap();
+
+    let output = env
+        .command()
+        .arg("list")
+        .current_dir(&project)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("run `trop init`"));
+    assert!(!env.data_dir.exists());
+}
+
 // ============================================================================
 // Environment Variable Tests
 // ============================================================================
@@ -532,6 +844,73 @@ fn test_trop_data_dir_env_variable() {
     assert!(custom_data.exists());
 }
 
+/// Test that `~` and `$HOME` in TROP_DATA_DIR expand to the home directory.
+///
+/// The variable is passed through unexpanded, as it would be when quoted in
+/// a shell profile or set by a service manager.
+#[cfg(unix)]
+#[test]
+fn test_trop_data_dir_env_variable_expansion() {
+    for (value, dir) in [
+        ("~/tilde-data", "tilde-data"),
+        ("$HOME/var-data", "var-data"),
+    ] {
+        let home = tempfile::tempdir().unwrap();
+        let test_path = home.path().join("test-project");
+        std::fs::create_dir_all(&test_path).unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("trop").unwrap();
+        cmd.env("HOME", home.path())
+            .env("TROP_DATA_DIR", value)
+            .arg("reserve")
+            .arg("--path")
+            .arg(&test_path)
+            .arg("--allow-unrelated-path")
+            .assert()
+            .success();
+
+        assert!(
+            home.path().join(dir).join("trop.db").exists(),
+            "{value} should expand under the home directory"
+        );
+    }
+}
+
+/// Test that a data directory naming an unset variable fails before the
+/// command runs, in the requested error format.
+#[test]
+fn test_data_dir_unset_variable_rejected() {
+    let temp = tempfile::tempdir().unwrap();
+    let log_file = temp.path().join("trop.log");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("trop").unwrap();
+    let output = cmd
+        .env_remove("TROP_TEST_UNSET_DIR")
+        .env("TROP_DATA_DIR", "$TROP_TEST_UNSET_DIR/data")
+        .args(["--error-format", "json", "--log-file"])
+        .arg(&log_file)
+        .args(["list"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(4));
+    assert!(output.stdout.is_empty());
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["kind"], "InvalidArguments");
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .contains("Invalid data directory"),
+        "{error}"
+    );
+    let log = std::fs::read_to_string(&log_file).unwrap();
+    assert!(
+        log.contains("ERROR: failed with exit code 4"),
+        "log was: {log}"
+    );
+}
+
 /// Test --data-dir flag overrides TROP_DATA_DIR env variable.
 ///
 /// CLI flags should have higher precedence than environment variables.
@@ -693,6 +1072,102 @@ fn test_configuration_precedence() {
     assert!(!env_data.exists());
 }
 
+// ============================================================================
+// Profile Tests
+// ============================================================================
+
+/// Write a user config whose `ci` profile moves the port range.
+fn write_profile_config(env: &TestEnv) {
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+    std::fs::write(
+        env.data_dir.join("config.yaml"),
+        "ports:\n  min: 5000\n  max: 5999\nprofiles:\n  ci:\n    ports:\n      min: 7000\n      max: 7999\n",
+    )
+    .unwrap();
+}
+
+/// Reserve a port with extra global arguments and return it.
+fn reserve_port_with(env: &TestEnv, args: &[&str], profile_env: Option<&str>) -> u16 {
+    let test_path = env.create_dir("profile-project");
+    let mut cmd = env.command();
+    if let Some(profile) = profile_env {
+        cmd.env("TROP_PROFILE", profile);
+    }
+    let output = cmd
+        .args(args)
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--allow-unrelated-path", "--skip-occupancy-check"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap()
+}
+
+/// Test that --profile merges the profile's overrides.
+#[test]
+fn test_profile_flag_applies_overrides() {
+    let env = TestEnv::new();
+    write_profile_config(&env);
+
+    let port = reserve_port_with(&env, &["--profile", "ci"], None);
+    assert!(
+        (7000..=7999).contains(&port),
+        "port {port} outside ci range"
+    );
+}
+
+/// Test that TROP_PROFILE selects a profile.
+#[test]
+fn test_trop_profile_env_variable() {
+    let env = TestEnv::new();
+    write_profile_config(&env);
+
+    let port = reserve_port_with(&env, &[], Some("ci"));
+    assert!(
+        (7000..=7999).contains(&port),
+        "port {port} outside ci range"
+    );
+}
+
+/// Test that the base configuration is used without a profile.
+#[test]
+fn test_no_profile_uses_base_config() {
+    let env = TestEnv::new();
+    write_profile_config(&env);
+
+    let port = reserve_port_with(&env, &[], None);
+    assert!(
+        (5000..=5999).contains(&port),
+        "port {port} outside base range"
+    );
+}
+
+/// Test that selecting an undefined profile is a configuration error.
+#[test]
+fn test_unknown_profile_fails() {
+    let env = TestEnv::new();
+    write_profile_config(&env);
+
+    env.command()
+        .args(["--profile", "staging"])
+        .arg("list")
+        .assert()
+        .failure()
+        .code(7)
+        .stderr(predicate::str::contains("Unknown profile 'staging'"))
+        .stderr(predicate::str::contains("available: ci"));
+}
+
 // ============================================================================
 // Multiple Global Flags Tests
 // ============================================================================
@@ -778,6 +1253,144 @@ fn test_version_with_global_flags() {
         .stdout(predicate::str::contains("trop"));
 }
 
+// ============================================================================
+// Color Tests
+// ============================================================================
+
+/// Run a failing command with the given color flag and environment, and
+/// report whether its stderr contains ANSI escapes.
+fn error_output_is_colored(flag: Option<&str>, env_vars: &[(&str, &str)]) -> bool {
+    let env = TestEnv::new();
+
+    let mut cmd = env.command();
+    cmd.env_remove("NO_COLOR").env_remove("CLICOLOR_FORCE");
+    for (key, value) in env_vars {
+        cmd.env(key, value);
+    }
+    if let Some(flag) = flag {
+        cmd.arg("--color").arg(flag);
+    }
+
+    let output = cmd
+        .arg("release")
+        .arg("--by-port")
+        .arg("6999")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Error:"), "{stderr}");
+    stderr.contains('\x1b')
+}
+
+/// Test piped output is not colored by default.
+#[test]
+fn test_color_auto_piped_is_plain() {
+    assert!(!error_output_is_colored(None, &[]));
+    assert!(!error_output_is_colored(Some("auto"), &[]));
+}
+
+/// Test `--color always` colors piped output, even with `NO_COLOR`.
+#[test]
+fn test_color_always_forces_color() {
+    assert!(error_output_is_colored(Some("always"), &[]));
+    assert!(error_output_is_colored(
+        Some("always"),
+        &[("NO_COLOR", "1")]
+    ));
+}
+
+/// Test `--color never` disables color, even with `CLICOLOR_FORCE`.
+#[test]
+fn test_color_never_disables_color() {
+    assert!(!error_output_is_colored(
+        Some("never"),
+        &[("CLICOLOR_FORCE", "1")]
+    ));
+}
+
+/// Test `CLICOLOR_FORCE` enables color for piped output under `auto`.
+#[test]
+fn test_clicolor_force_enables_color() {
+    assert!(error_output_is_colored(None, &[("CLICOLOR_FORCE", "1")]));
+    assert!(!error_output_is_colored(None, &[("CLICOLOR_FORCE", "0")]));
+}
+
+/// Test `NO_COLOR` takes precedence over `CLICOLOR_FORCE` under `auto`.
+#[test]
+fn test_no_color_wins_over_clicolor_force() {
+    assert!(!error_output_is_colored(
+        None,
+        &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]
+    ));
+}
+
+/// Test captured stdout and stderr carry no ANSI escapes by default.
+#[test]
+fn test_color_auto_captured_output_is_plain() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("plain");
+    let port = env.reserve_simple(&test_path);
+
+    for args in [
+        vec!["list".to_string()],
+        vec!["port-info".to_string(), port.to_string()],
+        vec![
+            "scan".to_string(),
+            "--min".to_string(),
+            port.to_string(),
+            "--max".to_string(),
+            port.to_string(),
+        ],
+    ] {
+        let output = env
+            .command()
+            .env_remove("NO_COLOR")
+            .env_remove("CLICOLOR_FORCE")
+            .args(&args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{args:?}");
+        assert!(
+            !output.stdout.contains(&0x1b),
+            "{args:?} stdout has escapes"
+        );
+        assert!(
+            !output.stderr.contains(&0x1b),
+            "{args:?} stderr has escapes"
+        );
+    }
+}
+
+/// Test `--color always` highlights the `list` table even when piped.
+#[test]
+fn test_color_always_highlights_list() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("colored");
+    env.reserve_simple(&test_path);
+
+    env.command()
+        .arg("--color")
+        .arg("always")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[1mPORT\x1b[0m"));
+}
+
+/// Test an invalid `--color` value is rejected.
+#[test]
+fn test_color_invalid_value_rejected() {
+    let env = TestEnv::new();
+    env.command()
+        .arg("--color")
+        .arg("sometimes")
+        .arg("list")
+        .assert()
+        .failure();
+}
+
 // ============================================================================
 // Edge Cases
 // ============================================================================
@@ -3,11 +3,14 @@
 //! These tests verify the CLI behavior of `reserve-group` and `autoreserve`
 //! commands, including:
 //! - Output format variations (export, json, dotenv, human)
+//! - Merging allocations into an existing dotenv file
 //! - Shell type detection and explicit specification
 //! - Configuration discovery (autoreserve)
 //! - Quiet/verbose output modes
 //! - Dry-run behavior
 //! - Override flags (force, allow-*)
+//! - Tag decoration (--tag-prefix, --tag-suffix)
+//! - Service selection (--only, --except)
 //! - Task identifier handling (flag vs env var)
 //! - Error cases (config not found, invalid format, allocation failures)
 //!
@@ -19,6 +22,7 @@ mod common;
 
 use common::TestEnv;
 use predicates::prelude::*;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -284,6 +288,41 @@ fn test_reserve_group_json_format() {
     );
 }
 
+/// Test reserve-group with --format=yaml.
+///
+/// YAML format is a mapping from service tag to port with sorted keys, so
+/// tools like Ansible can consume it directly.
+#[test]
+fn test_reserve_group_yaml_format() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--format")
+        .arg("yaml")
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+
+    let parsed: std::collections::BTreeMap<String, u16> =
+        serde_yaml::from_str(&stdout).expect("Output should be valid YAML");
+    assert_eq!(
+        parsed.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["api", "web"]
+    );
+
+    // Keys are written in sorted order
+    assert!(stdout.starts_with("api: "), "{stdout}");
+}
+
 /// Test reserve-group with --format=dotenv.
 ///
 /// Dotenv format outputs lines in "VAR=value" format, suitable for use in
@@ -332,6 +371,308 @@ fn test_reserve_group_dotenv_format() {
     }
 }
 
+/// Test reserve-group with --format=dotenv --quote.
+///
+/// Quoted dotenv output wraps every value in double quotes, even ports.
+#[test]
+fn test_reserve_group_dotenv_quoted() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "dotenv", "--quote", "--allow-unrelated-path"])
+        .output()
+        .expect("Failed to run reserve-group");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+
+    for line in stdout.lines().filter(|line| !line.is_empty()) {
+        let (_, value) = line
+            .split_once('=')
+            .expect("dotenv line should contain '='");
+        assert!(
+            value.len() > 2 && value.starts_with('"') && value.ends_with('"'),
+            "quoted dotenv value should be double-quoted: {line}"
+        );
+        value[1..value.len() - 1]
+            .parse::<u16>()
+            .expect("quoted value should be a port");
+    }
+}
+
+/// Test that --quote is rejected for formats other than dotenv.
+#[test]
+fn test_reserve_group_quote_requires_dotenv() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "json", "--quote", "--allow-unrelated-path"])
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicates::str::contains("--quote"));
+}
+
+/// Test --env-prefix prefixes explicit `env` names and tag-derived names.
+#[test]
+fn test_reserve_group_env_prefix() {
+    let env = TestEnv::new();
+    let mapped_dir = env.create_dir("mapped");
+    let mapped = mapped_dir.join("trop.yaml");
+    create_test_config(&mapped, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&mapped)
+        .args(["--format", "dotenv", "--env-prefix", "MYAPP_"])
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(stdout.contains("MYAPP_WEB_PORT="), "{stdout}");
+    assert!(stdout.contains("MYAPP_API_PORT="), "{stdout}");
+
+    let unmapped_dir = env.create_dir("unmapped");
+    let unmapped = unmapped_dir.join("trop.yaml");
+    create_config_without_env_mappings(&unmapped);
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&unmapped)
+        .args([
+            "--format",
+            "export",
+            "--shell",
+            "bash",
+            "--env-prefix",
+            "MYAPP_",
+        ])
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(stdout.contains("export MYAPP_WEB="), "{stdout}");
+    assert!(stdout.contains("export MYAPP_API="), "{stdout}");
+
+    // JSON is keyed by tag and ignores the prefix
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&mapped)
+        .args(["--format", "json", "--env-prefix", "MYAPP_"])
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(!stdout.contains("MYAPP_"), "{stdout}");
+}
+
+/// Test that an invalid --env-prefix is rejected before anything is reserved.
+#[test]
+fn test_env_prefix_rejects_invalid_prefix() {
+    let env = TestEnv::new();
+    let project_dir = env.create_dir("project");
+    let config_path = project_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "dotenv", "--env-prefix", "MY-APP_"])
+        .arg("--allow-unrelated-path")
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicates::str::contains("prefix"));
+
+    env.command()
+        .arg("autoreserve")
+        .args(["--format", "dotenv", "--env-prefix", "1APP_"])
+        .arg("--allow-unrelated-path")
+        .current_dir(&project_dir)
+        .assert()
+        .failure()
+        .code(4);
+
+    let list = env.list();
+    assert!(!list.contains("8000"), "nothing should be reserved: {list}");
+}
+
+/// Parse `NAME=port` lines from dotenv output into a map.
+fn parse_dotenv_ports(output: &str) -> HashMap<String, u16> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, port)| (name.to_string(), port.parse().expect("port value")))
+        .collect()
+}
+
+/// Test --tag-suffix stores decorated tags but keeps variable names.
+#[test]
+fn test_reserve_group_tag_suffix() {
+    let env = TestEnv::new();
+    let project_dir = env.create_dir("project");
+    let config_path = project_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "dotenv", "--tag-suffix", "task1"])
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let ports = parse_dotenv_ports(&String::from_utf8(output.stdout).unwrap());
+    assert!(ports.contains_key("WEB_PORT"), "{ports:?}");
+    assert!(ports.contains_key("API_PORT"), "{ports:?}");
+
+    let output = env
+        .command()
+        .args(["list", "--format", "json"])
+        .output()
+        .expect("Failed to run list");
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut tags: Vec<&str> = list
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["tag"].as_str().unwrap())
+        .collect();
+    tags.sort_unstable();
+    assert_eq!(tags, ["api-task1", "web-task1"]);
+
+    // --tag-prefix decorates the other end
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "dotenv", "--tag-prefix", "ci"])
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success();
+    let list = env.list();
+    assert!(list.contains("ci-web"), "{list}");
+    assert!(list.contains("ci-api"), "{list}");
+}
+
+/// Test that two different suffixes reserve separate ports for one group.
+#[test]
+fn test_reserve_group_tag_suffixes_do_not_collide() {
+    let env = TestEnv::new();
+    let project_dir = env.create_dir("project");
+    let config_path = project_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let reserve = |suffix: &str| {
+        let output = env
+            .command()
+            .arg("reserve-group")
+            .arg(&config_path)
+            .args(["--format", "dotenv", "--tag-suffix", suffix])
+            .arg("--allow-unrelated-path")
+            .output()
+            .expect("Failed to run reserve-group");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        parse_dotenv_ports(&String::from_utf8(output.stdout).unwrap())
+    };
+    let first = reserve("a");
+    let second = reserve("b");
+
+    assert_ne!(first["WEB_PORT"], second["WEB_PORT"]);
+    assert_ne!(first["API_PORT"], second["API_PORT"]);
+    assert_ne!(first["WEB_PORT"], second["API_PORT"]);
+    assert_ne!(first["API_PORT"], second["WEB_PORT"]);
+}
+
+/// Test --only and --except restrict which services are reserved.
+#[test]
+fn test_reserve_group_service_selectors() {
+    let env = TestEnv::new();
+    let project_dir = env.create_dir("project");
+    let config_path = project_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let reserve = |selector: &[&str]| {
+        let output = env
+            .command()
+            .arg("reserve-group")
+            .arg(&config_path)
+            .args(["--format", "dotenv"])
+            .args(selector)
+            .arg("--allow-unrelated-path")
+            .output()
+            .expect("Failed to run reserve-group");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        parse_dotenv_ports(&String::from_utf8(output.stdout).unwrap())
+    };
+
+    let ports = reserve(&["--only", "web"]);
+    assert_eq!(ports.keys().collect::<Vec<_>>(), ["WEB_PORT"]);
+    let list = env.list();
+    assert!(list.contains("web"), "{list}");
+    assert!(!list.contains("api"), "{list}");
+
+    let ports = reserve(&["--except", "api"]);
+    assert_eq!(ports.keys().collect::<Vec<_>>(), ["WEB_PORT"]);
+
+    let ports = reserve(&["--except", "web"]);
+    assert_eq!(ports.keys().collect::<Vec<_>>(), ["API_PORT"]);
+}
+
+/// Test that unknown services and combined selectors are rejected.
+#[test]
+fn test_reserve_group_service_selector_errors() {
+    let env = TestEnv::new();
+    let project_dir = env.create_dir("project");
+    let config_path = project_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--only", "web,cache"])
+        .arg("--allow-unrelated-path")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Service 'cache' not found"));
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--only", "web", "--except", "api"])
+        .arg("--allow-unrelated-path")
+        .assert()
+        .failure()
+        .code(2);
+
+    assert!(!env.list().contains("web"));
+}
+
 /// Test reserve-group with --format=human.
 ///
 /// Human format provides a readable summary of the allocations, suitable
@@ -375,6 +716,53 @@ fn test_reserve_group_human_format() {
     );
 }
 
+/// Test that the default `--format auto` emits JSON when stdout is captured.
+///
+/// Captured output is never a terminal, so auto must pick JSON; an explicit
+/// `--shell` still selects export statements, and an explicit `--format`
+/// always wins.
+#[test]
+fn test_reserve_group_auto_format_when_piped() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("auto format should be JSON");
+    assert!(json["web"].is_u64(), "{json}");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--shell", "bash", "--allow-unrelated-path"])
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("export WEB_PORT="), "{stdout}");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "dotenv", "--allow-unrelated-path"])
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("WEB_PORT="), "{stdout}");
+}
+
 /// Test reserve-group with --format=export and explicit shell type.
 ///
 /// Export format generates shell-specific variable export statements. When
@@ -480,17 +868,161 @@ fn test_reserve_group_export_format_powershell() {
     );
 }
 
+/// Test reserve-group export format with nushell syntax.
+///
+/// Nushell assigns to the `$env` record: `$env.VAR = "value"`.
+#[test]
+fn test_reserve_group_export_format_nushell() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--format")
+        .arg("export")
+        .arg("--shell")
+        .arg("nu")
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+
+    assert!(
+        stdout.contains("$env.WEB_PORT = \"") || stdout.contains("$env.WEB = \""),
+        "nushell export should use '$env.VAR = \"value\"' syntax: {stdout}"
+    );
+}
+
 // ============================================================================
 // reserve-group: Quiet and Verbose Modes
 // ============================================================================
 
-/// Test reserve-group with --quiet flag.
+/// Test reserve-group with --quiet flag.
+///
+/// Quiet mode should suppress status messages on stderr while still outputting
+/// the formatted allocations on stdout. This is important for scripting where
+/// you want clean output: `eval $(trop reserve-group config.yaml --quiet)`
+#[test]
+fn test_reserve_group_quiet_mode() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("--quiet")
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--format")
+        .arg("json")
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
+
+    // Stdout should still contain the allocations
+    assert!(
+        !stdout.is_empty(),
+        "quiet mode should still output allocations"
+    );
+    let _parsed: serde_json::Value = serde_json::from_str(&stdout).expect("Should be valid JSON");
+
+    // Stderr should be empty (no status messages)
+    assert!(
+        stderr.is_empty() || stderr.trim().is_empty(),
+        "quiet mode should not output status to stderr: {stderr}"
+    );
+}
+
+/// Test reserve-group with --verbose flag.
+///
+/// Verbose mode should include additional diagnostic information on stderr,
+/// while stdout remains clean and contains only the formatted output. This
+/// helps with debugging without breaking scripts that parse stdout.
+#[test]
+fn test_reserve_group_verbose_mode() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("--verbose")
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--format")
+        .arg("json")
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let _stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
+
+    // Stdout should contain clean JSON
+    let _parsed: serde_json::Value = serde_json::from_str(&stdout).expect("Should be valid JSON");
+
+    // Stderr may contain verbose diagnostics (implementation-dependent)
+    // At minimum, it should have some output in verbose mode
+    // (This is a weak assertion since verbose behavior may vary)
+}
+
+/// Test reserve-group with `--summary json`.
+///
+/// The stderr status should be a single JSON object describing the
+/// reservation, so it can be parsed independently of stdout.
+#[test]
+fn test_reserve_group_summary_json() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--format")
+        .arg("dotenv")
+        .arg("--summary")
+        .arg("json")
+        .arg("--allow-unrelated-path")
+        .output()
+        .expect("Failed to run reserve-group");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(stdout.contains("WEB_PORT="));
+
+    let summary: serde_json::Value =
+        serde_json::from_slice(&output.stderr).expect("stderr should be a JSON summary");
+    assert_eq!(summary["reserved"], 2);
+    assert_eq!(summary["config"], config_path.to_str().unwrap());
+    assert!(summary["services"]["web"].as_u64().is_some());
+    assert!(summary["services"]["api"].as_u64().is_some());
+    assert!(summary["warnings"].as_array().unwrap().is_empty());
+}
+
+/// Test reserve-group with `--summary none`.
 ///
-/// Quiet mode should suppress status messages on stderr while still outputting
-/// the formatted allocations on stdout. This is important for scripting where
-/// you want clean output: `eval $(trop reserve-group config.yaml --quiet)`
+/// The status summary should be suppressed while stdout is unaffected.
 #[test]
-fn test_reserve_group_quiet_mode() {
+fn test_reserve_group_summary_none() {
     let env = TestEnv::new();
     let config_dir = env.create_dir("project");
     let config_path = config_dir.join("trop.yaml");
@@ -498,11 +1030,12 @@ fn test_reserve_group_quiet_mode() {
 
     let output = env
         .command()
-        .arg("--quiet")
         .arg("reserve-group")
         .arg(&config_path)
         .arg("--format")
         .arg("json")
+        .arg("--summary")
+        .arg("none")
         .arg("--allow-unrelated-path")
         .output()
         .expect("Failed to run reserve-group");
@@ -510,56 +1043,41 @@ fn test_reserve_group_quiet_mode() {
     assert!(output.status.success());
 
     let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
-    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
-
-    // Stdout should still contain the allocations
-    assert!(
-        !stdout.is_empty(),
-        "quiet mode should still output allocations"
-    );
     let _parsed: serde_json::Value = serde_json::from_str(&stdout).expect("Should be valid JSON");
 
-    // Stderr should be empty (no status messages)
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
     assert!(
-        stderr.is_empty() || stderr.trim().is_empty(),
-        "quiet mode should not output status to stderr: {stderr}"
+        !stderr.contains("Reserved"),
+        "summary should be suppressed: {stderr}"
     );
 }
 
-/// Test reserve-group with --verbose flag.
-///
-/// Verbose mode should include additional diagnostic information on stderr,
-/// while stdout remains clean and contains only the formatted output. This
-/// helps with debugging without breaking scripts that parse stdout.
+/// Test autoreserve with `--summary json` reports the discovered config.
 #[test]
-fn test_reserve_group_verbose_mode() {
+fn test_autoreserve_summary_json() {
     let env = TestEnv::new();
-    let config_dir = env.create_dir("project");
-    let config_path = config_dir.join("trop.yaml");
+    let project_dir = env.create_dir("project");
+    let config_path = project_dir.join("trop.yaml");
     create_test_config(&config_path, "test-project");
 
     let output = env
         .command()
-        .arg("--verbose")
-        .arg("reserve-group")
-        .arg(&config_path)
+        .arg("autoreserve")
         .arg("--format")
         .arg("json")
+        .arg("--summary")
+        .arg("json")
         .arg("--allow-unrelated-path")
+        .current_dir(&project_dir)
         .output()
-        .expect("Failed to run reserve-group");
+        .expect("Failed to run autoreserve");
 
     assert!(output.status.success());
 
-    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
-    let _stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
-
-    // Stdout should contain clean JSON
-    let _parsed: serde_json::Value = serde_json::from_str(&stdout).expect("Should be valid JSON");
-
-    // Stderr may contain verbose diagnostics (implementation-dependent)
-    // At minimum, it should have some output in verbose mode
-    // (This is a weak assertion since verbose behavior may vary)
+    let summary: serde_json::Value =
+        serde_json::from_slice(&output.stderr).expect("stderr should be a JSON summary");
+    assert_eq!(summary["reserved"], 2);
+    assert!(summary["config"].as_str().unwrap().ends_with("trop.yaml"));
 }
 
 /// Test stdout/stderr separation is maintained across all formats.
@@ -1329,6 +1847,371 @@ fn test_autoreserve_with_override_flags() {
         .success();
 }
 
+/// Test that --exclude keeps the group off an ad-hoc range for this run only.
+#[test]
+fn test_reserve_group_exclude() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    let config = create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "json", "--allow-unrelated-path"])
+        .args(["--exclude", "8000..8004"])
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    for service in ["web", "api"] {
+        let port = parsed[service].as_u64().unwrap();
+        assert!(!(8000..=8004).contains(&port), "{service} got {port}");
+    }
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), config);
+}
+
+// ============================================================================
+// Output File
+// ============================================================================
+
+/// Test that --output-file receives exactly what stdout would have.
+///
+/// Two fresh environments with the same config allocate the same ports, so
+/// the file from one can be compared byte-for-byte with stdout from the other.
+#[test]
+fn test_reserve_group_output_file_matches_stdout() {
+    let run = |output_file: Option<&PathBuf>| {
+        let env = TestEnv::new();
+        let config_dir = env.create_dir("project");
+        let config_path = config_dir.join("trop.yaml");
+        create_test_config(&config_path, "test-project");
+
+        let mut cmd = env.command();
+        cmd.arg("reserve-group").arg(&config_path).args([
+            "--format",
+            "dotenv",
+            "--allow-unrelated-path",
+        ]);
+        if let Some(output_file) = output_file {
+            cmd.arg("--output-file").arg(output_file);
+        }
+        let output = cmd.output().expect("Failed to run reserve-group");
+        assert!(output.status.success());
+        output.stdout
+    };
+
+    let stdout = run(None);
+
+    let dir = tempfile::tempdir().unwrap();
+    let output_file = dir.path().join("ports.env");
+    assert!(run(Some(&output_file)).is_empty(), "stdout should be empty");
+
+    let written = fs::read(&output_file).expect("Output file should exist");
+    assert_eq!(written, stdout);
+}
+
+/// Test that an existing output file is only replaced with --force.
+#[test]
+fn test_reserve_group_output_file_requires_force() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+    let output_file = env.path().join("ports.env");
+    fs::write(&output_file, "KEEP=1\n").unwrap();
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "dotenv", "--allow-unrelated-path"])
+        .arg("--output-file")
+        .arg(&output_file)
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains("--force"));
+    assert_eq!(fs::read_to_string(&output_file).unwrap(), "KEEP=1\n");
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "dotenv", "--allow-unrelated-path", "--force"])
+        .arg("--output-file")
+        .arg(&output_file)
+        .assert()
+        .success();
+    let written = fs::read_to_string(&output_file).unwrap();
+    assert!(written.contains("WEB_PORT="), "file: {written}");
+}
+
+/// Test that autoreserve writes its output to --output-file.
+#[test]
+fn test_autoreserve_output_file() {
+    let env = TestEnv::new();
+    let project_dir = env.create_dir("project");
+    create_test_config(&project_dir.join("trop.yaml"), "test-project");
+    let output_file = env.path().join("ports.json");
+
+    let output = env
+        .command()
+        .arg("autoreserve")
+        .args(["--format", "json", "--allow-unrelated-path"])
+        .arg("--output-file")
+        .arg(&output_file)
+        .current_dir(&project_dir)
+        .output()
+        .expect("Failed to run autoreserve");
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let written = fs::read_to_string(&output_file).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&written).expect("Should be valid JSON");
+    assert!(parsed.get("web").is_some(), "file: {written}");
+}
+
+// ============================================================================
+// Dotenv Merging
+// ============================================================================
+
+/// Test that --merge-into updates only the services' variables.
+///
+/// Unrelated keys, comments, and blank lines survive, and a second run
+/// updates the same keys in place instead of appending duplicates.
+#[test]
+fn test_reserve_group_merge_into_preserves_other_lines() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+    let env_file = env.path().join(".env.base");
+    fs::write(
+        &env_file,
+        "# committed defaults\nDATABASE_URL=postgres://localhost/app\n\nWEB_PORT=1234\n",
+    )
+    .unwrap();
+
+    let merge = || {
+        let output = env
+            .command()
+            .arg("reserve-group")
+            .arg(&config_path)
+            .arg("--allow-unrelated-path")
+            .arg("--merge-into")
+            .arg(&env_file)
+            .output()
+            .expect("Failed to run reserve-group");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(output.stdout.is_empty(), "stdout should be empty");
+        fs::read_to_string(&env_file).unwrap()
+    };
+
+    let first = merge();
+    let lines: Vec<&str> = first.lines().collect();
+    assert_eq!(lines[0], "# committed defaults");
+    assert_eq!(lines[1], "DATABASE_URL=postgres://localhost/app");
+    assert_eq!(lines[2], "");
+    assert!(lines[3].starts_with("WEB_PORT="), "file: {first}");
+    assert_ne!(lines[3], "WEB_PORT=1234");
+    assert!(lines[4].starts_with("API_PORT="), "file: {first}");
+    assert_eq!(lines.len(), 5, "file: {first}");
+
+    // Group reservations currently allocate fresh ports on every run, so the
+    // values may change but the keys stay where they were
+    let second = merge();
+    let keys = |contents: &str| -> Vec<String> {
+        contents
+            .lines()
+            .map(|line| line.split('=').next().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(keys(&second), keys(&first), "file: {second}");
+}
+
+/// Test that autoreserve --merge-into creates a missing dotenv file.
+#[test]
+fn test_autoreserve_merge_into_creates_file() {
+    let env = TestEnv::new();
+    let project_dir = env.create_dir("project");
+    create_test_config(&project_dir.join("trop.yaml"), "test-project");
+    let env_file = project_dir.join(".env");
+
+    env.command()
+        .arg("autoreserve")
+        .args(["--allow-unrelated-path", "--quote", "--merge-into"])
+        .arg(&env_file)
+        .current_dir(&project_dir)
+        .assert()
+        .success();
+
+    let written = fs::read_to_string(&env_file).unwrap();
+    assert!(written.contains("API_PORT=\""), "file: {written}");
+    assert!(written.contains("WEB_PORT=\""), "file: {written}");
+}
+
+/// Test that --merge-into can't be combined with another output format.
+#[test]
+fn test_reserve_group_merge_into_conflicts_with_format() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "json", "--merge-into", ".env"])
+        .assert()
+        .failure();
+}
+
+/// Test that `env` is accepted as an alias for the dotenv format.
+#[test]
+fn test_reserve_group_env_format_alias() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--format", "env", "--allow-unrelated-path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("WEB_PORT="))
+        .stdout(predicate::str::contains("export").not());
+}
+
+// ============================================================================
+// Named Groups
+// ============================================================================
+
+/// Write a trop.yaml with `frontend` and `backend` reservation groups.
+fn create_multi_group_config(path: &PathBuf) {
+    let config = r#"
+project: "multi"
+
+ports:
+  min: 5000
+  max: 9000
+
+reservations:
+  frontend:
+    base: 8000
+    services:
+      web:
+        offset: 0
+        env: WEB_PORT
+  backend:
+    base: 8100
+    services:
+      api:
+        offset: 0
+        env: API_PORT
+      worker:
+        offset: 1
+        env: WORKER_PORT
+"#;
+    fs::write(path, config).expect("Failed to write test config");
+}
+
+/// Test that --group reserves only the chosen group's services.
+#[test]
+fn test_reserve_group_named_group() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_multi_group_config(&config_path);
+
+    let output = env
+        .command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--group", "backend", "--format", "dotenv"])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("API_PORT="), "{stdout}");
+    assert!(stdout.contains("WORKER_PORT="), "{stdout}");
+    assert!(!stdout.contains("WEB_PORT"), "{stdout}");
+}
+
+/// Test that autoreserve accepts --group too.
+#[test]
+fn test_autoreserve_named_group() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    create_multi_group_config(&config_dir.join("trop.yaml"));
+
+    let output = env
+        .command()
+        .args(["autoreserve", "--group", "frontend", "--format", "dotenv"])
+        .current_dir(&config_dir)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("WEB_PORT="), "{stdout}");
+    assert!(!stdout.contains("API_PORT"), "{stdout}");
+}
+
+/// Test that an unknown group name fails and lists the defined groups.
+#[test]
+fn test_reserve_group_unknown_group() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_multi_group_config(&config_path);
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .args(["--group", "mobile"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Reservation group 'mobile' not found (available: backend, frontend)",
+        ));
+}
+
+/// Test that several groups without --group is an error.
+#[test]
+fn test_reserve_group_several_groups_require_group() {
+    let env = TestEnv::new();
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_multi_group_config(&config_path);
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("choose one with --group"));
+}
+
 // ============================================================================
 // Error Cases
 // ============================================================================
@@ -1643,3 +2526,51 @@ fn test_reserve_group_then_autoreserve() {
     assert!(json2.get("web").is_some());
     assert!(json2.get("api").is_some());
 }
+
+/// Test that reserve-group and autoreserve ask the pre-reserve hook about
+/// each service's port, moving past a vetoed base port.
+#[cfg(unix)]
+#[test]
+fn test_group_commands_run_pre_reserve_hook() {
+    let env = TestEnv::new();
+    fs::create_dir_all(&env.data_dir).unwrap();
+    fs::write(
+        env.data_dir.join("config.yaml"),
+        "pre_reserve_hook: 'test \"$TROP_HOOK_PORT\" != 8000'\n",
+    )
+    .unwrap();
+
+    let config_dir = env.create_dir("project");
+    let config_path = config_dir.join("trop.yaml");
+    create_test_config(&config_path, "test-project");
+
+    let output = env
+        .command()
+        .args([
+            "reserve-group",
+            "--format",
+            "json",
+            "--allow-unrelated-path",
+        ])
+        .arg(&config_path)
+        .output()
+        .expect("Failed to run reserve-group");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["web"], 8001, "vetoed base port was used: {json}");
+    assert_eq!(json["api"], 8002);
+
+    let auto_dir = env.create_dir("auto");
+    create_test_config(&auto_dir.join("trop.yaml"), "auto-project");
+
+    let output = env
+        .command()
+        .args(["autoreserve", "--format", "json", "--allow-unrelated-path"])
+        .current_dir(&auto_dir)
+        .output()
+        .expect("Failed to run autoreserve");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["web"], 8003, "autoreserve skipped the hook: {json}");
+    assert_eq!(json["api"], 8004);
+}
@@ -0,0 +1,82 @@
+//! Integration tests for the `health` command.
+//!
+//! These tests verify the overall status and exit code for a healthy
+//! installation and for missing or corrupted databases.
+
+mod common;
+
+use common::TestEnv;
+
+/// Run `health --format json` and return its exit success and report.
+fn health(env: &TestEnv) -> (bool, serde_json::Value) {
+    let output = env
+        .command()
+        .args(["health", "--format", "json"])
+        .output()
+        .expect("Failed to run health");
+    let report = serde_json::from_slice(&output.stdout).expect("health should print JSON");
+    (output.status.success(), report)
+}
+
+/// Find a check in the report by name.
+fn check<'a>(report: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == name)
+        .unwrap_or_else(|| panic!("missing {name} check: {report}"))
+}
+
+/// Test that an initialized, usable installation reports ok and exits 0.
+#[test]
+fn test_health_ok() {
+    let env = TestEnv::new();
+    env.reserve_simple(&env.create_dir("project"));
+
+    let (success, report) = health(&env);
+    assert!(success, "{report}");
+    assert_eq!(report["status"], "ok");
+    for name in ["data_dir", "database", "schema", "integrity"] {
+        assert_eq!(check(&report, name)["status"], "pass", "{report}");
+    }
+}
+
+/// Test that a missing database fails and skips the checks that need it.
+#[test]
+fn test_health_missing_database_fails() {
+    let env = TestEnv::new();
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+
+    let (success, report) = health(&env);
+    assert!(!success);
+    assert_eq!(report["status"], "fail");
+    assert_eq!(check(&report, "data_dir")["status"], "pass");
+    assert_eq!(check(&report, "database")["status"], "fail");
+    assert_eq!(check(&report, "integrity")["status"], "skip");
+
+    env.command()
+        .args(["health", "--format", "text"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicates::str::contains("[FAIL] database:"))
+        .stdout(predicates::str::contains("status: fail"));
+}
+
+/// Test that a corrupted database file fails.
+#[test]
+fn test_health_corrupted_database_fails() {
+    let env = TestEnv::new();
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+    std::fs::write(
+        env.data_dir.join("trop.db"),
+        b"this is not a sqlite database, just some bytes padded out to a page",
+    )
+    .unwrap();
+
+    let (success, report) = health(&env);
+    assert!(!success);
+    assert_eq!(report["status"], "fail");
+    assert_eq!(check(&report, "database")["status"], "fail", "{report}");
+}
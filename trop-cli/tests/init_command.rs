@@ -3,10 +3,11 @@
 //! These tests verify all aspects of database initialization, including:
 //! - Fresh initialization in empty directory
 //! - Existing directory handling
-//! - Existing database error handling
+//! - Existing database handling (idempotent re-init, --migrate, --schema-version)
 //! - Overwrite mode (--overwrite flag)
 //! - Config file creation (--with-config flag)
 //! - Config file preservation (not overwriting existing)
+//! - Project trop.yaml scaffolding (--project-config, --force)
 //! - Dry-run mode (--dry-run flag)
 //! - Custom data-dir handling (--data-dir flag)
 //! - Global data-dir flag respect
@@ -120,19 +121,17 @@ fn test_init_existing_directory() {
 }
 
 // ============================================================================
-// Existing Database Error Handling
+// Existing Database Handling
 // ============================================================================
 
-/// Test error when database already exists (without --overwrite).
+/// Test re-running init on an existing database (without --overwrite).
 ///
 /// If a database already exists and --overwrite is not specified, init should:
-/// - Fail with an error
+/// - Succeed (so provisioning scripts can safely re-run it)
+/// - Report that trop is already initialized, with the schema version
 /// - Not modify the existing database
-/// - Provide a helpful error message mentioning --overwrite
-///
-/// This prevents accidentally destroying existing data.
 #[test]
-fn test_init_existing_database_error() {
+fn test_init_existing_database_is_idempotent() {
     let env = TestEnv::new();
 
     // Initialize once
@@ -150,7 +149,7 @@ fn test_init_existing_database_error() {
     let original_metadata = fs::metadata(&db_path).expect("Failed to get metadata");
     let original_modified = original_metadata.modified().expect("Failed to get mtime");
 
-    // Try to init again without --overwrite
+    // Init again without --overwrite
     let output = env
         .command_bare()
         .arg("init")
@@ -159,18 +158,16 @@ fn test_init_existing_database_error() {
         .output()
         .expect("Failed to run init");
 
-    // Should fail
     assert!(
-        !output.status.success(),
-        "Init should fail when database exists"
+        output.status.success(),
+        "Init should succeed when database exists: {}",
+        String::from_utf8_lossy(&output.stderr)
     );
 
-    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
-
-    // Error message should mention the problem
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
     assert!(
-        stderr.contains("already exists") || stderr.contains("exist"),
-        "Error should mention database exists: {stderr}"
+        stdout.contains("already initialized") && stdout.contains("(schema 5)"),
+        "Output should report the existing schema: {stdout}"
     );
 
     // Database should be unchanged (check modification time)
@@ -182,6 +179,169 @@ fn test_init_existing_database_error() {
     );
 }
 
+/// Test init reports an outdated schema and upgrades it with --migrate.
+///
+/// An uninitialized database file (schema 0) stands in for an outdated one.
+#[test]
+fn test_init_migrate_upgrades_existing_database() {
+    let env = TestEnv::new();
+    fs::create_dir_all(&env.data_dir).expect("Failed to create data dir");
+    fs::write(env.data_dir.join("trop.db"), b"").expect("Failed to create database file");
+
+    // Without --migrate: report only, exit 0
+    let output = env
+        .command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .output()
+        .expect("Failed to run init");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(
+        stdout.contains("(schema 0)") && stdout.contains("--migrate"),
+        "Output should suggest --migrate: {stdout}"
+    );
+
+    // With --migrate: upgrade in the same invocation
+    let output = env
+        .command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .arg("--migrate")
+        .output()
+        .expect("Failed to run init");
+
+    assert!(
+        output.status.success(),
+        "Init --migrate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(
+        stdout.contains("Upgraded database schema from 0 to 5"),
+        "Output should report the upgrade: {stdout}"
+    );
+
+    // The upgraded database is usable
+    let path = env.create_dir("project");
+    env.reserve_simple(&path);
+}
+
+/// Create a database in `env.data_dir` and roll it back to schema 2 by
+/// dropping the columns added in later versions.
+fn create_schema_2_database(env: &TestEnv) {
+    env.command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .assert()
+        .success();
+    let conn = rusqlite::Connection::open(env.data_dir.join("trop.db")).unwrap();
+    conn.execute_batch(
+        "ALTER TABLE reservations DROP COLUMN priority;
+         ALTER TABLE reservations DROP COLUMN note;
+         ALTER TABLE reservations DROP COLUMN expires_at;
+         UPDATE metadata SET value = '2' WHERE key = 'schema_version';",
+    )
+    .unwrap();
+}
+
+/// Test --migrate --dry-run lists the pending schema steps and leaves the
+/// database alone.
+#[test]
+fn test_init_migrate_dry_run_lists_pending_steps() {
+    let env = TestEnv::new();
+    create_schema_2_database(&env);
+
+    let output = env
+        .command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .arg("--migrate")
+        .arg("--dry-run")
+        .output()
+        .expect("Failed to run init");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(
+        stdout.contains("Upgrade database schema from 2 to 5"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Schema 3: add expires_at column"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Schema 4: add note column"), "{stdout}");
+    assert!(stdout.contains("Schema 5: add priority column"), "{stdout}");
+    assert!(!stdout.contains("Schema 2:"), "{stdout}");
+
+    let output = env
+        .command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .output()
+        .expect("Failed to run init");
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(
+        stdout.contains("(schema 2)"),
+        "Dry-run should not upgrade: {stdout}"
+    );
+}
+
+/// Test --schema-version upgrades no further than the target, rejects
+/// downgrades, and is a no-op when the database is already there.
+#[test]
+fn test_init_migrate_to_schema_version() {
+    let env = TestEnv::new();
+    create_schema_2_database(&env);
+
+    let migrate_to = |version: &str| {
+        env.command_bare()
+            .arg("init")
+            .arg("--data-dir")
+            .arg(&env.data_dir)
+            .arg("--migrate")
+            .arg("--schema-version")
+            .arg(version)
+            .output()
+            .expect("Failed to run init")
+    };
+
+    let output = migrate_to("3");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(
+        stdout.contains("Upgraded database schema from 2 to 3"),
+        "{stdout}"
+    );
+
+    // Already at the target: nothing to upgrade
+    let output = migrate_to("3");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(stdout.contains("(schema 3)"), "{stdout}");
+    assert!(!stdout.contains("Upgraded"), "{stdout}");
+
+    let output = migrate_to("2");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("downgrade"), "{stderr}");
+
+    env.command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .arg("--schema-version")
+        .arg("4")
+        .assert()
+        .failure();
+}
+
 // ============================================================================
 // Overwrite Mode Tests
 // ============================================================================
@@ -383,6 +543,98 @@ fn test_init_preserves_existing_config() {
     );
 }
 
+/// Test --project-config scaffolds a starter trop.yaml that validates.
+///
+/// The scaffold is written into the working directory and must pass the
+/// same checks as `trop validate`.
+#[test]
+fn test_init_project_config_scaffolds_valid_tropfile() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+
+    // --with-config alone only writes the data directory's config.yaml
+    env.command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .arg("--with-config")
+        .current_dir(&project)
+        .assert()
+        .success();
+    assert!(!project.join("trop.yaml").exists());
+
+    let output = env
+        .command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .arg("--project-config")
+        .current_dir(&project)
+        .output()
+        .expect("Failed to run init");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(
+        stdout.contains("Created project configuration"),
+        "Should report the scaffold: {stdout}"
+    );
+
+    let tropfile = project.join("trop.yaml");
+    let contents = fs::read_to_string(&tropfile).expect("trop.yaml should be written");
+    assert!(contents.contains("ports:"), "{contents}");
+    assert!(contents.contains("reservations:"), "{contents}");
+    assert!(contents.contains("# "), "Scaffold should be commented");
+
+    env.command()
+        .arg("validate")
+        .arg(&tropfile)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Configuration is valid"));
+}
+
+/// Test --project-config refuses to overwrite an existing trop.yaml unless
+/// --force is given, and does nothing else when it refuses.
+#[test]
+fn test_init_project_config_requires_force_to_overwrite_tropfile() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    let tropfile = project.join("trop.yaml");
+    fs::write(&tropfile, "project: mine\n").expect("Failed to write trop.yaml");
+
+    env.command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .arg("--project-config")
+        .current_dir(&project)
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicates::str::contains("--force"));
+    assert_eq!(fs::read_to_string(&tropfile).unwrap(), "project: mine\n");
+    assert!(
+        !env.data_dir.join("trop.db").exists(),
+        "Refusing should leave the data directory untouched"
+    );
+
+    env.command_bare()
+        .arg("init")
+        .arg("--data-dir")
+        .arg(&env.data_dir)
+        .arg("--project-config")
+        .arg("--force")
+        .current_dir(&project)
+        .assert()
+        .success();
+    let contents = fs::read_to_string(&tropfile).unwrap();
+    assert!(contents.contains("reservations:"), "{contents}");
+}
+
 /// Test init without --with-config doesn't create config.
 ///
 /// By default (without --with-config), init should:
@@ -555,11 +807,11 @@ fn test_init_dry_run_with_config() {
     assert!(!config_path.exists(), "Dry-run should not create config");
 }
 
-/// Test --dry-run shows error for existing database (without --overwrite).
+/// Test --dry-run reports an existing database (without --overwrite).
 ///
-/// Even in dry-run mode, we should indicate what the error would be.
+/// Even in dry-run mode, we should indicate that the database would be kept.
 #[test]
-fn test_init_dry_run_shows_error_for_existing_db() {
+fn test_init_dry_run_reports_existing_db() {
     let env = TestEnv::new();
 
     // Create database
@@ -570,7 +822,7 @@ fn test_init_dry_run_shows_error_for_existing_db() {
         .assert()
         .success();
 
-    // Dry-run without --overwrite (should show error)
+    // Dry-run without --overwrite (should report the existing database)
     let output = env
         .command_bare()
         .arg("init")
@@ -584,10 +836,10 @@ fn test_init_dry_run_shows_error_for_existing_db() {
 
     let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
 
-    // Should mention an error would occur
+    // Should mention the existing database
     assert!(
-        stdout.contains("ERROR") || stdout.contains("already exists"),
-        "Should show error would occur: {stdout}"
+        stdout.contains("already exists"),
+        "Should report existing database: {stdout}"
     );
 }
 
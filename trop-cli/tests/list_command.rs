@@ -10,7 +10,7 @@
 
 mod common;
 
-use common::TestEnv;
+use common::{parse_port, TestEnv};
 use serde_json::Value;
 
 // ============================================================================
@@ -130,19 +130,94 @@ fn test_list_shows_metadata() {
 /// Test default table format.
 ///
 /// Without specifying a format, list should output in table format
-/// (tab-separated values with header).
+/// (space-aligned columns with header).
 #[test]
 fn test_list_default_table_format() {
     let env = TestEnv::new();
     let test_path = env.create_dir("test-project");
     env.reserve_simple(&test_path);
 
-    // Default format should be table (contains tabs)
+    // Default format should be table (aligned with spaces, not tabs)
     let output = env.list();
-    assert!(output.contains('\t'), "Table format should use tabs");
+    assert!(
+        !output.contains('\t'),
+        "Table format should align with spaces"
+    );
     assert!(output.contains("PORT"), "Should have header");
 }
 
+/// Test the table aligns each reservation's values under the header columns.
+#[test]
+fn test_list_table_columns_align() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("aligned-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("web")
+        .arg("--project")
+        .arg("shop")
+        .arg("--task")
+        .arg("checkout")
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let port = parse_port(&String::from_utf8_lossy(&output.stdout));
+
+    let output = env.list();
+    let mut lines = output.lines();
+    let header = lines.next().unwrap();
+    let row = lines.next().unwrap();
+    assert_eq!(
+        header.split_whitespace().collect::<Vec<_>>(),
+        ["PORT", "PATH", "TAG", "PROJECT", "TASK", "USED", "NOTE"]
+    );
+
+    // Every value starts where its column header starts
+    let column = |name: &str| header.find(name).unwrap();
+    let cell = |at: usize| row[at..].split_whitespace().next().unwrap();
+    assert_eq!(cell(column("PORT")), port.to_string());
+    assert!(
+        cell(column("PATH")).ends_with("aligned-project"),
+        "{output}"
+    );
+    assert_eq!(cell(column("TAG")), "web");
+    assert_eq!(cell(column("PROJECT")), "shop");
+    assert_eq!(cell(column("TASK")), "checkout");
+    assert_eq!(cell(column("NOTE")), "-");
+}
+
+/// Test long paths are truncated with an ellipsis unless --no-truncate is given.
+#[test]
+fn test_list_table_truncates_long_paths() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir(&["nested-directory"; 4].join("/"));
+    env.reserve_simple(&test_path);
+
+    let output = env.list();
+    assert!(
+        output.contains('…'),
+        "Long path should be truncated: {output}"
+    );
+
+    let output = env
+        .command()
+        .args(["list", "--no-truncate", "--show-full-paths"])
+        .output()
+        .unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.contains('…'), "{output}");
+    assert!(
+        output.contains(&test_path.display().to_string()),
+        "{output}"
+    );
+}
+
 /// Test JSON format.
 ///
 /// JSON format should produce valid JSON with all reservation details.
@@ -208,6 +283,41 @@ fn test_list_json_empty() {
     assert_eq!(json.as_array().unwrap().len(), 0);
 }
 
+/// Test YAML format.
+///
+/// YAML output is a sequence of the same records as JSON, and an empty
+/// database is an empty sequence.
+#[test]
+fn test_list_yaml_format() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .args(["list", "--format", "yaml"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let yaml: Vec<serde_yaml::Value> = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert!(yaml.is_empty());
+
+    let test_path = env.create_dir("test-project");
+    let port = env.reserve_simple(&test_path);
+
+    let output = env
+        .command()
+        .args(["list", "--format", "yaml"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let yaml: Vec<serde_yaml::Value> = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert_eq!(yaml.len(), 1);
+    assert_eq!(yaml[0]["port"].as_u64(), Some(u64::from(port)));
+    assert_eq!(
+        yaml[0]["path"].as_str(),
+        Some(test_path.to_string_lossy().as_ref())
+    );
+}
+
 /// Test CSV format.
 ///
 /// CSV format should produce comma-separated values with proper escaping.
@@ -497,6 +607,62 @@ fn test_list_filter_no_matches() {
     assert_eq!(lines.len(), 1, "Should only have header line");
 }
 
+/// Test the --project, --task, and --tag filters combined.
+///
+/// Only reservations matching all of the given filters should be listed.
+#[test]
+fn test_list_filter_project_task_tag() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+
+    let reserve = |tag: &str, project: &str, task: &str| {
+        let output = env
+            .command()
+            .args([
+                "reserve",
+                "--tag",
+                tag,
+                "--project",
+                project,
+                "--task",
+                task,
+            ])
+            .arg("--path")
+            .arg(&path)
+            .arg("--allow-unrelated-path")
+            .output()
+            .unwrap();
+        common::parse_port(&String::from_utf8(output.stdout).unwrap())
+    };
+    let web_main = reserve("web", "alpha", "main");
+    reserve("api", "alpha", "main");
+    reserve("db", "alpha", "feature");
+
+    let ports = |args: &[&str]| -> Vec<u64> {
+        let output = env
+            .command()
+            .args(["list", "--format", "json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json.as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["port"].as_u64().unwrap())
+            .collect()
+    };
+
+    assert_eq!(ports(&["--project", "alpha"]).len(), 3);
+    assert_eq!(ports(&["--task", "main"]).len(), 2);
+    assert_eq!(
+        ports(&["--project", "alpha", "--task", "main", "--tag", "web"]),
+        vec![u64::from(web_main)]
+    );
+    assert!(ports(&["--task", "feature", "--tag", "web"]).is_empty());
+}
+
 // ============================================================================
 // Path Display Tests
 // ============================================================================
@@ -614,8 +780,8 @@ fn test_cli_format_overrides_env() {
 
     let output_str = String::from_utf8(output.stdout).unwrap();
 
-    // Should be table format (tabs), not JSON
-    assert!(output_str.contains('\t'));
+    // Should be table format, not JSON
+    assert!(output_str.starts_with("PORT"));
     assert!(!output_str.trim().starts_with('['));
 }
 
@@ -701,6 +867,54 @@ fn test_list_with_special_characters_in_path() {
     assert!(csv.contains("spaces"));
 }
 
+/// Test that CSV output quotes fields and parses back to the same values.
+///
+/// A path containing a comma and a note containing quotes must survive a
+/// round trip through a CSV parser, and timestamps are RFC 3339.
+#[test]
+fn test_list_csv_round_trips_special_fields() {
+    let env = TestEnv::new();
+    let special_path = env.create_dir("alpha,beta");
+    let note = "say \"hi\", then leave";
+
+    let output = env
+        .command()
+        .args(["reserve", "--allow-unrelated-path", "--note", note])
+        .arg("--path")
+        .arg(&special_path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let port = parse_port(&String::from_utf8(output.stdout).unwrap());
+
+    let output = env
+        .command()
+        .args(["list", "--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut reader = csv::Reader::from_reader(output.stdout.as_slice());
+    let headers = reader.headers().unwrap().clone();
+    assert_eq!(headers.get(1), Some("path"));
+    let records: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+    assert_eq!(records.len(), 1);
+
+    let record = &records[0];
+    assert_eq!(record.get(0), Some(port.to_string().as_str()));
+    assert_eq!(record.get(1), special_path.to_str());
+    assert_eq!(record.get(7), Some(note));
+    let created_at = record.get(5).unwrap();
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(created_at).is_ok(),
+        "{created_at}"
+    );
+}
+
 /// Test list with Unicode in metadata.
 ///
 /// Project names, tasks, and tags with Unicode should display correctly.
@@ -787,6 +1001,136 @@ fn test_list_verbose_mode() {
     // Just verify it doesn't crash
 }
 
+// ============================================================================
+// Ports-Only Tests
+// ============================================================================
+
+/// Test that --ports-only prints just the sorted port numbers.
+#[test]
+fn test_list_ports_only() {
+    let env = TestEnv::new();
+    let path1 = env.create_dir("project1");
+    let path2 = env.create_dir("project2");
+    let path3 = env.create_dir("project3");
+
+    let mut ports = [
+        env.reserve_simple(&path1),
+        env.reserve_simple(&path2),
+        env.reserve_simple(&path3),
+    ];
+    ports.sort_unstable();
+
+    let output = env
+        .command()
+        .arg("list")
+        .arg("--ports-only")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let expected: String = ports.iter().map(|p| format!("{p}\n")).collect();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
+
+/// Test that --join puts the ports on one line with the given separator.
+#[test]
+fn test_list_ports_only_join() {
+    let env = TestEnv::new();
+    let path1 = env.create_dir("project1");
+    let path2 = env.create_dir("project2");
+
+    let mut ports = [env.reserve_simple(&path1), env.reserve_simple(&path2)];
+    ports.sort_unstable();
+
+    let output = env
+        .command()
+        .arg("list")
+        .arg("--ports-only")
+        .arg("--join")
+        .arg(",")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{},{}\n", ports[0], ports[1])
+    );
+}
+
+/// Test that --ports-only respects filters.
+#[test]
+fn test_list_ports_only_with_filters() {
+    let env = TestEnv::new();
+    let inside = env.create_dir("inside");
+    let outside = env.create_dir("outside");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&inside)
+        .arg("--project")
+        .arg("proj-a")
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    let port_a = common::parse_port(&String::from_utf8(output.stdout).unwrap());
+    let port_b = env.reserve_simple(&outside);
+
+    let output = env
+        .command()
+        .arg("list")
+        .arg("--ports-only")
+        .arg("--filter-project")
+        .arg("proj-a")
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{port_a}\n")
+    );
+
+    let output = env
+        .command()
+        .arg("list")
+        .arg("--ports-only")
+        .arg("--filter-path")
+        .arg(&outside)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{port_b}\n")
+    );
+}
+
+/// Test that --ports-only prints nothing when there are no reservations.
+#[test]
+fn test_list_ports_only_empty() {
+    let env = TestEnv::new();
+
+    env.command()
+        .arg("list")
+        .arg("--ports-only")
+        .assert()
+        .success()
+        .stdout("");
+}
+
+/// Test that --join requires --ports-only.
+#[test]
+fn test_list_join_requires_ports_only() {
+    let env = TestEnv::new();
+
+    env.command()
+        .arg("list")
+        .arg("--join")
+        .arg(",")
+        .assert()
+        .failure();
+}
+
 // ============================================================================
 // Integration Tests
 // ============================================================================
@@ -840,3 +1184,98 @@ fn test_list_with_many_reservations() {
     // Should have header + 50 data lines
     assert_eq!(lines.len(), 51, "Should have 1 header + 50 reservations");
 }
+
+// ============================================================================
+// Time Filter Tests
+// ============================================================================
+
+/// Set a reservation's created and last-used timestamps to `days` days ago.
+fn backdate(env: &TestEnv, port: u16, created_days: u64, used_days: u64) {
+    let ago = |days: u64| {
+        let at = std::time::SystemTime::now() - std::time::Duration::from_secs(days * 86400);
+        at.duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64
+    };
+    let conn = rusqlite::Connection::open(env.data_dir.join("trop.db")).unwrap();
+    conn.execute(
+        "UPDATE reservations SET created_at = ?1, last_used_at = ?2 WHERE port = ?3",
+        rusqlite::params![ago(created_days), ago(used_days), i64::from(port)],
+    )
+    .unwrap();
+}
+
+/// Run `list --ports-only` with extra arguments and return the sorted ports.
+fn list_ports(env: &TestEnv, args: &[&str]) -> Vec<u16> {
+    let output = env
+        .command()
+        .arg("list")
+        .arg("--ports-only")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "list failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let mut ports: Vec<u16> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect();
+    ports.sort_unstable();
+    ports
+}
+
+/// Test filtering by creation and last-used time with relative bounds.
+///
+/// Seeds three reservations: created 30 days ago and idle since, created 30
+/// days ago but used yesterday, and created yesterday.
+#[test]
+fn test_list_filter_by_time() {
+    let env = TestEnv::new();
+    let idle = env.reserve_simple(&env.create_dir("idle"));
+    let busy = env.reserve_simple(&env.create_dir("busy"));
+    let new = env.reserve_simple(&env.create_dir("new"));
+    backdate(&env, idle, 30, 30);
+    backdate(&env, busy, 30, 1);
+    backdate(&env, new, 1, 1);
+
+    let mut old = vec![idle, busy];
+    old.sort_unstable();
+    let mut recent = vec![busy, new];
+    recent.sort_unstable();
+
+    assert_eq!(list_ports(&env, &["--created-before", "7d"]), old);
+    assert_eq!(list_ports(&env, &["--created-since", "7d"]), vec![new]);
+    assert_eq!(list_ports(&env, &["--used-since", "7d"]), recent);
+    assert_eq!(list_ports(&env, &["--used-before", "7d"]), vec![idle]);
+    assert_eq!(
+        list_ports(&env, &["--created-before", "7d", "--used-since", "7d"]),
+        vec![busy]
+    );
+}
+
+/// Test that an absolute RFC 3339 bound is accepted.
+#[test]
+fn test_list_filter_by_rfc3339_time() {
+    let env = TestEnv::new();
+    let port = env.reserve_simple(&env.create_dir("project"));
+
+    assert_eq!(
+        list_ports(&env, &["--created-since", "2000-01-01T00:00:00Z"]),
+        vec![port]
+    );
+    assert!(list_ports(&env, &["--created-before", "2000-01-01T00:00:00Z"]).is_empty());
+}
+
+/// Test that an unparseable time is rejected as an invalid argument.
+#[test]
+fn test_list_filter_rejects_invalid_time() {
+    let env = TestEnv::new();
+
+    env.command()
+        .args(["list", "--used-since", "last tuesday"])
+        .assert()
+        .failure()
+        .code(4);
+}
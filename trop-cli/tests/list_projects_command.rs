@@ -8,6 +8,7 @@
 //! - Alphabetical ordering
 //! - Output format (one per line)
 //! - Integration with reserve operations
+//! - JSON output with per-project counts
 
 mod common;
 
@@ -931,3 +932,74 @@ fn test_list_projects_many_projects() {
     assert!(lines[0].starts_with("project-"));
     assert!(lines[99].starts_with("project-"));
 }
+
+// ============================================================================
+// JSON Format Tests
+// ============================================================================
+
+/// Test `--format json` reports per-project counts, with reservations lacking
+/// a project grouped under a `null` project.
+#[test]
+fn test_list_projects_json_counts() {
+    let env = TestEnv::new();
+
+    for (dir, project) in [("api", "shop"), ("web", "shop"), ("docs", "blog")] {
+        let path = env.create_dir(dir);
+        env.command()
+            .arg("reserve")
+            .arg("--path")
+            .arg(&path)
+            .arg("--project")
+            .arg(project)
+            .arg("--allow-unrelated-path")
+            .assert()
+            .success();
+    }
+    env.reserve_simple(&env.create_dir("scratch"));
+
+    let output = env
+        .command()
+        .args(["list-projects", "--format", "json"])
+        .output()
+        .expect("Failed to run list-projects");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_array().unwrap();
+    let counts: Vec<(serde_json::Value, u64)> = entries
+        .iter()
+        .map(|e| {
+            (
+                e["project"].clone(),
+                e["reservation_count"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            (serde_json::Value::Null, 1),
+            (serde_json::json!("blog"), 1),
+            (serde_json::json!("shop"), 2),
+        ]
+    );
+    for entry in entries {
+        assert!(entry["latest_used_at"].is_string(), "{entry}");
+    }
+}
+
+/// Test `--format json` on an empty database is an empty array.
+#[test]
+fn test_list_projects_json_empty() {
+    let env = TestEnv::new();
+
+    let output = env
+        .command()
+        .args(["list-projects", "--format", "json"])
+        .output()
+        .expect("Failed to run list-projects");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json, serde_json::json!([]));
+}
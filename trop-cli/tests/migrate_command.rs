@@ -64,14 +64,14 @@ impl TestEnv {
 
         let stdout = String::from_utf8(output.stdout).ok()?;
         // Parse port from list output
-        // Format is tab-separated: PORT\tPATH\tTAG\tPROJECT\tTASK\tCREATED_AT\tLAST_USED_AT
+        // Columns are space-aligned: PORT PATH TAG PROJECT TASK USED NOTE
         // Skip the header line and parse the first data line
         stdout
             .lines()
             .nth(1) // Skip header and get first data line
             .and_then(|line| {
                 // First column is the port
-                line.split('\t').next()
+                line.split_whitespace().next()
             })
             .and_then(|port_str| port_str.trim().parse::<u16>().ok())
     }
@@ -0,0 +1,133 @@
+//! Integration tests for the `move` command.
+//!
+//! These tests verify that `trop move` transfers one reservation to a new
+//! path without reallocating it:
+//! - The port, tag, and project are kept and the old key is gone
+//! - Moving onto an existing reservation requires --force
+//! - Dry runs leave the database unchanged
+//! - Moving a missing reservation fails with exit code 1
+
+mod common;
+
+use common::TestEnv;
+
+// ============================================================================
+// Move Tests
+// ============================================================================
+
+/// Test that move keeps the port and tag at the new path.
+#[test]
+fn test_move_keeps_port() {
+    let env = TestEnv::new();
+    let old = env.create_dir("old-name");
+    let new = env.create_dir("new-name");
+    let port = env.reserve_with_tag(&old, "web");
+
+    let output = env
+        .command()
+        .args(["move", "--tag", "web", "--path"])
+        .arg(&old)
+        .arg("--to")
+        .arg(&new)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "move failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        common::parse_port(&String::from_utf8_lossy(&output.stdout)),
+        port
+    );
+
+    // The reservation is found at the new path with the same port
+    let output = env
+        .command()
+        .args(["assert-reservation", "--tag", "web", "--path"])
+        .arg(&new)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        common::parse_port(&String::from_utf8_lossy(&output.stdout)),
+        port
+    );
+    env.command()
+        .args(["assert-reservation", "--tag", "web", "--path"])
+        .arg(&old)
+        .assert()
+        .failure();
+}
+
+/// Test that moving onto an existing reservation requires --force.
+#[test]
+fn test_move_conflict_requires_force() {
+    let env = TestEnv::new();
+    let old = env.create_dir("old-name");
+    let new = env.create_dir("new-name");
+    let port = env.reserve_simple(&old);
+    let other = env.reserve_simple(&new);
+
+    env.command()
+        .args(["move", "--path"])
+        .arg(&old)
+        .arg("--to")
+        .arg(&new)
+        .assert()
+        .failure()
+        .code(1);
+    assert!(env.list().contains(&other.to_string()));
+
+    env.command()
+        .args(["move", "--force", "--path"])
+        .arg(&old)
+        .arg("--to")
+        .arg(&new)
+        .assert()
+        .success();
+
+    let listing = env.list();
+    assert!(listing.contains(&port.to_string()));
+    assert!(!listing.contains(&other.to_string()));
+}
+
+/// Test that dry-run makes no changes.
+#[test]
+fn test_move_dry_run() {
+    let env = TestEnv::new();
+    let old = env.create_dir("old-name");
+    let new = env.create_dir("new-name");
+    env.reserve_simple(&old);
+
+    env.command()
+        .args(["move", "--dry-run", "--path"])
+        .arg(&old)
+        .arg("--to")
+        .arg(&new)
+        .assert()
+        .success();
+
+    env.command()
+        .args(["assert-reservation", "--path"])
+        .arg(&old)
+        .assert()
+        .success();
+}
+
+/// Test that moving a missing reservation fails with exit code 1.
+#[test]
+fn test_move_missing_reservation() {
+    let env = TestEnv::new();
+    let old = env.create_dir("old-name");
+    let new = env.create_dir("new-name");
+
+    env.command()
+        .args(["move", "--path"])
+        .arg(&old)
+        .arg("--to")
+        .arg(&new)
+        .assert()
+        .failure()
+        .code(1);
+}
@@ -0,0 +1,329 @@
+//! Integration tests for the `pin` and `unpin` commands.
+//!
+//! These tests verify that pinned reservations survive every operation that
+//! could move or remove them, including forced ones:
+//! - Single release (with and without --force)
+//! - Recursive release and release by port
+//! - Group reallocation via reserve-group --force
+//! - Cleanup (prune)
+//! - Migration onto a pinned destination
+//!
+//! Each displacement path is also checked to succeed once the reservation
+//! is unpinned.
+
+mod common;
+
+use common::TestEnv;
+use std::fs;
+use std::path::Path;
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+impl TestEnv {
+    /// Pin the reservation for `path` (and optional `tag`).
+    fn pin(&self, path: &Path, tag: Option<&str>) {
+        let mut cmd = self.command();
+        cmd.arg("pin").arg("--path").arg(path);
+        if let Some(tag) = tag {
+            cmd.arg("--tag").arg(tag);
+        }
+        cmd.assert().success();
+    }
+
+    /// Unpin the reservation for `path` (and optional `tag`).
+    fn unpin(&self, path: &Path, tag: Option<&str>) {
+        let mut cmd = self.command();
+        cmd.arg("unpin").arg("--path").arg(path);
+        if let Some(tag) = tag {
+            cmd.arg("--tag").arg(tag);
+        }
+        cmd.assert().success();
+    }
+
+    /// Return the port reserved for `path` and `tag`, if any.
+    fn reserved_port(&self, path: &Path, tag: Option<&str>) -> Option<u64> {
+        let output = self
+            .command()
+            .arg("list")
+            .arg("--format")
+            .arg("json")
+            .output()
+            .expect("Failed to run list");
+        assert!(output.status.success());
+
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        let path = path.display().to_string();
+        entries
+            .iter()
+            .find(|e| e["path"] == path.as_str() && e["tag"].as_str() == tag)
+            .and_then(|e| e["port"].as_u64())
+    }
+}
+
+/// Write a reserve-group config with two offset services.
+fn write_group_config(dir: &Path) -> std::path::PathBuf {
+    let config_path = dir.join("trop.yaml");
+    fs::write(
+        &config_path,
+        r"
+ports:
+  min: 5000
+  max: 7000
+
+reservations:
+  services:
+    web:
+      offset: 0
+    api:
+      offset: 1
+",
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+// ============================================================================
+// Pin / Unpin Basics
+// ============================================================================
+
+/// Test that pin and unpin toggle the pinned flag shown by `list`.
+#[test]
+fn test_pin_and_unpin_toggle_flag() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    env.reserve_simple(&path);
+
+    env.pin(&path, None);
+    let output = env
+        .command()
+        .args(["list", "--format", "json"])
+        .output()
+        .unwrap();
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entries[0]["pinned"], true);
+
+    env.unpin(&path, None);
+    let output = env
+        .command()
+        .args(["list", "--format", "json"])
+        .output()
+        .unwrap();
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entries[0]["pinned"], false);
+}
+
+/// Test that pinning a missing reservation fails with exit code 1.
+#[test]
+fn test_pin_missing_reservation_fails() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+
+    env.command()
+        .arg("pin")
+        .arg("--path")
+        .arg(&path)
+        .assert()
+        .failure()
+        .code(1);
+}
+
+/// Test that pinning an already pinned reservation is a no-op.
+#[test]
+fn test_pin_is_idempotent() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    env.reserve_simple(&path);
+
+    env.pin(&path, None);
+    let output = env
+        .command()
+        .arg("pin")
+        .arg("--path")
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("already pinned"), "stderr: {stderr}");
+}
+
+// ============================================================================
+// Release Paths
+// ============================================================================
+
+/// Test that a pinned reservation survives `release --force` until unpinned.
+#[test]
+fn test_release_force_keeps_pinned_reservation() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    let port = env.reserve_simple(&path);
+    env.pin(&path, None);
+
+    let output = env
+        .command()
+        .arg("release")
+        .arg("--path")
+        .arg(&path)
+        .arg("--force")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("pinned"), "stderr: {stderr}");
+    assert_eq!(env.reserved_port(&path, None), Some(u64::from(port)));
+
+    env.unpin(&path, None);
+    env.release(&path);
+    assert_eq!(env.reserved_port(&path, None), None);
+}
+
+/// Test that recursive release skips pinned reservations.
+#[test]
+fn test_recursive_release_skips_pinned_reservations() {
+    let env = TestEnv::new();
+    let parent = env.create_dir("parent");
+    let pinned_child = env.create_dir("parent/pinned");
+    let other_child = env.create_dir("parent/other");
+
+    let pinned_port = env.reserve_simple(&pinned_child);
+    env.reserve_simple(&other_child);
+    env.pin(&pinned_child, None);
+
+    let output = env
+        .command()
+        .arg("release")
+        .arg("--path")
+        .arg(&parent)
+        .arg("--recursive")
+        .arg("--force")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Skipped 1 pinned reservation(s)"),
+        "stderr: {stderr}"
+    );
+    assert_eq!(
+        env.reserved_port(&pinned_child, None),
+        Some(u64::from(pinned_port))
+    );
+    assert_eq!(env.reserved_port(&other_child, None), None);
+}
+
+/// Test that `release --by-port` refuses a pinned holder, even with --force.
+#[test]
+fn test_release_by_port_refuses_pinned_holder() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    let port = env.reserve_simple(&path);
+    env.pin(&path, None);
+
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg(port.to_string())
+        .arg("--force")
+        .assert()
+        .failure()
+        .code(1);
+    assert_eq!(env.reserved_port(&path, None), Some(u64::from(port)));
+
+    env.unpin(&path, None);
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg(port.to_string())
+        .assert()
+        .success();
+    assert_eq!(env.reserved_port(&path, None), None);
+}
+
+// ============================================================================
+// Group Allocation
+// ============================================================================
+
+/// Test that `reserve-group --force` won't reallocate a pinned service.
+#[test]
+fn test_reserve_group_force_keeps_pinned_service() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let config_path = write_group_config(&dir);
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success();
+    let web_port = env.reserved_port(&dir, Some("web")).expect("web reserved");
+    env.pin(&dir, Some("web"));
+
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--force")
+        .assert()
+        .failure()
+        .code(1);
+    assert_eq!(env.reserved_port(&dir, Some("web")), Some(web_port));
+
+    env.unpin(&dir, Some("web"));
+    env.command()
+        .arg("reserve-group")
+        .arg(&config_path)
+        .arg("--force")
+        .assert()
+        .success();
+}
+
+// ============================================================================
+// Cleanup and Migration
+// ============================================================================
+
+/// Test that prune leaves pinned reservations for deleted directories.
+#[test]
+fn test_prune_keeps_pinned_reservation() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    let port = env.reserve_simple(&path);
+    env.pin(&path, None);
+
+    fs::remove_dir_all(&path).expect("Failed to remove directory");
+
+    env.command().arg("prune").assert().success();
+    assert_eq!(env.reserved_port(&path, None), Some(u64::from(port)));
+
+    env.unpin(&path, None);
+    env.command().arg("prune").assert().success();
+    assert_eq!(env.reserved_port(&path, None), None);
+}
+
+/// Test that `migrate --force` won't overwrite a pinned destination.
+#[test]
+fn test_migrate_force_keeps_pinned_destination() {
+    let env = TestEnv::new();
+    let from = env.create_dir("from");
+    let to = env.create_dir("to");
+    env.reserve_simple(&from);
+    let to_port = env.reserve_simple(&to);
+    env.pin(&to, None);
+
+    env.command()
+        .arg("migrate")
+        .arg("--from")
+        .arg(&from)
+        .arg("--to")
+        .arg(&to)
+        .arg("--force")
+        .assert()
+        .failure()
+        .code(1);
+    assert_eq!(env.reserved_port(&to, None), Some(u64::from(to_port)));
+}
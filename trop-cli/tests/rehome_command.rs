@@ -0,0 +1,207 @@
+//! Integration tests for the `rehome` command.
+//!
+//! These tests reserve ports under the default range, then narrow the range
+//! with `TROP_PORT_MIN`/`TROP_PORT_MAX` so the reservations are stranded, and
+//! verify that:
+//! - `--dry-run` reports what would move without changing anything
+//! - Stranded reservations are moved into range with their metadata intact
+//! - Pinned reservations are only moved with `--force`
+
+mod common;
+
+use assert_cmd::Command;
+use common::TestEnv;
+use std::path::Path;
+
+/// Narrowed range used by these tests; the default range starts at 5000.
+const NARROW_MIN: u16 = 6500;
+const NARROW_MAX: u16 = 6600;
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+impl TestEnv {
+    /// A command running with the narrowed port range.
+    fn narrowed(&self) -> Command {
+        let mut cmd = self.command();
+        cmd.env("TROP_PORT_MIN", NARROW_MIN.to_string())
+            .env("TROP_PORT_MAX", NARROW_MAX.to_string());
+        cmd
+    }
+
+    /// Return the `list --format json` entry for `path`.
+    fn entry(&self, path: &Path) -> serde_json::Value {
+        let output = self
+            .command()
+            .args(["list", "--format", "json"])
+            .output()
+            .expect("Failed to run list");
+        assert!(output.status.success());
+
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+        let path = path.display().to_string();
+        entries
+            .into_iter()
+            .find(|e| e["path"] == path.as_str())
+            .expect("reservation should exist")
+    }
+}
+
+fn in_narrow_range(port: u64) -> bool {
+    (u64::from(NARROW_MIN)..=u64::from(NARROW_MAX)).contains(&port)
+}
+
+// ============================================================================
+// Rehome Tests
+// ============================================================================
+
+/// Test that there is nothing to do when every reservation is in range.
+#[test]
+fn test_rehome_nothing_out_of_range() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    env.reserve_simple(&path);
+
+    let output = env
+        .command()
+        .args(["rehome", "--out-of-range"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("No reservations outside the configured range"),
+        "stderr: {stderr}"
+    );
+}
+
+/// Test that --dry-run lists stranded reservations without moving them.
+#[test]
+fn test_rehome_dry_run() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    let port = env.reserve_simple(&path);
+
+    let output = env
+        .narrowed()
+        .args(["rehome", "--out-of-range", "--dry-run"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Reservations to rehome (1)"),
+        "stderr: {stderr}"
+    );
+    assert!(stderr.contains("Dry run"), "stderr: {stderr}");
+    assert_eq!(env.entry(&path)["port"], u64::from(port));
+}
+
+/// Test that stranded reservations move into range, keeping their metadata.
+///
+/// The project makes the first reservation sticky, so this needs --force.
+#[test]
+fn test_rehome_moves_reservations_into_range() {
+    let env = TestEnv::new();
+    let first = env.create_dir("first");
+    let second = env.create_dir("second");
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&first)
+        .args(["--project", "alpha", "--allow-unrelated-path"])
+        .assert()
+        .success();
+    env.reserve_simple(&second);
+
+    env.narrowed()
+        .args(["rehome", "--out-of-range", "--force"])
+        .assert()
+        .success();
+
+    let first_entry = env.entry(&first);
+    let second_entry = env.entry(&second);
+    let first_port = first_entry["port"].as_u64().unwrap();
+    let second_port = second_entry["port"].as_u64().unwrap();
+    assert!(in_narrow_range(first_port), "port {first_port}");
+    assert!(in_narrow_range(second_port), "port {second_port}");
+    assert_ne!(first_port, second_port);
+    assert_eq!(first_entry["project"], "alpha");
+}
+
+/// Test that pinned reservations are skipped unless --force is given.
+#[test]
+fn test_rehome_pinned_requires_force() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    let port = env.reserve_simple(&path);
+    env.command()
+        .arg("pin")
+        .arg("--path")
+        .arg(&path)
+        .assert()
+        .success();
+
+    let output = env
+        .narrowed()
+        .args(["rehome", "--out-of-range"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("use --force"), "stderr: {stderr}");
+    assert_eq!(env.entry(&path)["port"], u64::from(port));
+
+    env.narrowed()
+        .args(["rehome", "--out-of-range", "--force"])
+        .assert()
+        .success();
+    let entry = env.entry(&path);
+    assert!(in_narrow_range(entry["port"].as_u64().unwrap()));
+    assert_eq!(entry["pinned"], true);
+}
+
+/// Test that reservations with a project are skipped unless --force is given.
+#[test]
+fn test_rehome_sticky_requires_force() {
+    let env = TestEnv::new();
+    let path = env.create_dir("project");
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&path)
+        .args(["--project", "alpha", "--allow-unrelated-path"])
+        .assert()
+        .success();
+    let port = env.entry(&path)["port"].as_u64().unwrap();
+
+    let output = env
+        .narrowed()
+        .args(["rehome", "--out-of-range"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("use --force"), "stderr: {stderr}");
+    assert_eq!(env.entry(&path)["port"], port);
+
+    env.narrowed()
+        .args(["rehome", "--out-of-range", "--force"])
+        .assert()
+        .success();
+    let entry = env.entry(&path);
+    assert!(in_narrow_range(entry["port"].as_u64().unwrap()));
+    assert_eq!(entry["project"], "alpha");
+}
+
+/// Test that a selector is required.
+#[test]
+fn test_rehome_requires_out_of_range() {
+    let env = TestEnv::new();
+
+    env.command().arg("rehome").assert().failure();
+}
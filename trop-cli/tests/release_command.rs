@@ -7,6 +7,7 @@
 //! - Force flag behavior
 //! - Error cases (nothing to release, etc.)
 //! - Release with various flag combinations
+//! - Bulk release by project or task
 
 mod common;
 
@@ -589,6 +590,47 @@ fn test_release_quiet_mode() {
     assert!(stderr.trim().is_empty() || stderr.is_empty());
 }
 
+/// Test that `--format json` prints the released key to stdout.
+#[test]
+fn test_release_json_output() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    env.reserve_with_tag(&test_path, "web");
+
+    let output = env
+        .command()
+        .arg("release")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("web")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["path"], test_path.to_str().unwrap());
+    assert_eq!(json["tag"], "web");
+    assert_eq!(json["released"], true);
+
+    // Releasing again reports that nothing was released
+    let output = env
+        .command()
+        .arg("release")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("web")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["released"], false);
+}
+
 /// Test --verbose provides additional information.
 #[test]
 fn test_release_verbose_mode() {
@@ -714,3 +756,220 @@ fn test_release_idempotency_with_force() {
         assert!(!stderr.is_empty());
     }
 }
+
+// ============================================================================
+// Release By Port Tests
+// ============================================================================
+
+/// Test releasing a reservation by the port it holds.
+///
+/// `--by-port` should find the holder regardless of the current directory
+/// and release it, leaving other reservations intact.
+#[test]
+fn test_release_by_port() {
+    let env = TestEnv::new();
+    let path_a = env.create_dir("project-a");
+    let path_b = env.create_dir("project-b");
+
+    let port_a = env.reserve_simple(&path_a);
+    let port_b = env.reserve_simple(&path_b);
+
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg(port_a.to_string())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Released port {port_a}")));
+
+    let list = env.list();
+    assert!(!list.contains(&port_a.to_string()));
+    assert!(list.contains(&port_b.to_string()));
+}
+
+/// Test that `--by-port` exits 1 when nothing holds the port.
+#[test]
+fn test_release_by_port_not_reserved() {
+    let env = TestEnv::new();
+
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg("5999")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("not reserved"));
+}
+
+/// Test that sticky holders (with project/task) require `--force`.
+#[test]
+fn test_release_by_port_sticky_requires_force() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--project")
+        .arg("my-project")
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let port: u16 = String::from_utf8(output.stdout)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg(port.to_string())
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("--force"));
+    assert!(env.list().contains(&port.to_string()));
+
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg(port.to_string())
+        .arg("--force")
+        .assert()
+        .success();
+    assert!(!env.list().contains(&port.to_string()));
+}
+
+/// Test that `--by-port` dry run leaves the reservation in place.
+#[test]
+fn test_release_by_port_dry_run() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let port = env.reserve_simple(&test_path);
+
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg(port.to_string())
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Dry run"));
+
+    assert!(env.list().contains(&port.to_string()));
+}
+
+/// Test that `--by-port` cannot be combined with path filters.
+#[test]
+fn test_release_by_port_conflicts_with_path() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("release")
+        .arg("--by-port")
+        .arg("5000")
+        .arg("--path")
+        .arg(&test_path)
+        .assert()
+        .failure();
+}
+
+// ============================================================================
+// Bulk Release Tests
+// ============================================================================
+
+/// Reserve a port for `path` under `project`, returning the port.
+fn reserve_in_project(env: &TestEnv, path: &std::path::Path, project: &str) -> u16 {
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(path)
+        .arg("--project")
+        .arg(project)
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap()
+}
+
+/// Test releasing every reservation in a project at once.
+#[test]
+fn test_release_project_releases_all() {
+    let env = TestEnv::new();
+    let port_a = reserve_in_project(&env, &env.create_dir("a"), "shop");
+    let port_b = reserve_in_project(&env, &env.create_dir("b"), "shop");
+    let port_c = reserve_in_project(&env, &env.create_dir("c"), "blog");
+
+    env.command()
+        .arg("release")
+        .arg("--project")
+        .arg("shop")
+        .arg("--force")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "would release 2 reservation(s) for project shop",
+        ));
+    assert!(env.list().contains(&port_a.to_string()));
+
+    env.command()
+        .arg("release")
+        .arg("--all-for-project")
+        .arg("shop")
+        .arg("--force")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Released 2 reservation(s) for project shop",
+        ));
+
+    let list = env.list();
+    assert!(!list.contains(&port_a.to_string()));
+    assert!(!list.contains(&port_b.to_string()));
+    assert!(list.contains(&port_c.to_string()));
+}
+
+/// Test that bulk release refuses sticky reservations without `--force`.
+#[test]
+fn test_release_project_requires_force() {
+    let env = TestEnv::new();
+    let port = reserve_in_project(&env, &env.create_dir("a"), "shop");
+
+    env.command()
+        .arg("release")
+        .arg("--project")
+        .arg("shop")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("--force"));
+
+    assert!(env.list().contains(&port.to_string()));
+}
+
+/// Test that a bulk release matching nothing succeeds without changes.
+#[test]
+fn test_release_project_no_match() {
+    let env = TestEnv::new();
+    let port = reserve_in_project(&env, &env.create_dir("a"), "shop");
+
+    env.command()
+        .arg("release")
+        .arg("--task")
+        .arg("missing")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Released 0 reservation(s)"));
+
+    assert!(env.list().contains(&port.to_string()));
+}
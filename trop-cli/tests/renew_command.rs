@@ -0,0 +1,255 @@
+//! Integration tests for the `renew` command.
+//!
+//! These tests verify that `trop renew` refreshes a reservation's
+//! last-used timestamp without touching anything else:
+//! - The timestamp moves forward and the port stays the same
+//! - Dry runs leave the database unchanged
+//! - Renewing a missing reservation fails with exit code 1
+//! - `--project` renews every reservation in a project at once
+
+mod common;
+
+use common::TestEnv;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+impl TestEnv {
+    /// Open a direct connection to the test database.
+    fn connection(&self) -> rusqlite::Connection {
+        rusqlite::Connection::open(self.data_dir.join("trop.db")).expect("Failed to open database")
+    }
+
+    /// Set the last_used_at timestamp of the reservation on `port` to
+    /// `days` days ago and return the stored value.
+    fn age_reservation(&self, port: u16, days: u64) -> i64 {
+        let old = SystemTime::now() - Duration::from_secs(days * 86400);
+        let timestamp = old
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        self.connection()
+            .execute(
+                "UPDATE reservations SET last_used_at = ?1 WHERE port = ?2",
+                rusqlite::params![timestamp, i64::from(port)],
+            )
+            .expect("Failed to update timestamp");
+        timestamp
+    }
+
+    /// Read the (port, last_used_at) of the untagged reservation for `path`.
+    fn stored_reservation(&self, path: &Path) -> (u16, i64) {
+        self.connection()
+            .query_row(
+                "SELECT port, last_used_at FROM reservations WHERE path = ?1 AND tag IS NULL",
+                rusqlite::params![path.to_string_lossy()],
+                |row| Ok((row.get::<_, i64>(0)? as u16, row.get(1)?)),
+            )
+            .expect("Reservation not found")
+    }
+}
+
+// ============================================================================
+// Renew Tests
+// ============================================================================
+
+/// Test that renew refreshes last_used_at and keeps the port.
+#[test]
+fn test_renew_updates_last_used() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = env.reserve_simple(&dir);
+    let old = env.age_reservation(port, 30);
+
+    env.command()
+        .args(["renew", "--path"])
+        .arg(&dir)
+        .assert()
+        .success();
+
+    let (stored_port, last_used) = env.stored_reservation(&dir);
+    assert_eq!(stored_port, port);
+    assert!(last_used > old, "last_used_at was not refreshed");
+}
+
+/// Test that a renewed reservation survives expire.
+#[test]
+fn test_renew_prevents_expiry() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = env.reserve_simple(&dir);
+    env.age_reservation(port, 30);
+
+    env.command()
+        .args(["renew", "--path"])
+        .arg(&dir)
+        .assert()
+        .success();
+    env.command()
+        .args(["expire", "--days", "7"])
+        .assert()
+        .success();
+
+    assert!(env.list().contains(&port.to_string()));
+}
+
+/// Test that dry-run leaves the timestamp unchanged.
+#[test]
+fn test_renew_dry_run() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let port = env.reserve_simple(&dir);
+    let old = env.age_reservation(port, 30);
+
+    env.command()
+        .args(["renew", "--dry-run", "--path"])
+        .arg(&dir)
+        .assert()
+        .success();
+
+    assert_eq!(env.stored_reservation(&dir), (port, old));
+}
+
+/// Test that renewing a tagged reservation requires the matching tag.
+#[test]
+fn test_renew_tagged_reservation() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    env.reserve_with_tag(&dir, "web");
+
+    env.command()
+        .args(["renew", "--tag", "web", "--path"])
+        .arg(&dir)
+        .assert()
+        .success();
+    env.command()
+        .args(["renew", "--path"])
+        .arg(&dir)
+        .assert()
+        .failure()
+        .code(1);
+}
+
+/// Test that renewing a missing reservation fails with exit code 1.
+#[test]
+fn test_renew_missing_reservation() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+
+    env.command()
+        .args(["renew", "--path"])
+        .arg(&dir)
+        .assert()
+        .failure()
+        .code(1);
+}
+
+// ============================================================================
+// Project Renew Tests
+// ============================================================================
+
+impl TestEnv {
+    /// Reserve an untagged port for `path` in `project` and `task`.
+    fn reserve_in_project(&self, path: &Path, project: &str, task: &str) -> u16 {
+        let output = self
+            .command()
+            .args(["reserve", "--allow-unrelated-path", "--project", project])
+            .args(["--task", task, "--path"])
+            .arg(path)
+            .output()
+            .expect("Failed to run reserve");
+        assert!(output.status.success());
+        common::parse_port(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Test that --project renews every reservation in the project and no others.
+#[test]
+fn test_renew_project_touches_all_matches() {
+    let env = TestEnv::new();
+    let web = env.create_dir("shop-web");
+    let api = env.create_dir("shop-api");
+    let blog = env.create_dir("blog");
+    let old_web = env.age_reservation(env.reserve_in_project(&web, "shop", "checkout"), 30);
+    let old_api = env.age_reservation(env.reserve_in_project(&api, "shop", "search"), 30);
+    let old_blog = env.age_reservation(env.reserve_in_project(&blog, "blog", "posts"), 30);
+
+    env.command()
+        .args(["renew", "--project", "shop"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Renewed 2 reservation(s)"));
+
+    assert!(env.stored_reservation(&web).1 > old_web);
+    assert!(env.stored_reservation(&api).1 > old_api);
+    assert_eq!(env.stored_reservation(&blog).1, old_blog);
+}
+
+/// Test that --task narrows a project renew.
+#[test]
+fn test_renew_project_and_task() {
+    let env = TestEnv::new();
+    let web = env.create_dir("shop-web");
+    let api = env.create_dir("shop-api");
+    let old_web = env.age_reservation(env.reserve_in_project(&web, "shop", "checkout"), 30);
+    let old_api = env.age_reservation(env.reserve_in_project(&api, "shop", "search"), 30);
+
+    env.command()
+        .args(["renew", "--project", "shop", "--task", "search"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Renewed 1 reservation(s)"));
+
+    assert_eq!(env.stored_reservation(&web).1, old_web);
+    assert!(env.stored_reservation(&api).1 > old_api);
+}
+
+/// Test that a project with no reservations is a successful no-op.
+#[test]
+fn test_renew_project_no_matches() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let old = env.age_reservation(env.reserve_in_project(&dir, "shop", "checkout"), 30);
+
+    env.command()
+        .args(["renew", "--project", "missing"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Renewed 0 reservation(s)"));
+
+    assert_eq!(env.stored_reservation(&dir).1, old);
+}
+
+/// Test that a project dry run reports the count without touching anything.
+#[test]
+fn test_renew_project_dry_run() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    let old = env.age_reservation(env.reserve_in_project(&dir, "shop", "checkout"), 30);
+
+    env.command()
+        .args(["renew", "--project", "shop", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("would renew 1 reservation(s)"));
+
+    assert_eq!(env.stored_reservation(&dir).1, old);
+}
+
+/// Test that --task requires --project and --project conflicts with --tag.
+#[test]
+fn test_renew_project_argument_validation() {
+    let env = TestEnv::new();
+
+    env.command()
+        .args(["renew", "--task", "search"])
+        .assert()
+        .failure();
+    env.command()
+        .args(["renew", "--project", "shop", "--tag", "web"])
+        .assert()
+        .failure();
+}
@@ -4,11 +4,13 @@
 //! - Basic reservation (with and without explicit path)
 //! - Reservation with tags
 //! - Reservation with metadata (project, task)
-//! - Preferred port handling
+//! - Preferred port handling and why an unavailable port was rejected
 //! - Port range constraints
+//! - Skipping ports occupied by other processes
 //! - Idempotency (repeated reservations)
 //! - Flag combinations (force, overwrite, allow-change, etc.)
 //! - Dry-run mode
+//! - Batch reservations from paths on stdin
 //! - Error cases
 
 mod common;
@@ -201,6 +203,108 @@ fn test_reserve_with_same_tag_is_idempotent() {
     );
 }
 
+/// Test that re-reserving keeps the existing port even when another is preferred.
+///
+/// Plain re-reserve is deterministic: the existing reservation wins over
+/// allocation hints, so the same port comes back every time.
+#[test]
+fn test_reserve_again_returns_existing_port() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let port = env.reserve_simple(&test_path);
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--preferred")
+        .arg((port + 10).to_string())
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(parse_port(&String::from_utf8_lossy(&output.stdout)), port);
+}
+
+/// Test that --replace updates project, task, and note but keeps the port.
+#[test]
+fn test_reserve_replace_updates_metadata_keeps_port() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let reserve = |extra: &[&str]| {
+        let output = env
+            .command()
+            .arg("reserve")
+            .arg("--path")
+            .arg(&test_path)
+            .arg("--allow-unrelated-path")
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        parse_port(&String::from_utf8_lossy(&output.stdout))
+    };
+
+    let port = reserve(&["--project", "old-project", "--task", "old-task"]);
+
+    // Without --replace the sticky project is protected
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .args(["--project", "new-project"])
+        .assert()
+        .failure();
+
+    let replaced = reserve(&[
+        "--replace",
+        "--project",
+        "new-project",
+        "--task",
+        "new-task",
+        "--note",
+        "moved",
+    ]);
+    assert_eq!(replaced, port);
+
+    let output = env
+        .command()
+        .args(["list", "--format", "json"])
+        .output()
+        .unwrap();
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let reservation = &list.as_array().unwrap()[0];
+    assert_eq!(reservation["port"], port);
+    assert_eq!(reservation["project"], "new-project");
+    assert_eq!(reservation["task"], "new-task");
+    assert_eq!(reservation["note"], "moved");
+
+    // Replacing with only a task clears the project, and vice versa
+    let listed = |field: &str| {
+        let output = env
+            .command()
+            .args(["list", "--format", "json"])
+            .output()
+            .unwrap();
+        let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        list[0][field].clone()
+    };
+    assert_eq!(reserve(&["--replace", "--task", "only-task"]), port);
+    assert!(listed("project").is_null());
+    assert_eq!(listed("task"), "only-task");
+
+    assert_eq!(reserve(&["--replace", "--project", "only-project"]), port);
+    assert_eq!(listed("project"), "only-project");
+    assert!(listed("task").is_null());
+}
+
 // ============================================================================
 // Preferred Port Tests
 // ============================================================================
@@ -397,6 +501,95 @@ fn test_reserve_with_min_only_uses_existing_max() {
     );
 }
 
+/// Test --max-offset sets the range end relative to --min.
+///
+/// With the highest-first strategy the allocated port is the range end,
+/// so `--min 6000 --max-offset 100` must yield 6100.
+#[test]
+fn test_reserve_with_max_offset() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--min", "6000", "--max-offset", "100"])
+        .args(["--strategy", "highest", "--skip-occupancy-check"])
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(parse_port(&String::from_utf8(output.stdout).unwrap()), 6100);
+}
+
+/// Test --max-offset replaces a maximum from `TROP_PORT_MAX`.
+///
+/// Only --max and --max-offset given together on the command line conflict;
+/// an environment maximum is simply overridden.
+#[test]
+fn test_reserve_max_offset_overrides_env_max() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .env("TROP_PORT_MAX", "7000")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--min", "6000", "--max-offset", "100"])
+        .args(["--strategy", "highest", "--skip-occupancy-check"])
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(parse_port(&String::from_utf8(output.stdout).unwrap()), 6100);
+}
+
+/// Test that --max and --max-offset together are rejected.
+#[test]
+fn test_reserve_max_and_max_offset_conflict() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--min", "6000", "--max", "6200", "--max-offset", "100"])
+        .arg("--allow-unrelated-path")
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains("both max and max_offset"));
+
+    env.command()
+        .args([
+            "scan",
+            "--min",
+            "6000",
+            "--max",
+            "6200",
+            "--max-offset",
+            "100",
+        ])
+        .assert()
+        .failure()
+        .code(4);
+}
+
 // ============================================================================
 // Force and Override Tests
 // ============================================================================
@@ -597,6 +790,62 @@ fn test_reserve_dry_run_shows_plan() {
         .stderr(predicate::str::contains("Dry run"));
 }
 
+/// Test that dry-run shows the resolved values a new reservation would get.
+#[test]
+fn test_reserve_dry_run_shows_resolved_fields() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--allow-unrelated-path", "--skip-occupancy-check"])
+        .args(["--min", "6200", "--max", "6210"])
+        .args(["--tag", "web", "--project", "app", "--task", "build"])
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("#web"))
+        .stderr(predicate::str::contains("+   port: 6200"))
+        .stderr(predicate::str::contains("+   project: app"))
+        .stderr(predicate::str::contains("+   task: build"))
+        .stderr(predicate::str::contains("+   sticky: false"));
+
+    assert!(!env.data_dir.exists(), "Dry-run should not create database");
+}
+
+/// Test that dry-run shows old and new values for an update without applying it.
+#[test]
+fn test_reserve_dry_run_shows_update_diff() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--allow-unrelated-path", "--note", "first"])
+        .assert()
+        .success();
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--allow-unrelated-path", "--note", "second"])
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("~   note: first -> second"))
+        .stderr(predicate::str::contains("port:").not());
+
+    // The stored note is unchanged
+    assert!(env.list().contains("first"));
+    assert!(!env.list().contains("second"));
+}
+
 /// Test that dry-run with --quiet suppresses output.
 ///
 /// Even in dry-run mode, --quiet should suppress the plan output.
@@ -931,19 +1180,12 @@ fn test_reserve_quiet_suppresses_warnings() {
     // Note: errors still go to stderr, but warnings should be suppressed
 }
 
-// ============================================================================
-// Flag Combination Tests
-// ============================================================================
-
-/// Test multiple flags work together correctly.
-///
-/// This test combines several flags to verify they don't interfere with each other.
+/// Test that `--json` emits the reservation as a JSON object.
 #[test]
-fn test_reserve_with_multiple_flags() {
+fn test_reserve_json_output() {
     let env = TestEnv::new();
     let test_path = env.create_dir("test-project");
 
-    // Combine tag, project, task, and preferred port
     let output = env
         .command()
         .arg("reserve")
@@ -953,48 +1195,1487 @@ fn test_reserve_with_multiple_flags() {
         .arg("web")
         .arg("--project")
         .arg("my-project")
-        .arg("--task")
-        .arg("feature-1")
-        .arg("--port")
-        .arg("8080")
         .arg("--allow-unrelated-path")
+        .arg("--json")
         .output()
         .unwrap();
 
     assert!(output.status.success());
-    let port = parse_port(&String::from_utf8(output.stdout).unwrap());
-    // Port might be fallback if preferred was occupied
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["port"].as_u64().is_some());
+    assert_eq!(json["path"], test_path.to_str().unwrap());
+    assert_eq!(json["tag"], "web");
+    assert_eq!(json["project"], "my-project");
+    assert!(json.get("occupancy").is_none());
+}
+
+/// Test that `--format json` emits the reservation, while the default stays a bare port.
+#[test]
+fn test_reserve_format_json_output() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("web")
+        .arg("--allow-unrelated-path")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let port = json["port"].as_u64().expect("port should be a number");
+    assert_eq!(json["path"], test_path.to_str().unwrap());
+    assert_eq!(json["tag"], "web");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("web")
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), port.to_string());
+}
+
+/// Test that `--include-occupancy` reports a foreign listener on the reserved port.
+#[test]
+fn test_reserve_json_include_occupancy_occupied() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--port")
+        .arg(port.to_string())
+        .arg("--min")
+        .arg("1")
+        .arg("--max")
+        .arg("65535")
+        .arg("--ignore-occupied")
+        .arg("--allow-unrelated-path")
+        .arg("--json")
+        .arg("--include-occupancy")
+        .output()
+        .unwrap();
+
     assert!(
-        (5000..=7000).contains(&port),
-        "Should allocate a port in valid range"
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
     );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["port"], u64::from(port));
+    assert_eq!(json["occupancy"]["status"], "occupied");
+}
 
-    // Verify all metadata in list
-    let list_output = env.list();
-    assert!(list_output.contains("web"));
-    assert!(list_output.contains("my-project"));
-    assert!(list_output.contains("feature-1"));
-    assert!(list_output.contains(&port.to_string()));
+/// Test that `--include-occupancy` reports "skipped" when checks are disabled.
+#[test]
+fn test_reserve_json_include_occupancy_skipped() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--skip-occupancy-check")
+        .arg("--json")
+        .arg("--include-occupancy")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["occupancy"]["status"], "skipped");
 }
 
-/// Test that conflicting flags are handled correctly.
-///
-/// Some flag combinations don't make sense (e.g., --quiet and --verbose).
-/// The CLI should handle these gracefully (typically, last one wins or
-/// one takes precedence).
+/// Test that `--include-occupancy` requires `--json`.
 #[test]
-fn test_reserve_with_conflicting_flags() {
+fn test_reserve_include_occupancy_requires_json() {
     let env = TestEnv::new();
     let test_path = env.create_dir("test-project");
 
-    // --quiet and --verbose together - should not crash
     env.command()
-        .arg("--quiet")
-        .arg("--verbose")
         .arg("reserve")
         .arg("--path")
         .arg(&test_path)
         .arg("--allow-unrelated-path")
+        .arg("--include-occupancy")
         .assert()
-        .success();
+        .failure();
+}
+
+// ============================================================================
+// Default Tag Tests
+// ============================================================================
+
+/// Reserve with the given extra arguments and return the JSON output.
+fn reserve_json_with_args(
+    env: &TestEnv,
+    path: &std::path::Path,
+    args: &[&str],
+) -> serde_json::Value {
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(path)
+        .arg("--allow-unrelated-path")
+        .arg("--json")
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// Write a user config that sets `default_tag: web`.
+fn write_default_tag_config(env: &TestEnv) {
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+    std::fs::write(env.data_dir.join("config.yaml"), "default_tag: web\n").unwrap();
+}
+
+/// Test that the configured `default_tag` applies when `--tag` is omitted.
+#[test]
+fn test_reserve_uses_configured_default_tag() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    write_default_tag_config(&env);
+
+    let json = reserve_json_with_args(&env, &test_path, &[]);
+    assert_eq!(json["tag"], "web");
+}
+
+/// Test that an explicit `--tag` overrides the configured default.
+#[test]
+fn test_reserve_explicit_tag_overrides_default_tag() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    write_default_tag_config(&env);
+
+    let json = reserve_json_with_args(&env, &test_path, &["--tag", "api"]);
+    assert_eq!(json["tag"], "api");
+}
+
+/// Test that `--tag ""` and `--no-tag` force an untagged reservation.
+#[test]
+fn test_reserve_untagged_despite_default_tag() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    write_default_tag_config(&env);
+
+    let json = reserve_json_with_args(&env, &test_path, &["--tag", ""]);
+    assert!(json["tag"].is_null());
+
+    let json = reserve_json_with_args(&env, &test_path, &["--no-tag"]);
+    assert!(json["tag"].is_null());
+}
+
+/// Test that `TROP_DEFAULT_TAG` supplies a default tag.
+#[test]
+fn test_reserve_default_tag_from_env() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .env("TROP_DEFAULT_TAG", "worker")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["tag"], "worker");
+}
+
+// ============================================================================
+// Tag Case Tests
+// ============================================================================
+
+/// Test that tags are case-sensitive by default.
+#[test]
+fn test_reserve_tag_case_sensitive_by_default() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let upper = reserve_json_with_args(&env, &test_path, &["--tag", "Web"]);
+    let lower = reserve_json_with_args(&env, &test_path, &["--tag", "web"]);
+
+    assert_eq!(upper["tag"], "Web");
+    assert_eq!(lower["tag"], "web");
+    assert_ne!(upper["port"], lower["port"]);
+}
+
+/// Test that `tag_case: insensitive` makes differently-cased tags collide.
+#[test]
+fn test_reserve_tag_case_insensitive() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+    std::fs::write(env.data_dir.join("config.yaml"), "tag_case: insensitive\n").unwrap();
+
+    let upper = reserve_json_with_args(&env, &test_path, &["--tag", "Web"]);
+    let lower = reserve_json_with_args(&env, &test_path, &["--tag", "web"]);
+
+    assert_eq!(upper["tag"], "web");
+    assert_eq!(upper["port"], lower["port"]);
+
+    // Release also matches regardless of case
+    env.command()
+        .arg("release")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("WEB")
+        .assert()
+        .success();
+
+    let output = env.list();
+    assert!(!output.contains("web"), "{output}");
+}
+
+// ============================================================================
+// Allocation Strategy Tests
+// ============================================================================
+
+/// Test that `--strategy highest` takes the top of the range.
+#[test]
+fn test_reserve_strategy_highest() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let reservation = reserve_json_with_args(
+        &env,
+        &test_path,
+        &[
+            "--strategy",
+            "highest",
+            "--min",
+            "6100",
+            "--max",
+            "6110",
+            "--skip-occupancy-check",
+        ],
+    );
+    assert_eq!(reservation["port"], 6110);
+}
+
+/// Test that `--strategy random` stays within the requested range.
+#[test]
+fn test_reserve_strategy_random_within_range() {
+    let env = TestEnv::new();
+
+    for i in 0..5 {
+        let test_path = env.create_dir(&format!("project-{i}"));
+        let reservation = reserve_json_with_args(
+            &env,
+            &test_path,
+            &[
+                "--strategy",
+                "random",
+                "--min",
+                "6200",
+                "--max",
+                "6205",
+                "--skip-occupancy-check",
+            ],
+        );
+        let port = reservation["port"].as_u64().unwrap();
+        assert!((6200..=6205).contains(&port), "port {port} out of range");
+    }
+}
+
+/// Test that `allocation_strategy` in the config file is honored.
+#[test]
+fn test_reserve_strategy_from_config() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+    std::fs::write(
+        env.data_dir.join("config.yaml"),
+        "allocation_strategy: highest\nexcluded_ports:\n  - 6310\n",
+    )
+    .unwrap();
+
+    let reservation = reserve_json_with_args(
+        &env,
+        &test_path,
+        &["--min", "6300", "--max", "6310", "--skip-occupancy-check"],
+    );
+    assert_eq!(reservation["port"], 6309);
+}
+
+/// Test that an unknown strategy is rejected.
+#[test]
+fn test_reserve_strategy_invalid() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--strategy", "middle"])
+        .assert()
+        .failure();
+}
+
+// ============================================================================
+// Ad-hoc Exclusion Tests
+// ============================================================================
+
+/// Test that --exclude keeps the lowest free ports from being allocated
+/// without touching the config file.
+#[test]
+fn test_reserve_exclude_skips_lowest_ports() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let config = "excluded_ports:\n  - 6400\n";
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+    std::fs::write(env.data_dir.join("config.yaml"), config).unwrap();
+
+    let reservation = reserve_json_with_args(
+        &env,
+        &test_path,
+        &[
+            "--min",
+            "6400",
+            "--max",
+            "6410",
+            "--skip-occupancy-check",
+            "--exclude",
+            "6401",
+            "--exclude",
+            "6402..6404",
+        ],
+    );
+    assert_eq!(reservation["port"], 6405);
+
+    assert_eq!(
+        std::fs::read_to_string(env.data_dir.join("config.yaml")).unwrap(),
+        config
+    );
+}
+
+/// Test that malformed --exclude values are rejected.
+#[test]
+fn test_reserve_exclude_invalid() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    for bad in ["web", "6404..6402"] {
+        env.command()
+            .arg("reserve")
+            .arg("--path")
+            .arg(&test_path)
+            .args(["--exclude", bad])
+            .assert()
+            .failure()
+            .code(4);
+    }
+}
+
+// ============================================================================
+// Port Block Tests
+// ============================================================================
+
+/// Run `reserve --count` with extra arguments and return the printed ports.
+fn reserve_block(env: &TestEnv, path: &std::path::Path, args: &[&str]) -> Vec<u16> {
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(path)
+        .args(["--allow-unrelated-path", "--skip-occupancy-check"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect()
+}
+
+/// Test that --count reserves consecutive ports with prefixed tags.
+#[test]
+fn test_reserve_count_allocates_block() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("cluster");
+
+    let ports = reserve_block(
+        &env,
+        &test_path,
+        &[
+            "--min",
+            "6500",
+            "--max",
+            "6510",
+            "--count",
+            "3",
+            "--tag-prefix",
+            "node",
+        ],
+    );
+    assert_eq!(ports, vec![6500, 6501, 6502]);
+
+    let list = env.list();
+    for (i, port) in ports.iter().enumerate() {
+        assert!(list.contains(&format!("node-{i}")), "{list}");
+        assert!(list.contains(&port.to_string()), "{list}");
+    }
+
+    // Reserving the same block again returns the same ports
+    let again = reserve_block(
+        &env,
+        &test_path,
+        &[
+            "--min",
+            "6500",
+            "--max",
+            "6510",
+            "--count",
+            "3",
+            "--tag-prefix",
+            "node",
+        ],
+    );
+    assert_eq!(again, ports);
+}
+
+/// Test that exclusions inside a candidate block push it past them.
+#[test]
+fn test_reserve_count_respects_exclusions() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("cluster");
+
+    let ports = reserve_block(
+        &env,
+        &test_path,
+        &[
+            "--min",
+            "6500",
+            "--max",
+            "6510",
+            "--count",
+            "3",
+            "--exclude",
+            "6502",
+        ],
+    );
+    assert_eq!(ports, vec![6503, 6504, 6505]);
+}
+
+/// Test that a fragmented range fails with a block-specific error.
+#[test]
+fn test_reserve_count_fragmented_range_fails() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("cluster");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--allow-unrelated-path", "--skip-occupancy-check"])
+        .args(["--min", "6500", "--max", "6507", "--count", "3"])
+        .args(["--exclude", "6502", "--exclude", "6505"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no block of 3 consecutive available ports",
+        ));
+
+    // Nothing was reserved
+    assert!(!env.list().contains("port-0"));
+}
+
+/// Test that --count must be positive and --tag-prefix needs --count.
+#[test]
+fn test_reserve_count_invalid_arguments() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("cluster");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--count", "0"])
+        .assert()
+        .failure()
+        .code(4);
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .args(["--tag-prefix", "node"])
+        .assert()
+        .failure();
+}
+
+// ============================================================================
+// Export Line Tests
+// ============================================================================
+
+/// Test that --print-export prints the port followed by an export line.
+#[test]
+fn test_reserve_print_export() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--print-export")
+        .arg("--env-name")
+        .arg("API_PORT")
+        .arg("--shell")
+        .arg("bash")
+        .output()
+        .expect("Failed to run reserve");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "stdout: {stdout}");
+    let port: u16 = lines[0].parse().expect("First line should be the port");
+    assert_eq!(lines[1], format!("export API_PORT={port}"));
+}
+
+/// Test that --export-only prints just the export line, suitable for eval.
+#[test]
+fn test_reserve_export_only() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--print-export")
+        .arg("--env-name")
+        .arg("API_PORT")
+        .arg("--shell")
+        .arg("fish")
+        .arg("--export-only")
+        .output()
+        .expect("Failed to run reserve");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.trim().starts_with("set -x API_PORT "),
+        "stdout: {stdout}"
+    );
+    assert_eq!(stdout.lines().count(), 1);
+}
+
+/// Test that the variable name is derived from the tag when --env-name is omitted.
+#[test]
+fn test_reserve_print_export_derives_name_from_tag() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--tag")
+        .arg("web-api")
+        .arg("--print-export")
+        .arg("--shell")
+        .arg("bash")
+        .arg("--export-only")
+        .output()
+        .expect("Failed to run reserve");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("export WEB_API="), "stdout: {stdout}");
+}
+
+/// Test that an invalid --env-name is rejected before anything is reserved.
+#[test]
+fn test_reserve_print_export_invalid_env_name() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--print-export")
+        .arg("--env-name")
+        .arg("1-BAD")
+        .arg("--shell")
+        .arg("bash")
+        .assert()
+        .failure()
+        .code(4);
+
+    assert!(!env.list().contains(&test_path.display().to_string()));
+}
+
+/// Test that --export-only requires --print-export.
+#[test]
+fn test_reserve_export_only_requires_print_export() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--export-only")
+        .assert()
+        .failure();
+}
+
+// ============================================================================
+// Output File Tests
+// ============================================================================
+
+/// Test that --output-file writes the port instead of printing it.
+#[test]
+fn test_reserve_output_file() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let output_file = env.path().join("port.txt");
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--output-file")
+        .arg(&output_file)
+        .output()
+        .expect("Failed to run reserve");
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let written = std::fs::read_to_string(&output_file).unwrap();
+    let port: u16 = written
+        .trim_end()
+        .parse()
+        .expect("File should hold the port");
+    assert_eq!(written, format!("{port}\n"));
+}
+
+/// Test that an existing output file fails the reservation without --force.
+#[test]
+fn test_reserve_output_file_exists_without_force() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+    let output_file = env.path().join("port.txt");
+    std::fs::write(&output_file, "old\n").unwrap();
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--output-file")
+        .arg(&output_file)
+        .assert()
+        .failure()
+        .code(4);
+
+    assert_eq!(std::fs::read_to_string(&output_file).unwrap(), "old\n");
+    assert!(!env.list().contains(&test_path.display().to_string()));
+}
+
+// ============================================================================
+// Note Tests
+// ============================================================================
+
+/// Test that a note round-trips unchanged through reserve, list, and port-info.
+#[test]
+fn test_reserve_note_round_trips() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("noted");
+    let note = "Demo für Kunden — bitte nicht löschen 🙏";
+
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--note")
+        .arg(note)
+        .output()
+        .expect("Failed to run reserve");
+    assert!(output.status.success());
+    let port = parse_port(&String::from_utf8_lossy(&output.stdout));
+
+    let output = env
+        .command()
+        .args(["list", "--format", "json"])
+        .output()
+        .expect("Failed to run list");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["note"], note);
+
+    env.command()
+        .arg("port-info")
+        .arg(port.to_string())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("Note: {note}")));
+}
+
+/// Test that re-reserving with a new note updates it without moving the port.
+#[test]
+fn test_reserve_note_update_keeps_port() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("noted");
+
+    let reserve = |note: &str| {
+        let output = env
+            .command()
+            .arg("reserve")
+            .arg("--path")
+            .arg(&test_path)
+            .arg("--allow-unrelated-path")
+            .arg("--note")
+            .arg(note)
+            .output()
+            .expect("Failed to run reserve");
+        assert!(output.status.success());
+        parse_port(&String::from_utf8_lossy(&output.stdout))
+    };
+
+    let first = reserve("first");
+    let second = reserve("second");
+    assert_eq!(first, second);
+
+    let output = env
+        .command()
+        .args(["list", "--format", "json"])
+        .output()
+        .expect("Failed to run list");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    assert_eq!(json[0]["note"], "second");
+}
+
+/// Test that an overlong note is rejected as invalid arguments.
+#[test]
+fn test_reserve_note_too_long() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("noted");
+
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--note")
+        .arg("x".repeat(1025))
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains("at most 1024 characters"));
+
+    assert!(!env.list().contains(&test_path.display().to_string()));
+}
+
+// ============================================================================
+// Flag Combination Tests
+// ============================================================================
+
+/// Test multiple flags work together correctly.
+///
+/// This test combines several flags to verify they don't interfere with each other.
+#[test]
+fn test_reserve_with_multiple_flags() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    // Combine tag, project, task, and preferred port
+    let output = env
+        .command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--tag")
+        .arg("web")
+        .arg("--project")
+        .arg("my-project")
+        .arg("--task")
+        .arg("feature-1")
+        .arg("--port")
+        .arg("8080")
+        .arg("--allow-unrelated-path")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let port = parse_port(&String::from_utf8(output.stdout).unwrap());
+    // Port might be fallback if preferred was occupied
+    assert!(
+        (5000..=7000).contains(&port),
+        "Should allocate a port in valid range"
+    );
+
+    // Verify all metadata in list
+    let list_output = env.list();
+    assert!(list_output.contains("web"));
+    assert!(list_output.contains("my-project"));
+    assert!(list_output.contains("feature-1"));
+    assert!(list_output.contains(&port.to_string()));
+}
+
+/// Test that conflicting flags are handled correctly.
+///
+/// Some flag combinations don't make sense (e.g., --quiet and --verbose).
+/// The CLI should handle these gracefully (typically, last one wins or
+/// one takes precedence).
+#[test]
+fn test_reserve_with_conflicting_flags() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("test-project");
+
+    // --quiet and --verbose together - should not crash
+    env.command()
+        .arg("--quiet")
+        .arg("--verbose")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .assert()
+        .success();
+}
+
+// ============================================================================
+// Unavailable Port Reason Tests
+// ============================================================================
+
+/// Reserve `port` with a range of just that port and expect a failure,
+/// returning the JSON error from stderr.
+fn reserve_unavailable(env: &TestEnv, name: &str, port: u16, args: &[&str]) -> serde_json::Value {
+    let port = port.to_string();
+    let output = env
+        .command()
+        .args([
+            "--error-format",
+            "json",
+            "reserve",
+            "--allow-unrelated-path",
+        ])
+        .arg("--path")
+        .arg(env.create_dir(name))
+        .args(["--port", &port, "--min", &port, "--max", &port])
+        .args(args)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(6));
+    serde_json::from_slice(&output.stderr).unwrap()
+}
+
+/// Test that a requested port already held by another reservation says so.
+#[test]
+fn test_reserve_unavailable_reason_reserved() {
+    let env = TestEnv::new();
+    let holder = env.create_dir("holder");
+    env.command()
+        .args([
+            "reserve",
+            "--allow-unrelated-path",
+            "--skip-occupancy-check",
+        ])
+        .arg("--path")
+        .arg(&holder)
+        .args(["--port", "6700"])
+        .assert()
+        .success();
+
+    let json = reserve_unavailable(&env, "second", 6700, &["--skip-occupancy-check"]);
+
+    assert_eq!(json["kind"], "PortExhausted");
+    assert_eq!(json["reason"], "reserved");
+    assert!(json["message"]
+        .as_str()
+        .unwrap()
+        .contains("requested port 6700 is reserved"));
+}
+
+/// Test that an excluded requested port says so.
+#[test]
+fn test_reserve_unavailable_reason_excluded() {
+    let env = TestEnv::new();
+
+    let json = reserve_unavailable(
+        &env,
+        "project",
+        6710,
+        &["--exclude", "6710", "--skip-occupancy-check"],
+    );
+
+    assert_eq!(json["reason"], "excluded");
+}
+
+/// Test that an occupied requested port says so, in human output too.
+#[test]
+fn test_reserve_unavailable_reason_occupied() {
+    let env = TestEnv::new();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let json = reserve_unavailable(&env, "project", port, &[]);
+    assert_eq!(json["reason"], "occupied");
+
+    let port = port.to_string();
+    env.command()
+        .args(["reserve", "--allow-unrelated-path"])
+        .arg("--path")
+        .arg(env.create_dir("other"))
+        .args(["--port", &port, "--min", &port, "--max", &port])
+        .assert()
+        .code(6)
+        .stderr(predicate::str::contains(format!(
+            "requested port {port} is occupied"
+        )));
+}
+
+// ============================================================================
+// Occupancy Tests
+// ============================================================================
+
+/// Bind a TCP listener on an ephemeral localhost port.
+fn occupy_port() -> (std::net::TcpListener, u16) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    (listener, port)
+}
+
+/// Reserve in `[min, max]` for a fresh directory, returning the output.
+fn reserve_in_range(
+    env: &TestEnv,
+    name: &str,
+    min: u16,
+    max: u16,
+    args: &[&str],
+) -> std::process::Output {
+    env.command()
+        .args(["reserve", "--allow-unrelated-path", "--skip-udp"])
+        .arg("--path")
+        .arg(env.create_dir(name))
+        .args(["--min", &min.to_string(), "--max", &max.to_string()])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Test that a port held by another process is skipped during allocation.
+#[test]
+fn test_reserve_skips_occupied_port() {
+    let env = TestEnv::new();
+    let (_listener, port) = occupy_port();
+
+    let output = reserve_in_range(&env, "project", port, port + 1, &[]);
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        parse_port(&String::from_utf8(output.stdout).unwrap()),
+        port + 1
+    );
+}
+
+/// Test that a range with every port reserved, excluded, or occupied fails
+/// with a breakdown of why.
+#[test]
+fn test_reserve_range_fully_unavailable() {
+    let env = TestEnv::new();
+    let (_listener, port) = occupy_port();
+    let reserved = (port + 1).to_string();
+
+    let holder = reserve_in_range(
+        &env,
+        "holder",
+        port + 1,
+        port + 1,
+        &["--skip-occupancy-check"],
+    );
+    assert!(holder.status.success());
+
+    let output = reserve_in_range(
+        &env,
+        "project",
+        port,
+        port + 2,
+        &["--exclude", &(port + 2).to_string()],
+    );
+
+    assert_eq!(output.status.code(), Some(6));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("(1 reserved, 1 excluded, 1 occupied)"),
+        "{stderr}"
+    );
+    assert!(env.list().contains(&reserved));
+}
+
+/// Test that `--check-occupancy` re-enables checks a tropfile turned off.
+#[test]
+fn test_reserve_check_occupancy_overrides_config() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(
+        project.join("trop.yaml"),
+        "occupancy_check:\n  skip: true\n",
+    )
+    .unwrap();
+    let (_listener, port) = occupy_port();
+    let range = [
+        "--min".to_string(),
+        port.to_string(),
+        "--max".to_string(),
+        (port + 1).to_string(),
+    ];
+
+    let reserve = |tag: &str, extra: &[&str]| {
+        let output = env
+            .command()
+            .args(["reserve", "--tag", tag])
+            .args(&range)
+            .args(extra)
+            .current_dir(&project)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        parse_port(&String::from_utf8(output.stdout).unwrap())
+    };
+
+    // The configuration skips occupancy checks, so the held port is handed out
+    assert_eq!(reserve("unchecked", &[]), port);
+    env.release_with_tag(&project, "unchecked");
+
+    assert_eq!(reserve("checked", &["--check-occupancy"]), port + 1);
+}
+
+/// Test that `--skip-occupancy` lets a bound port be allocated, on reserve
+/// and likewise on scan.
+#[test]
+fn test_reserve_skip_occupancy_ignores_listener() {
+    let env = TestEnv::new();
+    let (_listener, port) = occupy_port();
+
+    let checked = reserve_in_range(&env, "checked", port, port, &[]);
+    assert!(
+        !checked.status.success(),
+        "the bound port should be skipped"
+    );
+
+    let skipped = reserve_in_range(&env, "skipped", port, port, &["--skip-occupancy"]);
+    assert!(
+        skipped.status.success(),
+        "{}",
+        String::from_utf8_lossy(&skipped.stderr)
+    );
+    assert_eq!(
+        parse_port(&String::from_utf8(skipped.stdout).unwrap()),
+        port
+    );
+
+    let output = env
+        .command()
+        .args(["scan", "--json", "--skip-occupancy", "--min"])
+        .arg(port.to_string())
+        .arg("--max")
+        .arg(port.to_string())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["status"], "reserved", "{json}");
+}
+
+/// Test that `--interface` only counts binds on the listed addresses.
+#[test]
+fn test_reserve_interface_limits_occupancy_check() {
+    let env = TestEnv::new();
+    let (_listener, port) = occupy_port();
+
+    let loopback = reserve_in_range(
+        &env,
+        "loopback",
+        port,
+        port + 1,
+        &["--interface", "127.0.0.1", "--interface", "::1"],
+    );
+    assert!(loopback.status.success());
+    assert_eq!(
+        parse_port(&String::from_utf8(loopback.stdout).unwrap()),
+        port + 1
+    );
+
+    let elsewhere = reserve_in_range(&env, "elsewhere", port, port, &["--interface", "127.0.0.2"]);
+    assert!(
+        elsewhere.status.success(),
+        "{}",
+        String::from_utf8_lossy(&elsewhere.stderr)
+    );
+    assert_eq!(
+        parse_port(&String::from_utf8(elsewhere.stdout).unwrap()),
+        port
+    );
+}
+
+// ============================================================================
+// Preferred Port Tests
+// ============================================================================
+
+/// Test that a free preferred port is reserved without comment.
+#[test]
+fn test_reserve_preferred_port_free() {
+    let env = TestEnv::new();
+
+    let output = reserve_in_range(
+        &env,
+        "project",
+        47000,
+        47010,
+        &["--skip-occupancy-check", "--preferred", "47005"],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        parse_port(&String::from_utf8(output.stdout).unwrap()),
+        47005
+    );
+    assert!(!String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("preferred"));
+}
+
+/// Test that a taken preferred port falls back to the range and says so.
+#[test]
+fn test_reserve_preferred_port_taken_falls_back() {
+    let env = TestEnv::new();
+    let holder = reserve_in_range(
+        &env,
+        "holder",
+        47000,
+        47010,
+        &["--skip-occupancy-check", "--port", "47005"],
+    );
+    assert!(holder.status.success());
+
+    let output = reserve_in_range(
+        &env,
+        "project",
+        47000,
+        47010,
+        &["--skip-occupancy-check", "--preferred", "47005"],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        parse_port(&String::from_utf8(output.stdout).unwrap()),
+        47000
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("preferred 47005 unavailable (reserved), allocated 47000"),
+        "{stderr}"
+    );
+}
+
+/// Test that a taken preferred port with nothing else free still fails.
+#[test]
+fn test_reserve_preferred_port_no_fallback() {
+    let env = TestEnv::new();
+    let holder = reserve_in_range(&env, "holder", 47005, 47005, &["--skip-occupancy-check"]);
+    assert!(holder.status.success());
+
+    let output = reserve_in_range(
+        &env,
+        "project",
+        47005,
+        47005,
+        &["--skip-occupancy-check", "--preferred", "47005"],
+    );
+
+    assert_eq!(output.status.code(), Some(6));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("preferred port 47005 is reserved"),
+        "{stderr}"
+    );
+}
+
+// ============================================================================
+// Lock Wait Tests
+// ============================================================================
+
+/// Hold the database write lock from another connection until a message is
+/// sent on the returned sender (or it is dropped).
+fn hold_write_lock(env: &TestEnv) -> (std::sync::mpsc::Sender<()>, std::thread::JoinHandle<()>) {
+    let db_path = env.data_dir.join("trop.db");
+    let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+    let holder = std::thread::spawn(move || {
+        let conn = rusqlite::Connection::open(db_path).unwrap();
+        conn.execute_batch("BEGIN IMMEDIATE").unwrap();
+        locked_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+        conn.execute_batch("COMMIT").unwrap();
+    });
+    locked_rx.recv().unwrap();
+
+    (release_tx, holder)
+}
+
+/// Test reserve --wait succeeds once a held lock is released.
+#[test]
+fn test_reserve_wait_succeeds_after_lock_released() {
+    let env = TestEnv::new();
+    env.reserve_simple(&env.create_dir("existing"));
+    let test_path = env.create_dir("waiting");
+
+    let (release, holder) = hold_write_lock(&env);
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        release.send(()).unwrap();
+    });
+
+    let output = env
+        .command()
+        .arg("--busy-timeout")
+        .arg("0")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--wait")
+        .arg("10")
+        .output()
+        .unwrap();
+
+    releaser.join().unwrap();
+    holder.join().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_port(&String::from_utf8_lossy(&output.stdout));
+}
+
+/// Test reserve --wait also retries a lock hit while opening the database.
+///
+/// Opening a fresh database sets it up under a write lock, so a held lock
+/// blocks the open itself rather than the later transaction.
+#[test]
+fn test_reserve_wait_retries_locked_database_open() {
+    let env = TestEnv::new();
+    let test_path = env.create_dir("waiting");
+    std::fs::create_dir_all(&env.data_dir).unwrap();
+
+    let (release, holder) = hold_write_lock(&env);
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        release.send(()).unwrap();
+    });
+
+    let output = env
+        .command()
+        .arg("--busy-timeout")
+        .arg("0")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--wait")
+        .arg("10")
+        .output()
+        .unwrap();
+
+    releaser.join().unwrap();
+    holder.join().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_port(&String::from_utf8_lossy(&output.stdout));
+}
+
+/// Test reserve --wait gives up with the timeout exit code while the lock is held.
+#[test]
+fn test_reserve_wait_times_out_while_locked() {
+    let env = TestEnv::new();
+    env.reserve_simple(&env.create_dir("existing"));
+    let test_path = env.create_dir("waiting");
+
+    let (release, holder) = hold_write_lock(&env);
+
+    let started = std::time::Instant::now();
+    let output = env
+        .command()
+        .arg("--busy-timeout")
+        .arg("0")
+        .arg("reserve")
+        .arg("--path")
+        .arg(&test_path)
+        .arg("--allow-unrelated-path")
+        .arg("--wait")
+        .arg("1")
+        .output()
+        .unwrap();
+    let elapsed = started.elapsed();
+
+    release.send(()).unwrap();
+    holder.join().unwrap();
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(elapsed >= std::time::Duration::from_secs(1), "{elapsed:?}");
+}
+
+// ============================================================================
+// Batch Reservation Tests
+// ============================================================================
+
+/// Test --stdin-paths reserves a port per path and prints `path<TAB>port`.
+#[test]
+fn test_reserve_stdin_paths_batch() {
+    let env = TestEnv::new();
+    let first = env.create_dir("first");
+    let second = env.create_dir("second");
+    let input = format!("{}\n\n{}\n", first.display(), second.display());
+
+    let output = env
+        .command()
+        .args(["reserve", "--stdin-paths", "--allow-unrelated-path"])
+        .write_stdin(input)
+        .output()
+        .expect("Failed to run reserve");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let lines: Vec<(&str, u16)> = stdout
+        .lines()
+        .map(|line| {
+            let (path, port) = line.split_once('\t').expect("path<TAB>port");
+            (path, port.parse().expect("port should be numeric"))
+        })
+        .collect();
+    assert_eq!(lines.len(), 2, "blank lines are skipped: {stdout}");
+    assert_eq!(lines[0].0, first.display().to_string());
+    assert_eq!(lines[1].0, second.display().to_string());
+    assert_ne!(lines[0].1, lines[1].1);
+
+    // Each path got its own reservation
+    let list = env.list();
+    assert!(list.contains(&lines[0].1.to_string()), "{list}");
+    assert!(list.contains(&lines[1].1.to_string()), "{list}");
+}
+
+/// Test that a path failing to reserve aborts the batch, naming its line.
+#[test]
+fn test_reserve_stdin_paths_unrelated_path_names_line() {
+    let env = TestEnv::new();
+    let first = env.create_dir("first");
+    let unrelated = env.create_dir("elsewhere");
+    let input = format!("{}\n\n{}\n", first.display(), unrelated.display());
+
+    env.command()
+        .current_dir(&first)
+        .args(["reserve", "--stdin-paths"])
+        .write_stdin(input)
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "line 3: path relationship violation",
+        ));
+
+    let list = env.list();
+    assert!(
+        !list.contains(&first.display().to_string()),
+        "nothing should be reserved: {list}"
+    );
+}
+
+/// Test that an invalid line aborts the whole batch, naming the line.
+#[test]
+fn test_reserve_stdin_paths_invalid_line_aborts() {
+    let env = TestEnv::new();
+    let first = env.create_dir("first");
+    let input = format!("{}\n~nosuchuser/project\n", first.display());
+
+    env.command()
+        .args(["reserve", "--stdin-paths", "--allow-unrelated-path"])
+        .write_stdin(input)
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains("line 2"));
+
+    let list = env.list();
+    assert!(
+        !list.contains(&first.display().to_string()),
+        "nothing should be reserved: {list}"
+    );
 }
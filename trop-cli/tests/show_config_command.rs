@@ -0,0 +1,109 @@
+//! Integration tests for the `show-config` command.
+//!
+//! These tests verify that the printed configuration reflects the full
+//! merge (files, profile, environment) and that `--sources` attributes each
+//! field to the source that supplied it.
+
+mod common;
+
+use common::TestEnv;
+use predicates::prelude::*;
+
+/// Test that the defaults are shown as YAML when no config files exist.
+#[test]
+fn test_show_config_defaults() {
+    let env = TestEnv::new();
+
+    env.command()
+        .arg("show-config")
+        .current_dir(&env.temp_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("maximum_lock_wait_seconds: 5"))
+        .stdout(predicate::str::contains("min: 5000"));
+}
+
+/// Test that an environment override beats a tropfile value and is
+/// attributed to the environment.
+#[test]
+fn test_show_config_env_override_sources() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(
+        project.join("trop.yaml"),
+        "project: from-file\nmaximum_lock_wait_seconds: 12\n",
+    )
+    .unwrap();
+
+    let output = env
+        .command()
+        .args(["show-config", "--format", "json", "--sources"])
+        .current_dir(&project)
+        .env("TROP_PROJECT", "from-env")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["config"]["project"], "from-env");
+    assert_eq!(json["sources"]["project"], "env");
+    assert_eq!(json["config"]["maximum_lock_wait_seconds"], 12);
+    let lock_source = json["sources"]["maximum_lock_wait_seconds"]
+        .as_str()
+        .unwrap();
+    assert!(lock_source.starts_with("trop.yaml ("), "{lock_source}");
+    assert_eq!(json["sources"]["output_format"], "default");
+}
+
+/// Test that YAML output annotates fields with `# source:` comments.
+#[test]
+fn test_show_config_yaml_sources() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(project.join("trop.local.yaml"), "project: local\n").unwrap();
+
+    let output = env
+        .command()
+        .args(["show-config", "--sources"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let comment = stdout.find("# source: trop.local.yaml (").expect(&stdout);
+    let field = stdout.find("project: local").expect(&stdout);
+    assert!(comment < field, "{stdout}");
+    assert!(stdout.contains("# source: default\n"), "{stdout}");
+}
+
+/// Test that the selected profile is applied and attributed.
+#[test]
+fn test_show_config_profile_source() {
+    let env = TestEnv::new();
+    let project = env.create_dir("project");
+    std::fs::write(
+        project.join("trop.yaml"),
+        "profiles:\n  ci:\n    maximum_lock_wait_seconds: 30\n",
+    )
+    .unwrap();
+
+    let output = env
+        .command()
+        .args([
+            "--profile",
+            "ci",
+            "show-config",
+            "--format",
+            "json",
+            "--sources",
+        ])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["config"]["maximum_lock_wait_seconds"], 30);
+    assert_eq!(json["sources"]["maximum_lock_wait_seconds"], "profile ci");
+}
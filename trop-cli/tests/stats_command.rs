@@ -0,0 +1,119 @@
+//! Integration tests for the `stats` command.
+//!
+//! These tests verify the database usage summary, including:
+//! - Totals, distinct ports, and sticky counts
+//! - Per-project counts, with unassigned reservations grouped together
+//! - Port range utilization against the default range
+//! - The opt-in missing-path check
+
+mod common;
+
+use common::TestEnv;
+use serde_json::Value;
+
+/// Run `stats --format json` with extra arguments and parse the output.
+fn stats_json(env: &TestEnv, args: &[&str]) -> Value {
+    let output = env
+        .command()
+        .args(["stats", "--format", "json"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stats failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// Reserve a port for `path` under `project`.
+fn reserve_in_project(env: &TestEnv, path: &std::path::Path, project: &str) {
+    env.command()
+        .arg("reserve")
+        .arg("--path")
+        .arg(path)
+        .args(["--project", project, "--allow-unrelated-path"])
+        .assert()
+        .success();
+}
+
+// ============================================================================
+// Summary Tests
+// ============================================================================
+
+/// Test stats on an empty database.
+#[test]
+fn test_stats_empty_database() {
+    let env = TestEnv::new();
+    env.command().arg("init").assert().success();
+
+    let stats = stats_json(&env, &[]);
+    assert_eq!(stats["total"], 0);
+    assert_eq!(stats["distinct_ports"], 0);
+    assert_eq!(stats["projects"], serde_json::json!([]));
+    assert_eq!(stats["missing_paths"], Value::Null);
+}
+
+/// Test counts, per-project grouping, and range utilization.
+#[test]
+fn test_stats_counts_reservations() {
+    let env = TestEnv::new();
+    reserve_in_project(&env, &env.create_dir("api"), "shop");
+    reserve_in_project(&env, &env.create_dir("web"), "shop");
+    env.reserve_simple(&env.create_dir("scratch"));
+
+    let stats = stats_json(&env, &[]);
+    assert_eq!(stats["total"], 3);
+    assert_eq!(stats["distinct_ports"], 3);
+    assert_eq!(stats["sticky"], 2);
+    assert_eq!(stats["ports_in_range"], 3);
+    assert_eq!(stats["range"]["min"], 5000);
+    assert_eq!(stats["range"]["max"], 7000);
+    assert_eq!(
+        stats["projects"],
+        serde_json::json!([
+            {"project": null, "count": 1},
+            {"project": "shop", "count": 2},
+        ])
+    );
+
+    let utilization = stats["range_utilization_percent"].as_f64().unwrap();
+    assert!((utilization - 300.0 / 2001.0).abs() < 1e-9, "{utilization}");
+}
+
+/// Test that --check-paths counts reservations for deleted directories.
+#[test]
+fn test_stats_check_paths() {
+    let env = TestEnv::new();
+    let kept = env.create_dir("kept");
+    let deleted = env.create_dir("deleted");
+    env.reserve_simple(&kept);
+    env.reserve_simple(&deleted);
+    env.reserve_with_tag(&deleted, "web");
+    std::fs::remove_dir_all(&deleted).unwrap();
+
+    let stats = stats_json(&env, &["--check-paths"]);
+    assert_eq!(stats["total"], 3);
+    assert_eq!(stats["missing_paths"], 2);
+}
+
+/// Test the human-readable summary.
+#[test]
+fn test_stats_table_format() {
+    let env = TestEnv::new();
+    reserve_in_project(&env, &env.create_dir("api"), "shop");
+
+    let output = env.command().arg("stats").output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Total reservations: 1"), "{stdout}");
+    assert!(stdout.contains("Sticky reservations: 1"), "{stdout}");
+    assert!(
+        stdout.contains("Port range: 5000-7000 (1 of 2001 ports reserved, 0.0%)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("  shop\t1"), "{stdout}");
+    assert!(!stdout.contains("Missing paths"), "{stdout}");
+}
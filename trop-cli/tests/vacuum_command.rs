@@ -0,0 +1,50 @@
+//! Integration tests for the `vacuum` command.
+//!
+//! These tests verify that `trop vacuum` compacts the database without
+//! losing live reservations and reports the space it reclaimed.
+
+mod common;
+
+use common::TestEnv;
+
+// ============================================================================
+// Vacuum Tests
+// ============================================================================
+
+/// Test that vacuum keeps live reservations and reports reclaimed bytes.
+#[test]
+fn test_vacuum_keeps_reservations() {
+    let env = TestEnv::new();
+    let keep = env.create_dir("keep");
+    let port = env.reserve_simple(&keep);
+    for i in 0..5 {
+        let dir = env.create_dir(&format!("gone-{i}"));
+        env.reserve_simple(&dir);
+        env.release(&dir);
+    }
+
+    let output = env
+        .command()
+        .args(["vacuum", "--checkpoint"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "vacuum failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("reclaimed"));
+    assert!(common::contains_port(&env.list(), port));
+}
+
+/// Test that --quiet suppresses the report.
+#[test]
+fn test_vacuum_quiet() {
+    let env = TestEnv::new();
+    let dir = env.create_dir("project");
+    env.reserve_simple(&dir);
+
+    let output = env.command().args(["--quiet", "vacuum"]).output().unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
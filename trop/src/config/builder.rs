@@ -6,11 +6,124 @@
 use crate::config::environment::EnvironmentConfig;
 use crate::config::loader::{ConfigLoader, ConfigSource};
 use crate::config::merger::ConfigMerger;
-use crate::config::schema::{CleanupConfig, Config, OccupancyConfig, OutputFormat, PortConfig};
+use crate::config::schema::{
+    CleanupConfig, Config, OccupancyConfig, OccupancyErrorPolicy, OutputFormat, PortConfig, TagCase,
+};
 use crate::config::validator::ConfigValidator;
-use crate::error::Result;
+use crate::database::DEFAULT_DATABASE_FILENAME;
+use crate::error::{Error, Result};
+use crate::operations::AllocationStrategy;
+use crate::path::normalize::expand_path;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
 
+/// Where the effective value of a top-level configuration field came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    /// Built-in default.
+    Default,
+    /// The user configuration file in the data directory.
+    UserConfig(PathBuf),
+    /// A project tropfile (`trop.yaml`, `trop.local.yaml`, or JSON variants).
+    Tropfile(PathBuf),
+    /// The named profile.
+    Profile(String),
+    /// A `TROP_*` environment variable.
+    Environment,
+    /// Configuration passed to [`ConfigBuilder::with_config`], such as
+    /// command-line flags.
+    Cli,
+}
+
+impl ConfigOrigin {
+    fn from_source(source: &ConfigSource) -> Self {
+        match source.precedence {
+            0 => Self::Default,
+            1 => Self::UserConfig(source.path.clone()),
+            _ => Self::Tropfile(source.path.clone()),
+        }
+    }
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::UserConfig(path) => write!(f, "user config ({})", path.display()),
+            Self::Tropfile(path) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                write!(f, "{name} ({})", path.display())
+            }
+            Self::Profile(name) => write!(f, "profile {name}"),
+            Self::Environment => write!(f, "env"),
+            Self::Cli => write!(f, "CLI"),
+        }
+    }
+}
+
+/// The origin of each top-level field of a built [`Config`], keyed by the
+/// field's serialized name. Fields no source sets are absent.
+pub type ConfigProvenance = BTreeMap<String, ConfigOrigin>;
+
+/// Port range overrides from the command line (`--min`, `--max`, and
+/// `--max-offset`), applied over every other configuration source.
+///
+/// A minimum replaces the configured one and keeps the configured maximum.
+/// A maximum or a maximum offset replaces whichever of the two was
+/// configured; giving both is left for the validator to reject.
+///
+/// # Examples
+///
+/// ```
+/// use trop::config::{ConfigBuilder, PortRangeOverrides};
+///
+/// let config = ConfigBuilder::new()
+///     .skip_files()
+///     .skip_env()
+///     .with_port_overrides(PortRangeOverrides {
+///         min: Some(6000),
+///         max_offset: Some(100),
+///         ..PortRangeOverrides::default()
+///     })
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.effective_port_range().unwrap().max().value(), 6100);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PortRangeOverrides {
+    /// Minimum port.
+    pub min: Option<u16>,
+    /// Maximum port.
+    pub max: Option<u16>,
+    /// Maximum port as an offset from the minimum.
+    pub max_offset: Option<u16>,
+}
+
+impl PortRangeOverrides {
+    /// Returns `true` if no override is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none() && self.max_offset.is_none()
+    }
+
+    /// Applies the overrides to a port configuration.
+    fn apply(&self, ports: &mut PortConfig) {
+        if let Some(min) = self.min {
+            ports.min = min;
+        }
+        if let Some(max) = self.max {
+            ports.max = Some(max);
+            ports.max_offset = None;
+        }
+        if let Some(offset) = self.max_offset {
+            // Keeps an explicit maximum so the validator rejects the pair
+            ports.max = self.max;
+            ports.max_offset = Some(offset);
+        }
+    }
+}
+
 /// Builder for loading and constructing configuration.
 ///
 /// # Examples
@@ -30,6 +143,8 @@ pub struct ConfigBuilder {
     skip_env: bool,
     skip_files: bool,
     additional_config: Option<Config>,
+    port_overrides: PortRangeOverrides,
+    profile: Option<String>,
 }
 
 impl ConfigBuilder {
@@ -42,6 +157,8 @@ impl ConfigBuilder {
             skip_env: false,
             skip_files: false,
             additional_config: None,
+            port_overrides: PortRangeOverrides::default(),
+            profile: None,
         }
     }
 
@@ -58,7 +175,8 @@ impl ConfigBuilder {
     /// Set the data directory for user config loading.
     ///
     /// This overrides the default data directory (`~/.trop` or `$TROP_DATA_DIR`)
-    /// when loading the user configuration file.
+    /// when loading the user configuration file. A leading `~` and `$VAR`
+    /// references are expanded when the configuration is built.
     #[must_use]
     pub fn with_data_dir(mut self, dir: impl AsRef<Path>) -> Self {
         self.data_dir = Some(dir.as_ref().to_path_buf());
@@ -93,22 +211,72 @@ impl ConfigBuilder {
         self
     }
 
+    /// Override the port range over every other source, including
+    /// [`with_config`](Self::with_config).
+    #[must_use]
+    pub const fn with_port_overrides(mut self, overrides: PortRangeOverrides) -> Self {
+        self.port_overrides = overrides;
+        self
+    }
+
+    /// Select a named profile to merge over the file-based configuration.
+    ///
+    /// Takes priority over `TROP_PROFILE`. Profile overrides sit below
+    /// environment variables in precedence.
+    #[must_use]
+    pub fn with_profile(mut self, name: impl Into<String>) -> Self {
+        self.profile = Some(name.into());
+        self
+    }
+
     /// Build the final configuration.
     ///
     /// Performs the following steps:
     /// 1. Starts with default configuration
     /// 2. Loads and merges file-based configurations (if not skipped)
-    /// 3. Applies environment variable overrides (if not skipped)
-    /// 4. Applies additional configuration (if provided)
-    /// 5. Validates the final configuration
+    /// 3. Applies the selected profile, from [`with_profile`](Self::with_profile)
+    ///    or `TROP_PROFILE` (if any)
+    /// 4. Applies environment variable overrides (if not skipped)
+    /// 5. Applies additional configuration (if provided)
+    /// 6. Applies port range overrides (if provided)
+    /// 7. Validates the final configuration
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Configuration files cannot be read or parsed
+    /// - The selected profile is not defined
     /// - Environment variables contain invalid values
     /// - The final configuration fails validation
     pub fn build(self) -> Result<Config> {
+        self.build_with_provenance().map(|(config, _)| config)
+    }
+
+    /// Build the final configuration and record where each top-level field
+    /// came from.
+    ///
+    /// Runs the same steps as [`build`](Self::build). A field is attributed
+    /// to the highest-precedence source that sets it, even if that source
+    /// sets the value an earlier one already had.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`build`](Self::build).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::config::{ConfigBuilder, ConfigOrigin};
+    ///
+    /// let (config, provenance) = ConfigBuilder::new()
+    ///     .skip_files()
+    ///     .skip_env()
+    ///     .build_with_provenance()
+    ///     .unwrap();
+    /// assert_eq!(config.maximum_lock_wait_seconds, Some(5));
+    /// assert_eq!(provenance["maximum_lock_wait_seconds"], ConfigOrigin::Default);
+    /// ```
+    pub fn build_with_provenance(self) -> Result<(Config, ConfigProvenance)> {
         let mut sources = Vec::new();
 
         // Load configuration files
@@ -117,7 +285,8 @@ impl ConfigBuilder {
                 .working_dir
                 .as_deref()
                 .unwrap_or_else(|| Path::new("."));
-            sources = ConfigLoader::load_all(working_dir, self.data_dir.as_deref())?;
+            let data_dir = self.data_dir.as_deref().map(expand_path).transpose()?;
+            sources = ConfigLoader::load_all(working_dir, data_dir.as_deref())?;
         }
 
         // Add default configuration at lowest precedence
@@ -133,54 +302,120 @@ impl ConfigBuilder {
         // Check if we should treat this as a tropfile before consuming sources
         // Consider it a tropfile if any source was a tropfile OR if additional config was provided
         // (additional config is programmatic and should allow tropfile-only fields)
-        let is_tropfile = self.additional_config.is_some()
-            || sources.iter().any(|s| {
-                s.path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .is_some_and(|n| n == "trop.yaml" || n == "trop.local.yaml")
+        let is_tropfile =
+            self.additional_config.is_some() || sources.iter().any(ConfigSource::is_tropfile);
+
+        // Hooks run arbitrary commands, so a checked-out repository's trop.yaml
+        // must never be able to define one
+        if let Some(source) = sources.iter().find(|s| {
+            s.is_tropfile()
+                && (s.config.pre_reserve_hook.is_some()
+                    || s.config
+                        .profiles
+                        .iter()
+                        .flat_map(|p| p.values())
+                        .any(|p| p.pre_reserve_hook.is_some()))
+        }) {
+            return Err(Error::Validation {
+                field: "pre_reserve_hook".into(),
+                message: format!(
+                    "pre_reserve_hook is only allowed in user configuration, not in {}",
+                    source.path.display()
+                ),
             });
+        }
 
         // Merge all file-based configs
+        let mut provenance = ConfigProvenance::new();
+        for source in &sources {
+            record_fields(&mut provenance, set_fields(&source.config), || {
+                ConfigOrigin::from_source(source)
+            });
+        }
         let mut config = ConfigMerger::merge(sources);
 
-        // Apply environment overrides
+        // Apply the selected profile just below environment overrides
+        let profile = match self.profile {
+            Some(name) => Some(name),
+            None if !self.skip_env => EnvironmentConfig::selected_profile(),
+            None => None,
+        };
+        if let Some(name) = profile {
+            let fields = config
+                .profiles
+                .as_ref()
+                .and_then(|profiles| profiles.get(&name))
+                .map(set_fields)
+                .unwrap_or_default();
+            ConfigMerger::apply_profile(&mut config, &name)?;
+            record_fields(&mut provenance, fields, || {
+                ConfigOrigin::Profile(name.clone())
+            });
+        }
+
+        // Apply environment overrides. Applying them to an empty config as
+        // well finds the fields they set to an unchanged value.
         if !self.skip_env {
+            let before = config.clone();
             EnvironmentConfig::apply_overrides(&mut config)?;
+            let mut env_only = Config::default();
+            EnvironmentConfig::apply_overrides(&mut env_only)?;
+            let mut fields = set_fields(&env_only);
+            fields.extend(changed_fields(&before, &config));
+            record_fields(&mut provenance, fields, || ConfigOrigin::Environment);
         }
 
         // Apply additional config if provided
         if let Some(additional) = self.additional_config {
+            record_fields(&mut provenance, set_fields(&additional), || {
+                ConfigOrigin::Cli
+            });
             ConfigMerger::merge_into(&mut config, &additional);
         }
 
+        // Apply port range overrides over everything else
+        if !self.port_overrides.is_empty() {
+            let ports = config.ports.get_or_insert_with(PortConfig::default);
+            self.port_overrides.apply(ports);
+            record_fields(&mut provenance, vec!["ports".to_string()], || {
+                ConfigOrigin::Cli
+            });
+        }
+
         // Validate final configuration
         ConfigValidator::validate(&config, is_tropfile)?;
 
-        Ok(config)
+        Ok((config, provenance))
     }
 
     /// Create default configuration.
     ///
     /// Returns a configuration with all defaults matching the specification:
     /// - Port range: 5000-7000
+    /// - Allocation strategy: lowest
     /// - Expire after: 30 days
     /// - Lock timeout: 5 seconds
     /// - All auto behaviors enabled
     /// - All permission flags disabled
     /// - All occupancy checks enabled
+    /// - Occupancy check errors treated as occupied
+    /// - No pre-reserve hook (10 second timeout when one is configured)
     /// - Output format: table
     fn default_config() -> Config {
         Config {
             project: None,
+            default_tag: None,
+            tag_case: Some(TagCase::Sensitive),
             ports: Some(PortConfig {
                 min: 5000,
                 max: Some(7000),
                 max_offset: None,
             }),
+            allocation_strategy: Some(AllocationStrategy::Lowest),
             excluded_ports: None,
             cleanup: Some(CleanupConfig {
                 expire_after_days: Some(30),
+                protect_priority: Some(0),
             }),
             occupancy_check: Some(OccupancyConfig {
                 skip: Some(false),
@@ -189,7 +424,9 @@ impl ConfigBuilder {
                 skip_tcp: Some(false),
                 skip_udp: Some(false),
                 check_all_interfaces: Some(false),
+                interfaces: None,
             }),
+            on_occupancy_error: Some(OccupancyErrorPolicy::TreatAsOccupied),
             reservations: None,
             disable_autoinit: Some(false),
             disable_autoprune: Some(false),
@@ -199,11 +436,53 @@ impl ConfigBuilder {
             allow_change_task: Some(false),
             allow_change: Some(false),
             maximum_lock_wait_seconds: Some(5),
+            database_filename: Some(DEFAULT_DATABASE_FILENAME.to_string()),
             output_format: Some(OutputFormat::Table),
+            pre_reserve_hook: None,
+            pre_reserve_hook_timeout_seconds: Some(10),
+            profiles: None,
         }
     }
 }
 
+/// Serialize a config's top-level fields by name.
+fn top_level_fields(config: &Config) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(config) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// Names of the top-level fields `config` sets.
+fn set_fields(config: &Config) -> Vec<String> {
+    top_level_fields(config)
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Names of the top-level fields whose values differ between two configs.
+fn changed_fields(before: &Config, after: &Config) -> Vec<String> {
+    let before = top_level_fields(before);
+    top_level_fields(after)
+        .into_iter()
+        .filter(|(name, value)| before.get(name) != Some(value))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Attribute each named field to the origin built by `origin`.
+fn record_fields(
+    provenance: &mut ConfigProvenance,
+    fields: Vec<String>,
+    origin: impl Fn() -> ConfigOrigin,
+) {
+    for field in fields {
+        provenance.insert(field, origin());
+    }
+}
+
 impl Default for ConfigBuilder {
     fn default() -> Self {
         Self::new()
@@ -340,10 +619,15 @@ mod tests {
         let ports = defaults.ports.unwrap();
         assert_eq!(ports.min, 5000);
         assert_eq!(ports.max, Some(7000));
+        assert_eq!(
+            defaults.allocation_strategy,
+            Some(AllocationStrategy::Lowest)
+        );
 
         // Cleanup
         let cleanup = defaults.cleanup.unwrap();
         assert_eq!(cleanup.expire_after_days, Some(30));
+        assert_eq!(cleanup.protect_priority, Some(0));
 
         // Lock timeout
         assert_eq!(defaults.maximum_lock_wait_seconds, Some(5));
@@ -370,6 +654,10 @@ mod tests {
 
         // Output format
         assert_eq!(defaults.output_format, Some(OutputFormat::Table));
+
+        // Pre-reserve hook
+        assert_eq!(defaults.pre_reserve_hook, None);
+        assert_eq!(defaults.pre_reserve_hook_timeout_seconds, Some(10));
     }
 
     #[test]
@@ -415,4 +703,241 @@ mod tests {
         assert_eq!(ports.max, None);
         assert_eq!(ports.max_offset, Some(25));
     }
+
+    #[test]
+    fn test_builder_rejects_pre_reserve_hook_in_tropfile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("trop.yaml"),
+            "pre_reserve_hook: \"true\"\n",
+        )
+        .unwrap();
+
+        let result = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(temp_dir.path().join("data"))
+            .skip_env()
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(Error::Validation { ref field, .. }) if field == "pre_reserve_hook"
+        ));
+    }
+
+    #[test]
+    fn test_builder_accepts_pre_reserve_hook_in_user_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        fs::create_dir_all(&data_dir).unwrap();
+        fs::write(
+            data_dir.join("config.yaml"),
+            "pre_reserve_hook: \"true\"\npre_reserve_hook_timeout_seconds: 3\n",
+        )
+        .unwrap();
+
+        let config = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(&data_dir)
+            .skip_env()
+            .build()
+            .unwrap();
+
+        assert_eq!(config.pre_reserve_hook, Some("true".to_string()));
+        assert_eq!(config.pre_reserve_hook_timeout_seconds, Some(3));
+    }
+
+    #[test]
+    fn test_builder_allocation_strategy_from_tropfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        fs::create_dir_all(&data_dir).unwrap();
+        fs::write(
+            temp_dir.path().join("trop.yaml"),
+            "allocation_strategy: random\n",
+        )
+        .unwrap();
+
+        let config = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(&data_dir)
+            .skip_env()
+            .build()
+            .unwrap();
+
+        assert_eq!(config.allocation_strategy, Some(AllocationStrategy::Random));
+    }
+
+    const PROFILE_TROPFILE: &str = "\
+ports:
+  min: 5000
+  max: 5999
+profiles:
+  ci:
+    ports:
+      min: 7000
+      max: 7999
+    maximum_lock_wait_seconds: 30
+";
+
+    #[test]
+    fn test_builder_applies_selected_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("trop.yaml"), PROFILE_TROPFILE).unwrap();
+
+        let config = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(temp_dir.path().join("data"))
+            .skip_env()
+            .with_profile("ci")
+            .build()
+            .unwrap();
+
+        let ports = config.ports.unwrap();
+        assert_eq!((ports.min, ports.max), (7000, Some(7999)));
+        assert_eq!(config.maximum_lock_wait_seconds, Some(30));
+    }
+
+    #[test]
+    fn test_builder_uses_base_without_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("trop.yaml"), PROFILE_TROPFILE).unwrap();
+
+        let config = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(temp_dir.path().join("data"))
+            .skip_env()
+            .build()
+            .unwrap();
+
+        let ports = config.ports.unwrap();
+        assert_eq!((ports.min, ports.max), (5000, Some(5999)));
+        assert_eq!(config.maximum_lock_wait_seconds, Some(5));
+    }
+
+    #[test]
+    fn test_builder_rejects_unknown_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("trop.yaml"), PROFILE_TROPFILE).unwrap();
+
+        let result = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(temp_dir.path().join("data"))
+            .skip_env()
+            .with_profile("staging")
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(Error::Validation { ref field, ref message })
+                if field == "profile" && message.contains("available: ci")
+        ));
+    }
+
+    #[test]
+    fn test_builder_rejects_pre_reserve_hook_in_tropfile_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("trop.yaml"),
+            "profiles:\n  ci:\n    pre_reserve_hook: \"true\"\n",
+        )
+        .unwrap();
+
+        let result = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(temp_dir.path().join("data"))
+            .skip_env()
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(Error::Validation { ref field, .. }) if field == "pre_reserve_hook"
+        ));
+    }
+
+    #[test]
+    fn test_builder_records_provenance() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("trop.yaml"), PROFILE_TROPFILE).unwrap();
+
+        let (config, provenance) = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .with_data_dir(temp_dir.path().join("data"))
+            .skip_env()
+            .with_profile("ci")
+            .with_config(Config {
+                project: Some("cli-project".to_string()),
+                ..Default::default()
+            })
+            .build_with_provenance()
+            .unwrap();
+
+        assert_eq!(config.project.as_deref(), Some("cli-project"));
+        assert_eq!(provenance["project"], ConfigOrigin::Cli);
+        assert_eq!(provenance["ports"], ConfigOrigin::Profile("ci".to_string()));
+        assert_eq!(provenance["cleanup"], ConfigOrigin::Default);
+        assert!(matches!(
+            &provenance["profiles"],
+            ConfigOrigin::Tropfile(path) if path.ends_with("trop.yaml")
+        ));
+        assert!(!provenance.contains_key("default_tag"));
+    }
+
+    #[test]
+    fn test_builder_port_overrides_replace_configured_range() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("trop.yaml"),
+            "ports:\n  min: 8000\n  max: 9000\n",
+        )
+        .unwrap();
+
+        let (config, provenance) = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .skip_env()
+            .with_port_overrides(PortRangeOverrides {
+                min: Some(6000),
+                max_offset: Some(100),
+                ..PortRangeOverrides::default()
+            })
+            .build_with_provenance()
+            .unwrap();
+
+        let ports = config.ports.unwrap();
+        assert_eq!(ports.min, 6000);
+        assert_eq!(ports.max, None);
+        assert_eq!(ports.effective_max().unwrap(), 6100);
+        assert_eq!(provenance["ports"], ConfigOrigin::Cli);
+
+        // A minimum alone keeps the configured maximum
+        let config = ConfigBuilder::new()
+            .with_working_dir(temp_dir.path())
+            .skip_env()
+            .with_port_overrides(PortRangeOverrides {
+                min: Some(8500),
+                ..PortRangeOverrides::default()
+            })
+            .build()
+            .unwrap();
+        let ports = config.ports.unwrap();
+        assert_eq!((ports.min, ports.max), (8500, Some(9000)));
+    }
+
+    #[test]
+    fn test_builder_port_overrides_reject_max_with_max_offset() {
+        let result = ConfigBuilder::new()
+            .skip_files()
+            .skip_env()
+            .with_port_overrides(PortRangeOverrides {
+                min: Some(6000),
+                max: Some(6200),
+                max_offset: Some(100),
+            })
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(Error::Validation { ref field, .. }) if field == "ports"
+        ));
+    }
 }
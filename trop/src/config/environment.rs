@@ -3,7 +3,7 @@
 //! This module provides support for TROP_* environment variables that
 //! override configuration file values.
 
-use crate::config::schema::{Config, PortExclusion};
+use crate::config::schema::{normalize_exclusions, Config, PortExclusion};
 use crate::error::{Error, Result};
 use std::env;
 
@@ -35,6 +35,15 @@ impl EnvironmentConfig {
             config.project = Some(project);
         }
 
+        // TROP_DEFAULT_TAG (empty value clears any configured default)
+        if let Ok(tag) = env::var("TROP_DEFAULT_TAG") {
+            config.default_tag = if tag.trim().is_empty() {
+                None
+            } else {
+                Some(tag)
+            };
+        }
+
         // TROP_DISABLE_AUTOINIT
         if let Ok(val) = env::var("TROP_DISABLE_AUTOINIT") {
             config.disable_autoinit = Some(Self::parse_bool("TROP_DISABLE_AUTOINIT", &val)?);
@@ -56,10 +65,9 @@ impl EnvironmentConfig {
         // TROP_EXCLUDED_PORTS (comma-separated)
         if let Ok(excluded) = env::var("TROP_EXCLUDED_PORTS") {
             let exclusions = Self::parse_excluded_ports(&excluded)?;
-            match &mut config.excluded_ports {
-                Some(existing) => existing.extend(exclusions),
-                None => config.excluded_ports = Some(exclusions),
-            }
+            let existing = config.excluded_ports.get_or_insert_with(Vec::new);
+            existing.extend(exclusions);
+            normalize_exclusions(existing);
         }
 
         // TROP_EXPIRE_AFTER_DAYS
@@ -82,6 +90,11 @@ impl EnvironmentConfig {
                 })?);
         }
 
+        // TROP_DB_NAME
+        if let Ok(name) = env::var("TROP_DB_NAME") {
+            config.database_filename = Some(name);
+        }
+
         // Permission flags
         if let Ok(val) = env::var("TROP_ALLOW_UNRELATED_PATH") {
             config.allow_unrelated_path =
@@ -107,6 +120,15 @@ impl EnvironmentConfig {
         Ok(())
     }
 
+    /// Returns the profile named by `TROP_PROFILE`, if set and non-empty.
+    #[must_use]
+    pub fn selected_profile() -> Option<String> {
+        env::var("TROP_PROFILE")
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
     /// Apply port-related environment variable overrides.
     fn apply_port_overrides(config: &mut Config) -> Result<()> {
         let mut port_config = config.ports.clone().unwrap_or_default();
@@ -207,7 +229,13 @@ impl EnvironmentConfig {
     /// Parse excluded ports from comma-separated string.
     ///
     /// Supports both individual ports (e.g., "5001") and ranges (e.g., "5000..5010").
-    fn parse_excluded_ports(s: &str) -> Result<Vec<PortExclusion>> {
+    /// This is the `TROP_EXCLUDED_PORTS` syntax; other sources of ad-hoc
+    /// exclusions use it too so the formats stay consistent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if any entry is not a port or range.
+    pub fn parse_excluded_ports(s: &str) -> Result<Vec<PortExclusion>> {
         let mut exclusions = Vec::new();
 
         for part in s.split(',') {
@@ -370,6 +398,25 @@ mod tests {
         assert_eq!(ports.max, None);
         assert_eq!(ports.max_offset, Some(25));
     }
+
+    #[test]
+    #[serial]
+    fn test_apply_default_tag_override() {
+        let mut config = Config {
+            default_tag: Some("web".to_string()),
+            ..Default::default()
+        };
+
+        std::env::set_var("TROP_DEFAULT_TAG", "api");
+        EnvironmentConfig::apply_overrides(&mut config).unwrap();
+        assert_eq!(config.default_tag, Some("api".to_string()));
+
+        // An empty value clears the configured default
+        std::env::set_var("TROP_DEFAULT_TAG", "");
+        EnvironmentConfig::apply_overrides(&mut config).unwrap();
+        std::env::remove_var("TROP_DEFAULT_TAG");
+        assert_eq!(config.default_tag, None);
+    }
 }
 
 // Property-based tests for environment variable parsing
@@ -486,7 +533,7 @@ mod property_tests {
                     prop_assert_eq!(*e, end, "End value preserved");
                     prop_assert!(*s <= *e, "Range invariant maintained");
                 }
-                PortExclusion::Single(_) => prop_assert!(false, "Expected Range variant"),
+                _ => prop_assert!(false, "Expected Range variant"),
             }
         }
     }
@@ -595,7 +642,7 @@ mod property_tests {
                     prop_assert_eq!(*start, range_start, "Range start correct");
                     prop_assert_eq!(*end, range_end, "Range end correct");
                 }
-                PortExclusion::Single(_) => prop_assert!(false, "Second should be Range"),
+                _ => prop_assert!(false, "Second should be Range"),
             }
             prop_assert_eq!(&exclusions[2], &PortExclusion::Single(port2), "Third port correct");
         }
@@ -3,7 +3,7 @@
 //! This module handles discovering and loading trop configuration files
 //! from various locations with proper precedence.
 
-use crate::config::schema::Config;
+use crate::config::schema::{normalize_exclusions, Config};
 use crate::error::{Error, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -35,6 +35,33 @@ pub struct ConfigSource {
     pub config: Config,
 }
 
+impl ConfigSource {
+    /// Returns true if this source is a project tropfile (`trop.yaml`,
+    /// `trop.local.yaml`, or their `.json` counterparts).
+    #[must_use]
+    pub fn is_tropfile(&self) -> bool {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| TROPFILE_NAMES.contains(&n))
+    }
+}
+
+/// Project configuration file names, as (YAML, JSON) pairs in increasing
+/// precedence. Within a pair the YAML file wins if both exist.
+const TROPFILE_PAIRS: [(&str, &str); 2] = [
+    ("trop.yaml", "trop.json"),
+    ("trop.local.yaml", "trop.local.json"),
+];
+
+/// Every file name [`TROPFILE_PAIRS`] recognizes.
+const TROPFILE_NAMES: [&str; 4] = [
+    "trop.yaml",
+    "trop.json",
+    "trop.local.yaml",
+    "trop.local.json",
+];
+
 /// Loads configuration from various sources.
 ///
 /// # Examples
@@ -109,66 +136,165 @@ impl ConfigLoader {
 
     /// Discover project configurations by walking up directories.
     ///
-    /// Stops at the first directory containing either trop.yaml or trop.local.yaml.
+    /// Stops at the first directory containing any tropfile. Each of
+    /// `trop.yaml` (precedence 2) and `trop.local.yaml` (precedence 3) may
+    /// instead be written as `trop.json` or `trop.local.json`. If a directory
+    /// has both the YAML and JSON form of the same file, the YAML file is used
+    /// and the JSON file is ignored with a warning.
     ///
     /// # Errors
     ///
     /// Returns an error if any discovered file cannot be read or parsed.
     pub fn discover_project_configs(start_dir: &Path) -> Result<Vec<ConfigSource>> {
-        let mut configs = Vec::new();
+        Self::locate_project_configs(start_dir)
+            .into_iter()
+            .map(|(precedence, path)| {
+                let config = Self::load_file(&path)?;
+                Ok(ConfigSource {
+                    path,
+                    precedence,
+                    config,
+                })
+            })
+            .collect()
+    }
+
+    /// Find the project tropfiles [`Self::discover_project_configs`] would
+    /// load, without reading them.
+    ///
+    /// Paths are returned in increasing precedence, so the last one is the
+    /// file whose settings win.
+    #[must_use]
+    pub fn project_config_paths(start_dir: &Path) -> Vec<PathBuf> {
+        Self::locate_project_configs(start_dir)
+            .into_iter()
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    /// Walk up from `start_dir` to the first directory holding a tropfile,
+    /// returning each tropfile there with its precedence.
+    fn locate_project_configs(start_dir: &Path) -> Vec<(u8, PathBuf)> {
+        let mut found = Vec::new();
         let mut current = start_dir.to_path_buf();
 
         loop {
-            let mut found_any = false;
-
-            // Check for trop.yaml
-            let trop_yaml = current.join("trop.yaml");
-            if trop_yaml.exists() {
-                let config = Self::load_file(&trop_yaml)?;
-                configs.push(ConfigSource {
-                    path: trop_yaml,
-                    precedence: 2,
-                    config,
-                });
-                found_any = true;
-            }
-
-            // Check for trop.local.yaml (higher precedence)
-            let trop_local = current.join("trop.local.yaml");
-            if trop_local.exists() {
-                let config = Self::load_file(&trop_local)?;
-                configs.push(ConfigSource {
-                    path: trop_local,
-                    precedence: 3,
-                    config,
-                });
-                found_any = true;
+            // Check for trop.yaml, then trop.local.yaml (higher precedence)
+            for (precedence, (yaml_name, json_name)) in (2..).zip(TROPFILE_PAIRS) {
+                if let Some(path) = Self::pick_tropfile(&current, yaml_name, json_name) {
+                    found.push((precedence, path));
+                }
             }
 
             // Stop if we found configs or can't go up anymore
-            if found_any || !current.pop() {
+            if !found.is_empty() || !current.pop() {
                 break;
             }
         }
 
-        Ok(configs)
+        found
+    }
+
+    /// Choose between the YAML and JSON form of a tropfile in `dir`.
+    ///
+    /// Returns the YAML file if it exists, warning if the JSON file is
+    /// present too, and otherwise the JSON file if that exists.
+    fn pick_tropfile(dir: &Path, yaml_name: &str, json_name: &str) -> Option<PathBuf> {
+        let yaml = dir.join(yaml_name);
+        let json = dir.join(json_name);
+        match (yaml.exists(), json.exists()) {
+            (true, json_exists) => {
+                if json_exists {
+                    log::warn!(
+                        "Ignoring {} because {} takes precedence",
+                        json.display(),
+                        yaml.display()
+                    );
+                }
+                Some(yaml)
+            }
+            (false, true) => Some(json),
+            (false, false) => None,
+        }
+    }
+
+    /// Find every tropfile in the directory tree under `root`.
+    ///
+    /// Returns every tropfile (YAML or JSON) present in each directory,
+    /// sorted by path, including JSON files that
+    /// [`Self::discover_project_configs`] would skip in favor of YAML. Hidden directories (such as `.git`) and symlinked
+    /// directories are not descended into.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory cannot be read.
+    pub fn find_tropfiles(root: &Path) -> Result<Vec<PathBuf>> {
+        let mut found = Vec::new();
+        let mut pending = vec![root.to_path_buf()];
+
+        while let Some(dir) = pending.pop() {
+            for name in TROPFILE_NAMES {
+                let candidate = dir.join(name);
+                if candidate.is_file() {
+                    found.push(candidate);
+                }
+            }
+
+            let entries = fs::read_dir(&dir).map_err(|e| Error::InvalidPath {
+                path: dir.clone(),
+                reason: format!("Failed to read directory: {e}"),
+            })?;
+
+            for entry in entries {
+                let entry = entry.map_err(|e| Error::InvalidPath {
+                    path: dir.clone(),
+                    reason: format!("Failed to read directory entry: {e}"),
+                })?;
+                let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+                // file_type() does not follow symlinks
+                let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+                if is_dir && !is_hidden {
+                    pending.push(entry.path());
+                }
+            }
+        }
+
+        found.sort();
+        Ok(found)
     }
 
-    /// Load and parse a YAML configuration file.
+    /// Load and parse a configuration file.
+    ///
+    /// Files with a `.json` extension are parsed as JSON; anything else is
+    /// parsed as YAML. Both use the same schema.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read or the YAML is invalid.
+    /// Returns an error if the file cannot be read or its contents are invalid.
     pub fn load_file(path: &Path) -> Result<Config> {
         let contents = fs::read_to_string(path).map_err(|e| Error::InvalidPath {
             path: path.to_path_buf(),
             reason: format!("Failed to read configuration file: {e}"),
         })?;
 
-        serde_yaml::from_str(&contents).map_err(|e| Error::Validation {
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let parsed = if is_json {
+            serde_json::from_str(&contents).map_err(|e| format!("Invalid JSON: {e}"))
+        } else {
+            serde_yaml::from_str(&contents).map_err(|e| format!("Invalid YAML: {e}"))
+        };
+        let mut config: Config = parsed.map_err(|message| Error::Validation {
             field: format!("{}", path.display()),
-            message: format!("Invalid YAML: {e}"),
-        })
+            message,
+        })?;
+
+        if let Some(ref mut exclusions) = config.excluded_ports {
+            normalize_exclusions(exclusions);
+        }
+
+        Ok(config)
     }
 
     /// Get user config directory path.
@@ -185,6 +311,7 @@ impl ConfigLoader {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::schema::PortExclusion;
     use std::fs;
     use tempfile::TempDir;
 
@@ -214,6 +341,29 @@ mod tests {
         assert_eq!(config.project, Some("test-project".to_string()));
     }
 
+    #[test]
+    fn test_load_file_normalizes_exclusions() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(
+            &config_path,
+            "excluded_ports:\n  - \"6000..6010\"\n  - \"5000..5000\"\n  - 5000\n  - start: 6000\n    end: 6010\n",
+        )
+        .unwrap();
+
+        let config = ConfigLoader::load_file(&config_path).unwrap();
+        assert_eq!(
+            config.excluded_ports.unwrap(),
+            vec![
+                PortExclusion::Single(5000),
+                PortExclusion::Range {
+                    start: 6000,
+                    end: 6010
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_discover_no_configs() {
         let temp_dir = TempDir::new().unwrap();
@@ -264,6 +414,140 @@ mod tests {
         assert_eq!(configs[0].config.project, Some("parent".to_string()));
     }
 
+    #[test]
+    fn test_find_tropfiles_recursive() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("services/api")).unwrap();
+        fs::create_dir_all(root.join("services/web")).unwrap();
+        fs::create_dir_all(root.join(".git/hooks")).unwrap();
+
+        fs::write(root.join("trop.yaml"), "project: root\n").unwrap();
+        fs::write(root.join("services/api/trop.yaml"), "project: api\n").unwrap();
+        fs::write(root.join("services/api/trop.local.yaml"), "project: api\n").unwrap();
+        fs::write(root.join("services/web/config.yaml"), "project: web\n").unwrap();
+        fs::write(root.join(".git/hooks/trop.yaml"), "project: hidden\n").unwrap();
+
+        let found = ConfigLoader::find_tropfiles(root).unwrap();
+        assert_eq!(
+            found,
+            vec![
+                root.join("services/api/trop.local.yaml"),
+                root.join("services/api/trop.yaml"),
+                root.join("trop.yaml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_tropfiles_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let found = ConfigLoader::find_tropfiles(temp_dir.path()).unwrap();
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_load_json_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("trop.json");
+        fs::write(
+            &config_path,
+            r#"{"project": "json-project", "ports": {"min": 8000, "max": 8100}}"#,
+        )
+        .unwrap();
+
+        let config = ConfigLoader::load_file(&config_path).unwrap();
+        assert_eq!(config.project, Some("json-project".to_string()));
+        assert_eq!(config.ports.unwrap().min, 8000);
+    }
+
+    #[test]
+    fn test_load_invalid_json_names_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("trop.json");
+        fs::write(&config_path, "{\"project\": }").unwrap();
+
+        let err = ConfigLoader::load_file(&config_path).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Invalid JSON"), "{message}");
+        assert!(
+            message.contains(&config_path.display().to_string()),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_discover_json_only_project() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("trop.json"), r#"{"project": "json"}"#).unwrap();
+
+        let configs = ConfigLoader::discover_project_configs(temp_dir.path()).unwrap();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].precedence, 2);
+        assert!(configs[0].is_tropfile());
+        assert_eq!(configs[0].config.project, Some("json".to_string()));
+    }
+
+    #[test]
+    fn test_discover_json_local_override() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("trop.yaml"), "project: main\n").unwrap();
+        fs::write(
+            temp_dir.path().join("trop.local.json"),
+            r#"{"project": "local"}"#,
+        )
+        .unwrap();
+
+        let configs = ConfigLoader::discover_project_configs(temp_dir.path()).unwrap();
+        assert_eq!(configs.len(), 2);
+        assert!(configs[0].path.ends_with("trop.yaml"));
+        assert_eq!(configs[0].precedence, 2);
+        assert!(configs[1].path.ends_with("trop.local.json"));
+        assert_eq!(configs[1].precedence, 3);
+        assert_eq!(configs[1].config.project, Some("local".to_string()));
+    }
+
+    #[test]
+    fn test_discover_mixed_directory_prefers_yaml() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("trop.yaml"), "project: yaml\n").unwrap();
+        fs::write(temp_dir.path().join("trop.json"), r#"{"project": "json"}"#).unwrap();
+
+        let configs = ConfigLoader::discover_project_configs(temp_dir.path()).unwrap();
+        assert_eq!(configs.len(), 1);
+        assert!(configs[0].path.ends_with("trop.yaml"));
+        assert_eq!(configs[0].config.project, Some("yaml".to_string()));
+
+        // find_tropfiles still reports both so validation covers the JSON file
+        let found = ConfigLoader::find_tropfiles(temp_dir.path()).unwrap();
+        assert_eq!(
+            found,
+            vec![
+                temp_dir.path().join("trop.json"),
+                temp_dir.path().join("trop.yaml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_config_paths_without_loading() {
+        let temp_dir = TempDir::new().unwrap();
+        let child = temp_dir.path().join("child");
+        fs::create_dir(&child).unwrap();
+        fs::write(temp_dir.path().join("trop.json"), "not json").unwrap();
+        fs::write(temp_dir.path().join("trop.local.json"), "{}").unwrap();
+
+        // Unparseable files are still found, in increasing precedence
+        let paths = ConfigLoader::project_config_paths(&child);
+        assert_eq!(
+            paths,
+            [
+                temp_dir.path().join("trop.json"),
+                temp_dir.path().join("trop.local.json")
+            ]
+        );
+    }
+
     #[test]
     fn test_load_all_sorts_by_precedence() {
         let temp_dir = TempDir::new().unwrap();
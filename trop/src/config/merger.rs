@@ -4,7 +4,8 @@
 //! with special handling for accumulated fields like `excluded_ports`.
 
 use crate::config::loader::ConfigSource;
-use crate::config::schema::{CleanupConfig, Config, PortConfig};
+use crate::config::schema::{normalize_exclusions, CleanupConfig, Config, PortConfig};
+use crate::error::{Error, Result};
 
 /// Merges configuration sources according to precedence rules.
 ///
@@ -49,12 +50,25 @@ impl ConfigMerger {
     /// - Nested configs: field-by-field merge
     /// - Occupancy config: atomic replacement
     /// - Reservation groups: complete replacement
+    /// - Profiles: merged by name, each with these same rules
     pub fn merge_into(target: &mut Config, source: &Config) {
         // Simple fields - source overwrites if Some
         if source.project.is_some() {
             target.project.clone_from(&source.project);
         }
 
+        if source.default_tag.is_some() {
+            target.default_tag.clone_from(&source.default_tag);
+        }
+
+        if source.tag_case.is_some() {
+            target.tag_case = source.tag_case;
+        }
+
+        if source.allocation_strategy.is_some() {
+            target.allocation_strategy = source.allocation_strategy;
+        }
+
         if source.disable_autoinit.is_some() {
             target.disable_autoinit = source.disable_autoinit;
         }
@@ -87,10 +101,28 @@ impl ConfigMerger {
             target.maximum_lock_wait_seconds = source.maximum_lock_wait_seconds;
         }
 
+        if source.database_filename.is_some() {
+            target
+                .database_filename
+                .clone_from(&source.database_filename);
+        }
+
         if source.output_format.is_some() {
             target.output_format = source.output_format;
         }
 
+        if source.on_occupancy_error.is_some() {
+            target.on_occupancy_error = source.on_occupancy_error;
+        }
+
+        if source.pre_reserve_hook.is_some() {
+            target.pre_reserve_hook.clone_from(&source.pre_reserve_hook);
+        }
+
+        if source.pre_reserve_hook_timeout_seconds.is_some() {
+            target.pre_reserve_hook_timeout_seconds = source.pre_reserve_hook_timeout_seconds;
+        }
+
         // Merge ports config
         if let Some(ref source_ports) = source.ports {
             target.ports = Some(match &target.ports {
@@ -99,16 +131,11 @@ impl ConfigMerger {
             });
         }
 
-        // Merge excluded_ports (union of all exclusions)
+        // Merge excluded_ports (union of all exclusions, kept in canonical form)
         if let Some(ref source_excluded) = source.excluded_ports {
-            match &mut target.excluded_ports {
-                Some(target_excluded) => {
-                    target_excluded.extend(source_excluded.clone());
-                }
-                None => {
-                    target.excluded_ports.clone_from(&source.excluded_ports);
-                }
-            }
+            let target_excluded = target.excluded_ports.get_or_insert_with(Vec::new);
+            target_excluded.extend(source_excluded.iter().cloned());
+            normalize_exclusions(target_excluded);
         }
 
         // Merge cleanup config
@@ -128,6 +155,70 @@ impl ConfigMerger {
         if source.reservations.is_some() {
             target.reservations.clone_from(&source.reservations);
         }
+
+        // Profiles - same-named profiles merge field-by-field
+        if let Some(ref source_profiles) = source.profiles {
+            let target_profiles = target.profiles.get_or_insert_with(Default::default);
+            for (name, profile) in source_profiles {
+                Self::merge_into(target_profiles.entry(name.clone()).or_default(), profile);
+            }
+        }
+    }
+
+    /// Merge the named profile's overrides into the configuration.
+    ///
+    /// The profile is merged with the same rules as any other source, so its
+    /// exclusions are added to (not substituted for) the base exclusions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if no profile with that name is defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use trop::config::{Config, ConfigMerger};
+    ///
+    /// let ci = Config { project: Some("ci".to_string()), ..Default::default() };
+    /// let mut config = Config {
+    ///     profiles: Some(HashMap::from([("ci".to_string(), ci)])),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// ConfigMerger::apply_profile(&mut config, "ci").unwrap();
+    /// assert_eq!(config.project, Some("ci".to_string()));
+    /// assert!(ConfigMerger::apply_profile(&mut config, "staging").is_err());
+    /// ```
+    pub fn apply_profile(config: &mut Config, name: &str) -> Result<()> {
+        let Some(profile) = config
+            .profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(name))
+            .cloned()
+        else {
+            let mut available: Vec<&str> = config
+                .profiles
+                .iter()
+                .flat_map(|profiles| profiles.keys().map(String::as_str))
+                .collect();
+            available.sort_unstable();
+            let message = if available.is_empty() {
+                format!("Unknown profile '{name}': no profiles are defined")
+            } else {
+                format!(
+                    "Unknown profile '{name}' (available: {})",
+                    available.join(", ")
+                )
+            };
+            return Err(Error::Validation {
+                field: "profile".into(),
+                message,
+            });
+        };
+
+        Self::merge_into(config, &profile);
+        Ok(())
     }
 
     /// Merge port configuration.
@@ -157,6 +248,7 @@ impl ConfigMerger {
     fn merge_cleanup(target: &CleanupConfig, source: &CleanupConfig) -> CleanupConfig {
         CleanupConfig {
             expire_after_days: source.expire_after_days.or(target.expire_after_days),
+            protect_priority: source.protect_priority.or(target.protect_priority),
         }
     }
 }
@@ -219,6 +311,47 @@ mod tests {
         assert_eq!(target.excluded_ports.as_ref().unwrap().len(), 2);
     }
 
+    #[test]
+    fn test_merge_excluded_ports_normalizes_equivalent_forms() {
+        let mut target = Config {
+            excluded_ports: Some(vec![
+                PortExclusion::Range {
+                    start: 5010,
+                    end: 5020,
+                },
+                PortExclusion::Range {
+                    start: 5000,
+                    end: 5000,
+                },
+            ]),
+            ..Default::default()
+        };
+        let source = Config {
+            excluded_ports: Some(vec![
+                PortExclusion::Single(5000),
+                PortExclusion::Range {
+                    start: 5010,
+                    end: 5020,
+                },
+                PortExclusion::Single(5005),
+            ]),
+            ..Default::default()
+        };
+
+        ConfigMerger::merge_into(&mut target, &source);
+        assert_eq!(
+            target.excluded_ports.unwrap(),
+            vec![
+                PortExclusion::Single(5000),
+                PortExclusion::Single(5005),
+                PortExclusion::Range {
+                    start: 5010,
+                    end: 5020,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_merge_port_config() {
         let mut target = Config {
@@ -302,12 +435,14 @@ mod tests {
         let mut target = Config {
             cleanup: Some(CleanupConfig {
                 expire_after_days: Some(30),
+                protect_priority: None,
             }),
             ..Default::default()
         };
         let source = Config {
             cleanup: Some(CleanupConfig {
                 expire_after_days: Some(60),
+                protect_priority: None,
             }),
             ..Default::default()
         };
@@ -327,6 +462,7 @@ mod tests {
                 skip_tcp: Some(false),
                 skip_udp: Some(false),
                 check_all_interfaces: Some(false),
+                interfaces: None,
             }),
             ..Default::default()
         };
@@ -338,6 +474,7 @@ mod tests {
                 skip_tcp: None,
                 skip_udp: None,
                 check_all_interfaces: None,
+                interfaces: None,
             }),
             ..Default::default()
         };
@@ -399,6 +536,112 @@ mod tests {
         assert_eq!(target.project, Some("existing".to_string()));
         assert_eq!(target.disable_autoinit, Some(true));
     }
+
+    #[test]
+    fn test_merge_profiles_by_name() {
+        let low = Config {
+            profiles: Some(
+                [
+                    (
+                        "ci".to_string(),
+                        Config {
+                            maximum_lock_wait_seconds: Some(30),
+                            disable_autoprune: Some(true),
+                            ..Default::default()
+                        },
+                    ),
+                    ("dev".to_string(), Config::default()),
+                ]
+                .into(),
+            ),
+            ..Default::default()
+        };
+        let high = Config {
+            profiles: Some(
+                [(
+                    "ci".to_string(),
+                    Config {
+                        maximum_lock_wait_seconds: Some(60),
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+            ),
+            ..Default::default()
+        };
+
+        let result = ConfigMerger::merge(vec![make_source(1, low), make_source(2, high)]);
+        let profiles = result.profiles.unwrap();
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles["ci"].maximum_lock_wait_seconds, Some(60));
+        assert_eq!(profiles["ci"].disable_autoprune, Some(true));
+    }
+
+    #[test]
+    fn test_apply_profile_overrides_base() {
+        let mut config = Config {
+            ports: Some(PortConfig {
+                min: 5000,
+                max: Some(7000),
+                max_offset: None,
+            }),
+            excluded_ports: Some(vec![PortExclusion::Single(5001)]),
+            disable_autoexpire: Some(false),
+            profiles: Some(
+                [(
+                    "staging".to_string(),
+                    Config {
+                        ports: Some(PortConfig {
+                            min: 8000,
+                            max: Some(8100),
+                            max_offset: None,
+                        }),
+                        excluded_ports: Some(vec![PortExclusion::Single(8050)]),
+                        ..Default::default()
+                    },
+                )]
+                .into(),
+            ),
+            ..Default::default()
+        };
+
+        ConfigMerger::apply_profile(&mut config, "staging").unwrap();
+        let ports = config.ports.as_ref().unwrap();
+        assert_eq!((ports.min, ports.max), (8000, Some(8100)));
+        assert_eq!(
+            config.excluded_ports,
+            Some(vec![
+                PortExclusion::Single(5001),
+                PortExclusion::Single(8050)
+            ])
+        );
+        assert_eq!(config.disable_autoexpire, Some(false));
+    }
+
+    #[test]
+    fn test_apply_unknown_profile_fails() {
+        let mut config = Config {
+            profiles: Some(
+                [
+                    ("dev".to_string(), Config::default()),
+                    ("ci".to_string(), Config::default()),
+                ]
+                .into(),
+            ),
+            ..Default::default()
+        };
+
+        let err = ConfigMerger::apply_profile(&mut config, "prod")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown profile 'prod'"), "{err}");
+        assert!(err.contains("available: ci, dev"), "{err}");
+
+        let err = ConfigMerger::apply_profile(&mut Config::default(), "prod")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no profiles are defined"), "{err}");
+    }
 }
 
 // Property-based tests for configuration merging
@@ -553,6 +796,7 @@ mod property_tests {
     ///
     /// Mathematical Property: excluded_ports is accumulated, not replaced:
     /// merge(c1, c2).excluded_ports = c1.excluded_ports ∪ c2.excluded_ports
+    /// (as a set: duplicates are removed by normalization)
     ///
     /// WHY THIS MATTERS: Port exclusions from all config sources should be combined,
     /// not replaced. This ensures all exclusions are respected regardless of source.
@@ -578,14 +822,12 @@ mod property_tests {
                 ..Default::default()
             };
 
-            let original_count = target.excluded_ports.as_ref().unwrap().len();
-            let source_count = source.excluded_ports.as_ref().unwrap().len();
-
             ConfigMerger::merge_into(&mut target, &source);
 
-            // Should have sum of both lists
+            // Should have the union of both lists (duplicates removed)
+            let distinct: std::collections::BTreeSet<u16> = [port1, port2, port3].into();
             let final_count = target.excluded_ports.as_ref().unwrap().len();
-            prop_assert_eq!(final_count, original_count + source_count, "Exclusions accumulated");
+            prop_assert_eq!(final_count, distinct.len(), "Exclusions accumulated");
 
             // Should contain all original exclusions
             let excluded = target.excluded_ports.as_ref().unwrap();
@@ -616,7 +858,8 @@ mod property_tests {
             }
 
             let final_exclusions = result.excluded_ports.as_ref().unwrap();
-            prop_assert_eq!(final_exclusions.len(), ports.len(), "All exclusions accumulated");
+            let distinct: std::collections::BTreeSet<u16> = ports.iter().copied().collect();
+            prop_assert_eq!(final_exclusions.len(), distinct.len(), "All exclusions accumulated");
 
             // Verify all ports are present
             for port in ports {
@@ -728,6 +971,7 @@ mod property_tests {
             let mut target = Config {
                 cleanup: Some(CleanupConfig {
                     expire_after_days: Some(target_days),
+                    protect_priority: None,
                 }),
                 ..Default::default()
             };
@@ -735,6 +979,7 @@ mod property_tests {
             let source = Config {
                 cleanup: Some(CleanupConfig {
                     expire_after_days: source_days_opt,
+                    protect_priority: None,
                 }),
                 ..Default::default()
             };
@@ -786,6 +1031,7 @@ mod property_tests {
                     skip_tcp: Some(false),
                     skip_udp: Some(true),
                     check_all_interfaces: Some(false),
+                    interfaces: None,
                 }),
                 ..Default::default()
             };
@@ -799,6 +1045,7 @@ mod property_tests {
                     skip_tcp: None,
                     skip_udp: None,
                     check_all_interfaces: None,
+                    interfaces: None,
                 }),
                 ..Default::default()
             };
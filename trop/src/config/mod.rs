@@ -82,12 +82,14 @@ pub mod validator;
 mod proptests;
 
 // Re-export key types at module root
-pub use builder::ConfigBuilder;
+pub use builder::{ConfigBuilder, ConfigOrigin, ConfigProvenance, PortRangeOverrides};
 pub use environment::EnvironmentConfig;
 pub use loader::{ConfigLoader, ConfigSource};
 pub use merger::ConfigMerger;
 pub use schema::{
-    CleanupConfig, Config, OccupancyConfig, OutputFormat, PortConfig, PortExclusion,
-    ReservationGroup, ServiceDefinition, DEFAULT_MAX_PORT, DEFAULT_MIN_PORT,
+    compact_exclusions, merge_exclusion_reasons, normalize_exclusions, CleanupConfig, Config,
+    OccupancyConfig, OccupancyErrorPolicy, OutputFormat, PortConfig, PortExclusion,
+    ReservationGroup, ReservationGroups, ServiceDefinition, TagCase, DEFAULT_MAX_PORT,
+    DEFAULT_MIN_PORT,
 };
 pub use validator::ConfigValidator;
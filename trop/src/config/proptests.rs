@@ -191,6 +191,7 @@ proptest! {
     fn cleanup_config_valid_days(days in prop::option::of(1u32..=365)) {
         let config = CleanupConfig {
             expire_after_days: days,
+            protect_priority: None,
         };
 
         if let Some(d) = config.expire_after_days {
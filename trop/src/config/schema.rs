@@ -3,8 +3,12 @@
 //! This module defines the complete configuration structure for trop,
 //! including all settings for ports, exclusions, cleanup, and reservation groups.
 
+use crate::error::Error;
+use crate::operations::AllocationStrategy;
+use crate::port::{Port, PortRange};
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
 
 /// Default minimum port for port allocation ranges.
 pub const DEFAULT_MIN_PORT: u16 = 5000;
@@ -39,9 +43,18 @@ pub struct Config {
     /// Project identifier (only valid in trop.yaml files).
     pub project: Option<String>,
 
+    /// Tag applied by `reserve` when no `--tag` is given.
+    pub default_tag: Option<String>,
+
+    /// Whether tags are case-sensitive when forming reservation keys.
+    pub tag_case: Option<TagCase>,
+
     /// Port allocation settings.
     pub ports: Option<PortConfig>,
 
+    /// Order in which automatic allocation tries ports (default: lowest).
+    pub allocation_strategy: Option<AllocationStrategy>,
+
     /// Excluded ports list.
     #[serde(default)]
     pub excluded_ports: Option<Vec<PortExclusion>>,
@@ -52,8 +65,11 @@ pub struct Config {
     /// Occupancy check settings.
     pub occupancy_check: Option<OccupancyConfig>,
 
+    /// How to treat ports whose occupancy check itself fails.
+    pub on_occupancy_error: Option<OccupancyErrorPolicy>,
+
     /// Batch reservation groups (only valid in trop.yaml files).
-    pub reservations: Option<ReservationGroup>,
+    pub reservations: Option<ReservationGroups>,
 
     /// Disable automatic database initialization.
     pub disable_autoinit: Option<bool>,
@@ -79,8 +95,58 @@ pub struct Config {
     /// Maximum time to wait for database lock acquisition (seconds).
     pub maximum_lock_wait_seconds: Option<u64>,
 
+    /// File name of the database inside the data directory.
+    pub database_filename: Option<String>,
+
     /// Output format for list commands.
     pub output_format: Option<OutputFormat>,
+
+    /// External command run before committing a new reservation (user config only).
+    pub pre_reserve_hook: Option<String>,
+
+    /// Maximum time to wait for the pre-reserve hook to finish (seconds).
+    pub pre_reserve_hook_timeout_seconds: Option<u64>,
+
+    /// Named overrides selected with `--profile` or `TROP_PROFILE`.
+    ///
+    /// Each profile uses the same schema as the top level; when selected, its
+    /// fields are merged over the file-based configuration.
+    pub profiles: Option<HashMap<String, Config>>,
+}
+
+impl Config {
+    /// Resolves the port range allocations are drawn from.
+    ///
+    /// See [`PortConfig::effective_range`] for how `max` and `max_offset`
+    /// are resolved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no port configuration is present or it does not
+    /// describe a valid range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::config::{Config, PortConfig};
+    ///
+    /// let config = Config {
+    ///     ports: Some(PortConfig { min: 8000, max: None, max_offset: Some(99) }),
+    ///     ..Default::default()
+    /// };
+    /// let range = config.effective_port_range().unwrap();
+    /// assert_eq!(range.min().value(), 8000);
+    /// assert_eq!(range.max().value(), 8099);
+    /// ```
+    pub fn effective_port_range(&self) -> crate::Result<PortRange> {
+        self.ports
+            .as_ref()
+            .ok_or_else(|| Error::Validation {
+                field: "ports".into(),
+                message: "Port configuration is required".into(),
+            })?
+            .effective_range()
+    }
 }
 
 /// Port range configuration.
@@ -122,9 +188,58 @@ impl Default for PortConfig {
     }
 }
 
+impl PortConfig {
+    /// Resolves the inclusive upper bound of the range.
+    ///
+    /// Uses `max` when set, otherwise `min + max_offset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if both or neither of `max` and `max_offset` are
+    /// set, or if `min + max_offset` overflows.
+    pub fn effective_max(&self) -> crate::Result<u16> {
+        match (self.max, self.max_offset) {
+            (Some(_), Some(_)) => Err(Error::Validation {
+                field: "ports".into(),
+                message: "Cannot specify both max and max_offset".into(),
+            }),
+            (Some(max), None) => Ok(max),
+            (None, Some(offset)) => self
+                .min
+                .checked_add(offset)
+                .ok_or_else(|| Error::Validation {
+                    field: "ports.max_offset".into(),
+                    message: format!(
+                        "Offset {offset} would overflow when added to min port {}",
+                        self.min
+                    ),
+                }),
+            (None, None) => Err(Error::Validation {
+                field: "ports".into(),
+                message: "Either max or max_offset must be specified".into(),
+            }),
+        }
+    }
+
+    /// Resolves the configured bounds into a validated `PortRange`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the upper bound cannot be resolved (see
+    /// [`PortConfig::effective_max`]), either bound is not a valid port, or
+    /// `max` is below `min`.
+    pub fn effective_range(&self) -> crate::Result<PortRange> {
+        let min = Port::try_from(self.min)?;
+        let max = Port::try_from(self.effective_max()?)?;
+        Ok(PortRange::new(min, max)?)
+    }
+}
+
 /// Port exclusion (single port or range).
 ///
-/// Supports both individual ports and inclusive ranges.
+/// Supports both individual ports and inclusive ranges, optionally annotated
+/// with the reason the ports are excluded. Described exclusions are written
+/// as `{ port: 5001, reason: "..." }` or `{ start: 5005, end: 5009, reason: "..." }`.
 ///
 /// # Examples
 ///
@@ -133,6 +248,11 @@ impl Default for PortConfig {
 ///
 /// let single = PortExclusion::Single(5001);
 /// let range = PortExclusion::Range { start: 5005, end: 5009 };
+/// let described = PortExclusion::DescribedSingle {
+///     port: 5002,
+///     reason: "corporate VPN uses this".to_string(),
+/// };
+/// assert_eq!(described.reason(), Some("corporate VPN uses this"));
 /// ```
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 #[serde(untagged)]
@@ -146,6 +266,240 @@ pub enum PortExclusion {
         /// End of the range (inclusive).
         end: u16,
     },
+    /// A single excluded port with the reason it is excluded.
+    DescribedSingle {
+        /// The excluded port.
+        port: u16,
+        /// Why the port is excluded.
+        reason: String,
+    },
+    /// An inclusive range of excluded ports with the reason it is excluded.
+    DescribedRange {
+        /// Start of the range (inclusive).
+        start: u16,
+        /// End of the range (inclusive).
+        end: u16,
+        /// Why the range is excluded.
+        reason: String,
+    },
+}
+
+impl PortExclusion {
+    /// Builds the canonical exclusion for inclusive `start..=end` bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::config::PortExclusion;
+    ///
+    /// assert_eq!(PortExclusion::from_bounds(5000, 5000, None), PortExclusion::Single(5000));
+    /// assert_eq!(
+    ///     PortExclusion::from_bounds(5000, 5009, None),
+    ///     PortExclusion::Range { start: 5000, end: 5009 }
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_bounds(start: u16, end: u16, reason: Option<String>) -> Self {
+        match reason {
+            None if start == end => Self::Single(start),
+            None => Self::Range { start, end },
+            Some(reason) if start == end => Self::DescribedSingle {
+                port: start,
+                reason,
+            },
+            Some(reason) => Self::DescribedRange { start, end, reason },
+        }
+    }
+
+    /// Returns the canonical form of this exclusion.
+    ///
+    /// A range covering exactly one port (e.g. `5000..5000`) collapses to
+    /// `Single`; everything else is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::config::PortExclusion;
+    ///
+    /// let range = PortExclusion::Range { start: 5000, end: 5000 };
+    /// assert_eq!(range.normalized(), PortExclusion::Single(5000));
+    /// ```
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        let (start, end) = self.bounds();
+        Self::from_bounds(start, end, self.reason().map(str::to_string))
+    }
+
+    /// Returns the inclusive `(start, end)` bounds of this exclusion.
+    #[must_use]
+    pub const fn bounds(&self) -> (u16, u16) {
+        match *self {
+            Self::Single(port) | Self::DescribedSingle { port, .. } => (port, port),
+            Self::Range { start, end } | Self::DescribedRange { start, end, .. } => (start, end),
+        }
+    }
+
+    /// Returns whether `port` falls within this exclusion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::config::PortExclusion;
+    ///
+    /// let range = PortExclusion::Range { start: 5000, end: 5009 };
+    /// assert!(range.contains(5000));
+    /// assert!(range.contains(5009));
+    /// assert!(!range.contains(5010));
+    /// ```
+    #[must_use]
+    pub const fn contains(&self, port: u16) -> bool {
+        let (start, end) = self.bounds();
+        start <= port && port <= end
+    }
+
+    /// Returns the recorded reason for this exclusion, if any.
+    #[must_use]
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Single(_) | Self::Range { .. } => None,
+            Self::DescribedSingle { reason, .. } | Self::DescribedRange { reason, .. } => {
+                Some(reason)
+            }
+        }
+    }
+
+    /// Returns this exclusion with its reason replaced by `reason`.
+    #[must_use]
+    pub fn with_reason(&self, reason: Option<String>) -> Self {
+        let (start, end) = self.bounds();
+        Self::from_bounds(start, end, reason)
+    }
+}
+
+/// Combines the reasons of two exclusions being merged into one.
+///
+/// Distinct reasons are kept and joined with `"; "`; a reason already
+/// present is not repeated.
+///
+/// # Examples
+///
+/// ```
+/// use trop::config::merge_exclusion_reasons;
+///
+/// assert_eq!(merge_exclusion_reasons(Some("vpn"), None), Some("vpn".to_string()));
+/// assert_eq!(merge_exclusion_reasons(Some("vpn"), Some("vpn")), Some("vpn".to_string()));
+/// assert_eq!(merge_exclusion_reasons(Some("vpn"), Some("db")), Some("vpn; db".to_string()));
+/// ```
+#[must_use]
+pub fn merge_exclusion_reasons(first: Option<&str>, second: Option<&str>) -> Option<String> {
+    match (first, second) {
+        (None, None) => None,
+        (Some(reason), None) | (None, Some(reason)) => Some(reason.to_string()),
+        (Some(first), Some(second)) => {
+            if first.split("; ").any(|existing| existing == second) {
+                Some(first.to_string())
+            } else {
+                Some(format!("{first}; {second}"))
+            }
+        }
+    }
+}
+
+/// Normalizes an exclusion list into canonical form.
+///
+/// Collapses single-port ranges to `Single`, sorts by bounds, and merges
+/// entries with identical bounds (combining their reasons), so equivalent
+/// lists compare equal regardless of how they were written. Overlapping or
+/// adjacent entries are left as-is; use [`compact_exclusions`] to merge them.
+///
+/// # Examples
+///
+/// ```
+/// use trop::config::{normalize_exclusions, PortExclusion};
+///
+/// let mut exclusions = vec![
+///     PortExclusion::Range { start: 5005, end: 5009 },
+///     PortExclusion::Range { start: 5000, end: 5000 },
+///     PortExclusion::Single(5000),
+/// ];
+/// normalize_exclusions(&mut exclusions);
+/// assert_eq!(
+///     exclusions,
+///     vec![
+///         PortExclusion::Single(5000),
+///         PortExclusion::Range { start: 5005, end: 5009 },
+///     ]
+/// );
+/// ```
+pub fn normalize_exclusions(exclusions: &mut Vec<PortExclusion>) {
+    exclusions.sort_by_key(PortExclusion::bounds);
+
+    let mut merged: Vec<PortExclusion> = Vec::with_capacity(exclusions.len());
+    for exclusion in exclusions.drain(..) {
+        match merged.last_mut() {
+            Some(last) if last.bounds() == exclusion.bounds() => {
+                *last =
+                    last.with_reason(merge_exclusion_reasons(last.reason(), exclusion.reason()));
+            }
+            _ => merged.push(exclusion.normalized()),
+        }
+    }
+    *exclusions = merged;
+}
+
+/// Compacts an exclusion list to its minimal representation.
+///
+/// Overlapping and adjacent exclusions are coalesced into a single range.
+/// Reasons of coalesced exclusions are kept (joined with `"; "`).
+///
+/// # Examples
+///
+/// ```
+/// use trop::config::{compact_exclusions, PortExclusion};
+///
+/// let exclusions = vec![
+///     PortExclusion::Single(5000),
+///     PortExclusion::Range { start: 5001, end: 5005 },
+///     PortExclusion::Range { start: 5003, end: 5009 },
+/// ];
+/// assert_eq!(
+///     compact_exclusions(&exclusions),
+///     vec![PortExclusion::Range { start: 5000, end: 5009 }]
+/// );
+/// ```
+#[must_use]
+pub fn compact_exclusions(exclusions: &[PortExclusion]) -> Vec<PortExclusion> {
+    let mut sorted: Vec<&PortExclusion> = exclusions.iter().collect();
+    sorted.sort_by_key(|e| e.bounds());
+
+    // Sweep in order of start port, extending the current range while the
+    // next exclusion overlaps or touches it
+    let mut result = Vec::new();
+    let mut current: Option<(u16, u16, Option<String>)> = None;
+
+    for exclusion in sorted {
+        let (start, end) = exclusion.bounds();
+        current = match current {
+            Some((cur_start, cur_end, reason)) if u32::from(start) <= u32::from(cur_end) + 1 => {
+                let reason = merge_exclusion_reasons(reason.as_deref(), exclusion.reason());
+                Some((cur_start, cur_end.max(end), reason))
+            }
+            previous => {
+                if let Some((cur_start, cur_end, reason)) = previous {
+                    result.push(PortExclusion::from_bounds(cur_start, cur_end, reason));
+                }
+                Some((start, end, exclusion.reason().map(str::to_string)))
+            }
+        };
+    }
+
+    // Save final range
+    if let Some((start, end, reason)) = current {
+        debug_assert!(start <= end, "start should never exceed end");
+        result.push(PortExclusion::from_bounds(start, end, reason));
+    }
+
+    result
 }
 
 impl<'de> Deserialize<'de> for PortExclusion {
@@ -159,13 +513,33 @@ impl<'de> Deserialize<'de> for PortExclusion {
         #[serde(untagged)]
         enum Helper {
             Single(u16),
-            Range { start: u16, end: u16 },
+            Range {
+                start: u16,
+                end: u16,
+                #[serde(default)]
+                reason: Option<String>,
+            },
+            Port {
+                port: u16,
+                #[serde(default)]
+                reason: Option<String>,
+            },
             String(String),
         }
 
         match Helper::deserialize(deserializer)? {
             Helper::Single(port) => Ok(PortExclusion::Single(port)),
-            Helper::Range { start, end } => Ok(PortExclusion::Range { start, end }),
+            Helper::Range {
+                start,
+                end,
+                reason: None,
+            } => Ok(PortExclusion::Range { start, end }),
+            Helper::Range {
+                start,
+                end,
+                reason: Some(reason),
+            } => Ok(PortExclusion::DescribedRange { start, end, reason }),
+            Helper::Port { port, reason } => Ok(PortExclusion::from_bounds(port, port, reason)),
             Helper::String(s) => {
                 // Parse "5000..5010" format
                 if let Some((start_str, end_str)) = s.split_once("..") {
@@ -200,6 +574,7 @@ impl<'de> Deserialize<'de> for PortExclusion {
 ///
 /// let config = CleanupConfig {
 ///     expire_after_days: Some(30),
+///     protect_priority: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
@@ -207,6 +582,9 @@ impl<'de> Deserialize<'de> for PortExclusion {
 pub struct CleanupConfig {
     /// Number of days after which unused reservations expire.
     pub expire_after_days: Option<u32>,
+    /// Reservations with a priority above this are kept by `expire` and
+    /// `autoclean` unless forced (`None` protects nothing).
+    pub protect_priority: Option<i32>,
 }
 
 /// Occupancy check configuration.
@@ -225,6 +603,7 @@ pub struct CleanupConfig {
 ///     skip_tcp: Some(false),
 ///     skip_udp: Some(false),
 ///     check_all_interfaces: Some(false),
+///     interfaces: Some(vec!["127.0.0.1".parse().unwrap()]),
 /// };
 /// ```
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
@@ -247,6 +626,94 @@ pub struct OccupancyConfig {
 
     /// Check all network interfaces (not just localhost).
     pub check_all_interfaces: Option<bool>,
+
+    /// Only report a port as occupied if it conflicts with a bind on one of
+    /// these addresses. Ignored when `check_all_interfaces` is set.
+    pub interfaces: Option<Vec<IpAddr>>,
+}
+
+/// Policy for ports whose occupancy check fails with an error.
+///
+/// An occupancy probe can fail for reasons unrelated to whether the port is
+/// actually in use (e.g. permission denied while binding). This policy decides
+/// how such a port is treated wherever occupancy is evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use trop::config::OccupancyErrorPolicy;
+///
+/// let policy: OccupancyErrorPolicy = serde_yaml::from_str("treat-as-free").unwrap();
+/// assert_eq!(policy, OccupancyErrorPolicy::TreatAsFree);
+/// assert_eq!(OccupancyErrorPolicy::default(), OccupancyErrorPolicy::TreatAsOccupied);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OccupancyErrorPolicy {
+    /// Treat the port as free (available for allocation).
+    TreatAsFree,
+    /// Treat the port as occupied (never hand out uncertain ports).
+    #[default]
+    TreatAsOccupied,
+    /// Propagate the error and abort the operation.
+    Fail,
+}
+
+impl std::fmt::Display for OccupancyErrorPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TreatAsFree => write!(f, "treat-as-free"),
+            Self::TreatAsOccupied => write!(f, "treat-as-occupied"),
+            Self::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// Case sensitivity of reservation tags.
+///
+/// With `insensitive`, tags are lowercased before the reservation key is
+/// formed, so `Web` and `web` name the same reservation. Switching an
+/// existing data directory to `insensitive` does not rewrite stored tags:
+/// reservations created earlier as `Web` and `web` remain separate rows, and
+/// only the lowercase one is reachable through the normalized key.
+///
+/// # Examples
+///
+/// ```
+/// use trop::config::TagCase;
+///
+/// let case: TagCase = serde_yaml::from_str("insensitive").unwrap();
+/// assert_eq!(case.normalize("Web".to_string()), "web");
+/// assert_eq!(TagCase::default().normalize("Web".to_string()), "Web");
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagCase {
+    /// Tags are compared exactly as given.
+    #[default]
+    Sensitive,
+    /// Tags are lowercased before use.
+    Insensitive,
+}
+
+impl TagCase {
+    /// Apply this case policy to a tag.
+    #[must_use]
+    pub fn normalize(self, tag: String) -> String {
+        match self {
+            Self::Sensitive => tag,
+            Self::Insensitive => tag.to_lowercase(),
+        }
+    }
+}
+
+impl std::fmt::Display for TagCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sensitive => write!(f, "sensitive"),
+            Self::Insensitive => write!(f, "insensitive"),
+        }
+    }
 }
 
 /// Reservation group definition.
@@ -282,6 +749,92 @@ pub struct ReservationGroup {
     pub services: HashMap<String, ServiceDefinition>,
 }
 
+/// The `reservations` section of a trop.yaml file.
+///
+/// Either a single unnamed group, or several groups keyed by name so that
+/// `reserve-group --group NAME` can pick one.
+///
+/// # Examples
+///
+/// ```
+/// use trop::config::ReservationGroups;
+///
+/// let groups: ReservationGroups = serde_yaml::from_str(
+///     "frontend:\n  services:\n    web: {}\nbackend:\n  services:\n    api: {}\n",
+/// )
+/// .unwrap();
+/// assert_eq!(groups.names(), vec!["backend", "frontend"]);
+/// assert!(groups.select(Some("frontend")).is_ok());
+/// assert!(groups.select(None).is_err());
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ReservationGroups {
+    /// A single unnamed group.
+    Single(ReservationGroup),
+    /// Groups keyed by name.
+    Named(BTreeMap<String, ReservationGroup>),
+}
+
+impl ReservationGroups {
+    /// Returns the names of the groups, or an empty list for a single
+    /// unnamed group.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            Self::Single(_) => Vec::new(),
+            Self::Named(groups) => groups.keys().map(String::as_str).collect(),
+        }
+    }
+
+    /// Picks the group to reserve.
+    ///
+    /// A single unnamed group is used when no name is given. Named groups
+    /// require a name unless there is only one of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `name` doesn't match a group, if a
+    /// name is given for a single unnamed group, or if several groups are
+    /// defined and no name is given.
+    pub fn select(&self, name: Option<&str>) -> Result<&ReservationGroup, Error> {
+        match (self, name) {
+            (Self::Single(group), None) => Ok(group),
+            (Self::Single(_), Some(name)) => Err(Error::Validation {
+                field: "reservations".to_string(),
+                message: format!(
+                    "Reservation group '{name}' not found: the configuration defines a single unnamed group"
+                ),
+            }),
+            (Self::Named(groups), Some(name)) => {
+                groups.get(name).ok_or_else(|| Error::Validation {
+                    field: "reservations".to_string(),
+                    message: format!(
+                        "Reservation group '{name}' not found (available: {})",
+                        self.names().join(", ")
+                    ),
+                })
+            }
+            (Self::Named(groups), None) => match groups.values().next() {
+                Some(group) if groups.len() == 1 => Ok(group),
+                _ => Err(Error::Validation {
+                    field: "reservations".to_string(),
+                    message: format!(
+                        "Configuration defines several reservation groups ({}); choose one with --group",
+                        self.names().join(", ")
+                    ),
+                }),
+            },
+        }
+    }
+}
+
+impl From<ReservationGroup> for ReservationGroups {
+    fn from(group: ReservationGroup) -> Self {
+        Self::Single(group)
+    }
+}
+
 /// Individual service definition in a reservation group.
 ///
 /// # Examples
@@ -379,6 +932,153 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_exclusions_mixed_forms() {
+        let mut a = vec![
+            PortExclusion::Single(5003),
+            PortExclusion::Range {
+                start: 5000,
+                end: 5000,
+            },
+            PortExclusion::Range {
+                start: 5001,
+                end: 5002,
+            },
+        ];
+        let mut b = vec![
+            PortExclusion::Range {
+                start: 5001,
+                end: 5002,
+            },
+            PortExclusion::Single(5000),
+            PortExclusion::Range {
+                start: 5003,
+                end: 5003,
+            },
+            PortExclusion::Single(5000),
+        ];
+
+        normalize_exclusions(&mut a);
+        normalize_exclusions(&mut b);
+
+        assert_eq!(a, b);
+        assert_eq!(
+            a,
+            vec![
+                PortExclusion::Single(5000),
+                PortExclusion::Range {
+                    start: 5001,
+                    end: 5002
+                },
+                PortExclusion::Single(5003),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_port_exclusion_with_reason() {
+        let single: PortExclusion =
+            serde_yaml::from_str("port: 5432\nreason: local postgres").unwrap();
+        assert_eq!(
+            single,
+            PortExclusion::DescribedSingle {
+                port: 5432,
+                reason: "local postgres".to_string()
+            }
+        );
+
+        let range: PortExclusion =
+            serde_yaml::from_str("start: 6000\nend: 6010\nreason: vpn").unwrap();
+        assert_eq!(
+            range,
+            PortExclusion::DescribedRange {
+                start: 6000,
+                end: 6010,
+                reason: "vpn".to_string()
+            }
+        );
+
+        // Round-trips through the documented object form
+        let yaml = serde_yaml::to_string(&range).unwrap();
+        assert!(yaml.contains("reason: vpn"));
+        let parsed: PortExclusion = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, range);
+    }
+
+    #[test]
+    fn test_normalize_exclusions_merges_reasons() {
+        let mut exclusions = vec![
+            PortExclusion::DescribedRange {
+                start: 5432,
+                end: 5432,
+                reason: "postgres".to_string(),
+            },
+            PortExclusion::Single(5432),
+            PortExclusion::DescribedSingle {
+                port: 5432,
+                reason: "pgbouncer".to_string(),
+            },
+        ];
+
+        normalize_exclusions(&mut exclusions);
+
+        assert_eq!(
+            exclusions,
+            vec![PortExclusion::DescribedSingle {
+                port: 5432,
+                reason: "postgres; pgbouncer".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_port_exclusion_contains() {
+        let single = PortExclusion::Single(5432);
+        assert!(single.contains(5432));
+        assert!(!single.contains(5431));
+        assert!(!single.contains(5433));
+
+        let range = PortExclusion::DescribedRange {
+            start: 5000,
+            end: 5009,
+            reason: "dev servers".to_string(),
+        };
+        assert!(range.contains(5000));
+        assert!(range.contains(5005));
+        assert!(range.contains(5009));
+        assert!(!range.contains(4999));
+        assert!(!range.contains(5010));
+    }
+
+    #[test]
+    fn test_compact_exclusions_merges_adjacent_and_overlapping() {
+        let exclusions = vec![
+            PortExclusion::Single(6000),
+            PortExclusion::Range {
+                start: 5005,
+                end: 5009,
+            },
+            PortExclusion::Single(5000),
+            PortExclusion::Range {
+                start: 5001,
+                end: 5006,
+            },
+            PortExclusion::Single(6002),
+        ];
+
+        assert_eq!(
+            compact_exclusions(&exclusions),
+            vec![
+                PortExclusion::Range {
+                    start: 5000,
+                    end: 5009
+                },
+                PortExclusion::Single(6000),
+                PortExclusion::Single(6002),
+            ]
+        );
+    }
+
     #[test]
     fn test_config_default() {
         let config = Config::default();
@@ -386,6 +1086,111 @@ mod tests {
         assert!(config.ports.is_none());
     }
 
+    #[test]
+    fn test_effective_port_range_uses_max() {
+        let ports = PortConfig {
+            min: 5000,
+            max: Some(5100),
+            max_offset: None,
+        };
+        let range = ports.effective_range().unwrap();
+        assert_eq!(range.min().value(), 5000);
+        assert_eq!(range.max().value(), 5100);
+    }
+
+    #[test]
+    fn test_effective_port_range_applies_max_offset() {
+        let ports = PortConfig {
+            min: 8000,
+            max: None,
+            max_offset: Some(25),
+        };
+        assert_eq!(ports.effective_max().unwrap(), 8025);
+
+        let range = ports.effective_range().unwrap();
+        assert_eq!(range.min().value(), 8000);
+        assert_eq!(range.max().value(), 8025);
+    }
+
+    #[test]
+    fn test_effective_port_range_offset_to_top_port() {
+        let ports = PortConfig {
+            min: 65000,
+            max: None,
+            max_offset: Some(535),
+        };
+        assert_eq!(ports.effective_range().unwrap().max().value(), 65535);
+    }
+
+    #[test]
+    fn test_effective_port_range_offset_overflow() {
+        let ports = PortConfig {
+            min: 65000,
+            max: None,
+            max_offset: Some(536),
+        };
+        assert!(matches!(
+            ports.effective_max(),
+            Err(Error::Validation { ref field, .. }) if field == "ports.max_offset"
+        ));
+    }
+
+    #[test]
+    fn test_effective_port_range_rejects_both_or_neither() {
+        let both = PortConfig {
+            min: 5000,
+            max: Some(6000),
+            max_offset: Some(10),
+        };
+        assert!(matches!(
+            both.effective_max(),
+            Err(Error::Validation { ref message, .. }) if message.contains("both")
+        ));
+
+        let neither = PortConfig {
+            min: 5000,
+            max: None,
+            max_offset: None,
+        };
+        assert!(matches!(
+            neither.effective_max(),
+            Err(Error::Validation { ref message, .. }) if message.contains("Either")
+        ));
+    }
+
+    #[test]
+    fn test_effective_port_range_rejects_invalid_bounds() {
+        let inverted = PortConfig {
+            min: 6000,
+            max: Some(5000),
+            max_offset: None,
+        };
+        assert!(inverted.effective_range().is_err());
+
+        let zero_min = PortConfig {
+            min: 0,
+            max: Some(5000),
+            max_offset: None,
+        };
+        assert!(zero_min.effective_range().is_err());
+    }
+
+    #[test]
+    fn test_config_effective_port_range() {
+        let config = Config {
+            ports: Some(PortConfig::default()),
+            ..Default::default()
+        };
+        let range = config.effective_port_range().unwrap();
+        assert_eq!(range.min().value(), DEFAULT_MIN_PORT);
+        assert_eq!(range.max().value(), DEFAULT_MAX_PORT);
+
+        assert!(matches!(
+            Config::default().effective_port_range(),
+            Err(Error::Validation { ref field, .. }) if field == "ports"
+        ));
+    }
+
     #[test]
     fn test_port_config_default() {
         let config = PortConfig::default();
@@ -423,6 +1228,28 @@ project: my-app
         assert_eq!(config.project, Some("my-app".to_string()));
     }
 
+    #[test]
+    fn test_reservations_single_and_named_forms() {
+        let single: Config =
+            serde_yaml::from_str("reservations:\n  base: 5000\n  services:\n    web: {}\n")
+                .unwrap();
+        assert!(matches!(
+            single.reservations,
+            Some(ReservationGroups::Single(ReservationGroup {
+                base: Some(5000),
+                ..
+            }))
+        ));
+
+        let named: Config = serde_yaml::from_str(
+            "reservations:\n  frontend:\n    services:\n      web: {}\n  backend:\n    base: 6000\n    services:\n      api: {}\n",
+        )
+        .unwrap();
+        let groups = named.reservations.unwrap();
+        assert_eq!(groups.names(), vec!["backend", "frontend"]);
+        assert_eq!(groups.select(Some("backend")).unwrap().base, Some(6000));
+    }
+
     #[test]
     fn test_complete_config() {
         let yaml = r#"
@@ -443,6 +1270,9 @@ occupancy_check:
   skip_tcp: false
   skip_udp: false
   check_all_interfaces: false
+  interfaces:
+    - 127.0.0.1
+    - "::1"
 ports:
   min: 5000
   max: 7000
@@ -457,6 +1287,9 @@ cleanup:
         assert_eq!(config.output_format, Some(OutputFormat::Json));
         assert!(config.ports.is_some());
         assert!(config.cleanup.is_some());
+        let interfaces = config.occupancy_check.unwrap().interfaces.unwrap();
+        assert_eq!(interfaces.len(), 2);
+        assert!(interfaces[1].is_ipv6());
     }
 }
 
@@ -494,7 +1327,7 @@ mod property_tests {
                         prop_assert_eq!(s, start, "Start value preserved");
                         prop_assert_eq!(e, end, "End value preserved");
                     }
-                    PortExclusion::Single(_) => prop_assert!(false, "Expected Range variant"),
+                    _ => prop_assert!(false, "Expected Range variant"),
                 }
             }
         }
@@ -553,7 +1386,7 @@ mod property_tests {
                     prop_assert_eq!(e, end, "End value parsed correctly");
                     prop_assert!(s <= e, "Parsed range is valid");
                 }
-                PortExclusion::Single(_) => prop_assert!(false, "String range should parse to Range variant"),
+                _ => prop_assert!(false, "String range should parse to Range variant"),
             }
         }
     }
@@ -682,6 +1515,7 @@ mod property_tests {
         fn prop_cleanup_config_roundtrip(expire_days in proptest::option::of(1u32..=1000)) {
             let cleanup = CleanupConfig {
                 expire_after_days: expire_days,
+                protect_priority: None,
             };
 
             let yaml = serde_yaml::to_string(&cleanup).unwrap();
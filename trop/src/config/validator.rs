@@ -3,7 +3,10 @@
 //! This module provides comprehensive validation for all configuration fields,
 //! ensuring that values are valid and consistent.
 
-use crate::config::schema::{CleanupConfig, Config, PortConfig, PortExclusion, ReservationGroup};
+use crate::config::schema::{
+    CleanupConfig, Config, PortConfig, PortExclusion, ReservationGroup, ReservationGroups,
+};
+use crate::database::validate_database_filename;
 use crate::error::{Error, Result};
 use crate::port::Port;
 use std::collections::HashSet;
@@ -39,6 +42,10 @@ impl ConfigValidator {
             Self::validate_identifier("project", project)?;
         }
 
+        if let Some(ref tag) = config.default_tag {
+            Self::validate_identifier("default_tag", tag)?;
+        }
+
         // Validate reservations (only in trop.yaml)
         if let Some(ref reservations) = config.reservations {
             if !is_tropfile {
@@ -47,7 +54,7 @@ impl ConfigValidator {
                     message: "reservations field is only valid in trop.yaml files".into(),
                 });
             }
-            Self::validate_reservation_group(reservations)?;
+            Self::validate_reservation_groups(reservations)?;
         }
 
         // Validate port configuration
@@ -75,6 +82,43 @@ impl ConfigValidator {
             }
         }
 
+        if let Some(ref name) = config.database_filename {
+            validate_database_filename(name)?;
+        }
+
+        // Validate pre-reserve hook
+        if let Some(ref hook) = config.pre_reserve_hook {
+            if hook.trim().is_empty() {
+                return Err(Error::Validation {
+                    field: "pre_reserve_hook".into(),
+                    message: "Hook command cannot be empty".into(),
+                });
+            }
+        }
+
+        if let Some(timeout) = config.pre_reserve_hook_timeout_seconds {
+            if timeout == 0 {
+                return Err(Error::Validation {
+                    field: "pre_reserve_hook_timeout_seconds".into(),
+                    message: "Timeout must be greater than 0".into(),
+                });
+            }
+        }
+
+        // Validate profiles (same rules as the top level, but no nesting)
+        if let Some(ref profiles) = config.profiles {
+            for (name, profile) in profiles {
+                Self::validate_identifier("profiles", name)?;
+                if profile.profiles.is_some() {
+                    return Err(Error::Validation {
+                        field: format!("profiles.{name}"),
+                        message: "Profiles cannot define nested profiles".into(),
+                    });
+                }
+                Self::validate(profile, is_tropfile)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -113,8 +157,13 @@ impl ConfigValidator {
     /// Validate port configuration.
     ///
     /// Ensures min and max are valid ports, max >= min, and that max and
-    /// `max_offset` are not both specified.
-    fn validate_port_config(config: &PortConfig) -> Result<()> {
+    /// `max_offset` are not both specified. Also used on their own to check
+    /// port ranges given as CLI overrides.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error describing the first problem found.
+    pub fn validate_port_config(config: &PortConfig) -> Result<()> {
         // Validate min port
         Port::try_from(config.min).map_err(|_| Error::Validation {
             field: "ports.min".into(),
@@ -176,14 +225,22 @@ impl ConfigValidator {
     /// Ensures all ports are valid and ranges are properly ordered.
     fn validate_excluded_ports(excluded: &[PortExclusion]) -> Result<()> {
         for (i, exclusion) in excluded.iter().enumerate() {
+            if exclusion.reason().is_some_and(|r| r.trim().is_empty()) {
+                return Err(Error::Validation {
+                    field: format!("excluded_ports[{i}].reason"),
+                    message: "Reason cannot be empty".into(),
+                });
+            }
+
             match exclusion {
-                PortExclusion::Single(port) => {
+                PortExclusion::Single(port) | PortExclusion::DescribedSingle { port, .. } => {
                     Port::try_from(*port).map_err(|_| Error::Validation {
                         field: format!("excluded_ports[{i}]"),
                         message: format!("Invalid port: {port}"),
                     })?;
                 }
-                PortExclusion::Range { start, end } => {
+                PortExclusion::Range { start, end }
+                | PortExclusion::DescribedRange { start, end, .. } => {
                     Port::try_from(*start).map_err(|_| Error::Validation {
                         field: format!("excluded_ports[{i}]"),
                         message: format!("Invalid start port: {start}"),
@@ -219,6 +276,36 @@ impl ConfigValidator {
         Ok(())
     }
 
+    /// Validate the `reservations` section.
+    ///
+    /// Named groups must have identifier names, and each group is held to
+    /// the single-group rules independently.
+    fn validate_reservation_groups(groups: &ReservationGroups) -> Result<()> {
+        let named = match groups {
+            ReservationGroups::Single(group) => return Self::validate_reservation_group(group),
+            ReservationGroups::Named(named) => named,
+        };
+        if named.is_empty() {
+            return Err(Error::Validation {
+                field: "reservations".into(),
+                message: "Reservations must define services or at least one named group".into(),
+            });
+        }
+
+        // Each group is validated on its own; errors name the group
+        for (name, group) in named {
+            Self::validate_identifier("reservations", name)?;
+            Self::validate_reservation_group(group).map_err(|e| match e {
+                Error::Validation { field, message } => Error::Validation {
+                    field: field.replacen("reservations", &format!("reservations.{name}"), 1),
+                    message,
+                },
+                other => other,
+            })?;
+        }
+        Ok(())
+    }
+
     /// Validate reservation group.
     ///
     /// Ensures all service tags are valid identifiers, offsets are unique,
@@ -367,6 +454,24 @@ mod tests {
         assert!(ConfigValidator::validate(&config, true).is_ok());
     }
 
+    #[test]
+    fn test_validate_default_tag() {
+        let config = Config {
+            default_tag: Some("web".to_string()),
+            ..Default::default()
+        };
+        assert!(ConfigValidator::validate(&config, true).is_ok());
+
+        let config = Config {
+            default_tag: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ConfigValidator::validate(&config, false),
+            Err(Error::Validation { ref field, .. }) if field == "default_tag"
+        ));
+    }
+
     #[test]
     fn test_validate_identifier_empty() {
         let result = ConfigValidator::validate_identifier("test", "");
@@ -479,6 +584,7 @@ mod tests {
     fn test_validate_cleanup_valid() {
         let cleanup = CleanupConfig {
             expire_after_days: Some(30),
+            protect_priority: None,
         };
         assert!(ConfigValidator::validate_cleanup(&cleanup).is_ok());
     }
@@ -487,6 +593,7 @@ mod tests {
     fn test_validate_cleanup_zero_days() {
         let cleanup = CleanupConfig {
             expire_after_days: Some(0),
+            protect_priority: None,
         };
         assert!(ConfigValidator::validate_cleanup(&cleanup).is_err());
     }
@@ -542,6 +649,40 @@ mod tests {
         assert!(ConfigValidator::validate_reservation_group(&group).is_ok());
     }
 
+    #[test]
+    fn test_validate_named_reservation_groups_independently() {
+        // Each group may reuse offsets and env names from another group
+        let yaml = r"
+reservations:
+  frontend:
+    services:
+      web: { offset: 0, env: PORT }
+  backend:
+    services:
+      api: { offset: 0, env: PORT }
+";
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert!(ConfigValidator::validate(&config, true).is_ok());
+
+        let yaml = r"
+reservations:
+  backend:
+    services:
+      api: { offset: 0 }
+      db: { offset: 0 }
+";
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        match ConfigValidator::validate(&config, true) {
+            Err(Error::Validation { field, .. }) => {
+                assert!(
+                    field.starts_with("reservations.backend.services."),
+                    "{field}"
+                );
+            }
+            other => panic!("Expected validation error, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_validate_reservation_group_duplicate_offset() {
         let mut services = HashMap::new();
@@ -653,6 +794,30 @@ mod tests {
 
         assert!(ConfigValidator::validate_reservation_group(&group).is_err());
     }
+
+    #[test]
+    fn test_validate_profiles() {
+        let profile = Config {
+            ports: Some(PortConfig {
+                min: 6000,
+                max: Some(5000),
+                max_offset: None,
+            }),
+            ..Default::default()
+        };
+        let config = Config {
+            profiles: Some(HashMap::from([("ci".to_string(), profile)])),
+            ..Default::default()
+        };
+        assert!(ConfigValidator::validate(&config, false).is_err());
+
+        let nested = Config {
+            profiles: Some(HashMap::from([("ci".to_string(), config)])),
+            ..Default::default()
+        };
+        let err = ConfigValidator::validate(&nested, false).unwrap_err();
+        assert!(err.to_string().contains("nested profiles"), "{err}");
+    }
 }
 
 // Property-based tests for configuration validation
@@ -975,6 +1140,7 @@ mod property_tests {
         fn prop_positive_expire_days_accepted(days in 1u32..=10000) {
             let cleanup = CleanupConfig {
                 expire_after_days: Some(days),
+                protect_priority: None,
             };
 
             let result = ConfigValidator::validate_cleanup(&cleanup);
@@ -990,6 +1156,7 @@ mod property_tests {
         fn prop_zero_expire_days_rejected(_dummy in any::<u8>()) {
             let cleanup = CleanupConfig {
                 expire_after_days: Some(0),
+                protect_priority: None,
             };
 
             let result = ConfigValidator::validate_cleanup(&cleanup);
@@ -1005,6 +1172,7 @@ mod property_tests {
         fn prop_none_expire_days_accepted(_dummy in any::<u8>()) {
             let cleanup = CleanupConfig {
                 expire_after_days: None,
+                protect_priority: None,
             };
 
             let result = ConfigValidator::validate_cleanup(&cleanup);
@@ -7,6 +7,7 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::error::{Error, Result};
+use crate::path::normalize::expand_path;
 
 /// Configuration for database connections.
 ///
@@ -113,7 +114,8 @@ impl DatabaseConfig {
 /// Returns the default data directory for trop.
 ///
 /// Resolution order:
-/// 1. `$TROP_DATA_DIR` if set
+/// 1. `$TROP_DATA_DIR` if set, with `~` and environment variable
+///    references expanded
 /// 2. `~/.trop` on Unix-like systems or `%USERPROFILE%\.trop` on Windows
 ///
 /// This directory contains the database file, config file, and other
@@ -122,7 +124,7 @@ impl DatabaseConfig {
 /// # Errors
 ///
 /// Returns an error if the home directory cannot be determined and
-/// `TROP_DATA_DIR` is not set.
+/// `TROP_DATA_DIR` is not set, or if `TROP_DATA_DIR` cannot be expanded.
 ///
 /// # Examples
 ///
@@ -135,7 +137,7 @@ impl DatabaseConfig {
 pub fn default_data_dir() -> Result<PathBuf> {
     // Check TROP_DATA_DIR first
     if let Ok(data_dir) = std::env::var("TROP_DATA_DIR") {
-        return Ok(PathBuf::from(data_dir));
+        return expand_path(Path::new(&data_dir));
     }
 
     // Fall back to ~/.trop
@@ -148,16 +150,58 @@ pub fn default_data_dir() -> Result<PathBuf> {
     Ok(PathBuf::from(home).join(".trop"))
 }
 
+/// File name of the database inside the data directory, unless overridden.
+pub const DEFAULT_DATABASE_FILENAME: &str = "trop.db";
+
+/// Validates a database file name.
+///
+/// The name must be a single path component: non-empty, not `.` or `..`,
+/// and free of path separators, so the database always lives directly in
+/// the data directory.
+///
+/// # Errors
+///
+/// Returns a validation error describing why the name is not usable.
+///
+/// # Examples
+///
+/// ```
+/// use trop::database::validate_database_filename;
+///
+/// assert!(validate_database_filename("staging.db").is_ok());
+/// assert!(validate_database_filename("../trop.db").is_err());
+/// ```
+pub fn validate_database_filename(name: &str) -> Result<()> {
+    let problem = if name.trim().is_empty() {
+        Some("Database file name cannot be empty")
+    } else if name.contains('/') || name.contains('\\') {
+        Some("Database file name cannot contain path separators")
+    } else if name == "." || name == ".." {
+        Some("Database file name must name a file")
+    } else {
+        None
+    };
+
+    match problem {
+        Some(message) => Err(Error::Validation {
+            field: "database_filename".into(),
+            message: format!("{message}: {name:?}"),
+        }),
+        None => Ok(()),
+    }
+}
+
 /// Resolves the database path using environment variables or defaults.
 ///
-/// The resolution order is:
-/// 1. `$TROP_DATA_DIR/trop.db` if the `TROP_DATA_DIR` environment variable is set
-/// 2. `~/.trop/trop.db` otherwise
+/// The data directory is resolved by [`default_data_dir`];
+/// the file name is `$TROP_DB_NAME` if set and [`DEFAULT_DATABASE_FILENAME`]
+/// otherwise.
 ///
 /// # Errors
 ///
 /// Returns an error if the home directory cannot be determined and
-/// `TROP_DATA_DIR` is not set.
+/// `TROP_DATA_DIR` is not set, or if `TROP_DB_NAME` is not a valid file
+/// name (see [`validate_database_filename`]).
 ///
 /// # Examples
 ///
@@ -168,10 +212,14 @@ pub fn default_data_dir() -> Result<PathBuf> {
 /// println!("Database path: {}", db_path.display());
 /// ```
 pub fn resolve_database_path() -> Result<PathBuf> {
-    if let Ok(data_dir) = std::env::var("TROP_DATA_DIR") {
-        Ok(PathBuf::from(data_dir).join("trop.db"))
-    } else {
-        Ok(default_data_dir()?.join("trop.db"))
+    let data_dir = default_data_dir()?;
+
+    match std::env::var("TROP_DB_NAME") {
+        Ok(name) => {
+            validate_database_filename(&name)?;
+            Ok(data_dir.join(name))
+        }
+        Err(_) => Ok(data_dir.join(DEFAULT_DATABASE_FILENAME)),
     }
 }
 
@@ -219,6 +267,7 @@ mod tests {
     fn test_resolve_database_path() {
         // Test with default (no TROP_DATA_DIR set)
         std::env::remove_var("TROP_DATA_DIR");
+        std::env::remove_var("TROP_DB_NAME");
         let result = resolve_database_path();
         if std::env::var("HOME").is_ok() || std::env::var("USERPROFILE").is_ok() {
             let path = result.unwrap();
@@ -230,8 +279,39 @@ mod tests {
         let path = resolve_database_path().unwrap();
         assert_eq!(path, PathBuf::from("/custom/data/trop.db"));
 
+        // Test with TROP_DB_NAME set
+        std::env::set_var("TROP_DB_NAME", "staging.db");
+        let path = resolve_database_path().unwrap();
+        assert_eq!(path, PathBuf::from("/custom/data/staging.db"));
+
         // Clean up
         std::env::remove_var("TROP_DATA_DIR");
+        std::env::remove_var("TROP_DB_NAME");
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_database_path_rejects_separator() {
+        std::env::set_var("TROP_DATA_DIR", "/custom/data");
+        std::env::set_var("TROP_DB_NAME", "nested/trop.db");
+        let result = resolve_database_path();
+        std::env::remove_var("TROP_DATA_DIR");
+        std::env::remove_var("TROP_DB_NAME");
+
+        assert!(matches!(
+            result,
+            Err(Error::Validation { ref field, .. }) if field == "database_filename"
+        ));
+    }
+
+    #[test]
+    fn test_validate_database_filename() {
+        assert!(validate_database_filename("trop.db").is_ok());
+        assert!(validate_database_filename("instance-b.sqlite").is_ok());
+        assert!(validate_database_filename("").is_err());
+        assert!(validate_database_filename("a/b.db").is_err());
+        assert!(validate_database_filename("a\\b.db").is_err());
+        assert!(validate_database_filename("..").is_err());
     }
 
     #[test]
@@ -3,9 +3,12 @@
 //! This module provides the main database connection type with proper
 //! initialization and PRAGMA settings for optimal `SQLite` configuration.
 
-use rusqlite::{Connection, OpenFlags, Transaction, TransactionBehavior};
+use std::path::Path;
 
-use crate::error::Result;
+use rusqlite::backup::Progress;
+use rusqlite::{Connection, DatabaseName, ErrorCode, OpenFlags, Transaction, TransactionBehavior};
+
+use crate::error::{Error, Result};
 
 use super::config::DatabaseConfig;
 
@@ -25,10 +28,30 @@ use super::config::DatabaseConfig;
 #[derive(Debug)]
 pub struct Database {
     pub(super) conn: Connection,
-    #[allow(dead_code)]
     config: DatabaseConfig,
 }
 
+/// Database size before and after a [`Database::vacuum`].
+///
+/// Sizes are in bytes of database pages (`page_count * page_size`), which
+/// shrink as soon as the vacuum finishes, even before the WAL is
+/// checkpointed back into the main file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VacuumStats {
+    /// Database size before vacuuming.
+    pub size_before: u64,
+    /// Database size after vacuuming.
+    pub size_after: u64,
+}
+
+impl VacuumStats {
+    /// Returns the number of bytes reclaimed.
+    #[must_use]
+    pub const fn reclaimed(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+}
+
 impl Database {
     /// Opens a database connection with the given configuration.
     ///
@@ -156,11 +179,171 @@ impl Database {
             .conn
             .transaction_with_behavior(TransactionBehavior::Immediate)?)
     }
+
+    /// Rebuilds the database file to reclaim space left by deleted rows.
+    ///
+    /// `VACUUM` needs exclusive access; it waits up to the configured busy
+    /// timeout for other connections to finish and must not be called inside
+    /// a transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::LockTimeout` if another process still holds a lock
+    /// after the busy timeout, or another error if the vacuum fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    ///
+    /// let mut db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let stats = db.vacuum().unwrap();
+    /// println!("reclaimed {} bytes", stats.reclaimed());
+    /// ```
+    pub fn vacuum(&mut self) -> Result<VacuumStats> {
+        let size_before = self.page_bytes()?;
+        self.conn
+            .execute_batch("VACUUM")
+            .map_err(|e| self.lock_error(e))?;
+        let size_after = self.page_bytes()?;
+
+        Ok(VacuumStats {
+            size_before,
+            size_after,
+        })
+    }
+
+    /// Checkpoints the write-ahead log into the main file and truncates it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::LockTimeout` if readers or writers prevented a full
+    /// checkpoint, or another error if the checkpoint fails.
+    pub fn checkpoint_wal(&mut self) -> Result<()> {
+        let busy: i64 = self
+            .conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))
+            .map_err(|e| self.lock_error(e))?;
+        if busy != 0 {
+            return Err(self.lock_timeout());
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the database's pages in bytes.
+    fn page_bytes(&self) -> Result<u64> {
+        let page_count: u64 = self
+            .conn
+            .query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let page_size: u64 = self
+            .conn
+            .query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok(page_count * page_size)
+    }
+
+    /// Converts busy and locked errors into `Error::LockTimeout`.
+    pub(super) fn lock_error(&self, error: rusqlite::Error) -> Error {
+        match error.sqlite_error_code() {
+            Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked) => self.lock_timeout(),
+            _ => error.into(),
+        }
+    }
+
+    /// Returns a lock timeout error for the configured busy timeout.
+    const fn lock_timeout(&self) -> Error {
+        Error::LockTimeout {
+            seconds: self.config.busy_timeout.as_secs(),
+        }
+    }
+
+    /// Copies the database to `dest` using `SQLite`'s online backup API.
+    ///
+    /// The copy is consistent even while other processes are writing. The
+    /// backup file is switched to rollback journaling so it is a single
+    /// self-contained file. It is written next to `dest` and renamed into
+    /// place, so an existing file at `dest` is replaced only once the copy
+    /// is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination cannot be written or the backup
+    /// fails part-way.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    ///
+    /// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// db.backup_to("/tmp/trop-backup.db").unwrap();
+    /// ```
+    pub fn backup_to(&self, dest: impl AsRef<Path>) -> Result<()> {
+        let dest = dest.as_ref();
+        let file_name = dest.file_name().map_or_else(
+            || "trop-backup".into(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let temp_path = dest.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+
+        let result = self.backup_and_rename(&temp_path, dest);
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    /// Back up to `temp_path`, make it self-contained, and rename it to `dest`.
+    fn backup_and_rename(&self, temp_path: &Path, dest: &Path) -> Result<()> {
+        self.conn.backup(DatabaseName::Main, temp_path, None)?;
+
+        let backup = Connection::open(temp_path)?;
+        let _: String = backup.query_row("PRAGMA journal_mode = DELETE", [], |row| row.get(0))?;
+        backup.close().map_err(|(_, e)| e)?;
+
+        std::fs::rename(temp_path, dest)?;
+        Ok(())
+    }
+
+    /// Replaces the contents of this database with the backup at `source`.
+    ///
+    /// The source is validated before anything is overwritten: it must pass
+    /// `PRAGMA integrity_check` and carry a schema version this build
+    /// understands.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The source cannot be opened
+    /// - The source fails the integrity check (`Error::DatabaseCorruption`)
+    /// - The source schema is incompatible
+    /// - The restore fails part-way
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    ///
+    /// let mut db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// db.restore_from("/tmp/trop-backup.db").unwrap();
+    /// ```
+    pub fn restore_from(&mut self, source: impl AsRef<Path>) -> Result<()> {
+        let source = source.as_ref();
+
+        // Opening read-only runs the schema check without initializing
+        let mut backup = Self::open(DatabaseConfig::new(source).read_only())?;
+        backup.verify_integrity()?;
+        drop(backup);
+
+        self.conn
+            .restore(DatabaseName::Main, source, None::<fn(Progress)>)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::test_util::create_test_reservation;
     use tempfile::tempdir;
 
     #[test]
@@ -229,4 +412,75 @@ mod tests {
         // Test mutable accessor
         let _conn_mut = db.connection_mut();
     }
+
+    #[test]
+    fn test_backup_and_restore_round_trip() {
+        let dir = tempdir().unwrap();
+        let backup_path = dir.path().join("backup.db");
+
+        let mut db = Database::open(DatabaseConfig::new(dir.path().join("a.db"))).unwrap();
+        let reservation = create_test_reservation("/backup/project", 5000);
+        db.create_reservation(&reservation).unwrap();
+        db.backup_to(&backup_path).unwrap();
+
+        let mut fresh = Database::open(DatabaseConfig::new(dir.path().join("b.db"))).unwrap();
+        fresh.restore_from(&backup_path).unwrap();
+
+        let restored = Database::list_all_reservations(fresh.connection()).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].key(), reservation.key());
+        assert_eq!(restored[0].port(), reservation.port());
+    }
+
+    #[test]
+    fn test_restore_rejects_non_trop_database() {
+        let dir = tempdir().unwrap();
+        let bogus = dir.path().join("bogus.db");
+        std::fs::write(&bogus, b"this is not a sqlite database").unwrap();
+
+        let mut db = Database::open(DatabaseConfig::new(dir.path().join("a.db"))).unwrap();
+        let reservation = create_test_reservation("/backup/project", 5000);
+        db.create_reservation(&reservation).unwrap();
+
+        assert!(db.restore_from(&bogus).is_err());
+        let remaining = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(remaining.len(), 1);
+    }
+
+    #[test]
+    fn test_vacuum_reclaims_deleted_reservations() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let mut db = Database::open(DatabaseConfig::new(&path)).unwrap();
+
+        let mut sizes = Vec::new();
+        for round in 0..3 {
+            let reservations: Vec<_> = (0..300)
+                .map(|i| create_test_reservation(&format!("/vacuum/{round}/{i}"), 5000 + i))
+                .collect();
+            for reservation in &reservations {
+                db.create_reservation(reservation).unwrap();
+            }
+            for reservation in &reservations {
+                db.delete_reservation(reservation.key()).unwrap();
+            }
+
+            let stats = db.vacuum().unwrap();
+            assert!(stats.size_after <= stats.size_before);
+            db.checkpoint_wal().unwrap();
+            sizes.push(std::fs::metadata(&path).unwrap().len());
+        }
+
+        // Each cycle leaves an empty database behind, so the file can't keep growing
+        assert!(sizes.windows(2).all(|pair| pair[1] <= pair[0]), "{sizes:?}");
+    }
+
+    #[test]
+    fn test_vacuum_stats_reclaimed() {
+        let stats = VacuumStats {
+            size_before: 8192,
+            size_after: 4096,
+        };
+        assert_eq!(stats.reclaimed(), 4096);
+    }
 }
@@ -8,6 +8,7 @@ use rusqlite::Connection;
 use crate::error::{Error, Result};
 
 use super::schema::{
+    ADD_EXPIRES_AT_COLUMN, ADD_NOTE_COLUMN, ADD_PINNED_COLUMN, ADD_PRIORITY_COLUMN,
     CREATE_LAST_USED_INDEX, CREATE_METADATA_TABLE, CREATE_PORT_INDEX, CREATE_PROJECT_INDEX,
     CREATE_RESERVATIONS_TABLE, CURRENT_SCHEMA_VERSION, INSERT_SCHEMA_VERSION,
     SELECT_SCHEMA_VERSION,
@@ -92,7 +93,7 @@ pub fn get_schema_version(conn: &Connection) -> Result<i32> {
 /// This function:
 /// 1. Checks the current schema version
 /// 2. If version is 0, initializes the schema
-/// 3. If version is older than current, returns an error (migrations needed)
+/// 3. If version is older than current, returns an error (run `trop init --migrate`)
 /// 4. If version is newer than current, returns an error (client too old)
 /// 5. If version matches, applies any necessary schema fixes
 ///
@@ -119,12 +120,12 @@ pub fn check_schema_compatibility(conn: &Connection) -> Result<()> {
         // Fresh database, initialize it
         initialize_schema(conn)?;
     } else if version < CURRENT_SCHEMA_VERSION {
-        // Database is older than current version
-        // In the future, we would apply migrations here
+        // Database is older than current version; upgrades are explicit
+        // (see `upgrade_schema`)
         return Err(Error::Validation {
             field: "schema_version".into(),
             message: format!(
-                "Database schema version {version} is older than client version {CURRENT_SCHEMA_VERSION}. Migration not yet implemented."
+                "Database schema version {version} is older than client version {CURRENT_SCHEMA_VERSION}. Run 'trop init --migrate' to upgrade it."
             ),
         });
     } else if version > CURRENT_SCHEMA_VERSION {
@@ -144,6 +145,163 @@ pub fn check_schema_compatibility(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// One step of a schema upgrade: the version it produces and what it does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaUpgrade {
+    /// Schema version after this step is applied.
+    pub version: i32,
+    /// Human-readable description of the change.
+    pub description: &'static str,
+    /// SQL statement that applies the change.
+    sql: &'static str,
+}
+
+/// Column additions that bring a schema up to each version, in order.
+const SCHEMA_UPGRADES: &[SchemaUpgrade] = &[
+    SchemaUpgrade {
+        version: 2,
+        description: "add pinned column to reservations",
+        sql: ADD_PINNED_COLUMN,
+    },
+    SchemaUpgrade {
+        version: 3,
+        description: "add expires_at column to reservations",
+        sql: ADD_EXPIRES_AT_COLUMN,
+    },
+    SchemaUpgrade {
+        version: 4,
+        description: "add note column to reservations",
+        sql: ADD_NOTE_COLUMN,
+    },
+    SchemaUpgrade {
+        version: 5,
+        description: "add priority column to reservations",
+        sql: ADD_PRIORITY_COLUMN,
+    },
+];
+
+/// Lists the steps that upgrading a schema from `from` to `target` would
+/// apply, without touching any database.
+///
+/// An uninitialized database (version 0) has no steps: it is created at
+/// [`CURRENT_SCHEMA_VERSION`] directly.
+///
+/// # Errors
+///
+/// Returns an error if `target` is older than `from` (downgrades are not
+/// supported), either version is newer than this client supports, or an
+/// uninitialized database is targeted at an older version.
+///
+/// # Examples
+///
+/// ```
+/// use trop::database::migrations::pending_schema_upgrades;
+///
+/// let steps = pending_schema_upgrades(2, 4).unwrap();
+/// assert_eq!(steps.iter().map(|s| s.version).collect::<Vec<_>>(), vec![3, 4]);
+/// assert!(pending_schema_upgrades(3, 2).is_err());
+/// ```
+pub fn pending_schema_upgrades(from: i32, target: i32) -> Result<Vec<SchemaUpgrade>> {
+    if from > CURRENT_SCHEMA_VERSION {
+        return Err(Error::Validation {
+            field: "schema_version".into(),
+            message: format!(
+                "Database schema version {from} is newer than client version {CURRENT_SCHEMA_VERSION}. Please upgrade trop."
+            ),
+        });
+    }
+    if target > CURRENT_SCHEMA_VERSION {
+        return Err(Error::Validation {
+            field: "schema_version".into(),
+            message: format!(
+                "Target schema version {target} is newer than client version {CURRENT_SCHEMA_VERSION}"
+            ),
+        });
+    }
+    if target < from {
+        return Err(Error::Validation {
+            field: "schema_version".into(),
+            message: format!(
+                "Cannot downgrade database schema from version {from} to {target}; downgrades are not supported"
+            ),
+        });
+    }
+    if from == 0 && target != CURRENT_SCHEMA_VERSION {
+        return Err(Error::Validation {
+            field: "schema_version".into(),
+            message: format!(
+                "An uninitialized database can only be created at schema version {CURRENT_SCHEMA_VERSION}"
+            ),
+        });
+    }
+
+    Ok(SCHEMA_UPGRADES
+        .iter()
+        .filter(|step| from != 0 && step.version > from && step.version <= target)
+        .copied()
+        .collect())
+}
+
+/// Upgrades the database schema to the current version.
+///
+/// Unlike [`check_schema_compatibility`], which rejects databases with an
+/// older schema, this applies the migrations needed to bring the schema up
+/// to [`CURRENT_SCHEMA_VERSION`]. An uninitialized database (version 0) is
+/// initialized from scratch.
+///
+/// Returns the schema version found before the upgrade.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The schema is newer than this client supports
+/// - A migration step fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusqlite::Connection;
+/// use trop::database::migrations::upgrade_schema;
+///
+/// let conn = Connection::open_in_memory().unwrap();
+/// let previous = upgrade_schema(&conn).unwrap();
+/// assert_eq!(previous, 0);
+/// ```
+pub fn upgrade_schema(conn: &Connection) -> Result<i32> {
+    upgrade_schema_to(conn, CURRENT_SCHEMA_VERSION)
+}
+
+/// Upgrades the database schema to `target`, and no further.
+///
+/// This allows staged rollouts where some clients still expect an older
+/// schema. Upgrading to the version the database already has is a no-op
+/// apart from the version 1 fixes applied on every upgrade.
+///
+/// Returns the schema version found before the upgrade.
+///
+/// # Errors
+///
+/// Returns an error if the upgrade is rejected by
+/// [`pending_schema_upgrades`] (e.g. a downgrade) or a migration step fails.
+pub fn upgrade_schema_to(conn: &Connection, target: i32) -> Result<i32> {
+    let version = get_schema_version(conn)?;
+    let steps = pending_schema_upgrades(version, target)?;
+
+    if version == 0 {
+        initialize_schema(conn)?;
+    } else {
+        // Version 1 fixes (e.g., the UNIQUE constraint on port) come first,
+        // since they recreate the table with the version 1 columns
+        apply_schema_fixes_v1(conn)?;
+        for step in &steps {
+            conn.execute(step.sql, [])?;
+        }
+        conn.execute(INSERT_SCHEMA_VERSION, [target])?;
+    }
+
+    Ok(version)
+}
+
 /// Applies schema fixes for version 1 databases.
 ///
 /// This function checks if the port column has a UNIQUE constraint and
@@ -338,7 +496,7 @@ mod tests {
         // Manually set an older version (if current version > 1)
         if CURRENT_SCHEMA_VERSION > 1 {
             conn.execute(
-                "UPDATE metadata SET value = '0' WHERE key = 'schema_version'",
+                "UPDATE metadata SET value = '1' WHERE key = 'schema_version'",
                 [],
             )
             .unwrap();
@@ -348,6 +506,7 @@ mod tests {
             assert!(result.is_err());
             let err = result.unwrap_err();
             assert!(err.to_string().contains("older than client"));
+            assert!(err.to_string().contains("trop init --migrate"));
         }
     }
 
@@ -368,4 +527,289 @@ mod tests {
         // We should have 3 indices (port, project, last_used)
         assert_eq!(index_count, 3);
     }
+
+    #[test]
+    fn test_upgrade_schema_fresh_database() {
+        let conn = create_test_connection();
+
+        let previous = upgrade_schema(&conn).unwrap();
+
+        assert_eq!(previous, 0);
+        assert_eq!(get_schema_version(&conn).unwrap(), CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_upgrade_schema_adds_missing_unique_constraint() {
+        let conn = create_test_connection();
+        conn.execute(CREATE_METADATA_TABLE, []).unwrap();
+        conn.execute(
+            "CREATE TABLE reservations (
+                path TEXT NOT NULL,
+                tag TEXT,
+                port INTEGER NOT NULL,
+                project TEXT,
+                task TEXT,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                PRIMARY KEY (path, tag)
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(INSERT_SCHEMA_VERSION, [1]).unwrap();
+
+        let previous = upgrade_schema(&conn).unwrap();
+        assert_eq!(previous, 1);
+        assert_eq!(get_schema_version(&conn).unwrap(), CURRENT_SCHEMA_VERSION);
+
+        let has_unique: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master
+                 WHERE name = 'reservations' AND sql LIKE '%port INTEGER NOT NULL UNIQUE%'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(has_unique);
+    }
+
+    #[test]
+    fn test_upgrade_schema_adds_pinned_column() {
+        let conn = create_test_connection();
+        conn.execute(CREATE_METADATA_TABLE, []).unwrap();
+        conn.execute(
+            "CREATE TABLE reservations (
+                path TEXT NOT NULL,
+                tag TEXT,
+                port INTEGER NOT NULL UNIQUE,
+                project TEXT,
+                task TEXT,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                PRIMARY KEY (path, tag)
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO reservations VALUES ('/path', NULL, 5000, NULL, NULL, 0, 0)",
+            [],
+        )
+        .unwrap();
+        conn.execute(INSERT_SCHEMA_VERSION, [1]).unwrap();
+
+        // Version 1 databases are rejected until upgraded
+        assert!(check_schema_compatibility(&conn).is_err());
+
+        let previous = upgrade_schema(&conn).unwrap();
+        assert_eq!(previous, 1);
+
+        // Existing reservations are preserved and start out unpinned
+        let pinned: bool = conn
+            .query_row(
+                "SELECT pinned FROM reservations WHERE port = 5000",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!pinned);
+        check_schema_compatibility(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_schema_adds_expires_at_column() {
+        let conn = create_test_connection();
+        conn.execute(CREATE_METADATA_TABLE, []).unwrap();
+        conn.execute(
+            "CREATE TABLE reservations (
+                path TEXT NOT NULL,
+                tag TEXT,
+                port INTEGER NOT NULL UNIQUE,
+                project TEXT,
+                task TEXT,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (path, tag)
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO reservations VALUES ('/path', NULL, 5000, NULL, NULL, 0, 0, 1)",
+            [],
+        )
+        .unwrap();
+        conn.execute(INSERT_SCHEMA_VERSION, [2]).unwrap();
+
+        assert!(check_schema_compatibility(&conn).is_err());
+
+        let previous = upgrade_schema(&conn).unwrap();
+        assert_eq!(previous, 2);
+
+        // Existing reservations are preserved and have no TTL
+        let (pinned, expires_at): (bool, Option<i64>) = conn
+            .query_row(
+                "SELECT pinned, expires_at FROM reservations WHERE port = 5000",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert!(pinned);
+        assert_eq!(expires_at, None);
+        check_schema_compatibility(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_schema_adds_note_column() {
+        let conn = create_test_connection();
+        conn.execute(CREATE_METADATA_TABLE, []).unwrap();
+        conn.execute(
+            "CREATE TABLE reservations (
+                path TEXT NOT NULL,
+                tag TEXT,
+                port INTEGER NOT NULL UNIQUE,
+                project TEXT,
+                task TEXT,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                expires_at INTEGER,
+                PRIMARY KEY (path, tag)
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO reservations VALUES ('/path', NULL, 5000, NULL, NULL, 0, 0, 0, 60)",
+            [],
+        )
+        .unwrap();
+        conn.execute(INSERT_SCHEMA_VERSION, [3]).unwrap();
+
+        assert!(check_schema_compatibility(&conn).is_err());
+
+        let previous = upgrade_schema(&conn).unwrap();
+        assert_eq!(previous, 3);
+
+        // Existing reservations keep their TTL and have no note
+        let (expires_at, note): (Option<i64>, Option<String>) = conn
+            .query_row(
+                "SELECT expires_at, note FROM reservations WHERE port = 5000",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(expires_at, Some(60));
+        assert_eq!(note, None);
+        check_schema_compatibility(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_schema_adds_priority_column() {
+        let conn = create_test_connection();
+        conn.execute(CREATE_METADATA_TABLE, []).unwrap();
+        conn.execute(
+            "CREATE TABLE reservations (
+                path TEXT NOT NULL,
+                tag TEXT,
+                port INTEGER NOT NULL UNIQUE,
+                project TEXT,
+                task TEXT,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                expires_at INTEGER,
+                note TEXT,
+                PRIMARY KEY (path, tag)
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO reservations VALUES ('/path', NULL, 5000, NULL, NULL, 0, 0, 0, NULL, 'db')",
+            [],
+        )
+        .unwrap();
+        conn.execute(INSERT_SCHEMA_VERSION, [4]).unwrap();
+
+        assert!(check_schema_compatibility(&conn).is_err());
+
+        let previous = upgrade_schema(&conn).unwrap();
+        assert_eq!(previous, 4);
+
+        // Existing reservations keep their note and get the default priority
+        let (note, priority): (Option<String>, i32) = conn
+            .query_row(
+                "SELECT note, priority FROM reservations WHERE port = 5000",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(note.as_deref(), Some("db"));
+        assert_eq!(priority, 0);
+        check_schema_compatibility(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_schema_newer_version() {
+        let conn = create_test_connection();
+        initialize_schema(&conn).unwrap();
+        conn.execute(
+            "UPDATE metadata SET value = '999' WHERE key = 'schema_version'",
+            [],
+        )
+        .unwrap();
+
+        let err = upgrade_schema(&conn).unwrap_err();
+        assert!(err.to_string().contains("newer than client"));
+    }
+
+    #[test]
+    fn test_upgrade_schema_to_stops_at_target() {
+        let conn = create_test_connection();
+        initialize_schema(&conn).unwrap();
+        conn.execute("ALTER TABLE reservations DROP COLUMN priority", [])
+            .unwrap();
+        conn.execute("ALTER TABLE reservations DROP COLUMN note", [])
+            .unwrap();
+        conn.execute("ALTER TABLE reservations DROP COLUMN expires_at", [])
+            .unwrap();
+        conn.execute(INSERT_SCHEMA_VERSION, [2]).unwrap();
+
+        let previous = upgrade_schema_to(&conn, 3).unwrap();
+        assert_eq!(previous, 2);
+        assert_eq!(get_schema_version(&conn).unwrap(), 3);
+        let has_note: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('reservations') WHERE name = 'note'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!has_note, "upgrade must not go beyond the target");
+
+        // Targeting the version the database already has is a no-op
+        assert_eq!(upgrade_schema_to(&conn, 3).unwrap(), 3);
+        assert_eq!(get_schema_version(&conn).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_pending_schema_upgrades() {
+        let versions = |from, to| {
+            pending_schema_upgrades(from, to)
+                .unwrap()
+                .iter()
+                .map(|step| step.version)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(versions(1, CURRENT_SCHEMA_VERSION), vec![2, 3, 4, 5]);
+        assert_eq!(versions(3, 3), Vec::<i32>::new());
+        assert_eq!(versions(0, CURRENT_SCHEMA_VERSION), Vec::<i32>::new());
+
+        let err = pending_schema_upgrades(3, 2).unwrap_err();
+        assert!(err.to_string().contains("downgrade"), "{err}");
+        assert!(pending_schema_upgrades(1, CURRENT_SCHEMA_VERSION + 1).is_err());
+        assert!(pending_schema_upgrades(0, 2).is_err());
+    }
 }
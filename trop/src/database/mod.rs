@@ -32,6 +32,7 @@ mod config;
 mod connection;
 pub mod migrations;
 mod operations;
+mod portable;
 mod schema;
 mod transaction;
 
@@ -39,8 +40,17 @@ mod transaction;
 pub mod test_util;
 
 // Re-export public API
-pub use config::{default_data_dir, resolve_database_path, DatabaseConfig};
-pub use connection::Database;
+pub use config::{
+    default_data_dir, resolve_database_path, validate_database_filename, DatabaseConfig,
+    DEFAULT_DATABASE_FILENAME,
+};
+pub use connection::{Database, VacuumStats};
+pub use operations::{ProjectSummary, ReservationFilter, ReservationStats};
+pub use portable::{ExportDocument, ExportRecord, ImportSummary, EXPORT_FORMAT_VERSION};
+pub use schema::CURRENT_SCHEMA_VERSION;
 
 // Re-export migration functions for advanced use cases
-pub use migrations::{check_schema_compatibility, get_schema_version, initialize_schema};
+pub use migrations::{
+    check_schema_compatibility, get_schema_version, initialize_schema, pending_schema_upgrades,
+    upgrade_schema, upgrade_schema_to, SchemaUpgrade,
+};
@@ -4,7 +4,7 @@
 //! for port reservations in the database.
 
 use std::env;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 use rusqlite::{params, Connection, TransactionBehavior};
@@ -37,9 +37,96 @@ pub(super) fn unix_secs_to_systemtime(secs: i64) -> SystemTime {
     SystemTime::UNIX_EPOCH + Duration::from_secs(secs as u64)
 }
 
+/// Criteria for narrowing a reservation listing.
+///
+/// Each field that is set must match exactly; unset fields match anything.
+/// When several fields are set, a reservation must match all of them.
+/// Time bounds compare at one-second precision; `*_since` bounds are
+/// inclusive and `*_before` bounds are exclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReservationFilter {
+    /// Only include reservations with this project.
+    pub project: Option<String>,
+    /// Only include reservations with this task.
+    pub task: Option<String>,
+    /// Only include reservations with this tag.
+    pub tag: Option<String>,
+    /// Only include reservations created at or after this time.
+    pub created_since: Option<SystemTime>,
+    /// Only include reservations created before this time.
+    pub created_before: Option<SystemTime>,
+    /// Only include reservations last used at or after this time.
+    pub used_since: Option<SystemTime>,
+    /// Only include reservations last used before this time.
+    pub used_before: Option<SystemTime>,
+}
+
+impl ReservationFilter {
+    /// Returns true if no criteria are set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.project.is_none()
+            && self.task.is_none()
+            && self.tag.is_none()
+            && self.created_since.is_none()
+            && self.created_before.is_none()
+            && self.used_since.is_none()
+            && self.used_before.is_none()
+    }
+}
+
+/// Aggregate counts describing the reservation database.
+///
+/// Produced by [`Database::reservation_stats`] with `SQL` aggregates, so
+/// computing it never loads individual reservations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservationStats {
+    /// Total number of reservations.
+    pub total: usize,
+    /// Number of distinct ports held by reservations.
+    pub distinct_ports: usize,
+    /// Reservations carrying a project or task. These fields are sticky:
+    /// changing them on re-reservation requires `--force`.
+    pub sticky: usize,
+    /// The port range the utilization figures are measured against.
+    pub range: PortRange,
+    /// Number of distinct reserved ports inside `range`.
+    pub ports_in_range: usize,
+    /// Reservation counts per project, ordered by project with reservations
+    /// lacking a project (`None`) first.
+    pub by_project: Vec<(Option<String>, usize)>,
+}
+
+impl ReservationStats {
+    /// Returns the percentage of `range` occupied by reserved ports.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn range_utilization(&self) -> f64 {
+        if self.range.is_empty() {
+            return 0.0;
+        }
+        self.ports_in_range as f64 * 100.0 / f64::from(self.range.len())
+    }
+}
+
+/// Reservation count and most recent use for one project.
+///
+/// Produced by [`Database::project_summaries`] with a grouped aggregate, so
+/// summarizing never loads individual reservations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectSummary {
+    /// The project, or `None` for reservations without one.
+    pub project: Option<String>,
+    /// Number of reservations in the project.
+    pub reservation_count: usize,
+    /// The latest `last_used_at` among the project's reservations.
+    pub latest_used_at: SystemTime,
+}
+
 /// Helper function to deserialize a reservation from a database row.
 ///
-/// Expects row fields in this order: path, tag, port, project, task, `created_at`, `last_used_at`
+/// Expects row fields in this order: path, tag, port, project, task, `created_at`, `last_used_at`,
+/// `pinned`, `expires_at`, `note`, `priority`
 fn row_to_reservation(row: &rusqlite::Row<'_>) -> rusqlite::Result<Reservation> {
     let path: String = row.get(0)?;
     let tag: Option<String> = row.get(1)?;
@@ -48,6 +135,10 @@ fn row_to_reservation(row: &rusqlite::Row<'_>) -> rusqlite::Result<Reservation>
     let task: Option<String> = row.get(4)?;
     let created_secs: i64 = row.get(5)?;
     let last_used_secs: i64 = row.get(6)?;
+    let pinned: bool = row.get(7)?;
+    let expires_secs: Option<i64> = row.get(8)?;
+    let note: Option<String> = row.get(9)?;
+    let priority: i32 = row.get(10)?;
 
     let key = ReservationKey::new(path.into(), tag)
         .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
@@ -63,13 +154,17 @@ fn row_to_reservation(row: &rusqlite::Row<'_>) -> rusqlite::Result<Reservation>
         .task(task)
         .created_at(created_at)
         .last_used_at(last_used_at)
+        .pinned(pinned)
+        .expires_at(expires_secs.map(unix_secs_to_systemtime))
+        .note(note)
+        .priority(priority)
         .build()
         .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
 }
 
 // SQL statements for CRUD operations
 const SELECT_RESERVATION: &str = r"
-    SELECT port, project, task, created_at, last_used_at
+    SELECT port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
     FROM reservations
     WHERE path = ? AND tag IS ?
 ";
@@ -80,12 +175,43 @@ const UPDATE_LAST_USED: &str = r"
     WHERE path = ? AND tag IS ?
 ";
 
+const TOUCH_BY_PROJECT: &str = r"
+    UPDATE reservations
+    SET last_used_at = ?1
+    WHERE project = ?2 AND (?3 IS NULL OR task = ?3)
+";
+
+const UPDATE_PATH: &str = r"
+    UPDATE reservations
+    SET path = ?
+    WHERE path = ? AND tag IS ?
+";
+
+const UPDATE_PINNED: &str = r"
+    UPDATE reservations
+    SET pinned = ?
+    WHERE path = ? AND tag IS ?
+";
+
 const LIST_RESERVATIONS: &str = r"
-    SELECT path, tag, port, project, task, created_at, last_used_at
+    SELECT path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
     FROM reservations
     ORDER BY path, tag
 ";
 
+const LIST_RESERVATIONS_FILTERED: &str = r"
+    SELECT path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
+    FROM reservations
+    WHERE (?1 IS NULL OR project = ?1)
+      AND (?2 IS NULL OR task = ?2)
+      AND (?3 IS NULL OR tag = ?3)
+      AND (?4 IS NULL OR created_at >= ?4)
+      AND (?5 IS NULL OR created_at < ?5)
+      AND (?6 IS NULL OR last_used_at >= ?6)
+      AND (?7 IS NULL OR last_used_at < ?7)
+    ORDER BY path, tag
+";
+
 const SELECT_RESERVED_PORTS: &str = r"
     SELECT DISTINCT port
     FROM reservations
@@ -93,26 +219,70 @@ const SELECT_RESERVED_PORTS: &str = r"
     ORDER BY port
 ";
 
+const SELECT_RESERVATIONS_IN_RANGE: &str = r"
+    SELECT path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
+    FROM reservations
+    WHERE port >= ? AND port <= ?
+    ORDER BY port
+";
+
 const SELECT_BY_PATH_PREFIX: &str = r"
-    SELECT path, tag, port, project, task, created_at, last_used_at
+    SELECT path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
     FROM reservations
     WHERE path LIKE ? || '%'
     ORDER BY path, tag
 ";
 
 const SELECT_EXPIRED: &str = r"
-    SELECT path, tag, port, project, task, created_at, last_used_at
+    SELECT path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
     FROM reservations
     WHERE last_used_at < ?
     ORDER BY last_used_at
 ";
 
+const SELECT_PAST_TTL: &str = r"
+    SELECT path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
+    FROM reservations
+    WHERE expires_at IS NOT NULL AND expires_at <= ?
+    ORDER BY expires_at
+";
+
+const SELECT_STATS: &str = r"
+    SELECT
+        COUNT(*),
+        COUNT(DISTINCT port),
+        COUNT(CASE WHEN project IS NOT NULL OR task IS NOT NULL THEN 1 END),
+        COUNT(DISTINCT CASE WHEN port >= ?1 AND port <= ?2 THEN port END)
+    FROM reservations
+";
+
+const SELECT_PROJECT_COUNTS: &str = r"
+    SELECT project, COUNT(*)
+    FROM reservations
+    GROUP BY project
+    ORDER BY project
+";
+
+const SELECT_PROJECT_SUMMARIES: &str = r"
+    SELECT project, COUNT(*), MAX(last_used_at)
+    FROM reservations
+    GROUP BY project
+    ORDER BY project
+";
+
+const SELECT_PATH_COUNTS: &str = r"
+    SELECT path, COUNT(*)
+    FROM reservations
+    GROUP BY path
+    ORDER BY path
+";
+
 const CHECK_PORT_RESERVED: &str = r"
     SELECT COUNT(*) FROM reservations WHERE port = ?
 ";
 
 const SELECT_BY_PORT: &str = r"
-    SELECT path, tag, port, project, task, created_at, last_used_at
+    SELECT path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority
     FROM reservations
     WHERE port = ?
 ";
@@ -181,6 +351,10 @@ impl Database {
 
         let created_secs = systemtime_to_unix_secs(reservation.created_at())?;
         let last_used_secs = systemtime_to_unix_secs(reservation.last_used_at())?;
+        let expires_secs = reservation
+            .expires_at()
+            .map(systemtime_to_unix_secs)
+            .transpose()?;
 
         tx.execute(
             INSERT_RESERVATION,
@@ -192,6 +366,10 @@ impl Database {
                 reservation.task(),
                 created_secs,
                 last_used_secs,
+                reservation.pinned(),
+                expires_secs,
+                reservation.note(),
+                reservation.priority(),
             ],
         )?;
 
@@ -237,6 +415,10 @@ impl Database {
 
             let created_secs = systemtime_to_unix_secs(reservation.created_at())?;
             let last_used_secs = systemtime_to_unix_secs(reservation.last_used_at())?;
+            let expires_secs = reservation
+                .expires_at()
+                .map(systemtime_to_unix_secs)
+                .transpose()?;
 
             conn.execute(
                 INSERT_RESERVATION,
@@ -248,6 +430,10 @@ impl Database {
                     reservation.task(),
                     created_secs,
                     last_used_secs,
+                    reservation.pinned(),
+                    expires_secs,
+                    reservation.note(),
+                    reservation.priority(),
                 ],
             )?;
 
@@ -292,6 +478,10 @@ impl Database {
             let task: Option<String> = row.get(2)?;
             let created_secs: i64 = row.get(3)?;
             let last_used_secs: i64 = row.get(4)?;
+            let pinned: bool = row.get(5)?;
+            let expires_secs: Option<i64> = row.get(6)?;
+            let note: Option<String> = row.get(7)?;
+            let priority: i32 = row.get(8)?;
 
             let created_at = unix_secs_to_systemtime(created_secs);
             let last_used_at = unix_secs_to_systemtime(last_used_secs);
@@ -301,6 +491,10 @@ impl Database {
                 .task(task)
                 .created_at(created_at)
                 .last_used_at(last_used_at)
+                .pinned(pinned)
+                .expires_at(expires_secs.map(unix_secs_to_systemtime))
+                .note(note)
+                .priority(priority)
                 .build()
                 .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
         }) {
@@ -403,6 +597,38 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
+    /// Updates `last_used_at` for every reservation in a project.
+    ///
+    /// When `task` is given, only reservations with that task are touched.
+    /// All matching reservations are updated by a single statement, so the
+    /// set is touched atomically. Matching nothing is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamp conversion fails or the database update fails.
+    ///
+    /// # Returns
+    ///
+    /// The number of reservations touched.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    ///
+    /// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let touched = Database::touch_reservations_by_project(db.connection(), "shop", None).unwrap();
+    /// ```
+    pub fn touch_reservations_by_project(
+        conn: &Connection,
+        project: &str,
+        task: Option<&str>,
+    ) -> Result<usize> {
+        let now = systemtime_to_unix_secs(SystemTime::now())?;
+        let rows_affected = conn.execute(TOUCH_BY_PROJECT, params![now, project, task])?;
+        Ok(rows_affected)
+    }
+
     /// Deletes a reservation from the database (without creating a transaction).
     ///
     /// This method is intended for use within an existing transaction.
@@ -417,6 +643,110 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
+    /// Moves a reservation to a new key, keeping its port and metadata.
+    ///
+    /// The path is changed with a single `UPDATE` inside an IMMEDIATE
+    /// transaction, so the reservation is never duplicated or lost. Only the
+    /// path may differ between `from` and `to`; the tag is kept from `from`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction or update fails, or
+    /// `Error::ReservationConflict` if a reservation already exists at `to`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the reservation was found and moved
+    /// - `Ok(false)` if no reservation exists at `from`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    /// use trop::ReservationKey;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = DatabaseConfig::new("/tmp/trop.db");
+    /// let mut db = Database::open(config).unwrap();
+    ///
+    /// let from = ReservationKey::new(PathBuf::from("/old"), None).unwrap();
+    /// let to = ReservationKey::new(PathBuf::from("/new"), None).unwrap();
+    /// let moved = db.move_reservation(&from, &to).unwrap();
+    /// ```
+    pub fn move_reservation(&mut self, from: &ReservationKey, to: &ReservationKey) -> Result<bool> {
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+        let moved = Self::move_reservation_simple(&tx, from, to)?;
+
+        tx.commit()?;
+        Ok(moved)
+    }
+
+    /// Moves a reservation to a new key (without creating a transaction).
+    ///
+    /// This method is intended for use within an existing transaction.
+    /// For standalone use, use `move_reservation` instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails, or
+    /// `Error::ReservationConflict` if a reservation already exists at `to`.
+    pub fn move_reservation_simple(
+        conn: &Connection,
+        from: &ReservationKey,
+        to: &ReservationKey,
+    ) -> Result<bool> {
+        Self::with_savepoint(conn, "trop_move_reservation", |conn| {
+            // The primary key can't catch this for NULL tags (NULL != NULL),
+            // so check the destination explicitly
+            if Self::get_reservation(conn, to)?.is_some() {
+                return Err(Error::ReservationConflict {
+                    details: format!("a reservation already exists for {to}"),
+                });
+            }
+
+            let rows_affected = conn.execute(
+                UPDATE_PATH,
+                params![to.path_as_string(), from.path_as_string(), from.tag],
+            )?;
+            Ok(rows_affected > 0)
+        })
+    }
+
+    /// Sets or clears the pinned flag on a reservation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the reservation was found and updated
+    /// - `Ok(false)` if the reservation was not found
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    /// use trop::ReservationKey;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = DatabaseConfig::new("/tmp/trop.db");
+    /// let db = Database::open(config).unwrap();
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+    /// let updated = Database::set_pinned(db.connection(), &key, true).unwrap();
+    /// ```
+    pub fn set_pinned(conn: &Connection, key: &ReservationKey, pinned: bool) -> Result<bool> {
+        let rows_affected = conn.execute(
+            UPDATE_PINNED,
+            params![pinned, key.path_as_string(), key.tag],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
     /// Lists all reservations in the database.
     ///
     /// # Errors
@@ -447,6 +777,55 @@ impl Database {
         Ok(reservations)
     }
 
+    /// Lists the reservations matching a filter.
+    ///
+    /// Filtering happens in the query itself, so only matching rows are
+    /// loaded. An empty filter lists every reservation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails, if a time bound is before the
+    /// Unix epoch, or if any reservation cannot be deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig, ReservationFilter};
+    ///
+    /// let config = DatabaseConfig::new("/tmp/trop.db");
+    /// let db = Database::open(config).unwrap();
+    ///
+    /// let filter = ReservationFilter {
+    ///     project: Some("my-app".into()),
+    ///     ..ReservationFilter::default()
+    /// };
+    /// let reservations = Database::list_reservations_filtered(db.connection(), &filter).unwrap();
+    /// ```
+    pub fn list_reservations_filtered(
+        conn: &Connection,
+        filter: &ReservationFilter,
+    ) -> Result<Vec<Reservation>> {
+        let bound = |time: Option<SystemTime>| time.map(systemtime_to_unix_secs).transpose();
+        let mut stmt = conn.prepare(LIST_RESERVATIONS_FILTERED)?;
+
+        let reservations = stmt
+            .query_map(
+                params![
+                    filter.project,
+                    filter.task,
+                    filter.tag,
+                    bound(filter.created_since)?,
+                    bound(filter.created_before)?,
+                    bound(filter.used_since)?,
+                    bound(filter.used_before)?,
+                ],
+                row_to_reservation,
+            )?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        Ok(reservations)
+    }
+
     /// Gets all reserved ports within a given range.
     ///
     /// This query is useful for finding which ports in a range are
@@ -485,6 +864,46 @@ impl Database {
         Ok(ports)
     }
 
+    /// Gets all reservations whose ports fall within a given range.
+    ///
+    /// Results are ordered by port. This is useful for reporting who holds
+    /// the ports in a range, e.g. when joining against occupancy results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    /// use trop::{Port, PortRange};
+    ///
+    /// let config = DatabaseConfig::new("/tmp/trop.db");
+    /// let db = Database::open(config).unwrap();
+    ///
+    /// let min = Port::try_from(5000).unwrap();
+    /// let max = Port::try_from(5100).unwrap();
+    /// let range = PortRange::new(min, max).unwrap();
+    ///
+    /// let reservations = Database::get_reservations_in_range(db.connection(), &range).unwrap();
+    /// ```
+    pub fn get_reservations_in_range(
+        conn: &Connection,
+        range: &PortRange,
+    ) -> Result<Vec<Reservation>> {
+        let mut stmt = conn.prepare(SELECT_RESERVATIONS_IN_RANGE)?;
+
+        let reservations = stmt
+            .query_map(
+                params![range.min().value(), range.max().value()],
+                row_to_reservation,
+            )?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        Ok(reservations)
+    }
+
     /// Gets all reservations whose paths start with the given prefix.
     ///
     /// This is useful for finding all reservations under a directory tree.
@@ -550,10 +969,76 @@ impl Database {
         let max_age_secs = max_age.as_secs() as i64;
         let cutoff = now_secs.saturating_sub(max_age_secs);
 
+        Self::query_used_before(conn, cutoff)
+    }
+
+    /// Finds reservations last used before `cutoff`.
+    ///
+    /// This is the absolute-time counterpart of
+    /// [`Database::find_expired_reservations`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or `cutoff` is before the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    ///
+    /// let cutoff = SystemTime::now() - Duration::from_secs(86400 * 30);
+    /// let stale = Database::find_reservations_used_before(db.connection(), cutoff).unwrap();
+    /// ```
+    pub fn find_reservations_used_before(
+        conn: &Connection,
+        cutoff: SystemTime,
+    ) -> Result<Vec<Reservation>> {
+        Self::query_used_before(conn, systemtime_to_unix_secs(cutoff)?)
+    }
+
+    fn query_used_before(conn: &Connection, cutoff_secs: i64) -> Result<Vec<Reservation>> {
         let mut stmt = conn.prepare(SELECT_EXPIRED)?;
 
         let reservations = stmt
-            .query_map([cutoff], row_to_reservation)?
+            .query_map([cutoff_secs], row_to_reservation)?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        Ok(reservations)
+    }
+
+    /// Finds reservations whose TTL has run out as of `now`.
+    ///
+    /// Only reservations created with a TTL have an `expires_at`; all others
+    /// are left to the age-based [`Database::find_expired_reservations`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or `now` is before the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    /// use std::time::SystemTime;
+    ///
+    /// let config = DatabaseConfig::new("/tmp/trop.db");
+    /// let db = Database::open(config).unwrap();
+    ///
+    /// let expired = Database::find_past_ttl_reservations(db.connection(), SystemTime::now()).unwrap();
+    /// ```
+    pub fn find_past_ttl_reservations(
+        conn: &Connection,
+        now: SystemTime,
+    ) -> Result<Vec<Reservation>> {
+        let now_secs = systemtime_to_unix_secs(now)?;
+
+        let mut stmt = conn.prepare(SELECT_PAST_TTL)?;
+
+        let reservations = stmt
+            .query_map([now_secs], row_to_reservation)?
             .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
 
         Ok(reservations)
@@ -683,6 +1168,102 @@ impl Database {
         Ok(projects)
     }
 
+    /// Computes aggregate statistics over all reservations.
+    ///
+    /// `range` is the configured allocation range; only reserved ports
+    /// inside it count towards [`ReservationStats::ports_in_range`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    /// use trop::{Port, PortRange};
+    ///
+    /// let config = DatabaseConfig::new("/tmp/trop.db");
+    /// let db = Database::open(config).unwrap();
+    ///
+    /// let range = PortRange::new(
+    ///     Port::try_from(5000).unwrap(),
+    ///     Port::try_from(7000).unwrap(),
+    /// ).unwrap();
+    /// let stats = Database::reservation_stats(db.connection(), &range).unwrap();
+    /// println!("{} reservations, {:.1}% of range used", stats.total, stats.range_utilization());
+    /// ```
+    pub fn reservation_stats(conn: &Connection, range: &PortRange) -> Result<ReservationStats> {
+        let (total, distinct_ports, sticky, ports_in_range) = conn.query_row(
+            SELECT_STATS,
+            params![range.min().value(), range.max().value()],
+            |row| {
+                Ok((
+                    row.get::<_, usize>(0)?,
+                    row.get::<_, usize>(1)?,
+                    row.get::<_, usize>(2)?,
+                    row.get::<_, usize>(3)?,
+                ))
+            },
+        )?;
+
+        let mut stmt = conn.prepare(SELECT_PROJECT_COUNTS)?;
+        let by_project = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        Ok(ReservationStats {
+            total,
+            distinct_ports,
+            sticky,
+            range: *range,
+            ports_in_range,
+            by_project,
+        })
+    }
+
+    /// Summarizes reservations per project.
+    ///
+    /// Projects are returned in sorted order, with reservations lacking a
+    /// project grouped into a single `None` entry first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn project_summaries(conn: &Connection) -> Result<Vec<ProjectSummary>> {
+        let mut stmt = conn.prepare(SELECT_PROJECT_SUMMARIES)?;
+        let summaries = stmt
+            .query_map([], |row| {
+                Ok(ProjectSummary {
+                    project: row.get(0)?,
+                    reservation_count: row.get(1)?,
+                    latest_used_at: unix_secs_to_systemtime(row.get(2)?),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        Ok(summaries)
+    }
+
+    /// Lists each distinct reserved path with its number of reservations.
+    ///
+    /// Paths are returned in sorted order. This lets callers check paths
+    /// against the filesystem once each, however many tags share them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn reservation_path_counts(conn: &Connection) -> Result<Vec<(PathBuf, usize)>> {
+        let mut stmt = conn.prepare(SELECT_PATH_COUNTS)?;
+        let paths = stmt
+            .query_map([], |row| {
+                Ok((PathBuf::from(row.get::<_, String>(0)?), row.get(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        Ok(paths)
+    }
+
     /// Verifies database integrity using PRAGMA `integrity_check`.
     ///
     /// This is compatible with existing transaction patterns as it's a read-only operation.
@@ -803,6 +1384,121 @@ mod tests {
         assert!(loaded.last_used_at() > reservation.last_used_at());
     }
 
+    #[test]
+    fn test_move_reservation() {
+        let mut db = create_test_database();
+        let from = ReservationKey::new(PathBuf::from("/old"), Some("web".into())).unwrap();
+        let reservation = Reservation::builder(from.clone(), Port::try_from(5000).unwrap())
+            .project(Some("proj".into()))
+            .task(Some("task".into()))
+            .pinned(true)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let to = ReservationKey::new(PathBuf::from("/new"), Some("web".into())).unwrap();
+        assert!(db.move_reservation(&from, &to).unwrap());
+
+        assert!(Database::get_reservation(db.connection(), &from)
+            .unwrap()
+            .is_none());
+        let moved = Database::get_reservation(db.connection(), &to)
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.port(), reservation.port());
+        assert_eq!(moved.project(), Some("proj"));
+        assert_eq!(moved.task(), Some("task"));
+        assert!(moved.pinned());
+        assert_eq!(
+            Database::list_all_reservations(db.connection())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_move_reservation_onto_existing_key_fails() {
+        let mut db = create_test_database();
+        let from = create_test_reservation("/old", 5000);
+        let to = create_test_reservation("/new", 5001);
+        db.create_reservation(&from).unwrap();
+        db.create_reservation(&to).unwrap();
+
+        assert!(matches!(
+            db.move_reservation(from.key(), to.key()),
+            Err(Error::ReservationConflict { .. })
+        ));
+
+        // Both reservations are untouched
+        let kept = Database::get_reservation(db.connection(), from.key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(kept.port(), from.port());
+        let other = Database::get_reservation(db.connection(), to.key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(other.port(), to.port());
+    }
+
+    #[test]
+    fn test_move_reservation_not_found() {
+        let mut db = create_test_database();
+        let from = ReservationKey::new(PathBuf::from("/missing"), None).unwrap();
+        let to = ReservationKey::new(PathBuf::from("/new"), None).unwrap();
+        assert!(!db.move_reservation(&from, &to).unwrap());
+    }
+
+    #[test]
+    fn test_touch_reservations_by_project() {
+        let mut db = create_test_database();
+        let old = SystemTime::now() - Duration::from_secs(30 * 24 * 60 * 60);
+
+        for (path, port, project, task) in [
+            ("/shop/web", 5000, Some("shop"), Some("checkout")),
+            ("/shop/api", 5001, Some("shop"), Some("search")),
+            ("/blog", 5002, Some("blog"), None),
+            ("/loose", 5003, None, None),
+        ] {
+            let key = ReservationKey::new(PathBuf::from(path), None).unwrap();
+            let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+                .project(project.map(String::from))
+                .task(task.map(String::from))
+                .last_used_at(old)
+                .build()
+                .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let last_used = |db: &Database, path: &str| {
+            let key = ReservationKey::new(PathBuf::from(path), None).unwrap();
+            Database::get_reservation(db.connection(), &key)
+                .unwrap()
+                .unwrap()
+                .last_used_at()
+        };
+
+        // Narrowing by task touches only that task's reservations
+        let touched =
+            Database::touch_reservations_by_project(db.connection(), "shop", Some("search"))
+                .unwrap();
+        assert_eq!(touched, 1);
+        assert!(last_used(&db, "/shop/api") > old);
+        assert!(last_used(&db, "/shop/web") <= old);
+
+        let touched =
+            Database::touch_reservations_by_project(db.connection(), "shop", None).unwrap();
+        assert_eq!(touched, 2);
+        assert!(last_used(&db, "/shop/web") > old);
+        assert!(last_used(&db, "/blog") <= old);
+        assert!(last_used(&db, "/loose") <= old);
+
+        // No matches is a no-op rather than an error
+        let touched =
+            Database::touch_reservations_by_project(db.connection(), "missing", None).unwrap();
+        assert_eq!(touched, 0);
+    }
+
     #[test]
     fn test_update_last_used_not_found() {
         let mut db = create_test_database();
@@ -836,6 +1532,39 @@ mod tests {
         assert!(!deleted);
     }
 
+    #[test]
+    fn test_set_pinned() {
+        let mut db = create_test_database();
+        let reservation = create_test_reservation("/test/path", 5000);
+        db.create_reservation(&reservation).unwrap();
+
+        assert!(Database::set_pinned(db.connection(), reservation.key(), true).unwrap());
+        let loaded = Database::get_reservation(db.connection(), reservation.key())
+            .unwrap()
+            .unwrap();
+        assert!(loaded.pinned());
+
+        // Pinned flag is visible through the row-based queries too
+        let by_port = Database::get_reservation_by_port(db.connection(), reservation.port())
+            .unwrap()
+            .unwrap();
+        assert!(by_port.pinned());
+
+        assert!(Database::set_pinned(db.connection(), reservation.key(), false).unwrap());
+        let loaded = Database::get_reservation(db.connection(), reservation.key())
+            .unwrap()
+            .unwrap();
+        assert!(!loaded.pinned());
+    }
+
+    #[test]
+    fn test_set_pinned_not_found() {
+        let db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/nonexistent"), None).unwrap();
+
+        assert!(!Database::set_pinned(db.connection(), &key, true).unwrap());
+    }
+
     #[test]
     fn test_list_all_reservations() {
         let mut db = create_test_database();
@@ -865,6 +1594,126 @@ mod tests {
         assert_eq!(all.len(), 0);
     }
 
+    #[test]
+    fn test_list_reservations_filtered() {
+        let mut db = create_test_database();
+
+        let make = |path: &str, tag: Option<&str>, port: u16, project: &str, task: &str| {
+            let key = ReservationKey::new(PathBuf::from(path), tag.map(String::from)).unwrap();
+            Reservation::builder(key, Port::try_from(port).unwrap())
+                .project(Some(project.into()))
+                .task(Some(task.into()))
+                .build()
+                .unwrap()
+        };
+        db.create_reservation(&make("/a", Some("web"), 5000, "alpha", "main"))
+            .unwrap();
+        db.create_reservation(&make("/a", Some("api"), 5001, "alpha", "feature"))
+            .unwrap();
+        db.create_reservation(&make("/b", Some("web"), 5002, "beta", "main"))
+            .unwrap();
+
+        let filter =
+            |project: Option<&str>, task: Option<&str>, tag: Option<&str>| ReservationFilter {
+                project: project.map(String::from),
+                task: task.map(String::from),
+                tag: tag.map(String::from),
+                ..ReservationFilter::default()
+            };
+        let ports = |filter: &ReservationFilter| -> Vec<u16> {
+            Database::list_reservations_filtered(db.connection(), filter)
+                .unwrap()
+                .iter()
+                .map(|r| r.port().value())
+                .collect()
+        };
+
+        assert_eq!(ports(&ReservationFilter::default()), vec![5001, 5000, 5002]);
+        assert_eq!(ports(&filter(Some("alpha"), None, None)), vec![5001, 5000]);
+        assert_eq!(ports(&filter(None, Some("main"), None)), vec![5000, 5002]);
+        assert_eq!(ports(&filter(None, None, Some("web"))), vec![5000, 5002]);
+        assert_eq!(
+            ports(&filter(Some("alpha"), Some("main"), Some("web"))),
+            vec![5000]
+        );
+        assert!(ports(&filter(Some("beta"), Some("feature"), None)).is_empty());
+    }
+
+    #[test]
+    fn test_list_reservations_filtered_by_time() {
+        let mut db = create_test_database();
+        let day = Duration::from_secs(86400);
+        let now = SystemTime::now();
+
+        // (path, port, created days ago, last used days ago)
+        for (path, port, created, used) in [
+            ("/old-idle", 5000, 60, 45),
+            ("/old-busy", 5001, 60, 1),
+            ("/new", 5002, 2, 2),
+        ] {
+            let key = ReservationKey::new(PathBuf::from(path), None).unwrap();
+            let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+                .created_at(now - day * created)
+                .last_used_at(now - day * used)
+                .build()
+                .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let ports = |filter: ReservationFilter| -> Vec<u16> {
+            Database::list_reservations_filtered(db.connection(), &filter)
+                .unwrap()
+                .iter()
+                .map(|r| r.port().value())
+                .collect()
+        };
+        let week_ago = Some(now - day * 7);
+
+        assert_eq!(
+            ports(ReservationFilter {
+                created_since: week_ago,
+                ..ReservationFilter::default()
+            }),
+            vec![5002]
+        );
+        assert_eq!(
+            ports(ReservationFilter {
+                created_before: week_ago,
+                ..ReservationFilter::default()
+            }),
+            vec![5001, 5000]
+        );
+        assert_eq!(
+            ports(ReservationFilter {
+                used_since: week_ago,
+                ..ReservationFilter::default()
+            }),
+            vec![5002, 5001]
+        );
+        assert_eq!(
+            ports(ReservationFilter {
+                used_before: week_ago,
+                ..ReservationFilter::default()
+            }),
+            vec![5000]
+        );
+
+        // Bounds combine into a window
+        assert_eq!(
+            ports(ReservationFilter {
+                created_before: week_ago,
+                used_since: week_ago,
+                ..ReservationFilter::default()
+            }),
+            vec![5001]
+        );
+
+        let stale =
+            Database::find_reservations_used_before(db.connection(), now - day * 7).unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].port().value(), 5000);
+    }
+
     #[test]
     fn test_reservation_with_optional_fields() {
         let mut db = create_test_database();
@@ -1004,6 +1853,29 @@ mod tests {
         assert_eq!(reserved[2].value(), 5010);
     }
 
+    #[test]
+    fn test_get_reservations_in_range() {
+        let mut db = create_test_database();
+
+        db.create_reservation(&create_test_reservation("/path2", 5005))
+            .unwrap();
+        db.create_reservation(&create_test_reservation("/path1", 5000))
+            .unwrap();
+        db.create_reservation(&create_test_reservation("/path3", 5020))
+            .unwrap();
+
+        let min = Port::try_from(5000).unwrap();
+        let max = Port::try_from(5010).unwrap();
+        let range = PortRange::new(min, max).unwrap();
+
+        let reservations = Database::get_reservations_in_range(db.connection(), &range).unwrap();
+        assert_eq!(reservations.len(), 2);
+        assert_eq!(reservations[0].port().value(), 5000);
+        assert_eq!(reservations[0].key().path, PathBuf::from("/path1"));
+        assert_eq!(reservations[1].port().value(), 5005);
+        assert_eq!(reservations[1].key().path, PathBuf::from("/path2"));
+    }
+
     #[test]
     fn test_get_reservations_by_path_prefix() {
         let mut db = create_test_database();
@@ -1085,6 +1957,37 @@ mod tests {
         assert_eq!(expired[0].key().path, PathBuf::from("/old/path"));
     }
 
+    #[test]
+    fn test_find_past_ttl_reservations() {
+        let mut db = create_test_database();
+        let now = SystemTime::now();
+
+        // One TTL already past, one still running, one without a TTL
+        for (path, port, expires_at) in [
+            ("/ttl/past", 5000, Some(now - Duration::from_secs(60))),
+            ("/ttl/future", 5001, Some(now + Duration::from_secs(3600))),
+            ("/ttl/none", 5002, None),
+        ] {
+            let key = ReservationKey::new(PathBuf::from(path), None).unwrap();
+            let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+                .expires_at(expires_at)
+                .build()
+                .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let expired = Database::find_past_ttl_reservations(db.connection(), now).unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].key().path, PathBuf::from("/ttl/past"));
+
+        // The deadline survives a round trip through the database
+        let key = ReservationKey::new(PathBuf::from("/ttl/future"), None).unwrap();
+        let stored = Database::get_reservation(db.connection(), &key)
+            .unwrap()
+            .unwrap();
+        assert!(stored.expires_at().is_some());
+    }
+
     #[test]
     fn test_is_port_reserved() {
         let mut db = create_test_database();
@@ -1300,4 +2203,118 @@ mod tests {
         assert_eq!(projects.len(), 1);
         assert_eq!(projects[0], "has-project");
     }
+
+    #[test]
+    fn test_reservation_stats() {
+        let mut db = create_test_database();
+        let range =
+            PortRange::new(Port::try_from(5000).unwrap(), Port::try_from(5009).unwrap()).unwrap();
+
+        let empty = Database::reservation_stats(db.connection(), &range).unwrap();
+        assert_eq!(empty.total, 0);
+        assert!(empty.by_project.is_empty());
+        assert!(empty.range_utilization().abs() < f64::EPSILON);
+
+        let seeds = [
+            ("/a", 5000, Some("alpha"), None),
+            ("/b", 5001, Some("alpha"), Some("build")),
+            ("/c", 5002, None, Some("task-only")),
+            ("/d", 6000, None, None),
+        ];
+        for (path, port, project, task) in seeds {
+            let reservation = Reservation::builder(
+                ReservationKey::new(PathBuf::from(path), None).unwrap(),
+                Port::try_from(port).unwrap(),
+            )
+            .project(project.map(String::from))
+            .task(task.map(String::from))
+            .build()
+            .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let stats = Database::reservation_stats(db.connection(), &range).unwrap();
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.distinct_ports, 4);
+        assert_eq!(stats.sticky, 3);
+        assert_eq!(stats.ports_in_range, 3);
+        assert!((stats.range_utilization() - 30.0).abs() < f64::EPSILON);
+        assert_eq!(
+            stats.by_project,
+            vec![(None, 2), (Some("alpha".to_string()), 2)]
+        );
+    }
+
+    #[test]
+    fn test_project_summaries() {
+        let mut db = create_test_database();
+        assert!(Database::project_summaries(db.connection())
+            .unwrap()
+            .is_empty());
+
+        let seeds = [
+            ("/a", 5000, Some("alpha"), 100),
+            ("/b", 5001, Some("alpha"), 300),
+            ("/c", 5002, None, 200),
+            ("/d", 5003, Some("beta"), 50),
+            ("/e", 5004, None, 400),
+        ];
+        for (path, port, project, used) in seeds {
+            let reservation = Reservation::builder(
+                ReservationKey::new(PathBuf::from(path), None).unwrap(),
+                Port::try_from(port).unwrap(),
+            )
+            .project(project.map(String::from))
+            .last_used_at(SystemTime::UNIX_EPOCH + Duration::from_secs(used))
+            .build()
+            .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let summaries = Database::project_summaries(db.connection()).unwrap();
+        let actual: Vec<_> = summaries
+            .iter()
+            .map(|s| {
+                (
+                    s.project.as_deref(),
+                    s.reservation_count,
+                    s.latest_used_at
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            actual,
+            vec![
+                (None, 2, 400),
+                (Some("alpha"), 2, 300),
+                (Some("beta"), 1, 50)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reservation_path_counts() {
+        let mut db = create_test_database();
+
+        for (tag, port) in [(None, 5000), (Some("web"), 5001)] {
+            let reservation = Reservation::builder(
+                ReservationKey::new(PathBuf::from("/shared"), tag.map(String::from)).unwrap(),
+                Port::try_from(port).unwrap(),
+            )
+            .build()
+            .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+        let other = create_test_reservation("/other", 5002);
+        db.create_reservation(&other).unwrap();
+
+        let counts = Database::reservation_path_counts(db.connection()).unwrap();
+        assert_eq!(
+            counts,
+            vec![(PathBuf::from("/other"), 1), (PathBuf::from("/shared"), 2)]
+        );
+    }
 }
@@ -0,0 +1,466 @@
+//! Portable JSON export and import of reservations.
+//!
+//! Unlike a raw copy of the `SQLite` file, an export document does not depend
+//! on the database schema, so it can move reservations between machines
+//! running different trop versions. A document carries a format version
+//! header and an array of reservation records:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "reservations": [
+//!     {"path": "/srv/app", "tag": "web", "port": 5000, "pinned": false,
+//!      "created_at": 1700000000, "last_used_at": 1700000000}
+//!   ]
+//! }
+//! ```
+//!
+//! Timestamps are Unix epoch seconds; `expires_at` and `note` appear only on
+//! reservations that have them. On import every record is rebuilt
+//! through [`Reservation::builder`], so malformed records are rejected with
+//! the index of the offending record before anything is written.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::{Port, Reservation, ReservationKey};
+
+use super::connection::Database;
+use super::operations::{systemtime_to_unix_secs, unix_secs_to_systemtime};
+
+/// The export format version written by this build.
+///
+/// Documents with a newer version are rejected on import.
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// A single reservation as it appears in an export document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRecord {
+    /// The reservation's directory path.
+    pub path: PathBuf,
+    /// The optional service tag.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// The reserved port.
+    pub port: u16,
+    /// The optional project identifier.
+    #[serde(default)]
+    pub project: Option<String>,
+    /// The optional task identifier.
+    #[serde(default)]
+    pub task: Option<String>,
+    /// Whether the reservation is pinned.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Creation time in Unix epoch seconds.
+    pub created_at: i64,
+    /// Last-used time in Unix epoch seconds.
+    pub last_used_at: i64,
+    /// TTL deadline in Unix epoch seconds, if the reservation has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    /// The optional free-text note.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// The cleanup priority.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl ExportRecord {
+    /// Creates a record from a reservation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a timestamp is before the Unix epoch.
+    pub fn from_reservation(reservation: &Reservation) -> Result<Self> {
+        Ok(Self {
+            path: reservation.key().path.clone(),
+            tag: reservation.key().tag.clone(),
+            port: reservation.port().value(),
+            project: reservation.project().map(String::from),
+            task: reservation.task().map(String::from),
+            pinned: reservation.pinned(),
+            created_at: systemtime_to_unix_secs(reservation.created_at())?,
+            last_used_at: systemtime_to_unix_secs(reservation.last_used_at())?,
+            expires_at: reservation
+                .expires_at()
+                .map(systemtime_to_unix_secs)
+                .transpose()?,
+            note: reservation.note().map(String::from),
+            priority: reservation.priority(),
+        })
+    }
+
+    /// Rebuilds the reservation, validating every field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port, path, tag, project, task, note, or a
+    /// timestamp is invalid.
+    pub fn to_reservation(&self) -> Result<Reservation> {
+        for (field, secs) in [
+            ("created_at", Some(self.created_at)),
+            ("last_used_at", Some(self.last_used_at)),
+            ("expires_at", self.expires_at),
+        ] {
+            let Some(secs) = secs else { continue };
+            if secs < 0 {
+                return Err(Error::Validation {
+                    field: field.into(),
+                    message: format!("timestamp {secs} is before the Unix epoch"),
+                });
+            }
+        }
+
+        let key = ReservationKey::new(self.path.clone(), self.tag.clone())?;
+        Ok(Reservation::builder(key, Port::try_from(self.port)?)
+            .project(self.project.clone())
+            .task(self.task.clone())
+            .pinned(self.pinned)
+            .created_at(unix_secs_to_systemtime(self.created_at))
+            .last_used_at(unix_secs_to_systemtime(self.last_used_at))
+            .expires_at(self.expires_at.map(unix_secs_to_systemtime))
+            .note(self.note.clone())
+            .priority(self.priority)
+            .build()?)
+    }
+}
+
+/// A versioned export document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportDocument {
+    /// The export format version.
+    pub version: u32,
+    /// The exported reservations.
+    pub reservations: Vec<ExportRecord>,
+}
+
+impl ExportDocument {
+    /// Creates a document containing the given reservations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a reservation timestamp is before the Unix epoch.
+    pub fn from_reservations(reservations: &[Reservation]) -> Result<Self> {
+        Ok(Self {
+            version: EXPORT_FORMAT_VERSION,
+            reservations: reservations
+                .iter()
+                .map(ExportRecord::from_reservation)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Creates a document containing every reservation in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reservations cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig, ExportDocument};
+    ///
+    /// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let document = ExportDocument::export(db.connection()).unwrap();
+    /// println!("{}", document.to_json().unwrap());
+    /// ```
+    pub fn export(conn: &Connection) -> Result<Self> {
+        Self::from_reservations(&Database::list_all_reservations(conn)?)
+    }
+
+    /// Serializes the document as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::Validation {
+            field: "export".into(),
+            message: format!("failed to serialize export: {e}"),
+        })
+    }
+
+    /// Parses a document from JSON text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not an export document or its version
+    /// is newer than [`EXPORT_FORMAT_VERSION`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::database::ExportDocument;
+    ///
+    /// let document = ExportDocument::from_json(r#"{"version": 1, "reservations": []}"#).unwrap();
+    /// assert!(document.reservations.is_empty());
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self> {
+        let document: Self = serde_json::from_str(json).map_err(|e| Error::Validation {
+            field: "import".into(),
+            message: format!("invalid export document: {e}"),
+        })?;
+
+        if document.version > EXPORT_FORMAT_VERSION {
+            return Err(Error::Validation {
+                field: "version".into(),
+                message: format!(
+                    "export format version {} is newer than supported version {}",
+                    document.version, EXPORT_FORMAT_VERSION
+                ),
+            });
+        }
+
+        Ok(document)
+    }
+
+    /// Validates every record and returns the reservations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error naming the first invalid record (by its
+    /// index in the array), or the first key that appears twice.
+    pub fn to_reservations(&self) -> Result<Vec<Reservation>> {
+        let mut seen = HashSet::new();
+        let mut reservations = Vec::with_capacity(self.reservations.len());
+
+        for (index, record) in self.reservations.iter().enumerate() {
+            let reservation = record.to_reservation().map_err(|e| Error::Validation {
+                field: format!("reservations[{index}]"),
+                message: e.to_string(),
+            })?;
+            if !seen.insert(reservation.key().clone()) {
+                return Err(Error::Validation {
+                    field: format!("reservations[{index}]"),
+                    message: format!("duplicate entry for {}", reservation.key()),
+                });
+            }
+            reservations.push(reservation);
+        }
+
+        Ok(reservations)
+    }
+
+    /// Imports the document's reservations into the database.
+    ///
+    /// A record conflicts if its key already has a reservation or its port is
+    /// held by a different key. Conflicting records are skipped, unless
+    /// `force` is set, in which case the conflicting reservations are
+    /// replaced. All records are validated before anything is written, and
+    /// the import runs in a savepoint, so a failure leaves the database
+    /// unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A record is invalid (see [`ExportDocument::to_reservations`])
+    /// - With `force`, a conflicting reservation is pinned
+    /// - A database operation fails
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig, ExportDocument};
+    ///
+    /// let mut db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let json = std::fs::read_to_string("/tmp/trop-export.json").unwrap();
+    /// let document = ExportDocument::from_json(&json).unwrap();
+    ///
+    /// let tx = db.begin_transaction().unwrap();
+    /// let summary = document.import(&tx, false).unwrap();
+    /// tx.commit().unwrap();
+    /// println!("created {}, skipped {}", summary.created, summary.skipped);
+    /// ```
+    pub fn import(&self, conn: &Connection, force: bool) -> Result<ImportSummary> {
+        let reservations = self.to_reservations()?;
+
+        Database::with_savepoint(conn, "trop_import", |conn| {
+            let mut summary = ImportSummary::default();
+            for reservation in &reservations {
+                let existing = Database::get_reservation(conn, reservation.key())?;
+                let holder = Database::get_reservation_by_port(conn, reservation.port())?
+                    .filter(|holder| holder.key() != reservation.key());
+
+                if existing.is_none() && holder.is_none() {
+                    Database::create_reservation_simple(conn, reservation)?;
+                    summary.created += 1;
+                    continue;
+                }
+
+                if !force {
+                    summary.skipped += 1;
+                    continue;
+                }
+
+                // A pinned reservation can't be replaced, even with force
+                for conflict in existing.iter().chain(holder.iter()) {
+                    conflict.ensure_unpinned()?;
+                }
+                if let Some(holder) = holder {
+                    Database::delete_reservation_simple(conn, holder.key())?;
+                }
+                Database::create_reservation_simple(conn, reservation)?;
+                summary.replaced += 1;
+            }
+            Ok(summary)
+        })
+    }
+}
+
+/// Counts of what an import did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Records created without conflict.
+    pub created: usize,
+    /// Records that replaced conflicting reservations (only with force).
+    pub replaced: usize,
+    /// Conflicting records left out (only without force).
+    pub skipped: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_util::{create_test_database, create_test_reservation};
+    use std::time::{Duration, SystemTime};
+
+    fn export_json(db: &Database) -> String {
+        ExportDocument::export(db.connection())
+            .unwrap()
+            .to_json()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let mut source = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/srv/app"), Some("web".into())).unwrap();
+        let reservation = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .project(Some("app".into()))
+            .task(Some("feature".into()))
+            .pinned(true)
+            .expires_at(Some(SystemTime::now() + Duration::from_secs(3600)))
+            .note(Some("shared staging box – ask before reusing".into()))
+            .priority(7)
+            .build()
+            .unwrap();
+        source.create_reservation(&reservation).unwrap();
+        source
+            .create_reservation(&create_test_reservation("/srv/other", 5001))
+            .unwrap();
+
+        let document = ExportDocument::from_json(&export_json(&source)).unwrap();
+        assert_eq!(document.version, EXPORT_FORMAT_VERSION);
+
+        let target = create_test_database();
+        let summary = document.import(target.connection(), false).unwrap();
+        assert_eq!(summary.created, 2);
+
+        let imported = Database::get_reservation(target.connection(), reservation.key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(imported.port(), reservation.port());
+        assert_eq!(imported.project(), Some("app"));
+        assert_eq!(imported.task(), Some("feature"));
+        assert!(imported.pinned());
+        assert!(imported.expires_at().is_some());
+        assert_eq!(imported.note(), reservation.note());
+        assert_eq!(imported.priority(), 7);
+        assert_eq!(
+            systemtime_to_unix_secs(imported.created_at()).unwrap(),
+            systemtime_to_unix_secs(reservation.created_at()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_import_skips_conflicts_without_force() {
+        let mut source = create_test_database();
+        source
+            .create_reservation(&create_test_reservation("/srv/app", 5000))
+            .unwrap();
+        source
+            .create_reservation(&create_test_reservation("/srv/new", 5001))
+            .unwrap();
+        let document = ExportDocument::from_json(&export_json(&source)).unwrap();
+
+        let mut target = create_test_database();
+        // Same key, different port
+        target
+            .create_reservation(&create_test_reservation("/srv/app", 6000))
+            .unwrap();
+        // Same port, different key
+        target
+            .create_reservation(&create_test_reservation("/srv/local", 5001))
+            .unwrap();
+
+        let summary = document.import(target.connection(), false).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                created: 0,
+                replaced: 0,
+                skipped: 2
+            }
+        );
+
+        let summary = document.import(target.connection(), true).unwrap();
+        assert_eq!(summary.replaced, 2);
+
+        let all = Database::list_all_reservations(target.connection()).unwrap();
+        assert_eq!(all.len(), 2);
+        let ports: HashSet<u16> = all.iter().map(|r| r.port().value()).collect();
+        assert_eq!(ports, HashSet::from([5000, 5001]));
+    }
+
+    #[test]
+    fn test_import_force_refuses_pinned_conflict() {
+        let mut source = create_test_database();
+        source
+            .create_reservation(&create_test_reservation("/srv/app", 5000))
+            .unwrap();
+        let document = ExportDocument::from_json(&export_json(&source)).unwrap();
+
+        let mut target = create_test_database();
+        let pinned = create_test_reservation("/srv/app", 6000);
+        target.create_reservation(&pinned).unwrap();
+        Database::set_pinned(target.connection(), pinned.key(), true).unwrap();
+
+        let result = document.import(target.connection(), true);
+        assert!(matches!(result, Err(Error::ReservationPinned { .. })));
+        let kept = Database::get_reservation(target.connection(), pinned.key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(kept.port().value(), 6000);
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_record_with_index() {
+        let json = r#"{"version": 1, "reservations": [
+            {"path": "/srv/ok", "port": 5000, "created_at": 0, "last_used_at": 0},
+            {"path": "/srv/bad", "port": 0, "created_at": 0, "last_used_at": 0}
+        ]}"#;
+        let document = ExportDocument::from_json(json).unwrap();
+
+        let db = create_test_database();
+        match document.import(db.connection(), false) {
+            Err(Error::Validation { field, .. }) => assert_eq!(field, "reservations[1]"),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+        assert!(Database::list_all_reservations(db.connection())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_from_json_rejects_newer_version() {
+        let result = ExportDocument::from_json(r#"{"version": 99, "reservations": []}"#);
+        assert!(matches!(result, Err(Error::Validation { .. })));
+    }
+}
@@ -7,7 +7,7 @@
 ///
 /// This version is stored in the metadata table and is used to ensure
 /// compatibility between the database and the application.
-pub const CURRENT_SCHEMA_VERSION: i32 = 1;
+pub const CURRENT_SCHEMA_VERSION: i32 = 5;
 
 /// SQL statement to create the metadata table.
 ///
@@ -24,7 +24,11 @@ pub const CREATE_METADATA_TABLE: &str = r"
 /// The reservations table stores all port reservations with their associated
 /// metadata. The primary key is the combination of (path, tag) to ensure
 /// uniqueness of reservations. The port column has a UNIQUE constraint to
-/// prevent duplicate port allocations under concurrent load.
+/// prevent duplicate port allocations under concurrent load. The pinned
+/// column marks reservations whose port must never be displaced,
+/// `expires_at` holds an optional per-reservation TTL deadline, `note`
+/// holds optional free text describing the reservation, and `priority`
+/// ranks how strongly the reservation resists cleanup.
 pub const CREATE_RESERVATIONS_TABLE: &str = r"
     CREATE TABLE IF NOT EXISTS reservations (
         path TEXT NOT NULL,
@@ -34,6 +38,10 @@ pub const CREATE_RESERVATIONS_TABLE: &str = r"
         task TEXT,
         created_at INTEGER NOT NULL,
         last_used_at INTEGER NOT NULL,
+        pinned INTEGER NOT NULL DEFAULT 0,
+        expires_at INTEGER,
+        note TEXT,
+        priority INTEGER NOT NULL DEFAULT 0,
         PRIMARY KEY (path, tag)
     )";
 
@@ -69,10 +77,24 @@ pub const INSERT_SCHEMA_VERSION: &str =
 /// different reservation key fails instead of deleting that unrelated row.
 pub const INSERT_RESERVATION: &str = r"
     INSERT INTO reservations
-    (path, tag, port, project, task, created_at, last_used_at)
-    VALUES (?, ?, ?, ?, ?, ?, ?)
+    (path, tag, port, project, task, created_at, last_used_at, pinned, expires_at, note, priority)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 ";
 
+/// SQL statement to add the pinned column to a version 1 reservations table.
+pub const ADD_PINNED_COLUMN: &str =
+    "ALTER TABLE reservations ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0";
+
+/// SQL statement to add the `expires_at` column to a version 2 reservations table.
+pub const ADD_EXPIRES_AT_COLUMN: &str = "ALTER TABLE reservations ADD COLUMN expires_at INTEGER";
+
+/// SQL statement to add the note column to a version 3 reservations table.
+pub const ADD_NOTE_COLUMN: &str = "ALTER TABLE reservations ADD COLUMN note TEXT";
+
+/// SQL statement to add the priority column to a version 4 reservations table.
+pub const ADD_PRIORITY_COLUMN: &str =
+    "ALTER TABLE reservations ADD COLUMN priority INTEGER NOT NULL DEFAULT 0";
+
 /// SQL statement to delete a reservation by key.
 ///
 /// Used by both single and batch delete operations.
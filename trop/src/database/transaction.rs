@@ -2,7 +2,7 @@
 //!
 //! This module provides transaction helpers for complex database operations.
 
-use rusqlite::{params, TransactionBehavior};
+use rusqlite::{params, Transaction, TransactionBehavior};
 
 use crate::error::Result;
 use crate::{Reservation, ReservationKey};
@@ -12,6 +12,58 @@ use super::operations::systemtime_to_unix_secs;
 use super::schema::{DELETE_RESERVATION, INSERT_RESERVATION};
 
 impl Database {
+    /// Runs `f` inside an IMMEDIATE transaction.
+    ///
+    /// The transaction commits if `f` returns `Ok` and rolls back if it
+    /// returns `Err` or panics, so a group of writes either all persist or
+    /// none do. The transaction dereferences to a [`rusqlite::Connection`],
+    /// so the connection-taking operations such as
+    /// [`Database::create_reservation_simple`] can be used inside `f`.
+    ///
+    /// IMMEDIATE transactions take the write lock when they begin. Other
+    /// writers are serialized behind it, while readers in WAL mode keep
+    /// seeing the last committed state until this one commits. If another
+    /// connection holds the write lock, beginning (or committing) waits up
+    /// to the configured busy timeout before failing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::LockTimeout` if the write lock is not acquired within
+    /// the busy timeout, the error returned by `f`, or an error if the
+    /// transaction cannot be committed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::database::{Database, DatabaseConfig};
+    /// use trop::{Reservation, ReservationKey, Port};
+    /// use std::path::PathBuf;
+    ///
+    /// let mut db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    ///
+    /// db.transaction(|tx| {
+    ///     for (path, port) in [("/web", 8080), ("/api", 8081)] {
+    ///         let key = ReservationKey::new(PathBuf::from(path), None)?;
+    ///         let reservation = Reservation::builder(key, Port::try_from(port)?).build()?;
+    ///         Database::create_reservation_simple(tx, &reservation)?;
+    ///     }
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn transaction<T>(&mut self, f: impl FnOnce(&Transaction) -> Result<T>) -> Result<T> {
+        // `&mut self` already guarantees exclusive use of the connection,
+        // which is what the checked constructor would enforce
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)
+            .map_err(|e| self.lock_error(e))?;
+
+        // Dropping an uncommitted transaction, including while unwinding
+        // from a panic in `f`, rolls it back
+        let value = f(&tx)?;
+        tx.commit().map_err(|e| self.lock_error(e))?;
+        Ok(value)
+    }
+
     /// Creates multiple reservations in a single transaction.
     ///
     /// This operation is atomic - either all reservations are created
@@ -64,6 +116,10 @@ impl Database {
 
                 let created_secs = systemtime_to_unix_secs(reservation.created_at())?;
                 let last_used_secs = systemtime_to_unix_secs(reservation.last_used_at())?;
+                let expires_secs = reservation
+                    .expires_at()
+                    .map(systemtime_to_unix_secs)
+                    .transpose()?;
 
                 insert_stmt.execute(params![
                     reservation.key().path.to_string_lossy().to_string(),
@@ -73,6 +129,10 @@ impl Database {
                     reservation.task(),
                     created_secs,
                     last_used_secs,
+                    reservation.pinned(),
+                    expires_secs,
+                    reservation.note(),
+                    reservation.priority(),
                 ])?;
             }
         }
@@ -244,4 +304,67 @@ mod tests {
         assert_eq!(all[0].key().path, PathBuf::from("/existing"));
         assert_eq!(all[0].port().value(), 5000);
     }
+
+    #[test]
+    fn test_transaction_commits_on_ok() {
+        let mut db = create_test_database();
+
+        let count = db
+            .transaction(|tx| {
+                Database::create_reservation_simple(tx, &create_test_reservation("/a", 5000))?;
+                Database::create_reservation_simple(tx, &create_test_reservation("/b", 5001))?;
+                Ok(2)
+            })
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_on_err() {
+        let mut db = create_test_database();
+
+        let result: Result<()> = db.transaction(|tx| {
+            Database::create_reservation_simple(tx, &create_test_reservation("/a", 5000))?;
+            Database::create_reservation_simple(tx, &create_test_reservation("/b", 5001))?;
+            Err(crate::Error::Validation {
+                field: "test".to_string(),
+                message: "abort".to_string(),
+            })
+        });
+        assert!(result.is_err());
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert!(all.is_empty());
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_on_panic() {
+        let mut db = create_test_database();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            db.transaction(|tx| -> Result<()> {
+                Database::create_reservation_simple(tx, &create_test_reservation("/a", 5000))?;
+                panic!("abort");
+            })
+        }));
+        assert!(result.is_err());
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert!(all.is_empty());
+
+        // The connection is usable again afterwards
+        db.transaction(|tx| {
+            Database::create_reservation_simple(tx, &create_test_reservation("/b", 5001))
+        })
+        .unwrap();
+        assert_eq!(
+            Database::list_all_reservations(db.connection())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
 }
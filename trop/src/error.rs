@@ -143,6 +143,15 @@ pub enum Error {
         details: String,
     },
 
+    /// Attempted to move or release a pinned reservation.
+    #[error("reservation {key} is pinned to port {port} (run 'trop unpin' first)")]
+    ReservationPinned {
+        /// The pinned reservation's key.
+        key: String,
+        /// The port the reservation is pinned to.
+        port: crate::port::Port,
+    },
+
     /// A path does not exist.
     #[error("path not found: {}", path.display())]
     PathNotFound {
@@ -172,12 +181,37 @@ pub enum Error {
     },
 
     /// No ports are available in the specified range.
-    #[error("port range {range} exhausted{}", if *.tried_cleanup { " after cleanup" } else { "" })]
+    #[error(
+        "port range {range} exhausted{}{}{}",
+        if *.tried_cleanup { " after cleanup" } else { "" },
+        .usage.map(|u| format!(" ({u})")).unwrap_or_default(),
+        .requested.map(|(port, reason)| format!(
+            "; {} port {port} is {reason}",
+            if *.requested_is_preferred { "preferred" } else { "requested" }
+        )).unwrap_or_default()
+    )]
     PortExhausted {
         /// The port range that was exhausted.
         range: crate::port::PortRange,
         /// Whether cleanup was attempted.
         tried_cleanup: bool,
+        /// Why the scanned ports were unavailable, if known.
+        usage: Option<crate::port::allocator::RangeUsage>,
+        /// The explicitly requested port and why it couldn't be used, if one
+        /// was requested.
+        requested: Option<(crate::port::Port, PortUnavailableReason)>,
+        /// Whether `requested` was a fallible preference (`--preferred`)
+        /// rather than a port asked for outright (`--port`).
+        requested_is_preferred: bool,
+    },
+
+    /// No run of consecutive available ports is long enough for a block.
+    #[error("no block of {count} consecutive available ports in range {range}")]
+    NoContiguousBlock {
+        /// The number of consecutive ports requested.
+        count: usize,
+        /// The port range that was searched.
+        range: crate::port::PortRange,
     },
 
     /// Port occupancy check failed.
@@ -218,6 +252,8 @@ pub enum PortUnavailableReason {
     Excluded,
     /// Port is currently occupied on the system.
     Occupied,
+    /// Port is outside the configured port range.
+    OutOfRange,
 }
 
 impl std::fmt::Display for PortUnavailableReason {
@@ -226,6 +262,7 @@ impl std::fmt::Display for PortUnavailableReason {
             Self::Reserved => write!(f, "reserved"),
             Self::Excluded => write!(f, "excluded"),
             Self::Occupied => write!(f, "occupied"),
+            Self::OutOfRange => write!(f, "out of range"),
         }
     }
 }
@@ -292,6 +329,109 @@ impl Error {
     pub fn is_permission_denied(&self) -> bool {
         matches!(self, Self::PermissionDenied { .. })
     }
+
+    /// Check if error is transient lock contention worth retrying.
+    ///
+    /// This covers [`Error::LockTimeout`] and `SQLite` busy or locked
+    /// errors that were not converted into one. Other errors are permanent:
+    /// retrying the same operation would fail the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::Error;
+    ///
+    /// assert!(Error::LockTimeout { seconds: 5 }.is_retryable());
+    /// assert!(!Error::Validation {
+    ///     field: "port".into(),
+    ///     message: "out of range".into(),
+    /// }
+    /// .is_retryable());
+    /// ```
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::LockTimeout { .. } => true,
+            Self::Database(e) => matches!(
+                e.sqlite_error_code(),
+                Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Returns why a specific port couldn't be used, if this error is about one.
+    ///
+    /// This is the reason a preferred port was rejected, or the reason the
+    /// explicitly requested port was passed over before a range was exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::{Error, Port, PortUnavailableReason};
+    ///
+    /// let err = Error::PreferredPortUnavailable {
+    ///     port: Port::try_from(8080).unwrap(),
+    ///     reason: PortUnavailableReason::Occupied,
+    /// };
+    /// assert_eq!(err.port_unavailable_reason(), Some(PortUnavailableReason::Occupied));
+    /// ```
+    #[must_use]
+    pub const fn port_unavailable_reason(&self) -> Option<PortUnavailableReason> {
+        match self {
+            Self::PreferredPortUnavailable { reason, .. }
+            | Self::PortExhausted {
+                requested: Some((_, reason)),
+                ..
+            } => Some(*reason),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of this error's variant, for machine-readable output.
+    ///
+    /// The name is stable across releases and never includes the error's
+    /// payload.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::Error;
+    ///
+    /// let err = Error::NotFound { resource: "reservation".to_string() };
+    /// assert_eq!(err.kind(), "NotFound");
+    /// ```
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::InvalidPort { .. } => "InvalidPort",
+            Self::InvalidPath { .. } => "InvalidPath",
+            Self::Database(_) => "Database",
+            Self::Configuration(_) => "Configuration",
+            Self::Io(_) => "Io",
+            Self::Validation { .. } => "Validation",
+            Self::PortUnavailable { .. } => "PortUnavailable",
+            Self::ReservationConflict { .. } => "ReservationConflict",
+            Self::NotFound { .. } => "NotFound",
+            Self::InvalidPortRange { .. } => "InvalidPortRange",
+            Self::LockTimeout { .. } => "LockTimeout",
+            Self::DataDirectoryNotFound { .. } => "DataDirectoryNotFound",
+            Self::DatabaseCorruption { .. } => "DatabaseCorruption",
+            Self::UnsupportedSchemaVersion { .. } => "UnsupportedSchemaVersion",
+            Self::UnrelatedPath { .. } => "UnrelatedPath",
+            Self::StickyFieldChange { .. } => "StickyFieldChange",
+            Self::ReservationPinned { .. } => "ReservationPinned",
+            Self::PathNotFound { .. } => "PathNotFound",
+            Self::PermissionDenied { .. } => "PermissionDenied",
+            Self::SymlinkLoop { .. } => "SymlinkLoop",
+            Self::PathRelationshipViolation { .. } => "PathRelationshipViolation",
+            Self::PortExhausted { .. } => "PortExhausted",
+            Self::NoContiguousBlock { .. } => "NoContiguousBlock",
+            Self::OccupancyCheckFailed { .. } => "OccupancyCheckFailed",
+            Self::PreferredPortUnavailable { .. } => "PreferredPortUnavailable",
+            Self::GroupAllocationFailed { .. } => "GroupAllocationFailed",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -354,6 +494,18 @@ mod tests {
         assert!(display.contains("already reserved"));
     }
 
+    #[test]
+    fn test_reservation_pinned_error() {
+        let err = Error::ReservationPinned {
+            key: "/path:web".to_string(),
+            port: crate::port::Port::try_from(5000).unwrap(),
+        };
+        let display = format!("{err}");
+        assert!(display.contains("pinned to port 5000"));
+        assert!(display.contains("/path:web"));
+        assert!(display.contains("unpin"));
+    }
+
     #[test]
     fn test_not_found_error() {
         let err = Error::NotFound {
@@ -384,6 +536,27 @@ mod tests {
         assert!(display.contains('5'));
     }
 
+    #[test]
+    fn test_is_retryable() {
+        assert!(Error::LockTimeout { seconds: 5 }.is_retryable());
+
+        let busy = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            None,
+        );
+        assert!(Error::Database(busy).is_retryable());
+
+        let constraint = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT),
+            None,
+        );
+        assert!(!Error::Database(constraint).is_retryable());
+        assert!(!Error::NotFound {
+            resource: "reservation".into()
+        }
+        .is_retryable());
+    }
+
     #[test]
     fn test_data_directory_not_found_error() {
         let err = Error::DataDirectoryNotFound {
@@ -458,4 +631,16 @@ mod tests {
 
         assert!(returns_result().is_err());
     }
+
+    #[test]
+    fn test_error_kind_is_variant_name() {
+        let err = Error::Validation {
+            field: "count".to_string(),
+            message: "secret details".to_string(),
+        };
+        assert_eq!(err.kind(), "Validation");
+
+        let io_err: Error = std::io::Error::other("boom").into();
+        assert_eq!(io_err.kind(), "Io");
+    }
 }
@@ -14,7 +14,7 @@
 //! - [`Port`] and [`PortRange`]: Network port types with validation
 //! - [`Reservation`] and [`ReservationKey`]: Port reservation tracking
 //! - [`Error`] and [`Result`]: Error handling types
-//! - [`Logger`] and [`LogLevel`]: Logging infrastructure
+//! - [`Logger`] and [`LogLevel`]: Logging infrastructure, plus [`PhaseTimings`] for `--verbose` timing
 //!
 //! ## Examples
 //!
@@ -46,13 +46,14 @@ pub mod reservation;
 pub use config::{Config, ConfigBuilder};
 pub use database::{Database, DatabaseConfig};
 pub use error::{Error, PortUnavailableReason, Result};
-pub use logging::{init_logger, LogLevel, Logger};
+pub use logging::{init_logger, LogLevel, Logger, PhaseTimings};
 pub use operations::{
-    execute_migrate, init_database, AutocleanResult, AutoreserveOptions, AutoreservePlan,
-    CleanupOperations, ExecutionResult, ExpireResult, InitOptions, InitResult, MigrateOptions,
-    MigratePlan, MigrateResult, MigrationItem, OperationPlan, PlanAction, PlanExecutor,
-    PruneResult, ReleaseOptions, ReleasePlan, ReserveGroupOptions, ReserveGroupPlan,
-    ReserveOptions, ReservePlan,
+    execute_migrate, execute_rehome, init_database, AllocationStrategy, AutocleanResult,
+    AutoreserveOptions, AutoreservePlan, BulkReleaseOptions, BulkReleasePlan, CleanupOperations,
+    ExecutionResult, ExpireResult, InitOptions, InitResult, MigrateOptions, MigratePlan,
+    MigrateResult, MigrationItem, MoveOptions, MovePlan, OperationPlan, PlanAction, PlanExecutor,
+    PruneResult, RehomeOptions, RehomePlan, RehomeResult, ReleaseOptions, ReleasePlan,
+    RenewOptions, RenewPlan, ReserveGroupOptions, ReserveGroupPlan, ReserveOptions, ReservePlan,
 };
 pub use path::{PathProvenance, PathRelationship, PathResolver};
 pub use port::{Port, PortRange};
@@ -1,10 +1,15 @@
 //! Logging infrastructure for the trop library.
 //!
 //! This module provides a simple stderr-based logging system with
-//! configurable log levels.
+//! configurable log levels, optionally redirected to a log file.
 
 use std::env;
 use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Logging level for controlling output verbosity.
 ///
@@ -73,7 +78,8 @@ impl LogLevel {
 /// A simple stderr-based logger.
 ///
 /// The logger respects the configured log level and only outputs messages
-/// at or above that level.
+/// at or above that level. With [`Logger::with_file`], messages go to a log
+/// file instead of stderr.
 ///
 /// # Examples
 ///
@@ -84,8 +90,10 @@ impl LogLevel {
 /// logger.error("This is an error message");
 /// logger.info("This will not be printed (requires Verbose)");
 /// ```
+#[derive(Debug)]
 pub struct Logger {
     level: LogLevel,
+    file: Option<Mutex<File>>,
 }
 
 impl Logger {
@@ -100,7 +108,21 @@ impl Logger {
     /// ```
     #[must_use]
     pub const fn new(level: LogLevel) -> Self {
-        Self { level }
+        Self { level, file: None }
+    }
+
+    /// Redirects log output to the file at `path`.
+    ///
+    /// The file is created if missing and appended to otherwise, so several
+    /// runs can share one log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened for appending.
+    pub fn with_file(mut self, path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        self.file = Some(Mutex::new(file));
+        Ok(self)
     }
 
     /// Returns the current log level.
@@ -109,6 +131,26 @@ impl Logger {
         self.level
     }
 
+    /// Returns whether output goes to a log file rather than stderr.
+    #[must_use]
+    pub const fn has_file(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Writes one line to the log file, or to stderr without one.
+    fn emit(&self, prefix: &str, message: &str) {
+        match &self.file {
+            Some(file) => {
+                let mut file = file
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                // Logging must never fail the operation being logged
+                let _ = writeln!(file, "{prefix}: {message}");
+            }
+            None => eprintln!("{prefix}: {message}"),
+        }
+    }
+
     /// Logs an error message.
     ///
     /// Error messages are always displayed unless the level is Quiet.
@@ -123,7 +165,7 @@ impl Logger {
     /// ```
     pub fn error(&self, message: &str) {
         if self.level >= LogLevel::Normal {
-            eprintln!("ERROR: {message}");
+            self.emit("ERROR", message);
         }
     }
 
@@ -141,7 +183,7 @@ impl Logger {
     /// ```
     pub fn warn(&self, message: &str) {
         if self.level >= LogLevel::Normal {
-            eprintln!("WARN: {message}");
+            self.emit("WARN", message);
         }
     }
 
@@ -159,7 +201,7 @@ impl Logger {
     /// ```
     pub fn info(&self, message: &str) {
         if self.level >= LogLevel::Verbose {
-            eprintln!("INFO: {message}");
+            self.emit("INFO", message);
         }
     }
 
@@ -177,7 +219,25 @@ impl Logger {
     /// ```
     pub fn debug(&self, message: &str) {
         if self.level >= LogLevel::Verbose {
-            eprintln!("DEBUG: {message}");
+            self.emit("DEBUG", message);
+        }
+    }
+
+    /// Logs a `TIMING:` line for a phase of the current operation.
+    ///
+    /// Timing lines are only displayed at Verbose level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::{Logger, LogLevel};
+    ///
+    /// let logger = Logger::new(LogLevel::Verbose);
+    /// logger.timing("plan build: 0.412ms");
+    /// ```
+    pub fn timing(&self, message: &str) {
+        if self.level >= LogLevel::Verbose {
+            self.emit("TIMING", message);
         }
     }
 }
@@ -188,17 +248,101 @@ impl Default for Logger {
     }
 }
 
+/// Records how long each phase of an operation took.
+///
+/// Commands create one per invocation, time their phases with
+/// [`PhaseTimings::time`] (or hand it to a
+/// [`PlanExecutor`](crate::operations::PlanExecutor)), and print the
+/// breakdown with [`PhaseTimings::report`]. A disabled recorder runs the
+/// phases without timing them and reports nothing.
+///
+/// # Examples
+///
+/// ```
+/// use trop::PhaseTimings;
+///
+/// let mut timings = PhaseTimings::new(true);
+/// let answer = timings.time("config load", || 42);
+/// assert_eq!(answer, 42);
+/// assert_eq!(timings.phases()[0].0, "config load");
+///
+/// let mut disabled = PhaseTimings::new(false);
+/// disabled.time("config load", || ());
+/// assert!(disabled.phases().is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PhaseTimings {
+    enabled: bool,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl PhaseTimings {
+    /// Creates a recorder that times phases only when `enabled`.
+    #[must_use]
+    pub const fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            phases: Vec::new(),
+        }
+    }
+
+    /// Returns whether phases are being timed.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Runs `f`, recording its duration under `phase`.
+    pub fn time<T>(&mut self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+        let started = Instant::now();
+        let value = f();
+        self.record(phase, started.elapsed());
+        value
+    }
+
+    /// Records that `phase` took `elapsed`.
+    pub fn record(&mut self, phase: &'static str, elapsed: Duration) {
+        if self.enabled {
+            self.phases.push((phase, elapsed));
+        }
+    }
+
+    /// Returns the recorded phases in the order they ran.
+    #[must_use]
+    pub fn phases(&self) -> &[(&'static str, Duration)] {
+        &self.phases
+    }
+
+    /// Writes one `TIMING:` line per recorded phase through `logger`.
+    ///
+    /// The lines go to the logger's file if it has one, else to stderr.
+    pub fn report(&self, logger: &Logger) {
+        for (phase, elapsed) in &self.phases {
+            logger.timing(&format!("{phase}: {:.3}ms", elapsed.as_secs_f64() * 1000.0));
+        }
+    }
+}
+
 /// Initializes a logger based on environment variables and CLI flags.
 ///
-/// The priority order is:
+/// The level's priority order is:
 /// 1. CLI flags (verbose/quiet)
 /// 2. `TROP_LOG_MODE` environment variable
 /// 3. Default (Normal)
 ///
+/// Output goes to `log_file` if given, else to the `TROP_LOG_FILE`
+/// environment variable's path if set, else to stderr. A log file that
+/// cannot be opened produces a warning on stderr and logging falls back to
+/// stderr rather than failing.
+///
 /// # Arguments
 ///
 /// * `verbose` - If true, sets level to Verbose
 /// * `quiet` - If true, sets level to Quiet
+/// * `log_file` - Optional path of a file to append log lines to
 ///
 /// If both `verbose` and `quiet` are true, `verbose` takes precedence.
 ///
@@ -208,33 +352,53 @@ impl Default for Logger {
 /// use trop::init_logger;
 ///
 /// // Use default (Normal) level
-/// let logger = init_logger(false, false);
+/// let logger = init_logger(false, false, None);
 ///
 /// // Force verbose
-/// let logger = init_logger(true, false);
+/// let logger = init_logger(true, false, None);
 ///
 /// // Force quiet
-/// let logger = init_logger(false, true);
+/// let logger = init_logger(false, true, None);
 /// ```
 #[must_use]
-pub fn init_logger(verbose: bool, quiet: bool) -> Logger {
+pub fn init_logger(verbose: bool, quiet: bool, log_file: Option<&Path>) -> Logger {
+    let logger = Logger::new(resolve_log_level(verbose, quiet));
+
+    let env_file = env::var_os("TROP_LOG_FILE").filter(|value| !value.is_empty());
+    let Some(path) = log_file.or(env_file.as_deref().map(Path::new)) else {
+        return logger;
+    };
+
+    let level = logger.level();
+    logger.with_file(path).unwrap_or_else(|e| {
+        let fallback = Logger::new(level);
+        fallback.warn(&format!(
+            "cannot open log file {}: {e}; logging to stderr",
+            path.display()
+        ));
+        fallback
+    })
+}
+
+/// Resolves the log level from CLI flags, then `TROP_LOG_MODE`.
+fn resolve_log_level(verbose: bool, quiet: bool) -> LogLevel {
     // CLI flags take precedence
     if verbose {
-        return Logger::new(LogLevel::Verbose);
+        return LogLevel::Verbose;
     }
     if quiet {
-        return Logger::new(LogLevel::Quiet);
+        return LogLevel::Quiet;
     }
 
     // Check environment variable
     if let Ok(env_value) = env::var("TROP_LOG_MODE") {
         if let Ok(level) = LogLevel::parse(&env_value) {
-            return Logger::new(level);
+            return level;
         }
     }
 
     // Default to Normal
-    Logger::new(LogLevel::Normal)
+    LogLevel::Normal
 }
 
 #[cfg(test)]
@@ -291,7 +455,7 @@ mod tests {
         // Clear env var for this test
         env::remove_var("TROP_LOG_MODE");
 
-        let logger = init_logger(false, false);
+        let logger = init_logger(false, false, None);
         assert_eq!(logger.level(), LogLevel::Normal);
 
         // Restore env var if it existed
@@ -302,19 +466,19 @@ mod tests {
 
     #[test]
     fn test_init_logger_verbose_flag() {
-        let logger = init_logger(true, false);
+        let logger = init_logger(true, false, None);
         assert_eq!(logger.level(), LogLevel::Verbose);
     }
 
     #[test]
     fn test_init_logger_quiet_flag() {
-        let logger = init_logger(false, true);
+        let logger = init_logger(false, true, None);
         assert_eq!(logger.level(), LogLevel::Quiet);
     }
 
     #[test]
     fn test_init_logger_verbose_takes_precedence() {
-        let logger = init_logger(true, true);
+        let logger = init_logger(true, true, None);
         assert_eq!(logger.level(), LogLevel::Verbose);
     }
 
@@ -324,11 +488,11 @@ mod tests {
         let saved_env = env::var("TROP_LOG_MODE").ok();
 
         env::set_var("TROP_LOG_MODE", "verbose");
-        let logger = init_logger(false, false);
+        let logger = init_logger(false, false, None);
         assert_eq!(logger.level(), LogLevel::Verbose);
 
         env::set_var("TROP_LOG_MODE", "quiet");
-        let logger = init_logger(false, false);
+        let logger = init_logger(false, false, None);
         assert_eq!(logger.level(), LogLevel::Quiet);
 
         // Restore env var if it existed, or remove if it didn't
@@ -344,7 +508,7 @@ mod tests {
         let saved_env = env::var("TROP_LOG_MODE").ok();
 
         env::set_var("TROP_LOG_MODE", "invalid");
-        let logger = init_logger(false, false);
+        let logger = init_logger(false, false, None);
         // Should fall back to default (Normal)
         assert_eq!(logger.level(), LogLevel::Normal);
 
@@ -361,7 +525,7 @@ mod tests {
         let saved_env = env::var("TROP_LOG_MODE").ok();
 
         env::set_var("TROP_LOG_MODE", "normal");
-        let logger = init_logger(true, false);
+        let logger = init_logger(true, false, None);
         // CLI flag should override env
         assert_eq!(logger.level(), LogLevel::Verbose);
 
@@ -375,4 +539,69 @@ mod tests {
     // Note: We can't easily test the actual output of the logging methods
     // without capturing stderr, which is complex in unit tests. The methods
     // are simple enough that visual/integration testing is more appropriate.
+    // File output is easy to inspect, though.
+
+    #[test]
+    fn test_logger_file_respects_level() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trop.log");
+
+        let logger = Logger::new(LogLevel::Normal).with_file(&path).unwrap();
+        assert!(logger.has_file());
+        logger.warn("first");
+        logger.info("hidden at normal");
+        drop(logger);
+
+        // A second logger appends rather than truncating
+        let logger = Logger::new(LogLevel::Verbose).with_file(&path).unwrap();
+        logger.debug("second");
+        drop(logger);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "WARN: first\nDEBUG: second\n");
+    }
+
+    #[test]
+    fn test_init_logger_unopenable_file_falls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("trop.log");
+
+        let logger = init_logger(false, true, Some(&path));
+        assert!(!logger.has_file());
+        assert_eq!(logger.level(), LogLevel::Quiet);
+    }
+
+    #[test]
+    fn test_phase_timings_records_in_order() {
+        let mut timings = PhaseTimings::new(true);
+        timings.time("config load", || ());
+        timings.record("plan execute", Duration::from_millis(5));
+
+        let phases: Vec<&str> = timings.phases().iter().map(|(phase, _)| *phase).collect();
+        assert_eq!(phases, ["config load", "plan execute"]);
+        assert_eq!(timings.phases()[1].1, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_phase_timings_disabled_records_nothing() {
+        let mut timings = PhaseTimings::new(false);
+        assert_eq!(timings.time("config load", || 7), 7);
+        timings.record("plan execute", Duration::from_millis(5));
+        assert!(timings.phases().is_empty());
+    }
+
+    #[test]
+    fn test_phase_timings_report_to_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trop.log");
+        let logger = Logger::new(LogLevel::Verbose).with_file(&path).unwrap();
+
+        let mut timings = PhaseTimings::new(true);
+        timings.record("plan execute", Duration::from_millis(5));
+        timings.report(&logger);
+        drop(logger);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "TIMING: plan execute: 5.000ms\n");
+    }
 }
@@ -5,10 +5,11 @@
 
 use std::path::PathBuf;
 
-use crate::config::ConfigLoader;
+use crate::config::{ConfigLoader, TagCase};
 use crate::error::{Error, Result};
 use rusqlite::Connection;
 
+use super::hook::PreReserveHook;
 use super::plan::OperationPlan;
 use super::reserve_group::{ReserveGroupOptions, ReserveGroupPlan};
 
@@ -36,6 +37,16 @@ pub struct AutoreserveOptions {
 
     /// Allow changing the task field.
     pub allow_task_change: bool,
+
+    /// Case sensitivity applied to service tags (unless the group's config
+    /// file sets its own `tag_case`).
+    pub tag_case: TagCase,
+
+    /// Which named reservation group to reserve.
+    pub group: Option<String>,
+
+    /// Hook asked about every port the group would get.
+    pub pre_reserve_hook: Option<PreReserveHook>,
 }
 
 impl AutoreserveOptions {
@@ -61,6 +72,9 @@ impl AutoreserveOptions {
             allow_unrelated_path: false,
             allow_project_change: false,
             allow_task_change: false,
+            tag_case: TagCase::Sensitive,
+            group: None,
+            pre_reserve_hook: None,
         }
     }
 
@@ -98,6 +112,27 @@ impl AutoreserveOptions {
         self.allow_task_change = allow;
         self
     }
+
+    /// Sets the tag case sensitivity.
+    #[must_use]
+    pub const fn with_tag_case(mut self, tag_case: TagCase) -> Self {
+        self.tag_case = tag_case;
+        self
+    }
+
+    /// Sets the name of the reservation group to reserve.
+    #[must_use]
+    pub fn with_group(mut self, group: Option<String>) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// Sets the pre-reserve hook consulted for each allocated port.
+    #[must_use]
+    pub fn with_pre_reserve_hook(mut self, hook: Option<PreReserveHook>) -> Self {
+        self.pre_reserve_hook = hook;
+        self
+    }
 }
 
 /// An autoreserve plan generator.
@@ -170,6 +205,8 @@ impl AutoreservePlan {
     ///
     /// Returns an error if:
     /// - The config does not contain a reservation group
+    /// - The requested group is not defined, or several groups are defined
+    ///   and none was requested
     /// - The reservation group is invalid
     /// - Group allocation validation fails
     ///
@@ -194,6 +231,14 @@ impl AutoreservePlan {
             allow_unrelated_path: self.options.allow_unrelated_path,
             allow_project_change: self.options.allow_project_change,
             allow_task_change: self.options.allow_task_change,
+            tag_case: self.options.tag_case,
+            exclusions: Vec::new(),
+            group: self.options.group.clone(),
+            tag_prefix: None,
+            tag_suffix: None,
+            only: Vec::new(),
+            except: Vec::new(),
+            pre_reserve_hook: self.options.pre_reserve_hook.clone(),
         };
 
         // Delegate to ReserveGroupPlan
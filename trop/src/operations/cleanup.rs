@@ -2,9 +2,12 @@
 //!
 //! This module provides operations for cleaning up reservations in two ways:
 //! 1. **Pruning**: Remove reservations for paths that no longer exist on the filesystem
-//! 2. **Expiring**: Remove reservations that haven't been used within a time threshold
+//! 2. **Expiring**: Remove reservations that haven't been used within a time threshold,
+//!    or whose own TTL has run out
 //!
 //! All cleanup operations support dry-run mode for previewing changes before applying them.
+//! Pinned reservations are never removed by cleanup, and expiry also keeps reservations
+//! whose priority is above the configured `protect_priority` threshold.
 //!
 //! ## Transactional Semantics
 //!
@@ -13,12 +16,14 @@
 //! error occurs midway through a cleanup, earlier deletions will have been committed.
 
 use std::fs;
-use std::path::Path;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 use crate::config::CleanupConfig;
 use crate::database::Database;
-use crate::{Reservation, Result};
+use crate::{PathRelationship, Reservation, Result};
 
 /// Number of seconds in a day, used for expiration calculations.
 const SECONDS_PER_DAY: u64 = 86400;
@@ -34,6 +39,57 @@ pub struct PruneResult {
     pub removed_reservations: Vec<Reservation>,
 }
 
+/// Limits which reservations a prune considers.
+///
+/// The default scope covers every reservation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneScope {
+    /// Only consider reservations at or below this path.
+    pub path: Option<PathBuf>,
+    /// Only consider reservations belonging to this project.
+    pub project: Option<String>,
+}
+
+impl PruneScope {
+    /// Creates a scope from an optional path subtree and project.
+    #[must_use]
+    pub const fn new(path: Option<PathBuf>, project: Option<String>) -> Self {
+        Self { path, project }
+    }
+
+    /// Returns true if `reservation` falls within this scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use trop::operations::PruneScope;
+    /// use trop::{Port, Reservation, ReservationKey};
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/work/app/api"), None).unwrap();
+    /// let reservation = Reservation::builder(key, Port::try_from(8080).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(PruneScope::new(Some(PathBuf::from("/work/app")), None).contains(&reservation));
+    /// assert!(!PruneScope::new(Some(PathBuf::from("/work/other")), None).contains(&reservation));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, reservation: &Reservation) -> bool {
+        let in_path = self.path.as_deref().map_or(true, |path| {
+            matches!(
+                PathRelationship::between(path, &reservation.key().path),
+                PathRelationship::Same | PathRelationship::Ancestor
+            )
+        });
+        let in_project = self
+            .project
+            .as_deref()
+            .map_or(true, |project| reservation.project() == Some(project));
+        in_path && in_project
+    }
+}
+
 /// Result of an expire operation.
 ///
 /// Expiring removes reservations that haven't been used within a configured time threshold.
@@ -48,6 +104,9 @@ pub struct ExpireResult {
 /// Result of an autoclean operation.
 ///
 /// Autoclean combines both pruning and expiring in a single operation.
+///
+/// It serializes as a summary for logging: the counts, plus the keys of the
+/// pruned and expired reservations in their string form (`path#tag`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AutocleanResult {
     /// Number of reservations pruned.
@@ -62,6 +121,65 @@ pub struct AutocleanResult {
     pub expired_reservations: Vec<Reservation>,
 }
 
+impl AutocleanResult {
+    /// Returns every removed reservation paired with the reason for its removal.
+    ///
+    /// Pruned reservations come first, followed by expired ones, matching the
+    /// order in which autoclean applies them.
+    pub fn removals(&self) -> impl Iterator<Item = (&Reservation, CleanupReason)> {
+        self.pruned_reservations
+            .iter()
+            .map(|r| (r, CleanupReason::PathMissing))
+            .chain(
+                self.expired_reservations
+                    .iter()
+                    .map(|r| (r, CleanupReason::Expired)),
+            )
+    }
+}
+
+impl Serialize for AutocleanResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let keys = |reservations: &[Reservation]| -> Vec<String> {
+            reservations.iter().map(|r| r.key().to_string()).collect()
+        };
+
+        let mut state = serializer.serialize_struct("AutocleanResult", 5)?;
+        state.serialize_field("pruned_count", &self.pruned_count)?;
+        state.serialize_field("expired_count", &self.expired_count)?;
+        state.serialize_field("total_removed", &self.total_removed)?;
+        state.serialize_field("pruned", &keys(&self.pruned_reservations))?;
+        state.serialize_field("expired", &keys(&self.expired_reservations))?;
+        state.end()
+    }
+}
+
+/// Why a cleanup operation removes a reservation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupReason {
+    /// The reservation's path no longer exists (prune).
+    PathMissing,
+    /// The reservation has not been used within the expiration threshold (expire).
+    Expired,
+}
+
+impl CleanupReason {
+    /// Returns the stable machine-readable name of this reason.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PathMissing => "path_missing",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+impl std::fmt::Display for CleanupReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Cleanup operations for removing stale reservations.
 ///
 /// All operations are static methods that work on a database instance.
@@ -102,12 +220,51 @@ impl CleanupOperations {
     /// println!("Pruned {} reservations", result.removed_count);
     /// ```
     pub fn prune(db: &mut Database, dry_run: bool) -> Result<PruneResult> {
+        Self::prune_with_cutoff(db, None, dry_run)
+    }
+
+    /// Remove reservations for missing paths, plus any last used before a cutoff.
+    ///
+    /// Behaves like [`prune`](Self::prune) when `used_before` is `None`. With a
+    /// cutoff, reservations whose `last_used_at` is strictly earlier than it
+    /// are removed as well, even if their path still exists. Pinned
+    /// reservations are always kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operations fail.
+    pub fn prune_with_cutoff(
+        db: &mut Database,
+        used_before: Option<SystemTime>,
+        dry_run: bool,
+    ) -> Result<PruneResult> {
+        Self::prune_scoped(db, &PruneScope::default(), used_before, dry_run)
+    }
+
+    /// Prune only the reservations within `scope`.
+    ///
+    /// Behaves like [`prune_with_cutoff`](Self::prune_with_cutoff), except
+    /// that reservations outside the scope are neither checked nor removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operations fail.
+    pub fn prune_scoped(
+        db: &mut Database,
+        scope: &PruneScope,
+        used_before: Option<SystemTime>,
+        dry_run: bool,
+    ) -> Result<PruneResult> {
         // Get all reservations
         let all_reservations = Database::list_all_reservations(db.connection())?;
 
-        // Filter to those with non-existent paths
+        // Filter to those in scope with non-existent paths
         let mut to_remove = Vec::new();
         for reservation in all_reservations {
+            if reservation.pinned() || !scope.contains(&reservation) {
+                continue;
+            }
+
             // Fail-open policy: if we can't check the path (e.g., permission errors),
             // we conservatively assume it exists to avoid accidentally removing
             // valid reservations.
@@ -117,6 +274,18 @@ impl CleanupOperations {
             }
         }
 
+        // Then add stale reservations whose paths are still present
+        if let Some(cutoff) = used_before {
+            for reservation in Database::find_reservations_used_before(db.connection(), cutoff)? {
+                if !reservation.pinned()
+                    && scope.contains(&reservation)
+                    && !to_remove.iter().any(|r| r.key() == reservation.key())
+                {
+                    to_remove.push(reservation);
+                }
+            }
+        }
+
         let removed_count = to_remove.len();
 
         // If not dry-run, actually delete the reservations
@@ -136,7 +305,10 @@ impl CleanupOperations {
     /// Remove reservations that haven't been used within the configured time threshold.
     ///
     /// This operation removes reservations where `last_used_at` is older than
-    /// the threshold specified in the cleanup configuration.
+    /// the threshold specified in the cleanup configuration, along with any
+    /// reservation whose `expires_at` (set by a per-reservation TTL) is in the
+    /// past. TTL expiry applies even when no threshold is configured.
+    /// Reservations with a priority above `protect_priority` are kept.
     ///
     /// # Arguments
     ///
@@ -160,6 +332,7 @@ impl CleanupOperations {
     ///
     /// let cleanup_config = CleanupConfig {
     ///     expire_after_days: Some(30),
+    ///     protect_priority: None,
     /// };
     ///
     /// // Preview what would be expired
@@ -175,20 +348,113 @@ impl CleanupOperations {
         config: &CleanupConfig,
         dry_run: bool,
     ) -> Result<ExpireResult> {
-        // If no expiration configured, return empty result
-        let Some(expire_after_days) = config.expire_after_days else {
-            return Ok(ExpireResult {
-                removed_count: 0,
-                removed_reservations: Vec::new(),
-            });
-        };
+        Self::expire_with_cutoff(db, config, None, dry_run)
+    }
 
-        // Calculate the max age duration
+    /// Expire reservations, optionally also removing any last used before a cutoff.
+    ///
+    /// Behaves like [`expire`](Self::expire) when `used_before` is `None`. With
+    /// a cutoff, reservations whose `last_used_at` is strictly earlier than it
+    /// are removed in addition to those caught by TTLs and the configured
+    /// threshold. Pinned reservations are always kept, as are those above
+    /// the configured `protect_priority`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operations fail.
+    pub fn expire_with_cutoff(
+        db: &mut Database,
+        config: &CleanupConfig,
+        used_before: Option<SystemTime>,
+        dry_run: bool,
+    ) -> Result<ExpireResult> {
         #[allow(clippy::cast_lossless)]
-        let max_age = Duration::from_secs(expire_after_days as u64 * SECONDS_PER_DAY);
+        let max_age = config
+            .expire_after_days
+            .map(|days| Duration::from_secs(days as u64 * SECONDS_PER_DAY));
+        Self::expire_older_than(db, max_age, used_before, config.protect_priority, dry_run)
+    }
 
-        // Find expired reservations
-        let to_remove = Database::find_expired_reservations(db.connection(), max_age)?;
+    /// Expire reservations unused for longer than `max_age`.
+    ///
+    /// Like [`expire_with_cutoff`](Self::expire_with_cutoff), but the age
+    /// threshold is given directly instead of as whole days from the
+    /// configuration, so a one-off run can use a different (or sub-day)
+    /// threshold. With `max_age` of `None` only TTLs and the cutoff apply.
+    /// Reservations with a priority above `protect_priority` are kept; pass
+    /// `None` to expire regardless of priority.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operations fail.
+    pub fn expire_older_than(
+        db: &mut Database,
+        max_age: Option<Duration>,
+        used_before: Option<SystemTime>,
+        protect_priority: Option<i32>,
+        dry_run: bool,
+    ) -> Result<ExpireResult> {
+        let now = SystemTime::now();
+
+        // Reservations past their own TTL expire regardless of configuration
+        let mut to_remove = Database::find_past_ttl_reservations(db.connection(), now)?;
+
+        // Then add those unused for longer than the threshold or since the
+        // explicit cutoff
+        let mut stale = Vec::new();
+        if let Some(max_age) = max_age {
+            stale.extend(Database::find_expired_reservations(
+                db.connection(),
+                max_age,
+            )?);
+        }
+        if let Some(cutoff) = used_before {
+            stale.extend(Database::find_reservations_used_before(
+                db.connection(),
+                cutoff,
+            )?);
+        }
+        for reservation in stale {
+            if !to_remove.iter().any(|r| r.key() == reservation.key()) {
+                to_remove.push(reservation);
+            }
+        }
+
+        Self::remove_expired(db, to_remove, protect_priority, dry_run)
+    }
+
+    /// Expire only reservations past their own TTL.
+    ///
+    /// Unlike [`expire`](Self::expire), the age threshold is ignored, so
+    /// reservations without an `expires_at` are never removed. This suits
+    /// setups where per-reservation TTLs are the sole lifecycle mechanism.
+    /// Pinned reservations are always kept, as are those with a priority
+    /// above `protect_priority`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database operations fail.
+    pub fn expire_past_ttl(
+        db: &mut Database,
+        protect_priority: Option<i32>,
+        dry_run: bool,
+    ) -> Result<ExpireResult> {
+        let to_remove = Database::find_past_ttl_reservations(db.connection(), SystemTime::now())?;
+        Self::remove_expired(db, to_remove, protect_priority, dry_run)
+    }
+
+    /// Delete the expiry candidates, skipping pinned and protected reservations.
+    fn remove_expired(
+        db: &mut Database,
+        mut to_remove: Vec<Reservation>,
+        protect_priority: Option<i32>,
+        dry_run: bool,
+    ) -> Result<ExpireResult> {
+        // Leave pinned and high-priority reservations in place
+        to_remove.retain(|reservation| {
+            !reservation.pinned()
+                && protect_priority.map_or(true, |threshold| reservation.priority() <= threshold)
+        });
         let removed_count = to_remove.len();
 
         // If not dry-run, actually delete the reservations
@@ -208,7 +474,8 @@ impl CleanupOperations {
     ///
     /// This is a convenience method that performs both pruning (removing reservations
     /// for non-existent paths) and expiring (removing old unused reservations) in a
-    /// single operation.
+    /// single operation. The configured `protect_priority` only shields
+    /// reservations from expiry; those whose path is gone are still pruned.
     ///
     /// # Arguments
     ///
@@ -232,6 +499,7 @@ impl CleanupOperations {
     ///
     /// let cleanup_config = CleanupConfig {
     ///     expire_after_days: Some(30),
+    ///     protect_priority: None,
     /// };
     ///
     /// // Preview what would be cleaned
@@ -251,7 +519,19 @@ impl CleanupOperations {
         let prune_result = Self::prune(db, dry_run)?;
 
         // Then run expire
-        let expire_result = Self::expire(db, config, dry_run)?;
+        let mut expire_result = Self::expire(db, config, dry_run)?;
+
+        // In dry-run mode pruned reservations are still in the database, so
+        // expire may report them again; count each reservation only once
+        if dry_run {
+            expire_result.removed_reservations.retain(|expired| {
+                !prune_result
+                    .removed_reservations
+                    .iter()
+                    .any(|pruned| pruned.key() == expired.key())
+            });
+            expire_result.removed_count = expire_result.removed_reservations.len();
+        }
 
         Ok(AutocleanResult {
             pruned_count: prune_result.removed_count,
@@ -328,6 +608,26 @@ mod tests {
         assert_eq!(all.len(), 0);
     }
 
+    #[test]
+    fn test_prune_skips_pinned_reservation() {
+        let mut db = create_test_database();
+
+        let nonexistent = PathBuf::from("/this/path/definitely/does/not/exist/at/all");
+        let key = ReservationKey::new(nonexistent, None).unwrap();
+        let port = Port::try_from(5000).unwrap();
+        let reservation = Reservation::builder(key, port)
+            .pinned(true)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let result = CleanupOperations::prune(&mut db, false).unwrap();
+        assert_eq!(result.removed_count, 0);
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(all.len(), 1);
+    }
+
     #[test]
     fn test_prune_dry_run() {
         let mut db = create_test_database();
@@ -382,12 +682,164 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: None,
+            protect_priority: None,
         };
 
         let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
         assert_eq!(result.removed_count, 0);
     }
 
+    #[test]
+    fn test_expire_past_ttl_without_config() {
+        let mut db = create_test_database();
+        let now = SystemTime::now();
+
+        let key = ReservationKey::new(PathBuf::from("/scratch"), None).unwrap();
+        let scratch = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .expires_at(Some(now - Duration::from_secs(1)))
+            .build()
+            .unwrap();
+        db.create_reservation(&scratch).unwrap();
+
+        let key = ReservationKey::new(PathBuf::from("/keeper"), None).unwrap();
+        let keeper = Reservation::builder(key, Port::try_from(5001).unwrap())
+            .expires_at(Some(now + Duration::from_secs(3600)))
+            .build()
+            .unwrap();
+        db.create_reservation(&keeper).unwrap();
+
+        let config = CleanupConfig {
+            expire_after_days: None,
+            protect_priority: None,
+        };
+        let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].key(), scratch.key());
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].key(), keeper.key());
+    }
+
+    #[test]
+    fn test_expire_past_ttl_ignores_age() {
+        let mut db = create_test_database();
+        let now = SystemTime::now();
+        let old_time = now - Duration::from_secs(100 * SECONDS_PER_DAY);
+
+        let key = ReservationKey::new(PathBuf::from("/ttl"), None).unwrap();
+        let ttl = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .expires_at(Some(now - Duration::from_secs(1)))
+            .build()
+            .unwrap();
+        db.create_reservation(&ttl).unwrap();
+
+        // Far older than any age threshold, but without a TTL
+        let key = ReservationKey::new(PathBuf::from("/old"), None).unwrap();
+        let old = Reservation::builder(key, Port::try_from(5001).unwrap())
+            .last_used_at(old_time)
+            .build()
+            .unwrap();
+        db.create_reservation(&old).unwrap();
+
+        let result = CleanupOperations::expire_past_ttl(&mut db, None, false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].key(), ttl.key());
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].key(), old.key());
+    }
+
+    #[test]
+    fn test_expire_older_than_sub_day_threshold() {
+        let mut db = create_test_database();
+        let now = SystemTime::now();
+
+        let key = ReservationKey::new(PathBuf::from("/two-days"), None).unwrap();
+        let stale = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .last_used_at(now - Duration::from_secs(2 * SECONDS_PER_DAY))
+            .build()
+            .unwrap();
+        db.create_reservation(&stale).unwrap();
+
+        let key = ReservationKey::new(PathBuf::from("/half-day"), None).unwrap();
+        let recent = Reservation::builder(key, Port::try_from(5001).unwrap())
+            .last_used_at(now - Duration::from_secs(SECONDS_PER_DAY / 2))
+            .build()
+            .unwrap();
+        db.create_reservation(&recent).unwrap();
+
+        let max_age = Some(Duration::from_secs(SECONDS_PER_DAY));
+        let result =
+            CleanupOperations::expire_older_than(&mut db, max_age, None, None, false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].key(), stale.key());
+    }
+
+    #[test]
+    fn test_expire_keeps_protected_priority() {
+        let mut db = create_test_database();
+        let old_time = SystemTime::now() - Duration::from_secs(10 * SECONDS_PER_DAY);
+
+        let key = ReservationKey::new(PathBuf::from("/important"), None).unwrap();
+        let important = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .last_used_at(old_time)
+            .priority(5)
+            .build()
+            .unwrap();
+        db.create_reservation(&important).unwrap();
+
+        let key = ReservationKey::new(PathBuf::from("/ordinary"), None).unwrap();
+        let ordinary = Reservation::builder(key, Port::try_from(5001).unwrap())
+            .last_used_at(old_time)
+            .build()
+            .unwrap();
+        db.create_reservation(&ordinary).unwrap();
+
+        let config = CleanupConfig {
+            expire_after_days: Some(7),
+            protect_priority: Some(0),
+        };
+        let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].key(), ordinary.key());
+
+        // Without a threshold the high-priority reservation expires too
+        let config = CleanupConfig {
+            protect_priority: None,
+            ..config
+        };
+        let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].key(), important.key());
+        assert!(Database::list_all_reservations(db.connection())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_expire_counts_ttl_and_age_once() {
+        let mut db = create_test_database();
+
+        // Both past its TTL and older than the threshold
+        let old_time = SystemTime::now() - Duration::from_secs(10 * SECONDS_PER_DAY);
+        let key = ReservationKey::new(PathBuf::from("/both"), None).unwrap();
+        let reservation = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .last_used_at(old_time)
+            .expires_at(Some(old_time))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let config = CleanupConfig {
+            expire_after_days: Some(7),
+            protect_priority: None,
+        };
+        let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
+        assert_eq!(result.removed_count, 1);
+    }
+
     #[test]
     fn test_expire_no_old_reservations() {
         let mut db = create_test_database();
@@ -400,6 +852,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
@@ -427,6 +880,7 @@ mod tests {
         // Configure to expire after 7 days
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
@@ -437,6 +891,32 @@ mod tests {
         assert_eq!(all.len(), 0);
     }
 
+    #[test]
+    fn test_expire_skips_pinned_reservation() {
+        let mut db = create_test_database();
+
+        let old_time = SystemTime::now() - Duration::from_secs(10 * SECONDS_PER_DAY);
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(5000).unwrap();
+        let reservation = Reservation::builder(key, port)
+            .last_used_at(old_time)
+            .pinned(true)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let config = CleanupConfig {
+            expire_after_days: Some(7),
+            protect_priority: None,
+        };
+
+        let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
+        assert_eq!(result.removed_count, 0);
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(all.len(), 1);
+    }
+
     #[test]
     fn test_expire_dry_run() {
         let mut db = create_test_database();
@@ -453,6 +933,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         // Dry run should report what would be removed
@@ -486,6 +967,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
@@ -529,6 +1011,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::autoclean(&mut db, &config, false).unwrap();
@@ -541,6 +1024,41 @@ mod tests {
         assert_eq!(all.len(), 1);
     }
 
+    #[test]
+    fn test_autoclean_result_serializes_keys() {
+        let pruned = Reservation::builder(
+            ReservationKey::new(PathBuf::from("/gone"), None).unwrap(),
+            Port::try_from(5000).unwrap(),
+        )
+        .build()
+        .unwrap();
+        let expired = Reservation::builder(
+            ReservationKey::new(PathBuf::from("/old"), Some("web".to_string())).unwrap(),
+            Port::try_from(5001).unwrap(),
+        )
+        .build()
+        .unwrap();
+        let result = AutocleanResult {
+            pruned_count: 1,
+            expired_count: 1,
+            total_removed: 2,
+            pruned_reservations: vec![pruned],
+            expired_reservations: vec![expired],
+        };
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "pruned_count": 1,
+                "expired_count": 1,
+                "total_removed": 2,
+                "pruned": ["/gone"],
+                "expired": ["/old#web"],
+            })
+        );
+    }
+
     #[test]
     fn test_autoclean_dry_run() {
         let mut db = create_test_database();
@@ -555,6 +1073,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         // Dry run should report what would be removed
@@ -614,6 +1133,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
@@ -652,6 +1172,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::autoclean(&mut db, &config, false).unwrap();
@@ -662,6 +1183,34 @@ mod tests {
         assert_eq!(result.expired_reservations.len(), 1);
     }
 
+    #[test]
+    fn test_autoclean_dry_run_counts_overlap_once() {
+        // A dead path that is also old would be reported by both prune and
+        // expire in dry-run mode; it should only appear as pruned
+        let mut db = create_test_database();
+
+        let old_time = SystemTime::now() - Duration::from_secs(10 * SECONDS_PER_DAY);
+        let key = ReservationKey::new(PathBuf::from("/nonexistent"), None).unwrap();
+        let reservation = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .last_used_at(old_time)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let config = CleanupConfig {
+            expire_after_days: Some(7),
+            protect_priority: None,
+        };
+
+        let result = CleanupOperations::autoclean(&mut db, &config, true).unwrap();
+        assert_eq!(result.pruned_count, 1);
+        assert_eq!(result.expired_count, 0);
+        assert_eq!(result.total_removed, 1);
+
+        let reasons: Vec<_> = result.removals().map(|(_, reason)| reason).collect();
+        assert_eq!(reasons, vec![CleanupReason::PathMissing]);
+    }
+
     #[test]
     fn test_prune_result_contains_correct_reservations() {
         // Test that prune result includes the actual removed reservations
@@ -706,6 +1255,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
@@ -731,6 +1281,7 @@ mod tests {
 
         let config = CleanupConfig {
             expire_after_days: Some(7),
+            protect_priority: None,
         };
 
         // Run all cleanup operations
@@ -790,6 +1341,7 @@ mod tests {
         // Expire with 45-day threshold - should remove 60 and 90 day old
         let config = CleanupConfig {
             expire_after_days: Some(45),
+            protect_priority: None,
         };
 
         let result = CleanupOperations::expire(&mut db, &config, false).unwrap();
@@ -798,4 +1350,129 @@ mod tests {
         let remaining = Database::list_all_reservations(db.connection()).unwrap();
         assert_eq!(remaining.len(), 1);
     }
+
+    #[test]
+    fn test_prune_with_cutoff_removes_stale_existing_paths() {
+        let mut db = create_test_database();
+        let existing = std::env::current_dir().unwrap();
+        let now = SystemTime::now();
+
+        let stale = Reservation::builder(
+            ReservationKey::new(existing.clone(), Some("stale".to_string())).unwrap(),
+            Port::try_from(5000).unwrap(),
+        )
+        .last_used_at(now - Duration::from_secs(10 * SECONDS_PER_DAY))
+        .build()
+        .unwrap();
+        let pinned = Reservation::builder(
+            ReservationKey::new(existing.clone(), Some("pinned".to_string())).unwrap(),
+            Port::try_from(5001).unwrap(),
+        )
+        .last_used_at(now - Duration::from_secs(10 * SECONDS_PER_DAY))
+        .pinned(true)
+        .build()
+        .unwrap();
+        let fresh = Reservation::builder(
+            ReservationKey::new(existing, Some("fresh".to_string())).unwrap(),
+            Port::try_from(5002).unwrap(),
+        )
+        .last_used_at(now)
+        .build()
+        .unwrap();
+        for r in [&stale, &pinned, &fresh] {
+            db.create_reservation(r).unwrap();
+        }
+
+        // Without a cutoff nothing is removed: every path exists
+        let result = CleanupOperations::prune(&mut db, true).unwrap();
+        assert_eq!(result.removed_count, 0);
+
+        let cutoff = now - Duration::from_secs(7 * SECONDS_PER_DAY);
+        let result = CleanupOperations::prune_with_cutoff(&mut db, Some(cutoff), false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].key(), stale.key());
+
+        let remaining = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(remaining.len(), 2);
+    }
+
+    #[test]
+    fn test_prune_scoped_only_removes_within_path() {
+        let mut db = create_test_database();
+        let inside = Reservation::builder(
+            ReservationKey::new(PathBuf::from("/nonexistent/mine/api"), None).unwrap(),
+            Port::try_from(5000).unwrap(),
+        )
+        .build()
+        .unwrap();
+        let outside = Reservation::builder(
+            ReservationKey::new(PathBuf::from("/nonexistent/theirs"), None).unwrap(),
+            Port::try_from(5001).unwrap(),
+        )
+        .build()
+        .unwrap();
+        db.create_reservation(&inside).unwrap();
+        db.create_reservation(&outside).unwrap();
+
+        let scope = PruneScope::new(Some(PathBuf::from("/nonexistent/mine")), None);
+        let result = CleanupOperations::prune_scoped(&mut db, &scope, None, false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].key(), inside.key());
+
+        let remaining = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].key(), outside.key());
+    }
+
+    #[test]
+    fn test_prune_scoped_by_project() {
+        let mut db = create_test_database();
+        for (path, port, project) in [
+            ("/nonexistent/a", 5000, Some("mine")),
+            ("/nonexistent/b", 5001, Some("theirs")),
+            ("/nonexistent/c", 5002, None),
+        ] {
+            let reservation = Reservation::builder(
+                ReservationKey::new(PathBuf::from(path), None).unwrap(),
+                Port::try_from(port).unwrap(),
+            )
+            .project(project.map(String::from))
+            .build()
+            .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let scope = PruneScope::new(None, Some("mine".to_string()));
+        let result = CleanupOperations::prune_scoped(&mut db, &scope, None, true).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.removed_reservations[0].project(), Some("mine"));
+    }
+
+    #[test]
+    fn test_expire_with_cutoff_without_config() {
+        let mut db = create_test_database();
+        let now = SystemTime::now();
+
+        for (i, days) in [(0u16, 3u64), (1, 1)] {
+            let key = ReservationKey::new(PathBuf::from(format!("/test/path{i}")), None).unwrap();
+            let reservation = Reservation::builder(key, Port::try_from(5000 + i).unwrap())
+                .last_used_at(now - Duration::from_secs(days * SECONDS_PER_DAY))
+                .build()
+                .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let config = CleanupConfig {
+            expire_after_days: None,
+            protect_priority: None,
+        };
+        let cutoff = now - Duration::from_secs(2 * SECONDS_PER_DAY);
+        let result =
+            CleanupOperations::expire_with_cutoff(&mut db, &config, Some(cutoff), false).unwrap();
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(
+            result.removed_reservations[0].key().path,
+            PathBuf::from("/test/path0")
+        );
+    }
 }
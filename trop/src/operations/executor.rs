@@ -8,9 +8,11 @@ use std::collections::HashMap;
 use crate::database::Database;
 use crate::error::Result;
 use crate::port::allocator::allocator_from_config;
-use crate::Port;
+use crate::{PhaseTimings, Port};
 use rusqlite::Connection;
+use std::time::Instant;
 
+use super::hook::{HookOutcome, PreReserveHook};
 use super::plan::{OperationPlan, PlanAction};
 
 /// Result of executing a plan.
@@ -107,6 +109,7 @@ impl ExecutionResult {
 pub struct PlanExecutor<'conn> {
     conn: &'conn Connection,
     dry_run: bool,
+    timings: Option<&'conn mut PhaseTimings>,
 }
 
 impl<'conn> PlanExecutor<'conn> {
@@ -126,6 +129,7 @@ impl<'conn> PlanExecutor<'conn> {
         Self {
             conn,
             dry_run: false,
+            timings: None,
         }
     }
 
@@ -149,6 +153,29 @@ impl<'conn> PlanExecutor<'conn> {
         self
     }
 
+    /// Records how long [`execute`](Self::execute) takes in `timings`,
+    /// under the `plan execute` phase.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::operations::{OperationPlan, PlanExecutor};
+    /// use trop::{Database, DatabaseConfig, LogLevel, Logger, PhaseTimings};
+    ///
+    /// let mut db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let mut timings = PhaseTimings::new(true);
+    /// PlanExecutor::new(db.connection())
+    ///     .with_timings(&mut timings)
+    ///     .execute(&OperationPlan::new("Test operation"))
+    ///     .unwrap();
+    /// timings.report(&Logger::new(LogLevel::Verbose));
+    /// ```
+    #[must_use]
+    pub fn with_timings(mut self, timings: &'conn mut PhaseTimings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
     /// Executes the given plan.
     ///
     /// If in dry-run mode, validates the plan but makes no database changes.
@@ -171,6 +198,16 @@ impl<'conn> PlanExecutor<'conn> {
     /// let result = executor.execute(&plan).unwrap();
     /// ```
     pub fn execute(&mut self, plan: &OperationPlan) -> Result<ExecutionResult> {
+        let started = Instant::now();
+        let result = self.execute_plan(plan);
+        if let Some(timings) = self.timings.as_deref_mut() {
+            timings.record("plan execute", started.elapsed());
+        }
+        result
+    }
+
+    /// Applies (or, in dry-run mode, validates) every action in `plan`.
+    fn execute_plan(&mut self, plan: &OperationPlan) -> Result<ExecutionResult> {
         if self.dry_run {
             // In dry-run mode, extract port without database queries
             let port = Self::extract_port_from_plan_dry_run(plan);
@@ -203,7 +240,9 @@ impl<'conn> PlanExecutor<'conn> {
                 Database::create_reservation_simple(self.conn, reservation)?;
                 Ok(None)
             }
-            PlanAction::UpdateReservation(reservation) => {
+            PlanAction::UpdateReservation {
+                to: reservation, ..
+            } => {
                 // For updates, use the simple create (upsert) - no transaction needed here
                 // Updates are for existing reservations where we're changing metadata
                 Database::create_reservation_simple(self.conn, reservation)?;
@@ -217,13 +256,26 @@ impl<'conn> PlanExecutor<'conn> {
                 Database::delete_reservation_simple(self.conn, key)?;
                 Ok(None)
             }
+            PlanAction::MoveReservation { from, to } => {
+                Database::move_reservation_simple(self.conn, from, to)?;
+                Ok(None)
+            }
             PlanAction::AllocateGroup {
                 request,
                 full_config,
                 occupancy_config,
             } => {
                 let allocator = allocator_from_config(full_config)?;
-                let result = allocator.allocate_group(self.conn, request, occupancy_config)?;
+                let hook = PreReserveHook::from_config(full_config);
+                let result = allocator.allocate_group_with_approval(
+                    self.conn,
+                    request,
+                    occupancy_config,
+                    |port, key| match &hook {
+                        Some(hook) => Ok(hook.evaluate(port, key)? == HookOutcome::Approved),
+                        None => Ok(true),
+                    },
+                )?;
                 Ok(Some(result.allocations))
             }
         }
@@ -236,11 +288,11 @@ impl<'conn> PlanExecutor<'conn> {
     fn extract_port_from_plan(&self, plan: &OperationPlan) -> Option<Port> {
         for action in &plan.actions {
             match action {
-                PlanAction::CreateReservation(r) | PlanAction::UpdateReservation(r) => {
+                PlanAction::CreateReservation(r) | PlanAction::UpdateReservation { to: r, .. } => {
                     return Some(r.port());
                 }
-                PlanAction::UpdateLastUsed(key) => {
-                    // For idempotent case, get the existing reservation's port
+                PlanAction::UpdateLastUsed(key) | PlanAction::MoveReservation { to: key, .. } => {
+                    // For idempotent and move cases, get the existing reservation's port
                     if let Ok(Some(reservation)) = Database::get_reservation(self.conn, key) {
                         return Some(reservation.port());
                     }
@@ -261,14 +313,15 @@ impl<'conn> PlanExecutor<'conn> {
     fn extract_port_from_plan_dry_run(plan: &OperationPlan) -> Option<Port> {
         for action in &plan.actions {
             match action {
-                PlanAction::CreateReservation(r) | PlanAction::UpdateReservation(r) => {
+                PlanAction::CreateReservation(r) | PlanAction::UpdateReservation { to: r, .. } => {
                     return Some(r.port());
                 }
                 PlanAction::UpdateLastUsed(_)
                 | PlanAction::DeleteReservation(_)
+                | PlanAction::MoveReservation { .. }
                 | PlanAction::AllocateGroup { .. } => {
                     // In dry-run mode, we don't query the database.
-                    // For UpdateLastUsed and AllocateGroup, return None.
+                    // For UpdateLastUsed, MoveReservation, and AllocateGroup, return None.
                     // Release operations also don't return a port.
                 }
             }
@@ -462,4 +515,19 @@ mod tests {
 
         assert_eq!(result.port, Some(port));
     }
+
+    #[test]
+    fn test_execute_records_timing() {
+        let db = create_test_database();
+        let mut timings = PhaseTimings::new(true);
+
+        let plan = OperationPlan::new("Test operation");
+        PlanExecutor::new(db.connection())
+            .with_timings(&mut timings)
+            .execute(&plan)
+            .unwrap();
+
+        assert_eq!(timings.phases().len(), 1);
+        assert_eq!(timings.phases()[0].0, "plan execute");
+    }
 }
@@ -0,0 +1,263 @@
+//! Pre-reserve hook execution.
+//!
+//! A pre-reserve hook is an external command, configured with
+//! `pre_reserve_hook`, that trop runs before committing a new reservation.
+//! The candidate port and reservation key are passed to the command via
+//! environment variables:
+//!
+//! - `TROP_HOOK_PORT`: the candidate port number
+//! - `TROP_HOOK_PATH`: the reservation path
+//! - `TROP_HOOK_TAG`: the reservation tag (empty if untagged)
+//!
+//! A zero exit status approves the candidate; any other exit status (or a
+//! timeout) vetoes it, and the reserve operation moves on to the next
+//! candidate port.
+//!
+//! Group allocation (`reserve-group` and `autoreserve`) asks the hook about
+//! each service's port: a vetoed offset port moves the whole group on to the
+//! next base port, and a vetoed preferred port fails the group.
+
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::{Port, ReservationKey};
+
+/// Default time limit for a pre-reserve hook, in seconds.
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 10;
+
+/// How often a running hook is polled for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Outcome of running a pre-reserve hook for one candidate port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookOutcome {
+    /// The hook exited successfully; the candidate may be reserved.
+    Approved,
+    /// The hook rejected the candidate.
+    Vetoed {
+        /// Human-readable explanation (exit status or timeout).
+        reason: String,
+    },
+}
+
+/// An external command consulted before committing a reservation.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::PathBuf;
+/// use std::time::Duration;
+/// use trop::operations::hook::{HookOutcome, PreReserveHook};
+/// use trop::{Port, ReservationKey};
+///
+/// let hook = PreReserveHook::new("test \"$TROP_HOOK_PORT\" != 6000", Duration::from_secs(5));
+/// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+///
+/// let outcome = hook.evaluate(Port::try_from(5000).unwrap(), &key).unwrap();
+/// assert_eq!(outcome, HookOutcome::Approved);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreReserveHook {
+    command: String,
+    timeout: Duration,
+}
+
+impl PreReserveHook {
+    /// Creates a hook running `command` through the system shell.
+    #[must_use]
+    pub fn new(command: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            command: command.into(),
+            timeout,
+        }
+    }
+
+    /// Builds the hook from configuration, if one is configured.
+    ///
+    /// Uses `pre_reserve_hook_timeout_seconds`, falling back to
+    /// [`DEFAULT_HOOK_TIMEOUT_SECONDS`].
+    #[must_use]
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let command = config.pre_reserve_hook.as_ref()?;
+        let timeout = config
+            .pre_reserve_hook_timeout_seconds
+            .unwrap_or(DEFAULT_HOOK_TIMEOUT_SECONDS);
+        Some(Self::new(command.clone(), Duration::from_secs(timeout)))
+    }
+
+    /// Returns the hook command.
+    #[must_use]
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Returns the time limit for a single hook run.
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Runs the hook for a candidate port and reports whether it approves.
+    ///
+    /// The hook's stdout is discarded (so it can't corrupt trop's own
+    /// output) and its stderr is inherited. A hook that exceeds its time
+    /// limit is killed and treated as a veto.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hook process cannot be spawned or waited on.
+    pub fn evaluate(&self, port: Port, key: &ReservationKey) -> Result<HookOutcome> {
+        log::info!(
+            "Running pre-reserve hook for port {port} ({key}): {}",
+            self.command
+        );
+
+        let mut child = self
+            .shell_command()
+            .env("TROP_HOOK_PORT", port.value().to_string())
+            .env("TROP_HOOK_PATH", &key.path)
+            .env("TROP_HOOK_TAG", key.tag.as_deref().unwrap_or_default())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let deadline = Instant::now() + self.timeout;
+        let outcome = loop {
+            if let Some(status) = child.try_wait()? {
+                break Self::outcome_from_status(status);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                // Best effort: the process may have exited in the meantime
+                let _ = child.kill();
+                child.wait()?;
+                break HookOutcome::Vetoed {
+                    reason: format!("timed out after {}s", self.timeout.as_secs_f64()),
+                };
+            }
+            thread::sleep(POLL_INTERVAL.min(deadline - now));
+        };
+
+        match &outcome {
+            HookOutcome::Approved => log::info!("Pre-reserve hook approved port {port}"),
+            HookOutcome::Vetoed { reason } => {
+                log::warn!("Pre-reserve hook vetoed port {port}: {reason}");
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Builds the platform shell invocation for the hook command.
+    fn shell_command(&self) -> Command {
+        #[cfg(windows)]
+        {
+            let mut command = Command::new("cmd");
+            command.arg("/C").arg(&self.command);
+            command
+        }
+        #[cfg(not(windows))]
+        {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(&self.command);
+            command
+        }
+    }
+
+    /// Maps a hook's exit status to an outcome.
+    fn outcome_from_status(status: ExitStatus) -> HookOutcome {
+        if status.success() {
+            return HookOutcome::Approved;
+        }
+        let reason = match status.code() {
+            Some(code) => format!("exited with status {code}"),
+            None => "terminated by signal".to_string(),
+        };
+        HookOutcome::Vetoed { reason }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn key() -> ReservationKey {
+        ReservationKey::new(PathBuf::from("/test/path"), Some("web".to_string())).unwrap()
+    }
+
+    fn port(value: u16) -> Port {
+        Port::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_hook_approves_on_success() {
+        let hook = PreReserveHook::new("exit 0", Duration::from_secs(5));
+        assert_eq!(
+            hook.evaluate(port(5000), &key()).unwrap(),
+            HookOutcome::Approved
+        );
+    }
+
+    #[test]
+    fn test_hook_vetoes_on_nonzero_exit() {
+        let hook = PreReserveHook::new("exit 3", Duration::from_secs(5));
+        assert_eq!(
+            hook.evaluate(port(5000), &key()).unwrap(),
+            HookOutcome::Vetoed {
+                reason: "exited with status 3".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_hook_receives_candidate_environment() {
+        let hook = PreReserveHook::new(
+            "test \"$TROP_HOOK_PORT\" = 5001 && test \"$TROP_HOOK_PATH\" = /test/path \
+             && test \"$TROP_HOOK_TAG\" = web",
+            Duration::from_secs(5),
+        );
+        assert_eq!(
+            hook.evaluate(port(5001), &key()).unwrap(),
+            HookOutcome::Approved
+        );
+        assert!(matches!(
+            hook.evaluate(port(5002), &key()).unwrap(),
+            HookOutcome::Vetoed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_hook_timeout_vetoes() {
+        let hook = PreReserveHook::new("sleep 5", Duration::from_millis(100));
+        let started = Instant::now();
+        let outcome = hook.evaluate(port(5000), &key()).unwrap();
+
+        assert!(matches!(
+            outcome,
+            HookOutcome::Vetoed { ref reason } if reason.starts_with("timed out")
+        ));
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert!(PreReserveHook::from_config(&Config::default()).is_none());
+
+        let config = Config {
+            pre_reserve_hook: Some("true".to_string()),
+            ..Default::default()
+        };
+        let hook = PreReserveHook::from_config(&config).unwrap();
+        assert_eq!(hook.command(), "true");
+        assert_eq!(
+            hook.timeout(),
+            Duration::from_secs(DEFAULT_HOOK_TIMEOUT_SECONDS)
+        );
+    }
+}
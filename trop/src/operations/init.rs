@@ -5,20 +5,37 @@
 //! configuration file creation.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use rusqlite::{Connection, OpenFlags};
+
+use crate::database::{
+    get_schema_version, upgrade_schema_to, validate_database_filename, CURRENT_SCHEMA_VERSION,
+    DEFAULT_DATABASE_FILENAME,
+};
 use crate::error::{Error, Result};
 use crate::{Database, DatabaseConfig};
 
 /// Options for database initialization.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct InitOptions {
     /// Data directory to initialize.
     pub data_dir: PathBuf,
+    /// File name of the database inside `data_dir`.
+    pub database_filename: String,
     /// Overwrite existing database if it exists.
     pub overwrite: bool,
     /// Create a default configuration file.
     pub create_config: bool,
+    /// Upgrade the schema of an existing database to the current version.
+    pub migrate: bool,
+    /// Schema version to upgrade to instead of the current one.
+    pub target_schema_version: Option<i32>,
+    /// Directory to write a starter `trop.yaml` into, if any.
+    pub project_config_dir: Option<PathBuf>,
+    /// Overwrite an existing `trop.yaml` in `project_config_dir`.
+    pub force: bool,
 }
 
 impl InitOptions {
@@ -27,11 +44,29 @@ impl InitOptions {
     pub fn new(data_dir: PathBuf) -> Self {
         Self {
             data_dir,
+            database_filename: DEFAULT_DATABASE_FILENAME.to_string(),
             overwrite: false,
             create_config: false,
+            migrate: false,
+            target_schema_version: None,
+            project_config_dir: None,
+            force: false,
         }
     }
 
+    /// Sets the database file name (default: `trop.db`).
+    #[must_use]
+    pub fn with_database_filename(mut self, name: impl Into<String>) -> Self {
+        self.database_filename = name.into();
+        self
+    }
+
+    /// Path of the database file to initialize.
+    #[must_use]
+    pub fn database_path(&self) -> PathBuf {
+        self.data_dir.join(&self.database_filename)
+    }
+
     /// Sets whether to overwrite existing database.
     #[must_use]
     pub fn with_overwrite(mut self, overwrite: bool) -> Self {
@@ -45,10 +80,53 @@ impl InitOptions {
         self.create_config = create_config;
         self
     }
+
+    /// Sets whether to upgrade the schema of an existing database.
+    #[must_use]
+    pub fn with_migrate(mut self, migrate: bool) -> Self {
+        self.migrate = migrate;
+        self
+    }
+
+    /// Sets the schema version `migrate` upgrades to (default: current).
+    #[must_use]
+    pub fn with_target_schema_version(mut self, version: Option<i32>) -> Self {
+        self.target_schema_version = version;
+        self
+    }
+
+    /// Schema version `migrate` upgrades an existing database to.
+    #[must_use]
+    pub fn target_schema_version(&self) -> i32 {
+        self.target_schema_version.unwrap_or(CURRENT_SCHEMA_VERSION)
+    }
+
+    /// Sets the directory to scaffold a starter `trop.yaml` into.
+    #[must_use]
+    pub fn with_project_config(mut self, dir: Option<PathBuf>) -> Self {
+        self.project_config_dir = dir;
+        self
+    }
+
+    /// Sets whether an existing `trop.yaml` may be overwritten.
+    #[must_use]
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Path of the `trop.yaml` that will be scaffolded, if any.
+    #[must_use]
+    pub fn project_config_path(&self) -> Option<PathBuf> {
+        self.project_config_dir
+            .as_ref()
+            .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+    }
 }
 
 /// Result of initialization operation.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct InitResult {
     /// Whether the data directory was created.
     pub data_dir_created: bool,
@@ -56,8 +134,24 @@ pub struct InitResult {
     pub database_created: bool,
     /// Whether a configuration file was created.
     pub config_created: bool,
+    /// Path of the starter `trop.yaml` that was written, if any.
+    pub project_config_created: Option<PathBuf>,
     /// Path to the data directory.
     pub data_dir: PathBuf,
+    /// Schema version of the database after initialization.
+    pub schema_version: i32,
+    /// Schema version found on an existing database (None if one was created).
+    pub existing_schema_version: Option<i32>,
+    /// Whether the schema of an existing database was upgraded.
+    pub schema_upgraded: bool,
+}
+
+impl InitResult {
+    /// Returns true if the database is older than this client's schema.
+    #[must_use]
+    pub const fn is_schema_outdated(&self) -> bool {
+        self.schema_version < CURRENT_SCHEMA_VERSION
+    }
 }
 
 /// Default minimal configuration template.
@@ -72,6 +166,9 @@ const DEFAULT_CONFIG_TEMPLATE: &str = r"# Trop Configuration File
 # Maximum lock wait time in seconds (default: 5)
 # maximum_lock_wait_seconds: 5
 
+# Database file name inside this directory (default: trop.db)
+# database_filename: trop.db
+
 # Group reservations (example)
 # reservations:
 #   path: .  # Base path for group reservations
@@ -84,18 +181,75 @@ const DEFAULT_CONFIG_TEMPLATE: &str = r"# Trop Configuration File
 #       env: API_PORT
 ";
 
+/// File name of the project configuration scaffolded by `init`.
+const PROJECT_CONFIG_FILE: &str = "trop.yaml";
+
+/// Starter project configuration written by `init` when a project config
+/// directory is given.
+///
+/// Kept here rather than in a data file so the schema tests below catch any
+/// drift between the template and [`Config`](crate::config::Config).
+pub const PROJECT_CONFIG_TEMPLATE: &str = r"# trop.yaml - project configuration for trop
+#
+# trop reads this file from the directory a command runs in, or the nearest
+# parent that has one. Check it with `trop validate trop.yaml`.
+
+# Identifier recorded on every reservation made under this directory.
+project: my-project
+
+# Range automatic allocation draws ports from. `max` is inclusive; use
+# `max_offset: N` instead to size the range relative to `min`.
+ports:
+  min: 5000
+  max: 5999
+
+# Ports trop must never hand out: a single port, or an inclusive range with
+# an optional reason.
+excluded_ports:
+  - 5432 # PostgreSQL
+  # - start: 5900
+  #   end: 5910
+  #   reason: VNC
+
+# Services reserved together by `trop reserve-group` and `trop autoreserve`.
+reservations:
+  # First port of the group. Each service gets `base + offset`; omit `base`
+  # to let trop find a free block in the range above.
+  base: 5000
+  services:
+    web:
+      # Offset from `base`
+      offset: 0
+      # Environment variable the port is exported as
+      env: PORT
+    api:
+      offset: 1
+      env: API_PORT
+      # Absolute port to try first; falls back to the offset when taken
+      # preferred: 8080
+";
+
 /// Initializes the trop data directory and database.
 ///
 /// This function creates the data directory if needed, initializes the database,
 /// and optionally creates a default configuration file.
 ///
+/// Initialization is idempotent: if the database already exists (and
+/// `overwrite` is false), it is left in place and its schema version is
+/// reported. With `migrate`, an outdated schema is upgraded to the current
+/// version (or [`InitOptions::target_schema_version`]) in the same call.
+///
 /// # Errors
 ///
 /// Returns an error if:
+/// - The database file name is not valid
 /// - The data directory cannot be created
 /// - The database cannot be initialized
 /// - The configuration file cannot be written
-/// - Overwrite is false and the database already exists
+/// - A `trop.yaml` already exists in the project config directory and
+///   `force` is not set
+/// - An existing database's schema is newer than this client supports
+/// - Upgrading an existing database's schema fails
 ///
 /// # Examples
 ///
@@ -111,11 +265,28 @@ const DEFAULT_CONFIG_TEMPLATE: &str = r"# Trop Configuration File
 /// println!("Database created: {}", result.database_created);
 /// ```
 pub fn init_database(options: &InitOptions) -> Result<InitResult> {
+    validate_database_filename(&options.database_filename)?;
+
+    // Refuse up front so an existing trop.yaml never leaves a half-done init
+    let project_config_path = options.project_config_path();
+    if let Some(ref path) = project_config_path {
+        if path.exists() && !options.force {
+            return Err(Error::Validation {
+                field: "project_config".into(),
+                message: format!("{} already exists", path.display()),
+            });
+        }
+    }
+
     let mut result = InitResult {
         data_dir_created: false,
         database_created: false,
         config_created: false,
+        project_config_created: None,
         data_dir: options.data_dir.clone(),
+        schema_version: CURRENT_SCHEMA_VERSION,
+        existing_schema_version: None,
+        schema_upgraded: false,
     };
 
     // 1. Create data directory if it doesn't exist
@@ -125,31 +296,46 @@ pub fn init_database(options: &InitOptions) -> Result<InitResult> {
     }
 
     // 2. Determine database path
-    let db_path = options.data_dir.join("trop.db");
+    let db_path = options.database_path();
 
     // 3. Check if database already exists
     let db_exists = db_path.exists();
 
     if db_exists && !options.overwrite {
-        return Err(Error::Validation {
-            field: "database".into(),
-            message: format!(
-                "Database already exists at {}. Use --overwrite to replace it.",
-                db_path.display()
-            ),
-        });
-    }
+        // Leave the existing database in place, upgrading it if requested
+        let version = if options.migrate {
+            let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+            let target = options.target_schema_version();
+            let previous = upgrade_schema_to(&conn, target)?;
+            result.schema_upgraded = previous != target;
+            result.schema_version = target;
+            previous
+        } else {
+            let version = existing_schema_version(&db_path)?;
+            if version > CURRENT_SCHEMA_VERSION {
+                return Err(Error::Validation {
+                    field: "schema_version".into(),
+                    message: format!(
+                        "Database schema version {version} is newer than client version {CURRENT_SCHEMA_VERSION}. Please upgrade trop."
+                    ),
+                });
+            }
+            result.schema_version = version;
+            version
+        };
+        result.existing_schema_version = Some(version);
+    } else {
+        // 4. Remove existing database if overwriting
+        if db_exists {
+            fs::remove_file(&db_path)?;
+        }
 
-    // 4. Remove existing database if overwriting
-    if db_exists && options.overwrite {
-        fs::remove_file(&db_path)?;
+        // 5. Initialize database (this will create schema)
+        let db_config = DatabaseConfig::new(&db_path);
+        let mut _db = Database::open(db_config)?;
+        result.database_created = true;
     }
 
-    // 5. Initialize database (this will create schema)
-    let db_config = DatabaseConfig::new(&db_path);
-    let mut _db = Database::open(db_config)?;
-    result.database_created = true;
-
     // 6. Optionally create default configuration file
     if options.create_config {
         let config_path = options.data_dir.join("config.yaml");
@@ -161,9 +347,27 @@ pub fn init_database(options: &InitOptions) -> Result<InitResult> {
         }
     }
 
+    // 7. Optionally scaffold a project configuration file
+    if let Some(path) = project_config_path {
+        fs::write(&path, PROJECT_CONFIG_TEMPLATE)?;
+        result.project_config_created = Some(path);
+    }
+
     Ok(result)
 }
 
+/// Reads the schema version of an existing database without modifying it.
+///
+/// Returns 0 if the database has not been initialized.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened or queried.
+pub fn existing_schema_version(db_path: &Path) -> Result<i32> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    get_schema_version(&conn)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +401,22 @@ mod tests {
         assert!(data_dir.join("trop.db").exists());
     }
 
+    #[test]
+    fn test_init_custom_database_filename() {
+        let temp = TempDir::new().unwrap();
+        let data_dir = temp.path().to_path_buf();
+
+        let options = InitOptions::new(data_dir.clone()).with_database_filename("other.db");
+        let result = init_database(&options).unwrap();
+
+        assert!(result.database_created);
+        assert!(data_dir.join("other.db").exists());
+        assert!(!data_dir.join("trop.db").exists());
+
+        let options = InitOptions::new(data_dir).with_database_filename("../escape.db");
+        assert!(init_database(&options).is_err());
+    }
+
     #[test]
     fn test_init_with_config() {
         let temp = TempDir::new().unwrap();
@@ -216,7 +436,7 @@ mod tests {
     }
 
     #[test]
-    fn test_init_fails_without_overwrite() {
+    fn test_init_existing_database_is_idempotent() {
         let temp = TempDir::new().unwrap();
         let data_dir = temp.path().join("trop");
 
@@ -224,19 +444,73 @@ mod tests {
         let options = InitOptions::new(data_dir.clone());
         init_database(&options).unwrap();
 
-        // Try to create again without overwrite
+        // Init again without overwrite reports the existing schema
         let options = InitOptions::new(data_dir.clone());
-        let result = init_database(&options);
-
-        assert!(result.is_err());
-        match result {
-            Err(Error::Validation { field, message }) => {
-                assert_eq!(field, "database");
-                assert!(message.contains("already exists"));
-                assert!(message.contains("--overwrite"));
-            }
-            _ => panic!("Expected validation error"),
-        }
+        let result = init_database(&options).unwrap();
+
+        assert!(!result.database_created);
+        assert!(!result.schema_upgraded);
+        assert_eq!(result.existing_schema_version, Some(CURRENT_SCHEMA_VERSION));
+        assert_eq!(result.schema_version, CURRENT_SCHEMA_VERSION);
+        assert!(!result.is_schema_outdated());
+    }
+
+    #[test]
+    fn test_init_reports_outdated_schema_without_migrate() {
+        let temp = TempDir::new().unwrap();
+        let data_dir = temp.path().to_path_buf();
+
+        // An empty database file has no schema (version 0)
+        fs::write(data_dir.join("trop.db"), b"").unwrap();
+
+        let result = init_database(&InitOptions::new(data_dir.clone())).unwrap();
+
+        assert!(!result.database_created);
+        assert!(!result.schema_upgraded);
+        assert_eq!(result.existing_schema_version, Some(0));
+        assert!(result.is_schema_outdated());
+        assert_eq!(
+            existing_schema_version(&data_dir.join("trop.db")).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_init_migrate_upgrades_outdated_schema() {
+        let temp = TempDir::new().unwrap();
+        let data_dir = temp.path().to_path_buf();
+        fs::write(data_dir.join("trop.db"), b"").unwrap();
+
+        let options = InitOptions::new(data_dir.clone()).with_migrate(true);
+        let result = init_database(&options).unwrap();
+
+        assert!(result.schema_upgraded);
+        assert_eq!(result.existing_schema_version, Some(0));
+        assert_eq!(result.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(
+            existing_schema_version(&data_dir.join("trop.db")).unwrap(),
+            CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_init_rejects_newer_schema() {
+        let temp = TempDir::new().unwrap();
+        let data_dir = temp.path().to_path_buf();
+        init_database(&InitOptions::new(data_dir.clone())).unwrap();
+
+        let conn = Connection::open(data_dir.join("trop.db")).unwrap();
+        conn.execute(
+            "UPDATE metadata SET value = '999' WHERE key = 'schema_version'",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        let result = init_database(&InitOptions::new(data_dir));
+        assert!(
+            matches!(result, Err(Error::Validation { ref field, .. }) if field == "schema_version")
+        );
     }
 
     #[test]
@@ -277,4 +551,37 @@ mod tests {
         let config_content = fs::read_to_string(&config_path).unwrap();
         assert_eq!(config_content, "custom config");
     }
+
+    #[test]
+    fn test_project_config_template_matches_schema() {
+        let config: crate::config::Config = serde_yaml::from_str(PROJECT_CONFIG_TEMPLATE).unwrap();
+        crate::config::ConfigValidator::validate(&config, true).unwrap();
+
+        assert_eq!(config.project.as_deref(), Some("my-project"));
+        let group = config.reservations.unwrap();
+        let group = group.select(None).unwrap();
+        assert_eq!(group.base, Some(5000));
+        assert_eq!(group.services.len(), 2);
+    }
+
+    #[test]
+    fn test_init_project_config_requires_force_to_overwrite() {
+        let temp = TempDir::new().unwrap();
+        let data_dir = temp.path().join("trop");
+        let project = temp.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("trop.yaml"), "project: mine\n").unwrap();
+
+        let options = InitOptions::new(data_dir.clone()).with_project_config(Some(project.clone()));
+        assert!(init_database(&options).is_err());
+        assert!(!data_dir.exists(), "nothing should be initialized");
+
+        let result = init_database(&options.with_force(true)).unwrap();
+        assert_eq!(
+            result.project_config_created,
+            Some(project.join("trop.yaml"))
+        );
+        let contents = fs::read_to_string(project.join("trop.yaml")).unwrap();
+        assert_eq!(contents, PROJECT_CONFIG_TEMPLATE);
+    }
 }
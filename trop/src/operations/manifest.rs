@@ -0,0 +1,313 @@
+//! Declarative reservation manifests and their comparison with the database.
+//!
+//! A manifest is a JSON array of reservation entries in the same shape that
+//! `trop list --format json` produces, so a snapshot of the current state is
+//! itself a valid manifest. Only `path`, `tag`, `port`, `project`, `task`, and
+//! `pinned` are significant; other fields (such as timestamps) are ignored.
+//!
+//! [`ManifestDiff`] describes what applying a manifest would create, delete,
+//! or change, without modifying anything.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::path::normalize::normalize;
+use crate::{Port, Reservation, ReservationKey};
+
+/// A single reservation declared in a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The reservation key (normalized path and optional tag).
+    pub key: ReservationKey,
+    /// The desired port.
+    pub port: Port,
+    /// The desired project, if any.
+    pub project: Option<String>,
+    /// The desired task, if any.
+    pub task: Option<String>,
+    /// The desired pinned state; `None` leaves it unspecified.
+    pub pinned: Option<bool>,
+}
+
+/// Raw manifest entry as it appears in JSON.
+#[derive(Debug, Deserialize)]
+struct RawEntry {
+    path: PathBuf,
+    #[serde(default)]
+    tag: Option<String>,
+    port: u16,
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default)]
+    task: Option<String>,
+    #[serde(default)]
+    pinned: Option<bool>,
+}
+
+/// A parsed reservation manifest.
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    /// Declared reservations, in file order.
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Loads a manifest from a JSON file.
+    ///
+    /// Relative paths in the manifest are resolved against the directory
+    /// containing the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid manifest
+    /// (see [`Manifest::from_json`]).
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::from_json(&contents, base)
+    }
+
+    /// Parses a manifest from JSON text.
+    ///
+    /// Relative paths are resolved against `base` and all paths are
+    /// normalized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The text is not a JSON array of entries
+    /// - An entry has an invalid port, path, tag, project, or task
+    /// - The same path and tag appear more than once
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::Manifest;
+    /// use std::path::Path;
+    ///
+    /// let manifest = Manifest::from_json(
+    ///     r#"[{"path": "/srv/app", "tag": "web", "port": 5000}]"#,
+    ///     Path::new("/"),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(manifest.entries.len(), 1);
+    /// assert_eq!(manifest.entries[0].port.value(), 5000);
+    /// ```
+    pub fn from_json(json: &str, base: &Path) -> Result<Self> {
+        let raw: Vec<RawEntry> = serde_json::from_str(json).map_err(|e| Error::Validation {
+            field: "manifest".into(),
+            message: format!("invalid manifest: {e}"),
+        })?;
+
+        let mut seen = HashSet::new();
+        let mut entries = Vec::with_capacity(raw.len());
+        for entry in raw {
+            let path = normalize(&base.join(&entry.path))?;
+            let key = ReservationKey::new(path, entry.tag)?;
+            if !seen.insert(key.clone()) {
+                return Err(Error::Validation {
+                    field: "manifest".into(),
+                    message: format!("duplicate entry for {key}"),
+                });
+            }
+
+            // Reuse the reservation builder's validation of project and task
+            let validated = Reservation::builder(key, Port::try_from(entry.port)?)
+                .project(entry.project)
+                .task(entry.task)
+                .build()?;
+
+            entries.push(ManifestEntry {
+                key: validated.key().clone(),
+                port: validated.port(),
+                project: validated.project().map(String::from),
+                task: validated.task().map(String::from),
+                pinned: entry.pinned,
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Compares the manifest against the given current reservations.
+    #[must_use]
+    pub fn diff(&self, current: &[Reservation]) -> ManifestDiff {
+        let mut remaining: HashMap<&ReservationKey, &Reservation> =
+            current.iter().map(|r| (r.key(), r)).collect();
+
+        let mut diff = ManifestDiff::default();
+        for entry in &self.entries {
+            match remaining.remove(&entry.key) {
+                None => diff.create.push(entry.clone()),
+                Some(existing) => {
+                    let fields = changed_fields(entry, existing);
+                    if fields.is_empty() {
+                        diff.unchanged += 1;
+                    } else {
+                        diff.change.push(ManifestChange {
+                            current: existing.clone(),
+                            desired: entry.clone(),
+                            fields,
+                        });
+                    }
+                }
+            }
+        }
+        diff.delete = remaining.into_values().cloned().collect();
+
+        diff.create.sort_by(|a, b| key_order(&a.key, &b.key));
+        diff.delete.sort_by(|a, b| key_order(a.key(), b.key()));
+        diff.change
+            .sort_by(|a, b| key_order(a.current.key(), b.current.key()));
+        diff
+    }
+}
+
+/// An existing reservation whose fields differ from its manifest entry.
+#[derive(Debug, Clone)]
+pub struct ManifestChange {
+    /// The reservation as currently stored.
+    pub current: Reservation,
+    /// The manifest entry for the same key.
+    pub desired: ManifestEntry,
+    /// Names of the differing fields (`port`, `project`, `task`, `pinned`).
+    pub fields: Vec<&'static str>,
+}
+
+/// Differences between a manifest and the current reservations.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestDiff {
+    /// Entries with no matching reservation.
+    pub create: Vec<ManifestEntry>,
+    /// Reservations not mentioned in the manifest.
+    pub delete: Vec<Reservation>,
+    /// Reservations whose fields differ from the manifest.
+    pub change: Vec<ManifestChange>,
+    /// Number of reservations that already match the manifest.
+    pub unchanged: usize,
+}
+
+impl ManifestDiff {
+    /// Returns true if applying the manifest would change nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.create.is_empty() && self.delete.is_empty() && self.change.is_empty()
+    }
+}
+
+/// Lists the fields of `existing` that differ from `entry`.
+fn changed_fields(entry: &ManifestEntry, existing: &Reservation) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if entry.port != existing.port() {
+        fields.push("port");
+    }
+    if entry.project.as_deref() != existing.project() {
+        fields.push("project");
+    }
+    if entry.task.as_deref() != existing.task() {
+        fields.push("task");
+    }
+    if entry
+        .pinned
+        .is_some_and(|pinned| pinned != existing.pinned())
+    {
+        fields.push("pinned");
+    }
+    fields
+}
+
+/// Orders keys by path, then tag (untagged first), for stable output.
+fn key_order(a: &ReservationKey, b: &ReservationKey) -> std::cmp::Ordering {
+    (&a.path, &a.tag).cmp(&(&b.path, &b.tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reservation(path: &str, tag: Option<&str>, port: u16) -> Reservation {
+        let key = ReservationKey::new(PathBuf::from(path), tag.map(String::from)).unwrap();
+        Reservation::builder(key, Port::try_from(port).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_from_json_resolves_relative_paths() {
+        let manifest = Manifest::from_json(
+            r#"[{"path": "app", "port": 5000, "created_at": "ignored"}]"#,
+            Path::new("/repo"),
+        )
+        .unwrap();
+        assert_eq!(manifest.entries[0].key.path, PathBuf::from("/repo/app"));
+        assert_eq!(manifest.entries[0].key.tag, None);
+    }
+
+    #[test]
+    fn test_from_json_rejects_duplicates() {
+        let result = Manifest::from_json(
+            r#"[{"path": "/a", "port": 5000}, {"path": "/a", "port": 5001}]"#,
+            Path::new("/"),
+        );
+        assert!(matches!(result, Err(Error::Validation { .. })));
+    }
+
+    #[test]
+    fn test_from_json_rejects_invalid_port() {
+        let result = Manifest::from_json(r#"[{"path": "/a", "port": 0}]"#, Path::new("/"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_json_rejects_non_array() {
+        let result = Manifest::from_json(r#"{"path": "/a"}"#, Path::new("/"));
+        assert!(matches!(result, Err(Error::Validation { .. })));
+    }
+
+    #[test]
+    fn test_diff_create_delete_change() {
+        let manifest = Manifest::from_json(
+            r#"[
+                {"path": "/same", "port": 5000},
+                {"path": "/moved", "tag": "web", "port": 5101},
+                {"path": "/new", "port": 5200, "project": "p"}
+            ]"#,
+            Path::new("/"),
+        )
+        .unwrap();
+        let current = vec![
+            reservation("/same", None, 5000),
+            reservation("/moved", Some("web"), 5100),
+            reservation("/gone", None, 5300),
+        ];
+
+        let diff = manifest.diff(&current);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.create.len(), 1);
+        assert_eq!(diff.create[0].key.path, PathBuf::from("/new"));
+        assert_eq!(diff.delete.len(), 1);
+        assert_eq!(diff.delete[0].port().value(), 5300);
+        assert_eq!(diff.change.len(), 1);
+        assert_eq!(diff.change[0].fields, vec!["port"]);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_pinned_only_when_specified() {
+        let current = vec![reservation("/a", None, 5000)];
+
+        let unspecified =
+            Manifest::from_json(r#"[{"path": "/a", "port": 5000}]"#, Path::new("/")).unwrap();
+        assert!(unspecified.diff(&current).is_empty());
+
+        let pinned = Manifest::from_json(
+            r#"[{"path": "/a", "port": 5000, "pinned": true}]"#,
+            Path::new("/"),
+        )
+        .unwrap();
+        assert_eq!(pinned.diff(&current).change[0].fields, vec!["pinned"]);
+    }
+}
@@ -223,9 +223,13 @@ impl MigratePlan {
             // Create new reservation key with the new path but same tag
             let to_key = ReservationKey::new(new_path, from_key.tag.clone())?;
 
-            // Check if reservation already exists at destination
-            let has_conflict = Database::get_reservation(db.connection(), &to_key)?.is_some();
-            if has_conflict {
+            // Check if reservation already exists at destination. A pinned
+            // one can't be overwritten, even with --force.
+            let existing = Database::get_reservation(db.connection(), &to_key)?;
+            if let Some(ref existing) = existing {
+                existing.ensure_unpinned()?;
+            }
+            if existing.is_some() {
                 // Conflict detected - track it
                 self.conflicts.push(to_key.clone());
                 log::debug!("Conflict detected: {to_key} already exists");
@@ -349,6 +353,10 @@ pub fn to_operation_plan(migrate_plan: &MigratePlan) -> OperationPlan {
             .task(item.reservation.task().map(String::from))
             .created_at(item.reservation.created_at())
             .last_used_at(item.reservation.last_used_at())
+            .pinned(item.reservation.pinned())
+            .expires_at(item.reservation.expires_at())
+            .note(item.reservation.note().map(String::from))
+            .priority(item.reservation.priority())
             .build()
             .expect("Building reservation from valid data should succeed");
 
@@ -430,6 +438,8 @@ mod tests {
     use crate::database::test_util::create_test_database;
     #[cfg(unix)]
     use crate::{Port, Reservation};
+    #[cfg(unix)]
+    use std::time::{Duration, SystemTime};
 
     #[test]
     fn test_migrate_options_new() {
@@ -593,6 +603,89 @@ mod tests {
         assert_eq!(plan.conflict_count(), 1); // Conflict detected but will be resolved
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_migrate_plan_pinned_conflict_force() {
+        let mut db = create_test_database();
+
+        let from_key = ReservationKey::new(PathBuf::from("/old/path"), None).unwrap();
+        let to_key = ReservationKey::new(PathBuf::from("/new/path"), None).unwrap();
+        let port1 = Port::try_from(5000).unwrap();
+        let port2 = Port::try_from(5001).unwrap();
+
+        let r1 = Reservation::builder(from_key, port1).build().unwrap();
+        let r2 = Reservation::builder(to_key, port2)
+            .pinned(true)
+            .build()
+            .unwrap();
+
+        db.create_reservation(&r1).unwrap();
+        db.create_reservation(&r2).unwrap();
+
+        // Force does not overwrite a pinned destination reservation
+        let options = MigrateOptions::new(PathBuf::from("/old/path"), PathBuf::from("/new/path"))
+            .with_force(true);
+        let mut plan = MigratePlan::new(options);
+        let result = plan.build(&db);
+
+        assert!(matches!(result, Err(Error::ReservationPinned { .. })));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_migrate_preserves_pinned_flag() {
+        let mut db = create_test_database();
+
+        let from_key = ReservationKey::new(PathBuf::from("/old/path"), None).unwrap();
+        let port = Port::try_from(5000).unwrap();
+        let reservation = Reservation::builder(from_key, port)
+            .pinned(true)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let options = MigrateOptions::new(PathBuf::from("/old/path"), PathBuf::from("/new/path"));
+        let mut plan = MigratePlan::new(options);
+        plan.build(&db).unwrap();
+
+        let operation_plan = to_operation_plan(&plan);
+        let created = operation_plan
+            .actions
+            .iter()
+            .find_map(|action| match action {
+                PlanAction::CreateReservation(r) => Some(r),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(created.port(), port);
+        assert!(created.pinned());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_migrate_preserves_ttl() {
+        let mut db = create_test_database();
+
+        let from_key = ReservationKey::new(PathBuf::from("/old/path"), None).unwrap();
+        let expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(4_000_000_000);
+        let reservation = Reservation::builder(from_key, Port::try_from(5000).unwrap())
+            .expires_at(Some(expires_at))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let options = MigrateOptions::new(PathBuf::from("/old/path"), PathBuf::from("/new/path"));
+        let mut plan = MigratePlan::new(options);
+        plan.build(&db).unwrap();
+        execute_migrate(&plan, &mut db).unwrap();
+
+        let to_key = ReservationKey::new(PathBuf::from("/new/path"), None).unwrap();
+        let migrated = Database::get_reservation(db.connection(), &to_key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(migrated.expires_at(), Some(expires_at));
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_migrate_plan_recursive() {
@@ -38,11 +38,16 @@
 pub mod autoreserve;
 pub mod cleanup;
 pub mod executor;
+pub mod hook;
 pub mod inference;
 pub mod init;
+pub mod manifest;
 pub mod migrate;
+pub mod move_reservation;
 pub mod plan;
+pub mod rehome;
 pub mod release;
+pub mod renew;
 pub mod reserve;
 pub mod reserve_group;
 
@@ -50,11 +55,18 @@ pub mod reserve_group;
 mod proptests;
 
 pub use autoreserve::{AutoreserveOptions, AutoreservePlan};
-pub use cleanup::{AutocleanResult, CleanupOperations, ExpireResult, PruneResult};
+pub use cleanup::{
+    AutocleanResult, CleanupOperations, CleanupReason, ExpireResult, PruneResult, PruneScope,
+};
 pub use executor::{ExecutionResult, PlanExecutor};
+pub use hook::{HookOutcome, PreReserveHook};
 pub use init::{init_database, InitOptions, InitResult};
+pub use manifest::{Manifest, ManifestChange, ManifestDiff, ManifestEntry};
 pub use migrate::{execute_migrate, MigrateOptions, MigratePlan, MigrateResult, MigrationItem};
+pub use move_reservation::{MoveOptions, MovePlan};
 pub use plan::{OperationPlan, PlanAction};
-pub use release::{ReleaseOptions, ReleasePlan};
-pub use reserve::{ReserveOptions, ReservePlan};
+pub use rehome::{execute_rehome, RehomeItem, RehomeOptions, RehomePlan, RehomeResult};
+pub use release::{BulkReleaseOptions, BulkReleasePlan, ReleaseOptions, ReleasePlan};
+pub use renew::{RenewOptions, RenewPlan};
+pub use reserve::{first_free_port, AllocationStrategy, ReserveOptions, ReservePlan};
 pub use reserve_group::{ReserveGroupOptions, ReserveGroupPlan};
@@ -0,0 +1,236 @@
+//! Move operation for transferring a single reservation to a new path.
+//!
+//! Moving changes only the path component of a reservation's key. The port,
+//! tag, project, task, timestamps, and pinned flag are all kept, so a renamed
+//! project directory keeps the port its configuration already refers to.
+
+use std::path::PathBuf;
+
+use crate::database::Database;
+use crate::error::{Error, Result};
+use crate::path::normalize::normalize;
+use crate::ReservationKey;
+use rusqlite::Connection;
+
+use super::plan::{OperationPlan, PlanAction};
+
+/// Options for a move operation.
+#[derive(Debug, Clone)]
+pub struct MoveOptions {
+    /// The reservation to move.
+    pub key: ReservationKey,
+    /// The path to move the reservation to.
+    pub to_path: PathBuf,
+    /// Replace an existing reservation at the destination.
+    pub force: bool,
+}
+
+impl MoveOptions {
+    /// Creates a new `MoveOptions` for the given key and destination path.
+    ///
+    /// Force is off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::MoveOptions;
+    /// use trop::ReservationKey;
+    /// use std::path::PathBuf;
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/old"), None).unwrap();
+    /// let options = MoveOptions::new(key, PathBuf::from("/new"));
+    /// assert!(!options.force);
+    /// ```
+    #[must_use]
+    pub const fn new(key: ReservationKey, to_path: PathBuf) -> Self {
+        Self {
+            key,
+            to_path,
+            force: false,
+        }
+    }
+
+    /// Sets the force flag.
+    #[must_use]
+    pub const fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+}
+
+/// A move plan generator.
+pub struct MovePlan {
+    options: MoveOptions,
+}
+
+impl MovePlan {
+    /// Creates a new move plan with the given options.
+    #[must_use]
+    pub const fn new(options: MoveOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds an operation plan for this move request.
+    ///
+    /// The destination path is normalized and keeps the source's tag. If a
+    /// reservation already exists there, the plan deletes it first (only with
+    /// force). This method does NOT modify the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The destination path cannot be normalized
+    /// - No reservation exists for the key (`Error::NotFound`)
+    /// - The destination is the reservation's current key
+    /// - A reservation exists at the destination and force is not set
+    ///   (`Error::ReservationConflict`)
+    /// - The reservation at the destination is pinned, even with force
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::operations::{MovePlan, MoveOptions};
+    /// use trop::{Database, DatabaseConfig, ReservationKey};
+    /// use std::path::PathBuf;
+    ///
+    /// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let key = ReservationKey::new(PathBuf::from("/old"), None).unwrap();
+    ///
+    /// let options = MoveOptions::new(key, PathBuf::from("/new"));
+    /// let plan = MovePlan::new(options).build_plan(db.connection()).unwrap();
+    /// ```
+    pub fn build_plan(&self, conn: &Connection) -> Result<OperationPlan> {
+        let from = &self.options.key;
+        if Database::get_reservation(conn, from)?.is_none() {
+            return Err(Error::NotFound {
+                resource: format!("reservation for {from}"),
+            });
+        }
+
+        let to_path = normalize(&self.options.to_path)?;
+        let to = ReservationKey::new(to_path, from.tag.clone())?;
+        if &to == from {
+            return Err(Error::Validation {
+                field: "path".into(),
+                message: format!("reservation is already at {}", to.path.display()),
+            });
+        }
+
+        let mut plan = OperationPlan::new(format!("Move reservation for {from} to {to}"));
+
+        // A pinned destination can't be replaced, even with force
+        if let Some(existing) = Database::get_reservation(conn, &to)? {
+            existing.ensure_unpinned()?;
+            if !self.options.force {
+                return Err(Error::ReservationConflict {
+                    details: format!(
+                        "{to} already has a reservation on port {}. Use --force to replace it.",
+                        existing.port()
+                    ),
+                });
+            }
+            plan = plan.add_action(PlanAction::DeleteReservation(to.clone()));
+        }
+
+        Ok(plan.add_action(PlanAction::MoveReservation {
+            from: from.clone(),
+            to,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_util::create_test_database;
+    use crate::operations::PlanExecutor;
+    use crate::{Port, Reservation};
+
+    fn reserve(db: &mut Database, path: &str, tag: Option<&str>, port: u16) -> Reservation {
+        let key = ReservationKey::new(PathBuf::from(path), tag.map(String::from)).unwrap();
+        let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+            .project(Some("proj".into()))
+            .task(Some("task".into()))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+        reservation
+    }
+
+    #[test]
+    fn test_move_keeps_port_and_metadata() {
+        let mut db = create_test_database();
+        let original = reserve(&mut db, "/old", Some("web"), 5000);
+
+        let options = MoveOptions::new(original.key().clone(), PathBuf::from("/new"));
+        let plan = MovePlan::new(options).build_plan(db.connection()).unwrap();
+        let result = PlanExecutor::new(db.connection()).execute(&plan).unwrap();
+        assert_eq!(result.port, Some(original.port()));
+
+        let to = ReservationKey::new(PathBuf::from("/new"), Some("web".into())).unwrap();
+        let moved = Database::get_reservation(db.connection(), &to)
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.port(), original.port());
+        assert_eq!(moved.project(), original.project());
+        assert_eq!(moved.task(), original.task());
+        assert!(Database::get_reservation(db.connection(), original.key())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_move_missing_reservation() {
+        let db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/missing"), None).unwrap();
+
+        let result =
+            MovePlan::new(MoveOptions::new(key, PathBuf::from("/new"))).build_plan(db.connection());
+        assert!(matches!(result, Err(Error::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_move_onto_existing_requires_force() {
+        let mut db = create_test_database();
+        let original = reserve(&mut db, "/old", None, 5000);
+        reserve(&mut db, "/new", None, 5001);
+
+        let options = MoveOptions::new(original.key().clone(), PathBuf::from("/new"));
+        let result = MovePlan::new(options.clone()).build_plan(db.connection());
+        assert!(matches!(result, Err(Error::ReservationConflict { .. })));
+
+        let plan = MovePlan::new(options.with_force(true))
+            .build_plan(db.connection())
+            .unwrap();
+        assert_eq!(plan.actions.len(), 2);
+        PlanExecutor::new(db.connection()).execute(&plan).unwrap();
+
+        let all = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].key().path, PathBuf::from("/new"));
+        assert_eq!(all[0].port(), original.port());
+    }
+
+    #[test]
+    fn test_move_onto_pinned_refused_with_force() {
+        let mut db = create_test_database();
+        let original = reserve(&mut db, "/old", None, 5000);
+        let pinned = reserve(&mut db, "/new", None, 5001);
+        Database::set_pinned(db.connection(), pinned.key(), true).unwrap();
+
+        let options =
+            MoveOptions::new(original.key().clone(), PathBuf::from("/new")).with_force(true);
+        let result = MovePlan::new(options).build_plan(db.connection());
+        assert!(matches!(result, Err(Error::ReservationPinned { .. })));
+    }
+
+    #[test]
+    fn test_move_to_same_path_rejected() {
+        let mut db = create_test_database();
+        let original = reserve(&mut db, "/old", None, 5000);
+
+        let options = MoveOptions::new(original.key().clone(), PathBuf::from("/old"));
+        let result = MovePlan::new(options).build_plan(db.connection());
+        assert!(matches!(result, Err(Error::Validation { .. })));
+    }
+}
@@ -3,6 +3,10 @@
 //! This module defines the plan structures that describe what actions
 //! will be taken during an operation, without actually performing them.
 
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+
 use crate::port::group::GroupAllocationRequest;
 use crate::port::occupancy::OccupancyCheckConfig;
 use crate::{Reservation, ReservationKey};
@@ -29,7 +33,12 @@ pub enum PlanAction {
     CreateReservation(Reservation),
 
     /// Update an existing reservation.
-    UpdateReservation(Reservation),
+    UpdateReservation {
+        /// The reservation as currently stored.
+        from: Reservation,
+        /// The reservation that will be written in its place.
+        to: Reservation,
+    },
 
     /// Update the `last_used_at` timestamp for a reservation.
     UpdateLastUsed(ReservationKey),
@@ -37,6 +46,14 @@ pub enum PlanAction {
     /// Delete a reservation.
     DeleteReservation(ReservationKey),
 
+    /// Move a reservation to a new key, keeping its port and metadata.
+    MoveReservation {
+        /// The current reservation key.
+        from: ReservationKey,
+        /// The key to move the reservation to.
+        to: ReservationKey,
+    },
+
     /// Allocate a group of related ports.
     AllocateGroup {
         /// The group allocation request.
@@ -56,8 +73,8 @@ impl PlanAction {
             Self::CreateReservation(r) => {
                 format!("Create reservation for {} on port {}", r.key(), r.port())
             }
-            Self::UpdateReservation(r) => {
-                format!("Update reservation for {} to port {}", r.key(), r.port())
+            Self::UpdateReservation { to, .. } => {
+                format!("Update reservation for {} to port {}", to.key(), to.port())
             }
             Self::UpdateLastUsed(key) => {
                 format!("Update last_used_at timestamp for {key}")
@@ -65,6 +82,9 @@ impl PlanAction {
             Self::DeleteReservation(key) => {
                 format!("Delete reservation for {key}")
             }
+            Self::MoveReservation { from, to } => {
+                format!("Move reservation for {from} to {to}")
+            }
             Self::AllocateGroup { request, .. } => {
                 format!(
                     "Allocate group of {} services at {}",
@@ -74,6 +94,88 @@ impl PlanAction {
             }
         }
     }
+
+    /// Returns diff lines describing what this action would write.
+    ///
+    /// New reservations (`+`) list every resolved field; updates (`~`) list
+    /// each changed field as `old -> new`; deletions are marked `-`, and
+    /// timestamp-only refreshes `=`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::PlanAction;
+    /// use trop::{Port, Reservation, ReservationKey};
+    /// use std::path::PathBuf;
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+    /// let reservation = Reservation::builder(key, Port::try_from(8080).unwrap())
+    ///     .project(Some("app".to_string()))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let lines = PlanAction::CreateReservation(reservation).diff_lines();
+    /// assert!(lines.contains(&"+   port: 8080".to_string()));
+    /// assert!(lines.contains(&"+   project: app".to_string()));
+    /// ```
+    #[must_use]
+    pub fn diff_lines(&self) -> Vec<String> {
+        match self {
+            Self::CreateReservation(r) => std::iter::once(format!("+ {}", r.key()))
+                .chain(
+                    written_fields(r)
+                        .into_iter()
+                        .map(|(name, value)| format!("+   {name}: {value}")),
+                )
+                .collect(),
+            Self::UpdateReservation { from, to } => {
+                let mut lines = vec![format!("~ {}", to.key())];
+                lines.extend(
+                    written_fields(from)
+                        .into_iter()
+                        .zip(written_fields(to))
+                        .filter(|((_, old), (_, new))| old != new)
+                        .map(|((name, old), (_, new))| format!("~   {name}: {old} -> {new}")),
+                );
+                if lines.len() == 1 {
+                    lines.push("~   (no field changes)".to_string());
+                }
+                lines
+            }
+            Self::UpdateLastUsed(key) => vec![format!("= {key} (last_used_at only)")],
+            Self::DeleteReservation(key) => vec![format!("- {key}")],
+            Self::MoveReservation { from, to } => vec![format!("- {from}"), format!("+ {to}")],
+            Self::AllocateGroup { .. } => vec![format!("+ {}", self.description())],
+        }
+    }
+}
+
+/// Lists the user-visible fields a reservation write would store.
+///
+/// `last_used_at` is left out since every write refreshes it.
+fn written_fields(reservation: &Reservation) -> [(&'static str, String); 8] {
+    let or_none = |value: Option<&str>| value.unwrap_or("(none)").to_string();
+    [
+        ("port", reservation.port().to_string()),
+        ("project", or_none(reservation.project())),
+        ("task", or_none(reservation.task())),
+        ("sticky", reservation.sticky().to_string()),
+        ("pinned", reservation.pinned().to_string()),
+        (
+            "expires_at",
+            reservation
+                .expires_at()
+                .map_or_else(|| "(none)".to_string(), format_time),
+        ),
+        ("note", or_none(reservation.note())),
+        ("priority", reservation.priority().to_string()),
+    ]
+}
+
+/// Formats a timestamp the way the CLI displays them (UTC).
+fn format_time(time: SystemTime) -> String {
+    let time: DateTime<Utc> = time.into();
+    time.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// A complete operation plan describing all actions to be taken.
@@ -193,6 +295,25 @@ impl OperationPlan {
     pub fn len(&self) -> usize {
         self.actions.len()
     }
+
+    /// Renders the plan as a diff of the changes it would make.
+    ///
+    /// Each action contributes its [`PlanAction::diff_lines`], followed by
+    /// one line per warning.
+    #[must_use]
+    pub fn render_diff(&self) -> String {
+        let mut out = String::new();
+        for line in self.actions.iter().flat_map(PlanAction::diff_lines) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        for warning in &self.warnings {
+            out.push_str("warning: ");
+            out.push_str(warning);
+            out.push('\n');
+        }
+        out
+    }
 }
 
 #[cfg(test)]
@@ -341,7 +462,10 @@ mod tests {
 
                 let actions = vec![
                     PlanAction::CreateReservation(reservation.clone()),
-                    PlanAction::UpdateReservation(reservation),
+                    PlanAction::UpdateReservation {
+                        from: reservation.clone(),
+                        to: reservation,
+                    },
                     PlanAction::UpdateLastUsed(key.clone()),
                     PlanAction::DeleteReservation(key),
                 ];
@@ -382,14 +506,17 @@ mod tests {
                 let reservation = Reservation::builder(key, port).build().unwrap();
 
                 let create_action = PlanAction::CreateReservation(reservation.clone());
-                let update_action = PlanAction::UpdateReservation(reservation.clone());
+                let update_action = PlanAction::UpdateReservation {
+                    from: reservation.clone(),
+                    to: reservation.clone(),
+                };
 
                 // Extract reservations from actions
                 if let PlanAction::CreateReservation(r) = create_action {
                     prop_assert_eq!(r.port(), port);
                 }
 
-                if let PlanAction::UpdateReservation(r) = update_action {
+                if let PlanAction::UpdateReservation { to: r, .. } = update_action {
                     prop_assert_eq!(r.port(), port);
                 }
             }
@@ -411,6 +538,44 @@ mod tests {
         assert!(desc.contains("8080"));
     }
 
+    #[test]
+    fn test_diff_lines_for_update_and_removal() {
+        let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+        let from = Reservation::builder(key.clone(), Port::try_from(8080).unwrap())
+            .task(Some("old".to_string()))
+            .build()
+            .unwrap();
+        let to = Reservation::builder(key.clone(), Port::try_from(8081).unwrap())
+            .task(Some("new".to_string()))
+            .sticky(true)
+            .build()
+            .unwrap();
+
+        let lines = PlanAction::UpdateReservation {
+            from: from.clone(),
+            to,
+        }
+        .diff_lines();
+        assert_eq!(
+            lines[1..],
+            [
+                "~   port: 8080 -> 8081",
+                "~   task: old -> new",
+                "~   sticky: false -> true",
+            ]
+        );
+
+        let unchanged = PlanAction::UpdateReservation {
+            from: from.clone(),
+            to: from,
+        }
+        .diff_lines();
+        assert_eq!(unchanged[1], "~   (no field changes)");
+
+        let removed = PlanAction::DeleteReservation(key.clone()).diff_lines();
+        assert_eq!(removed, [format!("- {key}")]);
+    }
+
     #[test]
     fn test_operation_plan_new() {
         let plan = OperationPlan::new("Test operation");
@@ -0,0 +1,468 @@
+//! Rehome operation for reservations outside the configured port range.
+//!
+//! When the configured port range is narrowed, existing reservations may be
+//! left on ports outside it. Rehoming reallocates each of them to an in-range
+//! port chosen by the allocator, preserving the reservation key and all other
+//! metadata (project, task, timestamps, pinned flag).
+//!
+//! Pinned and sticky reservations are left in place unless `force` is set.
+
+use rusqlite::Connection;
+
+use crate::config::Config;
+use crate::database::Database;
+use crate::error::{Error, Result};
+use crate::port::allocator::{allocator_from_config, PortAllocator};
+use crate::port::occupancy::{OccupancyCheckConfig, PortOccupancyChecker};
+use crate::{Port, PortRange, Reservation};
+
+use super::plan::{OperationPlan, PlanAction};
+
+/// Options for the rehome operation.
+#[derive(Debug, Clone, Default)]
+pub struct RehomeOptions {
+    /// Also rehome pinned and sticky reservations.
+    pub force: bool,
+    /// Preview changes without applying them.
+    pub dry_run: bool,
+}
+
+impl RehomeOptions {
+    /// Creates a new `RehomeOptions` with all flags disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::RehomeOptions;
+    ///
+    /// let options = RehomeOptions::new();
+    /// assert!(!options.force);
+    /// assert!(!options.dry_run);
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            force: false,
+            dry_run: false,
+        }
+    }
+
+    /// Sets the force flag.
+    #[must_use]
+    pub const fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Sets the `dry_run` flag.
+    #[must_use]
+    pub const fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// A single reservation to move into the configured range.
+#[derive(Debug)]
+pub struct RehomeItem {
+    /// The reservation as currently stored (on its out-of-range port).
+    pub reservation: Reservation,
+    /// The in-range port it will be moved to.
+    pub new_port: Port,
+}
+
+/// Rehome plan describing which reservations will be moved.
+#[derive(Debug)]
+pub struct RehomePlan {
+    /// The rehome options.
+    pub options: RehomeOptions,
+    /// The range reservations are being moved into.
+    pub range: Option<PortRange>,
+    /// Reservations to move, with their new ports.
+    pub moves: Vec<RehomeItem>,
+    /// Out-of-range reservations left in place because they are pinned or
+    /// sticky and `force` was not set.
+    pub skipped: Vec<Reservation>,
+}
+
+/// Result of rehome execution.
+#[derive(Debug)]
+pub struct RehomeResult {
+    /// Number of reservations moved into range.
+    pub rehomed_count: usize,
+    /// Number of out-of-range reservations left in place.
+    pub skipped_count: usize,
+}
+
+impl RehomePlan {
+    /// Creates a new empty rehome plan.
+    #[must_use]
+    pub const fn new(options: RehomeOptions) -> Self {
+        Self {
+            options,
+            range: None,
+            moves: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Builds the plan using the range, exclusions, and occupancy settings
+    /// from `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The configured port range is invalid
+    /// - Database queries or occupancy checks fail
+    /// - The range has too few free ports for every reservation being moved
+    pub fn build(&mut self, conn: &Connection, config: &Config) -> Result<()> {
+        let allocator = allocator_from_config(config)?;
+        let occupancy_config = OccupancyCheckConfig::from_config(config);
+        self.build_with_allocator(conn, &allocator, &occupancy_config)
+    }
+
+    /// Builds the plan using an explicit allocator.
+    ///
+    /// Every reservation whose port lies outside the allocator's range is
+    /// assigned the next free in-range port, scanning forward so that no two
+    /// reservations in the plan receive the same port.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database queries or occupancy checks fail, or if
+    /// the range is exhausted before every reservation has a new port.
+    pub fn build_with_allocator<C: PortOccupancyChecker>(
+        &mut self,
+        conn: &Connection,
+        allocator: &PortAllocator<C>,
+        occupancy_config: &OccupancyCheckConfig,
+    ) -> Result<()> {
+        let range = *allocator.range();
+        self.range = Some(range);
+
+        let mut start = Some(range.min());
+        for reservation in find_out_of_range(conn, &range)? {
+            if (reservation.pinned() || reservation.is_sticky()) && !self.options.force {
+                log::debug!("Skipping protected reservation {}", reservation.key());
+                self.skipped.push(reservation);
+                continue;
+            }
+
+            let next = match start {
+                Some(start) => allocator.find_next_available(start, conn, occupancy_config)?,
+                None => None,
+            };
+            let Some(new_port) = next else {
+                return Err(Error::PortExhausted {
+                    range,
+                    tried_cleanup: false,
+                    usage: None,
+                    requested: None,
+                    requested_is_preferred: false,
+                });
+            };
+
+            // Ports chosen earlier in this plan aren't in the database yet,
+            // so continue the scan after the one just assigned.
+            start = new_port.checked_add(1).filter(|port| range.contains(*port));
+            self.moves.push(RehomeItem {
+                reservation,
+                new_port,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of reservations that will be moved.
+    #[must_use]
+    pub fn rehome_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Returns the number of out-of-range reservations left in place.
+    #[must_use]
+    pub fn skipped_count(&self) -> usize {
+        self.skipped.len()
+    }
+
+    /// Converts this plan into an operation plan.
+    ///
+    /// Each move becomes an update of the existing reservation to its new
+    /// port with all other metadata preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if building a reservation from valid existing data fails,
+    /// which should never happen in practice.
+    #[must_use]
+    pub fn to_operation_plan(&self) -> OperationPlan {
+        let mut plan = OperationPlan::new("Rehome out-of-range reservations");
+
+        if self.moves.is_empty() {
+            plan = plan.add_warning("No reservations to rehome");
+        }
+
+        for item in &self.moves {
+            let existing = &item.reservation;
+            let moved = Reservation::builder(existing.key().clone(), item.new_port)
+                .project(existing.project().map(String::from))
+                .task(existing.task().map(String::from))
+                .sticky(existing.sticky())
+                .pinned(existing.pinned())
+                .expires_at(existing.expires_at())
+                .note(existing.note().map(String::from))
+                .priority(existing.priority())
+                .created_at(existing.created_at())
+                .last_used_at(existing.last_used_at())
+                .build()
+                .expect("Building reservation from valid data should succeed");
+            plan = plan.add_action(PlanAction::UpdateReservation {
+                from: existing.clone(),
+                to: moved,
+            });
+        }
+
+        plan
+    }
+}
+
+/// Lists reservations whose port lies outside `range`, ordered by port.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn find_out_of_range(conn: &Connection, range: &PortRange) -> Result<Vec<Reservation>> {
+    let mut stranded: Vec<Reservation> = Database::list_all_reservations(conn)?
+        .into_iter()
+        .filter(|r| !range.contains(r.port()))
+        .collect();
+    stranded.sort_by_key(Reservation::port);
+    Ok(stranded)
+}
+
+/// Executes a rehome plan inside a single transaction.
+///
+/// # Errors
+///
+/// Returns an error if any database operation fails; no changes are applied
+/// in that case.
+pub fn execute_rehome(plan: &RehomePlan, db: &mut Database) -> Result<RehomeResult> {
+    use super::executor::PlanExecutor;
+
+    let op_plan = plan.to_operation_plan();
+
+    let tx = db.begin_transaction()?;
+    let mut executor = PlanExecutor::new(&tx);
+    executor.execute(&op_plan)?;
+    tx.commit()?;
+
+    Ok(RehomeResult {
+        rehomed_count: plan.rehome_count(),
+        skipped_count: plan.skipped_count(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_util::create_test_database;
+    use crate::port::exclusions::ExclusionManager;
+    use crate::port::occupancy::MockOccupancyChecker;
+    use crate::ReservationKey;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    fn allocator(min: u16, max: u16) -> PortAllocator<MockOccupancyChecker> {
+        let range =
+            PortRange::new(Port::try_from(min).unwrap(), Port::try_from(max).unwrap()).unwrap();
+        PortAllocator::new(
+            MockOccupancyChecker::new(HashSet::new()),
+            ExclusionManager::empty(),
+            range,
+        )
+    }
+
+    fn reserve(db: &mut Database, path: &str, port: u16, pinned: bool) -> Reservation {
+        reserve_for_project(db, path, port, pinned, None)
+    }
+
+    fn reserve_for_project(
+        db: &mut Database,
+        path: &str,
+        port: u16,
+        pinned: bool,
+        project: Option<&str>,
+    ) -> Reservation {
+        let key = ReservationKey::new(PathBuf::from(path), None).unwrap();
+        let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+            .project(project.map(String::from))
+            .pinned(pinned)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+        reservation
+    }
+
+    #[test]
+    fn test_find_out_of_range() {
+        let mut db = create_test_database();
+        reserve(&mut db, "/a", 5000, false);
+        reserve(&mut db, "/b", 9000, false);
+        reserve(&mut db, "/c", 4000, false);
+
+        let range =
+            PortRange::new(Port::try_from(5000).unwrap(), Port::try_from(6000).unwrap()).unwrap();
+        let stranded = find_out_of_range(db.connection(), &range).unwrap();
+        let ports: Vec<u16> = stranded.iter().map(|r| r.port().value()).collect();
+        assert_eq!(ports, vec![4000, 9000]);
+    }
+
+    #[test]
+    fn test_rehome_assigns_distinct_free_ports() {
+        let mut db = create_test_database();
+        reserve(&mut db, "/in-range", 5000, false);
+        reserve(&mut db, "/a", 9000, false);
+        reserve(&mut db, "/b", 9001, false);
+
+        let mut plan = RehomePlan::new(RehomeOptions::new());
+        plan.build_with_allocator(
+            db.connection(),
+            &allocator(5000, 5010),
+            &OccupancyCheckConfig::default(),
+        )
+        .unwrap();
+
+        let new_ports: Vec<u16> = plan.moves.iter().map(|m| m.new_port.value()).collect();
+        assert_eq!(new_ports, vec![5001, 5002]);
+        assert_eq!(plan.skipped_count(), 0);
+    }
+
+    #[test]
+    fn test_rehome_skips_pinned_without_force() {
+        let mut db = create_test_database();
+        reserve(&mut db, "/pinned", 9000, true);
+        reserve(&mut db, "/plain", 9001, false);
+
+        let mut plan = RehomePlan::new(RehomeOptions::new());
+        plan.build_with_allocator(
+            db.connection(),
+            &allocator(5000, 5010),
+            &OccupancyCheckConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.rehome_count(), 1);
+        assert_eq!(plan.skipped_count(), 1);
+        assert!(plan.skipped[0].pinned());
+
+        let mut forced = RehomePlan::new(RehomeOptions::new().with_force(true));
+        forced
+            .build_with_allocator(
+                db.connection(),
+                &allocator(5000, 5010),
+                &OccupancyCheckConfig::default(),
+            )
+            .unwrap();
+        assert_eq!(forced.rehome_count(), 2);
+    }
+
+    #[test]
+    fn test_rehome_skips_sticky_without_force() {
+        let mut db = create_test_database();
+        reserve_for_project(&mut db, "/sticky", 9000, false, Some("proj"));
+        reserve(&mut db, "/plain", 9001, false);
+
+        let mut plan = RehomePlan::new(RehomeOptions::new());
+        plan.build_with_allocator(
+            db.connection(),
+            &allocator(5000, 5010),
+            &OccupancyCheckConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.rehome_count(), 1);
+        assert_eq!(plan.skipped_count(), 1);
+        assert_eq!(plan.skipped[0].project(), Some("proj"));
+
+        let mut forced = RehomePlan::new(RehomeOptions::new().with_force(true));
+        forced
+            .build_with_allocator(
+                db.connection(),
+                &allocator(5000, 5010),
+                &OccupancyCheckConfig::default(),
+            )
+            .unwrap();
+        assert_eq!(forced.rehome_count(), 2);
+    }
+
+    #[test]
+    fn test_rehome_exhausted() {
+        let mut db = create_test_database();
+        reserve(&mut db, "/taken", 5000, false);
+        reserve(&mut db, "/a", 9000, false);
+
+        let mut plan = RehomePlan::new(RehomeOptions::new());
+        let result = plan.build_with_allocator(
+            db.connection(),
+            &allocator(5000, 5000),
+            &OccupancyCheckConfig::default(),
+        );
+        assert!(matches!(result, Err(Error::PortExhausted { .. })));
+    }
+
+    #[test]
+    fn test_execute_rehome_preserves_metadata() {
+        let mut db = create_test_database();
+        let key = reserve_for_project(&mut db, "/a", 9000, true, Some("proj"))
+            .key()
+            .clone();
+        let original = Database::get_reservation(db.connection(), &key)
+            .unwrap()
+            .unwrap();
+
+        let mut plan = RehomePlan::new(RehomeOptions::new().with_force(true));
+        plan.build_with_allocator(
+            db.connection(),
+            &allocator(5000, 5010),
+            &OccupancyCheckConfig::default(),
+        )
+        .unwrap();
+        let result = execute_rehome(&plan, &mut db).unwrap();
+        assert_eq!(result.rehomed_count, 1);
+
+        let moved = Database::get_reservation(db.connection(), &key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.port().value(), 5000);
+        assert_eq!(moved.project(), Some("proj"));
+        assert!(moved.pinned());
+        assert_eq!(moved.created_at(), original.created_at());
+    }
+
+    #[test]
+    fn test_execute_rehome_preserves_ttl() {
+        let mut db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/ttl"), None).unwrap();
+        let expires_at = SystemTime::UNIX_EPOCH + Duration::from_secs(4_000_000_000);
+        let reservation = Reservation::builder(key.clone(), Port::try_from(9000).unwrap())
+            .expires_at(Some(expires_at))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let mut plan = RehomePlan::new(RehomeOptions::new());
+        plan.build_with_allocator(
+            db.connection(),
+            &allocator(5000, 5010),
+            &OccupancyCheckConfig::default(),
+        )
+        .unwrap();
+        execute_rehome(&plan, &mut db).unwrap();
+
+        let moved = Database::get_reservation(db.connection(), &key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.port().value(), 5000);
+        assert_eq!(moved.expires_at(), Some(expires_at));
+    }
+}
@@ -1,10 +1,11 @@
 //! Release operation planning and execution.
 //!
 //! This module implements the release planning logic, including
-//! path validation and idempotent behavior.
+//! path validation and idempotent behavior, plus bulk release of every
+//! reservation in a project or task.
 
-use crate::database::Database;
-use crate::error::Result;
+use crate::database::{Database, ReservationFilter};
+use crate::error::{Error, Result};
 use crate::ReservationKey;
 use rusqlite::Connection;
 
@@ -104,6 +105,7 @@ impl ReleasePlan {
     ///
     /// Returns an error if:
     /// - Path relationship validation fails
+    /// - The reservation is pinned
     ///
     /// # Examples
     ///
@@ -127,7 +129,10 @@ impl ReleasePlan {
         }
 
         // Step 2: Check if reservation exists
-        if Database::get_reservation(conn, &self.options.key)?.is_some() {
+        if let Some(existing) = Database::get_reservation(conn, &self.options.key)? {
+            // Pinned reservations survive even --force until unpinned
+            existing.ensure_unpinned()?;
+
             // Reservation exists - plan to delete it
             plan = plan.add_action(PlanAction::DeleteReservation(self.options.key.clone()));
         } else {
@@ -142,11 +147,147 @@ impl ReleasePlan {
     }
 }
 
+/// Options for releasing every reservation in a project or task.
+#[derive(Debug, Clone, Default)]
+pub struct BulkReleaseOptions {
+    /// Only release reservations with this project.
+    pub project: Option<String>,
+
+    /// Only release reservations with this task.
+    pub task: Option<String>,
+
+    /// Force flag - allows releasing sticky reservations.
+    pub force: bool,
+}
+
+impl BulkReleaseOptions {
+    /// Creates options matching the given project and/or task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::BulkReleaseOptions;
+    ///
+    /// let options = BulkReleaseOptions::new(Some("app".to_string()), None);
+    /// assert_eq!(options.project.as_deref(), Some("app"));
+    /// assert!(!options.force);
+    /// ```
+    #[must_use]
+    pub const fn new(project: Option<String>, task: Option<String>) -> Self {
+        Self {
+            project,
+            task,
+            force: false,
+        }
+    }
+
+    /// Sets the force flag.
+    #[must_use]
+    pub const fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+}
+
+/// A plan generator for releasing every matching reservation at once.
+///
+/// Pinned reservations are always kept (with a warning). Every other match
+/// carries the sticky project or task it was selected by, so a non-empty
+/// plan fails unless `force` is set and nothing is released by accident.
+pub struct BulkReleasePlan {
+    options: BulkReleaseOptions,
+}
+
+impl BulkReleasePlan {
+    /// Creates a new bulk release plan with the given options.
+    #[must_use]
+    pub const fn new(options: BulkReleaseOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds a plan deleting every reservation matching the project/task.
+    ///
+    /// Matching nothing yields an empty plan. It does NOT modify the
+    /// database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Neither a project nor a task was given
+    /// - A matching reservation is sticky and `force` is not set
+    /// - The database query fails
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::operations::{BulkReleaseOptions, BulkReleasePlan};
+    /// use trop::{Database, DatabaseConfig};
+    ///
+    /// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let options = BulkReleaseOptions::new(Some("app".to_string()), None);
+    ///
+    /// let plan = BulkReleasePlan::new(options).build_plan(db.connection()).unwrap();
+    /// ```
+    pub fn build_plan(&self, conn: &Connection) -> Result<OperationPlan> {
+        let BulkReleaseOptions {
+            project,
+            task,
+            force,
+        } = &self.options;
+        let scope = match (project, task) {
+            (Some(project), Some(task)) => format!("project {project}, task {task}"),
+            (Some(project), None) => format!("project {project}"),
+            (None, Some(task)) => format!("task {task}"),
+            (None, None) => {
+                return Err(Error::Validation {
+                    field: "project".into(),
+                    message: "Bulk release needs a project or a task".into(),
+                })
+            }
+        };
+
+        let filter = ReservationFilter {
+            project: project.clone(),
+            task: task.clone(),
+            ..ReservationFilter::default()
+        };
+        let matching = Database::list_reservations_filtered(conn, &filter)?;
+
+        let sticky: Vec<String> = matching
+            .iter()
+            .filter(|r| !r.pinned() && r.is_sticky())
+            .map(|r| r.key().to_string())
+            .collect();
+        if !sticky.is_empty() && !force {
+            return Err(Error::StickyFieldChange {
+                field: "sticky".into(),
+                details: format!(
+                    "{} reservation(s) in {scope} are sticky ({}); use --force to release them",
+                    sticky.len(),
+                    sticky.join(", ")
+                ),
+            });
+        }
+
+        let mut plan = OperationPlan::new(format!("Release reservations for {scope}"));
+        for reservation in matching {
+            if reservation.pinned() {
+                plan =
+                    plan.add_warning(format!("Skipped pinned reservation {}", reservation.key()));
+            } else {
+                plan = plan.add_action(PlanAction::DeleteReservation(reservation.key().clone()));
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::database::test_util::create_test_database;
-    use crate::{Port, Reservation};
+    use crate::{Error, Port, Reservation};
     use std::path::PathBuf;
 
     // Property-based testing module
@@ -390,6 +531,27 @@ mod tests {
         assert!(matches!(plan.actions[0], PlanAction::DeleteReservation(_)));
     }
 
+    #[test]
+    fn test_plan_release_pinned_reservation_rejected() {
+        let mut db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        let reservation = Reservation::builder(key.clone(), port)
+            .pinned(true)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        // Force does not override pinning
+        let options = ReleaseOptions::new(key)
+            .with_force(true)
+            .with_allow_unrelated_path(true);
+        let result = ReleasePlan::new(options).build_plan(db.connection());
+
+        assert!(matches!(result, Err(Error::ReservationPinned { .. })));
+    }
+
     #[test]
     fn test_plan_release_nonexistent_reservation() {
         let db = create_test_database();
@@ -442,4 +604,111 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    /// Stores a reservation at `/bulk/{name}` with the given fields.
+    fn create_bulk_reservation(
+        db: &mut Database,
+        name: &str,
+        port: u16,
+        project: &str,
+        task: Option<&str>,
+    ) -> Reservation {
+        let key = ReservationKey::new(PathBuf::from(format!("/bulk/{name}")), None).unwrap();
+        let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+            .project(Some(project.to_string()))
+            .task(task.map(String::from))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+        reservation
+    }
+
+    fn deleted_keys(plan: &OperationPlan) -> Vec<String> {
+        plan.actions
+            .iter()
+            .map(|action| match action {
+                PlanAction::DeleteReservation(key) => key.to_string(),
+                other => panic!("expected DeleteReservation, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bulk_release_project() {
+        let mut db = create_test_database();
+        create_bulk_reservation(&mut db, "a", 8080, "app", Some("one"));
+        create_bulk_reservation(&mut db, "b", 8081, "app", Some("two"));
+        create_bulk_reservation(&mut db, "c", 8082, "other", None);
+
+        let options = BulkReleaseOptions::new(Some("app".to_string()), None).with_force(true);
+        let plan = BulkReleasePlan::new(options)
+            .build_plan(db.connection())
+            .unwrap();
+        let mut keys = deleted_keys(&plan);
+        keys.sort();
+        assert_eq!(keys, ["/bulk/a", "/bulk/b"]);
+
+        // Narrowing to a task
+        let options = BulkReleaseOptions::new(Some("app".to_string()), Some("two".to_string()))
+            .with_force(true);
+        let plan = BulkReleasePlan::new(options)
+            .build_plan(db.connection())
+            .unwrap();
+        assert_eq!(deleted_keys(&plan), ["/bulk/b"]);
+    }
+
+    #[test]
+    fn test_bulk_release_sticky_requires_force() {
+        let mut db = create_test_database();
+        create_bulk_reservation(&mut db, "a", 8080, "app", None);
+        create_bulk_reservation(&mut db, "b", 8081, "app", None);
+
+        let options = BulkReleaseOptions::new(Some("app".to_string()), None);
+        let result = BulkReleasePlan::new(options.clone()).build_plan(db.connection());
+        assert!(
+            matches!(result, Err(Error::StickyFieldChange { ref details, .. }) if details.contains("2 reservation(s)"))
+        );
+
+        let plan = BulkReleasePlan::new(options.with_force(true))
+            .build_plan(db.connection())
+            .unwrap();
+        assert_eq!(plan.len(), 2);
+    }
+
+    #[test]
+    fn test_bulk_release_skips_pinned() {
+        let mut db = create_test_database();
+        create_bulk_reservation(&mut db, "a", 8080, "app", None);
+        let pinned = Reservation::builder(
+            ReservationKey::new(PathBuf::from("/bulk/pinned"), None).unwrap(),
+            Port::try_from(8081).unwrap(),
+        )
+        .project(Some("app".to_string()))
+        .pinned(true)
+        .build()
+        .unwrap();
+        db.create_reservation(&pinned).unwrap();
+
+        let options = BulkReleaseOptions::new(Some("app".to_string()), None).with_force(true);
+        let plan = BulkReleasePlan::new(options)
+            .build_plan(db.connection())
+            .unwrap();
+        assert_eq!(deleted_keys(&plan), ["/bulk/a"]);
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.warnings[0].contains("/bulk/pinned"));
+    }
+
+    #[test]
+    fn test_bulk_release_no_match_is_empty() {
+        let db = create_test_database();
+        let options = BulkReleaseOptions::new(Some("missing".to_string()), None);
+        let plan = BulkReleasePlan::new(options)
+            .build_plan(db.connection())
+            .unwrap();
+        assert!(plan.is_empty());
+
+        let result =
+            BulkReleasePlan::new(BulkReleaseOptions::default()).build_plan(db.connection());
+        assert!(matches!(result, Err(Error::Validation { .. })));
+    }
 }
@@ -0,0 +1,139 @@
+//! Renew operation planning.
+//!
+//! Renewing marks an existing reservation as freshly used by updating its
+//! `last_used_at` timestamp, which keeps it from being expired. The port and
+//! all other fields are left unchanged.
+
+use crate::database::Database;
+use crate::error::{Error, Result};
+use crate::ReservationKey;
+use rusqlite::Connection;
+
+use super::plan::{OperationPlan, PlanAction};
+
+/// Options for a renew operation.
+#[derive(Debug, Clone)]
+pub struct RenewOptions {
+    /// The reservation key to renew.
+    pub key: ReservationKey,
+}
+
+impl RenewOptions {
+    /// Creates a new `RenewOptions` for the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::RenewOptions;
+    /// use trop::ReservationKey;
+    /// use std::path::PathBuf;
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+    /// let options = RenewOptions::new(key);
+    /// assert_eq!(options.key.path, PathBuf::from("/path"));
+    /// ```
+    #[must_use]
+    pub const fn new(key: ReservationKey) -> Self {
+        Self { key }
+    }
+}
+
+/// A renew plan generator.
+pub struct RenewPlan {
+    options: RenewOptions,
+}
+
+impl RenewPlan {
+    /// Creates a new renew plan with the given options.
+    #[must_use]
+    pub const fn new(options: RenewOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds an operation plan for this renew request.
+    ///
+    /// The plan contains a single action that touches only `last_used_at`.
+    /// It does NOT modify the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NotFound` if no reservation exists for the key, or an
+    /// error if the database query fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use trop::operations::{RenewPlan, RenewOptions};
+    /// use trop::{Database, DatabaseConfig, ReservationKey};
+    /// use std::path::PathBuf;
+    ///
+    /// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+    /// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+    ///
+    /// let plan = RenewPlan::new(RenewOptions::new(key)).build_plan(db.connection()).unwrap();
+    /// ```
+    pub fn build_plan(&self, conn: &Connection) -> Result<OperationPlan> {
+        let key = &self.options.key;
+        if Database::get_reservation(conn, key)?.is_none() {
+            return Err(Error::NotFound {
+                resource: format!("reservation for {key}"),
+            });
+        }
+
+        Ok(OperationPlan::new(format!("Renew reservation for {key}"))
+            .add_action(PlanAction::UpdateLastUsed(key.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_util::create_test_database;
+    use crate::operations::PlanExecutor;
+    use crate::{Port, Reservation};
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_plan_renew_missing_reservation() {
+        let db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/missing"), None).unwrap();
+
+        let result = RenewPlan::new(RenewOptions::new(key)).build_plan(db.connection());
+        assert!(matches!(result, Err(Error::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_renew_updates_only_last_used() {
+        let mut db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/project"), Some("web".into())).unwrap();
+        let old = SystemTime::now() - Duration::from_secs(30 * 24 * 60 * 60);
+        let reservation = Reservation::builder(key.clone(), Port::try_from(5000).unwrap())
+            .project(Some("proj".into()))
+            .task(Some("task".into()))
+            .created_at(old)
+            .last_used_at(old)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+        let before = Database::get_reservation(db.connection(), &key)
+            .unwrap()
+            .unwrap();
+
+        let plan = RenewPlan::new(RenewOptions::new(key.clone()))
+            .build_plan(db.connection())
+            .unwrap();
+        assert_eq!(plan.actions, vec![PlanAction::UpdateLastUsed(key.clone())]);
+        PlanExecutor::new(db.connection()).execute(&plan).unwrap();
+
+        let after = Database::get_reservation(db.connection(), &key)
+            .unwrap()
+            .unwrap();
+        assert!(after.last_used_at() > before.last_used_at());
+        assert_eq!(after.created_at(), before.created_at());
+        assert_eq!(after.port(), before.port());
+        assert_eq!(after.project(), before.project());
+        assert_eq!(after.task(), before.task());
+        assert_eq!(after.sticky(), before.sticky());
+    }
+}
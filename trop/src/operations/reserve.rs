@@ -6,13 +6,124 @@
 use crate::config::Config;
 use crate::database::Database;
 use crate::error::{Error, Result};
-use crate::port::allocator::{allocator_from_config, AllocationOptions, AllocationResult};
-use crate::port::occupancy::OccupancyCheckConfig;
-use crate::{Port, Reservation, ReservationKey};
+use crate::port::allocator::{
+    allocator_from_config, AllocationOptions, AllocationResult, PortAllocator, RangeUsage,
+};
+use crate::port::occupancy::{OccupancyCheckConfig, SystemOccupancyChecker};
+use crate::{Port, PortRange, Reservation, ReservationKey};
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
 
+use super::hook::{HookOutcome, PreReserveHook};
 use super::plan::{OperationPlan, PlanAction};
 
+/// Tag prefix for block reservations whose key has no tag.
+const DEFAULT_BLOCK_TAG_PREFIX: &str = "port";
+
+/// Order in which automatic allocation visits the configured port range.
+///
+/// A requested or preferred port is always tried first; the strategy only
+/// decides the order of the fallback scan. Reserved, excluded, and occupied
+/// ports are skipped whichever order is used.
+///
+/// # Examples
+///
+/// ```
+/// use trop::operations::AllocationStrategy;
+///
+/// let strategy: AllocationStrategy = serde_yaml::from_str("random").unwrap();
+/// assert_eq!(strategy, AllocationStrategy::Random);
+/// assert_eq!(AllocationStrategy::default(), AllocationStrategy::Lowest);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AllocationStrategy {
+    /// Take the lowest available port.
+    #[default]
+    Lowest,
+    /// Take the highest available port.
+    Highest,
+    /// Take an available port chosen at random.
+    Random,
+}
+
+impl AllocationStrategy {
+    /// Returns the ports of `range` in the order this strategy tries them.
+    ///
+    /// `seed` only affects `Random`: the same seed always produces the same
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::AllocationStrategy;
+    /// use trop::{Port, PortRange};
+    ///
+    /// let range = PortRange::new(
+    ///     Port::try_from(5000).unwrap(),
+    ///     Port::try_from(5002).unwrap(),
+    /// ).unwrap();
+    ///
+    /// let highest = AllocationStrategy::Highest.candidates(range, 0);
+    /// assert_eq!(highest[0].value(), 5002);
+    ///
+    /// let random = AllocationStrategy::Random.candidates(range, 42);
+    /// assert_eq!(random, AllocationStrategy::Random.candidates(range, 42));
+    /// ```
+    #[must_use]
+    pub fn candidates(self, range: PortRange, seed: u64) -> Vec<Port> {
+        let mut ports: Vec<Port> = range.iter().collect();
+        match self {
+            Self::Lowest => {}
+            Self::Highest => ports.reverse(),
+            Self::Random => shuffle(&mut ports, seed),
+        }
+        ports
+    }
+}
+
+impl std::fmt::Display for AllocationStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lowest => write!(f, "lowest"),
+            Self::Highest => write!(f, "highest"),
+            Self::Random => write!(f, "random"),
+        }
+    }
+}
+
+/// Shuffles `ports` in place (Fisher-Yates) using a `SplitMix64` sequence
+/// started from `seed`.
+fn shuffle(ports: &mut [Port], seed: u64) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+
+    for i in (1..ports.len()).rev() {
+        // The remainder is at most `i`, so it always fits in usize
+        #[allow(clippy::cast_possible_truncation)]
+        let j = (next() % (i as u64 + 1)) as usize;
+        ports.swap(i, j);
+    }
+}
+
+/// Seed for random allocation when none is provided.
+fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    // Keep the low 64 bits; mix in the pid so concurrent processes differ
+    #[allow(clippy::cast_possible_truncation)]
+    let nanos = nanos as u64;
+    nanos ^ u64::from(std::process::id()).rotate_left(32)
+}
+
 /// Options for a reserve operation.
 ///
 /// This struct contains all the parameters needed to plan a reserve operation,
@@ -58,6 +169,32 @@ pub struct ReserveOptions {
 
     /// Disable automatic expiration during allocation.
     pub disable_autoexpire: bool,
+
+    /// Allocation strategy; `None` uses the configured `allocation_strategy`.
+    pub strategy: Option<AllocationStrategy>,
+
+    /// Seed for the `random` strategy; `None` seeds from the clock.
+    pub allocation_seed: Option<u64>,
+
+    /// Time to live; `expire` removes the reservation once it has passed.
+    pub ttl: Option<Duration>,
+
+    /// Free-text note; `Some("")` clears an existing note.
+    pub note: Option<String>,
+
+    /// Cleanup priority; `None` keeps an existing reservation's priority
+    /// (0 for new ones).
+    pub priority: Option<i32>,
+
+    /// Replace an existing reservation's project and task with the given
+    /// ones (clearing those not given) instead of refusing the change. The
+    /// port is always kept.
+    pub replace: bool,
+
+    /// Number of consecutive ports to reserve. Above 1, one reservation is
+    /// made per port, tagged `<tag>-0`, `<tag>-1`, and so on, using the key's
+    /// tag as the prefix (`port` when untagged).
+    pub count: usize,
 }
 
 impl ReserveOptions {
@@ -75,6 +212,13 @@ impl ReserveOptions {
     /// - `allow_task_change`: false
     /// - `disable_autoprune`: false
     /// - `disable_autoexpire`: false
+    /// - strategy: None (use the configured strategy)
+    /// - `allocation_seed`: None
+    /// - ttl: None (no per-reservation expiry)
+    /// - note: None (keep any existing note)
+    /// - priority: None (keep any existing priority)
+    /// - replace: false
+    /// - count: 1 (a single port)
     ///
     /// # Examples
     ///
@@ -104,6 +248,13 @@ impl ReserveOptions {
             allow_task_change: false,
             disable_autoprune: false,
             disable_autoexpire: false,
+            strategy: None,
+            allocation_seed: None,
+            ttl: None,
+            note: None,
+            priority: None,
+            replace: false,
+            count: 1,
         }
     }
 
@@ -184,6 +335,85 @@ impl ReserveOptions {
         self
     }
 
+    /// Sets the allocation strategy, overriding the configured one.
+    #[must_use]
+    pub const fn with_strategy(mut self, strategy: Option<AllocationStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the seed for the `random` allocation strategy.
+    #[must_use]
+    pub const fn with_allocation_seed(mut self, seed: Option<u64>) -> Self {
+        self.allocation_seed = seed;
+        self
+    }
+
+    /// Sets the time to live.
+    ///
+    /// Reserving an existing reservation with a TTL restarts its clock.
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the note.
+    ///
+    /// Reserving an existing reservation with a note replaces its note
+    /// without changing its port.
+    #[must_use]
+    pub fn with_note(mut self, note: Option<String>) -> Self {
+        self.note = note;
+        self
+    }
+
+    /// Sets the cleanup priority.
+    ///
+    /// Reservations above `cleanup.protect_priority` are skipped by
+    /// `expire` and `autoclean` unless forced.
+    #[must_use]
+    pub const fn with_priority(mut self, priority: Option<i32>) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the `replace` flag.
+    ///
+    /// Without it, reserving an existing key is idempotent: the same port is
+    /// returned and project or task changes are refused unless allowed. With
+    /// it, the existing reservation keeps its port but takes exactly the
+    /// given project and task, so one that is not given is cleared.
+    #[must_use]
+    pub const fn with_replace(mut self, replace: bool) -> Self {
+        self.replace = replace;
+        self
+    }
+
+    /// Sets the number of consecutive ports to reserve.
+    ///
+    /// See [`ReserveOptions::count`] for how the block's tags are derived.
+    #[must_use]
+    pub const fn with_count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Returns the keys for a block reservation, one per port.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a derived tag is not a valid reservation tag.
+    pub fn block_keys(&self) -> Result<Vec<ReservationKey>> {
+        let prefix = self.key.tag.as_deref().unwrap_or(DEFAULT_BLOCK_TAG_PREFIX);
+        (0..self.count)
+            .map(|i| {
+                ReservationKey::new(self.key.path.clone(), Some(format!("{prefix}-{i}")))
+                    .map_err(Error::from)
+            })
+            .collect()
+    }
+
     /// Infers project and task from git context if not explicitly provided.
     ///
     /// This method uses git repository information to automatically set
@@ -258,11 +488,7 @@ impl<'a> ReservePlan<'a> {
 
     /// Gets the occupancy check configuration from the overall config.
     fn occupancy_config(&self) -> OccupancyCheckConfig {
-        if let Some(ref occ_config) = self.config.occupancy_check {
-            OccupancyCheckConfig::from(occ_config)
-        } else {
-            OccupancyCheckConfig::default()
-        }
+        OccupancyCheckConfig::from_config(self.config)
     }
 
     /// Builds an operation plan for this reserve request.
@@ -304,68 +530,293 @@ impl<'a> ReservePlan<'a> {
             Database::validate_path_relationship(&self.options.key.path, false)?;
         }
 
+        if self.options.count > 1 {
+            return self.build_block_plan(conn);
+        }
+
         // Step 2: Check for existing reservation
         if let Some(existing) = Database::get_reservation(conn, &self.options.key)? {
-            // Reservation exists - validate sticky fields and return idempotent result
-            self.validate_sticky_fields(&existing)?;
+            // Reservation exists - keep its port, refusing sticky field
+            // changes unless they are being replaced
+            if !self.options.replace {
+                self.validate_sticky_fields(&existing)?;
+            }
+            return Ok(plan.add_action(self.renew_action(&existing)?));
+        }
+
+        // Step 3: Determine port (unified allocation with fallback)
+        let mut warnings = Vec::new();
+        let port = self.allocate_port(conn, &mut warnings)?;
+        for warning in warnings {
+            plan = plan.add_warning(warning);
+        }
+
+        // Step 4: Create the new reservation
+        let reservation = Reservation::builder(self.options.key.clone(), port)
+            .project(self.options.project.clone())
+            .task(self.options.task.clone())
+            .expires_at(self.ttl_deadline()?)
+            .note(self.options.note.clone())
+            .priority(self.options.priority.unwrap_or_default())
+            .build()?;
+
+        plan = plan.add_action(PlanAction::CreateReservation(reservation));
+
+        Ok(plan)
+    }
+
+    /// Builds the plan for a block of `count` consecutive ports.
+    ///
+    /// Reserving a block that already exists in full renews each of its
+    /// reservations. A partially existing block is an error, since its
+    /// ports can no longer be guaranteed to be consecutive.
+    fn build_block_plan(&self, conn: &Connection) -> Result<OperationPlan> {
+        let count = self.options.count;
+        let mut plan = OperationPlan::new(format!(
+            "Reserve block of {count} ports for {}",
+            self.options.key.path.display()
+        ));
+
+        let keys = self.options.block_keys()?;
+        let mut existing = Vec::new();
+        for key in &keys {
+            if let Some(reservation) = Database::get_reservation(conn, key)? {
+                existing.push(reservation);
+            }
+        }
 
-            // Idempotent case: reservation exists with compatible metadata
-            // Just update the timestamp
-            plan = plan.add_action(PlanAction::UpdateLastUsed(self.options.key.clone()));
+        if existing.len() == count {
+            for reservation in &existing {
+                if !self.options.replace {
+                    self.validate_sticky_fields(reservation)?;
+                }
+                plan = plan.add_action(self.renew_action(reservation)?);
+            }
             return Ok(plan);
         }
+        if !existing.is_empty() {
+            let tags: Vec<String> = existing.iter().map(|r| r.key().to_string()).collect();
+            return Err(Error::Validation {
+                field: "count".to_string(),
+                message: format!(
+                    "Only part of the block is reserved ({}); release it before reserving {count} ports",
+                    tags.join(", ")
+                ),
+            });
+        }
 
-        // Step 3: Determine port (unified allocation with fallback)
-        let port = {
-            let allocator = allocator_from_config(self.config)?;
+        let mut vetoes = Vec::new();
+        let base = self.allocate_block(conn, &keys, &mut vetoes)?;
+        for veto in vetoes {
+            plan = plan.add_warning(veto);
+        }
+
+        let expires_at = self.ttl_deadline()?;
+        for (key, port) in keys.into_iter().zip(base.value()..) {
+            let reservation = Reservation::builder(key, Port::try_from(port)?)
+                .project(self.options.project.clone())
+                .task(self.options.task.clone())
+                .expires_at(expires_at)
+                .note(self.options.note.clone())
+                .priority(self.options.priority.unwrap_or_default())
+                .build()?;
+            plan = plan.add_action(PlanAction::CreateReservation(reservation));
+        }
+
+        Ok(plan)
+    }
+
+    /// Returns the action that refreshes an existing, compatible reservation.
+    ///
+    /// A TTL restarts the expiry clock, a note or priority replaces the old
+    /// one, and with `replace` so do the project and task; otherwise only
+    /// the timestamp is updated.
+    fn renew_action(&self, existing: &Reservation) -> Result<PlanAction> {
+        let expires_at = self.ttl_deadline()?;
+        let note_changed = self
+            .options
+            .note
+            .as_deref()
+            .is_some_and(|note| existing.note().unwrap_or_default() != note);
+        let priority = self.options.priority.unwrap_or(existing.priority());
+        let (project, task) = if self.options.replace {
+            (
+                self.options.project.as_deref(),
+                self.options.task.as_deref(),
+            )
+        } else {
+            (existing.project(), existing.task())
+        };
+        let metadata_changed = project != existing.project() || task != existing.task();
+        let priority_changed = priority != existing.priority();
+        if !(expires_at.is_some() || note_changed || priority_changed || metadata_changed) {
+            return Ok(PlanAction::UpdateLastUsed(existing.key().clone()));
+        }
+
+        let renewed = Reservation::builder(existing.key().clone(), existing.port())
+            .project(project.map(String::from))
+            .task(task.map(String::from))
+            .sticky(existing.sticky())
+            .pinned(existing.pinned())
+            .created_at(existing.created_at())
+            .last_used_at(SystemTime::now())
+            .expires_at(expires_at.or(existing.expires_at()))
+            .note(
+                self.options
+                    .note
+                    .clone()
+                    .or_else(|| existing.note().map(String::from)),
+            )
+            .priority(priority)
+            .build()?;
+        Ok(PlanAction::UpdateReservation {
+            from: existing.clone(),
+            to: renewed,
+        })
+    }
+
+    /// Finds the lowest run of `keys.len()` consecutive available ports.
+    ///
+    /// Each port must pass the same checks as single-port allocation,
+    /// including the pre-reserve hook (asked about the key that would get
+    /// the port). Returns the first port of the run.
+    fn allocate_block(
+        &self,
+        conn: &Connection,
+        keys: &[ReservationKey],
+        vetoes: &mut Vec<String>,
+    ) -> Result<Port> {
+        let allocator = allocator_from_config(self.config)?;
+        let occupancy_config = self.occupancy_config();
+        let hook = PreReserveHook::from_config(self.config);
+        let range = *allocator.range();
+
+        let mut usage = RangeUsage::default();
+        let mut run_start: Option<Port> = None;
+        let mut run_len = 0;
+        for port in range {
+            let available = allocator
+                .find_first_available_with_usage([port], conn, &occupancy_config, &mut usage)?
+                .is_some()
+                && approve_port(hook.as_ref(), port, &keys[run_len], vetoes)?;
+            if !available {
+                run_start = None;
+                run_len = 0;
+                continue;
+            }
+
+            let start = *run_start.get_or_insert(port);
+            run_len += 1;
+            if run_len == keys.len() {
+                return Ok(start);
+            }
+        }
+
+        Err(Error::NoContiguousBlock {
+            count: keys.len(),
+            range,
+        })
+    }
+
+    /// Allocates a port for a new reservation.
+    ///
+    /// Tries the requested or preferred port first, then scans the
+    /// configured range in the order given by the allocation strategy
+    /// (lowest first by default). When a pre-reserve hook is configured,
+    /// each candidate must also be approved by it; vetoed candidates are
+    /// skipped and described in `warnings`. Falling back from a preferred
+    /// port is noted there as well.
+    ///
+    /// If nothing can be allocated, the exhaustion error reports how many
+    /// ports in the range were reserved, excluded, occupied, or vetoed.
+    fn allocate_port(&self, conn: &Connection, warnings: &mut Vec<String>) -> Result<Port> {
+        let allocator = allocator_from_config(self.config)?;
+        let occupancy_config = self.occupancy_config();
+        let hook = PreReserveHook::from_config(self.config);
+
+        let mut vetoes = Vec::new();
+        let mut approve =
+            |port: Port| approve_port(hook.as_ref(), port, &self.options.key, &mut vetoes);
+
+        // Why a requested or preferred port was passed over, for the error
+        // if the fallback scan finds nothing either
+        let mut requested = None;
+        let preferred = self.options.port.or(self.options.preferred_port);
+        if preferred.is_some() {
             let allocation_options = AllocationOptions {
-                preferred: self.options.port.or(self.options.preferred_port),
+                preferred,
                 ignore_occupied: self.options.ignore_occupied,
                 ignore_exclusions: self.options.ignore_exclusions,
             };
-            let occupancy_config = self.occupancy_config();
 
             match allocator.allocate_single(conn, &allocation_options, &occupancy_config)? {
-                AllocationResult::Allocated(port) => port,
-
-                AllocationResult::PreferredUnavailable { .. } => {
-                    // Preferred port unavailable - fall back to scanning
-                    let fallback_options = AllocationOptions {
-                        preferred: None,
-                        ignore_occupied: self.options.ignore_occupied,
-                        ignore_exclusions: self.options.ignore_exclusions,
-                    };
-
-                    match allocator.allocate_single(conn, &fallback_options, &occupancy_config)? {
-                        AllocationResult::Allocated(port) => port,
-                        AllocationResult::Exhausted { .. } => {
-                            return Err(Error::PortExhausted {
-                                range: *allocator.range(),
-                                tried_cleanup: false,
-                            });
-                        }
-                        AllocationResult::PreferredUnavailable { .. } => unreachable!(),
+                AllocationResult::Allocated(port) => {
+                    if approve(port)? {
+                        return Ok(port);
                     }
                 }
-
-                AllocationResult::Exhausted { .. } => {
-                    return Err(Error::PortExhausted {
-                        range: *allocator.range(),
-                        tried_cleanup: false,
-                    });
+                AllocationResult::PreferredUnavailable { port, reason } => {
+                    requested = Some((port, reason));
                 }
+                AllocationResult::Exhausted { .. } => {}
             }
-        };
+            // Preferred port unavailable or vetoed - fall back to scanning
+        }
 
-        // Step 4: Create the new reservation
-        let reservation = Reservation::builder(self.options.key.clone(), port)
-            .project(self.options.project.clone())
-            .task(self.options.task.clone())
-            .build()?;
+        let strategy = self
+            .options
+            .strategy
+            .or(self.config.allocation_strategy)
+            .unwrap_or_default();
+        let seed = self.options.allocation_seed.unwrap_or_else(random_seed);
+        let candidates = strategy.candidates(*allocator.range(), seed);
+
+        let mut usage = RangeUsage::default();
+        let allocated = first_approved_port(
+            &allocator,
+            candidates,
+            conn,
+            &occupancy_config,
+            &mut usage,
+            &mut approve,
+        )?;
+        warnings.append(&mut vetoes);
+
+        if let Some(port) = allocated {
+            if self.options.port.is_none() {
+                if let Some(preferred) = self.options.preferred_port {
+                    let why = requested.map(|(_, reason)| format!(" ({reason})"));
+                    warnings.push(format!(
+                        "preferred {preferred} unavailable{}, allocated {port}",
+                        why.unwrap_or_default()
+                    ));
+                }
+            }
+            return Ok(port);
+        }
 
-        plan = plan.add_action(PlanAction::CreateReservation(reservation));
+        Err(Error::PortExhausted {
+            range: *allocator.range(),
+            tried_cleanup: false,
+            usage: Some(usage),
+            requested,
+            requested_is_preferred: self.options.port.is_none(),
+        })
+    }
 
-        Ok(plan)
+    /// Returns when a reservation made now would expire, if a TTL is set.
+    fn ttl_deadline(&self) -> Result<Option<SystemTime>> {
+        self.options
+            .ttl
+            .map(|ttl| {
+                SystemTime::now()
+                    .checked_add(ttl)
+                    .ok_or_else(|| Error::Validation {
+                        field: "ttl".into(),
+                        message: format!("TTL of {}s is too large", ttl.as_secs()),
+                    })
+            })
+            .transpose()
     }
 
     /// Validates that sticky fields aren't being changed without permission.
@@ -418,6 +869,108 @@ impl<'a> ReservePlan<'a> {
     }
 }
 
+/// Finds the lowest port a new reservation could take, without reserving it.
+///
+/// Runs the same search as automatic allocation over the configured range,
+/// lowest first, skipping reserved and excluded ports. Occupied ports are
+/// skipped only when `check_occupancy` is set (using the configured
+/// occupancy checks); the pre-reserve hook is never run.
+///
+/// Returns `None` if every port in the range is taken.
+///
+/// # Errors
+///
+/// Returns an error if the configured port range is invalid, or if a
+/// database query or occupancy check fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use trop::config::ConfigBuilder;
+/// use trop::operations::first_free_port;
+/// use trop::{Database, DatabaseConfig};
+///
+/// let config = ConfigBuilder::new().build().unwrap();
+/// let db = Database::open(DatabaseConfig::new("/tmp/trop.db")).unwrap();
+/// if let Some(port) = first_free_port(db.connection(), &config, false).unwrap() {
+///     println!("{port}");
+/// }
+/// ```
+pub fn first_free_port(
+    conn: &Connection,
+    config: &Config,
+    check_occupancy: bool,
+) -> Result<Option<Port>> {
+    let allocator = allocator_from_config(config)?;
+    let occupancy_config = if check_occupancy {
+        OccupancyCheckConfig::from_config(config)
+    } else {
+        OccupancyCheckConfig {
+            skip_tcp: true,
+            skip_udp: true,
+            ..OccupancyCheckConfig::default()
+        }
+    };
+
+    let candidates = AllocationStrategy::Lowest.candidates(*allocator.range(), 0);
+    first_approved_port(
+        &allocator,
+        candidates,
+        conn,
+        &occupancy_config,
+        &mut RangeUsage::default(),
+        |_| Ok(true),
+    )
+}
+
+/// Finds the first of `candidates` that is free to reserve and that
+/// `approve` accepts, tallying every skipped port into `usage`.
+///
+/// Nothing is written to the database.
+fn first_approved_port(
+    allocator: &PortAllocator<SystemOccupancyChecker>,
+    candidates: impl IntoIterator<Item = Port>,
+    conn: &Connection,
+    occupancy_config: &OccupancyCheckConfig,
+    usage: &mut RangeUsage,
+    mut approve: impl FnMut(Port) -> Result<bool>,
+) -> Result<Option<Port>> {
+    let mut candidates = candidates.into_iter();
+    while let Some(port) = allocator.find_first_available_with_usage(
+        candidates.by_ref(),
+        conn,
+        occupancy_config,
+        usage,
+    )? {
+        if approve(port)? {
+            return Ok(Some(port));
+        }
+        usage.vetoed += 1;
+    }
+    Ok(None)
+}
+
+/// Asks the pre-reserve hook, if any, whether `port` may go to `key`.
+///
+/// A veto is recorded in `vetoes` and reported as `Ok(false)`.
+fn approve_port(
+    hook: Option<&PreReserveHook>,
+    port: Port,
+    key: &ReservationKey,
+    vetoes: &mut Vec<String>,
+) -> Result<bool> {
+    let Some(hook) = hook else {
+        return Ok(true);
+    };
+    match hook.evaluate(port, key)? {
+        HookOutcome::Approved => Ok(true),
+        HookOutcome::Vetoed { reason } => {
+            vetoes.push(format!("Pre-reserve hook vetoed port {port}: {reason}"));
+            Ok(false)
+        }
+    }
+}
+
 /// Generic helper to check if a sticky field can be changed.
 ///
 /// This function encapsulates the common logic for validating sticky field changes:
@@ -458,6 +1011,7 @@ mod tests {
     use super::*;
     use crate::config::{Config, PortConfig};
     use crate::database::test_util::create_test_database;
+    use crate::error::PortUnavailableReason;
     use std::path::PathBuf;
 
     // Helper to create a test config with reasonable defaults
@@ -855,121 +1409,346 @@ mod tests {
     }
 
     #[test]
-    fn test_plan_sticky_field_project_change_denied() {
+    fn test_plan_replace_updates_metadata_keeps_port() {
         let mut db = create_test_database();
         let config = create_test_config();
         let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
         let port = Port::try_from(8080).unwrap();
 
-        // Create initial reservation with project
         let reservation = Reservation::builder(key.clone(), port)
-            .project(Some("project1".to_string()))
+            .project(Some("old".to_string()))
+            .task(Some("t1".to_string()))
             .build()
             .unwrap();
         db.create_reservation(&reservation).unwrap();
 
-        // Try to change project without permission
-        let options = ReserveOptions::new(key, Some(port))
-            .with_project(Some("project2".to_string()))
-            .with_allow_unrelated_path(true);
-
-        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+        // Without --replace the project change is refused
+        let options = ReserveOptions::new(key.clone(), None)
+            .with_allow_unrelated_path(true)
+            .with_project(Some("new".to_string()));
+        let result = ReservePlan::new(options.clone(), &config).build_plan(db.connection());
+        assert!(matches!(result, Err(Error::StickyFieldChange { .. })));
 
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            Error::StickyFieldChange { .. }
-        ));
+        let plan = ReservePlan::new(options.with_replace(true), &config)
+            .build_plan(db.connection())
+            .unwrap();
+        let PlanAction::UpdateReservation { to, .. } = &plan.actions[0] else {
+            panic!("expected an update, got {:?}", plan.actions[0]);
+        };
+        assert_eq!(to.port(), port);
+        assert_eq!(to.project(), Some("new"));
+        // Not given, so cleared rather than merged from the old reservation
+        assert_eq!(to.task(), None);
     }
 
     #[test]
-    fn test_plan_sticky_field_project_change_with_force() {
+    fn test_plan_replace_without_project_clears_project() {
         let mut db = create_test_database();
         let config = create_test_config();
         let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
         let port = Port::try_from(8080).unwrap();
 
-        // Create initial reservation with project
         let reservation = Reservation::builder(key.clone(), port)
-            .project(Some("project1".to_string()))
+            .project(Some("old".to_string()))
+            .task(Some("t1".to_string()))
             .build()
             .unwrap();
         db.create_reservation(&reservation).unwrap();
 
-        // Change project with force flag
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_task(Some("t2".to_string()))
+            .with_replace(true);
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+        let PlanAction::UpdateReservation { to, .. } = &plan.actions[0] else {
+            panic!("expected an update, got {:?}", plan.actions[0]);
+        };
+        assert_eq!(to.port(), port);
+        assert_eq!(to.project(), None);
+        assert_eq!(to.task(), Some("t2"));
+    }
+
+    #[test]
+    fn test_plan_with_ttl_sets_expiry() {
+        let db = create_test_database();
+        let config = create_test_config();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
         let options = ReserveOptions::new(key, Some(port))
-            .with_project(Some("project2".to_string()))
-            .with_force(true)
-            .with_allow_unrelated_path(true);
+            .with_allow_unrelated_path(true)
+            .with_ttl(Some(Duration::from_secs(1800)));
 
-        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
 
-        // Should succeed with force
-        assert!(result.is_ok());
+        let PlanAction::CreateReservation(reservation) = &plan.actions[0] else {
+            panic!("expected CreateReservation, got {:?}", plan.actions[0]);
+        };
+        let expires_at = reservation.expires_at().unwrap();
+        assert!(expires_at > SystemTime::now() + Duration::from_secs(1700));
+        assert!(!reservation.is_past_ttl(SystemTime::now()));
     }
 
     #[test]
-    fn test_plan_sticky_field_project_change_with_allow_flag() {
+    fn test_plan_existing_reservation_with_ttl_restarts_clock() {
         let mut db = create_test_database();
         let config = create_test_config();
         let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
         let port = Port::try_from(8080).unwrap();
 
-        // Create initial reservation with project
         let reservation = Reservation::builder(key.clone(), port)
             .project(Some("project1".to_string()))
+            .expires_at(Some(SystemTime::now()))
             .build()
             .unwrap();
         db.create_reservation(&reservation).unwrap();
 
-        // Change project with specific allow flag
         let options = ReserveOptions::new(key, Some(port))
-            .with_project(Some("project2".to_string()))
-            .with_allow_project_change(true)
-            .with_allow_unrelated_path(true);
+            .with_project(Some("project1".to_string()))
+            .with_allow_unrelated_path(true)
+            .with_ttl(Some(Duration::from_secs(3600)));
 
-        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
 
-        // Should succeed with allow flag
-        assert!(result.is_ok());
+        let PlanAction::UpdateReservation { to: renewed, .. } = &plan.actions[0] else {
+            panic!("expected UpdateReservation, got {:?}", plan.actions[0]);
+        };
+        assert_eq!(renewed.port(), port);
+        assert_eq!(renewed.project(), Some("project1"));
+        assert!(renewed.expires_at().unwrap() > reservation.expires_at().unwrap());
     }
 
     #[test]
-    fn test_plan_sticky_field_task_change_denied() {
+    fn test_plan_existing_reservation_note_update_keeps_port() {
         let mut db = create_test_database();
         let config = create_test_config();
         let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
         let port = Port::try_from(8080).unwrap();
-
-        // Create initial reservation with task
         let reservation = Reservation::builder(key.clone(), port)
-            .task(Some("task1".to_string()))
+            .expires_at(Some(SystemTime::now() + Duration::from_secs(3600)))
+            .note(Some("old note".to_string()))
             .build()
             .unwrap();
         db.create_reservation(&reservation).unwrap();
 
-        // Try to change task without permission
-        let options = ReserveOptions::new(key, Some(port))
-            .with_task(Some("task2".to_string()))
-            .with_allow_unrelated_path(true);
+        // Re-reserving with the same note only touches the timestamp
+        let options = ReserveOptions::new(key.clone(), None)
+            .with_allow_unrelated_path(true)
+            .with_note(Some("old note".to_string()));
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+        assert!(matches!(plan.actions[0], PlanAction::UpdateLastUsed(_)));
 
-        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+        // A different note replaces the old one, keeping the port and TTL
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_note(Some("new note ✓".to_string()));
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
 
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            Error::StickyFieldChange { .. }
-        ));
+        let PlanAction::UpdateReservation { to: updated, .. } = &plan.actions[0] else {
+            panic!("expected UpdateReservation, got {:?}", plan.actions[0]);
+        };
+        assert_eq!(updated.port(), port);
+        assert_eq!(updated.note(), Some("new note ✓"));
+        let stored = Database::get_reservation(db.connection(), updated.key())
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.expires_at(), stored.expires_at());
     }
 
     #[test]
-    fn test_plan_automatic_allocation() {
+    fn test_plan_new_reservation_carries_resolved_fields() {
         let db = create_test_database();
         let config = create_test_config();
-        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
-
-        // No port specified - use automatic allocation
-        let options = ReserveOptions::new(key.clone(), None).with_allow_unrelated_path(true);
+        let key =
+            ReservationKey::new(PathBuf::from("/test/path"), Some("web".to_string())).unwrap();
+
+        let options = ReserveOptions::new(key.clone(), None)
+            .with_project(Some("app".to_string()))
+            .with_task(Some("build".to_string()))
+            .with_allow_unrelated_path(true);
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        let PlanAction::CreateReservation(created) = &plan.actions[0] else {
+            panic!("expected CreateReservation, got {:?}", plan.actions[0]);
+        };
+        assert_eq!(created.key(), &key);
+        assert_eq!(created.port().value(), 5000);
+        assert_eq!(created.project(), Some("app"));
+        assert_eq!(created.task(), Some("build"));
+        assert!(!created.sticky());
+
+        let diff = plan.render_diff();
+        assert!(diff.contains(&format!("+ {key}")), "{diff}");
+        assert!(diff.contains("+   port: 5000"), "{diff}");
+        assert!(diff.contains("+   project: app"), "{diff}");
+        assert!(diff.contains("+   task: build"), "{diff}");
+        assert!(diff.contains("+   sticky: false"), "{diff}");
+    }
+
+    #[test]
+    fn test_plan_update_carries_old_and_new_values() {
+        let mut db = create_test_database();
+        let config = create_test_config();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+        let reservation = Reservation::builder(key.clone(), port)
+            .project(Some("app".to_string()))
+            .note(Some("old note".to_string()))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let options = ReserveOptions::new(key, None)
+            .with_project(Some("app".to_string()))
+            .with_allow_unrelated_path(true)
+            .with_note(Some("new note".to_string()));
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        let PlanAction::UpdateReservation { from, to } = &plan.actions[0] else {
+            panic!("expected UpdateReservation, got {:?}", plan.actions[0]);
+        };
+        assert_eq!(from.note(), Some("old note"));
+        assert_eq!(to.note(), Some("new note"));
+        assert_eq!((from.port(), to.port()), (port, port));
+        assert_eq!(to.project(), Some("app"));
+
+        // Only the changed field shows up in the diff
+        let diff = plan.render_diff();
+        assert!(diff.contains("~   note: old note -> new note"), "{diff}");
+        assert!(!diff.contains("port:"), "{diff}");
+        assert!(!diff.contains("project:"), "{diff}");
+    }
+
+    #[test]
+    fn test_plan_sticky_field_project_change_denied() {
+        let mut db = create_test_database();
+        let config = create_test_config();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        // Create initial reservation with project
+        let reservation = Reservation::builder(key.clone(), port)
+            .project(Some("project1".to_string()))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        // Try to change project without permission
+        let options = ReserveOptions::new(key, Some(port))
+            .with_project(Some("project2".to_string()))
+            .with_allow_unrelated_path(true);
+
+        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            Error::StickyFieldChange { .. }
+        ));
+    }
+
+    #[test]
+    fn test_plan_sticky_field_project_change_with_force() {
+        let mut db = create_test_database();
+        let config = create_test_config();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        // Create initial reservation with project
+        let reservation = Reservation::builder(key.clone(), port)
+            .project(Some("project1".to_string()))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        // Change project with force flag
+        let options = ReserveOptions::new(key, Some(port))
+            .with_project(Some("project2".to_string()))
+            .with_force(true)
+            .with_allow_unrelated_path(true);
+
+        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+
+        // Should succeed with force
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_plan_sticky_field_project_change_with_allow_flag() {
+        let mut db = create_test_database();
+        let config = create_test_config();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        // Create initial reservation with project
+        let reservation = Reservation::builder(key.clone(), port)
+            .project(Some("project1".to_string()))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        // Change project with specific allow flag
+        let options = ReserveOptions::new(key, Some(port))
+            .with_project(Some("project2".to_string()))
+            .with_allow_project_change(true)
+            .with_allow_unrelated_path(true);
+
+        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+
+        // Should succeed with allow flag
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_plan_sticky_field_task_change_denied() {
+        let mut db = create_test_database();
+        let config = create_test_config();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        // Create initial reservation with task
+        let reservation = Reservation::builder(key.clone(), port)
+            .task(Some("task1".to_string()))
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        // Try to change task without permission
+        let options = ReserveOptions::new(key, Some(port))
+            .with_task(Some("task2".to_string()))
+            .with_allow_unrelated_path(true);
+
+        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            Error::StickyFieldChange { .. }
+        ));
+    }
+
+    #[test]
+    fn test_plan_automatic_allocation() {
+        let db = create_test_database();
+        let config = create_test_config();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+
+        // No port specified - use automatic allocation
+        let options = ReserveOptions::new(key.clone(), None).with_allow_unrelated_path(true);
 
         let plan = ReservePlan::new(options, &config)
             .build_plan(db.connection())
@@ -1025,6 +1804,312 @@ mod tests {
         assert!(matches!(result.unwrap_err(), Error::PortExhausted { .. }));
     }
 
+    fn block_test_config(excluded: Vec<crate::config::PortExclusion>) -> Config {
+        Config {
+            ports: Some(PortConfig {
+                min: 5000,
+                max: Some(5009),
+                max_offset: None,
+            }),
+            excluded_ports: Some(excluded),
+            occupancy_check: Some(crate::config::OccupancyConfig {
+                skip: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn planned_ports(plan: &OperationPlan) -> Vec<(Option<String>, u16)> {
+        plan.actions
+            .iter()
+            .map(|action| match action {
+                PlanAction::CreateReservation(r) => (r.key().tag.clone(), r.port().value()),
+                other => panic!("Expected CreateReservation, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_first_free_port_skips_reserved_and_excluded() {
+        let mut db = create_test_database();
+        let config = block_test_config(vec![crate::config::PortExclusion::Single(5001)]);
+
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let reservation = Reservation::builder(key, Port::try_from(5000).unwrap())
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let port = first_free_port(db.connection(), &config, false).unwrap();
+        assert_eq!(port.map(Port::value), Some(5002));
+
+        // Nothing is reserved by the lookup
+        assert!(
+            Database::get_reservation_by_port(db.connection(), Port::try_from(5002).unwrap())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_plan_block_allocates_consecutive_ports() {
+        let mut db = create_test_database();
+        let config = block_test_config(vec![]);
+        let held = ReservationKey::new(PathBuf::from("/test/held"), None).unwrap();
+        db.create_reservation(
+            &Reservation::builder(held, Port::try_from(5001).unwrap())
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let key = ReservationKey::new(PathBuf::from("/test/cluster"), Some("node".into())).unwrap();
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_count(3);
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        // 5000 fits only one port before the held 5001
+        assert_eq!(
+            planned_ports(&plan),
+            vec![
+                (Some("node-0".into()), 5002),
+                (Some("node-1".into()), 5003),
+                (Some("node-2".into()), 5004),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_block_skips_exclusions_inside_block() {
+        let db = create_test_database();
+        let config = block_test_config(vec![crate::config::PortExclusion::Single(5002)]);
+
+        let key = ReservationKey::new(PathBuf::from("/test/cluster"), None).unwrap();
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_count(3);
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        assert_eq!(
+            planned_ports(&plan),
+            vec![
+                (Some("port-0".into()), 5003),
+                (Some("port-1".into()), 5004),
+                (Some("port-2".into()), 5005),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_block_fragmented_range_fails() {
+        let db = create_test_database();
+        // Free runs are at most two ports long: 5000-5001, 5003-5004, 5006-5007, 5009
+        let config = block_test_config(vec![
+            crate::config::PortExclusion::Single(5002),
+            crate::config::PortExclusion::Single(5005),
+            crate::config::PortExclusion::Single(5008),
+        ]);
+
+        let key = ReservationKey::new(PathBuf::from("/test/cluster"), None).unwrap();
+        let options = ReserveOptions::new(key.clone(), None)
+            .with_allow_unrelated_path(true)
+            .with_count(3);
+        let err = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::NoContiguousBlock { count: 3, .. }),
+            "{err:?}"
+        );
+
+        // A single port still fits
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+        assert!(ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_plan_block_existing_block_is_idempotent() {
+        let mut db = create_test_database();
+        let config = block_test_config(vec![]);
+        let key = ReservationKey::new(PathBuf::from("/test/cluster"), None).unwrap();
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_count(2);
+
+        let plan = ReservePlan::new(options.clone(), &config)
+            .build_plan(db.connection())
+            .unwrap();
+        crate::operations::PlanExecutor::new(db.connection())
+            .execute(&plan)
+            .unwrap();
+
+        let plan = ReservePlan::new(options.clone(), &config)
+            .build_plan(db.connection())
+            .unwrap();
+        assert!(plan
+            .actions
+            .iter()
+            .all(|action| matches!(action, PlanAction::UpdateLastUsed(_))));
+        assert_eq!(plan.len(), 2);
+
+        // Releasing part of the block makes it ambiguous
+        let keys = options.block_keys().unwrap();
+        db.delete_reservation(&keys[1]).unwrap();
+        let err = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }), "{err:?}");
+    }
+
+    #[test]
+    fn test_plan_exhaustion_reports_range_usage() {
+        let mut db = create_test_database();
+        let config = Config {
+            ports: Some(PortConfig {
+                min: 5000,
+                max: Some(5002),
+                max_offset: None,
+            }),
+            excluded_ports: Some(vec![crate::config::PortExclusion::Single(5002)]),
+            occupancy_check: Some(crate::config::OccupancyConfig {
+                skip: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        for (i, port) in [5000, 5001].into_iter().enumerate() {
+            let key = ReservationKey::new(PathBuf::from(format!("/test/held{i}")), None).unwrap();
+            let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+                .build()
+                .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+
+        let err = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("(2 reserved, 1 excluded, 0 occupied)"));
+        match err {
+            Error::PortExhausted { usage, .. } => assert_eq!(
+                usage,
+                Some(RangeUsage {
+                    reserved: 2,
+                    excluded: 1,
+                    occupied: 0,
+                    vetoed: 0,
+                })
+            ),
+            other => panic!("Expected PortExhausted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_plan_skips_occupied_ports() {
+        let db = create_test_database();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let config = Config {
+            ports: Some(PortConfig {
+                min: port,
+                max: Some(port),
+                max_offset: None,
+            }),
+            occupancy_check: Some(crate::config::OccupancyConfig {
+                skip: Some(false),
+                skip_udp: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+        let err = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap_err();
+
+        match err {
+            Error::PortExhausted {
+                usage: Some(usage), ..
+            } => assert_eq!((usage.reserved, usage.occupied), (0, 1)),
+            other => panic!("Expected PortExhausted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_plan_exhaustion_reports_requested_port_reason() {
+        let mut db = create_test_database();
+        let config = Config {
+            ports: Some(PortConfig {
+                min: 5000,
+                max: Some(5001),
+                max_offset: None,
+            }),
+            excluded_ports: Some(vec![crate::config::PortExclusion::Single(5001)]),
+            occupancy_check: Some(crate::config::OccupancyConfig {
+                skip: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let held = ReservationKey::new(PathBuf::from("/test/held"), None).unwrap();
+        db.create_reservation(
+            &Reservation::builder(held, Port::try_from(5000).unwrap())
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        for (port, reason) in [
+            (5000, PortUnavailableReason::Reserved),
+            (5001, PortUnavailableReason::Excluded),
+            (6000, PortUnavailableReason::OutOfRange),
+        ] {
+            let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+            let options = ReserveOptions::new(key, Some(Port::try_from(port).unwrap()))
+                .with_allow_unrelated_path(true);
+
+            let err = ReservePlan::new(options, &config)
+                .build_plan(db.connection())
+                .unwrap_err();
+
+            assert_eq!(err.port_unavailable_reason(), Some(reason));
+            assert!(
+                err.to_string()
+                    .contains(&format!("requested port {port} is {reason}")),
+                "{err}"
+            );
+        }
+
+        // A --preferred port is named as such
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None)
+            .with_preferred_port(Some(Port::try_from(5000).unwrap()))
+            .with_allow_unrelated_path(true);
+        let err = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("preferred port 5000 is reserved"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_plan_path_relationship_denied() {
         let db = create_test_database();
@@ -1058,4 +2143,256 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    // Helper to create a small-range config with a pre-reserve hook
+    #[cfg(unix)]
+    fn create_hook_config(hook: &str) -> Config {
+        Config {
+            ports: Some(PortConfig {
+                min: 5000,
+                max: Some(5002),
+                max_offset: None,
+            }),
+            occupancy_check: Some(crate::config::OccupancyConfig {
+                skip: Some(true),
+                ..Default::default()
+            }),
+            pre_reserve_hook: Some(hook.to_string()),
+            pre_reserve_hook_timeout_seconds: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plan_hook_veto_tries_next_candidate() {
+        let db = create_test_database();
+        let config = create_hook_config("test \"$TROP_HOOK_PORT\" != 5000");
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        match &plan.actions[0] {
+            PlanAction::CreateReservation(res) => assert_eq!(res.port().value(), 5001),
+            _ => panic!("Expected CreateReservation action"),
+        }
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.warnings[0].contains("vetoed port 5000"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plan_hook_veto_of_requested_port_falls_back() {
+        let db = create_test_database();
+        let config = create_hook_config("test \"$TROP_HOOK_PORT\" != 5002");
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, Some(Port::try_from(5002).unwrap()))
+            .with_allow_unrelated_path(true);
+
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        match &plan.actions[0] {
+            PlanAction::CreateReservation(res) => assert_eq!(res.port().value(), 5000),
+            _ => panic!("Expected CreateReservation action"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plan_hook_vetoing_everything_exhausts() {
+        let db = create_test_database();
+        let config = create_hook_config("exit 1");
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+
+        let result = ReservePlan::new(options, &config).build_plan(db.connection());
+
+        assert!(matches!(
+            result,
+            Err(Error::PortExhausted {
+                usage: Some(RangeUsage { vetoed: 3, .. }),
+                ..
+            })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plan_hook_not_run_for_existing_reservation() {
+        let mut db = create_test_database();
+        let config = create_hook_config("exit 1");
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let reservation = Reservation::builder(key.clone(), Port::try_from(5000).unwrap())
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        assert!(matches!(plan.actions[0], PlanAction::UpdateLastUsed(_)));
+    }
+
+    // Helper: a 5000-5009 range with 5000, 5001, 5008, and 5009 excluded,
+    // occupancy checks skipped, and the given strategy configured
+    fn create_strategy_config(strategy: Option<AllocationStrategy>) -> Config {
+        Config {
+            ports: Some(PortConfig {
+                min: 5000,
+                max: Some(5009),
+                max_offset: None,
+            }),
+            allocation_strategy: strategy,
+            excluded_ports: Some(vec![
+                crate::config::PortExclusion::Range {
+                    start: 5000,
+                    end: 5001,
+                },
+                crate::config::PortExclusion::Range {
+                    start: 5008,
+                    end: 5009,
+                },
+            ]),
+            occupancy_check: Some(crate::config::OccupancyConfig {
+                skip: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    // Helper: plan an automatic allocation and return the chosen port
+    fn plan_port(config: &Config, options: ReserveOptions, conn: &Connection) -> u16 {
+        let plan = ReservePlan::new(options, config).build_plan(conn).unwrap();
+        match &plan.actions[0] {
+            PlanAction::CreateReservation(res) => res.port().value(),
+            other => panic!("Expected CreateReservation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_allocation_strategy_candidates() {
+        let range =
+            PortRange::new(Port::try_from(5000).unwrap(), Port::try_from(5099).unwrap()).unwrap();
+        let values = |ports: Vec<Port>| ports.iter().map(|p| p.value()).collect::<Vec<_>>();
+
+        let lowest = values(AllocationStrategy::Lowest.candidates(range, 1));
+        assert_eq!(lowest, (5000..=5099).collect::<Vec<_>>());
+
+        let highest = values(AllocationStrategy::Highest.candidates(range, 1));
+        assert_eq!(highest, (5000..=5099).rev().collect::<Vec<_>>());
+
+        // Random visits every port exactly once, deterministically per seed
+        let random = values(AllocationStrategy::Random.candidates(range, 7));
+        assert_eq!(
+            random,
+            values(AllocationStrategy::Random.candidates(range, 7))
+        );
+        assert_ne!(random, lowest);
+        let mut sorted = random.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, lowest);
+    }
+
+    #[test]
+    fn test_plan_strategy_lowest_is_default() {
+        let db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+
+        let port = plan_port(&create_strategy_config(None), options, db.connection());
+        assert_eq!(port, 5002);
+    }
+
+    #[test]
+    fn test_plan_strategy_highest_respects_exclusions() {
+        let db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None).with_allow_unrelated_path(true);
+
+        let config = create_strategy_config(Some(AllocationStrategy::Highest));
+        assert_eq!(plan_port(&config, options, db.connection()), 5007);
+    }
+
+    #[test]
+    fn test_plan_strategy_option_overrides_config() {
+        let db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_strategy(Some(AllocationStrategy::Lowest));
+
+        let config = create_strategy_config(Some(AllocationStrategy::Highest));
+        assert_eq!(plan_port(&config, options, db.connection()), 5002);
+    }
+
+    #[test]
+    fn test_plan_strategy_random_respects_range_and_exclusions() {
+        let mut db = create_test_database();
+        let config = create_strategy_config(Some(AllocationStrategy::Random));
+
+        // Hold 5002 so random allocation must skip a reserved port too
+        let held = ReservationKey::new(PathBuf::from("/test/held"), None).unwrap();
+        let reservation = Reservation::builder(held, Port::try_from(5002).unwrap())
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let mut seen = std::collections::HashSet::new();
+        for seed in 0..50 {
+            let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+            let options = ReserveOptions::new(key, None)
+                .with_allow_unrelated_path(true)
+                .with_allocation_seed(Some(seed));
+            let port = plan_port(&config, options.clone(), db.connection());
+            assert!((5003..=5007).contains(&port), "port {port} not allowed");
+            // Same seed, same port
+            assert_eq!(plan_port(&config, options, db.connection()), port);
+            seen.insert(port);
+        }
+        assert!(seen.len() > 1, "random strategy always chose {seen:?}");
+    }
+
+    #[test]
+    fn test_plan_strategy_preferred_port_still_wins() {
+        let db = create_test_database();
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_preferred_port(Some(Port::try_from(5004).unwrap()));
+
+        let config = create_strategy_config(Some(AllocationStrategy::Random));
+        assert_eq!(plan_port(&config, options, db.connection()), 5004);
+    }
+
+    #[test]
+    fn test_plan_preferred_port_falls_back_with_warning() {
+        let db = create_test_database();
+        let config = create_strategy_config(None);
+        let key = ReservationKey::new(PathBuf::from("/test/path"), None).unwrap();
+
+        // 5001 is excluded, so allocation falls back to the lowest free port
+        let options = ReserveOptions::new(key, None)
+            .with_allow_unrelated_path(true)
+            .with_preferred_port(Some(Port::try_from(5001).unwrap()));
+        let plan = ReservePlan::new(options, &config)
+            .build_plan(db.connection())
+            .unwrap();
+
+        match &plan.actions[0] {
+            PlanAction::CreateReservation(res) => assert_eq!(res.port().value(), 5002),
+            other => panic!("Expected CreateReservation, got {other:?}"),
+        }
+        assert_eq!(
+            plan.warnings,
+            vec!["preferred 5001 unavailable (excluded), allocated 5002".to_string()]
+        );
+    }
 }
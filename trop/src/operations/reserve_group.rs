@@ -3,15 +3,21 @@
 //! This module implements group reservation planning, which reserves multiple
 //! related ports based on a configuration file.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::config::{Config, ConfigLoader, ConfigValidator};
+use crate::config::{
+    normalize_exclusions, Config, ConfigLoader, ConfigValidator, PortExclusion, ReservationGroup,
+    TagCase,
+};
 use crate::error::{Error, Result};
+use crate::output::resolve_env_var_name;
 use crate::port::group::{GroupAllocationRequest, ServiceAllocationRequest};
 use crate::port::occupancy::OccupancyCheckConfig;
 use crate::Port;
 use rusqlite::Connection;
 
+use super::hook::PreReserveHook;
 use super::plan::{OperationPlan, PlanAction};
 
 /// Options for a reserve group operation.
@@ -38,6 +44,35 @@ pub struct ReserveGroupOptions {
 
     /// Allow changing the task field.
     pub allow_task_change: bool,
+
+    /// Case sensitivity applied to service tags (unless the group's config
+    /// file sets its own `tag_case`).
+    pub tag_case: TagCase,
+
+    /// Extra port exclusions layered on top of the config file's
+    /// `excluded_ports` for this operation only.
+    pub exclusions: Vec<PortExclusion>,
+
+    /// Which named reservation group to reserve; `None` selects the only
+    /// group defined.
+    pub group: Option<String>,
+
+    /// Prepended to every service's reservation tag (`<prefix>-web`).
+    pub tag_prefix: Option<String>,
+
+    /// Appended to every service's reservation tag (`web-<suffix>`).
+    pub tag_suffix: Option<String>,
+
+    /// Reserve only these services; empty means every service.
+    pub only: Vec<String>,
+
+    /// Skip these services.
+    pub except: Vec<String>,
+
+    /// Hook asked about every port the group would get. It is only allowed
+    /// in user configuration, so it comes from the caller rather than the
+    /// group's config file.
+    pub pre_reserve_hook: Option<PreReserveHook>,
 }
 
 impl ReserveGroupOptions {
@@ -63,6 +98,14 @@ impl ReserveGroupOptions {
             allow_unrelated_path: false,
             allow_project_change: false,
             allow_task_change: false,
+            tag_case: TagCase::Sensitive,
+            exclusions: Vec::new(),
+            group: None,
+            tag_prefix: None,
+            tag_suffix: None,
+            only: Vec::new(),
+            except: Vec::new(),
+            pre_reserve_hook: None,
         }
     }
 
@@ -100,6 +143,93 @@ impl ReserveGroupOptions {
         self.allow_task_change = allow;
         self
     }
+
+    /// Sets the tag case sensitivity.
+    #[must_use]
+    pub const fn with_tag_case(mut self, tag_case: TagCase) -> Self {
+        self.tag_case = tag_case;
+        self
+    }
+
+    /// Sets extra port exclusions for this operation.
+    #[must_use]
+    pub fn with_exclusions(mut self, exclusions: Vec<PortExclusion>) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Sets the name of the reservation group to reserve.
+    #[must_use]
+    pub fn with_group(mut self, group: Option<String>) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// Sets the pre-reserve hook consulted for each allocated port.
+    #[must_use]
+    pub fn with_pre_reserve_hook(mut self, hook: Option<PreReserveHook>) -> Self {
+        self.pre_reserve_hook = hook;
+        self
+    }
+
+    /// Sets the prefix added to every service's reservation tag.
+    #[must_use]
+    pub fn with_tag_prefix(mut self, prefix: Option<String>) -> Self {
+        self.tag_prefix = prefix;
+        self
+    }
+
+    /// Sets the suffix added to every service's reservation tag.
+    #[must_use]
+    pub fn with_tag_suffix(mut self, suffix: Option<String>) -> Self {
+        self.tag_suffix = suffix;
+        self
+    }
+
+    /// Restricts the operation to the named services.
+    #[must_use]
+    pub fn with_only(mut self, services: Vec<String>) -> Self {
+        self.only = services;
+        self
+    }
+
+    /// Skips the named services.
+    #[must_use]
+    pub fn with_except(mut self, services: Vec<String>) -> Self {
+        self.except = services;
+        self
+    }
+
+    /// Returns the reservation tag stored for `service`, with the tag prefix
+    /// and suffix applied.
+    ///
+    /// Environment variable names are still derived from the undecorated
+    /// service name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::operations::ReserveGroupOptions;
+    /// use std::path::PathBuf;
+    ///
+    /// let options = ReserveGroupOptions::new(PathBuf::from("trop.yaml"))
+    ///     .with_tag_suffix(Some("task-1".to_string()));
+    /// assert_eq!(options.decorate_tag("web"), "web-task-1");
+    /// ```
+    #[must_use]
+    pub fn decorate_tag(&self, service: &str) -> String {
+        let mut tag = String::new();
+        if let Some(ref prefix) = self.tag_prefix {
+            tag.push_str(prefix);
+            tag.push('-');
+        }
+        tag.push_str(service);
+        if let Some(ref suffix) = self.tag_suffix {
+            tag.push('-');
+            tag.push_str(suffix);
+        }
+        tag
+    }
 }
 
 /// A reserve group plan generator.
@@ -120,6 +250,8 @@ impl ReserveGroupPlan {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The tag prefix or suffix is empty or contains whitespace
+    /// - Both `only` and `except` are set
     /// - The config file cannot be read or parsed
     /// - The config file does not contain a reservation group
     /// - The reservation group is invalid
@@ -134,10 +266,32 @@ impl ReserveGroupPlan {
     /// let planner = ReserveGroupPlan::new(options).unwrap();
     /// ```
     pub fn new(options: ReserveGroupOptions) -> Result<Self> {
+        Self::validate_tag_decoration("tag_prefix", options.tag_prefix.as_deref())?;
+        Self::validate_tag_decoration("tag_suffix", options.tag_suffix.as_deref())?;
+        if !options.only.is_empty() && !options.except.is_empty() {
+            return Err(Error::Validation {
+                field: "services".to_string(),
+                message: "only and except are mutually exclusive".to_string(),
+            });
+        }
+
         // Load the configuration file
-        let config = ConfigLoader::load_file(&options.config_path)?;
+        let mut config = ConfigLoader::load_file(&options.config_path)?;
         ConfigValidator::validate(&config, true)?;
 
+        // Extra exclusions only live in memory; the file is never rewritten
+        if !options.exclusions.is_empty() {
+            let excluded = config.excluded_ports.get_or_insert_with(Vec::new);
+            excluded.extend(options.exclusions.iter().cloned());
+            normalize_exclusions(excluded);
+        }
+
+        // The group is allocated from this config, so the hook rides along
+        if let Some(hook) = &options.pre_reserve_hook {
+            config.pre_reserve_hook = Some(hook.command().to_string());
+            config.pre_reserve_hook_timeout_seconds = Some(hook.timeout().as_secs());
+        }
+
         // Get the base path (parent directory of the config file)
         let base_path = options
             .config_path
@@ -155,13 +309,79 @@ impl ReserveGroupPlan {
         })
     }
 
-    /// Gets the occupancy check configuration from the overall config.
-    fn occupancy_config(&self) -> OccupancyCheckConfig {
-        if let Some(ref occ_config) = self.config.occupancy_check {
-            OccupancyCheckConfig::from(occ_config)
+    /// Checks that a tag prefix or suffix is a non-empty word, so decorated
+    /// tags survive the whitespace trimming reservation keys apply.
+    fn validate_tag_decoration(field: &str, value: Option<&str>) -> Result<()> {
+        match value {
+            Some(value) if value.is_empty() || value.contains(char::is_whitespace) => {
+                Err(Error::Validation {
+                    field: field.to_string(),
+                    message: format!("must be non-empty and contain no whitespace: {value:?}"),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Gets the tag case sensitivity, preferring the group's own config file.
+    fn tag_case(&self) -> TagCase {
+        self.config.tag_case.unwrap_or(self.options.tag_case)
+    }
+
+    /// Gets the reservation group selected by the options.
+    fn reservation_group(&self) -> Result<&ReservationGroup> {
+        self.config
+            .reservations
+            .as_ref()
+            .ok_or_else(|| Error::Validation {
+                field: "reservations".to_string(),
+                message: "Configuration file does not contain a reservation group".to_string(),
+            })?
+            .select(self.options.group.as_deref())
+    }
+
+    /// Gets the selected reservation group narrowed to the services chosen
+    /// by `only` / `except`.
+    ///
+    /// Every named service must exist in the group.
+    fn selected_group(&self) -> Result<ReservationGroup> {
+        let mut group = self.reservation_group()?.clone();
+        let named = if self.options.only.is_empty() {
+            &self.options.except
         } else {
-            OccupancyCheckConfig::default()
+            &self.options.only
+        };
+        if named.is_empty() {
+            return Ok(group);
+        }
+
+        let tag_case = self.tag_case();
+        let normalize = |service: &str| tag_case.normalize(service.to_string());
+        let named: Vec<String> = named.iter().map(|service| normalize(service)).collect();
+        for service in &named {
+            if !group.services.keys().any(|tag| normalize(tag) == *service) {
+                let mut available: Vec<&str> = group.services.keys().map(String::as_str).collect();
+                available.sort_unstable();
+                return Err(Error::Validation {
+                    field: "services".to_string(),
+                    message: format!(
+                        "Service '{service}' not found in reservation group (available: {})",
+                        available.join(", ")
+                    ),
+                });
+            }
         }
+
+        let keep_named = !self.options.only.is_empty();
+        group
+            .services
+            .retain(|tag, _| named.contains(&normalize(tag)) == keep_named);
+        Ok(group)
+    }
+
+    /// Gets the occupancy check configuration from the overall config.
+    fn occupancy_config(&self) -> OccupancyCheckConfig {
+        OccupancyCheckConfig::from_config(&self.config)
     }
 
     /// Builds an operation plan for this reserve group request.
@@ -180,7 +400,10 @@ impl ReserveGroupPlan {
     ///
     /// Returns an error if:
     /// - The config does not contain a reservation group
+    /// - The requested group is not defined, or several groups are defined
+    ///   and none was requested
     /// - The reservation group is invalid
+    /// - A service named by `only` or `except` is not in the group
     /// - Group allocation validation fails
     ///
     /// # Examples
@@ -196,15 +419,8 @@ impl ReserveGroupPlan {
     /// let plan = planner.build_plan(db.connection()).unwrap();
     /// ```
     pub fn build_plan(&self, _conn: &Connection) -> Result<OperationPlan> {
-        // Extract the reservation group from config
-        let reservation_group =
-            self.config
-                .reservations
-                .as_ref()
-                .ok_or_else(|| Error::Validation {
-                    field: "reservations".to_string(),
-                    message: "Configuration file does not contain a reservation group".to_string(),
-                })?;
+        // Extract the selected reservation group and services from config
+        let reservation_group = &self.selected_group()?;
 
         // Validate that we have at least one service
         if reservation_group.services.is_empty() {
@@ -214,6 +430,12 @@ impl ReserveGroupPlan {
             });
         }
 
+        // Ensure no two services will be emitted under the same env var
+        Self::check_env_name_collisions(reservation_group)?;
+
+        // Ensure no two services will share a reservation key
+        Self::check_tag_collisions(reservation_group, self.tag_case())?;
+
         // Convert the reservation group to a GroupAllocationRequest
         let request = self.build_group_request(reservation_group)?;
 
@@ -236,18 +458,68 @@ impl ReserveGroupPlan {
         Ok(plan)
     }
 
+    /// Checks that the resolved environment variable names are unique.
+    ///
+    /// The config validator only compares explicit `env` names; this also
+    /// accounts for names derived from service tags, which is what the
+    /// output formatters will actually emit.
+    fn check_env_name_collisions(group: &ReservationGroup) -> Result<()> {
+        let mappings: HashMap<String, String> = group
+            .services
+            .iter()
+            .filter_map(|(tag, service)| service.env.clone().map(|env| (tag.clone(), env)))
+            .collect();
+
+        let mut tags: Vec<&String> = group.services.keys().collect();
+        tags.sort();
+
+        let mut seen: HashMap<String, &str> = HashMap::new();
+        for tag in tags {
+            let env_name = resolve_env_var_name(tag, Some(&mappings))?;
+            if let Some(other) = seen.insert(env_name.clone(), tag) {
+                return Err(Error::Validation {
+                    field: format!("reservations.services.{tag}.env"),
+                    message: format!(
+                        "Services '{other}' and '{tag}' both resolve to environment variable {env_name}"
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that service tags remain distinct under the tag case policy.
+    fn check_tag_collisions(group: &ReservationGroup, tag_case: TagCase) -> Result<()> {
+        let mut tags: Vec<&String> = group.services.keys().collect();
+        tags.sort();
+
+        let mut seen: HashMap<String, &str> = HashMap::new();
+        for tag in tags {
+            let normalized = tag_case.normalize(tag.clone());
+            if let Some(other) = seen.insert(normalized.clone(), tag) {
+                return Err(Error::Validation {
+                    field: format!("reservations.services.{tag}"),
+                    message: format!(
+                        "Services '{other}' and '{tag}' both resolve to tag '{normalized}' (tag_case: {tag_case})"
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Builds a `GroupAllocationRequest` from the reservation group.
-    fn build_group_request(
-        &self,
-        group: &crate::config::ReservationGroup,
-    ) -> Result<GroupAllocationRequest> {
+    fn build_group_request(&self, group: &ReservationGroup) -> Result<GroupAllocationRequest> {
         let mut services = Vec::new();
+        let tag_case = self.tag_case();
 
         for (tag, service_def) in &group.services {
             let preferred = service_def.preferred.map(Port::try_from).transpose()?;
 
             services.push(ServiceAllocationRequest {
-                tag: tag.clone(),
+                tag: tag_case.normalize(self.options.decorate_tag(tag)),
                 offset: service_def
                     .offset
                     .or_else(|| service_def.preferred.is_none().then_some(0)),
@@ -264,10 +536,7 @@ impl ReserveGroupPlan {
     }
 
     /// Returns configuration adjusted so `reservations.base` is the group scan start.
-    fn config_with_group_base_as_scan_start(
-        &self,
-        group: &crate::config::ReservationGroup,
-    ) -> Result<Config> {
+    fn config_with_group_base_as_scan_start(&self, group: &ReservationGroup) -> Result<Config> {
         let Some(base) = group.base else {
             return Ok(self.config.clone());
         };
@@ -278,25 +547,7 @@ impl ReserveGroupPlan {
             message: "Port configuration is required when reservations.base is set".to_string(),
         })?;
 
-        let max = if let Some(max) = ports.max {
-            max
-        } else if let Some(max_offset) = ports.max_offset {
-            ports
-                .min
-                .checked_add(max_offset)
-                .ok_or_else(|| Error::Validation {
-                    field: "ports.max_offset".to_string(),
-                    message: format!(
-                        "Offset {max_offset} would overflow when added to min port {}",
-                        ports.min
-                    ),
-                })?
-        } else {
-            return Err(Error::Validation {
-                field: "ports".to_string(),
-                message: "Either max or max_offset must be specified".to_string(),
-            });
-        };
+        let max = ports.effective_max()?;
 
         if base < ports.min || base > max {
             return Err(Error::Validation {
@@ -319,9 +570,8 @@ impl ReserveGroupPlan {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{ReservationGroup, ServiceDefinition};
+    use crate::config::ServiceDefinition;
     use crate::database::test_util::create_test_database;
-    use std::collections::HashMap;
     use std::fs;
     use tempfile::TempDir;
 
@@ -431,6 +681,82 @@ reservations:
         }
     }
 
+    const MULTI_GROUP_CONFIG: &str = r"
+project: test-project
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  frontend:
+    services:
+      web:
+        offset: 0
+        env: WEB_PORT
+  backend:
+    services:
+      api:
+        offset: 0
+      db:
+        offset: 1
+";
+
+    fn plan_group(config_content: &str, group: Option<&str>) -> Result<OperationPlan> {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let db = create_test_database();
+        let options = ReserveGroupOptions::new(config_path).with_group(group.map(String::from));
+        ReserveGroupPlan::new(options)?.build_plan(db.connection())
+    }
+
+    #[test]
+    fn test_reserve_group_plan_named_group() {
+        let plan = plan_group(MULTI_GROUP_CONFIG, Some("backend")).unwrap();
+
+        match &plan.actions[0] {
+            PlanAction::AllocateGroup { request, .. } => {
+                let mut tags: Vec<&str> = request.services.iter().map(|s| s.tag.as_str()).collect();
+                tags.sort_unstable();
+                assert_eq!(tags, vec!["api", "db"]);
+            }
+            _ => panic!("Expected AllocateGroup action"),
+        }
+    }
+
+    #[test]
+    fn test_reserve_group_plan_unknown_group() {
+        let err = plan_group(MULTI_GROUP_CONFIG, Some("mobile")).unwrap_err();
+
+        match err {
+            Error::Validation { field, message } => {
+                assert_eq!(field, "reservations");
+                assert!(message.contains("'mobile' not found"), "{message}");
+                assert!(message.contains("backend, frontend"), "{message}");
+            }
+            other => panic!("Expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_reserve_group_plan_several_groups_need_a_name() {
+        let err = plan_group(MULTI_GROUP_CONFIG, None).unwrap_err();
+        assert!(err.to_string().contains("choose one with --group"), "{err}");
+    }
+
+    #[test]
+    fn test_reserve_group_plan_single_group_rejects_name() {
+        let config_content = r"
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  services:
+    web:
+      offset: 0
+";
+        assert!(plan_group(config_content, None).is_ok());
+        assert!(plan_group(config_content, Some("web")).is_err());
+    }
+
     #[test]
     fn test_reserve_group_plan_empty_services() {
         let temp_dir = TempDir::new().unwrap();
@@ -458,6 +784,233 @@ reservations:
         }
     }
 
+    #[test]
+    fn test_reserve_group_plan_rejects_env_name_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        // `api` explicitly claims WEB, which `web` also resolves to by default
+        let config_content = r"
+project: test-project
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  services:
+    web:
+      offset: 0
+    api:
+      offset: 1
+      env: WEB
+";
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let db = create_test_database();
+
+        let planner = ReserveGroupPlan::new(ReserveGroupOptions::new(config_path)).unwrap();
+        let err = planner.build_plan(db.connection()).unwrap_err();
+
+        match err {
+            Error::Validation { message, .. } => {
+                assert!(message.contains("'api'"), "{message}");
+                assert!(message.contains("'web'"), "{message}");
+                assert!(message.contains("WEB"), "{message}");
+            }
+            other => panic!("Expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_reserve_group_plan_rejects_derived_env_name_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_content = r"
+project: test-project
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  services:
+    my-app:
+      offset: 0
+    my_app:
+      offset: 1
+";
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let db = create_test_database();
+
+        let planner = ReserveGroupPlan::new(ReserveGroupOptions::new(config_path)).unwrap();
+        let err = planner.build_plan(db.connection()).unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::Validation { ref message, .. } if message.contains("MY_APP")
+        ));
+    }
+
+    #[test]
+    fn test_reserve_group_plan_tag_case() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_content = r"
+project: test-project
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  services:
+    Web:
+      offset: 0
+      env: WEB_UPPER
+    web:
+      offset: 1
+      env: WEB_LOWER
+";
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let db = create_test_database();
+
+        // Case-sensitive (default): distinct services
+        let planner = ReserveGroupPlan::new(ReserveGroupOptions::new(config_path.clone())).unwrap();
+        assert!(planner.build_plan(db.connection()).is_ok());
+
+        // Case-insensitive: both services would share the `web` key
+        let options = ReserveGroupOptions::new(config_path).with_tag_case(TagCase::Insensitive);
+        let planner = ReserveGroupPlan::new(options).unwrap();
+        let err = planner.build_plan(db.connection()).unwrap_err();
+
+        match err {
+            Error::Validation { message, .. } => {
+                assert!(message.contains("'Web'"), "{message}");
+                assert!(message.contains("'web'"), "{message}");
+            }
+            other => panic!("Expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_new_layers_extra_exclusions() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_content = r"
+project: test
+ports:
+  min: 5000
+  max: 7000
+excluded_ports:
+  - 5001
+reservations:
+  services:
+    web:
+      offset: 0
+";
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let options = ReserveGroupOptions::new(config_path.clone()).with_exclusions(vec![
+            PortExclusion::Range {
+                start: 5002,
+                end: 5004,
+            },
+        ]);
+        let planner = ReserveGroupPlan::new(options).unwrap();
+
+        let excluded = planner.config.excluded_ports.clone().unwrap();
+        assert!(excluded.contains(&PortExclusion::Single(5001)));
+        assert!(excluded.contains(&PortExclusion::Range {
+            start: 5002,
+            end: 5004
+        }));
+        assert_eq!(
+            std::fs::read_to_string(&config_path).unwrap(),
+            config_content
+        );
+    }
+
+    #[test]
+    fn test_build_group_request_lowercases_insensitive_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_content = r"
+project: test
+tag_case: insensitive
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  services:
+    Web:
+      offset: 0
+";
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let planner = ReserveGroupPlan::new(ReserveGroupOptions::new(config_path)).unwrap();
+
+        let group = planner.reservation_group().unwrap();
+        let request = planner.build_group_request(group).unwrap();
+
+        assert_eq!(request.services[0].tag, "web");
+    }
+
+    #[test]
+    fn test_build_group_request_decorates_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_content = r"
+project: test
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  services:
+    web:
+      offset: 0
+";
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let options = ReserveGroupOptions::new(config_path.clone())
+            .with_tag_prefix(Some("ci".to_string()))
+            .with_tag_suffix(Some("task-1".to_string()));
+        let planner = ReserveGroupPlan::new(options).unwrap();
+
+        let group = planner.reservation_group().unwrap();
+        let request = planner.build_group_request(group).unwrap();
+
+        assert_eq!(request.services[0].tag, "ci-web-task-1");
+
+        let options =
+            ReserveGroupOptions::new(config_path).with_tag_suffix(Some("a b".to_string()));
+        assert!(ReserveGroupPlan::new(options).is_err());
+    }
+
+    #[test]
+    fn test_reserve_group_plan_service_selectors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_content = r"
+project: test
+ports:
+  min: 5000
+  max: 7000
+reservations:
+  services:
+    web:
+      offset: 0
+    api:
+      offset: 1
+    db:
+      offset: 2
+";
+        let config_path = create_test_config_file(&temp_dir, config_content);
+        let tags = |options: ReserveGroupOptions| -> Result<Vec<String>> {
+            let plan = ReserveGroupPlan::new(options)?.selected_group()?;
+            let mut tags: Vec<String> = plan.services.into_keys().collect();
+            tags.sort();
+            Ok(tags)
+        };
+        let options = ReserveGroupOptions::new(config_path.clone());
+
+        let only = options.clone().with_only(vec!["web".to_string()]);
+        assert_eq!(tags(only).unwrap(), ["web"]);
+
+        let except = options.clone().with_except(vec!["api".to_string()]);
+        assert_eq!(tags(except).unwrap(), ["db", "web"]);
+
+        let unknown = options.clone().with_only(vec!["cache".to_string()]);
+        assert!(tags(unknown).is_err());
+
+        let both = options
+            .with_only(vec!["web".to_string()])
+            .with_except(vec!["api".to_string()]);
+        assert!(ReserveGroupPlan::new(both).is_err());
+    }
+
     #[test]
     fn test_build_group_request_with_offsets() {
         let mut services = HashMap::new();
@@ -590,7 +1143,7 @@ reservations:
         let config_path = create_test_config_file(&temp_dir, config_content);
         let options = ReserveGroupOptions::new(config_path);
         let planner = ReserveGroupPlan::new(options).unwrap();
-        let group = planner.config.reservations.as_ref().unwrap();
+        let group = planner.reservation_group().unwrap();
 
         let config = planner.config_with_group_base_as_scan_start(group).unwrap();
         let ports = config.ports.unwrap();
@@ -617,7 +1170,7 @@ reservations:
         let config_path = create_test_config_file(&temp_dir, config_content);
         let options = ReserveGroupOptions::new(config_path);
         let planner = ReserveGroupPlan::new(options).unwrap();
-        let group = planner.config.reservations.as_ref().unwrap();
+        let group = planner.reservation_group().unwrap();
 
         let err = planner
             .config_with_group_base_as_scan_start(group)
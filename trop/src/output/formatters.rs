@@ -1,6 +1,8 @@
 //! Output formatter implementations.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
 
 use crate::{Error, Port, Result};
 
@@ -49,7 +51,7 @@ fn tag_to_env_var(tag: &str) -> Result<String> {
 }
 
 /// Resolve environment variable name for a service tag using optional mappings.
-fn resolve_env_var_name(
+pub(crate) fn resolve_env_var_name(
     tag: &str,
     env_mappings: Option<&HashMap<String, String>>,
 ) -> Result<String> {
@@ -63,10 +65,43 @@ fn resolve_env_var_name(
     }
 }
 
+/// Checks that `prefix` can be prepended to environment variable names.
+///
+/// A valid prefix is itself a valid variable name (e.g. `MYAPP_`), so any
+/// valid name stays valid once prefixed.
+///
+/// # Errors
+///
+/// Returns a validation error if the prefix is empty, starts with a digit,
+/// or contains characters other than ASCII letters, digits, and underscores.
+///
+/// # Examples
+///
+/// ```
+/// use trop::output::validate_env_prefix;
+///
+/// assert!(validate_env_prefix("MYAPP_").is_ok());
+/// assert!(validate_env_prefix("1APP_").is_err());
+/// assert!(validate_env_prefix("MY-APP_").is_err());
+/// ```
+pub fn validate_env_prefix(prefix: &str) -> Result<()> {
+    if is_valid_env_var_name(prefix) {
+        Ok(())
+    } else {
+        Err(Error::Validation {
+            field: "env_prefix".to_string(),
+            message: format!(
+                "invalid environment variable prefix '{prefix}': must contain only alphanumeric characters and underscores, and start with a letter or underscore"
+            ),
+        })
+    }
+}
+
 /// Formatter for shell-specific export statements.
 pub struct ExportFormatter {
     shell: ShellType,
     env_mappings: Option<HashMap<String, String>>,
+    env_prefix: String,
 }
 
 impl ExportFormatter {
@@ -82,8 +117,18 @@ impl ExportFormatter {
         Self {
             shell,
             env_mappings,
+            env_prefix: String::new(),
         }
     }
+
+    /// Prepend `prefix` to every variable name.
+    ///
+    /// The prefix is not validated here; see [`validate_env_prefix`].
+    #[must_use]
+    pub fn with_env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = prefix.into();
+        self
+    }
 }
 
 impl OutputFormatter for ExportFormatter {
@@ -99,6 +144,7 @@ impl OutputFormatter for ExportFormatter {
 
             // Resolve environment variable name
             let var_name = resolve_env_var_name(tag, self.env_mappings.as_ref())?;
+            let var_name = format!("{}{var_name}", self.env_prefix);
 
             exports.push(
                 self.shell
@@ -128,9 +174,52 @@ impl OutputFormatter for JsonFormatter {
     }
 }
 
+/// Formatter for YAML output.
+///
+/// Allocations are written as a mapping from service tag to port, with keys
+/// sorted so the output is deterministic. An empty set of allocations is an
+/// empty mapping (`{}`).
+pub struct YamlFormatter;
+
+impl YamlFormatter {
+    /// Serialize any value to YAML, without a trailing newline.
+    ///
+    /// This is used for output richer than an allocation map, such as full
+    /// reservation records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be represented as YAML.
+    pub fn format_value<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+        serde_yaml::to_string(value)
+            .map(|yaml| yaml.trim_end().to_string())
+            .map_err(|e| Error::Validation {
+                field: "yaml_output".to_string(),
+                message: format!("failed to serialize to YAML: {e}"),
+            })
+    }
+}
+
+impl OutputFormatter for YamlFormatter {
+    fn format(&self, allocations: &HashMap<String, Port>) -> Result<String> {
+        let yaml_map: BTreeMap<&str, u16> = allocations
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.value()))
+            .collect();
+
+        Self::format_value(&yaml_map)
+    }
+}
+
 /// Formatter for dotenv (.env file) format.
+///
+/// Values are written bare (`WEB=5000`) unless quoting is enabled with
+/// [`DotenvFormatter::with_quoting`], in which case they are wrapped in
+/// double quotes (`WEB="5000"`).
 pub struct DotenvFormatter {
     env_mappings: Option<HashMap<String, String>>,
+    env_prefix: String,
+    quote: bool,
 }
 
 impl DotenvFormatter {
@@ -142,8 +231,48 @@ impl DotenvFormatter {
     ///   If None, tags are converted to uppercase.
     #[must_use]
     pub fn new(env_mappings: Option<HashMap<String, String>>) -> Self {
-        Self { env_mappings }
+        Self {
+            env_mappings,
+            env_prefix: String::new(),
+            quote: false,
+        }
+    }
+
+    /// Prepend `prefix` to every variable name.
+    ///
+    /// The prefix is not validated here; see [`validate_env_prefix`].
+    #[must_use]
+    pub fn with_env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = prefix.into();
+        self
+    }
+
+    /// Set whether values are wrapped in double quotes.
+    ///
+    /// Quoted values escape embedded backslashes, double quotes, and
+    /// newlines, so any value survives a round trip through a dotenv parser.
+    #[must_use]
+    pub const fn with_quoting(mut self, quote: bool) -> Self {
+        self.quote = quote;
+        self
+    }
+}
+
+/// Wrap a value in double quotes, escaping characters dotenv parsers
+/// interpret inside them.
+fn quote_dotenv_value(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
     }
+    quoted.push('"');
+    quoted
 }
 
 impl OutputFormatter for DotenvFormatter {
@@ -159,14 +288,87 @@ impl OutputFormatter for DotenvFormatter {
 
             // Resolve environment variable name
             let var_name = resolve_env_var_name(tag, self.env_mappings.as_ref())?;
-
-            lines.push(format!("{}={}", var_name, port.value()));
+            let var_name = format!("{}{var_name}", self.env_prefix);
+
+            let value = port.value().to_string();
+            if self.quote {
+                lines.push(format!("{var_name}={}", quote_dotenv_value(&value)));
+            } else {
+                lines.push(format!("{var_name}={value}"));
+            }
         }
 
         Ok(lines.join("\n"))
     }
 }
 
+/// Merge dotenv `assignments` (`VAR=value` lines, as produced by
+/// [`DotenvFormatter`]) into the contents of an existing dotenv file.
+///
+/// Lines assigning one of the variables are replaced in place, keeping any
+/// `export ` prefix; variables not yet present are appended in order. Every
+/// other line, including comments and blank lines, is kept unchanged. The
+/// result ends with a newline unless it is empty.
+///
+/// # Examples
+///
+/// ```
+/// use trop::output::merge_dotenv;
+///
+/// let existing = "# shared settings\nDEBUG=1\nWEB_PORT=4000\n";
+/// let merged = merge_dotenv(existing, "API_PORT=5001\nWEB_PORT=5000");
+/// assert_eq!(
+///     merged,
+///     "# shared settings\nDEBUG=1\nWEB_PORT=5000\nAPI_PORT=5001\n"
+/// );
+/// ```
+#[must_use]
+pub fn merge_dotenv(existing: &str, assignments: &str) -> String {
+    let assignments: Vec<(&str, &str)> = assignments
+        .lines()
+        .filter_map(|line| Some((dotenv_key(line)?, line)))
+        .collect();
+    let mut placed = vec![false; assignments.len()];
+
+    let mut merged = String::with_capacity(existing.len() + assignments.len() * 16);
+    for line in existing.lines() {
+        let replacement =
+            dotenv_key(line).and_then(|key| assignments.iter().position(|(var, _)| *var == key));
+        match replacement {
+            Some(index) => {
+                if line.trim_start().starts_with("export ") {
+                    merged.push_str("export ");
+                }
+                merged.push_str(assignments[index].1);
+                placed[index] = true;
+            }
+            None => merged.push_str(line),
+        }
+        merged.push('\n');
+    }
+
+    for ((_, line), placed) in assignments.iter().zip(placed) {
+        if !placed {
+            merged.push_str(line);
+            merged.push('\n');
+        }
+    }
+
+    merged
+}
+
+/// The variable a dotenv line assigns, if it is an assignment.
+fn dotenv_key(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    if line.starts_with('#') {
+        return None;
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    (!key.is_empty()).then_some(key)
+}
+
 /// Formatter for human-readable output.
 pub struct HumanFormatter;
 
@@ -506,6 +708,21 @@ mod tests {
         assert_eq!(lines[2], "$env:WEB=\"8080\"");
     }
 
+    /// Test nushell export formatting.
+    ///
+    /// Nushell assigns to the `$env` record: `$env.VAR = "value"`.
+    #[test]
+    fn test_export_formatter_nushell() {
+        let mut allocations = HashMap::new();
+        allocations.insert("web".to_string(), Port::try_from(8080).unwrap());
+        allocations.insert("api".to_string(), Port::try_from(8081).unwrap());
+
+        let formatter = ExportFormatter::new(ShellType::Nushell, None);
+        let output = formatter.format(&allocations).unwrap();
+
+        assert_eq!(output, "$env.API = \"8081\"\n$env.WEB = \"8080\"");
+    }
+
     /// Test zsh export formatting.
     ///
     /// Zsh uses the same syntax as bash for exports, but we test it separately
@@ -607,6 +824,34 @@ mod tests {
         assert_eq!(ps_output, "$env:CUSTOM_WEB_PORT=\"8080\"");
     }
 
+    // ========================================================================
+    // YAML Formatter Tests
+    // ========================================================================
+
+    #[test]
+    fn test_yaml_formatter_sorted_keys() {
+        let mut allocations = HashMap::new();
+        allocations.insert("web".to_string(), Port::try_from(8080).unwrap());
+        allocations.insert("api".to_string(), Port::try_from(8081).unwrap());
+        allocations.insert("db-primary".to_string(), Port::try_from(5432).unwrap());
+
+        let output = YamlFormatter.format(&allocations).unwrap();
+        assert_eq!(output, "api: 8081\ndb-primary: 5432\nweb: 8080");
+
+        let parsed: HashMap<String, u16> = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed.get("db-primary"), Some(&5432));
+    }
+
+    #[test]
+    fn test_yaml_formatter_empty() {
+        let output = YamlFormatter.format(&HashMap::new()).unwrap();
+        assert_eq!(output, "{}");
+
+        let parsed: HashMap<String, u16> = serde_yaml::from_str(&output).unwrap();
+        assert!(parsed.is_empty());
+    }
+
     // ========================================================================
     // JSON Formatter Tests
     // ========================================================================
@@ -827,6 +1072,63 @@ mod tests {
         assert_eq!(output, "BACKEND_PORT=8081\nDB=5432\nFRONTEND_PORT=8080");
     }
 
+    #[test]
+    fn test_dotenv_formatter_quoted() {
+        let allocations = create_test_allocations();
+        let formatter = DotenvFormatter::new(None).with_quoting(true);
+        let output = formatter.format(&allocations).unwrap();
+
+        assert_eq!(output, "API=\"5001\"\nWEB=\"5000\"");
+    }
+
+    /// Test that quoted output parses back to the original values.
+    #[test]
+    fn test_dotenv_formatter_quoted_round_trip() {
+        let allocations = create_test_allocations();
+        let output = DotenvFormatter::new(None)
+            .with_quoting(true)
+            .format(&allocations)
+            .unwrap();
+
+        let parsed: HashMap<String, String> = dotenvy::from_read_iter(output.as_bytes())
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(parsed["API"], "5001");
+        assert_eq!(parsed["WEB"], "5000");
+    }
+
+    #[test]
+    fn test_quote_dotenv_value_escapes() {
+        let value = r#"say "hi" from C:\tmp"#;
+        let quoted = quote_dotenv_value(value);
+        assert_eq!(quoted, r#""say \"hi\" from C:\\tmp""#);
+
+        let line = format!("NOTE={quoted}\nMULTI={}", quote_dotenv_value("a\nb"));
+        let parsed: HashMap<String, String> = dotenvy::from_read_iter(line.as_bytes())
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(parsed["NOTE"], value);
+        assert_eq!(parsed["MULTI"], "a\nb");
+    }
+
+    #[test]
+    fn test_merge_dotenv_preserves_unrelated_lines() {
+        let existing = "# base\nexport DEBUG=1\n\nexport WEB=4000\nNAME=app";
+        let merged = merge_dotenv(existing, "API=5001\nWEB=5000");
+        assert_eq!(
+            merged,
+            "# base\nexport DEBUG=1\n\nexport WEB=5000\nNAME=app\nAPI=5001\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_dotenv_is_idempotent() {
+        let once = merge_dotenv("KEEP=yes\n", "API=5001\nWEB=5000");
+        let twice = merge_dotenv(&once, "API=5001\nWEB=5002");
+        assert_eq!(twice, "KEEP=yes\nAPI=5001\nWEB=5002\n");
+        assert_eq!(merge_dotenv("", ""), "");
+    }
+
     // ========================================================================
     // Human Formatter Tests
     // ========================================================================
@@ -1132,4 +1434,40 @@ mod tests {
         let result = resolve_env_var_name("db", None).unwrap();
         assert_eq!(result, "DB");
     }
+
+    #[test]
+    fn test_env_prefix_applies_to_export_and_dotenv() {
+        let allocations = create_test_allocations();
+        let mut mappings = HashMap::new();
+        mappings.insert("web".to_string(), "WEB_PORT".to_string());
+
+        let output = ExportFormatter::new(ShellType::Bash, Some(mappings.clone()))
+            .with_env_prefix("MYAPP_")
+            .format(&allocations)
+            .unwrap();
+        assert_eq!(output, "export MYAPP_API=5001\nexport MYAPP_WEB_PORT=5000");
+
+        let output = DotenvFormatter::new(None)
+            .with_env_prefix("MYAPP_")
+            .format(&allocations)
+            .unwrap();
+        assert_eq!(output, "MYAPP_API=5001\nMYAPP_WEB=5000");
+
+        // Tag-keyed formats ignore the prefix
+        let output = crate::output::OutputFormat::Json
+            .create_formatter(Some(mappings), Some("MYAPP_"))
+            .format(&allocations)
+            .unwrap();
+        assert!(!output.contains("MYAPP_"), "{output}");
+    }
+
+    #[test]
+    fn test_validate_env_prefix() {
+        assert!(validate_env_prefix("MYAPP_").is_ok());
+        assert!(validate_env_prefix("_").is_ok());
+        assert!(validate_env_prefix("").is_err());
+        assert!(validate_env_prefix("9APP_").is_err());
+        assert!(validate_env_prefix("MY-APP_").is_err());
+        assert!(validate_env_prefix("MY APP").is_err());
+    }
 }
@@ -1,8 +1,8 @@
 //! Output formatting module for port allocations.
 //!
 //! This module provides various output formats for displaying port allocations,
-//! including shell-specific export statements, JSON, dotenv format, and
-//! human-readable output.
+//! including shell-specific export statements, JSON, YAML, dotenv format,
+//! and human-readable output.
 
 mod formatters;
 mod shell;
@@ -11,7 +11,11 @@ use std::collections::HashMap;
 
 use crate::{Port, Result};
 
-pub use formatters::{DotenvFormatter, ExportFormatter, HumanFormatter, JsonFormatter};
+pub(crate) use formatters::resolve_env_var_name;
+pub use formatters::{
+    merge_dotenv, validate_env_prefix, DotenvFormatter, ExportFormatter, HumanFormatter,
+    JsonFormatter, YamlFormatter,
+};
 pub use shell::ShellType;
 
 /// Trait for formatting port allocations into different output formats.
@@ -35,8 +39,13 @@ pub enum OutputFormat {
     Export(ShellType),
     /// JSON format.
     Json,
+    /// YAML format.
+    Yaml,
     /// Dotenv (.env file) format.
-    Dotenv,
+    Dotenv {
+        /// Wrap values in double quotes.
+        quote: bool,
+    },
     /// Human-readable format.
     Human,
 }
@@ -48,15 +57,27 @@ impl OutputFormat {
     ///
     /// * `env_mappings` - Optional mapping from service tags to environment variable names.
     ///   If None, service tags are converted to uppercase for variable names.
+    /// * `env_prefix` - Prepended to every variable name by the export and
+    ///   dotenv formats; the JSON, YAML, and human formats key by tag and
+    ///   ignore it.
     #[must_use]
     pub fn create_formatter(
         &self,
         env_mappings: Option<HashMap<String, String>>,
+        env_prefix: Option<&str>,
     ) -> Box<dyn OutputFormatter> {
+        let env_prefix = env_prefix.unwrap_or_default();
         match self {
-            Self::Export(shell) => Box::new(ExportFormatter::new(*shell, env_mappings)),
+            Self::Export(shell) => {
+                Box::new(ExportFormatter::new(*shell, env_mappings).with_env_prefix(env_prefix))
+            }
             Self::Json => Box::new(JsonFormatter),
-            Self::Dotenv => Box::new(DotenvFormatter::new(env_mappings)),
+            Self::Yaml => Box::new(YamlFormatter),
+            Self::Dotenv { quote } => Box::new(
+                DotenvFormatter::new(env_mappings)
+                    .with_env_prefix(env_prefix)
+                    .with_quoting(*quote),
+            ),
             Self::Human => Box::new(HumanFormatter),
         }
     }
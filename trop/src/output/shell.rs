@@ -15,6 +15,8 @@ pub enum ShellType {
     Fish,
     /// `PowerShell`.
     PowerShell,
+    /// Nushell (nu).
+    Nushell,
 }
 
 impl ShellType {
@@ -23,9 +25,10 @@ impl ShellType {
     /// Detection precedence:
     /// 1. `ZSH_VERSION` - indicates zsh
     /// 2. `FISH_VERSION` - indicates fish
-    /// 3. `PSModulePath` - indicates `PowerShell`
-    /// 4. `SHELL` environment variable (path like `/bin/bash`)
-    /// 5. Default to bash if unable to determine
+    /// 3. `NU_VERSION` - indicates nushell
+    /// 4. `PSModulePath` - indicates `PowerShell`
+    /// 5. `SHELL` environment variable (path like `/bin/bash`)
+    /// 6. Default to bash if unable to determine
     ///
     /// # Errors
     ///
@@ -38,6 +41,10 @@ impl ShellType {
         if env::var("FISH_VERSION").is_ok() {
             return Ok(Self::Fish);
         }
+        // Checked before PSModulePath, which Windows sets for every process
+        if env::var("NU_VERSION").is_ok() {
+            return Ok(Self::Nushell);
+        }
         if env::var("PSModulePath").is_ok() {
             return Ok(Self::PowerShell);
         }
@@ -50,6 +57,12 @@ impl ShellType {
                 return Ok(Self::Fish);
             } else if shell_path.contains("pwsh") || shell_path.contains("powershell") {
                 return Ok(Self::PowerShell);
+            } else if shell_path.contains("nushell")
+                || std::path::Path::new(&shell_path)
+                    .file_stem()
+                    .is_some_and(|name| name == "nu")
+            {
+                return Ok(Self::Nushell);
             }
             // Default to bash for other shells (sh, bash, etc.)
             return Ok(Self::Bash);
@@ -63,7 +76,8 @@ impl ShellType {
     ///
     /// # Arguments
     ///
-    /// * `s` - Shell name (case-insensitive): "bash", "zsh", "fish", "powershell", "pwsh"
+    /// * `s` - Shell name (case-insensitive): "bash", "zsh", "fish", "powershell", "pwsh",
+    ///   "nu", "nushell"
     ///
     /// # Errors
     ///
@@ -74,10 +88,11 @@ impl ShellType {
             "zsh" => Ok(Self::Zsh),
             "fish" => Ok(Self::Fish),
             "powershell" | "pwsh" | "ps" => Ok(Self::PowerShell),
+            "nu" | "nushell" => Ok(Self::Nushell),
             _ => Err(crate::Error::Validation {
                 field: "shell".to_string(),
                 message: format!(
-                    "unknown shell type '{s}': supported shells are bash, zsh, fish, powershell"
+                    "unknown shell type '{s}': supported shells are bash, zsh, fish, powershell, nu"
                 ),
             }),
         }
@@ -98,6 +113,7 @@ impl ShellType {
     /// assert_eq!(ShellType::Bash.format_export("PORT", "5000"), "export PORT=5000");
     /// assert_eq!(ShellType::Fish.format_export("PORT", "5000"), "set -x PORT 5000");
     /// assert_eq!(ShellType::PowerShell.format_export("PORT", "5000"), "$env:PORT=\"5000\"");
+    /// assert_eq!(ShellType::Nushell.format_export("PORT", "5000"), "$env.PORT = \"5000\"");
     /// ```
     #[must_use]
     pub fn format_export(&self, var: &str, value: &str) -> String {
@@ -105,6 +121,7 @@ impl ShellType {
             Self::Bash | Self::Zsh => format!("export {var}={value}"),
             Self::Fish => format!("set -x {var} {value}"),
             Self::PowerShell => format!("$env:{var}=\"{value}\""),
+            Self::Nushell => format!("$env.{var} = \"{value}\""),
         }
     }
 }
@@ -128,6 +145,11 @@ mod tests {
             ShellType::from_string("pwsh").unwrap(),
             ShellType::PowerShell
         );
+        assert_eq!(ShellType::from_string("nu").unwrap(), ShellType::Nushell);
+        assert_eq!(
+            ShellType::from_string("nushell").unwrap(),
+            ShellType::Nushell
+        );
 
         // Unknown shell should error
         assert!(ShellType::from_string("unknown").is_err());
@@ -169,6 +191,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_export_nushell() {
+        let shell = ShellType::Nushell;
+        assert_eq!(shell.format_export("PORT", "5000"), "$env.PORT = \"5000\"");
+        assert_eq!(
+            shell.format_export("WEB_PORT", "8080"),
+            "$env.WEB_PORT = \"8080\""
+        );
+    }
+
     #[test]
     fn test_detect_defaults_to_bash() {
         // When no shell-specific variables are set, should default to bash
@@ -178,7 +210,11 @@ mod tests {
         // Just verify it returns successfully
         assert!(matches!(
             detected,
-            ShellType::Bash | ShellType::Zsh | ShellType::Fish | ShellType::PowerShell
+            ShellType::Bash
+                | ShellType::Zsh
+                | ShellType::Fish
+                | ShellType::PowerShell
+                | ShellType::Nushell
         ));
     }
 
@@ -518,16 +554,17 @@ mod tests {
     /// If new shells are added, this test will need updating.
     #[test]
     fn test_shell_type_variants() {
-        // We support exactly 4 shell types
+        // We support exactly 5 shell types
         let shells = [
             ShellType::Bash,
             ShellType::Zsh,
             ShellType::Fish,
             ShellType::PowerShell,
+            ShellType::Nushell,
         ];
 
         // Verify all are distinct
-        assert_eq!(shells.len(), 4);
+        assert_eq!(shells.len(), 5);
         for i in 0..shells.len() {
             for j in (i + 1)..shells.len() {
                 assert_ne!(shells[i], shells[j]);
@@ -2,6 +2,7 @@
 //!
 //! This module provides functionality to normalize paths by:
 //! - Expanding tilde (~) to the home directory
+//! - Expanding `$VAR` and `${VAR}` environment variable references
 //! - Converting relative paths to absolute paths
 //! - Resolving `.` and `..` components
 //! - Normalizing path separators for the platform
@@ -70,6 +71,103 @@ pub fn expand_tilde(path: &Path) -> Result<PathBuf> {
     }
 }
 
+/// Expand `$VAR` and `${VAR}` environment variable references in a path.
+///
+/// Variable names are ASCII letters, digits, and underscores. A `$` that is
+/// not followed by a name is kept as is.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The path contains invalid UTF-8
+/// - A referenced variable is not set
+/// - A `${` reference is not closed
+///
+/// # Examples
+///
+/// ```
+/// use trop::path::normalize::expand_env_vars;
+/// use std::path::Path;
+///
+/// std::env::set_var("TROP_DOC_ROOT", "/srv");
+/// let expanded = expand_env_vars(Path::new("$TROP_DOC_ROOT/trop")).unwrap();
+/// assert_eq!(expanded, Path::new("/srv/trop"));
+///
+/// let expanded = expand_env_vars(Path::new("${TROP_DOC_ROOT}-data")).unwrap();
+/// assert_eq!(expanded, Path::new("/srv-data"));
+/// ```
+pub fn expand_env_vars(path: &Path) -> Result<PathBuf> {
+    let path_str = path.to_str().ok_or_else(|| Error::InvalidPath {
+        path: path.to_path_buf(),
+        reason: "Path contains invalid UTF-8".to_string(),
+    })?;
+
+    if !path_str.contains('$') {
+        return Ok(path.to_path_buf());
+    }
+
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut expanded = String::with_capacity(path_str.len());
+    let mut rest = path_str;
+    while let Some(index) = rest.find('$') {
+        expanded.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+
+        let (name, remainder) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced.find('}').ok_or_else(|| Error::InvalidPath {
+                path: path.to_path_buf(),
+                reason: "Unclosed ${ in environment variable reference".to_string(),
+            })?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = after.find(|c| !is_name_char(c)).unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+
+        if name.is_empty() || !name.chars().all(is_name_char) {
+            // Not a variable reference; keep the `$` literally
+            expanded.push('$');
+            rest = after;
+            continue;
+        }
+
+        let value = env::var(name).map_err(|_| Error::InvalidPath {
+            path: path.to_path_buf(),
+            reason: format!("Environment variable {name} is not set"),
+        })?;
+        expanded.push_str(&value);
+        rest = remainder;
+    }
+    expanded.push_str(rest);
+
+    Ok(PathBuf::from(expanded))
+}
+
+/// Expand environment variable references and then a leading tilde.
+///
+/// This is the expansion applied to path-valued settings, such as the data
+/// directory, before they are used. Unlike [`normalize`], it does not make
+/// the path absolute.
+///
+/// # Errors
+///
+/// Returns an error if either expansion fails (see [`expand_env_vars`] and
+/// [`expand_tilde`]).
+///
+/// # Examples
+///
+/// ```
+/// use trop::path::normalize::expand_path;
+/// use std::path::Path;
+///
+/// let expanded = expand_path(Path::new("~/.trop")).unwrap();
+/// assert!(expanded.is_absolute());
+/// assert!(expanded.ends_with(".trop"));
+/// ```
+pub fn expand_path(path: &Path) -> Result<PathBuf> {
+    expand_tilde(&expand_env_vars(path)?)
+}
+
 /// Resolve `.` and `..` components in an absolute path.
 ///
 /// This function processes path components to remove current directory (`.`)
@@ -217,6 +315,40 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_expand_path_tilde() {
+        let home = home::home_dir().unwrap();
+        assert_eq!(expand_path(Path::new("~/foo")).unwrap(), home.join("foo"));
+    }
+
+    #[test]
+    fn test_expand_path_home_variable() {
+        let home = PathBuf::from(env::var("HOME").unwrap());
+        assert_eq!(
+            expand_path(Path::new("$HOME/foo")).unwrap(),
+            home.join("foo")
+        );
+        assert_eq!(
+            expand_path(Path::new("${HOME}/foo")).unwrap(),
+            home.join("foo")
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars_literal_dollar_kept() {
+        assert_eq!(
+            expand_env_vars(Path::new("/data/$/x$")).unwrap(),
+            PathBuf::from("/data/$/x$")
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars_unset_variable_fails() {
+        let result = expand_env_vars(Path::new("$TROP_TEST_SURELY_UNSET_VAR/foo"));
+        assert!(result.is_err());
+        assert!(expand_env_vars(Path::new("${HOME/foo")).is_err());
+    }
+
     #[test]
     fn test_resolve_components_simple() {
         let resolved = resolve_components(Path::new("/a/./b/../c")).unwrap();
@@ -189,6 +189,43 @@ impl PathRelationship {
         matches!(rel, Self::Ancestor | Self::Same)
     }
 
+    /// Express `path` relative to `base`, if it lies within `base`.
+    ///
+    /// Returns `.` when the paths are the same and `None` when `path` is
+    /// not beneath `base`. Both paths should already be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::path::PathRelationship;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let base = Path::new("/home/user");
+    ///
+    /// assert_eq!(
+    ///     PathRelationship::relative_to(Path::new("/home/user/project"), base),
+    ///     Some(PathBuf::from("project"))
+    /// );
+    /// assert_eq!(
+    ///     PathRelationship::relative_to(base, base),
+    ///     Some(PathBuf::from("."))
+    /// );
+    /// assert_eq!(PathRelationship::relative_to(Path::new("/srv"), base), None);
+    /// ```
+    #[must_use]
+    pub fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+        let path = normalize_for_comparison(path);
+        let relative = path
+            .strip_prefix(normalize_for_comparison(base))
+            .ok()?
+            .to_path_buf();
+        if relative.as_os_str().is_empty() {
+            Some(PathBuf::from("."))
+        } else {
+            Some(relative)
+        }
+    }
+
     /// Get a human-readable description of the relationship.
     ///
     /// # Examples
@@ -324,6 +361,26 @@ mod tests {
         assert!(!PathRelationship::Unrelated.is_allowed_without_force());
     }
 
+    #[test]
+    fn test_relative_to() {
+        assert_eq!(
+            PathRelationship::relative_to(Path::new("/a/b/c"), Path::new("/a")),
+            Some(PathBuf::from("b/c"))
+        );
+        assert_eq!(
+            PathRelationship::relative_to(Path::new("/a/"), Path::new("/a")),
+            Some(PathBuf::from("."))
+        );
+        assert_eq!(
+            PathRelationship::relative_to(Path::new("/ab"), Path::new("/a")),
+            None
+        );
+        assert_eq!(
+            PathRelationship::relative_to(Path::new("/a"), Path::new("/a/b")),
+            None
+        );
+    }
+
     #[test]
     fn test_is_within() {
         assert!(PathRelationship::is_within(
@@ -7,6 +7,7 @@ pub mod allocator;
 pub mod exclusions;
 pub mod group;
 pub mod occupancy;
+pub mod owner;
 
 // Property-based tests
 #[cfg(all(test, feature = "property-tests"))]
@@ -255,6 +256,30 @@ impl PortRange {
         port.value() >= self.min.value() && port.value() <= self.max.value()
     }
 
+    /// Returns `true` if the two ranges share at least one port.
+    ///
+    /// Both ranges are inclusive, so ranges that merely touch at a shared
+    /// endpoint overlap, while adjacent ranges (one ending at `n`, the other
+    /// starting at `n + 1`) do not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::{Port, PortRange};
+    ///
+    /// let range = |min, max| {
+    ///     PortRange::new(Port::try_from(min).unwrap(), Port::try_from(max).unwrap()).unwrap()
+    /// };
+    ///
+    /// assert!(range(5000, 5010).overlaps(&range(5010, 5020)));
+    /// assert!(range(5000, 5010).overlaps(&range(5003, 5004)));
+    /// assert!(!range(5000, 5010).overlaps(&range(5011, 5020)));
+    /// ```
+    #[must_use]
+    pub const fn overlaps(&self, other: &Self) -> bool {
+        self.min.value() <= other.max.value() && other.min.value() <= self.max.value()
+    }
+
     /// Returns the number of ports in the range (inclusive).
     ///
     /// # Examples
@@ -283,6 +308,9 @@ impl PortRange {
 
     /// Returns an iterator over all ports in this range.
     ///
+    /// Both `min` and `max` are included, so the iterator yields exactly
+    /// [`len`](Self::len) ports.
+    ///
     /// # Examples
     ///
     /// ```
@@ -299,9 +327,9 @@ impl PortRange {
     /// assert_eq!(ports[2].value(), 5002);
     /// ```
     #[must_use]
-    pub fn iter(self) -> PortRangeIter {
+    pub fn iter(&self) -> PortRangeIter {
         PortRangeIter {
-            range: self,
+            range: *self,
             current: u32::from(self.min.value()),
         }
     }
@@ -322,6 +350,15 @@ impl IntoIterator for PortRange {
     }
 }
 
+impl IntoIterator for &PortRange {
+    type Item = Port;
+    type IntoIter = PortRangeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 /// Iterator over ports in a `PortRange`.
 #[derive(Debug)]
 pub struct PortRangeIter {
@@ -502,6 +539,49 @@ mod tests {
         assert!(!range.contains(Port::try_from(5011).unwrap()));
     }
 
+    fn range(min: u16, max: u16) -> PortRange {
+        PortRange::new(Port::try_from(min).unwrap(), Port::try_from(max).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_port_range_contains_at_port_space_bounds() {
+        let full = range(Port::MIN, Port::MAX);
+        assert!(full.contains(Port::try_from(Port::MIN).unwrap()));
+        assert!(full.contains(Port::try_from(Port::MAX).unwrap()));
+
+        let single = range(Port::MAX, Port::MAX);
+        assert!(single.contains(Port::try_from(Port::MAX).unwrap()));
+        assert!(!single.contains(Port::try_from(Port::MAX - 1).unwrap()));
+    }
+
+    #[test]
+    fn test_port_range_overlaps() {
+        let base = range(5000, 5010);
+
+        // Partial overlap from either side
+        assert!(base.overlaps(&range(4990, 5000)));
+        assert!(base.overlaps(&range(5010, 5020)));
+        assert!(base.overlaps(&range(5005, 5015)));
+
+        // Containment in both directions, and identity
+        assert!(base.overlaps(&range(5003, 5004)));
+        assert!(base.overlaps(&range(4000, 6000)));
+        assert!(base.overlaps(&base));
+    }
+
+    #[test]
+    fn test_port_range_adjacent_and_disjoint_do_not_overlap() {
+        let base = range(5000, 5010);
+
+        // Adjacent on either side
+        assert!(!base.overlaps(&range(4990, 4999)));
+        assert!(!base.overlaps(&range(5011, 5020)));
+
+        // Disjoint
+        assert!(!base.overlaps(&range(6000, 6010)));
+        assert!(!range(6000, 6010).overlaps(&base));
+    }
+
     #[test]
     fn test_port_range_len() {
         let min = Port::try_from(5000).unwrap();
@@ -559,6 +639,37 @@ mod tests {
         assert_eq!(ports.len(), 3);
     }
 
+    #[test]
+    fn test_port_range_ref_into_iter() {
+        let min = Port::try_from(5000).unwrap();
+        let max = Port::try_from(5002).unwrap();
+        let range = PortRange::new(min, max).unwrap();
+
+        let mut ports = Vec::new();
+        for port in &range {
+            ports.push(port.value());
+        }
+        assert_eq!(ports, vec![5000, 5001, 5002]);
+    }
+
+    #[test]
+    fn test_port_range_iterator_single_port() {
+        let port = Port::try_from(5000).unwrap();
+        let range = PortRange::new(port, port).unwrap();
+
+        let ports: Vec<Port> = range.iter().collect();
+        assert_eq!(ports, vec![port]);
+    }
+
+    #[test]
+    fn test_port_range_iterator_count_matches_len() {
+        for (min, max) in [(1, 1), (1, 100), (5000, 7000), (60000, 65535)] {
+            let range =
+                PortRange::new(Port::try_from(min).unwrap(), Port::try_from(max).unwrap()).unwrap();
+            assert_eq!(range.iter().count(), usize::from(range.len()));
+        }
+    }
+
     #[test]
     fn test_port_range_large() {
         let min = Port::try_from(1).unwrap();
@@ -4,10 +4,12 @@
 //! available ports while respecting database reservations, exclusions, and
 //! system occupancy.
 
+use std::fmt;
+
 use rusqlite::Connection;
 
 use crate::database::Database;
-use crate::error::{Error, PortUnavailableReason};
+use crate::error::PortUnavailableReason;
 use crate::{Port, PortRange, Result};
 
 use super::exclusions::ExclusionManager;
@@ -76,6 +78,57 @@ pub enum AllocationResult {
     },
 }
 
+/// Breakdown of why ports scanned during a failed allocation were unusable.
+///
+/// Each port is counted once, under the first check that rejected it
+/// (reserved, then excluded, then occupied).
+///
+/// # Examples
+///
+/// ```
+/// use trop::port::allocator::RangeUsage;
+///
+/// let usage = RangeUsage { reserved: 3, excluded: 1, occupied: 2, vetoed: 0 };
+/// assert_eq!(usage.to_string(), "3 reserved, 1 excluded, 2 occupied");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RangeUsage {
+    /// Ports already reserved in the database.
+    pub reserved: usize,
+    /// Ports in the exclusion list.
+    pub excluded: usize,
+    /// Ports occupied on the system.
+    pub occupied: usize,
+    /// Ports rejected by the pre-reserve hook.
+    pub vetoed: usize,
+}
+
+impl RangeUsage {
+    /// Counts one scanned port by its availability.
+    fn record(&mut self, availability: PortAvailability) {
+        match availability {
+            PortAvailability::Available => {}
+            PortAvailability::Reserved => self.reserved += 1,
+            PortAvailability::Excluded => self.excluded += 1,
+            PortAvailability::Occupied => self.occupied += 1,
+        }
+    }
+}
+
+impl fmt::Display for RangeUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} reserved, {} excluded, {} occupied",
+            self.reserved, self.excluded, self.occupied
+        )?;
+        if self.vetoed > 0 {
+            write!(f, ", {} vetoed by hook", self.vetoed)?;
+        }
+        Ok(())
+    }
+}
+
 /// Stateless port allocator.
 ///
 /// The `PortAllocator` finds available ports by checking against database
@@ -217,7 +270,7 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
             if !self.range.contains(preferred) {
                 return Ok(AllocationResult::PreferredUnavailable {
                     port: preferred,
-                    reason: PortUnavailableReason::Excluded,
+                    reason: PortUnavailableReason::OutOfRange,
                 });
             }
 
@@ -278,15 +331,56 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
         start: Port,
         conn: &Connection,
         occupancy_config: &OccupancyCheckConfig,
+    ) -> Result<Option<Port>> {
+        self.find_next_available_with_usage(
+            start,
+            conn,
+            occupancy_config,
+            &mut RangeUsage::default(),
+        )
+    }
+
+    /// Like [`find_next_available`](Self::find_next_available), but also
+    /// tallies why each skipped port was unavailable into `usage`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database queries or occupancy checks fail.
+    pub fn find_next_available_with_usage(
+        &self,
+        start: Port,
+        conn: &Connection,
+        occupancy_config: &OccupancyCheckConfig,
+        usage: &mut RangeUsage,
     ) -> Result<Option<Port>> {
         // Scan from start to range max
         let scan_range = PortRange::new(start, self.range.max())?;
 
-        for port in scan_range {
-            if let PortAvailability::Available =
-                self.is_port_available(port, conn, occupancy_config)?
-            {
-                return Ok(Some(port));
+        self.find_first_available_with_usage(scan_range, conn, occupancy_config, usage)
+    }
+
+    /// Find the first available port among `candidates`, in the order given.
+    ///
+    /// This is the building block for allocation strategies that visit the
+    /// range in a different order than a forward scan. Candidates outside
+    /// the range count as excluded. Like
+    /// [`find_next_available_with_usage`](Self::find_next_available_with_usage),
+    /// each skipped port is tallied into `usage`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database queries or occupancy checks fail.
+    pub fn find_first_available_with_usage(
+        &self,
+        candidates: impl IntoIterator<Item = Port>,
+        conn: &Connection,
+        occupancy_config: &OccupancyCheckConfig,
+        usage: &mut RangeUsage,
+    ) -> Result<Option<Port>> {
+        for port in candidates {
+            match self.is_port_available(port, conn, occupancy_config)? {
+                PortAvailability::Available => return Ok(Some(port)),
+                unavailable => usage.record(unavailable),
             }
         }
 
@@ -305,7 +399,7 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
     ///
     /// # Errors
     ///
-    /// Returns an error if occupancy checks fail.
+    /// Returns an error if an occupancy check fails and the error policy is `fail`.
     pub fn find_next_allocatable(
         &self,
         start: Port,
@@ -326,12 +420,9 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
             }
 
             // Check if occupied on system
-            // Fail-closed policy: if the occupancy check itself fails (e.g., permission errors),
-            // we conservatively treat the port as occupied.
-            let occupied = self
-                .checker
-                .is_occupied(port, occupancy_config)
-                .unwrap_or(true);
+            // A failed check is resolved by the configured `on_error` policy
+            // (treat-as-occupied by default).
+            let occupied = self.checker.check_occupied(port, occupancy_config)?;
 
             if !occupied {
                 return Ok(Some(port));
@@ -374,13 +465,9 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
         }
 
         // Check if occupied on system
-        // Fail-closed policy: if the occupancy check itself fails (e.g., permission errors),
-        // we conservatively treat the port as occupied to avoid allocating ports that might
-        // be in use.
-        let occupied = self
-            .checker
-            .is_occupied(port, occupancy_config)
-            .unwrap_or(true);
+        // A failed check is resolved by the configured `on_error` policy
+        // (treat-as-occupied by default).
+        let occupied = self.checker.check_occupied(port, occupancy_config)?;
 
         if occupied {
             Ok(PortAvailability::Occupied)
@@ -417,33 +504,7 @@ pub fn allocator_from_config(
     config: &crate::config::Config,
 ) -> Result<PortAllocator<SystemOccupancyChecker>> {
     // Extract port range from config
-    let port_config = config.ports.as_ref().ok_or_else(|| Error::Validation {
-        field: "ports".into(),
-        message: "Port configuration is required".into(),
-    })?;
-
-    let min = Port::try_from(port_config.min)?;
-    let max_value = if let Some(max) = port_config.max {
-        max
-    } else if let Some(offset) = port_config.max_offset {
-        port_config
-            .min
-            .checked_add(offset)
-            .ok_or_else(|| Error::Validation {
-                field: "ports.max_offset".into(),
-                message: format!(
-                    "Offset {offset} would overflow when added to min port {}",
-                    port_config.min
-                ),
-            })?
-    } else {
-        return Err(Error::Validation {
-            field: "ports".into(),
-            message: "Either max or max_offset must be specified".into(),
-        });
-    };
-    let max = Port::try_from(max_value)?;
-    let range = PortRange::new(min, max)?;
+    let range = config.effective_port_range()?;
 
     // Create exclusion manager
     let exclusions = if let Some(ref excluded_ports) = config.excluded_ports {
@@ -461,7 +522,9 @@ pub fn allocator_from_config(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::OccupancyErrorPolicy;
     use crate::database::test_util::create_test_database;
+    use crate::error::Error;
     use crate::port::occupancy::MockOccupancyChecker;
     use crate::reservation::{Reservation, ReservationKey};
     use std::collections::HashSet;
@@ -663,6 +726,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_next_available_with_usage_counts_reasons() {
+        let mut db = create_test_database();
+        let occupied: HashSet<Port> = [5003, 5004]
+            .into_iter()
+            .map(|p| Port::try_from(p).unwrap())
+            .collect();
+        let exclusions =
+            ExclusionManager::from_config(&[crate::config::PortExclusion::Single(5002)]).unwrap();
+        let allocator = create_test_allocator(occupied, exclusions, 5000, 5004);
+
+        for (i, port) in [5000, 5001].into_iter().enumerate() {
+            let key = ReservationKey::new(PathBuf::from(format!("/test/{i}")), None).unwrap();
+            let reservation = Reservation::builder(key, Port::try_from(port).unwrap())
+                .build()
+                .unwrap();
+            db.create_reservation(&reservation).unwrap();
+        }
+
+        let mut usage = RangeUsage::default();
+        let result = allocator
+            .find_next_available_with_usage(
+                Port::try_from(5000).unwrap(),
+                db.connection(),
+                &OccupancyCheckConfig::default(),
+                &mut usage,
+            )
+            .unwrap();
+
+        assert_eq!(result, None);
+        assert_eq!(
+            usage,
+            RangeUsage {
+                reserved: 2,
+                excluded: 1,
+                occupied: 2,
+                vetoed: 0,
+            }
+        );
+    }
+
+    fn create_erroring_allocator() -> PortAllocator<MockOccupancyChecker> {
+        let mut checker = MockOccupancyChecker::empty();
+        checker.mark_error(Port::try_from(5000).unwrap());
+        let range =
+            PortRange::new(Port::try_from(5000).unwrap(), Port::try_from(5010).unwrap()).unwrap();
+        PortAllocator::new(checker, ExclusionManager::empty(), range)
+    }
+
+    #[test]
+    fn test_occupancy_error_treat_as_occupied_skips_port() {
+        let db = create_test_database();
+        let allocator = create_erroring_allocator();
+        let config = OccupancyCheckConfig {
+            on_error: OccupancyErrorPolicy::TreatAsOccupied,
+            ..Default::default()
+        };
+
+        let result = allocator
+            .allocate_single(db.connection(), &AllocationOptions::default(), &config)
+            .unwrap();
+        assert_eq!(
+            result,
+            AllocationResult::Allocated(Port::try_from(5001).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_occupancy_error_treat_as_free_allocates_port() {
+        let db = create_test_database();
+        let allocator = create_erroring_allocator();
+        let config = OccupancyCheckConfig {
+            on_error: OccupancyErrorPolicy::TreatAsFree,
+            ..Default::default()
+        };
+
+        let result = allocator
+            .allocate_single(db.connection(), &AllocationOptions::default(), &config)
+            .unwrap();
+        assert_eq!(
+            result,
+            AllocationResult::Allocated(Port::try_from(5000).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_occupancy_error_fail_propagates_error() {
+        let db = create_test_database();
+        let allocator = create_erroring_allocator();
+        let config = OccupancyCheckConfig {
+            on_error: OccupancyErrorPolicy::Fail,
+            ..Default::default()
+        };
+
+        let result =
+            allocator.allocate_single(db.connection(), &AllocationOptions::default(), &config);
+        assert!(matches!(result, Err(Error::OccupancyCheckFailed { .. })));
+
+        let result = allocator.find_next_allocatable(Port::try_from(5000).unwrap(), &config);
+        assert!(matches!(result, Err(Error::OccupancyCheckFailed { .. })));
+    }
+
     #[test]
     fn test_find_next_available() {
         let db = create_test_database();
@@ -795,7 +960,7 @@ mod tests {
             result,
             AllocationResult::PreferredUnavailable {
                 port: Port::try_from(6000).unwrap(),
-                reason: PortUnavailableReason::Excluded,
+                reason: PortUnavailableReason::OutOfRange,
             }
         );
     }
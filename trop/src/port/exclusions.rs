@@ -70,15 +70,15 @@ impl ExclusionManager {
         let mut excluded = BTreeSet::new();
 
         for exclusion in exclusions {
-            match exclusion {
-                PortExclusion::Single(port) => {
-                    let port = Port::try_from(*port)?;
+            match exclusion.bounds() {
+                (port, end) if port == end => {
+                    let port = Port::try_from(port)?;
                     excluded.insert(port);
                 }
-                PortExclusion::Range { start, end } => {
+                (start, end) => {
                     // Validate ports
-                    let start_port = Port::try_from(*start)?;
-                    let end_port = Port::try_from(*end)?;
+                    let start_port = Port::try_from(start)?;
+                    let end_port = Port::try_from(end)?;
 
                     // Create range and add all ports
                     let range = PortRange::new(start_port, end_port)?;
@@ -224,12 +224,12 @@ impl ExclusionManager {
     /// manager.add_exclusion(&PortExclusion::Single(8080)).unwrap();
     /// ```
     pub fn add_exclusion(&mut self, exclusion: &PortExclusion) -> crate::Result<()> {
-        match *exclusion {
-            PortExclusion::Single(port) => {
+        match exclusion.bounds() {
+            (port, end) if port == end => {
                 let port = Port::try_from(port)?;
                 self.excluded.insert(port);
             }
-            PortExclusion::Range { start, end } => {
+            (start, end) => {
                 let start_port = Port::try_from(start)?;
                 let end_port = Port::try_from(end)?;
                 let range = PortRange::new(start_port, end_port)?;
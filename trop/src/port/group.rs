@@ -139,12 +139,7 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
     /// - No base port can be found for the offset pattern
     /// - Database operations fail
     /// - Preferred ports are unavailable
-    ///
-    /// # Panics
-    ///
-    /// Does not panic. The code uses `unwrap()` on `service.preferred` at line 253,
-    /// but this is safe because `preferred_services` is filtered to only contain
-    /// services where `preferred.is_some()`.
+    /// - A service already has a pinned reservation
     ///
     /// # Examples
     ///
@@ -197,24 +192,40 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
         request: &GroupAllocationRequest,
         occupancy_config: &OccupancyCheckConfig,
     ) -> Result<GroupAllocationResult> {
-        // Validate request
-        if request.services.is_empty() {
-            return Err(Error::Validation {
-                field: "services".into(),
-                message: "Group allocation requires at least one service".into(),
-            });
-        }
+        self.allocate_group_with_approval(conn, request, occupancy_config, |_, _| Ok(true))
+    }
 
-        // Check for duplicate tags
-        let mut seen_tags = std::collections::HashSet::new();
-        for service in &request.services {
-            if !seen_tags.insert(&service.tag) {
-                return Err(Error::Validation {
-                    field: "services".into(),
-                    message: format!("Duplicate service tag: {}", service.tag),
-                });
-            }
-        }
+    /// Allocate a group of related ports, asking `approve` about each one.
+    ///
+    /// Works like [`allocate_group`](Self::allocate_group), but every port
+    /// that is otherwise available is passed to `approve` together with the
+    /// key of the service that would get it (this is how the pre-reserve
+    /// hook is consulted). A rejected offset port moves the search on to the
+    /// next base port; a rejected preferred port fails the allocation.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`allocate_group`](Self::allocate_group),
+    /// a [`Error::GroupAllocationFailed`] if a preferred port is rejected,
+    /// and any error returned by `approve`.
+    ///
+    /// # Panics
+    ///
+    /// Does not panic. The code uses `unwrap()` on `service.preferred`, but
+    /// this is safe because `preferred_services` is filtered to only contain
+    /// services where `preferred.is_some()`.
+    pub fn allocate_group_with_approval(
+        &self,
+        conn: &rusqlite::Connection,
+        request: &GroupAllocationRequest,
+        occupancy_config: &OccupancyCheckConfig,
+        mut approve: impl FnMut(Port, &ReservationKey) -> Result<bool>,
+    ) -> Result<GroupAllocationResult> {
+        Self::validate_request(request)?;
+
+        // Allocating the group replaces any existing reservations for its
+        // services, which would move a pinned port
+        Self::ensure_services_unpinned(conn, request)?;
 
         // Separate services into those with preferred ports and those with offsets
         let (preferred_services, offset_services): (Vec<_>, Vec<_>) =
@@ -230,6 +241,11 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
             // This allows us to provide a specific error message if all services
             // without preferred ports are also missing offsets.
             let pattern: Vec<u16> = offset_services.iter().filter_map(|s| s.offset).collect();
+            let keys = offset_services
+                .iter()
+                .filter(|s| s.offset.is_some())
+                .map(|s| ReservationKey::new(request.base_path.clone(), Some(s.tag.clone())))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
 
             if pattern.is_empty() {
                 return Err(Error::Validation {
@@ -238,9 +254,11 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
                 });
             }
 
-            // Find a base port where all offsets are available
+            // Find a base port where all offsets are available and approved
             let base = self
-                .find_pattern_match(&pattern, conn, occupancy_config)?
+                .scan_for_pattern(&pattern, conn, occupancy_config, |index, port| {
+                    approve(port, &keys[index])
+                })?
                 .ok_or_else(|| Error::GroupAllocationFailed {
                     attempted: 0,
                     reason: "No base port found for offset pattern".into(),
@@ -267,7 +285,12 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
 
             match self.allocate_single(conn, &options, occupancy_config)? {
                 crate::port::allocator::AllocationResult::Allocated(_) => {
-                    // Good, port is available
+                    if !approve(port, &key)? {
+                        return Err(Error::GroupAllocationFailed {
+                            attempted: allocations.len(),
+                            reason: format!("Preferred port {port} was vetoed"),
+                        });
+                    }
                 }
                 crate::port::allocator::AllocationResult::PreferredUnavailable { port, reason } => {
                     return Err(Error::PreferredPortUnavailable { port, reason });
@@ -332,6 +355,43 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
         })
     }
 
+    /// Rejects a request with no services or with duplicate service tags.
+    fn validate_request(request: &GroupAllocationRequest) -> Result<()> {
+        if request.services.is_empty() {
+            return Err(Error::Validation {
+                field: "services".into(),
+                message: "Group allocation requires at least one service".into(),
+            });
+        }
+
+        // Check for duplicate tags
+        let mut seen_tags = std::collections::HashSet::new();
+        for service in &request.services {
+            if !seen_tags.insert(&service.tag) {
+                return Err(Error::Validation {
+                    field: "services".into(),
+                    message: format!("Duplicate service tag: {}", service.tag),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error if any service in the request already has a pinned
+    /// reservation.
+    fn ensure_services_unpinned(
+        conn: &rusqlite::Connection,
+        request: &GroupAllocationRequest,
+    ) -> Result<()> {
+        for service in &request.services {
+            let key = ReservationKey::new(request.base_path.clone(), Some(service.tag.clone()))?;
+            if let Some(existing) = Database::get_reservation(conn, &key)? {
+                existing.ensure_unpinned()?;
+            }
+        }
+        Ok(())
+    }
+
     /// Find a base port where all offsets in the pattern are available.
     ///
     /// This scans forward from the range minimum looking for a base port where
@@ -345,6 +405,21 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
         pattern: &[u16],
         conn: &rusqlite::Connection,
         occupancy_config: &OccupancyCheckConfig,
+    ) -> Result<Option<Port>> {
+        self.scan_for_pattern(pattern, conn, occupancy_config, |_, _| Ok(true))
+    }
+
+    /// Finds the first base port where every offset in the pattern is
+    /// available and accepted by `approve`.
+    ///
+    /// `approve` receives the index of the offset in `pattern` and the
+    /// port, and is only asked once all ports for a base are available.
+    fn scan_for_pattern(
+        &self,
+        pattern: &[u16],
+        conn: &rusqlite::Connection,
+        occupancy_config: &OccupancyCheckConfig,
+        mut approve: impl FnMut(usize, Port) -> Result<bool>,
     ) -> Result<Option<Port>> {
         if pattern.is_empty() {
             return Ok(None);
@@ -382,13 +457,32 @@ impl<C: PortOccupancyChecker> PortAllocator<C> {
                 }
             }
 
-            if all_available {
+            if all_available && Self::approve_pattern(base, pattern, &mut approve)? {
                 return Ok(Some(base));
             }
         }
 
         Ok(None)
     }
+
+    /// Asks `approve` about each port of the pattern at `base`, stopping at
+    /// the first rejection.
+    fn approve_pattern(
+        base: Port,
+        pattern: &[u16],
+        approve: &mut impl FnMut(usize, Port) -> Result<bool>,
+    ) -> Result<bool> {
+        for (index, &offset) in pattern.iter().enumerate() {
+            // The availability scan already ruled out overflowing offsets
+            let Some(port) = base.checked_add(offset) else {
+                return Ok(false);
+            };
+            if !approve(index, port)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
 
 #[cfg(test)]
@@ -482,6 +576,48 @@ mod tests {
         assert!(reservations.is_empty());
     }
 
+    #[test]
+    fn test_group_allocation_preserves_pinned_reservation() {
+        let mut db = create_test_database();
+        let allocator = create_test_allocator(HashSet::new(), 5000, 5100);
+
+        // An existing pinned reservation for one of the group's services
+        let key = ReservationKey::new(PathBuf::from("/test/project"), Some("web".into())).unwrap();
+        let pinned_port = Port::try_from(5050).unwrap();
+        let reservation = Reservation::builder(key.clone(), pinned_port)
+            .pinned(true)
+            .build()
+            .unwrap();
+        db.create_reservation(&reservation).unwrap();
+
+        let request = GroupAllocationRequest {
+            base_path: PathBuf::from("/test/project"),
+            project: None,
+            task: None,
+            services: vec![
+                ServiceAllocationRequest {
+                    tag: "web".to_string(),
+                    offset: Some(0),
+                    preferred: None,
+                },
+                ServiceAllocationRequest {
+                    tag: "api".to_string(),
+                    offset: Some(1),
+                    preferred: None,
+                },
+            ],
+        };
+
+        let config = OccupancyCheckConfig::default();
+        let result = allocator.allocate_group(db.connection(), &request, &config);
+        assert!(matches!(result, Err(Error::ReservationPinned { .. })));
+
+        // The pinned reservation keeps its port and nothing else was created
+        let reservations = Database::list_all_reservations(db.connection()).unwrap();
+        assert_eq!(reservations.len(), 1);
+        assert_eq!(reservations[0].port(), pinned_port);
+    }
+
     #[test]
     fn test_group_allocation_with_gaps() {
         let db = create_test_database();
@@ -564,6 +700,73 @@ mod tests {
         assert_eq!(web_port.value(), 5002);
     }
 
+    #[test]
+    fn test_group_allocation_with_approval_skips_rejected_base() {
+        let db = create_test_database();
+        let allocator = create_test_allocator(HashSet::new(), 5000, 5100);
+
+        let request = GroupAllocationRequest {
+            base_path: PathBuf::from("/test/project"),
+            project: None,
+            task: None,
+            services: vec![
+                ServiceAllocationRequest {
+                    tag: "web".to_string(),
+                    offset: Some(0),
+                    preferred: None,
+                },
+                ServiceAllocationRequest {
+                    tag: "api".to_string(),
+                    offset: Some(1),
+                    preferred: None,
+                },
+            ],
+        };
+
+        // Reject 5001 for the api service, so neither 5000 nor 5001 can be
+        // the base
+        let mut asked = Vec::new();
+        let config = OccupancyCheckConfig::default();
+        let result = allocator
+            .allocate_group_with_approval(db.connection(), &request, &config, |port, key| {
+                asked.push((port.value(), key.tag.clone().unwrap()));
+                Ok(port.value() != 5001)
+            })
+            .unwrap();
+
+        assert_eq!(result.base_port.unwrap().value(), 5002);
+        assert_eq!(result.allocations.get("web").unwrap().value(), 5002);
+        assert_eq!(result.allocations.get("api").unwrap().value(), 5003);
+        assert!(asked.contains(&(5001, "api".to_string())));
+    }
+
+    #[test]
+    fn test_group_allocation_with_approval_rejected_preferred_fails() {
+        let db = create_test_database();
+        let allocator = create_test_allocator(HashSet::new(), 5000, 5100);
+
+        let request = GroupAllocationRequest {
+            base_path: PathBuf::from("/test/project"),
+            project: None,
+            task: None,
+            services: vec![ServiceAllocationRequest {
+                tag: "web".to_string(),
+                offset: None,
+                preferred: Some(Port::try_from(5050).unwrap()),
+            }],
+        };
+
+        let config = OccupancyCheckConfig::default();
+        let result =
+            allocator
+                .allocate_group_with_approval(db.connection(), &request, &config, |_, _| Ok(false));
+
+        assert!(matches!(result, Err(Error::GroupAllocationFailed { .. })));
+        assert!(Database::list_all_reservations(db.connection())
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_group_allocation_with_preferred() {
         let db = create_test_database();
@@ -4,9 +4,14 @@
 //! are actually in use on the system. The design uses traits for testability,
 //! allowing both real system checks and mock implementations for testing.
 
-use std::collections::HashSet;
-
-use crate::config::OccupancyConfig;
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::net::{IpAddr, SocketAddr, TcpListener, UdpSocket};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::{Config, OccupancyConfig, OccupancyErrorPolicy};
+use crate::port::owner::find_port_owner;
 use crate::{Port, PortRange, Result};
 
 /// Configuration for a single occupancy check.
@@ -26,6 +31,13 @@ pub struct OccupancyCheckConfig {
     pub skip_ipv6: bool,
     /// Check all network interfaces (not just localhost).
     pub check_all_interfaces: bool,
+    /// Only look for bind conflicts on these addresses.
+    ///
+    /// Empty means the default wildcard check. Ignored when
+    /// `check_all_interfaces` is set.
+    pub interfaces: Vec<IpAddr>,
+    /// How to treat a port whose occupancy check fails.
+    pub on_error: OccupancyErrorPolicy,
 }
 
 impl From<&OccupancyConfig> for OccupancyCheckConfig {
@@ -41,10 +53,96 @@ impl From<&OccupancyConfig> for OccupancyCheckConfig {
             skip_ipv4: skip_all || config.skip_ip4.unwrap_or(false),
             skip_ipv6: skip_all || config.skip_ip6.unwrap_or(false),
             check_all_interfaces: config.check_all_interfaces.unwrap_or(false),
+            interfaces: config.interfaces.clone().unwrap_or_default(),
+            on_error: OccupancyErrorPolicy::default(),
+        }
+    }
+}
+
+impl OccupancyCheckConfig {
+    /// Returns true if this configuration disables every occupancy check.
+    ///
+    /// Checks are effectively skipped when both transport protocols or both
+    /// IP versions are skipped.
+    #[must_use]
+    pub fn skips_all(&self) -> bool {
+        (self.skip_tcp && self.skip_udp) || (self.skip_ipv4 && self.skip_ipv6)
+    }
+
+    /// Build the check configuration from a complete trop configuration.
+    ///
+    /// Combines the `occupancy_check` section with the top-level
+    /// `on_occupancy_error` policy.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self {
+        let mut check = config
+            .occupancy_check
+            .as_ref()
+            .map(Self::from)
+            .unwrap_or_default();
+        check.on_error = config.on_occupancy_error.unwrap_or_default();
+        check
+    }
+
+    /// Resolve a failed occupancy check according to the `on_error` policy.
+    ///
+    /// Returns `Ok(false)` for `treat-as-free`, `Ok(true)` for
+    /// `treat-as-occupied`, and the original error for `fail`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `error` unchanged when the policy is `fail`.
+    pub fn resolve_error(&self, error: crate::Error) -> Result<bool> {
+        match self.on_error {
+            OccupancyErrorPolicy::TreatAsFree => Ok(false),
+            OccupancyErrorPolicy::TreatAsOccupied => Ok(true),
+            OccupancyErrorPolicy::Fail => Err(error),
         }
     }
 }
 
+/// The occupancy of a single port, with its owner when known.
+///
+/// `pid` and `process` are only filled in when the owner was requested and
+/// could be resolved; otherwise this carries the same information as the
+/// boolean from [`PortOccupancyChecker::check_occupied`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Occupancy {
+    /// Whether the port is in use.
+    pub occupied: bool,
+    /// Process ID of the owner, if resolved.
+    pub pid: Option<u32>,
+    /// Executable or command name of the owner, if resolved.
+    pub process: Option<String>,
+}
+
+impl Occupancy {
+    /// Creates an occupancy result without owner information.
+    #[must_use]
+    pub const fn new(occupied: bool) -> Self {
+        Self {
+            occupied,
+            pid: None,
+            process: None,
+        }
+    }
+
+    /// Looks up the process holding `port` if it is occupied.
+    ///
+    /// A free port, or an owner the system tools cannot see, leaves the
+    /// owner fields unset.
+    #[must_use]
+    pub fn with_owner(mut self, port: Port) -> Self {
+        if self.occupied {
+            if let Some(owner) = find_port_owner(port) {
+                self.pid = Some(owner.pid);
+                self.process = owner.process_name;
+            }
+        }
+        self
+    }
+}
+
 /// Trait for checking port occupancy on the system.
 ///
 /// This trait abstracts port occupancy checking to enable both real system
@@ -78,14 +176,51 @@ pub trait PortOccupancyChecker: Send + Sync {
     /// or permission problems.
     fn is_occupied(&self, port: Port, config: &OccupancyCheckConfig) -> Result<bool>;
 
+    /// Check if a port is occupied, applying the configured error policy.
+    ///
+    /// Behaves like `is_occupied`, except that a failed check is resolved
+    /// according to `config.on_error` instead of always being returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the check fails and the policy is `fail`.
+    fn check_occupied(&self, port: Port, config: &OccupancyCheckConfig) -> Result<bool> {
+        self.is_occupied(port, config)
+            .or_else(|e| config.resolve_error(e))
+    }
+
+    /// Check a port's occupancy, optionally resolving its owning process.
+    ///
+    /// Applies the error policy like `check_occupied`. With `with_process`,
+    /// an occupied port is annotated with its owner on a best-effort basis;
+    /// failing to find one is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the check fails and the policy is `fail`.
+    fn check_occupancy(
+        &self,
+        port: Port,
+        config: &OccupancyCheckConfig,
+        with_process: bool,
+    ) -> Result<Occupancy> {
+        let occupancy = Occupancy::new(self.check_occupied(port, config)?);
+        Ok(if with_process {
+            occupancy.with_owner(port)
+        } else {
+            occupancy
+        })
+    }
+
     /// Find all occupied ports in a given range.
     ///
     /// This is an optimization opportunity - implementations may batch checks
-    /// for efficiency. The default implementation calls `is_occupied` for each port.
+    /// for efficiency. The default implementation calls `check_occupied` for
+    /// each port, so failed checks are resolved by the configured error policy.
     ///
     /// # Errors
     ///
-    /// Returns an error if any occupancy check fails.
+    /// Returns an error if any occupancy check fails and the policy is `fail`.
     fn find_occupied_ports(
         &self,
         range: &PortRange,
@@ -93,7 +228,7 @@ pub trait PortOccupancyChecker: Send + Sync {
     ) -> Result<Vec<Port>> {
         let mut occupied = Vec::new();
         for port in *range {
-            if self.is_occupied(port, config)? {
+            if self.check_occupied(port, config)? {
                 occupied.push(port);
             }
         }
@@ -124,21 +259,23 @@ pub struct SystemOccupancyChecker;
 impl PortOccupancyChecker for SystemOccupancyChecker {
     fn is_occupied(&self, port: Port, config: &OccupancyCheckConfig) -> Result<bool> {
         // If we're skipping all checks, the port is available
-        if config.skip_tcp && config.skip_udp {
+        if config.skips_all() {
             return Ok(false);
         }
-        if config.skip_ipv4 && config.skip_ipv6 {
-            return Ok(false);
+
+        // Binds on specific interfaces only conflict with binds on those
+        // same addresses, so check just the requested ones
+        if !config.check_all_interfaces && !config.interfaces.is_empty() {
+            return occupied_on_interfaces(port, config);
         }
 
         // Use port-selector to check availability
         // The port-selector crate's is_free checks if we can bind to the port
         let port_u16 = port.value();
 
-        // By default, port-selector checks localhost binding
-        // If the port is free, we can bind to it, so it's NOT occupied
-        // Note: `check_all_interfaces` is currently reserved for future use when we implement
-        // interface-specific binding checks. For now, we always check localhost.
+        // port-selector binds the wildcard addresses, which conflicts with a
+        // listener on any interface. If the port is free, we can bind to it,
+        // so it's NOT occupied
         let is_free = port_selector::is_free(port_u16);
 
         // Port is occupied if it's NOT free
@@ -146,6 +283,36 @@ impl PortOccupancyChecker for SystemOccupancyChecker {
     }
 }
 
+/// Checks for a bind conflict on any of `config.interfaces`.
+///
+/// Honors the protocol and IP-version skip flags.
+fn occupied_on_interfaces(port: Port, config: &OccupancyCheckConfig) -> Result<bool> {
+    for &ip in &config.interfaces {
+        if (ip.is_ipv4() && config.skip_ipv4) || (ip.is_ipv6() && config.skip_ipv6) {
+            continue;
+        }
+        let addr = SocketAddr::new(ip, port.value());
+        if !config.skip_tcp && bind_conflicts(TcpListener::bind(addr).map(drop))? {
+            return Ok(true);
+        }
+        if !config.skip_udp && bind_conflicts(UdpSocket::bind(addr).map(drop))? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Interprets the outcome of a test bind.
+fn bind_conflicts(result: std::io::Result<()>) -> Result<bool> {
+    match result {
+        Ok(()) => Ok(false),
+        Err(e) if e.kind() == ErrorKind::AddrInUse => Ok(true),
+        // Nothing can be listening on an address this host doesn't have
+        Err(e) if e.kind() == ErrorKind::AddrNotAvailable => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Mock implementation for testing with configurable occupied ports.
 ///
 /// This checker allows tests to specify exactly which ports should be
@@ -171,6 +338,7 @@ impl PortOccupancyChecker for SystemOccupancyChecker {
 #[derive(Debug, Clone)]
 pub struct MockOccupancyChecker {
     occupied_ports: HashSet<Port>,
+    error_ports: HashSet<Port>,
 }
 
 impl MockOccupancyChecker {
@@ -190,7 +358,10 @@ impl MockOccupancyChecker {
     /// ```
     #[must_use]
     pub fn new(occupied_ports: HashSet<Port>) -> Self {
-        Self { occupied_ports }
+        Self {
+            occupied_ports,
+            error_ports: HashSet::new(),
+        }
     }
 
     /// Create an empty mock checker (all ports available).
@@ -206,6 +377,7 @@ impl MockOccupancyChecker {
     pub fn empty() -> Self {
         Self {
             occupied_ports: HashSet::new(),
+            error_ports: HashSet::new(),
         }
     }
 
@@ -241,6 +413,23 @@ impl MockOccupancyChecker {
         self.occupied_ports.remove(&port);
     }
 
+    /// Make occupancy checks for a port fail with an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trop::port::occupancy::{MockOccupancyChecker, OccupancyCheckConfig, PortOccupancyChecker};
+    /// use trop::Port;
+    ///
+    /// let mut checker = MockOccupancyChecker::empty();
+    /// let port = Port::try_from(8080).unwrap();
+    /// checker.mark_error(port);
+    /// assert!(checker.is_occupied(port, &OccupancyCheckConfig::default()).is_err());
+    /// ```
+    pub fn mark_error(&mut self, port: Port) {
+        self.error_ports.insert(port);
+    }
+
     /// Get the set of occupied ports.
     #[must_use]
     pub fn occupied_ports(&self) -> &HashSet<Port> {
@@ -250,9 +439,15 @@ impl MockOccupancyChecker {
 
 impl PortOccupancyChecker for MockOccupancyChecker {
     fn is_occupied(&self, port: Port, config: &OccupancyCheckConfig) -> Result<bool> {
-        if (config.skip_tcp && config.skip_udp) || (config.skip_ipv4 && config.skip_ipv6) {
+        if config.skips_all() {
             return Ok(false);
         }
+        if self.error_ports.contains(&port) {
+            return Err(crate::Error::OccupancyCheckFailed {
+                port,
+                source: "mock occupancy check failure".into(),
+            });
+        }
         Ok(self.occupied_ports.contains(&port))
     }
 
@@ -261,13 +456,13 @@ impl PortOccupancyChecker for MockOccupancyChecker {
         range: &PortRange,
         config: &OccupancyCheckConfig,
     ) -> Result<Vec<Port>> {
-        if (config.skip_tcp && config.skip_udp) || (config.skip_ipv4 && config.skip_ipv6) {
+        if config.skips_all() {
             return Ok(Vec::new());
         }
 
         let mut occupied = Vec::new();
         for port in *range {
-            if self.occupied_ports.contains(&port) {
+            if self.check_occupied(port, config)? {
                 occupied.push(port);
             }
         }
@@ -275,6 +470,90 @@ impl PortOccupancyChecker for MockOccupancyChecker {
     }
 }
 
+/// An occupancy checker that remembers recent results for a while.
+///
+/// Wraps another checker so that repeated scans within one process (such as
+/// `scan --watch`) don't re-probe a port checked less than `ttl` ago. Only
+/// successful probes are cached, and the cache assumes every lookup uses the
+/// same [`OccupancyCheckConfig`]. A zero TTL disables caching.
+///
+/// A cached result can go stale within the TTL, so callers must
+/// [`forget`](Self::forget) ports whose state they know has changed, such
+/// as ports that were reserved since they were probed.
+///
+/// # Examples
+///
+/// ```
+/// use trop::port::occupancy::{
+///     MockOccupancyChecker, OccupancyCache, OccupancyCheckConfig, PortOccupancyChecker,
+/// };
+/// use trop::Port;
+/// use std::time::Duration;
+///
+/// let cache = OccupancyCache::new(MockOccupancyChecker::empty(), Duration::from_secs(30));
+/// let port = Port::try_from(8080).unwrap();
+///
+/// assert!(!cache.is_occupied(port, &OccupancyCheckConfig::default()).unwrap());
+/// cache.forget(port);
+/// ```
+pub struct OccupancyCache<C> {
+    inner: C,
+    ttl: Duration,
+    clock: Box<dyn Fn() -> Instant + Send + Sync>,
+    entries: Mutex<HashMap<Port, (bool, Instant)>>,
+}
+
+impl<C: PortOccupancyChecker> OccupancyCache<C> {
+    /// Creates a cache around `inner` whose entries live for `ttl`.
+    #[must_use]
+    pub fn new(inner: C, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            clock: Box::new(Instant::now),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replaces the clock used to age entries (for tests).
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Fn() -> Instant + Send + Sync + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Drops any cached result for `port`, so the next lookup probes it.
+    pub fn forget(&self, port: Port) {
+        self.lock_entries().remove(&port);
+    }
+
+    fn lock_entries(&self) -> std::sync::MutexGuard<'_, HashMap<Port, (bool, Instant)>> {
+        // The map holds plain values, so a panic mid-update can't corrupt it
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<C: PortOccupancyChecker> PortOccupancyChecker for OccupancyCache<C> {
+    fn is_occupied(&self, port: Port, config: &OccupancyCheckConfig) -> Result<bool> {
+        if self.ttl.is_zero() {
+            return self.inner.is_occupied(port, config);
+        }
+
+        let now = (self.clock)();
+        if let Some(&(occupied, probed_at)) = self.lock_entries().get(&port) {
+            if now.saturating_duration_since(probed_at) < self.ttl {
+                return Ok(occupied);
+            }
+        }
+
+        let occupied = self.inner.is_occupied(port, config)?;
+        self.lock_entries().insert(port, (occupied, now));
+        Ok(occupied)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,6 +692,102 @@ mod tests {
         assert!(occupied_in_range.contains(&Port::try_from(5009).unwrap()));
     }
 
+    #[test]
+    fn test_occupancy_check_config_from_config() {
+        let config = Config {
+            occupancy_check: Some(OccupancyConfig {
+                skip_udp: Some(true),
+                ..Default::default()
+            }),
+            on_occupancy_error: Some(OccupancyErrorPolicy::Fail),
+            ..Default::default()
+        };
+
+        let check = OccupancyCheckConfig::from_config(&config);
+        assert!(check.skip_udp);
+        assert_eq!(check.on_error, OccupancyErrorPolicy::Fail);
+
+        let check = OccupancyCheckConfig::from_config(&Config::default());
+        assert_eq!(check.on_error, OccupancyErrorPolicy::TreatAsOccupied);
+    }
+
+    #[test]
+    fn test_check_occupied_applies_error_policy() {
+        let mut checker = MockOccupancyChecker::empty();
+        let port = Port::try_from(8080).unwrap();
+        checker.mark_error(port);
+
+        let with_policy = |on_error| OccupancyCheckConfig {
+            on_error,
+            ..Default::default()
+        };
+
+        assert!(checker
+            .check_occupied(port, &with_policy(OccupancyErrorPolicy::TreatAsOccupied))
+            .unwrap());
+        assert!(!checker
+            .check_occupied(port, &with_policy(OccupancyErrorPolicy::TreatAsFree))
+            .unwrap());
+        assert!(checker
+            .check_occupied(port, &with_policy(OccupancyErrorPolicy::Fail))
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_occupancy() {
+        let mut checker = MockOccupancyChecker::empty();
+        let occupied = Port::try_from(8080).unwrap();
+        let free = Port::try_from(8081).unwrap();
+        checker.mark_occupied(occupied);
+        let config = OccupancyCheckConfig::default();
+
+        assert_eq!(
+            checker.check_occupancy(occupied, &config, false).unwrap(),
+            Occupancy::new(true)
+        );
+        // Free ports never trigger an owner lookup
+        assert_eq!(
+            checker.check_occupancy(free, &config, true).unwrap(),
+            Occupancy::new(false)
+        );
+        // Owner lookup goes to the real system, so only occupancy is certain
+        assert!(
+            checker
+                .check_occupancy(occupied, &config, true)
+                .unwrap()
+                .occupied
+        );
+    }
+
+    #[test]
+    fn test_find_occupied_ports_applies_error_policy() {
+        let mut checker = MockOccupancyChecker::empty();
+        checker.mark_occupied(Port::try_from(5001).unwrap());
+        checker.mark_error(Port::try_from(5003).unwrap());
+        let range =
+            PortRange::new(Port::try_from(5000).unwrap(), Port::try_from(5005).unwrap()).unwrap();
+
+        let occupied = |on_error| {
+            checker.find_occupied_ports(
+                &range,
+                &OccupancyCheckConfig {
+                    on_error,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            occupied(OccupancyErrorPolicy::TreatAsOccupied).unwrap(),
+            vec![Port::try_from(5001).unwrap(), Port::try_from(5003).unwrap()]
+        );
+        assert_eq!(
+            occupied(OccupancyErrorPolicy::TreatAsFree).unwrap(),
+            vec![Port::try_from(5001).unwrap()]
+        );
+        assert!(occupied(OccupancyErrorPolicy::Fail).is_err());
+    }
+
     #[test]
     fn test_system_checker_skip_all_tcp_udp() {
         let checker = SystemOccupancyChecker;
@@ -452,6 +827,7 @@ mod tests {
             skip_ip4: Some(true),
             skip_ip6: Some(true),
             check_all_interfaces: Some(true),
+            interfaces: None,
         };
 
         let config = OccupancyCheckConfig::from(&occ_config);
@@ -473,6 +849,7 @@ mod tests {
             skip_ip4: None,
             skip_ip6: None,
             check_all_interfaces: None,
+            interfaces: None,
         };
 
         let config = OccupancyCheckConfig::from(&occ_config);
@@ -613,4 +990,119 @@ mod tests {
         let result2 = checker.is_occupied(port, &config2);
         assert!(result2.is_ok());
     }
+
+    #[test]
+    fn test_system_checker_interfaces_only_report_requested_binds() {
+        // A listener on 127.0.0.1 conflicts with that interface but not with
+        // another loopback address
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = Port::try_from(listener.local_addr().unwrap().port()).unwrap();
+        let checker = SystemOccupancyChecker;
+        let on = |ip: &str| OccupancyCheckConfig {
+            interfaces: vec![ip.parse().unwrap()],
+            ..Default::default()
+        };
+
+        assert!(checker.is_occupied(port, &on("127.0.0.1")).unwrap());
+        assert!(!checker.is_occupied(port, &on("127.0.0.2")).unwrap());
+    }
+
+    #[test]
+    fn test_system_checker_all_interfaces_overrides_interface_list() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = Port::try_from(listener.local_addr().unwrap().port()).unwrap();
+        let config = OccupancyCheckConfig {
+            interfaces: vec!["127.0.0.2".parse().unwrap()],
+            check_all_interfaces: true,
+            ..Default::default()
+        };
+
+        assert!(SystemOccupancyChecker.is_occupied(port, &config).unwrap());
+    }
+
+    #[test]
+    fn test_occupancy_check_config_from_interfaces() {
+        let occ_config = OccupancyConfig {
+            interfaces: Some(vec!["127.0.0.1".parse().unwrap(), "::1".parse().unwrap()]),
+            ..Default::default()
+        };
+
+        let config = OccupancyCheckConfig::from(&occ_config);
+        assert_eq!(config.interfaces.len(), 2);
+        assert!(config.interfaces[1].is_ipv6());
+    }
+
+    /// Counts the probes that reach the wrapped checker.
+    struct CountingChecker {
+        inner: MockOccupancyChecker,
+        probes: std::sync::atomic::AtomicUsize,
+    }
+
+    impl PortOccupancyChecker for CountingChecker {
+        fn is_occupied(&self, port: Port, config: &OccupancyCheckConfig) -> Result<bool> {
+            self.probes
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.is_occupied(port, config)
+        }
+    }
+
+    fn counting_cache(
+        ttl: Duration,
+    ) -> (
+        OccupancyCache<CountingChecker>,
+        std::sync::Arc<Mutex<Instant>>,
+    ) {
+        let now = std::sync::Arc::new(Mutex::new(Instant::now()));
+        let clock = std::sync::Arc::clone(&now);
+        let mut inner = MockOccupancyChecker::empty();
+        inner.mark_occupied(Port::try_from(8080).unwrap());
+        let checker = CountingChecker {
+            inner,
+            probes: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let cache = OccupancyCache::new(checker, ttl).with_clock(move || *clock.lock().unwrap());
+        (cache, now)
+    }
+
+    fn probes(cache: &OccupancyCache<CountingChecker>) -> usize {
+        cache.inner.probes.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_occupancy_cache_hits_within_ttl_and_reprobes_after() {
+        let (cache, now) = counting_cache(Duration::from_secs(10));
+        let config = OccupancyCheckConfig::default();
+        let occupied = Port::try_from(8080).unwrap();
+        let free = Port::try_from(8081).unwrap();
+
+        assert!(cache.is_occupied(occupied, &config).unwrap());
+        assert!(!cache.is_occupied(free, &config).unwrap());
+        assert_eq!(probes(&cache), 2);
+
+        *now.lock().unwrap() += Duration::from_secs(9);
+        assert!(cache.is_occupied(occupied, &config).unwrap());
+        assert!(!cache.is_occupied(free, &config).unwrap());
+        assert_eq!(probes(&cache), 2);
+
+        *now.lock().unwrap() += Duration::from_secs(1);
+        assert!(cache.is_occupied(occupied, &config).unwrap());
+        assert_eq!(probes(&cache), 3);
+    }
+
+    #[test]
+    fn test_occupancy_cache_forget_and_zero_ttl() {
+        let (cache, _now) = counting_cache(Duration::from_secs(10));
+        let config = OccupancyCheckConfig::default();
+        let port = Port::try_from(8081).unwrap();
+
+        cache.is_occupied(port, &config).unwrap();
+        cache.forget(port);
+        cache.is_occupied(port, &config).unwrap();
+        assert_eq!(probes(&cache), 2);
+
+        let (cache, _now) = counting_cache(Duration::ZERO);
+        cache.is_occupied(port, &config).unwrap();
+        cache.is_occupied(port, &config).unwrap();
+        assert_eq!(probes(&cache), 2);
+    }
 }
@@ -0,0 +1,242 @@
+//! Best-effort lookup of the process holding a port.
+//!
+//! Occupancy checks only tell us *whether* a port is bound. Finding out
+//! *who* bound it needs help from the operating system's own tools, so this
+//! module shells out to `lsof` (falling back to `ss` on Linux) on Unix and
+//! to `netstat`/`tasklist` on Windows. Every failure - a missing tool, a
+//! permission problem, unparseable output - degrades to "unknown" rather
+//! than an error, because callers use this to annotate reports, not to make
+//! decisions.
+
+use std::process::{Command, Stdio};
+
+use crate::Port;
+
+/// The process found listening on a port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortOwner {
+    /// Process ID of the owner.
+    pub pid: u32,
+    /// Executable or command name, when the tool reports one.
+    pub process_name: Option<String>,
+}
+
+/// Looks up the process holding `port`.
+///
+/// Returns `None` when no owner can be determined, including when the
+/// required system tools are not installed or the owner belongs to another
+/// user and the tools are not permitted to see it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use trop::port::owner::find_port_owner;
+/// use trop::Port;
+///
+/// if let Some(owner) = find_port_owner(Port::try_from(8080).unwrap()) {
+///     println!("held by pid {}", owner.pid);
+/// }
+/// ```
+#[must_use]
+pub fn find_port_owner(port: Port) -> Option<PortOwner> {
+    let port = port.value();
+
+    #[cfg(unix)]
+    {
+        lookup_with_lsof(port).or_else(|| lookup_with_ss(port))
+    }
+
+    #[cfg(windows)]
+    {
+        lookup_with_netstat(port)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = port;
+        None
+    }
+}
+
+/// Runs a command and returns its stdout, ignoring the exit status.
+///
+/// `lsof` exits non-zero when nothing matches, so the status carries no
+/// useful signal here; empty output is handled by the parsers.
+fn run_tool(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(unix)]
+fn lookup_with_lsof(port: u16) -> Option<PortOwner> {
+    let tcp = format!("-iTCP:{port}");
+    let udp = format!("-iUDP:{port}");
+    run_tool("lsof", &["-nP", &tcp, "-sTCP:LISTEN", "-Fpc"])
+        .and_then(|output| parse_lsof(&output))
+        .or_else(|| run_tool("lsof", &["-nP", &udp, "-Fpc"]).and_then(|o| parse_lsof(&o)))
+}
+
+#[cfg(unix)]
+fn lookup_with_ss(port: u16) -> Option<PortOwner> {
+    let filter = format!(":{port}");
+    let output = run_tool("ss", &["-lntup", "sport", "=", &filter])?;
+    parse_ss(&output)
+}
+
+#[cfg(windows)]
+fn lookup_with_netstat(port: u16) -> Option<PortOwner> {
+    let output = run_tool("netstat", &["-ano"])?;
+    let pid = parse_netstat(&output, port)?;
+    let filter = format!("PID eq {pid}");
+    let process_name = run_tool("tasklist", &["/FI", &filter, "/FO", "CSV", "/NH"])
+        .and_then(|output| parse_tasklist(&output));
+    Some(PortOwner { pid, process_name })
+}
+
+/// Parses `lsof -F pc` field output, taking the first process listed.
+///
+/// Each process is reported as a `p<pid>` line followed by a `c<command>`
+/// line.
+#[cfg(any(unix, test))]
+fn parse_lsof(output: &str) -> Option<PortOwner> {
+    let mut owner: Option<PortOwner> = None;
+    for line in output.lines() {
+        if let Some(pid) = line.strip_prefix('p') {
+            if owner.is_some() {
+                break;
+            }
+            owner = Some(PortOwner {
+                pid: pid.trim().parse().ok()?,
+                process_name: None,
+            });
+        } else if let Some(command) = line.strip_prefix('c') {
+            if let Some(owner) = owner.as_mut() {
+                owner.process_name = Some(command.trim().to_string());
+            }
+        }
+    }
+    owner
+}
+
+/// Parses `ss -p` output, taking the first `users:(("name",pid=N,...))`
+/// entry.
+#[cfg(any(unix, test))]
+fn parse_ss(output: &str) -> Option<PortOwner> {
+    output.lines().find_map(|line| {
+        let users = &line[line.find("users:((")? + "users:((".len()..];
+        let process_name = users
+            .strip_prefix('"')
+            .and_then(|rest| rest.split_once('"'))
+            .map(|(name, _)| name.to_string());
+        let pid = &users[users.find("pid=")? + "pid=".len()..];
+        let end = pid.find(|c: char| !c.is_ascii_digit()).unwrap_or(pid.len());
+        Some(PortOwner {
+            pid: pid[..end].parse().ok()?,
+            process_name,
+        })
+    })
+}
+
+/// Parses `netstat -ano` output for the PID bound to `port`.
+///
+/// TCP rows must be in the `LISTENING` state; UDP rows have no state
+/// column and are accepted as-is.
+#[cfg(any(windows, test))]
+fn parse_netstat(output: &str, port: u16) -> Option<u32> {
+    let suffix = format!(":{port}");
+    output.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let listening = match fields.as_slice() {
+            [proto, _, _, state, _] => {
+                proto.eq_ignore_ascii_case("TCP") && state.eq_ignore_ascii_case("LISTENING")
+            }
+            [proto, _, _, _] => proto.eq_ignore_ascii_case("UDP"),
+            _ => false,
+        };
+        if !listening || !fields[1].ends_with(&suffix) {
+            return None;
+        }
+        fields.last()?.parse().ok()
+    })
+}
+
+/// Parses the image name from `tasklist /FO CSV /NH` output.
+#[cfg(any(windows, test))]
+fn parse_tasklist(output: &str) -> Option<String> {
+    let line = output.lines().next()?.trim();
+    let name = line.strip_prefix('"')?.split_once('"')?.0;
+    Some(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lsof_takes_first_process() {
+        let output = "p4242\ncnode\nf23\np5151\ncother\n";
+        assert_eq!(
+            parse_lsof(output),
+            Some(PortOwner {
+                pid: 4242,
+                process_name: Some("node".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_lsof_empty_or_garbage() {
+        assert_eq!(parse_lsof(""), None);
+        assert_eq!(parse_lsof("pnot-a-pid\n"), None);
+    }
+
+    #[test]
+    fn test_parse_ss_users_column() {
+        let output = "\
+Netid State  Recv-Q Send-Q Local Address:Port Peer Address:Port Process
+tcp   LISTEN 0      128    0.0.0.0:8080       0.0.0.0:*         users:((\"python3\",pid=31337,fd=3))
+";
+        assert_eq!(
+            parse_ss(output),
+            Some(PortOwner {
+                pid: 31337,
+                process_name: Some("python3".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_ss_without_process_info() {
+        // Sockets owned by other users are listed without a users column
+        let output = "tcp   LISTEN 0      128    0.0.0.0:8080       0.0.0.0:*\n";
+        assert_eq!(parse_ss(output), None);
+    }
+
+    #[test]
+    fn test_parse_netstat_listening_rows() {
+        let output = "
+Active Connections
+
+  Proto  Local Address          Foreign Address        State           PID
+  TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1012
+  TCP    127.0.0.1:8080         127.0.0.1:50000        ESTABLISHED     2000
+  TCP    127.0.0.1:8080         0.0.0.0:0              LISTENING       2024
+  UDP    0.0.0.0:5353           *:*                                    3030
+";
+        assert_eq!(parse_netstat(output, 8080), Some(2024));
+        assert_eq!(parse_netstat(output, 5353), Some(3030));
+        assert_eq!(parse_netstat(output, 80), None);
+    }
+
+    #[test]
+    fn test_parse_tasklist_csv() {
+        let output = "\"node.exe\",\"2024\",\"Console\",\"1\",\"50,000 K\"\r\n";
+        assert_eq!(parse_tasklist(output), Some("node.exe".to_string()));
+        assert_eq!(parse_tasklist("INFO: No tasks are running"), None);
+    }
+}
@@ -4,6 +4,7 @@
 //! reservation keys, metadata, and builder patterns for construction.
 
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
@@ -11,11 +12,22 @@ use serde::{Deserialize, Serialize};
 use crate::path::PathResolver;
 use crate::Port;
 
+/// Maximum length of a reservation note, in characters.
+pub const MAX_NOTE_LENGTH: usize = 1024;
+
 /// A unique identifier for a port reservation.
 ///
 /// Reservations are identified by a filesystem path and an optional tag.
 /// The tag allows multiple ports to be reserved for the same path.
 ///
+/// # String form
+///
+/// A key displays as its path, followed by `#` and the tag if there is one
+/// (`/path/to/project#web`). Any `%` or `#` inside the path or tag is
+/// written as `%25` or `%23`, so the only bare `#` is the separator and
+/// [`str::parse`] recovers the same key. Round-tripping requires the path to
+/// be valid UTF-8.
+///
 /// # Examples
 ///
 /// ```
@@ -33,7 +45,12 @@ use crate::Port;
 ///     Some("web".to_string())
 /// ).unwrap();
 /// let display = format!("{key}").replace(std::path::MAIN_SEPARATOR, "/");
-/// assert_eq!(display, "/path/to/project:web");
+/// assert_eq!(display, "/path/to/project#web");
+///
+/// // The string form parses back to the same key, even with a `#` in the tag
+/// let key = ReservationKey::new(PathBuf::from("/srv/app"), Some("db#2".to_string())).unwrap();
+/// assert_eq!(key.to_string(), "/srv/app#db%232");
+/// assert_eq!(key.to_string().parse::<ReservationKey>().unwrap(), key);
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ReservationKey {
@@ -167,15 +184,60 @@ impl ReservationKey {
     }
 }
 
+/// Separates the path from the tag in a key's string form.
+const KEY_TAG_SEPARATOR: char = '#';
+
+/// Escapes `%` and the tag separator so neither can be mistaken for syntax.
+fn escape_key_part(part: &str) -> String {
+    part.replace('%', "%25").replace(KEY_TAG_SEPARATOR, "%23")
+}
+
+/// Reverses [`escape_key_part`], rejecting any other `%` sequence.
+fn unescape_key_part(part: &str) -> Result<String, ValidationError> {
+    let mut unescaped = String::with_capacity(part.len());
+    let mut rest = part;
+    while let Some(at) = rest.find('%') {
+        unescaped.push_str(&rest[..at]);
+        let escape = rest.get(at..at + 3);
+        unescaped.push(match escape {
+            Some("%25") => '%',
+            Some("%23") => KEY_TAG_SEPARATOR,
+            _ => {
+                return Err(ValidationError {
+                    field: "key".into(),
+                    message: format!("invalid escape in '{part}' (only %25 and %23 are allowed)"),
+                })
+            }
+        });
+        rest = &rest[at + 3..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
 impl std::fmt::Display for ReservationKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = escape_key_part(&self.path.to_string_lossy());
         match &self.tag {
-            Some(tag) => write!(f, "{}:{}", self.path.display(), tag),
-            None => write!(f, "{}", self.path.display()),
+            Some(tag) => write!(f, "{path}{KEY_TAG_SEPARATOR}{}", escape_key_part(tag)),
+            None => write!(f, "{path}"),
         }
     }
 }
 
+impl FromStr for ReservationKey {
+    type Err = ValidationError;
+
+    /// Parses the string form written by `Display` (`path` or `path#tag`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, tag) = match s.split_once(KEY_TAG_SEPARATOR) {
+            Some((path, tag)) => (path, Some(unescape_key_part(tag)?)),
+            None => (s, None),
+        };
+        Self::new(PathBuf::from(unescape_key_part(path)?), tag)
+    }
+}
+
 /// A port reservation with metadata.
 ///
 /// Reservations track which ports are allocated to which paths, along with
@@ -204,8 +266,16 @@ pub struct Reservation {
     project: Option<String>,
     task: Option<String>,
     sticky: bool,
+    #[serde(default)]
+    pinned: bool,
     created_at: SystemTime,
     last_used_at: SystemTime,
+    #[serde(default)]
+    expires_at: Option<SystemTime>,
+    #[serde(default)]
+    note: Option<String>,
+    #[serde(default)]
+    priority: i32,
 }
 
 impl Reservation {
@@ -229,8 +299,12 @@ impl Reservation {
             project: None,
             task: None,
             sticky: false,
+            pinned: false,
             created_at: None,
             last_used_at: None,
+            expires_at: None,
+            note: None,
+            priority: 0,
         }
     }
 
@@ -264,6 +338,40 @@ impl Reservation {
         self.sticky
     }
 
+    /// Returns whether this reservation carries sticky fields.
+    ///
+    /// A reservation is sticky when it has a project or task, since those
+    /// fields tie it to a piece of work; operations that would move or
+    /// release it in bulk need `force`. The `sticky` flag is not stored in
+    /// the database, so the fields are what count for stored reservations.
+    #[must_use]
+    pub const fn is_sticky(&self) -> bool {
+        self.sticky || self.project.is_some() || self.task.is_some()
+    }
+
+    /// Returns whether this reservation is pinned.
+    ///
+    /// A pinned reservation's port is never moved or released until it is
+    /// explicitly unpinned, even by forced operations.
+    #[must_use]
+    pub const fn pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// Returns an error if this reservation is pinned.
+    ///
+    /// Operations that would move or remove a reservation call this first,
+    /// regardless of `--force`.
+    pub(crate) fn ensure_unpinned(&self) -> crate::error::Result<()> {
+        if self.pinned {
+            return Err(crate::error::Error::ReservationPinned {
+                key: self.key.to_string(),
+                port: self.port,
+            });
+        }
+        Ok(())
+    }
+
     /// Returns the creation timestamp.
     #[must_use]
     pub const fn created_at(&self) -> SystemTime {
@@ -276,6 +384,55 @@ impl Reservation {
         self.last_used_at
     }
 
+    /// Returns the time after which `expire` removes this reservation, if it
+    /// has a TTL.
+    #[must_use]
+    pub const fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Returns the free-text note explaining what the reservation is for.
+    #[must_use]
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Returns the cleanup priority.
+    ///
+    /// Reservations with a priority above `cleanup.protect_priority` are
+    /// skipped by `expire` and `autoclean` unless forced. The default is 0.
+    #[must_use]
+    pub const fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Checks if the reservation's TTL has run out as of `now`.
+    ///
+    /// Reservations without a TTL never run out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use std::time::{Duration, SystemTime};
+    /// use trop::{Reservation, ReservationKey, Port};
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+    /// let port = Port::try_from(8080).unwrap();
+    /// let now = SystemTime::now();
+    /// let reservation = Reservation::builder(key, port)
+    ///     .expires_at(Some(now + Duration::from_secs(60)))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(!reservation.is_past_ttl(now));
+    /// assert!(reservation.is_past_ttl(now + Duration::from_secs(120)));
+    /// ```
+    #[must_use]
+    pub fn is_past_ttl(&self, now: SystemTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
     /// Checks if the reservation has expired based on the given maximum age.
     ///
     /// A reservation is considered expired if it hasn't been used for longer
@@ -297,9 +454,62 @@ impl Reservation {
     /// ```
     #[must_use]
     pub fn is_expired(&self, max_age: Duration) -> bool {
-        SystemTime::now()
-            .duration_since(self.last_used_at)
-            .is_ok_and(|age| age > max_age)
+        self.is_stale(SystemTime::now(), max_age)
+    }
+
+    /// Returns how long the reservation has gone unused as of `now`.
+    ///
+    /// If `last_used_at` lies after `now` (for example because of clock
+    /// skew), the age is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use std::time::{Duration, SystemTime};
+    /// use trop::{Reservation, ReservationKey, Port};
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+    /// let port = Port::try_from(8080).unwrap();
+    /// let now = SystemTime::now();
+    /// let reservation = Reservation::builder(key, port)
+    ///     .last_used_at(now - Duration::from_secs(90))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(reservation.age(now), Duration::from_secs(90));
+    /// assert_eq!(reservation.age(now - Duration::from_secs(120)), Duration::ZERO);
+    /// ```
+    #[must_use]
+    pub fn age(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.last_used_at)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Checks if the reservation has gone unused for longer than `max_age`
+    /// as of `now`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use std::time::{Duration, SystemTime};
+    /// use trop::{Reservation, ReservationKey, Port};
+    ///
+    /// let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+    /// let port = Port::try_from(8080).unwrap();
+    /// let now = SystemTime::now();
+    /// let reservation = Reservation::builder(key, port)
+    ///     .last_used_at(now - Duration::from_secs(90))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(reservation.is_stale(now, Duration::from_secs(60)));
+    /// assert!(!reservation.is_stale(now, Duration::from_secs(120)));
+    /// ```
+    #[must_use]
+    pub fn is_stale(&self, now: SystemTime, max_age: Duration) -> bool {
+        self.age(now) > max_age
     }
 }
 
@@ -314,8 +524,12 @@ pub struct ReservationBuilder {
     project: Option<String>,
     task: Option<String>,
     sticky: bool,
+    pinned: bool,
     created_at: Option<SystemTime>,
     last_used_at: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
+    note: Option<String>,
+    priority: i32,
 }
 
 impl ReservationBuilder {
@@ -344,6 +558,13 @@ impl ReservationBuilder {
         self
     }
 
+    /// Sets whether the reservation is pinned.
+    #[must_use]
+    pub const fn pinned(mut self, pinned: bool) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
     /// Sets the creation timestamp.
     #[must_use]
     pub fn created_at(mut self, created_at: SystemTime) -> Self {
@@ -358,6 +579,30 @@ impl ReservationBuilder {
         self
     }
 
+    /// Sets the time after which the reservation expires (`None` for no TTL).
+    #[must_use]
+    pub const fn expires_at(mut self, expires_at: Option<SystemTime>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    /// Sets the free-text note.
+    ///
+    /// The note is stored exactly as given; an empty note is treated as no
+    /// note at all.
+    #[must_use]
+    pub fn note(mut self, note: Option<String>) -> Self {
+        self.note = note.filter(|n| !n.is_empty());
+        self
+    }
+
+    /// Sets the cleanup priority (higher resists cleanup).
+    #[must_use]
+    pub const fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Builds the reservation.
     ///
     /// # Errors
@@ -365,6 +610,7 @@ impl ReservationBuilder {
     /// Returns an error if:
     /// - The project is provided but is empty after trimming
     /// - The task is provided but is empty after trimming
+    /// - The note is longer than [`MAX_NOTE_LENGTH`] characters
     ///
     /// # Examples
     ///
@@ -408,6 +654,16 @@ impl ReservationBuilder {
             }
         }
 
+        // Validate note
+        if let Some(ref note) = self.note {
+            if note.chars().count() > MAX_NOTE_LENGTH {
+                return Err(ValidationError {
+                    field: "note".into(),
+                    message: format!("note must be at most {MAX_NOTE_LENGTH} characters"),
+                });
+            }
+        }
+
         let now = SystemTime::now();
         Ok(Reservation {
             key: self.key,
@@ -415,8 +671,12 @@ impl ReservationBuilder {
             project: self.project,
             task: self.task,
             sticky: self.sticky,
+            pinned: self.pinned,
             created_at: self.created_at.unwrap_or(now),
             last_used_at: self.last_used_at.unwrap_or(now),
+            expires_at: self.expires_at,
+            note: self.note,
+            priority: self.priority,
         })
     }
 }
@@ -459,7 +719,33 @@ mod tests {
             .unwrap();
         assert_eq!(key.path, PathBuf::from("/path/to/project"));
         assert_eq!(key.tag, Some("web".to_string()));
-        assert_eq!(format!("{key}"), "/path/to/project:web");
+        assert_eq!(format!("{key}"), "/path/to/project#web");
+    }
+
+    #[test]
+    fn test_reservation_key_string_form_escapes() {
+        let key = ReservationKey::new(PathBuf::from("/srv/100%/a#b"), Some("db#%23".to_string()))
+            .unwrap();
+        assert_eq!(key.to_string(), "/srv/100%25/a%23b#db%23%2523");
+        assert_eq!(key.to_string().parse::<ReservationKey>().unwrap(), key);
+
+        let untagged = ReservationKey::new(PathBuf::from("/srv/a#b"), None).unwrap();
+        assert_eq!(
+            untagged.to_string().parse::<ReservationKey>().unwrap(),
+            untagged
+        );
+    }
+
+    #[test]
+    fn test_reservation_key_parse_rejects_bad_input() {
+        // Stray or unknown escapes
+        assert!("/srv/100%".parse::<ReservationKey>().is_err());
+        assert!("/srv/a%41".parse::<ReservationKey>().is_err());
+        // A separator must be followed by a non-empty tag
+        assert!("/srv/app#".parse::<ReservationKey>().is_err());
+        // Everything after the first bare separator is the tag
+        let key: ReservationKey = "/srv/app#a#b".parse().unwrap();
+        assert_eq!(key.tag.as_deref(), Some("a#b"));
     }
 
     #[test]
@@ -552,6 +838,21 @@ mod tests {
         assert_eq!(reservation.task(), Some("my-task"));
     }
 
+    #[test]
+    fn test_reservation_builder_pinned() {
+        let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        let reservation = Reservation::builder(key.clone(), port).build().unwrap();
+        assert!(!reservation.pinned());
+
+        let reservation = Reservation::builder(key, port)
+            .pinned(true)
+            .build()
+            .unwrap();
+        assert!(reservation.pinned());
+    }
+
     #[test]
     fn test_reservation_builder_sticky() {
         let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
@@ -564,6 +865,27 @@ mod tests {
         assert!(reservation.sticky());
     }
 
+    #[test]
+    fn test_reservation_is_sticky_with_project_or_task() {
+        let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        let plain = Reservation::builder(key.clone(), port).build().unwrap();
+        assert!(!plain.is_sticky());
+
+        let with_project = Reservation::builder(key.clone(), port)
+            .project(Some("app".to_string()))
+            .build()
+            .unwrap();
+        assert!(with_project.is_sticky());
+
+        let with_task = Reservation::builder(key, port)
+            .task(Some("feature".to_string()))
+            .build()
+            .unwrap();
+        assert!(with_task.is_sticky());
+    }
+
     #[test]
     fn test_reservation_builder_empty_project() {
         let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
@@ -622,6 +944,39 @@ mod tests {
         assert!(!reservation.is_expired(Duration::from_secs(150)));
     }
 
+    #[test]
+    fn test_reservation_age_and_staleness() {
+        let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+        let now = SystemTime::now();
+        let reservation = Reservation::builder(key, port)
+            .last_used_at(now - Duration::from_secs(100))
+            .build()
+            .unwrap();
+
+        assert_eq!(reservation.age(now), Duration::from_secs(100));
+        assert!(reservation.is_stale(now, Duration::from_secs(99)));
+        // Staleness requires strictly exceeding the maximum age
+        assert!(!reservation.is_stale(now, Duration::from_secs(100)));
+        assert!(!reservation.is_stale(now, Duration::from_secs(150)));
+    }
+
+    #[test]
+    fn test_reservation_age_with_clock_skew() {
+        let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+        let now = SystemTime::now();
+        let reservation = Reservation::builder(key, port)
+            .last_used_at(now + Duration::from_secs(3600))
+            .build()
+            .unwrap();
+
+        // A last-used time in the future counts as freshly used
+        assert_eq!(reservation.age(now), Duration::ZERO);
+        assert!(!reservation.is_stale(now, Duration::ZERO));
+        assert!(!reservation.is_expired(Duration::ZERO));
+    }
+
     #[test]
     fn test_reservation_timestamps() {
         let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
@@ -652,6 +1007,60 @@ mod tests {
         assert_eq!(deserialized, reservation);
     }
 
+    #[test]
+    fn test_reservation_builder_note() {
+        let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        // Notes are kept verbatim, unicode and surrounding whitespace included
+        let note = "  demo for the 東京 team 🚀 ".to_string();
+        let reservation = Reservation::builder(key.clone(), port)
+            .note(Some(note.clone()))
+            .build()
+            .unwrap();
+        assert_eq!(reservation.note(), Some(note.as_str()));
+
+        let reservation = Reservation::builder(key.clone(), port)
+            .note(Some(String::new()))
+            .build()
+            .unwrap();
+        assert_eq!(reservation.note(), None);
+
+        // The limit counts characters, not bytes
+        let at_limit = "é".repeat(MAX_NOTE_LENGTH);
+        assert!(Reservation::builder(key.clone(), port)
+            .note(Some(at_limit))
+            .build()
+            .is_ok());
+
+        let err = Reservation::builder(key, port)
+            .note(Some("x".repeat(MAX_NOTE_LENGTH + 1)))
+            .build()
+            .unwrap_err();
+        assert_eq!(err.field, "note");
+    }
+
+    #[test]
+    fn test_reservation_builder_priority() {
+        let key = ReservationKey::new(PathBuf::from("/path"), None).unwrap();
+        let port = Port::try_from(8080).unwrap();
+
+        let reservation = Reservation::builder(key.clone(), port).build().unwrap();
+        assert_eq!(reservation.priority(), 0);
+
+        let reservation = Reservation::builder(key, port)
+            .priority(-3)
+            .build()
+            .unwrap();
+        assert_eq!(reservation.priority(), -3);
+
+        // Older serialized reservations without a priority get the default
+        let mut json = serde_json::to_value(&reservation).unwrap();
+        json.as_object_mut().unwrap().remove("priority");
+        let deserialized: Reservation = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.priority(), 0);
+    }
+
     #[test]
     fn test_validation_error_display() {
         let err = ValidationError {
@@ -28,6 +28,22 @@ fn optional_tag_strategy() -> impl Strategy<Value = Option<String>> {
     prop::option::of("[a-z]{1,15}")
 }
 
+// Strategy for paths containing the key string form's special characters
+fn special_path_strategy() -> impl Strategy<Value = PathBuf> {
+    prop::collection::vec("[a-z#%:. ]{1,10}", 1..5).prop_map(|parts| {
+        let mut path = PathBuf::from("/tmp");
+        for part in parts {
+            path.push(part);
+        }
+        path
+    })
+}
+
+// Strategy for already-trimmed tags containing the special characters
+fn special_tag_strategy() -> impl Strategy<Value = Option<String>> {
+    prop::option::of("[a-z#%:]([a-z#%: ]{0,13}[a-z#%:])?")
+}
+
 proptest! {
     #![proptest_config(ProptestConfig {
         cases: 10000,
@@ -93,11 +109,27 @@ proptest! {
 
         if let Some(tag_str) = tag {
             prop_assert!(display.contains(&tag_str));
-            prop_assert!(display.contains(':'));
+            prop_assert!(display.contains('#'));
         }
         prop_assert!(display.contains(&path.to_string_lossy().to_string()));
     }
 
+    // The string form parses back to an equal key
+    #[test]
+    fn reservation_key_string_round_trip(path in special_path_strategy(), tag in special_tag_strategy()) {
+        let key = ReservationKey::new(path, tag).unwrap();
+        let parsed: ReservationKey = key.to_string().parse().unwrap();
+        prop_assert_eq!(parsed, key);
+    }
+
+    // The only bare separator in the string form is the one before the tag
+    #[test]
+    fn reservation_key_string_has_single_separator(path in special_path_strategy(), tag in special_tag_strategy()) {
+        let key = ReservationKey::new(path, tag.clone()).unwrap();
+        let separators = key.to_string().matches('#').count();
+        prop_assert_eq!(separators, usize::from(tag.is_some()));
+    }
+
     // Reservations preserve their key and port
     #[test]
     fn reservation_preserves_key_and_port(
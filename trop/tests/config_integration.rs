@@ -26,7 +26,7 @@ use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 use trop::config::{
     CleanupConfig, Config, ConfigBuilder, OutputFormat, PortConfig, PortExclusion,
-    ReservationGroup, ServiceDefinition,
+    ReservationGroup, ReservationGroups, ServiceDefinition,
 };
 use trop::error::Error;
 
@@ -883,6 +883,7 @@ fn test_validation_cleanup_expire_days_zero() {
     let config = Config {
         cleanup: Some(CleanupConfig {
             expire_after_days: Some(0), // Invalid
+            protect_priority: None,
         }),
         ..Default::default()
     };
@@ -940,10 +941,10 @@ fn test_validation_reservation_duplicate_offsets() {
     );
 
     let config = Config {
-        reservations: Some(ReservationGroup {
+        reservations: Some(ReservationGroups::Single(ReservationGroup {
             base: Some(5000),
             services,
-        }),
+        })),
         ..Default::default()
     };
 
@@ -980,10 +981,10 @@ fn test_validation_reservation_duplicate_preferred() {
     );
 
     let config = Config {
-        reservations: Some(ReservationGroup {
+        reservations: Some(ReservationGroups::Single(ReservationGroup {
             base: Some(5000),
             services,
-        }),
+        })),
         ..Default::default()
     };
 
@@ -1017,10 +1018,10 @@ fn test_validation_reservation_env_var_names() {
         );
 
         let config = Config {
-            reservations: Some(ReservationGroup {
+            reservations: Some(ReservationGroups::Single(ReservationGroup {
                 base: Some(5000),
                 services,
-            }),
+            })),
             ..Default::default()
         };
 
@@ -1046,10 +1047,10 @@ fn test_validation_reservation_env_var_names() {
         );
 
         let config = Config {
-            reservations: Some(ReservationGroup {
+            reservations: Some(ReservationGroups::Single(ReservationGroup {
                 base: Some(5000),
                 services,
-            }),
+            })),
             ..Default::default()
         };
 
@@ -1083,10 +1084,10 @@ fn test_validation_reservation_env_var_names() {
         );
 
         let config = Config {
-            reservations: Some(ReservationGroup {
+            reservations: Some(ReservationGroups::Single(ReservationGroup {
                 base: Some(5000),
                 services,
-            }),
+            })),
             ..Default::default()
         };
 
@@ -1294,7 +1295,7 @@ fn test_reservation_groups_complete_parsing() {
         .build()
         .unwrap();
 
-    let reservations = config.reservations.as_ref().unwrap();
+    let reservations = config.reservations.as_ref().unwrap().select(None).unwrap();
     assert_eq!(reservations.base, Some(5050));
     assert_eq!(reservations.services.len(), 4);
 
@@ -1349,7 +1350,13 @@ reservations:
         .build()
         .unwrap();
 
-    let services = &config.reservations.as_ref().unwrap().services;
+    let services = &config
+        .reservations
+        .as_ref()
+        .unwrap()
+        .select(None)
+        .unwrap()
+        .services;
     assert_eq!(services.len(), 4);
 
     let just_offset = services.get("just_offset").unwrap();
@@ -1396,7 +1403,13 @@ reservations:
         .build();
 
     let config = result.unwrap();
-    let services = &config.reservations.as_ref().unwrap().services;
+    let services = &config
+        .reservations
+        .as_ref()
+        .unwrap()
+        .select(None)
+        .unwrap()
+        .services;
     assert_eq!(services.get("web").unwrap().offset, None);
     assert_eq!(services.get("api").unwrap().offset, None);
 }
@@ -261,6 +261,7 @@ fn test_cleanup_with_expiration() {
     // Configure cleanup to expire after 7 days
     let cleanup_config = CleanupConfig {
         expire_after_days: Some(7),
+        protect_priority: None,
     };
 
     let result = CleanupOperations::expire(&mut db, &cleanup_config, false).unwrap();
@@ -491,6 +492,7 @@ fn test_autoclean_integration() {
 
     let cleanup_config = CleanupConfig {
         expire_after_days: Some(7),
+        protect_priority: None,
     };
 
     let result = CleanupOperations::autoclean(&mut db, &cleanup_config, false).unwrap();